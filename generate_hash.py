@@ -1,3 +0,0 @@
-import argon2
-ph = argon2.PasswordHasher()
-print(ph.hash("admin123"))
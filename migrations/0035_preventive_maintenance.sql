@@ -0,0 +1,17 @@
+-- Migration: 0035_preventive_maintenance
+-- Description: Link work orders to preventive schedules for automatic PM generation
+-- Created: 2026-10-18
+
+ALTER TABLE maintenance_work_orders
+    ADD COLUMN IF NOT EXISTS preventive_schedule_id UUID REFERENCES preventive_schedules(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_work_orders_preventive_schedule
+    ON maintenance_work_orders(preventive_schedule_id);
+
+-- Super Admin gets preventive schedule permissions
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r, permissions p
+WHERE r.code = 'super_admin' AND p.code LIKE 'preventive_schedule.%'
+ON CONFLICT DO NOTHING;
+
+COMMENT ON COLUMN maintenance_work_orders.preventive_schedule_id IS 'Preventive schedule that generated this work order';
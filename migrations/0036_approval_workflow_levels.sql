@@ -0,0 +1,94 @@
+-- Migration: 0036_approval_workflow_levels
+-- Description: Configurable N-level approval workflows bound to RBAC roles
+-- Created: 2026-10-18
+
+-- 1. Workflows are keyed on resource/action; asset_type becomes an optional category qualifier
+ALTER TABLE approval_workflows
+    ADD COLUMN IF NOT EXISTS resource_type VARCHAR(50) NOT NULL DEFAULT 'asset',
+    ADD COLUMN IF NOT EXISTS name VARCHAR(100),
+    ADD COLUMN IF NOT EXISTS description TEXT,
+    ADD COLUMN IF NOT EXISTS is_active BOOLEAN DEFAULT TRUE,
+    ALTER COLUMN asset_type DROP NOT NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_approval_workflows_key
+    ON approval_workflows (LOWER(resource_type), LOWER(action_type), COALESCE(UPPER(asset_type), ''));
+
+-- 2. Levels: rows sharing a level_number form a parallel step (every role must sign)
+CREATE TABLE IF NOT EXISTS approval_workflow_levels (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    workflow_id UUID NOT NULL REFERENCES approval_workflows(id) ON DELETE CASCADE,
+    level_number INTEGER NOT NULL CHECK (level_number > 0),
+    role_id UUID NOT NULL REFERENCES roles(id),
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (workflow_id, level_number, role_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_approval_workflow_levels_workflow ON approval_workflow_levels(workflow_id);
+
+-- Carry over the fixed L1/L2 role columns
+INSERT INTO approval_workflow_levels (workflow_id, level_number, role_id)
+SELECT id, 1, approval_level_1_role_id FROM approval_workflows WHERE approval_level_1_role_id IS NOT NULL
+ON CONFLICT DO NOTHING;
+
+INSERT INTO approval_workflow_levels (workflow_id, level_number, role_id)
+SELECT id, 2, approval_level_2_role_id FROM approval_workflows WHERE approval_level_2_role_id IS NOT NULL
+ON CONFLICT DO NOTHING;
+
+-- 3. Default workflow (matches any resource/action): Supervisor then Manager
+INSERT INTO approval_workflows (resource_type, action_type, asset_type, name, description, need_approval)
+SELECT '*', '*', NULL, 'Default', 'Fallback two-level approval (Supervisor, Manager)', TRUE
+WHERE NOT EXISTS (
+    SELECT 1 FROM approval_workflows WHERE resource_type = '*' AND action_type = '*' AND asset_type IS NULL
+);
+
+INSERT INTO approval_workflow_levels (workflow_id, level_number, role_id)
+SELECT w.id, l.level_number, r.id
+FROM approval_workflows w
+CROSS JOIN (VALUES (1, 'supervisor'), (2, 'manager')) AS l(level_number, role_code)
+JOIN roles r ON r.code = l.role_code
+WHERE w.resource_type = '*' AND w.action_type = '*' AND w.asset_type IS NULL
+ON CONFLICT DO NOTHING;
+
+-- 4. Requests remember the workflow they were opened against
+ALTER TABLE approval_requests
+    ADD COLUMN IF NOT EXISTS workflow_id UUID REFERENCES approval_workflows(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS total_levels INTEGER DEFAULT 2;
+
+UPDATE approval_requests
+SET workflow_id = (
+    SELECT id FROM approval_workflows WHERE resource_type = '*' AND action_type = '*' AND asset_type IS NULL
+)
+WHERE workflow_id IS NULL;
+
+-- Requests left half-way by the old L1 step are still pending at level 2
+UPDATE approval_requests SET status = 'PENDING' WHERE status = 'APPROVED_L1';
+UPDATE approval_requests SET status = 'APPROVED' WHERE status = 'APPROVED_L2';
+
+-- 5. Individual sign-offs per level/role
+CREATE TABLE IF NOT EXISTS approval_request_actions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    request_id UUID NOT NULL REFERENCES approval_requests(id) ON DELETE CASCADE,
+    level_number INTEGER NOT NULL,
+    role_id UUID NOT NULL REFERENCES roles(id),
+    approver_id UUID NOT NULL REFERENCES users(id),
+    action VARCHAR(20) NOT NULL CHECK (action IN ('approved', 'rejected')),
+    notes TEXT,
+    acted_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (request_id, level_number, role_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_approval_request_actions_request ON approval_request_actions(request_id);
+
+-- 6. Permissions for workflow administration
+INSERT INTO permissions (code, name, resource, action) VALUES
+('approval_workflow.read', 'View Approval Workflows', 'approval_workflow', 'read'),
+('approval_workflow.manage', 'Manage Approval Workflows', 'approval_workflow', 'manage')
+ON CONFLICT (code) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r, permissions p
+WHERE r.code = 'super_admin' AND p.code LIKE 'approval_workflow.%'
+ON CONFLICT DO NOTHING;
+
+COMMENT ON TABLE approval_workflow_levels IS 'Ordered approval steps; levels sharing a number are signed in parallel';
+COMMENT ON TABLE approval_request_actions IS 'Sign-off log for approval requests';
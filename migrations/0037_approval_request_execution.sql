@@ -0,0 +1,12 @@
+-- Migration: 0037_approval_request_execution
+-- Description: Record the outcome of applying an approved request's data_snapshot
+-- Created: 2026-10-18
+
+ALTER TABLE approval_requests
+    ADD COLUMN IF NOT EXISTS executed_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS execution_result JSONB,
+    ADD COLUMN IF NOT EXISTS execution_error TEXT;
+
+COMMENT ON COLUMN approval_requests.executed_at IS 'When the approved change was applied (or the apply attempt failed)';
+COMMENT ON COLUMN approval_requests.execution_result IS 'Resource state returned by the executor after applying the change';
+COMMENT ON COLUMN approval_requests.execution_error IS 'Reason the apply step was rejected; status is FAILED in that case';
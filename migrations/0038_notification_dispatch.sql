@@ -0,0 +1,60 @@
+-- Migration: 0038_notification_dispatch
+-- Description: Template-driven multi-channel notification delivery log
+-- Created: 2026-10-18
+
+-- 1. Webhook channel preference
+ALTER TABLE notification_preferences
+    ADD COLUMN IF NOT EXISTS webhook_enabled BOOLEAN DEFAULT true;
+
+-- 2. Delivery log: one row per message per channel, with retry state
+CREATE TABLE IF NOT EXISTS notification_deliveries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    notification_id UUID REFERENCES notifications(id) ON DELETE SET NULL,
+    template_id UUID REFERENCES notification_templates(id) ON DELETE SET NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    event_type VARCHAR(100) NOT NULL,
+    channel VARCHAR(20) NOT NULL,
+    recipient VARCHAR(255),
+
+    -- Rendered content and the variables it was rendered from
+    subject TEXT,
+    body TEXT NOT NULL,
+    payload JSONB,
+
+    entity_type VARCHAR(50),
+    entity_id UUID,
+
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'sent', 'retrying', 'failed', 'skipped')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL DEFAULT 5,
+    last_error TEXT,
+    next_attempt_at TIMESTAMPTZ,
+    sent_at TIMESTAMPTZ,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_notification_deliveries_retry
+    ON notification_deliveries(next_attempt_at) WHERE status = 'retrying';
+CREATE INDEX IF NOT EXISTS idx_notification_deliveries_user ON notification_deliveries(user_id);
+CREATE INDEX IF NOT EXISTS idx_notification_deliveries_created ON notification_deliveries(created_at DESC);
+
+CREATE TRIGGER update_notification_deliveries_updated_at
+    BEFORE UPDATE ON notification_deliveries
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+-- 3. Permissions for template administration
+INSERT INTO permissions (code, name, resource, action) VALUES
+('notification_template.read', 'View Notification Templates', 'notification_template', 'read'),
+('notification_template.manage', 'Manage Notification Templates', 'notification_template', 'manage')
+ON CONFLICT (code) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r, permissions p
+WHERE r.code = 'super_admin' AND p.code LIKE 'notification_template.%'
+ON CONFLICT DO NOTHING;
+
+COMMENT ON TABLE notification_deliveries IS 'Per-channel delivery attempts of rendered notifications';
@@ -0,0 +1,25 @@
+-- Migration: 0039_notification_digests
+-- Description: Queue deliveries for users on hourly/daily/weekly digests
+-- Created: 2026-10-18
+
+-- 1. Queued and digested delivery states
+ALTER TABLE notification_deliveries
+    DROP CONSTRAINT IF EXISTS notification_deliveries_status_check;
+
+ALTER TABLE notification_deliveries
+    ADD CONSTRAINT notification_deliveries_status_check
+    CHECK (status IN ('pending', 'sent', 'retrying', 'failed', 'skipped', 'queued', 'digested'));
+
+-- 2. Digest bookkeeping
+ALTER TABLE notification_deliveries
+    ADD COLUMN IF NOT EXISTS asset_id UUID REFERENCES assets(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS digest_frequency VARCHAR(20)
+        CHECK (digest_frequency IN ('hourly', 'daily', 'weekly')),
+    -- The digest delivery this item was included in
+    ADD COLUMN IF NOT EXISTS digest_id UUID REFERENCES notification_deliveries(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_notification_deliveries_queued
+    ON notification_deliveries(digest_frequency, user_id, channel) WHERE status = 'queued';
+CREATE INDEX IF NOT EXISTS idx_notification_deliveries_digest ON notification_deliveries(digest_id);
+
+COMMENT ON COLUMN notification_deliveries.digest_id IS 'Digest delivery that included this queued item';
@@ -0,0 +1,113 @@
+-- Migration: 0040_depreciation_engine
+-- Description: Depreciation methods per category and persisted monthly schedules
+-- Created: 2026-10-18
+
+-- 1. Category depreciation settings
+ALTER TABLE categories
+    ADD COLUMN IF NOT EXISTS declining_balance_rate DECIMAL(7, 4),  -- annual, declining balance only
+    ADD COLUMN IF NOT EXISTS estimated_total_hours DECIMAL(12, 2);  -- lifetime hour meter, units of production
+
+UPDATE categories SET depreciation_method = 'straight_line'
+WHERE depreciation_method IS NULL
+   OR depreciation_method NOT IN ('straight_line', 'declining_balance', 'double_declining_balance',
+                                  'sum_of_years_digits', 'units_of_production');
+
+ALTER TABLE categories
+    ADD CONSTRAINT categories_depreciation_method_check
+    CHECK (depreciation_method IN ('straight_line', 'declining_balance', 'double_declining_balance',
+                                   'sum_of_years_digits', 'units_of_production'));
+
+-- 2. Hour meter usage per period (units of production)
+ALTER TABLE depreciation_schedules
+    ADD COLUMN IF NOT EXISTS usage_units DECIMAL(12, 2);
+
+-- 3. Prefer the persisted schedule; fall back to straight-line for assets without one
+CREATE OR REPLACE FUNCTION calculate_depreciation(
+    p_asset_id UUID,
+    p_as_of_date DATE DEFAULT CURRENT_DATE
+) RETURNS TABLE (
+    original_cost DECIMAL,
+    accumulated_depreciation DECIMAL,
+    book_value DECIMAL,
+    monthly_depreciation DECIMAL,
+    remaining_months INTEGER
+) AS $$
+DECLARE
+    v_purchase_price DECIMAL;
+    v_purchase_date DATE;
+    v_residual_value DECIMAL;
+    v_useful_life_months INTEGER;
+    v_months_elapsed INTEGER;
+    v_monthly_dep DECIMAL;
+    v_accum_dep DECIMAL;
+BEGIN
+    -- Get asset data
+    SELECT a.purchase_price, a.purchase_date,
+           COALESCE(a.residual_value, a.purchase_price * c.residual_rate, 0),
+           COALESCE(a.useful_life_months, c.depreciation_period_months, 60)
+    INTO v_purchase_price, v_purchase_date, v_residual_value, v_useful_life_months
+    FROM assets a
+    LEFT JOIN categories c ON a.category_id = c.id
+    WHERE a.id = p_asset_id;
+
+    IF v_purchase_price IS NULL OR v_purchase_date IS NULL THEN
+        RETURN QUERY SELECT 0::DECIMAL, 0::DECIMAL, 0::DECIMAL, 0::DECIMAL, 0;
+        RETURN;
+    END IF;
+
+    IF EXISTS (SELECT 1 FROM depreciation_schedules ds WHERE ds.asset_id = p_asset_id) THEN
+        RETURN QUERY
+        SELECT
+            v_purchase_price,
+            COALESCE(cur.accumulated_depreciation, 0),
+            COALESCE(cur.closing_value, v_purchase_price),
+            COALESCE(
+                (SELECT ds.depreciation_amount FROM depreciation_schedules ds
+                 WHERE ds.asset_id = p_asset_id
+                   AND ds.period_start = date_trunc('month', p_as_of_date)::DATE),
+                0),
+            (SELECT COUNT(*)::INTEGER FROM depreciation_schedules ds
+             WHERE ds.asset_id = p_asset_id AND ds.period_start > p_as_of_date)
+        FROM (SELECT 1) one
+        LEFT JOIN LATERAL (
+            SELECT ds.accumulated_depreciation, ds.closing_value
+            FROM depreciation_schedules ds
+            WHERE ds.asset_id = p_asset_id AND ds.period_start <= p_as_of_date
+            ORDER BY ds.period_start DESC
+            LIMIT 1
+        ) cur ON true;
+        RETURN;
+    END IF;
+
+    -- Calculate months elapsed
+    v_months_elapsed := EXTRACT(YEAR FROM age(p_as_of_date, v_purchase_date)) * 12
+                      + EXTRACT(MONTH FROM age(p_as_of_date, v_purchase_date));
+
+    -- Calculate monthly depreciation (straight-line)
+    v_monthly_dep := (v_purchase_price - v_residual_value) / v_useful_life_months;
+
+    -- Calculate accumulated depreciation (capped at depreciable amount)
+    v_accum_dep := LEAST(v_monthly_dep * v_months_elapsed, v_purchase_price - v_residual_value);
+
+    RETURN QUERY SELECT
+        v_purchase_price,
+        v_accum_dep,
+        v_purchase_price - v_accum_dep,
+        v_monthly_dep,
+        GREATEST(v_useful_life_months - v_months_elapsed, 0)::INTEGER;
+END;
+$$ LANGUAGE plpgsql;
+
+-- 4. Permission to trigger recalculation
+INSERT INTO permissions (code, name, resource, action) VALUES
+('depreciation.read', 'View Depreciation Schedules', 'depreciation', 'read'),
+('depreciation.manage', 'Recalculate Depreciation', 'depreciation', 'manage')
+ON CONFLICT (code) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r, permissions p
+WHERE r.code = 'super_admin' AND p.code LIKE 'depreciation.%'
+ON CONFLICT DO NOTHING;
+
+COMMENT ON COLUMN categories.declining_balance_rate IS 'Annual rate for declining balance; defaults to 150% of the straight-line rate';
+COMMENT ON COLUMN categories.estimated_total_hours IS 'Expected lifetime hour meter usage for units-of-production';
@@ -0,0 +1,49 @@
+-- Migration: 0041_depreciation_period_close
+-- Description: Month-end depreciation close; posted schedule rows are locked until the period is reopened
+-- Created: 2026-10-18
+
+-- 1. One row per closed (or reopened) month
+CREATE TABLE IF NOT EXISTS depreciation_closings (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    period_start DATE NOT NULL UNIQUE CHECK (EXTRACT(DAY FROM period_start) = 1),
+    period_end DATE NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'closed' CHECK (status IN ('closed', 'reopened')),
+
+    -- Totals posted at close
+    asset_count INTEGER NOT NULL DEFAULT 0,
+    total_depreciation DECIMAL(18, 2) NOT NULL DEFAULT 0,
+    total_accumulated_depreciation DECIMAL(18, 2) NOT NULL DEFAULT 0,
+    total_book_value DECIMAL(18, 2) NOT NULL DEFAULT 0,
+
+    closed_by UUID REFERENCES users(id),  -- NULL when closed by the scheduler
+    closed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- Reopen goes through the approval workflow (resource_type 'depreciation_period')
+    reopen_request_id UUID REFERENCES approval_requests(id) ON DELETE SET NULL,
+    reopen_reason TEXT,
+    reopened_by UUID REFERENCES users(id),
+    reopened_at TIMESTAMPTZ,
+
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+-- 2. Schedule rows posted by a close
+ALTER TABLE depreciation_schedules
+    ADD COLUMN IF NOT EXISTS closing_id UUID REFERENCES depreciation_closings(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS posted_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_depreciation_schedules_closing ON depreciation_schedules(closing_id);
+
+-- 3. Permission to close and reopen periods
+INSERT INTO permissions (code, name, resource, action) VALUES
+('depreciation.close', 'Close Depreciation Periods', 'depreciation', 'close')
+ON CONFLICT (code) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r, permissions p
+WHERE r.code = 'super_admin' AND p.code = 'depreciation.close'
+ON CONFLICT DO NOTHING;
+
+COMMENT ON TABLE depreciation_closings IS 'Month-end depreciation close; rows of closed periods are not recalculated';
+COMMENT ON COLUMN depreciation_schedules.closing_id IS 'Close that posted the row; locked while that close is in status closed';
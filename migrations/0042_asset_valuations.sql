@@ -0,0 +1,38 @@
+-- Migration: 0042_asset_valuations
+-- Description: Appraisals, impairments and monthly calculated snapshots; revaluations reset the depreciation basis
+-- Created: 2026-10-18
+
+-- 1. Valuation records
+ALTER TABLE asset_valuations
+    ADD COLUMN IF NOT EXISTS revalued_amount DECIMAL(18, 2),    -- carrying amount set by a revaluation/impairment
+    ADD COLUMN IF NOT EXISTS adjustment_amount DECIMAL(18, 2),  -- revalued_amount minus the book value it replaced
+    ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP;
+
+UPDATE asset_valuations SET valuation_type = 'calculated'
+WHERE valuation_type IS NULL
+   OR valuation_type NOT IN ('calculated', 'appraisal', 'market', 'impairment');
+
+ALTER TABLE asset_valuations
+    ALTER COLUMN valuation_type SET NOT NULL,
+    ADD CONSTRAINT asset_valuations_type_check
+    CHECK (valuation_type IN ('calculated', 'appraisal', 'market', 'impairment'));
+
+CREATE INDEX IF NOT EXISTS idx_asset_valuations_asset_date ON asset_valuations(asset_id, valuation_date DESC);
+
+-- 2. Revaluation/impairment booked in a schedule month
+ALTER TABLE depreciation_schedules
+    ADD COLUMN IF NOT EXISTS valuation_adjustment DECIMAL(18, 2) NOT NULL DEFAULT 0;
+
+-- 3. Permissions
+INSERT INTO permissions (code, name, resource, action) VALUES
+('valuation.read', 'View Asset Valuations', 'valuation', 'read'),
+('valuation.manage', 'Record Appraisals and Impairments', 'valuation', 'manage')
+ON CONFLICT (code) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r, permissions p
+WHERE r.code = 'super_admin' AND p.code LIKE 'valuation.%'
+ON CONFLICT DO NOTHING;
+
+COMMENT ON COLUMN asset_valuations.revalued_amount IS 'Set when the valuation replaces the carrying amount; depreciation restarts from it';
+COMMENT ON COLUMN depreciation_schedules.valuation_adjustment IS 'Revaluation (+) or impairment (-) booked at the start of the period';
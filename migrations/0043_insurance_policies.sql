@@ -0,0 +1,81 @@
+-- Migration: 0043_insurance_policies
+-- Description: Insurance policy lifecycle, lapsed-coverage flag and claims against work orders or loss
+-- Created: 2026-10-18
+
+-- 1. Policy status and expiry tracking
+UPDATE insurances SET status = 'active' WHERE status IS NULL OR status = 'claimed';
+
+ALTER TABLE insurances
+    ALTER COLUMN status SET NOT NULL,
+    ADD COLUMN IF NOT EXISTS expiry_notified_at TIMESTAMPTZ, -- reset when end_date moves
+    ADD COLUMN IF NOT EXISTS created_by UUID REFERENCES users(id);
+
+ALTER TABLE insurances DROP CONSTRAINT IF EXISTS insurances_status_check;
+ALTER TABLE insurances ADD CONSTRAINT insurances_status_check
+    CHECK (status IN ('active', 'expired', 'cancelled'));
+
+ALTER TABLE insurances DROP CONSTRAINT IF EXISTS insurances_period_check;
+ALTER TABLE insurances ADD CONSTRAINT insurances_period_check CHECK (end_date >= start_date);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_insurances_asset_policy ON insurances(asset_id, policy_number);
+
+-- 2. Assets whose coverage lapsed (set by the expiry job, cleared by a covering policy)
+ALTER TABLE assets ADD COLUMN IF NOT EXISTS insurance_lapsed_at TIMESTAMPTZ;
+
+-- 3. Claims, raised from a work order (damage) or a lost/stolen transition
+CREATE TABLE IF NOT EXISTS insurance_claims (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    insurance_id UUID NOT NULL REFERENCES insurances(id) ON DELETE RESTRICT,
+    asset_id UUID NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+    claim_number VARCHAR(100), -- insurer's reference
+    incident_date DATE NOT NULL,
+    work_order_id UUID REFERENCES maintenance_work_orders(id) ON DELETE SET NULL,
+    lifecycle_history_id UUID REFERENCES asset_lifecycle_history(id) ON DELETE SET NULL,
+
+    claimed_amount DECIMAL(18, 2) NOT NULL CHECK (claimed_amount > 0),
+    approved_amount DECIMAL(18, 2) CHECK (approved_amount >= 0),
+    payout_amount DECIMAL(18, 2) CHECK (payout_amount >= 0),
+    payout_date DATE,
+
+    status VARCHAR(20) NOT NULL DEFAULT 'submitted'
+        CHECK (status IN ('submitted', 'approved', 'rejected', 'paid')),
+    description TEXT,
+    notes TEXT,
+
+    created_by UUID REFERENCES users(id),
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_insurance_claims_insurance ON insurance_claims(insurance_id);
+CREATE INDEX IF NOT EXISTS idx_insurance_claims_asset ON insurance_claims(asset_id);
+
+CREATE TRIGGER update_insurance_claims_updated_at BEFORE UPDATE ON insurance_claims
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- 4. Notification templates for the expiry job
+INSERT INTO notification_templates (code, name, event_type, subject_template, body_template, channels) VALUES
+    ('insurance_expiring', 'Insurance Expiring', 'insurance.expiring',
+     'Insurance Expiring: {{asset_name}}',
+     'Policy {{policy_number}} ({{insurance_provider}}) for {{asset_name}} expires on {{end_date}}.',
+     ARRAY['in_app', 'email']),
+
+    ('insurance_lapsed', 'Insurance Lapsed', 'insurance.lapsed',
+     'Coverage Lapsed: {{asset_name}}',
+     '{{asset_name}} ({{asset_code}}) has no active insurance policy since {{lapsed_since}}.',
+     ARRAY['in_app', 'email'])
+ON CONFLICT (code) DO NOTHING;
+
+-- 5. Permissions
+INSERT INTO permissions (code, name, resource, action) VALUES
+('insurance.read', 'View Insurance', 'insurance', 'read'),
+('insurance.manage', 'Manage Insurance', 'insurance', 'manage')
+ON CONFLICT (code) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r, permissions p
+WHERE r.code = 'super_admin' AND p.code LIKE 'insurance.%'
+ON CONFLICT DO NOTHING;
+
+COMMENT ON COLUMN assets.insurance_lapsed_at IS 'When the last covering policy ran out; NULL while covered or never insured';
+COMMENT ON TABLE insurance_claims IS 'Claims against a policy, linked to the work order or lost/stolen transition they arise from';
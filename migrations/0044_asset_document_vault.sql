@@ -0,0 +1,32 @@
+-- Migration: 0044_asset_document_vault
+-- Description: Versioned asset documents stored outside the public uploads directory, with expiry reminders
+-- Created: 2026-10-18
+
+-- 1. Versioning: all versions of a document share document_group_id; one is current
+ALTER TABLE asset_documents
+    ADD COLUMN IF NOT EXISTS document_group_id UUID,
+    ADD COLUMN IF NOT EXISTS version INTEGER NOT NULL DEFAULT 1,
+    ADD COLUMN IF NOT EXISTS is_current BOOLEAN NOT NULL DEFAULT true,
+    ADD COLUMN IF NOT EXISTS original_name VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS replaced_by UUID REFERENCES asset_documents(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS replaced_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS expiry_notified_at TIMESTAMPTZ;
+
+UPDATE asset_documents SET document_group_id = id WHERE document_group_id IS NULL;
+ALTER TABLE asset_documents ALTER COLUMN document_group_id SET NOT NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_asset_documents_current
+    ON asset_documents(document_group_id) WHERE is_current;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_asset_documents_group_version
+    ON asset_documents(document_group_id, version);
+
+-- 2. Reminder template for expiring certificates, warranties, permits, etc.
+INSERT INTO notification_templates (code, name, event_type, subject_template, body_template, channels) VALUES
+    ('document_expiring', 'Document Expiring', 'document.expiring',
+     'Document Expiring: {{document_name}}',
+     '{{document_type}} "{{document_name}}" for {{asset_name}} expires on {{expiry_date}}.',
+     ARRAY['in_app', 'email'])
+ON CONFLICT (code) DO NOTHING;
+
+COMMENT ON COLUMN asset_documents.file_path IS 'Storage key relative to DOCUMENT_STORAGE_DIR; not publicly served';
+COMMENT ON COLUMN asset_documents.document_group_id IS 'Shared by all versions of one document';
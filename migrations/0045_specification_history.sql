@@ -0,0 +1,22 @@
+-- Migration: 0045_specification_history
+-- Description: Per-category specification schema and structured specification history with a JSON diff
+-- Created: 2026-10-18
+
+-- 1. Schema the specifications of a category's assets are validated against
+ALTER TABLE categories ADD COLUMN IF NOT EXISTS specification_schema JSONB;
+
+COMMENT ON COLUMN categories.specification_schema IS
+    '{"fields": {"<key>": {"type": "string|number|integer|boolean|date|object|array", "required": bool, "min": n, "max": n, "enum": [...], "unit": "..."}}, "allow_additional": bool}';
+
+-- 2. Structured history: conversions and manual edits write one row each
+ALTER TABLE asset_specification_history
+    ADD COLUMN IF NOT EXISTS diff JSONB NOT NULL DEFAULT '[]'::jsonb;
+
+ALTER TABLE asset_specification_history DROP CONSTRAINT IF EXISTS asset_specification_history_change_type_check;
+ALTER TABLE asset_specification_history ADD CONSTRAINT asset_specification_history_change_type_check
+    CHECK (change_type IN ('conversion', 'upgrade', 'modification', 'correction'));
+
+CREATE INDEX IF NOT EXISTS idx_spec_history_asset_created
+    ON asset_specification_history(asset_id, created_at DESC);
+
+COMMENT ON COLUMN asset_specification_history.diff IS 'Changed paths from old to new specifications: [{path, change, old_value, new_value}]';
@@ -0,0 +1,45 @@
+-- Migration: 0046_organization_tenancy
+-- Description: User membership in organizations and organization-subtree scoping of assets and their loans, rentals and work orders
+-- Created: 2026-10-18
+
+-- 1. Memberships; users.organization_id stays the home organization used at login
+CREATE TABLE IF NOT EXISTS user_organizations (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    created_by UUID REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (user_id, organization_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_organizations_org ON user_organizations(organization_id);
+
+INSERT INTO user_organizations (user_id, organization_id)
+SELECT id, organization_id FROM users WHERE organization_id IS NOT NULL
+ON CONFLICT DO NOTHING;
+
+-- 2. Assets without an owner go to the oldest top-level organization
+UPDATE assets SET organization_id = (
+    SELECT id FROM organizations WHERE parent_id IS NULL ORDER BY created_at, code LIMIT 1
+)
+WHERE organization_id IS NULL;
+
+-- 3. An organization and all of its descendants
+CREATE OR REPLACE FUNCTION organization_subtree(root UUID)
+RETURNS SETOF UUID AS $$
+    WITH RECURSIVE tree AS (
+        SELECT id FROM organizations WHERE id = root
+        UNION
+        SELECT o.id FROM organizations o JOIN tree t ON o.parent_id = t.id
+    )
+    SELECT id FROM tree;
+$$ LANGUAGE sql STABLE;
+
+-- Whether an asset belongs to the subtree of `root`; loans, rentals and work
+-- orders are scoped through their asset
+CREATE OR REPLACE FUNCTION asset_in_organization(asset UUID, root UUID)
+RETURNS BOOLEAN AS $$
+    SELECT EXISTS (
+        SELECT 1 FROM assets a
+        WHERE a.id = asset AND a.organization_id IN (SELECT organization_subtree(root))
+    );
+$$ LANGUAGE sql STABLE;
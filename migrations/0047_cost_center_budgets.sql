@@ -0,0 +1,71 @@
+-- Migration: 0047_cost_center_budgets
+-- Description: Budget lines per cost center/department and fiscal period with a commitment/actual spend ledger
+-- Created: 2026-10-18
+
+-- 1. Budget lines; the cost center is the one of the owning organization
+CREATE TABLE IF NOT EXISTS budget_lines (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id),
+    department_id UUID REFERENCES departments(id),  -- NULL covers the whole cost center
+    name VARCHAR(255) NOT NULL,
+
+    -- Fiscal period
+    fiscal_year INTEGER NOT NULL,
+    period_start DATE NOT NULL,
+    period_end DATE NOT NULL,
+
+    amount DECIMAL(18, 2) NOT NULL CHECK (amount >= 0),
+    over_budget_action VARCHAR(20) NOT NULL DEFAULT 'block'
+        CHECK (over_budget_action IN ('block', 'escalate')),
+    notes TEXT,
+
+    created_by UUID REFERENCES users(id),
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+
+    CHECK (period_end >= period_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_budget_lines_org_period ON budget_lines(organization_id, period_start, period_end);
+
+CREATE TRIGGER update_budget_lines_updated_at BEFORE UPDATE ON budget_lines
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- 2. Spend booked against a line: one commitment and one actual per source
+CREATE TABLE IF NOT EXISTS budget_entries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    budget_line_id UUID NOT NULL REFERENCES budget_lines(id),
+    source_type VARCHAR(30) NOT NULL CHECK (source_type IN ('work_order', 'conversion', 'asset_purchase')),
+    source_id UUID NOT NULL,
+    asset_id UUID REFERENCES assets(id) ON DELETE SET NULL,
+    entry_type VARCHAR(20) NOT NULL CHECK (entry_type IN ('commitment', 'actual')),
+    amount DECIMAL(18, 2) NOT NULL CHECK (amount >= 0),
+    entry_date DATE NOT NULL,
+
+    -- Commitments are released once the actual is posted or the spend is dropped
+    released_at TIMESTAMPTZ,
+    -- Over-budget commitments accepted through the approval workflow
+    approval_request_id UUID REFERENCES approval_requests(id) ON DELETE SET NULL,
+
+    created_by UUID REFERENCES users(id),
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+
+    UNIQUE (source_type, source_id, entry_type)
+);
+
+CREATE INDEX IF NOT EXISTS idx_budget_entries_line ON budget_entries(budget_line_id);
+
+-- 3. Permissions
+INSERT INTO permissions (code, name, resource, action) VALUES
+('budget.read', 'View Budgets and Spend', 'budget', 'read'),
+('budget.manage', 'Manage Budget Lines', 'budget', 'manage')
+ON CONFLICT (code) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r, permissions p
+WHERE r.code = 'super_admin' AND p.code LIKE 'budget.%'
+ON CONFLICT DO NOTHING;
+
+COMMENT ON TABLE budget_lines IS 'Budget of a cost center (organization) or one of its departments for a fiscal period';
+COMMENT ON COLUMN budget_lines.over_budget_action IS 'block refuses spend above the line; escalate opens a budget OVERRUN approval';
+COMMENT ON TABLE budget_entries IS 'Commitments (approved work orders/conversions) and actual spend booked against budget lines';
@@ -0,0 +1,69 @@
+-- Migration: 0048_auth_sessions
+-- Description: Login sessions with rotating refresh tokens and a per-user token version for revocation
+-- Created: 2026-10-18
+
+-- 1. Token version: sessions of an older version are revoked
+ALTER TABLE users ADD COLUMN IF NOT EXISTS token_version INTEGER NOT NULL DEFAULT 0;
+
+CREATE OR REPLACE FUNCTION bump_user_token_version()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.password_hash IS DISTINCT FROM OLD.password_hash
+       OR NEW.role IS DISTINCT FROM OLD.role
+       OR NEW.role_id IS DISTINCT FROM OLD.role_id
+       OR NEW.is_active IS DISTINCT FROM OLD.is_active THEN
+        NEW.token_version := OLD.token_version + 1;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS bump_users_token_version ON users;
+CREATE TRIGGER bump_users_token_version BEFORE UPDATE ON users
+    FOR EACH ROW EXECUTE FUNCTION bump_user_token_version();
+
+-- Permissions granted to or removed from a role invalidate the tokens of its users
+CREATE OR REPLACE FUNCTION bump_role_token_versions()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE users SET token_version = token_version + 1
+    WHERE role_id IN (NEW.role_id, OLD.role_id);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS bump_role_permissions_token_versions ON role_permissions;
+CREATE TRIGGER bump_role_permissions_token_versions
+    AFTER INSERT OR UPDATE OR DELETE ON role_permissions
+    FOR EACH ROW EXECUTE FUNCTION bump_role_token_versions();
+
+-- 2. Login sessions; access tokens carry the session id
+CREATE TABLE IF NOT EXISTS user_sessions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    organization_id UUID REFERENCES organizations(id) ON DELETE SET NULL, -- active organization
+    token_version INTEGER NOT NULL,
+    user_agent TEXT,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    last_refreshed_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    revoked_reason VARCHAR(50)
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_sessions_user ON user_sessions(user_id) WHERE revoked_at IS NULL;
+
+-- 3. Refresh tokens, stored as SHA-256 hashes; each is used once
+CREATE TABLE IF NOT EXISTS refresh_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    session_id UUID NOT NULL REFERENCES user_sessions(id) ON DELETE CASCADE,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_session ON refresh_tokens(session_id);
+
+COMMENT ON COLUMN users.token_version IS 'Bumped on password, role or activation changes; older sessions are rejected';
+COMMENT ON TABLE user_sessions IS 'Login sessions; revoked on logout, refresh token reuse or token version change';
+COMMENT ON TABLE refresh_tokens IS 'Rotating refresh tokens (SHA-256); presenting a used token revokes its session';
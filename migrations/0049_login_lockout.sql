@@ -0,0 +1,12 @@
+-- Migration: 0049_login_lockout
+-- Description: Failed login tracking with progressive delays and temporary account lockout
+-- Created: 2026-10-18
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS failed_login_attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS last_failed_login_at TIMESTAMPTZ;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS locked_until TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_users_locked ON users(locked_until) WHERE locked_until IS NOT NULL;
+
+COMMENT ON COLUMN users.failed_login_attempts IS 'Consecutive failed logins; reset on success or admin unlock';
+COMMENT ON COLUMN users.locked_until IS 'Login refused until this time; lockouts are recorded in audit_logs (ACCOUNT_LOCKED)';
@@ -0,0 +1,42 @@
+-- Migration: 0050_two_factor
+-- Description: TOTP two-factor authentication with recovery codes; mandatory for roles flagged in RBAC
+-- Created: 2026-10-18
+
+-- 1. Policy: a role requires 2FA when flagged itself or when it holds a flagged permission
+ALTER TABLE roles ADD COLUMN IF NOT EXISTS requires_two_factor BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE permissions ADD COLUMN IF NOT EXISTS requires_two_factor BOOLEAN NOT NULL DEFAULT false;
+
+-- Approval permissions and the approver roles of approval workflows
+UPDATE permissions SET requires_two_factor = true
+WHERE action IN ('approve', 'approve_cost') OR code = 'approval_workflow.manage';
+
+UPDATE roles SET requires_two_factor = true
+WHERE id IN (SELECT role_id FROM approval_workflow_levels WHERE role_id IS NOT NULL);
+
+-- 2. TOTP secrets (RFC 6238, SHA-1, 6 digits, 30 s); pending until the first code is verified
+CREATE TABLE IF NOT EXISTS user_two_factor (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    secret VARCHAR(64) NOT NULL,            -- base32
+    enabled_at TIMESTAMPTZ,
+    last_used_step BIGINT,                  -- codes of this or earlier time steps are refused
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE TRIGGER update_user_two_factor_updated_at BEFORE UPDATE ON user_two_factor
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- 3. One-time recovery codes, stored as SHA-256 hashes
+CREATE TABLE IF NOT EXISTS user_recovery_codes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    code_hash VARCHAR(64) NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (user_id, code_hash)
+);
+
+COMMENT ON COLUMN roles.requires_two_factor IS 'Members must log in with a second factor';
+COMMENT ON COLUMN permissions.requires_two_factor IS 'Roles holding this permission must log in with a second factor';
+COMMENT ON TABLE user_two_factor IS 'TOTP enrolment per user; enabled once the first code is verified';
+COMMENT ON TABLE user_recovery_codes IS 'One-time recovery codes replacing a TOTP code when the device is lost';
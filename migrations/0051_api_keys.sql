@@ -0,0 +1,38 @@
+-- Migration: 0051_api_keys
+-- Description: Service accounts and scoped API keys for integrations
+-- Created: 2026-10-18
+
+-- 1. Service accounts are users without a usable password
+ALTER TABLE users ADD COLUMN IF NOT EXISTS is_service_account BOOLEAN NOT NULL DEFAULT false;
+
+COMMENT ON COLUMN users.is_service_account IS 'Integration identity; authenticates with API keys only';
+
+-- 2. API keys, stored as a SHA-256 hash and identified by their public prefix
+CREATE TABLE IF NOT EXISTS api_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    prefix VARCHAR(20) NOT NULL UNIQUE,
+    key_hash VARCHAR(64) NOT NULL,
+    scopes TEXT[] NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    last_used_at TIMESTAMPTZ,
+    last_used_ip VARCHAR(50),
+    revoked_at TIMESTAMPTZ,
+    created_by UUID REFERENCES users(id),
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_user ON api_keys(user_id);
+
+COMMENT ON COLUMN api_keys.scopes IS 'Permission codes the key may use; limited further by the owner''s role';
+
+DROP TRIGGER IF EXISTS update_api_keys_updated_at ON api_keys;
+CREATE TRIGGER update_api_keys_updated_at BEFORE UPDATE ON api_keys
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- 3. Audit entries made through an API key
+ALTER TABLE audit_logs ADD COLUMN IF NOT EXISTS api_key_id UUID REFERENCES api_keys(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_audit_logs_api_key ON audit_logs(api_key_id) WHERE api_key_id IS NOT NULL;
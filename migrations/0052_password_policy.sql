@@ -0,0 +1,68 @@
+-- Migration: 0052_password_policy
+-- Description: Password history, single-use reset tokens and admin-forced password changes
+-- Created: 2026-10-18
+
+-- 1. Forced change at the next login
+ALTER TABLE users ADD COLUMN IF NOT EXISTS must_change_password BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS password_changed_at TIMESTAMPTZ;
+
+-- Forcing a change also ends the user's sessions (see 0048_auth_sessions)
+CREATE OR REPLACE FUNCTION bump_user_token_version()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.password_hash IS DISTINCT FROM OLD.password_hash
+       OR NEW.role IS DISTINCT FROM OLD.role
+       OR NEW.role_id IS DISTINCT FROM OLD.role_id
+       OR NEW.is_active IS DISTINCT FROM OLD.is_active
+       OR (NEW.must_change_password AND NOT OLD.must_change_password) THEN
+        NEW.token_version := OLD.token_version + 1;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- 2. Previous password hashes, recorded whenever a password changes
+CREATE TABLE IF NOT EXISTS password_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    password_hash VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_password_history_user ON password_history(user_id, created_at DESC);
+
+CREATE OR REPLACE FUNCTION record_password_history()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.password_hash IS DISTINCT FROM OLD.password_hash THEN
+        INSERT INTO password_history (user_id, password_hash) VALUES (OLD.id, OLD.password_hash);
+        NEW.password_changed_at := NOW();
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS record_users_password_history ON users;
+CREATE TRIGGER record_users_password_history BEFORE UPDATE OF password_hash ON users
+    FOR EACH ROW EXECUTE FUNCTION record_password_history();
+
+-- 3. Reset tokens, stored as SHA-256 hashes; each is used once
+CREATE TABLE IF NOT EXISTS password_reset_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    expires_at TIMESTAMPTZ NOT NULL,
+    used_at TIMESTAMPTZ,
+    requested_ip VARCHAR(50),
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_password_reset_tokens_user ON password_reset_tokens(user_id) WHERE used_at IS NULL;
+
+-- 4. Reset link message; email only, the link must not sit in the in-app inbox
+INSERT INTO notification_templates (code, name, event_type, subject_template, body_template, channels) VALUES
+    ('password_reset', 'Password Reset', 'auth.password_reset',
+     'Reset your password',
+     'Hello {{name}}, use this link within {{expires_minutes}} minutes to choose a new password: {{reset_url}}. If you did not ask for this, ignore this message.',
+     ARRAY['email'])
+ON CONFLICT (code) DO NOTHING;
@@ -0,0 +1,117 @@
+-- Migration: 0053_permission_scopes
+-- Description: Data-level scopes on permission grants (own records, department subtree, location subtree or all)
+-- Created: 2026-10-18
+
+-- 1. Every grant carries a scope; existing grants keep seeing everything
+ALTER TABLE role_permissions ADD COLUMN IF NOT EXISTS scope VARCHAR(20) NOT NULL DEFAULT 'all';
+
+ALTER TABLE role_permissions DROP CONSTRAINT IF EXISTS role_permissions_scope_check;
+ALTER TABLE role_permissions ADD CONSTRAINT role_permissions_scope_check
+    CHECK (scope IN ('own', 'department', 'location', 'all'));
+
+COMMENT ON COLUMN role_permissions.scope IS 'Records the grant reaches: own, department subtree, location subtree or all';
+
+-- 2. Home location of a user, the root of location-scoped grants
+ALTER TABLE users ADD COLUMN IF NOT EXISTS location_id UUID REFERENCES locations(id);
+
+CREATE INDEX IF NOT EXISTS idx_users_location ON users(location_id);
+CREATE INDEX IF NOT EXISTS idx_assets_department ON assets(department_id);
+CREATE INDEX IF NOT EXISTS idx_assets_location ON assets(location_id);
+
+-- 3. Department and location trees
+CREATE OR REPLACE FUNCTION department_subtree(root UUID)
+RETURNS SETOF UUID AS $$
+    WITH RECURSIVE tree AS (
+        SELECT id FROM departments WHERE id = root
+        UNION
+        SELECT d.id FROM departments d JOIN tree t ON d.parent_id = t.id
+    )
+    SELECT id FROM tree;
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION location_subtree(root UUID)
+RETURNS SETOF UUID AS $$
+    WITH RECURSIVE tree AS (
+        SELECT id FROM locations WHERE id = root
+        UNION
+        SELECT l.id FROM locations l JOIN tree t ON l.parent_id = t.id
+    )
+    SELECT id FROM tree;
+$$ LANGUAGE sql STABLE;
+
+-- 4. Resolved scope of a user for one permission, bound by the application.
+-- The user always reaches their own records; department and location are the
+-- subtree roots granted, if any.
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'data_scope') THEN
+        CREATE TYPE data_scope AS (
+            all_records BOOLEAN,
+            owner_id UUID,
+            department_id UUID,
+            location_id UUID
+        );
+    END IF;
+END $$;
+
+-- Whether a record owned by any of `owners` and filed under the department and
+-- location given is within the scope
+CREATE OR REPLACE FUNCTION data_scope_allows(
+    scope data_scope,
+    owners UUID[],
+    department UUID,
+    location UUID
+)
+RETURNS BOOLEAN AS $$
+    SELECT COALESCE(scope.all_records, false)
+        OR (scope.owner_id IS NOT NULL AND scope.owner_id = ANY(owners))
+        OR (scope.department_id IS NOT NULL AND department IN (SELECT department_subtree(scope.department_id)))
+        OR (scope.location_id IS NOT NULL AND location IN (SELECT location_subtree(scope.location_id)));
+$$ LANGUAGE sql STABLE;
+
+-- Loans, rentals and work orders are scoped through their asset, plus the
+-- people the record belongs to
+CREATE OR REPLACE FUNCTION asset_in_data_scope(asset UUID, scope data_scope, owners UUID[])
+RETURNS BOOLEAN AS $$
+    SELECT COALESCE(scope.all_records, false)
+        OR (scope.owner_id IS NOT NULL AND scope.owner_id = ANY(owners))
+        OR EXISTS (
+            SELECT 1 FROM assets a
+            WHERE a.id = asset
+              AND data_scope_allows(scope, ARRAY[a.assigned_to], a.department_id, a.location_id)
+        );
+$$ LANGUAGE sql STABLE;
+
+-- 5. Reading loans becomes a permission of its own
+INSERT INTO permissions (code, name, resource, action) VALUES
+('loan.read', 'View Loans', 'loan', 'read')
+ON CONFLICT (code) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r, permissions p
+WHERE r.code = 'super_admin' AND p.code = 'loan.read'
+ON CONFLICT DO NOTHING;
+
+-- 6. Managers and supervisors work within their department subtree,
+-- technicians on the work orders they are on
+INSERT INTO role_permissions (role_id, permission_id, scope)
+SELECT r.id, p.id, 'department' FROM roles r, permissions p
+WHERE r.code = 'manager'
+  AND p.code IN (
+      'asset.read', 'asset.update',
+      'loan.read', 'loan.approve', 'loan.checkout', 'loan.checkin',
+      'report.view', 'report.export'
+  )
+ON CONFLICT DO NOTHING;
+
+UPDATE role_permissions rp SET scope = 'department'
+FROM roles r, permissions p
+WHERE rp.role_id = r.id AND rp.permission_id = p.id
+  AND r.code IN ('manager', 'supervisor')
+  AND p.code LIKE 'work_order.%';
+
+UPDATE role_permissions rp SET scope = 'own'
+FROM roles r, permissions p
+WHERE rp.role_id = r.id AND rp.permission_id = p.id
+  AND r.code = 'technician'
+  AND p.code LIKE 'work_order.%';
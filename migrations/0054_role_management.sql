@@ -0,0 +1,27 @@
+-- Migration: 0054_role_management
+-- Description: Custom roles managed by admins; role level changes invalidate the tokens of the role's users
+-- Created: 2026-10-18
+
+-- 1. The role level is carried in access tokens, like the role's permissions
+CREATE OR REPLACE FUNCTION bump_role_level_token_versions()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.role_level IS DISTINCT FROM OLD.role_level THEN
+        UPDATE users SET token_version = token_version + 1
+        WHERE role_id = NEW.id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS bump_roles_token_versions ON roles;
+CREATE TRIGGER bump_roles_token_versions
+    AFTER UPDATE ON roles
+    FOR EACH ROW EXECUTE FUNCTION bump_role_level_token_versions();
+
+-- 2. Role codes are identifiers used in tokens and checks
+ALTER TABLE roles DROP CONSTRAINT IF EXISTS roles_code_format_check;
+ALTER TABLE roles ADD CONSTRAINT roles_code_format_check
+    CHECK (code ~ '^[a-z][a-z0-9_]{1,49}$');
+
+COMMENT ON COLUMN roles.is_system IS 'Roles seeded by migrations; they cannot be deleted';
@@ -0,0 +1,25 @@
+-- Migration: 0055_event_outbox
+-- Description: Transactional outbox of domain events, relayed to subscribers at least once
+-- Created: 2026-10-18
+
+CREATE TABLE IF NOT EXISTS event_outbox (
+    id UUID PRIMARY KEY,                      -- Envelope id; subscribers deduplicate on it
+    seq BIGINT GENERATED ALWAYS AS IDENTITY,  -- Relay order
+    event_type VARCHAR(100) NOT NULL,
+    aggregate_id UUID NOT NULL,
+    envelope JSONB NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    attempts INT NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    locked_until TIMESTAMPTZ,                 -- Lease of the relay delivering the event
+    last_error TEXT,
+    delivered_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_outbox_pending
+    ON event_outbox (next_attempt_at, seq) WHERE delivered_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_event_outbox_aggregate
+    ON event_outbox (aggregate_id, seq);
+
+COMMENT ON TABLE event_outbox IS 'Domain events written with the change they describe; delivered by the outbox relay';
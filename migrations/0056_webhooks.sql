@@ -0,0 +1,44 @@
+-- Migration: 0056_webhooks
+-- Description: Admin-managed webhook subscriptions to domain events and their delivery log
+-- Created: 2026-10-18
+
+CREATE TABLE IF NOT EXISTS webhook_subscriptions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(100) NOT NULL,
+    url TEXT NOT NULL,
+    event_types TEXT[] NOT NULL DEFAULT '{}',  -- e.g. asset.created, rental.*; empty for all events
+    secret TEXT NOT NULL,                      -- HMAC-SHA256 signing key
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    max_attempts INT NOT NULL DEFAULT 8 CHECK (max_attempts BETWEEN 1 AND 20),
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    subscription_id UUID NOT NULL REFERENCES webhook_subscriptions(id) ON DELETE CASCADE,
+    event_id UUID NOT NULL,                    -- Envelope id of the event
+    event_type VARCHAR(100) NOT NULL,
+    payload JSONB NOT NULL,                    -- The event envelope, as sent
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'retrying', 'sent', 'dead')),
+    attempts INT NOT NULL DEFAULT 0,
+    last_status_code INT,
+    last_error TEXT,
+    next_attempt_at TIMESTAMPTZ DEFAULT NOW(),
+    sent_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Events are relayed at least once; each is delivered once per subscription
+    UNIQUE (subscription_id, event_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due
+    ON webhook_deliveries (next_attempt_at) WHERE status IN ('pending', 'retrying');
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_subscription
+    ON webhook_deliveries (subscription_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_status
+    ON webhook_deliveries (status, created_at DESC);
+
+COMMENT ON COLUMN webhook_deliveries.status IS 'dead: out of attempts; listed as dead letters until redelivered';
@@ -0,0 +1,56 @@
+-- Migration: 0057_document_numbering
+-- Description: Central per-document-type number sequences replacing timestamp-based numbers
+-- Created: 2026-10-18
+
+-- How numbers of each document type are built: PREFIX[-SEGMENT][-YYYY|-YYYYMM]-000042
+CREATE TABLE IF NOT EXISTS document_number_formats (
+    document_type VARCHAR(30) PRIMARY KEY,
+    prefix VARCHAR(10) NOT NULL CHECK (prefix ~ '^[A-Z0-9]+$'),
+    reset_period VARCHAR(10) NOT NULL DEFAULT 'yearly'
+        CHECK (reset_period IN ('never', 'yearly', 'monthly')),
+    padding INT NOT NULL DEFAULT 5 CHECK (padding BETWEEN 1 AND 12),
+    segment VARCHAR(20) NOT NULL DEFAULT 'none'
+        CHECK (segment IN ('none', 'department', 'location')),
+    updated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Prefixes match the ones used so far
+INSERT INTO document_number_formats (document_type, prefix, reset_period, padding) VALUES
+    ('loan', 'LN', 'yearly', 5),
+    ('work_order', 'WO', 'yearly', 5),
+    ('rental', 'RNT', 'yearly', 5),
+    ('client', 'CLT', 'never', 5),
+    ('conversion', 'CNV', 'yearly', 5),
+    ('invoice', 'INV', 'yearly', 6)
+ON CONFLICT (document_type) DO NOTHING;
+
+-- Last number issued per document type, segment and period. Incrementing a row
+-- locks it until the transaction ends, so concurrent requests never share a
+-- number, and an invoice whose transaction rolls back gives its number back.
+CREATE TABLE IF NOT EXISTS document_sequences (
+    document_type VARCHAR(30) NOT NULL
+        REFERENCES document_number_formats(document_type) ON DELETE CASCADE,
+    segment_key VARCHAR(20) NOT NULL DEFAULT '',  -- Department or location code; '' without a segment
+    period_key VARCHAR(6) NOT NULL DEFAULT '',    -- YYYY or YYYYMM; '' when never reset
+    last_value BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (document_type, segment_key, period_key)
+);
+
+-- Existing numbers are kept as they are. They have the old PREFIX-YYYYMMDDHHMMSS
+-- shape, which new numbers never take. Invoice numbers become unique unless
+-- earlier same-second collisions prevent it.
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM rental_billing_periods
+        WHERE invoice_number IS NOT NULL
+        GROUP BY invoice_number HAVING COUNT(*) > 1
+    ) THEN
+        RAISE NOTICE 'Duplicate invoice numbers exist; invoice_number is left non-unique';
+    ELSE
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_rental_billing_invoice_number_unique
+            ON rental_billing_periods (invoice_number) WHERE invoice_number IS NOT NULL;
+    END IF;
+END $$;
@@ -0,0 +1,16 @@
+-- Migration: 0058_manager_department_scope
+-- Description: Narrow the manager grants 0053 meant to limit to the department subtree
+-- Created: 2026-10-18
+
+-- 0053 added the department grants without touching grants the manager role
+-- already held, so those kept seeing all records
+UPDATE role_permissions rp SET scope = 'department'
+FROM roles r, permissions p
+WHERE rp.role_id = r.id AND rp.permission_id = p.id
+  AND r.code = 'manager'
+  AND p.code IN (
+      'asset.read', 'asset.update',
+      'loan.read', 'loan.approve', 'loan.checkout', 'loan.checkin',
+      'report.view', 'report.export'
+  )
+  AND rp.scope = 'all';
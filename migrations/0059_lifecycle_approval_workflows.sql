@@ -0,0 +1,37 @@
+-- Migration: 0059_lifecycle_approval_workflows
+-- Description: Lifecycle transitions need approval only where a workflow says so
+-- Created: 2026-10-18
+
+-- 1. Transitions without a workflow of their own run immediately instead of
+-- falling back to the two-level default
+INSERT INTO approval_workflows (resource_type, action_type, asset_type, name, description, need_approval)
+VALUES ('lifecycle_transition', '*', NULL, 'Lifecycle transitions',
+        'Transitions without a workflow of their own need no approval', FALSE)
+ON CONFLICT DO NOTHING;
+
+-- 2. The transitions the service used to hold back keep Supervisor then Manager
+INSERT INTO approval_workflows (resource_type, action_type, asset_type, name, description, need_approval)
+SELECT 'lifecycle_transition', 'transition_to_' || s.state, NULL, s.name,
+       'Supervisor, then Manager', TRUE
+FROM (VALUES
+    ('deployed', 'Deploy asset'),
+    ('under_conversion', 'Start asset conversion'),
+    ('retired', 'Retire asset'),
+    ('disposed', 'Dispose of asset'),
+    ('lost_stolen', 'Report asset lost or stolen')
+) AS s(state, name)
+ON CONFLICT DO NOTHING;
+
+INSERT INTO approval_workflow_levels (workflow_id, level_number, role_id)
+SELECT w.id, l.level_number, r.id
+FROM approval_workflows w
+CROSS JOIN (VALUES (1, 'supervisor'), (2, 'manager')) AS l(level_number, role_code)
+JOIN roles r ON r.code = l.role_code
+WHERE w.resource_type = 'lifecycle_transition'
+  AND w.action_type IN (
+      'transition_to_deployed', 'transition_to_under_conversion', 'transition_to_retired',
+      'transition_to_disposed', 'transition_to_lost_stolen'
+  )
+  AND w.asset_type IS NULL
+  AND NOT EXISTS (SELECT 1 FROM approval_workflow_levels x WHERE x.workflow_id = w.id)
+ON CONFLICT DO NOTHING;
@@ -104,7 +104,8 @@ export default function ApprovalScreen() {
     const getStatusColor = (status: string) => {
         switch (status) {
             case 'APPROVED_L1': return theme.colors.primary;
-            case 'APPROVED_L2': return 'green'; // theme.colors.tertiary might be better if defined
+            case 'APPROVED_L2':
+            case 'APPROVED': return 'green'; // theme.colors.tertiary might be better if defined
             case 'REJECTED': return theme.colors.error;
             case 'PENDING': return 'orange'; // theme.colors.warning if defined
             default: return theme.colors.secondary;
@@ -0,0 +1,108 @@
+//! API Key Handler
+//!
+//! Personal API keys, service accounts and their keys.
+
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use uuid::Uuid;
+
+use crate::api::server::AppState;
+use crate::application::dto::{ApiResponse, CreateApiKeyRequest, CreateServiceAccountRequest};
+use crate::domain::entities::{ApiKey, IssuedApiKey, ServiceAccount, UserClaims};
+use crate::shared::errors::AppError;
+
+pub async fn list_my_keys(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+) -> Result<Json<ApiResponse<Vec<ApiKey>>>, AppError> {
+    let keys = state.api_key_service.list_keys(claims.user_id()).await?;
+    Ok(Json(ApiResponse::success(keys)))
+}
+
+/// The plain key is only part of this response
+pub async fn create_my_key(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Json(payload): Json<CreateApiKeyRequest>,
+) -> Result<Json<ApiResponse<IssuedApiKey>>, AppError> {
+    let key = state
+        .api_key_service
+        .create_key(claims.user_id(), payload, &claims)
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        key,
+        "API key created; store it now, it is not shown again",
+    )))
+}
+
+/// Own keys, or any key for admins
+pub async fn rotate_key(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<IssuedApiKey>>, AppError> {
+    let key = state.api_key_service.rotate_key(id, &claims).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        key,
+        "API key rotated; the previous key no longer works",
+    )))
+}
+
+pub async fn revoke_key(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<ApiKey>>, AppError> {
+    let key = state.api_key_service.revoke_key(id, &claims).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        key,
+        "API key revoked",
+    )))
+}
+
+// ==================== SERVICE ACCOUNTS (admin) ====================
+
+pub async fn list_service_accounts(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<ServiceAccount>>>, AppError> {
+    let accounts = state.api_key_service.list_service_accounts().await?;
+    Ok(Json(ApiResponse::success(accounts)))
+}
+
+pub async fn create_service_account(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Json(payload): Json<CreateServiceAccountRequest>,
+) -> Result<Json<ApiResponse<ServiceAccount>>, AppError> {
+    let account = state
+        .api_key_service
+        .create_service_account(payload, claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success(account)))
+}
+
+pub async fn list_service_account_keys(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<ApiKey>>>, AppError> {
+    let keys = state.api_key_service.list_service_account_keys(id).await?;
+    Ok(Json(ApiResponse::success(keys)))
+}
+
+pub async fn create_service_account_key(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateApiKeyRequest>,
+) -> Result<Json<ApiResponse<IssuedApiKey>>, AppError> {
+    let key = state
+        .api_key_service
+        .create_service_account_key(id, payload, &claims)
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        key,
+        "API key created; store it now, it is not shown again",
+    )))
+}
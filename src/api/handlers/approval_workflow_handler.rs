@@ -0,0 +1,84 @@
+//! Approval Workflow Handlers
+//!
+//! Admin endpoints for configuring approval workflows.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::api::server::AppState;
+use crate::application::dto::{
+    ApiResponse, CreateApprovalWorkflowRequest, ResolveWorkflowParams,
+    UpdateApprovalWorkflowRequest,
+};
+use crate::domain::entities::ApprovalWorkflow;
+use crate::domain::errors::DomainError;
+use crate::shared::errors::AppError;
+
+pub async fn list_approval_workflows(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<ApprovalWorkflow>>>, AppError> {
+    let workflows = state.approval_service.list_workflows().await?;
+    Ok(Json(ApiResponse::success(workflows)))
+}
+
+pub async fn get_approval_workflow(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<ApprovalWorkflow>>, AppError> {
+    let workflow = state.approval_service.get_workflow(id).await?;
+    Ok(Json(ApiResponse::success(workflow)))
+}
+
+/// Preview which workflow a resource/action would use
+pub async fn resolve_approval_workflow(
+    State(state): State<AppState>,
+    Query(params): Query<ResolveWorkflowParams>,
+) -> Result<Json<ApiResponse<Option<ApprovalWorkflow>>>, AppError> {
+    let workflow = state
+        .approval_service
+        .resolve_workflow(
+            &params.resource_type,
+            &params.action_type,
+            params.category_id,
+        )
+        .await?;
+    Ok(Json(ApiResponse::success(workflow)))
+}
+
+pub async fn create_approval_workflow(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateApprovalWorkflowRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<ApprovalWorkflow>>), AppError> {
+    let workflow = state.approval_service.create_workflow(payload).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success_with_message(
+            workflow,
+            "Approval workflow created",
+        )),
+    ))
+}
+
+pub async fn update_approval_workflow(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateApprovalWorkflowRequest>,
+) -> Result<Json<ApiResponse<ApprovalWorkflow>>, AppError> {
+    let workflow = state.approval_service.update_workflow(id, payload).await?;
+    Ok(Json(ApiResponse::success(workflow)))
+}
+
+pub async fn delete_approval_workflow(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    if state.approval_service.delete_workflow(id).await? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(DomainError::not_found("ApprovalWorkflow", id).into())
+    }
+}
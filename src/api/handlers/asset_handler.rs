@@ -23,15 +23,9 @@ pub async fn list_assets(
     Extension(claims): Extension<UserClaims>,
     Query(params): Query<PaginationParams>,
 ) -> Result<Json<PaginatedResponse<AssetSummary>>, AppError> {
-    let department_filter = if claims.role == "super_admin" {
-        None
-    } else {
-        claims.department.as_deref()
-    };
-
     let result = state
         .asset_service
-        .list(params.page(), params.per_page(), department_filter)
+        .list(params.page(), params.per_page(), &claims)
         .await?;
     Ok(Json(result))
 }
@@ -39,22 +33,18 @@ pub async fn list_assets(
 pub async fn search_assets(
     State(state): State<AppState>,
     Extension(claims): Extension<UserClaims>,
-    Query(mut params): Query<AssetSearchParams>,
+    Query(params): Query<AssetSearchParams>,
 ) -> Result<Json<PaginatedResponse<AssetSummary>>, AppError> {
-    if claims.role != "super_admin" {
-        if let Some(dept) = &claims.department {
-            params.department = Some(dept.clone());
-        }
-    }
-    let result = state.asset_service.search(params).await?;
+    let result = state.asset_service.search(params, &claims).await?;
     Ok(Json(result))
 }
 
 pub async fn get_asset(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<Asset>, AppError> {
-    let asset = state.asset_service.get_by_id(id).await?;
+    let asset = state.asset_service.get_in_scope(id, &claims).await?;
     Ok(Json(asset))
 }
 
@@ -70,6 +60,8 @@ pub async fn create_asset(
         }
     }
 
+    assign_organization(&state, &claims, &mut payload).await?;
+
     // Parse user_id from subject
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID in token".to_string()))?;
@@ -99,8 +91,12 @@ pub async fn create_asset(
 pub async fn bulk_create_assets(
     State(state): State<AppState>,
     Extension(claims): Extension<UserClaims>,
-    Json(payload): Json<BulkCreateAssetRequest>,
+    Json(mut payload): Json<BulkCreateAssetRequest>,
 ) -> Result<impl IntoResponse, AppError> {
+    for asset in payload.assets.iter_mut() {
+        assign_organization(&state, &claims, asset).await?;
+    }
+
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
@@ -121,10 +117,11 @@ pub async fn bulk_create_assets(
 
 pub async fn update_asset(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateAssetRequest>,
 ) -> Result<Json<ApiResponse<Asset>>, AppError> {
-    let asset = state.asset_service.update(id, payload).await?;
+    let asset = state.asset_service.update(id, payload, &claims).await?;
     Ok(Json(ApiResponse::success_with_message(
         asset,
         "Asset updated",
@@ -133,8 +130,28 @@ pub async fn update_asset(
 
 pub async fn delete_asset(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<ApiResponse<()>>, AppError> {
-    state.asset_service.delete(id).await?;
+    state.asset_service.delete(id, &claims).await?;
     Ok(Json(ApiResponse::success_with_message((), "Asset deleted")))
 }
+
+/// New assets belong to the active organization unless another organization
+/// within its subtree is given
+async fn assign_organization(
+    state: &AppState,
+    claims: &UserClaims,
+    payload: &mut CreateAssetRequest,
+) -> Result<(), AppError> {
+    match payload.organization_id {
+        Some(org_id) => {
+            state
+                .organization_service
+                .get(org_id, claims.organization_id())
+                .await?;
+        }
+        None => payload.organization_id = claims.organization_id(),
+    }
+    Ok(())
+}
@@ -1,9 +1,20 @@
 //! Auth Handler
 
-use axum::{extract::State, Json};
+use std::net::SocketAddr;
+
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
 use serde::{Deserialize, Serialize};
 
+use crate::api::middleware::client_ip;
 use crate::api::server::AppState;
+use crate::application::dto::{ApiResponse, SwitchOrganizationRequest};
+use crate::application::services::{AuthTokens, ClientInfo, LoginOutcome};
+use crate::domain::entities::{User, UserClaims};
 use crate::shared::errors::AppError;
 
 #[derive(Deserialize)]
@@ -12,10 +23,44 @@ pub struct LoginRequest {
     pub password: String,
 }
 
+#[derive(Deserialize)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Deserialize, Default)]
+pub struct LogoutRequest {
+    /// Sign out of every session of the user, not just this one
+    #[serde(default)]
+    pub all: bool,
+}
+
 #[derive(Serialize)]
 pub struct LoginResponse {
     pub success: bool,
     pub token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    /// Access token lifetime in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_in: Option<i64>,
+    pub user: UserInfo,
+    /// Shown once, when two-factor enrolment completes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovery_codes: Option<Vec<String>>,
+}
+
+/// Password accepted; another step must follow before tokens are issued:
+/// a second factor on `/api/auth/2fa/verify`, or a new password on
+/// `/api/auth/password/change`
+#[derive(Serialize)]
+pub struct LoginChallengeResponse {
+    pub success: bool,
+    pub two_factor_required: bool,
+    /// The user has to enrol first (`/api/auth/2fa/setup`)
+    pub setup_required: bool,
+    pub password_change_required: bool,
+    pub challenge_token: String,
     pub user: UserInfo,
 }
 
@@ -27,23 +72,131 @@ pub struct UserInfo {
     pub role: String,
 }
 
+impl From<User> for UserInfo {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id.to_string(),
+            email: user.email,
+            name: user.name,
+            role: user.role,
+        }
+    }
+}
+
+impl LoginResponse {
+    pub(crate) fn with_tokens(user: User, tokens: AuthTokens) -> Self {
+        Self {
+            success: true,
+            token: tokens.access_token,
+            refresh_token: Some(tokens.refresh_token),
+            expires_in: Some(tokens.expires_in),
+            user: user.into(),
+            recovery_codes: None,
+        }
+    }
+}
+
+pub(crate) fn client_info(
+    headers: &HeaderMap,
+    peer: Option<ConnectInfo<SocketAddr>>,
+) -> ClientInfo {
+    ClientInfo {
+        ip: client_ip(headers, peer.map(|ConnectInfo(addr)| addr)),
+        user_agent: headers
+            .get(header::USER_AGENT)
+            .and_then(|h| h.to_str().ok())
+            .map(str::to_string),
+    }
+}
+
 pub async fn login(
     State(state): State<AppState>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
     Json(payload): Json<LoginRequest>,
+) -> Result<Response, AppError> {
+    let client = client_info(&headers, peer);
+    let outcome = state
+        .auth_service
+        .login(&payload.email, &payload.password, &client)
+        .await?;
+
+    Ok(login_outcome_response(outcome))
+}
+
+pub(crate) fn login_outcome_response(outcome: LoginOutcome) -> Response {
+    match outcome {
+        LoginOutcome::Authenticated(user, tokens) => {
+            Json(LoginResponse::with_tokens(user, tokens)).into_response()
+        }
+        LoginOutcome::TwoFactorRequired {
+            user,
+            challenge_token,
+            setup_required,
+        } => Json(LoginChallengeResponse {
+            success: true,
+            two_factor_required: true,
+            setup_required,
+            password_change_required: false,
+            challenge_token,
+            user: user.into(),
+        })
+        .into_response(),
+        LoginOutcome::PasswordChangeRequired {
+            user,
+            challenge_token,
+        } => Json(LoginChallengeResponse {
+            success: true,
+            two_factor_required: false,
+            setup_required: false,
+            password_change_required: true,
+            challenge_token,
+            user: user.into(),
+        })
+        .into_response(),
+    }
+}
+
+/// Exchange a refresh token for a new access/refresh pair
+pub async fn refresh_token(
+    State(state): State<AppState>,
+    Json(payload): Json<RefreshRequest>,
+) -> Result<Json<LoginResponse>, AppError> {
+    let (user, tokens) = state.auth_service.refresh(&payload.refresh_token).await?;
+
+    Ok(Json(LoginResponse::with_tokens(user, tokens)))
+}
+
+/// Revoke the current session (or all sessions of the user)
+pub async fn logout(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    payload: Option<Json<LogoutRequest>>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    let Json(payload) = payload.unwrap_or_default();
+    state.auth_service.logout(&claims, payload.all).await?;
+
+    Ok(Json(ApiResponse::success_with_message((), "Logged out")))
+}
+
+/// Re-issue the token with another active organization
+pub async fn switch_organization(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Json(payload): Json<SwitchOrganizationRequest>,
 ) -> Result<Json<LoginResponse>, AppError> {
     let (user, token) = state
         .auth_service
-        .login(&payload.email, &payload.password)
+        .switch_organization(&claims, payload.organization_id)
         .await?;
 
+    // The session and its refresh token stay the same
     Ok(Json(LoginResponse {
         success: true,
         token,
-        user: UserInfo {
-            id: user.id.to_string(),
-            email: user.email,
-            name: user.name,
-            role: user.role,
-        },
+        refresh_token: None,
+        expires_in: None,
+        user: user.into(),
+        recovery_codes: None,
     }))
 }
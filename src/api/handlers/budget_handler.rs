@@ -0,0 +1,113 @@
+//! Budget Handlers
+//!
+//! Budget lines of the cost centers in the caller's active organization, their
+//! ledger and the budget-vs-actual report.
+
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use uuid::Uuid;
+
+use crate::api::server::AppState;
+use crate::application::dto::{
+    ApiResponse, BudgetQueryParams, BudgetReport, CreateBudgetLineRequest, UpdateBudgetLineRequest,
+};
+use crate::domain::entities::{BudgetEntry, BudgetLine, UserClaims};
+use crate::shared::errors::AppError;
+
+pub async fn list_budget_lines(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Query(params): Query<BudgetQueryParams>,
+) -> Result<Json<ApiResponse<Vec<BudgetLine>>>, AppError> {
+    let lines = state
+        .budget_service
+        .list(claims.organization_id(), params.fiscal_year)
+        .await?;
+    Ok(Json(ApiResponse::success(lines)))
+}
+
+pub async fn create_budget_line(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Json(payload): Json<CreateBudgetLineRequest>,
+) -> Result<Json<ApiResponse<BudgetLine>>, AppError> {
+    let line = state
+        .budget_service
+        .create(payload, claims.organization_id(), claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        line,
+        "Budget line created",
+    )))
+}
+
+pub async fn get_budget_line(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<BudgetLine>>, AppError> {
+    let line = state
+        .budget_service
+        .get(id, claims.organization_id())
+        .await?;
+    Ok(Json(ApiResponse::success(line)))
+}
+
+pub async fn update_budget_line(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateBudgetLineRequest>,
+) -> Result<Json<ApiResponse<BudgetLine>>, AppError> {
+    let line = state
+        .budget_service
+        .update(id, payload, claims.organization_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        line,
+        "Budget line updated",
+    )))
+}
+
+pub async fn delete_budget_line(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state
+        .budget_service
+        .delete(id, claims.organization_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        "Budget line deleted",
+    )))
+}
+
+/// Commitments and actual spend booked against a line
+pub async fn list_budget_entries(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<BudgetEntry>>>, AppError> {
+    let entries = state
+        .budget_service
+        .entries(id, claims.organization_id())
+        .await?;
+    Ok(Json(ApiResponse::success(entries)))
+}
+
+/// Budget vs. committed and actual spend per cost center line
+pub async fn budget_report(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Query(params): Query<BudgetQueryParams>,
+) -> Result<Json<ApiResponse<BudgetReport>>, AppError> {
+    let report = state
+        .budget_service
+        .report(claims.organization_id(), params.fiscal_year)
+        .await?;
+    Ok(Json(ApiResponse::success(report)))
+}
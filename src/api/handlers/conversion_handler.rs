@@ -1,5 +1,6 @@
 use crate::api::server::AppState;
 use crate::application::dto::{ApiResponse, CreateConversionRequest, ExecuteConversionRequest};
+use crate::application::services::BudgetedResult;
 use crate::domain::entities::user::UserClaims;
 use crate::shared::errors::AppError;
 use axum::{
@@ -101,19 +102,28 @@ pub async fn approve_conversion(
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))?;
 
-    let conversion = state
+    match state
         .conversion_service
         .approve_request(id, user_id)
-        .await?;
-
-    Ok((
-        StatusCode::OK,
-        Json(ApiResponse::success_with_message(
-            conversion,
-            "Conversion request approved",
-        )),
-    )
-        .into_response())
+        .await?
+    {
+        BudgetedResult::Applied(conversion) => Ok((
+            StatusCode::OK,
+            Json(ApiResponse::success_with_message(
+                conversion,
+                "Conversion request approved",
+            )),
+        )
+            .into_response()),
+        BudgetedResult::PendingApproval(request) => Ok((
+            StatusCode::ACCEPTED,
+            Json(ApiResponse::success_with_message(
+                request,
+                "Conversion exceeds its budget; overrun submitted for approval",
+            )),
+        )
+            .into_response()),
+    }
 }
 
 /// Execute a conversion request
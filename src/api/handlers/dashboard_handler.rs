@@ -1,12 +1,13 @@
 //! Dashboard Handler - Analytics & Statistics
 
-use axum::{extract::State, Json};
+use axum::{extract::State, Extension, Json};
 use rust_decimal::Decimal;
 use serde::Serialize;
 
 use uuid::Uuid;
 
 use crate::api::server::AppState;
+use crate::domain::entities::UserClaims;
 use crate::shared::errors::AppError;
 
 #[derive(Serialize)]
@@ -67,20 +68,31 @@ pub struct RecentActivity {
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Asset filter of the active organization's subtree (`$1`)
+const ASSET_SCOPE: &str =
+    "($1::uuid IS NULL OR organization_id IN (SELECT organization_subtree($1)))";
+/// Filter on `asset_id` of loans, work orders and alerts (`$1`)
+const ASSET_ID_SCOPE: &str = "($1::uuid IS NULL OR asset_in_organization(asset_id, $1))";
+
 pub async fn get_dashboard_stats(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
 ) -> Result<Json<DashboardStats>, AppError> {
     let pool = state.pool.clone();
+    let org_scope = claims.organization_id();
 
     // Asset stats
-    let asset_total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM assets")
-        .fetch_one(&pool)
-        .await
-        .map_err(db_error)?;
+    let asset_total: (i64,) =
+        sqlx::query_as(&format!("SELECT COUNT(*) FROM assets WHERE {ASSET_SCOPE}"))
+            .bind(org_scope)
+            .fetch_one(&pool)
+            .await
+            .map_err(db_error)?;
 
-    let asset_by_status: Vec<StatusCount> = sqlx::query_as::<_, (String, i64)>(
-        "SELECT status, COUNT(*) FROM assets GROUP BY status ORDER BY COUNT(*) DESC",
-    )
+    let asset_by_status: Vec<StatusCount> = sqlx::query_as::<_, (String, i64)>(&format!(
+        "SELECT status, COUNT(*) FROM assets WHERE {ASSET_SCOPE} GROUP BY status ORDER BY COUNT(*) DESC"
+    ))
+    .bind(org_scope)
     .fetch_all(&pool)
     .await
     .map_err(db_error)?
@@ -88,51 +100,61 @@ pub async fn get_dashboard_stats(
     .map(|(status, count)| StatusCount { status, count })
     .collect();
 
-    let total_value: (Decimal,) =
-        sqlx::query_as("SELECT COALESCE(SUM(purchase_price), 0) FROM assets")
-            .fetch_one(&pool)
-            .await
-            .map_err(db_error)?;
+    let total_value: (Decimal,) = sqlx::query_as(&format!(
+        "SELECT COALESCE(SUM(purchase_price), 0) FROM assets WHERE {ASSET_SCOPE}"
+    ))
+    .bind(org_scope)
+    .fetch_one(&pool)
+    .await
+    .map_err(db_error)?;
 
     // Maintenance stats
-    let maintenance_pending: (i64,) =
-        sqlx::query_as("SELECT COUNT(*) FROM maintenance_work_orders WHERE status = 'pending'")
-            .fetch_one(&pool)
-            .await
-            .map_err(db_error)?;
+    let maintenance_pending: (i64,) = sqlx::query_as(&format!(
+        "SELECT COUNT(*) FROM maintenance_work_orders WHERE status = 'pending' AND {ASSET_ID_SCOPE}"
+    ))
+    .bind(org_scope)
+    .fetch_one(&pool)
+    .await
+    .map_err(db_error)?;
 
-    let maintenance_overdue: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM maintenance_work_orders WHERE due_date < CURRENT_DATE AND status NOT IN ('completed', 'cancelled')"
-    ).fetch_one(&pool).await.map_err(db_error)?;
+    let maintenance_overdue: (i64,) = sqlx::query_as(&format!(
+        "SELECT COUNT(*) FROM maintenance_work_orders WHERE due_date < CURRENT_DATE AND status NOT IN ('completed', 'cancelled') AND {ASSET_ID_SCOPE}"
+    )).bind(org_scope).fetch_one(&pool).await.map_err(db_error)?;
 
     // Loan stats
-    let loans_active: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM asset_loans WHERE status IN ('checked_out', 'in_use')",
-    )
+    let loans_active: (i64,) = sqlx::query_as(&format!(
+        "SELECT COUNT(*) FROM asset_loans WHERE status IN ('checked_out', 'in_use') AND {ASSET_ID_SCOPE}"
+    ))
+    .bind(org_scope)
     .fetch_one(&pool)
     .await
     .map_err(db_error)?;
 
-    let loans_overdue: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM asset_loans WHERE expected_return_date < CURRENT_DATE AND actual_return_date IS NULL AND status NOT IN ('returned', 'lost')"
-    ).fetch_one(&pool).await.map_err(db_error)?;
+    let loans_overdue: (i64,) = sqlx::query_as(&format!(
+        "SELECT COUNT(*) FROM asset_loans WHERE expected_return_date < CURRENT_DATE AND actual_return_date IS NULL AND status NOT IN ('returned', 'lost') AND {ASSET_ID_SCOPE}"
+    )).bind(org_scope).fetch_one(&pool).await.map_err(db_error)?;
 
-    let loans_pending: (i64,) =
-        sqlx::query_as("SELECT COUNT(*) FROM asset_loans WHERE status = 'requested'")
-            .fetch_one(&pool)
-            .await
-            .map_err(db_error)?;
+    let loans_pending: (i64,) = sqlx::query_as(&format!(
+        "SELECT COUNT(*) FROM asset_loans WHERE status = 'requested' AND {ASSET_ID_SCOPE}"
+    ))
+    .bind(org_scope)
+    .fetch_one(&pool)
+    .await
+    .map_err(db_error)?;
 
     // Alert stats
-    let alerts_active: (i64,) =
-        sqlx::query_as("SELECT COUNT(*) FROM sensor_alerts WHERE status = 'active'")
-            .fetch_one(&pool)
-            .await
-            .map_err(db_error)?;
+    let alerts_active: (i64,) = sqlx::query_as(&format!(
+        "SELECT COUNT(*) FROM sensor_alerts WHERE status = 'active' AND {ASSET_ID_SCOPE}"
+    ))
+    .bind(org_scope)
+    .fetch_one(&pool)
+    .await
+    .map_err(db_error)?;
 
-    let alerts_critical: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM sensor_alerts WHERE status = 'active' AND severity = 'critical'",
-    )
+    let alerts_critical: (i64,) = sqlx::query_as(&format!(
+        "SELECT COUNT(*) FROM sensor_alerts WHERE status = 'active' AND severity = 'critical' AND {ASSET_ID_SCOPE}"
+    ))
+    .bind(org_scope)
     .fetch_one(&pool)
     .await
     .map_err(db_error)?;
@@ -147,11 +169,13 @@ pub async fn get_dashboard_stats(
             COALESCE(SUM(a.purchase_price), 0) as value
         FROM assets a
         JOIN categories c ON a.category_id = c.id
+        WHERE $1::uuid IS NULL OR a.organization_id IN (SELECT organization_subtree($1))
         GROUP BY c.name
         ORDER BY value DESC
         LIMIT 5
         "#,
         )
+        .bind(org_scope)
         .fetch_all(&pool)
         .await
         .map_err(db_error)?
@@ -226,27 +250,26 @@ pub async fn get_recent_activities(
 pub struct DepreciationSummary {
     pub total_original_cost: Decimal,
     pub total_accumulated_depreciation: Decimal,
+    pub total_valuation_adjustment: Decimal,
     pub total_book_value: Decimal,
+    pub depreciation_this_month: Decimal,
 }
 
 pub async fn get_depreciation_summary(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
 ) -> Result<Json<DepreciationSummary>, AppError> {
-    let pool = state.pool.clone();
-    let totals: (Decimal,) = sqlx::query_as(
-        "SELECT COALESCE(SUM(purchase_price), 0) FROM assets WHERE purchase_price IS NOT NULL",
-    )
-    .fetch_one(&pool)
-    .await
-    .map_err(db_error)?;
-
-    // Simplified depreciation calculation
-    let depreciation = totals.0 * Decimal::new(20, 2); // 20% depreciation estimate
+    let totals = state
+        .depreciation_service
+        .totals(chrono::Utc::now().date_naive(), claims.organization_id())
+        .await?;
 
     Ok(Json(DepreciationSummary {
-        total_original_cost: totals.0,
-        total_accumulated_depreciation: depreciation,
-        total_book_value: totals.0 - depreciation,
+        total_original_cost: totals.total_original_cost,
+        total_accumulated_depreciation: totals.total_accumulated_depreciation,
+        total_valuation_adjustment: totals.total_valuation_adjustment,
+        total_book_value: totals.total_book_value,
+        depreciation_this_month: totals.period_depreciation,
     }))
 }
 
@@ -6,14 +6,19 @@ use axum::{
     extract::State,
     http::header,
     response::{IntoResponse, Response},
+    Extension,
 };
 
 use crate::api::server::AppState;
+use crate::domain::entities::UserClaims;
 use crate::shared::errors::AppError;
 
 /// Export assets as CSV
-pub async fn export_assets(State(state): State<AppState>) -> Result<Response, AppError> {
-    let csv_data = state.data_service.export_assets_csv().await?;
+pub async fn export_assets(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+) -> Result<Response, AppError> {
+    let csv_data = state.data_service.export_assets_csv(&claims).await?;
 
     let headers = [
         (header::CONTENT_TYPE, "text/csv"),
@@ -0,0 +1,126 @@
+//! Depreciation Handlers
+
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use chrono::{NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::api::server::AppState;
+use crate::application::dto::{
+    ApiResponse, DepreciationAsOfParams, ReopenDepreciationPeriodRequest,
+};
+use crate::application::services::{DepreciationCloseReport, DepreciationRun, DepreciationTotals};
+use crate::domain::entities::{
+    AssetDepreciationPosition, DepreciationClosing, DepreciationSchedule, UserClaims,
+};
+use crate::shared::errors::AppError;
+
+/// Monthly schedule of one asset
+pub async fn get_asset_depreciation_schedule(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<DepreciationSchedule>>>, AppError> {
+    let schedule = state.depreciation_service.get_schedule(id).await?;
+    Ok(Json(ApiResponse::success(schedule)))
+}
+
+pub async fn recalculate_asset_depreciation(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<DepreciationSchedule>>>, AppError> {
+    let schedule = state.depreciation_service.recalculate_asset(id).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        schedule,
+        "Depreciation schedule recalculated",
+    )))
+}
+
+/// Rebuild every asset's schedule
+pub async fn recalculate_all_depreciation(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<DepreciationRun>>, AppError> {
+    let run = state.depreciation_service.recalculate_all().await?;
+    Ok(Json(ApiResponse::success(run)))
+}
+
+/// Per-asset book values at a date
+pub async fn list_depreciation_positions(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Query(params): Query<DepreciationAsOfParams>,
+) -> Result<Json<ApiResponse<Vec<AssetDepreciationPosition>>>, AppError> {
+    let as_of = params.as_of.unwrap_or_else(|| Utc::now().date_naive());
+    let positions = state
+        .depreciation_service
+        .positions(as_of, claims.organization_id())
+        .await?;
+    Ok(Json(ApiResponse::success(positions)))
+}
+
+pub async fn get_depreciation_totals(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Query(params): Query<DepreciationAsOfParams>,
+) -> Result<Json<ApiResponse<DepreciationTotals>>, AppError> {
+    let as_of = params.as_of.unwrap_or_else(|| Utc::now().date_naive());
+    let totals = state
+        .depreciation_service
+        .totals(as_of, claims.organization_id())
+        .await?;
+    Ok(Json(ApiResponse::success(totals)))
+}
+
+// ==================== PERIOD CLOSE ====================
+
+pub async fn list_depreciation_periods(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<DepreciationClosing>>>, AppError> {
+    let closings = state.depreciation_service.list_closings().await?;
+    Ok(Json(ApiResponse::success(closings)))
+}
+
+/// Post and lock depreciation for the month containing `period`
+pub async fn close_depreciation_period(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(period): Path<NaiveDate>,
+) -> Result<Json<ApiResponse<DepreciationClosing>>, AppError> {
+    let closing = state
+        .depreciation_service
+        .close_period(period, Some(claims.user_id()))
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        closing,
+        "Depreciation period closed",
+    )))
+}
+
+/// Submit a reopen for approval (applied at once when no approval is required)
+pub async fn reopen_depreciation_period(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(period): Path<NaiveDate>,
+    Json(payload): Json<ReopenDepreciationPeriodRequest>,
+) -> Result<Json<ApiResponse<DepreciationClosing>>, AppError> {
+    let closing = state
+        .depreciation_service
+        .request_reopen(period, payload.reason, claims.user_id())
+        .await?;
+    let message = if closing.is_closed() {
+        "Reopen submitted for approval"
+    } else {
+        "Depreciation period reopened"
+    };
+    Ok(Json(ApiResponse::success_with_message(closing, message)))
+}
+
+/// Totals posted at close by category, department and location
+pub async fn get_depreciation_close_report(
+    State(state): State<AppState>,
+    Path(period): Path<NaiveDate>,
+) -> Result<Json<ApiResponse<DepreciationCloseReport>>, AppError> {
+    let report = state.depreciation_service.close_report(period).await?;
+    Ok(Json(ApiResponse::success(report)))
+}
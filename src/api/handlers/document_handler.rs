@@ -0,0 +1,183 @@
+//! Asset Document Handlers
+
+use axum::{
+    extract::{Multipart, Path, Query, State},
+    http::header,
+    response::IntoResponse,
+    Extension, Json,
+};
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::api::handlers::upload_handler::MAX_UPLOAD_SIZE;
+use crate::api::server::AppState;
+use crate::application::dto::{
+    ApiResponse, DocumentListParams, ExpiringDocumentsParams, UploadDocumentRequest,
+};
+use crate::domain::entities::{AssetDocument, UserClaims, DOCUMENT_EXPIRY_REMINDER_DAYS};
+use crate::shared::errors::AppError;
+
+/// Current documents of an asset (`?include_history=true` adds older versions)
+pub async fn list_asset_documents(
+    State(state): State<AppState>,
+    Path(asset_id): Path<Uuid>,
+    Query(params): Query<DocumentListParams>,
+) -> Result<Json<ApiResponse<Vec<AssetDocument>>>, AppError> {
+    let documents = state
+        .document_service
+        .list_by_asset(asset_id, params.include_history)
+        .await?;
+    Ok(Json(ApiResponse::success(documents)))
+}
+
+/// Attach a file to an asset (multipart: file, name, type, expiry_date, notes)
+pub async fn upload_asset_document(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(asset_id): Path<Uuid>,
+    multipart: Multipart,
+) -> Result<Json<ApiResponse<AssetDocument>>, AppError> {
+    let request = read_upload(multipart).await?;
+    let document = state
+        .document_service
+        .upload(asset_id, request, claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        document,
+        "Document uploaded",
+    )))
+}
+
+pub async fn get_asset_document(
+    State(state): State<AppState>,
+    Path((asset_id, id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<AssetDocument>>, AppError> {
+    let document = state.document_service.get(asset_id, id).await?;
+    Ok(Json(ApiResponse::success(document)))
+}
+
+/// Delete a document with all of its versions
+pub async fn delete_asset_document(
+    State(state): State<AppState>,
+    Path((asset_id, id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state.document_service.delete(asset_id, id).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        "Document deleted",
+    )))
+}
+
+pub async fn list_document_versions(
+    State(state): State<AppState>,
+    Path((asset_id, id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<Vec<AssetDocument>>>, AppError> {
+    let versions = state.document_service.list_versions(asset_id, id).await?;
+    Ok(Json(ApiResponse::success(versions)))
+}
+
+/// Replace the current version; omitted fields carry over from it
+pub async fn upload_document_version(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path((asset_id, id)): Path<(Uuid, Uuid)>,
+    multipart: Multipart,
+) -> Result<Json<ApiResponse<AssetDocument>>, AppError> {
+    let request = read_upload(multipart).await?;
+    let document = state
+        .document_service
+        .replace(asset_id, id, request, claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        document,
+        "New document version uploaded",
+    )))
+}
+
+pub async fn download_asset_document(
+    State(state): State<AppState>,
+    Path((asset_id, id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, AppError> {
+    let (document, data) = state.document_service.download(asset_id, id).await?;
+    let content_type = document
+        .mime_type
+        .clone()
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+    let disposition = format!(
+        "attachment; filename=\"{}\"",
+        document
+            .download_name()
+            .replace(['"', '\\', '\r', '\n'], "_")
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        data,
+    ))
+}
+
+/// Current documents expiring within `days` (default 30)
+pub async fn list_expiring_documents(
+    State(state): State<AppState>,
+    Query(params): Query<ExpiringDocumentsParams>,
+) -> Result<Json<ApiResponse<Vec<AssetDocument>>>, AppError> {
+    let documents = state
+        .document_service
+        .list_expiring(params.days.unwrap_or(DOCUMENT_EXPIRY_REMINDER_DAYS))
+        .await?;
+    Ok(Json(ApiResponse::success(documents)))
+}
+
+async fn read_upload(mut multipart: Multipart) -> Result<UploadDocumentRequest, AppError> {
+    let mut request = UploadDocumentRequest::default();
+    let mut has_file = false;
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(e.to_string()))?
+    {
+        let name = field.name().unwrap_or("").to_string();
+        if name == "file" {
+            request.original_name = field.file_name().map(|n| n.to_string());
+            request.mime_type = field.content_type().map(|t| t.to_string());
+            let data = field
+                .bytes()
+                .await
+                .map_err(|e| AppError::BadRequest(e.to_string()))?;
+            if data.len() as u64 > MAX_UPLOAD_SIZE {
+                return Err(AppError::BadRequest("File too large".to_string()));
+            }
+            request.data = data.to_vec();
+            has_file = true;
+            continue;
+        }
+
+        let value = field
+            .text()
+            .await
+            .map_err(|e| AppError::BadRequest(e.to_string()))?;
+        let value = Some(value.trim().to_string()).filter(|v| !v.is_empty());
+        match name.as_str() {
+            "name" => request.name = value,
+            "type" => request.type_ = value,
+            "notes" => request.notes = value,
+            "expiry_date" => {
+                request.expiry_date = value
+                    .map(|v| NaiveDate::parse_from_str(&v, "%Y-%m-%d"))
+                    .transpose()
+                    .map_err(|_| {
+                        AppError::BadRequest("expiry_date must be YYYY-MM-DD".to_string())
+                    })?
+            }
+            _ => {}
+        }
+    }
+
+    if !has_file {
+        return Err(AppError::BadRequest("No file field found".to_string()));
+    }
+    Ok(request)
+}
@@ -0,0 +1,49 @@
+//! Document Number Handlers
+//!
+//! Admin endpoints for the formats of loan, work order, rental, client,
+//! conversion and invoice numbers.
+
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+
+use crate::api::server::AppState;
+use crate::application::dto::{ApiResponse, UpdateDocumentNumberFormatRequest};
+use crate::domain::entities::{DocumentNumberFormat, UserClaims};
+use crate::shared::errors::AppError;
+
+pub async fn list_document_number_formats(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<DocumentNumberFormat>>>, AppError> {
+    let formats = state.document_number_service.list_formats().await?;
+    Ok(Json(ApiResponse::success(formats)))
+}
+
+pub async fn get_document_number_format(
+    State(state): State<AppState>,
+    Path(document_type): Path<String>,
+) -> Result<Json<ApiResponse<DocumentNumberFormat>>, AppError> {
+    let format = state
+        .document_number_service
+        .get_format(&document_type)
+        .await?;
+    Ok(Json(ApiResponse::success(format)))
+}
+
+/// Change how future numbers of a document type are built
+pub async fn update_document_number_format(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(document_type): Path<String>,
+    Json(payload): Json<UpdateDocumentNumberFormatRequest>,
+) -> Result<Json<ApiResponse<DocumentNumberFormat>>, AppError> {
+    let format = state
+        .document_number_service
+        .update_format(&document_type, payload, claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        format,
+        "Document number format updated",
+    )))
+}
@@ -1,7 +1,7 @@
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
 use uuid::Uuid;
 
@@ -9,7 +9,7 @@ use crate::api::server::AppState;
 use crate::application::dto::{
     ApiResponse, CreateEmployeeRequest, PaginationParams, UpdateEmployeeRequest,
 };
-use crate::domain::entities::Employee;
+use crate::domain::entities::{Employee, UserClaims};
 use crate::shared::errors::AppError;
 
 pub async fn list_employees(
@@ -70,10 +70,14 @@ pub async fn delete_employee(
 
 pub async fn create_employee_user(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
     Path(id): Path<Uuid>,
     Json(payload): Json<crate::application::dto::CreateEmployeeUserRequest>,
 ) -> Result<Json<ApiResponse<Employee>>, AppError> {
-    let employee = state.employee_service.create_user(id, payload).await?;
+    let employee = state
+        .employee_service
+        .create_user(id, payload, claims.organization_id())
+        .await?;
     Ok(Json(ApiResponse::success_with_message(
         employee,
         "User account created for employee",
@@ -0,0 +1,138 @@
+//! Insurance Handlers
+
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use uuid::Uuid;
+
+use crate::api::server::AppState;
+use crate::application::dto::{
+    ApiResponse, CreateInsuranceClaimRequest, CreateInsuranceRequest, ExpiringInsuranceParams,
+    InsuranceCoverageParams, UpdateInsuranceClaimStatusRequest, UpdateInsuranceRequest,
+};
+use crate::domain::entities::{
+    Insurance, InsuranceClaim, InsuranceCoverageLine, LapsedInsuranceAsset, UserClaims,
+};
+use crate::shared::errors::AppError;
+
+/// Policies of one asset, latest first
+pub async fn list_asset_insurances(
+    State(state): State<AppState>,
+    Path(asset_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<Insurance>>>, AppError> {
+    let policies = state.insurance_service.list_by_asset(asset_id).await?;
+    Ok(Json(ApiResponse::success(policies)))
+}
+
+pub async fn create_asset_insurance(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(asset_id): Path<Uuid>,
+    Json(payload): Json<CreateInsuranceRequest>,
+) -> Result<Json<ApiResponse<Insurance>>, AppError> {
+    let policy = state
+        .insurance_service
+        .create(asset_id, payload, claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        policy,
+        "Insurance policy created",
+    )))
+}
+
+pub async fn get_insurance(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Insurance>>, AppError> {
+    let policy = state.insurance_service.get_by_id(id).await?;
+    Ok(Json(ApiResponse::success(policy)))
+}
+
+pub async fn update_insurance(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateInsuranceRequest>,
+) -> Result<Json<ApiResponse<Insurance>>, AppError> {
+    let policy = state.insurance_service.update(id, payload).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        policy,
+        "Insurance policy updated",
+    )))
+}
+
+pub async fn delete_insurance(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state.insurance_service.delete(id).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        "Insurance policy deleted",
+    )))
+}
+
+/// Active policies ending soon
+pub async fn list_expiring_insurances(
+    State(state): State<AppState>,
+    Query(params): Query<ExpiringInsuranceParams>,
+) -> Result<Json<ApiResponse<Vec<Insurance>>>, AppError> {
+    let policies = state.insurance_service.list_expiring(params.days).await?;
+    Ok(Json(ApiResponse::success(policies)))
+}
+
+/// Assets flagged with lapsed coverage
+pub async fn list_lapsed_insurance_assets(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<LapsedInsuranceAsset>>>, AppError> {
+    let assets = state.insurance_service.list_lapsed().await?;
+    Ok(Json(ApiResponse::success(assets)))
+}
+
+/// Insured value versus book value per category
+pub async fn get_insurance_coverage_report(
+    State(state): State<AppState>,
+    Query(params): Query<InsuranceCoverageParams>,
+) -> Result<Json<ApiResponse<Vec<InsuranceCoverageLine>>>, AppError> {
+    let report = state
+        .insurance_service
+        .coverage_report(params.as_of)
+        .await?;
+    Ok(Json(ApiResponse::success(report)))
+}
+
+pub async fn list_insurance_claims(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<InsuranceClaim>>>, AppError> {
+    let claims = state.insurance_service.list_claims(id).await?;
+    Ok(Json(ApiResponse::success(claims)))
+}
+
+pub async fn create_insurance_claim(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CreateInsuranceClaimRequest>,
+) -> Result<Json<ApiResponse<InsuranceClaim>>, AppError> {
+    let claim = state
+        .insurance_service
+        .create_claim(id, payload, claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        claim,
+        "Insurance claim submitted",
+    )))
+}
+
+pub async fn update_insurance_claim_status(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateInsuranceClaimStatusRequest>,
+) -> Result<Json<ApiResponse<InsuranceClaim>>, AppError> {
+    let claim = state
+        .insurance_service
+        .update_claim_status(id, payload)
+        .await?;
+    Ok(Json(ApiResponse::success(claim)))
+}
@@ -14,7 +14,8 @@ use crate::application::dto::ApiResponse;
 use crate::application::services::lifecycle_service::{
     LifecycleService, StateInfo, StateInfoWithApproval, TransitionRequestResult,
 };
-use crate::domain::entities::{LifecycleHistory, UserClaims as Claims};
+use crate::application::services::ApprovalSubmission;
+use crate::domain::entities::{LifecycleHistory, UserClaims as Claims, LIFECYCLE_RESOURCE_TYPE};
 use crate::shared::errors::AppError;
 
 #[derive(Deserialize)]
@@ -61,30 +62,45 @@ pub async fn request_transition(
         TransitionRequestResult::RequiresApproval {
             from_state,
             to_state,
+            action_type,
+            category_id,
             data,
             requested_by,
         } => {
             // Create approval request
-            let approval_request = state
+            let submission = state
                 .approval_service
                 .create_request(
-                    "lifecycle_transition",
+                    LIFECYCLE_RESOURCE_TYPE,
                     asset_id,
-                    &format!("transition_to_{}", to_state),
+                    &action_type,
                     requested_by,
                     Some(data),
+                    Some(category_id),
                 )
                 .await?;
 
-            Ok(Json(ApiResponse::success(
-                TransitionResponse::ApprovalCreated {
-                    approval_request_id: approval_request.id,
-                    message: format!(
-                        "Transition from '{}' to '{}' requires approval. Request created.",
-                        from_state, to_state
-                    ),
-                },
-            )))
+            match submission {
+                ApprovalSubmission::Pending(approval_request) => Ok(Json(ApiResponse::success(
+                    TransitionResponse::ApprovalCreated {
+                        approval_request_id: approval_request.id,
+                        message: format!(
+                            "Transition from '{}' to '{}' requires approval. Request created.",
+                            from_state, to_state
+                        ),
+                    },
+                ))),
+                // Workflow configured without approval: execute right away
+                ApprovalSubmission::NotRequired => {
+                    let history = state
+                        .lifecycle_service
+                        .transition_asset(asset_id, &to_state, req.reason, Some(requested_by))
+                        .await?;
+                    Ok(Json(ApiResponse::success(TransitionResponse::Executed {
+                        history,
+                    })))
+                }
+            }
         }
     }
 }
@@ -14,28 +14,31 @@ use crate::shared::errors::AppError;
 
 pub async fn list_loans(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
     Query(params): Query<PaginationParams>,
 ) -> Result<Json<Vec<Loan>>, AppError> {
     let loans = state
         .loan_service
-        .list(params.page(), params.per_page())
+        .list(params.page(), params.per_page(), &claims)
         .await?;
     Ok(Json(loans))
 }
 
 pub async fn get_loan(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<Loan>, AppError> {
-    let loan = state.loan_service.get_by_id(id).await?;
+    let loan = state.loan_service.get_in_scope(id, &claims).await?;
     Ok(Json(loan))
 }
 
 pub async fn create_loan(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
     Json(payload): Json<CreateLoanRequest>,
 ) -> Result<(StatusCode, Json<ApiResponse<Loan>>), AppError> {
-    let loan = state.loan_service.create(payload).await?;
+    let loan = state.loan_service.create(payload, claims.user_id()).await?;
     Ok((
         StatusCode::CREATED,
         Json(ApiResponse::success_with_message(loan, "Loan created")),
@@ -47,8 +50,7 @@ pub async fn approve_loan(
     Extension(claims): Extension<UserClaims>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<ApiResponse<Loan>>, AppError> {
-    let approver_id = claims.user_id();
-    let loan = state.loan_service.approve(id, approver_id).await?;
+    let loan = state.loan_service.approve(id, &claims).await?;
     Ok(Json(ApiResponse::success_with_message(
         loan,
         "Loan approved",
@@ -57,8 +59,9 @@ pub async fn approve_loan(
 
 pub async fn list_overdue_loans(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
 ) -> Result<Json<Vec<Loan>>, AppError> {
-    let loans = state.loan_service.list_overdue().await?;
+    let loans = state.loan_service.list_overdue(&claims).await?;
     Ok(Json(loans))
 }
 
@@ -78,10 +81,9 @@ pub async fn checkout_loan(
     Path(id): Path<Uuid>,
     Json(payload): Json<CheckoutRequest>,
 ) -> Result<Json<ApiResponse<Loan>>, AppError> {
-    let checked_out_by = claims.user_id();
     let loan = state
         .loan_service
-        .checkout(id, checked_out_by, &payload.condition)
+        .checkout(id, &payload.condition, &claims)
         .await?;
     Ok(Json(ApiResponse::success_with_message(
         loan,
@@ -95,10 +97,9 @@ pub async fn checkin_loan(
     Path(id): Path<Uuid>,
     Json(payload): Json<CheckoutRequest>,
 ) -> Result<Json<ApiResponse<Loan>>, AppError> {
-    let checked_in_by = claims.user_id();
     let loan = state
         .loan_service
-        .checkin(id, checked_in_by, &payload.condition)
+        .checkin(id, &payload.condition, &claims)
         .await?;
     Ok(Json(ApiResponse::success_with_message(
         loan,
@@ -108,10 +109,14 @@ pub async fn checkin_loan(
 
 pub async fn reject_loan(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
     Path(id): Path<Uuid>,
     Json(payload): Json<RejectRequest>,
 ) -> Result<Json<ApiResponse<Loan>>, AppError> {
-    let loan = state.loan_service.reject(id, payload.reason).await?;
+    let loan = state
+        .loan_service
+        .reject(id, payload.reason, &claims)
+        .await?;
     Ok(Json(ApiResponse::success_with_message(
         loan,
         "Loan rejected",
@@ -120,8 +125,9 @@ pub async fn reject_loan(
 
 pub async fn list_my_loans(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
     Path(user_id): Path<Uuid>,
 ) -> Result<Json<Vec<Loan>>, AppError> {
-    let loans = state.loan_service.list_by_user(user_id).await?;
+    let loans = state.loan_service.list_by_user(user_id, &claims).await?;
     Ok(Json(loans))
 }
@@ -12,23 +12,32 @@ use uuid::Uuid;
 
 use crate::api::server::AppState;
 use crate::application::dto::UpdateAssetRequest;
-use crate::domain::entities::{Loan, UserClaims};
+use crate::domain::entities::{Loan, TenantResource, UserClaims};
 use crate::domain::errors::DomainError;
 use crate::shared::errors::AppError;
 
 /// Get asset by code (QR Scan)
 pub async fn scan_asset(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
     Path(code): Path<String>,
 ) -> Result<Response, AppError> {
-    // Try to find by code first via service
-    if let Ok(asset) = state.asset_service.get_by_code(&code).await {
-        return Ok(Json(asset).into_response());
-    }
+    // Try to find by code first via service, then by UUID
+    let asset = match state.asset_service.get_by_code(&code).await {
+        Ok(asset) => Some(asset),
+        Err(_) => match Uuid::parse_str(&code) {
+            Ok(id) => state.asset_service.get_by_id(id).await.ok(),
+            Err(_) => None,
+        },
+    };
 
-    // If matches UUID format, try get_by_id
-    if let Ok(id) = Uuid::parse_str(&code) {
-        if let Ok(asset) = state.asset_service.get_by_id(id).await {
+    // Assets of other organizations are reported as not found
+    if let Some(asset) = asset {
+        let visible = state
+            .organization_service
+            .resource_visible(TenantResource::Asset, asset.id, claims.organization_id())
+            .await?;
+        if visible {
             return Ok(Json(asset).into_response());
         }
     }
@@ -50,7 +59,7 @@ pub async fn my_loans(
 ) -> Result<Response, AppError> {
     let user_id = Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::Unauthorized("Invalid user ID".to_string()))?;
-    let loans = state.loan_service.list_by_user(user_id).await?;
+    let loans = state.loan_service.list_by_user(user_id, &claims).await?;
 
     // Split into active and history
     let (active, history): (Vec<Loan>, Vec<Loan>) = loans.into_iter().partition(|l| {
@@ -76,7 +85,7 @@ pub struct AuditRequest {
 /// Submit Audit
 pub async fn audit_asset(
     State(state): State<AppState>,
-    Extension(_claims): Extension<UserClaims>,
+    Extension(claims): Extension<UserClaims>,
     Json(payload): Json<AuditRequest>,
 ) -> Result<Response, AppError> {
     // Reuse AssetService::update to update condition/location
@@ -89,7 +98,7 @@ pub async fn audit_asset(
 
     let asset = state
         .asset_service
-        .update(payload.asset_id, update_req)
+        .update(payload.asset_id, update_req, &claims)
         .await?;
 
     Ok(Json(asset).into_response())
@@ -1,33 +1,48 @@
 //! API Handlers
 
 pub mod analytics_handler;
+pub mod api_key_handler;
 pub mod approval_handler;
+pub mod approval_workflow_handler;
 pub mod asset_handler;
 pub mod audit_handler;
 pub mod auth_handler;
 pub mod billing_handler;
+pub mod budget_handler;
 pub mod category_handler;
 pub mod client_handler;
 pub mod conversion_handler;
 pub mod dashboard_handler;
 pub mod data_handler;
+pub mod depreciation_handler;
+pub mod document_handler;
+pub mod document_number_handler;
 pub mod employee_handler;
 pub mod health_handler;
+pub mod insurance_handler;
 pub mod lifecycle_handler;
 pub mod loan_handler;
 pub mod lookup_handler;
 
 pub mod mobile_handler;
 pub mod notification_handler;
+pub mod notification_template_handler;
 pub mod notification_ws;
+pub mod organization_handler;
+pub mod password_handler;
+pub mod preventive_schedule_handler;
 pub mod profile_handler;
 pub mod rbac_handler;
 pub mod rental_handler;
 pub mod report_handler;
 pub mod sensor_handler;
+pub mod specification_handler;
 pub mod timesheet_handler;
+pub mod two_factor_handler;
 pub mod upload_handler;
 pub mod user_handler;
+pub mod valuation_handler;
+pub mod webhook_handler;
 pub mod work_order_handler;
 
 pub use approval_handler::*;
@@ -45,6 +60,7 @@ pub use lookup_handler::*;
 
 pub use mobile_handler::*;
 pub use notification_handler::*;
+pub use preventive_schedule_handler::*;
 pub use profile_handler::*;
 pub use rbac_handler::*;
 pub use rental_handler::*;
@@ -2,13 +2,14 @@
 
 use axum::{
     extract::{Path, Query, State},
-    Json,
+    Extension, Json,
 };
 use serde::Serialize;
 use uuid::Uuid;
 
 use crate::api::server::AppState;
-use crate::application::dto::{ApiResponse, PaginationParams};
+use crate::application::dto::{ApiResponse, PaginationParams, UpdateNotificationPreferenceRequest};
+use crate::domain::entities::{NotificationPreference, UserClaims};
 use crate::infrastructure::repositories::Notification;
 use crate::shared::errors::AppError;
 
@@ -66,3 +67,28 @@ pub async fn mark_all_notifications_as_read(
         &format!("Marked {} as read", count),
     )))
 }
+
+/// GET /api/notifications/preferences - current user's channel preferences
+pub async fn list_my_notification_preferences(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+) -> Result<Json<ApiResponse<Vec<NotificationPreference>>>, AppError> {
+    let preferences = state
+        .notification_service
+        .list_preferences(claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success(preferences)))
+}
+
+/// PUT /api/notifications/preferences - set channels/digest for one event type
+pub async fn update_my_notification_preference(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Json(payload): Json<UpdateNotificationPreferenceRequest>,
+) -> Result<Json<ApiResponse<NotificationPreference>>, AppError> {
+    let preference = state
+        .notification_service
+        .update_preference(claims.user_id(), payload)
+        .await?;
+    Ok(Json(ApiResponse::success(preference)))
+}
@@ -0,0 +1,114 @@
+//! Notification Template Handlers
+//!
+//! Admin endpoints for notification templates, the delivery log and digests.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::api::server::AppState;
+use crate::application::dto::{
+    ApiResponse, CreateNotificationTemplateRequest, NotificationDeliveryParams,
+    PreviewNotificationTemplateRequest, UpdateNotificationTemplateRequest,
+};
+use crate::domain::entities::{NotificationDelivery, NotificationTemplate, RenderedMessage};
+use crate::domain::errors::DomainError;
+use crate::shared::errors::AppError;
+
+pub async fn list_notification_templates(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<NotificationTemplate>>>, AppError> {
+    let templates = state.notification_service.list_templates().await?;
+    Ok(Json(ApiResponse::success(templates)))
+}
+
+pub async fn get_notification_template(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<NotificationTemplate>>, AppError> {
+    let template = state.notification_service.get_template(id).await?;
+    Ok(Json(ApiResponse::success(template)))
+}
+
+pub async fn create_notification_template(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateNotificationTemplateRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<NotificationTemplate>>), AppError> {
+    let template = state.notification_service.create_template(payload).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success_with_message(
+            template,
+            "Notification template created",
+        )),
+    ))
+}
+
+pub async fn update_notification_template(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateNotificationTemplateRequest>,
+) -> Result<Json<ApiResponse<NotificationTemplate>>, AppError> {
+    let template = state
+        .notification_service
+        .update_template(id, payload)
+        .await?;
+    Ok(Json(ApiResponse::success(template)))
+}
+
+pub async fn delete_notification_template(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    if state.notification_service.delete_template(id).await? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(DomainError::not_found("NotificationTemplate", id).into())
+    }
+}
+
+/// Render a template with sample variables
+pub async fn preview_notification_template(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<PreviewNotificationTemplateRequest>,
+) -> Result<Json<ApiResponse<RenderedMessage>>, AppError> {
+    let rendered = state
+        .notification_service
+        .preview_template(id, &payload.variables)
+        .await?;
+    Ok(Json(ApiResponse::success(rendered)))
+}
+
+/// Delivery log, newest first
+pub async fn list_notification_deliveries(
+    State(state): State<AppState>,
+    Query(params): Query<NotificationDeliveryParams>,
+) -> Result<Json<ApiResponse<Vec<NotificationDelivery>>>, AppError> {
+    let deliveries = state.notification_service.list_deliveries(&params).await?;
+    Ok(Json(ApiResponse::success(deliveries)))
+}
+
+/// Retry a failed delivery immediately
+pub async fn retry_notification_delivery(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<NotificationDelivery>>, AppError> {
+    let delivery = state.notification_service.retry_delivery(id).await?;
+    Ok(Json(ApiResponse::success(delivery)))
+}
+
+/// Send queued digests of one frequency now instead of waiting for the scheduler
+pub async fn send_notification_digests(
+    State(state): State<AppState>,
+    Path(frequency): Path<String>,
+) -> Result<Json<ApiResponse<usize>>, AppError> {
+    let sent = state.notification_service.send_digests(&frequency).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        sent,
+        &format!("{} digests sent", sent),
+    )))
+}
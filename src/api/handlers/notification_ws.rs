@@ -1,131 +1,295 @@
+//! Notification WebSocket
+//!
+//! Clients connect to `/ws?token=<access token>`, or send
+//! `{"type": "auth", "token": "..."}` as their first message, then follow
+//! topics with `{"type": "subscribe", "topic": "work_order:<id>"}`. Events for
+//! the user and the topics they follow are pushed as `NotificationMessage`s.
+
 use axum::{
     extract::{
         ws::{Message, WebSocket},
-        State, WebSocketUpgrade,
+        Query, State, WebSocketUpgrade,
     },
     response::IntoResponse,
 };
-use futures::{sink::SinkExt, stream::StreamExt};
-use serde::Serialize;
-use std::collections::HashMap;
-use tokio::sync::{mpsc, Mutex};
-use tracing::{debug, error, info};
+use futures::{
+    sink::SinkExt,
+    stream::{SplitStream, StreamExt},
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, info};
 use uuid::Uuid;
 
 use crate::api::server::AppState;
+use crate::domain::entities::{TenantResource, UserClaims};
+use crate::domain::errors::{DomainError, DomainResult};
+pub use crate::infrastructure::messaging::{NotificationMessage, Topic, WebSocketManager};
 
-/// A message sent to a specific user or broadcasted
-#[derive(Debug, Clone, Serialize)]
-pub struct NotificationMessage {
-    pub event_type: String, // e.g., "WORK_ORDER_UPDATED"
-    pub payload: serde_json::Value,
-}
+/// Time a client has to authenticate after connecting
+const AUTH_TIMEOUT: Duration = Duration::from_secs(10);
+/// How often the login session behind a connection is checked; connections
+/// of revoked sessions are closed
+const SESSION_CHECK_INTERVAL: Duration = Duration::from_secs(60);
 
-/// The global manager for WebSocket connections
-pub struct WebSocketManager {
-    // We map UserID to a channel sender.
-    // This allows us to push messages to a specific user's connection loop.
-    // Ideally we might have multiple connections per user (multiple tabs), so Value could be Vec<Sender>
-    // For simplicity, we'll store a list of sessions.
-    sessions: Mutex<HashMap<Uuid, mpsc::UnboundedSender<Message>>>,
+#[derive(Debug, Deserialize)]
+pub struct WsParams {
+    pub token: Option<String>,
 }
 
-impl WebSocketManager {
-    pub fn new() -> Self {
-        Self {
-            sessions: Mutex::new(HashMap::new()),
-        }
-    }
-
-    /// Broadcast a message to ALL connected clients
-    pub async fn broadcast(&self, msg: &NotificationMessage) {
-        let json = match serde_json::to_string(msg) {
-            Ok(j) => j,
-            Err(e) => {
-                error!("Failed to serialize broadcast message: {}", e);
-                return;
-            }
-        };
+/// Messages a client sends
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Auth { token: String },
+    Subscribe { topic: String },
+    Unsubscribe { topic: String },
+    Ping,
+}
 
-        let sessions = self.sessions.lock().await;
-        for (_id, tx) in sessions.iter() {
-            if let Err(_disconnected) = tx.send(Message::Text(json.clone())) {
-                // The tx is disconnected, we could cleanup here but handle_socket usually does it
-            }
-        }
-        debug!(
-            "Broadcasted event '{}' to {} clients",
-            msg.event_type,
-            sessions.len()
-        );
-    }
+/// Replies to client messages; events are sent as `NotificationMessage`s
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage<'a> {
+    Authenticated { user_id: Uuid, connection_id: Uuid },
+    Subscribed { topic: &'a str },
+    Unsubscribed { topic: &'a str },
+    Pong,
+    Error { message: &'a str },
+}
 
-    // Send to a specific user
-    /*
-    pub async fn send_to_user(&self, user_id: &Uuid, msg: &NotificationMessage) {
-        // Implementation for targeted updates
+impl ServerMessage<'_> {
+    fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
     }
-    */
 }
 
 /// The handler for the WebSocket upgrade request
 pub async fn ws_handler(
     ws: WebSocketUpgrade,
-    // ConnectInfo(addr): ConnectInfo<SocketAddr>, // Optional: get IP
     State(state): State<AppState>,
+    Query(params): Query<WsParams>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+    ws.on_upgrade(move |socket| handle_socket(socket, state, params.token))
 }
 
 /// Actual WebSocket connection handler
-async fn handle_socket(socket: WebSocket, state: AppState) {
+async fn handle_socket(socket: WebSocket, state: AppState, token: Option<String>) {
     let (mut sender, mut receiver) = socket.split();
 
-    // Create a channel for this session
-    let (tx, mut rx) = mpsc::unbounded_channel();
-
-    // Generate a temporary session ID or reuse User ID if authenticated
-    // TODO: proper auth extraction from query param or header?
-    // For now, generate a random ID to treat as an anonymous session until we add Auth.
-    // In a real app, client connects to `ws://.../ws?token=JwtToken`
-    let session_id = Uuid::new_v4();
+    let claims = match authenticate(&state, token, &mut receiver).await {
+        Ok(claims) => claims,
+        Err(message) => {
+            let reply = ServerMessage::Error { message: &message }.to_json();
+            let _ = sender.send(Message::Text(reply)).await;
+            let _ = sender.close().await;
+            return;
+        }
+    };
+    let user_id = claims.user_id();
 
-    // Register session
-    {
-        let mut sessions = state.ws_manager.sessions.lock().await;
-        sessions.insert(session_id, tx);
-    }
-    info!("New WebSocket connection: {}", session_id);
+    // Create a channel for this connection and register it under the user
+    let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+    let connection_id = state.ws_manager.register(user_id, tx.clone()).await;
+    let _ = tx.send(
+        ServerMessage::Authenticated {
+            user_id,
+            connection_id,
+        }
+        .to_json(),
+    );
+    info!(
+        "New WebSocket connection {} of user {}",
+        connection_id, user_id
+    );
 
     // Spawn a task to push messages from the channel to the websocket
     let mut send_task = tokio::spawn(async move {
-        while let Some(msg) = rx.recv().await {
-            if sender.send(msg).await.is_err() {
+        while let Some(text) = rx.recv().await {
+            if sender.send(Message::Text(text)).await.is_err() {
                 break;
             }
         }
     });
 
-    // Main loop: receive messages from client (e.g. pings)
+    // Main loop: client commands, and the session check
+    let recv_state = state.clone();
     let mut recv_task = tokio::spawn(async move {
-        while let Some(Ok(msg)) = receiver.next().await {
-            if let Message::Close(_) = msg {
-                break;
+        let mut session_check = tokio::time::interval(SESSION_CHECK_INTERVAL);
+        session_check.tick().await;
+        loop {
+            tokio::select! {
+                msg = receiver.next() => {
+                    let Some(Ok(msg)) = msg else { break };
+                    match msg {
+                        Message::Text(text) => {
+                            let reply =
+                                handle_client_message(&recv_state, &claims, connection_id, &text)
+                                    .await;
+                            if tx.send(reply).is_err() {
+                                break;
+                            }
+                        }
+                        Message::Close(_) => break,
+                        _ => {}
+                    }
+                }
+                _ = session_check.tick() => {
+                    if !matches!(recv_state.auth_service.session_is_active(&claims).await, Ok(true)) {
+                        debug!("Closing WebSocket {}: session ended", connection_id);
+                        break;
+                    }
+                }
             }
-            // Handle other messages (Pong, Text commands from client)
         }
     });
 
     // Wait for either task to finish (connection closed or error)
     tokio::select! {
-        _ = (&mut send_task) => {},
-        _ = (&mut recv_task) => {},
+        _ = (&mut send_task) => recv_task.abort(),
+        _ = (&mut recv_task) => send_task.abort(),
+    }
+
+    // Cleanup connection
+    state.ws_manager.unregister(connection_id).await;
+    info!("WebSocket disconnected: {}", connection_id);
+}
+
+/// Claims of the access token given in the query, or else in the first message
+async fn authenticate(
+    state: &AppState,
+    token: Option<String>,
+    receiver: &mut SplitStream<WebSocket>,
+) -> Result<UserClaims, String> {
+    let token = match token {
+        Some(token) => token,
+        None => {
+            let first = tokio::time::timeout(AUTH_TIMEOUT, receiver.next())
+                .await
+                .map_err(|_| "Authentication timed out".to_string())?;
+            match first {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str(&text) {
+                    Ok(ClientMessage::Auth { token }) => token,
+                    _ => return Err("Expected an auth message".to_string()),
+                },
+                _ => return Err("Expected an auth message".to_string()),
+            }
+        }
+    };
+
+    state
+        .auth_service
+        .authenticate(&token)
+        .await
+        .map_err(|_| "Invalid or expired token".to_string())
+}
+
+async fn handle_client_message(
+    state: &AppState,
+    claims: &UserClaims,
+    connection_id: Uuid,
+    text: &str,
+) -> String {
+    let message = match serde_json::from_str::<ClientMessage>(text) {
+        Ok(message) => message,
+        Err(_) => {
+            return ServerMessage::Error {
+                message: "Unrecognized message",
+            }
+            .to_json()
+        }
+    };
+
+    match message {
+        ClientMessage::Auth { .. } => ServerMessage::Error {
+            message: "Already authenticated",
+        }
+        .to_json(),
+        ClientMessage::Ping => ServerMessage::Pong.to_json(),
+        ClientMessage::Subscribe { topic: name } => {
+            let Some(topic) = Topic::parse(&name) else {
+                return ServerMessage::Error {
+                    message: "Unknown topic",
+                }
+                .to_json();
+            };
+            match can_follow(state, claims, &topic).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    return ServerMessage::Error {
+                        message: "Topic not found",
+                    }
+                    .to_json()
+                }
+                Err(e) => {
+                    tracing::warn!("Subscription check for {} failed: {}", name, e);
+                    return ServerMessage::Error {
+                        message: "Subscription failed",
+                    }
+                    .to_json();
+                }
+            }
+            if !state.ws_manager.subscribe(connection_id, &topic).await {
+                return ServerMessage::Error {
+                    message: "Too many subscriptions",
+                }
+                .to_json();
+            }
+            ServerMessage::Subscribed { topic: &name }.to_json()
+        }
+        ClientMessage::Unsubscribe { topic: name } => {
+            if let Some(topic) = Topic::parse(&name) {
+                state.ws_manager.unsubscribe(connection_id, &topic).await;
+            }
+            ServerMessage::Unsubscribed { topic: &name }.to_json()
+        }
     }
+}
 
-    // Cleanup session
+/// Users follow records they can read: within their active organization and
+/// the data scope of the read permission. Alerts need `asset.read`.
+async fn can_follow(state: &AppState, claims: &UserClaims, topic: &Topic) -> DomainResult<bool> {
+    let (resource, id) = match *topic {
+        Topic::Alerts => {
+            return state
+                .rbac_service
+                .user_has_permission(claims.user_id(), "asset.read")
+                .await
+        }
+        Topic::Asset(id) => (TenantResource::Asset, id),
+        Topic::WorkOrder(id) => (TenantResource::WorkOrder, id),
+        Topic::Rental(id) => (TenantResource::Rental, id),
+    };
+
+    let org_id = claims.organization_id();
+    if org_id.is_none() && claims.role != "super_admin" {
+        return Ok(false);
+    }
+    if !state
+        .organization_service
+        .resource_visible(resource, id, org_id)
+        .await?
     {
-        let mut sessions = state.ws_manager.sessions.lock().await;
-        sessions.remove(&session_id);
+        return Ok(false);
+    }
+
+    let in_scope = match resource {
+        TenantResource::Asset => state
+            .asset_service
+            .get_in_scope(id, claims)
+            .await
+            .map(|_| ()),
+        TenantResource::WorkOrder => state
+            .work_order_service
+            .get_in_scope(id, claims)
+            .await
+            .map(|_| ()),
+        _ => state.rental_service.get_by_id(id).await.map(|_| ()),
+    };
+    match in_scope {
+        Ok(()) => Ok(true),
+        Err(DomainError::NotFound { .. }) => Ok(false),
+        Err(e) => Err(e),
     }
-    info!("WebSocket disconnected: {}", session_id);
 }
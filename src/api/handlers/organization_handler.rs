@@ -0,0 +1,147 @@
+//! Organization Handlers
+//!
+//! Hierarchy and membership management, scoped to the caller's active
+//! organization.
+
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use uuid::Uuid;
+
+use crate::api::server::AppState;
+use crate::application::dto::{
+    AddOrganizationMemberRequest, ApiResponse, CreateOrganizationRequest, UpdateOrganizationRequest,
+};
+use crate::domain::entities::{Organization, OrganizationMember, UserClaims};
+use crate::shared::errors::AppError;
+
+/// Organizations in the subtree of the active organization
+pub async fn list_organizations(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+) -> Result<Json<ApiResponse<Vec<Organization>>>, AppError> {
+    let organizations = state
+        .organization_service
+        .list(claims.organization_id())
+        .await?;
+    Ok(Json(ApiResponse::success(organizations)))
+}
+
+pub async fn create_organization(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Json(payload): Json<CreateOrganizationRequest>,
+) -> Result<Json<ApiResponse<Organization>>, AppError> {
+    let organization = state
+        .organization_service
+        .create(payload, claims.organization_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        organization,
+        "Organization created",
+    )))
+}
+
+pub async fn get_organization(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Organization>>, AppError> {
+    let organization = state
+        .organization_service
+        .get(id, claims.organization_id())
+        .await?;
+    Ok(Json(ApiResponse::success(organization)))
+}
+
+pub async fn update_organization(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateOrganizationRequest>,
+) -> Result<Json<ApiResponse<Organization>>, AppError> {
+    let organization = state
+        .organization_service
+        .update(id, payload, claims.organization_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        organization,
+        "Organization updated",
+    )))
+}
+
+pub async fn delete_organization(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state
+        .organization_service
+        .delete(id, claims.organization_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        "Organization deleted",
+    )))
+}
+
+pub async fn list_organization_members(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<OrganizationMember>>>, AppError> {
+    let members = state
+        .organization_service
+        .members(id, claims.organization_id())
+        .await?;
+    Ok(Json(ApiResponse::success(members)))
+}
+
+pub async fn add_organization_member(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<AddOrganizationMemberRequest>,
+) -> Result<Json<ApiResponse<Vec<OrganizationMember>>>, AppError> {
+    let members = state
+        .organization_service
+        .add_member(
+            id,
+            payload.user_id,
+            claims.organization_id(),
+            claims.user_id(),
+        )
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        members,
+        "Member added",
+    )))
+}
+
+pub async fn remove_organization_member(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path((id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state
+        .organization_service
+        .remove_member(id, user_id, claims.organization_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        "Member removed",
+    )))
+}
+
+/// Organizations the current user belongs to
+pub async fn list_my_organizations(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+) -> Result<Json<ApiResponse<Vec<Organization>>>, AppError> {
+    let organizations = state
+        .organization_service
+        .memberships(claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success(organizations)))
+}
@@ -0,0 +1,114 @@
+//! Password Handler
+//!
+//! Forgot-password flow, the password change an admin can require at the next
+//! login, and forcing that change.
+
+use std::net::SocketAddr;
+
+use axum::{
+    extract::{ConnectInfo, Path, State},
+    http::HeaderMap,
+    response::Response,
+    Extension, Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::handlers::auth_handler::{client_info, login_outcome_response};
+use crate::api::server::AppState;
+use crate::application::dto::ApiResponse;
+use crate::domain::entities::UserClaims;
+use crate::shared::errors::AppError;
+
+#[derive(Deserialize)]
+pub struct ForgotPasswordRequest {
+    pub email: String,
+}
+
+#[derive(Deserialize)]
+pub struct ResetPasswordRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
+#[derive(Deserialize)]
+pub struct RequiredPasswordChangeRequest {
+    pub challenge_token: String,
+    pub new_password: String,
+}
+
+/// Send a reset link; answers the same whether or not the email is known
+pub async fn forgot(
+    State(state): State<AppState>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(payload): Json<ForgotPasswordRequest>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    let client = client_info(&headers, peer);
+    state
+        .password_service
+        .request_reset(&payload.email, client.ip.as_deref())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        "If the account exists, a reset link has been sent",
+    )))
+}
+
+/// Choose a new password with a reset token; all sessions of the user end
+pub async fn reset(
+    State(state): State<AppState>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(payload): Json<ResetPasswordRequest>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    let client = client_info(&headers, peer);
+    state
+        .password_service
+        .reset_password(&payload.token, &payload.new_password, client.ip.as_deref())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        "Password has been reset",
+    )))
+}
+
+/// Login step after `password_change_required`: set the new password and
+/// carry on with the login (2FA or tokens)
+pub async fn change_required(
+    State(state): State<AppState>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(payload): Json<RequiredPasswordChangeRequest>,
+) -> Result<Response, AppError> {
+    let client = client_info(&headers, peer);
+    let user = state
+        .auth_service
+        .password_change_user(&payload.challenge_token)
+        .await?;
+    state
+        .password_service
+        .change_required_password(&user, &payload.new_password)
+        .await?;
+    let outcome = state
+        .auth_service
+        .finish_password_change(&payload.challenge_token, &client)
+        .await?;
+    Ok(login_outcome_response(outcome))
+}
+
+/// Require a new password at the user's next login
+pub async fn force_reset(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state
+        .password_service
+        .force_reset(id, claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        "The user must choose a new password at the next login",
+    )))
+}
@@ -0,0 +1,104 @@
+//! Preventive Schedule Handlers
+//!
+//! HTTP handlers for preventive maintenance schedules.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use uuid::Uuid;
+
+use crate::api::server::AppState;
+use crate::application::dto::{
+    ApiResponse, CreatePreventiveScheduleRequest, UpdatePreventiveScheduleRequest,
+};
+use crate::application::services::DueSchedule;
+use crate::domain::entities::{PreventiveSchedule, UserClaims, WorkOrder};
+use crate::domain::errors::DomainError;
+use crate::shared::errors::AppError;
+
+/// List schedules for an asset
+pub async fn list_asset_preventive_schedules(
+    State(state): State<AppState>,
+    Path(asset_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<PreventiveSchedule>>>, AppError> {
+    let schedules = state
+        .preventive_maintenance_service
+        .list_by_asset(asset_id)
+        .await?;
+    Ok(Json(ApiResponse::success(schedules)))
+}
+
+/// Create a schedule for an asset
+pub async fn create_preventive_schedule(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(asset_id): Path<Uuid>,
+    Json(payload): Json<CreatePreventiveScheduleRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<PreventiveSchedule>>), AppError> {
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::Unauthorized("Invalid user ID in token".to_string()))?;
+
+    let schedule = state
+        .preventive_maintenance_service
+        .create(asset_id, payload, user_id)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success_with_message(
+            schedule,
+            "Preventive schedule created",
+        )),
+    ))
+}
+
+pub async fn get_preventive_schedule(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<PreventiveSchedule>>, AppError> {
+    let schedule = state.preventive_maintenance_service.get_by_id(id).await?;
+    Ok(Json(ApiResponse::success(schedule)))
+}
+
+pub async fn update_preventive_schedule(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdatePreventiveScheduleRequest>,
+) -> Result<Json<ApiResponse<PreventiveSchedule>>, AppError> {
+    let schedule = state
+        .preventive_maintenance_service
+        .update(id, payload)
+        .await?;
+    Ok(Json(ApiResponse::success(schedule)))
+}
+
+pub async fn delete_preventive_schedule(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    if state.preventive_maintenance_service.delete(id).await? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(DomainError::not_found("PreventiveSchedule", id).into())
+    }
+}
+
+/// List schedules that are currently due
+pub async fn list_due_preventive_schedules(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<DueSchedule>>>, AppError> {
+    let due = state.preventive_maintenance_service.list_due().await?;
+    Ok(Json(ApiResponse::success(due)))
+}
+
+/// Run the generator on demand (same job the scheduler runs daily)
+pub async fn generate_preventive_work_orders(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<WorkOrder>>>, AppError> {
+    let created = state
+        .preventive_maintenance_service
+        .generate_due_work_orders()
+        .await?;
+    Ok(Json(ApiResponse::success(created)))
+}
@@ -36,7 +36,10 @@ pub async fn change_password(
 ) -> Result<Json<ApiResponse<()>>, AppError> {
     let user_id = uuid::Uuid::parse_str(&claims.sub)
         .map_err(|_| AppError::Unauthorized("Invalid user ID in token".to_string()))?;
-    state.user_service.change_password(user_id, payload).await?;
+    state
+        .password_service
+        .change_password(user_id, payload)
+        .await?;
     Ok(Json(ApiResponse::success(())))
 }
 
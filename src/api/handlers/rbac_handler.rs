@@ -2,13 +2,18 @@
 
 use axum::{
     extract::{Path, State},
-    Json,
+    Extension, Json,
 };
 use uuid::Uuid;
 
 use crate::api::server::AppState;
-use crate::application::dto::ApiResponse;
-use crate::domain::entities::{Permission, Role};
+use crate::application::dto::{
+    ApiResponse, CloneRoleRequest, CreateRoleRequest, SetRolePermissionsRequest, UpdateRoleRequest,
+};
+use crate::domain::entities::{
+    Permission, PermissionGrant, Role, RolePermission, RolePermissionPreview, UserClaims,
+    UserPermission,
+};
 use crate::shared::errors::AppError;
 
 pub async fn list_roles(State(state): State<AppState>) -> Result<Json<Vec<Role>>, AppError> {
@@ -26,11 +31,137 @@ pub async fn list_permissions(
 pub async fn get_role_permissions(
     State(state): State<AppState>,
     Path(role_id): Path<Uuid>,
-) -> Result<Json<Vec<Permission>>, AppError> {
+) -> Result<Json<Vec<RolePermission>>, AppError> {
     let permissions = state.rbac_service.get_role_permissions(role_id).await?;
     Ok(Json(permissions))
 }
 
+// ==================== ROLE MANAGEMENT (admin) ====================
+
+pub async fn create_role(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Json(req): Json<CreateRoleRequest>,
+) -> Result<Json<ApiResponse<Role>>, AppError> {
+    let role = state
+        .rbac_service
+        .create_role(req, claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        role,
+        "Role created",
+    )))
+}
+
+pub async fn get_role(
+    State(state): State<AppState>,
+    Path(role_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Role>>, AppError> {
+    let role = state.rbac_service.get_role(role_id).await?;
+    Ok(Json(ApiResponse::success(role)))
+}
+
+pub async fn update_role(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(role_id): Path<Uuid>,
+    Json(req): Json<UpdateRoleRequest>,
+) -> Result<Json<ApiResponse<Role>>, AppError> {
+    let role = state
+        .rbac_service
+        .update_role(role_id, req, claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        role,
+        "Role updated",
+    )))
+}
+
+pub async fn delete_role(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(role_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state
+        .rbac_service
+        .delete_role(role_id, claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message((), "Role deleted")))
+}
+
+pub async fn clone_role(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(role_id): Path<Uuid>,
+    Json(req): Json<CloneRoleRequest>,
+) -> Result<Json<ApiResponse<Role>>, AppError> {
+    let role = state
+        .rbac_service
+        .clone_role(role_id, req, claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(role, "Role cloned")))
+}
+
+pub async fn set_role_permissions(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(role_id): Path<Uuid>,
+    Json(req): Json<SetRolePermissionsRequest>,
+) -> Result<Json<ApiResponse<Vec<RolePermission>>>, AppError> {
+    let permissions = state
+        .rbac_service
+        .set_role_permissions(role_id, req, claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        permissions,
+        "Role permissions saved",
+    )))
+}
+
+pub async fn attach_role_permission(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(role_id): Path<Uuid>,
+    Json(grant): Json<PermissionGrant>,
+) -> Result<Json<ApiResponse<Vec<RolePermission>>>, AppError> {
+    let permissions = state
+        .rbac_service
+        .attach_permission(role_id, grant, claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        permissions,
+        "Permission granted",
+    )))
+}
+
+pub async fn detach_role_permission(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path((role_id, permission_code)): Path<(Uuid, String)>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state
+        .rbac_service
+        .detach_permission(role_id, &permission_code, claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        "Permission revoked",
+    )))
+}
+
+/// Effective permission changes of the role's users, before saving
+pub async fn preview_role_permissions(
+    State(state): State<AppState>,
+    Path(role_id): Path<Uuid>,
+    Json(req): Json<SetRolePermissionsRequest>,
+) -> Result<Json<ApiResponse<RolePermissionPreview>>, AppError> {
+    let preview = state
+        .rbac_service
+        .preview_role_permissions(role_id, req)
+        .await?;
+    Ok(Json(ApiResponse::success(preview)))
+}
+
 pub async fn get_user_roles(
     State(state): State<AppState>,
     Path(user_id): Path<Uuid>,
@@ -42,7 +173,7 @@ pub async fn get_user_roles(
 pub async fn get_user_permissions(
     State(state): State<AppState>,
     Path(user_id): Path<Uuid>,
-) -> Result<Json<Vec<Permission>>, AppError> {
+) -> Result<Json<Vec<UserPermission>>, AppError> {
     let permissions = state.rbac_service.get_user_permissions(user_id).await?;
     Ok(Json(permissions))
 }
@@ -23,11 +23,12 @@ use crate::shared::errors::AppError;
 /// List all rentals
 pub async fn list_rentals(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
     Query(params): Query<PaginationParams>,
 ) -> Result<Json<Vec<Rental>>, AppError> {
     let rentals = state
         .rental_service
-        .list(params.page(), params.per_page())
+        .list(params.page(), params.per_page(), claims.organization_id())
         .await?;
     Ok(Json(rentals))
 }
@@ -128,16 +129,24 @@ pub async fn return_rental(
 /// List pending rentals
 pub async fn list_pending_rentals(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
 ) -> Result<Json<Vec<Rental>>, AppError> {
-    let rentals = state.rental_service.list_pending().await?;
+    let rentals = state
+        .rental_service
+        .list_pending(claims.organization_id())
+        .await?;
     Ok(Json(rentals))
 }
 
 /// List overdue rentals
 pub async fn list_overdue_rentals(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
 ) -> Result<Json<Vec<Rental>>, AppError> {
-    let rentals = state.rental_service.list_overdue().await?;
+    let rentals = state
+        .rental_service
+        .list_overdue(claims.organization_id())
+        .await?;
     Ok(Json(rentals))
 }
 
@@ -1,11 +1,13 @@
 use axum::{
     extract::{Query, State},
     response::{IntoResponse, Response},
+    Extension,
 };
 use chrono::NaiveDate;
 use serde::Deserialize;
 
 use crate::api::server::AppState;
+use crate::domain::entities::UserClaims;
 use crate::shared::errors::AppError;
 
 #[derive(Deserialize)]
@@ -14,8 +16,14 @@ pub struct ReportDateRangeParams {
     pub end_date: NaiveDate,
 }
 
-pub async fn export_assets(State(state): State<AppState>) -> Result<Response, AppError> {
-    let csv_content = state.report_service.generate_asset_inventory_csv().await?;
+pub async fn export_assets(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+) -> Result<Response, AppError> {
+    let csv_content = state
+        .report_service
+        .generate_asset_inventory_csv(&claims)
+        .await?;
 
     Ok((
         [
@@ -32,11 +40,12 @@ pub async fn export_assets(State(state): State<AppState>) -> Result<Response, Ap
 
 pub async fn export_maintenance(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
     Query(params): Query<ReportDateRangeParams>,
 ) -> Result<Response, AppError> {
     let csv_content = state
         .report_service
-        .generate_maintenance_log_csv(params.start_date, params.end_date)
+        .generate_maintenance_log_csv(params.start_date, params.end_date, &claims)
         .await?;
 
     Ok((
@@ -54,11 +63,12 @@ pub async fn export_maintenance(
 
 pub async fn export_rental_revenue(
     State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
     Query(params): Query<ReportDateRangeParams>,
 ) -> Result<Response, AppError> {
     let csv_content = state
         .report_service
-        .generate_rental_revenue_csv(params.start_date, params.end_date)
+        .generate_rental_revenue_csv(params.start_date, params.end_date, &claims)
         .await?;
 
     Ok((
@@ -74,10 +84,13 @@ pub async fn export_rental_revenue(
         .into_response())
 }
 
-pub async fn export_depreciation(State(state): State<AppState>) -> Result<Response, AppError> {
+pub async fn export_depreciation(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+) -> Result<Response, AppError> {
     let csv_content = state
         .report_service
-        .generate_asset_depreciation_csv()
+        .generate_asset_depreciation_csv(&claims)
         .await?;
 
     Ok((
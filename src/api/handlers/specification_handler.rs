@@ -0,0 +1,51 @@
+//! Asset Specification Handlers
+
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use uuid::Uuid;
+
+use crate::api::server::AppState;
+use crate::application::dto::{ApiResponse, SpecificationDiffParams, UpdateSpecificationsRequest};
+use crate::domain::entities::{Asset, AssetSpecificationHistory, SpecificationDiff, UserClaims};
+use crate::shared::errors::AppError;
+
+/// Category/specification changes of an asset, latest first
+pub async fn list_specification_history(
+    State(state): State<AppState>,
+    Path(asset_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<AssetSpecificationHistory>>>, AppError> {
+    let history = state.specification_service.history(asset_id).await?;
+    Ok(Json(ApiResponse::success(history)))
+}
+
+/// Diff the specifications after history entry `from` against entry `to`
+/// (default: current specifications)
+pub async fn diff_specifications(
+    State(state): State<AppState>,
+    Path(asset_id): Path<Uuid>,
+    Query(params): Query<SpecificationDiffParams>,
+) -> Result<Json<ApiResponse<SpecificationDiff>>, AppError> {
+    let diff = state
+        .specification_service
+        .diff(asset_id, params.from, params.to)
+        .await?;
+    Ok(Json(ApiResponse::success(diff)))
+}
+
+pub async fn update_specifications(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(asset_id): Path<Uuid>,
+    Json(payload): Json<UpdateSpecificationsRequest>,
+) -> Result<Json<ApiResponse<Asset>>, AppError> {
+    let asset = state
+        .specification_service
+        .update_specifications(asset_id, payload, claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        asset,
+        "Specifications updated",
+    )))
+}
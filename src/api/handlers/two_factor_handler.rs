@@ -0,0 +1,144 @@
+//! Two-Factor Handler
+//!
+//! The second login step and TOTP enrolment management of the current user.
+
+use std::net::SocketAddr;
+
+use axum::{
+    extract::{ConnectInfo, Path, State},
+    http::HeaderMap,
+    Extension, Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::handlers::auth_handler::{client_info, LoginResponse};
+use crate::api::server::AppState;
+use crate::application::dto::ApiResponse;
+use crate::domain::entities::{TwoFactorSetup, TwoFactorStatus, UserClaims};
+use crate::shared::errors::AppError;
+
+#[derive(Deserialize)]
+pub struct TwoFactorChallengeRequest {
+    pub challenge_token: String,
+}
+
+#[derive(Deserialize)]
+pub struct TwoFactorVerifyRequest {
+    pub challenge_token: String,
+    /// A TOTP code or a recovery code
+    pub code: String,
+}
+
+#[derive(Deserialize)]
+pub struct TwoFactorCodeRequest {
+    pub code: String,
+}
+
+/// Enrol during login when the user's role requires 2FA
+pub async fn setup_challenge(
+    State(state): State<AppState>,
+    Json(payload): Json<TwoFactorChallengeRequest>,
+) -> Result<Json<ApiResponse<TwoFactorSetup>>, AppError> {
+    let setup = state
+        .auth_service
+        .setup_two_factor_challenge(&payload.challenge_token)
+        .await?;
+    Ok(Json(ApiResponse::success(setup)))
+}
+
+/// Second login step: exchange the challenge token and a code for tokens
+pub async fn verify(
+    State(state): State<AppState>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(payload): Json<TwoFactorVerifyRequest>,
+) -> Result<Json<LoginResponse>, AppError> {
+    let client = client_info(&headers, peer);
+    let (user, tokens, recovery_codes) = state
+        .auth_service
+        .verify_two_factor(&payload.challenge_token, &payload.code, &client)
+        .await?;
+
+    Ok(Json(LoginResponse {
+        recovery_codes,
+        ..LoginResponse::with_tokens(user, tokens)
+    }))
+}
+
+pub async fn get_status(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+) -> Result<Json<ApiResponse<TwoFactorStatus>>, AppError> {
+    let status = state
+        .auth_service
+        .two_factor_status(claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success(status)))
+}
+
+pub async fn setup(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+) -> Result<Json<ApiResponse<TwoFactorSetup>>, AppError> {
+    let setup = state
+        .auth_service
+        .setup_two_factor(claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success(setup)))
+}
+
+/// Confirm enrolment; the recovery codes are only returned here
+pub async fn enable(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Json(payload): Json<TwoFactorCodeRequest>,
+) -> Result<Json<ApiResponse<Vec<String>>>, AppError> {
+    let codes = state
+        .auth_service
+        .enable_two_factor(claims.user_id(), &payload.code)
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        codes,
+        "Two-factor authentication enabled",
+    )))
+}
+
+pub async fn disable(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Json(payload): Json<TwoFactorCodeRequest>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state
+        .auth_service
+        .disable_two_factor(claims.user_id(), &payload.code)
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        "Two-factor authentication disabled",
+    )))
+}
+
+pub async fn regenerate_recovery_codes(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Json(payload): Json<TwoFactorCodeRequest>,
+) -> Result<Json<ApiResponse<Vec<String>>>, AppError> {
+    let codes = state
+        .auth_service
+        .regenerate_recovery_codes(claims.user_id(), &payload.code)
+        .await?;
+    Ok(Json(ApiResponse::success(codes)))
+}
+
+/// Drop a user's enrolment, e.g. after a lost device (admin only)
+pub async fn reset(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state.auth_service.reset_two_factor(id).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        "Two-factor authentication reset",
+    )))
+}
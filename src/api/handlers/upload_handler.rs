@@ -12,7 +12,7 @@ use uuid::Uuid;
 use crate::api::server::AppState;
 use crate::{AppError, AppResult};
 
-const MAX_UPLOAD_SIZE: u64 = 10 * 1024 * 1024; // 10MB
+pub(crate) const MAX_UPLOAD_SIZE: u64 = 10 * 1024 * 1024; // 10MB
 
 /// Upload a file
 pub async fn upload_file(
@@ -2,14 +2,14 @@
 
 use axum::{
     extract::{Path, Query, State},
-    Json,
+    Extension, Json,
 };
 use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::api::server::AppState;
 use crate::application::dto::{ApiResponse, CreateUserRequest, UpdateUserRequest};
-use crate::domain::entities::{User, UserSummary};
+use crate::domain::entities::{User, UserClaims, UserSummary};
 use crate::shared::errors::AppError;
 
 #[derive(Deserialize)]
@@ -32,8 +32,19 @@ pub async fn list_users(
 
 pub async fn create_user(
     State(state): State<AppState>,
-    Json(payload): Json<CreateUserRequest>,
+    Extension(claims): Extension<UserClaims>,
+    Json(mut payload): Json<CreateUserRequest>,
 ) -> Result<Json<ApiResponse<User>>, AppError> {
+    // New users join the active organization unless one inside it is given
+    match payload.organization_id {
+        Some(org_id) => {
+            state
+                .organization_service
+                .get(org_id, claims.organization_id())
+                .await?;
+        }
+        None => payload.organization_id = claims.organization_id(),
+    }
     let user = state.user_service.create_user(payload).await?;
     Ok(Json(ApiResponse::success(user)))
 }
@@ -54,3 +65,31 @@ pub async fn delete_user(
     state.user_service.delete_user(id).await?;
     Ok(Json(ApiResponse::success(())))
 }
+
+/// Sign a user out everywhere; their access tokens are rejected from now on
+pub async fn revoke_user_sessions(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<u64>>, AppError> {
+    let revoked = state.auth_service.revoke_sessions(id).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        revoked,
+        &format!("Revoked {} session(s)", revoked),
+    )))
+}
+
+/// Lift a login lockout before it expires
+pub async fn unlock_user(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    state
+        .auth_service
+        .unlock_account(id, claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        "Account unlocked",
+    )))
+}
@@ -0,0 +1,72 @@
+//! Valuation Handlers
+
+use axum::{
+    extract::{Path, Query, State},
+    Extension, Json,
+};
+use uuid::Uuid;
+
+use crate::api::server::AppState;
+use crate::application::dto::{
+    ApiResponse, RecordAppraisalRequest, RecordImpairmentRequest, ValuationHistoryParams,
+    ValuationSnapshotRequest,
+};
+use crate::application::services::ValuationSnapshot;
+use crate::domain::entities::{AssetValuation, UserClaims};
+use crate::shared::errors::AppError;
+
+/// Valuation history of one asset, newest first
+pub async fn list_asset_valuations(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<ValuationHistoryParams>,
+) -> Result<Json<ApiResponse<Vec<AssetValuation>>>, AppError> {
+    let valuations = state
+        .valuation_service
+        .history(id, params.valuation_type)
+        .await?;
+    Ok(Json(ApiResponse::success(valuations)))
+}
+
+pub async fn record_asset_appraisal(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<RecordAppraisalRequest>,
+) -> Result<Json<ApiResponse<AssetValuation>>, AppError> {
+    let valuation = state
+        .valuation_service
+        .record_appraisal(id, payload, claims.user_id())
+        .await?;
+    let message = if valuation.is_revaluation() {
+        "Appraisal recorded and asset revalued"
+    } else {
+        "Appraisal recorded"
+    };
+    Ok(Json(ApiResponse::success_with_message(valuation, message)))
+}
+
+pub async fn record_asset_impairment(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<RecordImpairmentRequest>,
+) -> Result<Json<ApiResponse<AssetValuation>>, AppError> {
+    let valuation = state
+        .valuation_service
+        .record_impairment(id, payload, claims.user_id())
+        .await?;
+    Ok(Json(ApiResponse::success_with_message(
+        valuation,
+        "Impairment recorded",
+    )))
+}
+
+/// Record calculated book values of all assets at a date
+pub async fn create_valuation_snapshot(
+    State(state): State<AppState>,
+    Json(payload): Json<ValuationSnapshotRequest>,
+) -> Result<Json<ApiResponse<ValuationSnapshot>>, AppError> {
+    let snapshot = state.valuation_service.snapshot(payload.as_of).await?;
+    Ok(Json(ApiResponse::success(snapshot)))
+}
@@ -0,0 +1,105 @@
+//! Webhook Handlers
+//!
+//! Admin endpoints for webhook subscriptions, their delivery log and dead letters.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use uuid::Uuid;
+
+use crate::api::server::AppState;
+use crate::application::dto::{
+    ApiResponse, CreateWebhookRequest, UpdateWebhookRequest, WebhookDeliveryParams,
+};
+use crate::domain::entities::{
+    UserClaims, WebhookDelivery, WebhookSubscription, WebhookSubscriptionSecret, WEBHOOK_DEAD,
+};
+use crate::domain::errors::DomainError;
+use crate::shared::errors::AppError;
+
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<WebhookSubscription>>>, AppError> {
+    let subscriptions = state.webhook_service.list_subscriptions().await?;
+    Ok(Json(ApiResponse::success(subscriptions)))
+}
+
+pub async fn get_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<WebhookSubscription>>, AppError> {
+    let subscription = state.webhook_service.get_subscription(id).await?;
+    Ok(Json(ApiResponse::success(subscription)))
+}
+
+/// Create a subscription; the response is the only place its secret is shown
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    Extension(claims): Extension<UserClaims>,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<WebhookSubscriptionSecret>>), AppError> {
+    let subscription = state
+        .webhook_service
+        .create_subscription(payload, claims.user_id())
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success_with_message(
+            subscription,
+            "Webhook created; store the secret, it is not shown again",
+        )),
+    ))
+}
+
+pub async fn update_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateWebhookRequest>,
+) -> Result<Json<ApiResponse<WebhookSubscription>>, AppError> {
+    let subscription = state
+        .webhook_service
+        .update_subscription(id, payload)
+        .await?;
+    Ok(Json(ApiResponse::success(subscription)))
+}
+
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, AppError> {
+    if state.webhook_service.delete_subscription(id).await? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(DomainError::not_found("WebhookSubscription", id).into())
+    }
+}
+
+/// Delivery log, newest first
+pub async fn list_webhook_deliveries(
+    State(state): State<AppState>,
+    Query(params): Query<WebhookDeliveryParams>,
+) -> Result<Json<ApiResponse<Vec<WebhookDelivery>>>, AppError> {
+    let deliveries = state.webhook_service.list_deliveries(&params).await?;
+    Ok(Json(ApiResponse::success(deliveries)))
+}
+
+/// Deliveries that ran out of attempts
+pub async fn list_webhook_dead_letters(
+    State(state): State<AppState>,
+    Query(mut params): Query<WebhookDeliveryParams>,
+) -> Result<Json<ApiResponse<Vec<WebhookDelivery>>>, AppError> {
+    params.status = Some(WEBHOOK_DEAD.to_string());
+    let deliveries = state.webhook_service.list_deliveries(&params).await?;
+    Ok(Json(ApiResponse::success(deliveries)))
+}
+
+/// Send a dead or retrying delivery again now
+pub async fn redeliver_webhook_delivery(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<WebhookDelivery>>, AppError> {
+    let delivery = state.webhook_service.redeliver(id).await?;
+    Ok(Json(ApiResponse::success(delivery)))
+}
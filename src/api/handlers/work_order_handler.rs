@@ -3,16 +3,17 @@
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
+    response::{IntoResponse, Response},
     Extension, Json,
 };
 use rust_decimal::Decimal;
 use serde::Deserialize;
 use uuid::Uuid;
 
-use crate::api::handlers::notification_ws::NotificationMessage;
+use crate::api::handlers::notification_ws::{NotificationMessage, Topic};
 use crate::api::server::AppState;
 use crate::application::dto::{ApiResponse, PaginationParams};
-use crate::application::services::CreateWorkOrderRequest;
+use crate::application::services::{BudgetedResult, CreateWorkOrderRequest};
 use crate::domain::entities::{UserClaims as Claims, WorkOrder};
 use crate::shared::errors::AppError;
 use serde_json::json;
@@ -39,14 +40,15 @@ fn get_user_id(claims: &Claims) -> Result<Uuid, AppError> {
         .map_err(|_| AppError::Unauthorized("Invalid user ID in token".to_string()))
 }
 
-/// List work orders (all authenticated users)
+/// List work orders (all authenticated users, within their data scope)
 pub async fn list_work_orders(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
     Query(params): Query<PaginationParams>,
 ) -> Result<Json<Vec<WorkOrder>>, AppError> {
     let orders = state
         .work_order_service
-        .list(params.page(), params.per_page())
+        .list(params.page(), params.per_page(), &claims)
         .await?;
     Ok(Json(orders))
 }
@@ -54,25 +56,28 @@ pub async fn list_work_orders(
 /// List pending work orders
 pub async fn list_pending_work_orders(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
 ) -> Result<Json<Vec<WorkOrder>>, AppError> {
-    let orders = state.work_order_service.list_pending().await?;
+    let orders = state.work_order_service.list_pending(&claims).await?;
     Ok(Json(orders))
 }
 
 /// List overdue work orders
 pub async fn list_overdue_work_orders(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
 ) -> Result<Json<Vec<WorkOrder>>, AppError> {
-    let orders = state.work_order_service.list_overdue().await?;
+    let orders = state.work_order_service.list_overdue(&claims).await?;
     Ok(Json(orders))
 }
 
 /// Get single work order
 pub async fn get_work_order(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<WorkOrder>, AppError> {
-    let order = state.work_order_service.get_by_id(id).await?;
+    let order = state.work_order_service.get_in_scope(id, &claims).await?;
     Ok(Json(order))
 }
 
@@ -91,18 +96,21 @@ pub async fn create_work_order(
         .create(payload, Some(user_id))
         .await?;
 
-    // Broadcast new work order event
+    // Tell the followers of the asset
     state
         .ws_manager
-        .broadcast(&NotificationMessage {
-            event_type: "WORK_ORDER_CREATED".to_string(),
-            payload: json!({
-                "id": order.id,
-                "asset_id": order.asset_id,
-                "status": order.status,
-                "created_by": user_id
-            }),
-        })
+        .publish(
+            &Topic::Asset(order.asset_id),
+            &NotificationMessage::new(
+                "WORK_ORDER_CREATED",
+                json!({
+                    "id": order.id,
+                    "asset_id": order.asset_id,
+                    "status": order.status,
+                    "created_by": user_id
+                }),
+            ),
+        )
         .await;
 
     Ok((
@@ -119,17 +127,25 @@ pub async fn approve_work_order(
     State(state): State<AppState>,
     Extension(claims): Extension<Claims>,
     Path(id): Path<Uuid>,
-) -> Result<Json<ApiResponse<WorkOrder>>, AppError> {
+) -> Result<Response, AppError> {
     // Check role: Supervisor (3) or higher
     check_role(&claims, ROLE_SUPERVISOR)?;
 
-    let approver_id = get_user_id(&claims)?;
-    let order = state.work_order_service.approve(id, approver_id).await?;
-
-    Ok(Json(ApiResponse::success_with_message(
-        order,
-        "Work order approved",
-    )))
+    match state.work_order_service.approve(id, &claims).await? {
+        BudgetedResult::Applied(order) => Ok(Json(ApiResponse::success_with_message(
+            order,
+            "Work order approved",
+        ))
+        .into_response()),
+        BudgetedResult::PendingApproval(request) => Ok((
+            StatusCode::ACCEPTED,
+            Json(ApiResponse::success_with_message(
+                request,
+                "Work order exceeds its budget; overrun submitted for approval",
+            )),
+        )
+            .into_response()),
+    }
 }
 
 /// Assign work order to technician (Supervisor+)
@@ -141,7 +157,10 @@ pub async fn assign_work_order(
     // Check role: Supervisor (3) or higher
     check_role(&claims, ROLE_SUPERVISOR)?;
 
-    let order = state.work_order_service.assign(id, technician_id).await?;
+    let order = state
+        .work_order_service
+        .assign(id, technician_id, &claims)
+        .await?;
 
     Ok(Json(ApiResponse::success_with_message(
         order,
@@ -158,7 +177,7 @@ pub async fn start_work_order(
     let user_id = get_user_id(&claims)?;
 
     // Get work order to check assignment
-    let wo = state.work_order_service.get_by_id(id).await?;
+    let wo = state.work_order_service.get_in_scope(id, &claims).await?;
 
     // Verify user is assigned technician (or Supervisor+ can override)
     if let Some(assigned) = wo.assigned_technician {
@@ -173,7 +192,7 @@ pub async fn start_work_order(
         ));
     }
 
-    let order = state.work_order_service.start_work(id).await?;
+    let order = state.work_order_service.start_work(id, &claims).await?;
 
     Ok(Json(ApiResponse::success_with_message(
         order,
@@ -197,7 +216,7 @@ pub async fn complete_work_order(
     let user_id = get_user_id(&claims)?;
 
     // Get work order to check assignment
-    let wo = state.work_order_service.get_by_id(id).await?;
+    let wo = state.work_order_service.get_in_scope(id, &claims).await?;
 
     // Verify user is assigned technician (or Supervisor+ can override)
     if let Some(assigned) = wo.assigned_technician {
@@ -213,20 +232,26 @@ pub async fn complete_work_order(
     }
     let order = state
         .work_order_service
-        .complete(id, user_id, &payload.work_performed, payload.actual_cost)
+        .complete(id, &payload.work_performed, payload.actual_cost, &claims)
         .await?;
 
-    // Broadcast work order completion
+    // Tell the followers of the work order and its asset
+    let msg = NotificationMessage::new(
+        "WORK_ORDER_COMPLETED",
+        json!({
+            "id": order.id,
+            "asset_id": order.asset_id,
+            "status": order.status,
+            "completed_by": user_id
+        }),
+    );
+    state
+        .ws_manager
+        .publish(&Topic::WorkOrder(order.id), &msg)
+        .await;
     state
         .ws_manager
-        .broadcast(&NotificationMessage {
-            event_type: "WORK_ORDER_COMPLETED".to_string(),
-            payload: json!({
-                "id": order.id,
-                "status": order.status,
-                "completed_by": user_id
-            }),
-        })
+        .publish(&Topic::Asset(order.asset_id), &msg)
         .await;
 
     Ok(Json(ApiResponse::success_with_message(
@@ -244,8 +269,7 @@ pub async fn cancel_work_order(
     // Check role: Manager (2) or higher
     check_role(&claims, ROLE_MANAGER)?;
 
-    // TODO: Implement cancel in service
-    let order = state.work_order_service.get_by_id(id).await?;
+    let order = state.work_order_service.cancel(id, &claims).await?;
 
     Ok(Json(ApiResponse::success_with_message(
         order,
@@ -269,9 +293,10 @@ pub struct AddPartRequest {
 // Handlers for Tasks
 pub async fn get_work_order_tasks(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<Vec<crate::domain::entities::ChecklistItem>>, AppError> {
-    let tasks = state.work_order_service.get_checklist(id).await?;
+    let tasks = state.work_order_service.get_checklist(id, &claims).await?;
     Ok(Json(tasks))
 }
 
@@ -285,7 +310,7 @@ pub async fn add_work_order_task(
 
     let task = state
         .work_order_service
-        .add_checklist_item(id, payload.task_number, payload.description)
+        .add_checklist_item(id, payload.task_number, payload.description, &claims)
         .await?;
     Ok(Json(task))
 }
@@ -293,13 +318,13 @@ pub async fn add_work_order_task(
 pub async fn remove_work_order_task(
     State(state): State<AppState>,
     Extension(claims): Extension<Claims>,
-    Path((_id, task_id)): Path<(Uuid, Uuid)>,
+    Path((id, task_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<ApiResponse<bool>>, AppError> {
     check_role(&claims, ROLE_SUPERVISOR)?;
 
     state
         .work_order_service
-        .remove_checklist_item(task_id)
+        .remove_checklist_item(task_id, id, &claims)
         .await?;
     Ok(Json(ApiResponse::success_with_message(
         true,
@@ -310,9 +335,10 @@ pub async fn remove_work_order_task(
 // Handlers for Parts
 pub async fn get_work_order_parts(
     State(state): State<AppState>,
+    Extension(claims): Extension<Claims>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<Vec<crate::domain::entities::WorkOrderPart>>, AppError> {
-    let parts = state.work_order_service.get_parts(id).await?;
+    let parts = state.work_order_service.get_parts(id, &claims).await?;
     Ok(Json(parts))
 }
 
@@ -326,7 +352,13 @@ pub async fn add_work_order_part(
 
     let part = state
         .work_order_service
-        .add_part(id, payload.part_name, payload.quantity, payload.unit_cost)
+        .add_part(
+            id,
+            payload.part_name,
+            payload.quantity,
+            payload.unit_cost,
+            &claims,
+        )
         .await?;
     Ok(Json(part))
 }
@@ -338,7 +370,10 @@ pub async fn remove_work_order_part(
 ) -> Result<Json<ApiResponse<bool>>, AppError> {
     check_role(&claims, ROLE_OPERATOR)?;
 
-    state.work_order_service.remove_part(part_id, id).await?;
+    state
+        .work_order_service
+        .remove_part(part_id, id, &claims)
+        .await?;
     Ok(Json(ApiResponse::success_with_message(
         true,
         "Part removed",
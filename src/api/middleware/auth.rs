@@ -1,17 +1,26 @@
 //! Auth Middleware
 
+use std::net::SocketAddr;
+
 use axum::{
-    extract::Request,
-    http::{header, StatusCode},
+    extract::{ConnectInfo, Request, State},
+    http::{header, Method, StatusCode},
     middleware::Next,
     response::Response,
 };
 
-use crate::domain::entities::UserClaims;
-use crate::shared::utils::jwt::{decode_token, JwtConfig};
+use crate::api::middleware::client_ip;
+use crate::api::server::AppState;
+use crate::domain::entities::API_KEY_PREFIX;
+use crate::infrastructure::repositories::ApiKeyRequestLog;
 
-/// Auth middleware
-pub async fn auth_middleware(mut request: Request, next: Next) -> Result<Response, StatusCode> {
+/// Auth middleware: a valid access token whose session is still live, or an
+/// API key. State-changing requests made with a key are audited against it.
+pub async fn auth_middleware(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
     let auth_header = request
         .headers()
         .get(header::AUTHORIZATION)
@@ -22,11 +31,54 @@ pub async fn auth_middleware(mut request: Request, next: Next) -> Result<Respons
         .strip_prefix("Bearer ")
         .ok_or(StatusCode::UNAUTHORIZED)?;
 
-    let config = JwtConfig::from_env();
+    if !token.starts_with(API_KEY_PREFIX) {
+        let claims = state
+            .auth_service
+            .authenticate(token)
+            .await
+            .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+        request.extensions_mut().insert(claims);
+        return Ok(next.run(request).await);
+    }
+
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr);
+    let ip = client_ip(request.headers(), peer);
+    let claims = state
+        .api_key_service
+        .authenticate(token, ip.as_deref())
+        .await
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let user_agent = request
+        .headers()
+        .get(header::USER_AGENT)
+        .and_then(|h| h.to_str().ok())
+        .map(str::to_string);
 
-    let _claims: UserClaims = decode_token(token, &config).map_err(|_| StatusCode::UNAUTHORIZED)?;
+    request.extensions_mut().insert(claims.clone());
+    let response = next.run(request).await;
 
-    request.extensions_mut().insert(_claims);
+    if !matches!(method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        state
+            .api_key_service
+            .record_request(
+                &claims,
+                &ApiKeyRequestLog {
+                    method: method.as_str(),
+                    path: &path,
+                    status: response.status().as_u16(),
+                    ip_address: ip.as_deref(),
+                    user_agent: user_agent.as_deref(),
+                },
+            )
+            .await;
+    }
 
-    Ok(next.run(request).await)
+    Ok(response)
 }
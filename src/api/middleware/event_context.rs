@@ -0,0 +1,61 @@
+//! Event Context Middleware
+//!
+//! Correlates the domain events raised by a request. `X-Correlation-ID` is
+//! passed along by clients across the requests of one business transaction;
+//! `X-Request-ID` names the request itself and becomes the events' cause.
+//! Both are generated when absent and echoed in the response.
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+
+use crate::domain::entities::UserClaims;
+use crate::infrastructure::messaging::EventContext;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+pub const CORRELATION_ID_HEADER: HeaderName = HeaderName::from_static("x-correlation-id");
+
+/// Longest id accepted from a client
+const MAX_ID_LENGTH: usize = 128;
+
+/// Runs behind the auth middleware, so events also name the signed-in user
+pub async fn event_context_middleware(request: Request, next: Next) -> Response {
+    let request_id =
+        header(&request, &REQUEST_ID_HEADER).unwrap_or_else(|| Uuid::new_v4().to_string());
+    let correlation_id =
+        header(&request, &CORRELATION_ID_HEADER).unwrap_or_else(|| request_id.clone());
+    let user_id = request
+        .extensions()
+        .get::<UserClaims>()
+        .map(|claims| claims.user_id());
+
+    let context = EventContext {
+        correlation_id: correlation_id.clone(),
+        causation_id: request_id.clone(),
+        user_id,
+    };
+    let mut response = context.scope(next.run(request)).await;
+
+    for (name, value) in [
+        (REQUEST_ID_HEADER, request_id),
+        (CORRELATION_ID_HEADER, correlation_id),
+    ] {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            response.headers_mut().insert(name, value);
+        }
+    }
+    response
+}
+
+fn header(request: &Request, name: &HeaderName) -> Option<String> {
+    request
+        .headers()
+        .get(name)
+        .and_then(|h| h.to_str().ok())
+        .filter(|v| !v.is_empty() && v.len() <= MAX_ID_LENGTH)
+        .map(str::to_string)
+}
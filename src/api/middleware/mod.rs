@@ -1,11 +1,14 @@
 //! API Middleware
 
 pub mod auth;
+pub mod event_context;
 pub mod rate_limit;
 pub mod rbac;
 
 // Explicitly export to avoid ambiguity
 pub use auth::auth_middleware;
+pub use event_context::event_context_middleware;
+pub use rate_limit::{client_ip, RateLimitKey, RateLimitLayer};
 pub use rbac::{
     admin_only_middleware, extract_user_claims, org_scope_middleware, permission_middleware,
 };
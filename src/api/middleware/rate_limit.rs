@@ -1,2 +1,164 @@
 //! Rate Limit Middleware
-//! Placeholder for rate limiting with Redis
+//!
+//! Tower layer applying a token bucket policy per client address or per user.
+//! Rejected requests get `429 Too Many Requests` with `Retry-After`.
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::OnceLock;
+use std::task::{Context, Poll};
+
+use axum::{
+    extract::{ConnectInfo, Request},
+    http::{HeaderMap, HeaderValue},
+    response::{IntoResponse, Response},
+};
+use tower::{Layer, Service};
+
+use crate::domain::entities::{RateLimitPolicy, UserClaims};
+use crate::domain::errors::DomainError;
+use crate::infrastructure::cache::RateLimiter;
+use crate::shared::errors::AppError;
+
+/// Whose bucket a request draws from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitKey {
+    ClientIp,
+    /// The authenticated user, falling back to the client address
+    User,
+}
+
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    limiter: RateLimiter,
+    policy: RateLimitPolicy,
+    key: RateLimitKey,
+}
+
+impl RateLimitLayer {
+    pub fn new(limiter: RateLimiter, policy: RateLimitPolicy, key: RateLimitKey) -> Self {
+        Self {
+            limiter,
+            policy,
+            key,
+        }
+    }
+
+    pub fn per_ip(limiter: RateLimiter, policy: RateLimitPolicy) -> Self {
+        Self::new(limiter, policy, RateLimitKey::ClientIp)
+    }
+
+    /// Must sit inside `auth_middleware` to see the user
+    pub fn per_user(limiter: RateLimiter, policy: RateLimitPolicy) -> Self {
+        Self::new(limiter, policy, RateLimitKey::User)
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    layer: RateLimitLayer,
+}
+
+impl<S> Service<Request> for RateLimitService<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // The clone may not be ready; keep the one that was polled
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let layer = self.layer.clone();
+
+        Box::pin(async move {
+            let subject = match layer.key {
+                RateLimitKey::User => request
+                    .extensions()
+                    .get::<UserClaims>()
+                    .map(|claims| format!("user:{}", claims.sub))
+                    .or_else(|| request_ip(&request).map(|ip| format!("ip:{}", ip))),
+                RateLimitKey::ClientIp => request_ip(&request).map(|ip| format!("ip:{}", ip)),
+            };
+            // Without a client address (e.g. in-process calls) there is nothing to key on
+            let Some(subject) = subject else {
+                return inner.call(request).await;
+            };
+
+            let decision = layer.limiter.check(&layer.policy, &subject).await;
+            if !decision.allowed {
+                tracing::warn!("Rate limit '{}' exceeded by {}", layer.policy.name, subject);
+                return Ok(AppError::from(DomainError::too_many_requests(
+                    "Too many requests, slow down",
+                    decision.retry_after_secs,
+                ))
+                .into_response());
+            }
+
+            let mut response = inner.call(request).await?;
+            let headers = response.headers_mut();
+            headers.insert(
+                "x-ratelimit-limit",
+                HeaderValue::from(layer.policy.capacity),
+            );
+            headers.insert(
+                "x-ratelimit-remaining",
+                HeaderValue::from(decision.remaining),
+            );
+            Ok(response)
+        })
+    }
+}
+
+fn request_ip(request: &Request) -> Option<String> {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr);
+    client_ip(request.headers(), peer)
+}
+
+/// Client address of a request: the peer address, or the first
+/// `X-Forwarded-For` entry when running behind a trusted proxy
+/// (`TRUST_PROXY_HEADERS=true`)
+pub fn client_ip(headers: &HeaderMap, peer: Option<SocketAddr>) -> Option<String> {
+    static TRUST_PROXY: OnceLock<bool> = OnceLock::new();
+    let trust_proxy = *TRUST_PROXY.get_or_init(|| {
+        std::env::var("TRUST_PROXY_HEADERS")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false)
+    });
+
+    if trust_proxy {
+        let forwarded = headers
+            .get("x-forwarded-for")
+            .and_then(|h| h.to_str().ok())
+            .and_then(|h| h.split(',').next())
+            .map(|ip| ip.trim().to_string())
+            .filter(|ip| !ip.is_empty());
+        if forwarded.is_some() {
+            return forwarded;
+        }
+    }
+    peer.map(|addr| addr.ip().to_string())
+}
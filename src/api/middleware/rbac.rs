@@ -9,7 +9,7 @@ use axum::{
 use uuid::Uuid;
 
 use crate::api::server::AppState;
-use crate::domain::entities::UserClaims;
+use crate::domain::entities::{TenantResource, UserClaims};
 use crate::shared::utils::jwt::{decode_token, JwtConfig};
 
 /// Extract user claims from request
@@ -107,11 +107,11 @@ pub async fn permission_middleware(
     Ok(next.run(request).await)
 }
 
-/// Admin-only middleware
+/// Admin-only middleware; API keys are limited to their scopes and never pass
 pub async fn admin_only_middleware(request: Request, next: Next) -> Result<Response, StatusCode> {
     let claims = extract_user_claims(&request).ok_or(StatusCode::UNAUTHORIZED)?;
 
-    if claims.role == "admin" || claims.role == "super_admin" {
+    if (claims.role == "admin" || claims.role == "super_admin") && !claims.is_api_key() {
         Ok(next.run(request).await)
     } else {
         Err(StatusCode::FORBIDDEN)
@@ -119,17 +119,38 @@ pub async fn admin_only_middleware(request: Request, next: Next) -> Result<Respo
 }
 
 /// Organization scope middleware - ensures user can only access their org's data
-pub async fn org_scope_middleware(request: Request, next: Next) -> Result<Response, StatusCode> {
+///
+/// Requests addressing an asset, loan, rental or work order by id outside the
+/// subtree of the active organization are answered as not found.
+pub async fn org_scope_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
     let claims = extract_user_claims(&request).ok_or(StatusCode::UNAUTHORIZED)?;
 
-    // Super admin can access everything
-    if claims.role == "super_admin" {
-        return Ok(next.run(request).await);
-    }
-
-    // For other users, org_id must be present
-    if claims.org.is_none() {
+    let Some(org_id) = claims.organization_id() else {
+        // Super admin without an active organization can access everything
+        if claims.role == "super_admin" {
+            return Ok(next.run(request).await);
+        }
+        // For other users, org_id must be present
         return Err(StatusCode::FORBIDDEN);
+    };
+
+    // /api/<resource>/<id>/...
+    let mut segments = request.uri().path().split('/').skip(2);
+    let resource = segments.next().and_then(TenantResource::from_path_segment);
+    let id = segments.next().and_then(|s| Uuid::parse_str(s).ok());
+    if let (Some(resource), Some(id)) = (resource, id) {
+        let visible = state
+            .organization_service
+            .resource_visible(resource, id, Some(org_id))
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        if !visible {
+            return Err(StatusCode::NOT_FOUND);
+        }
     }
 
     Ok(next.run(request).await)
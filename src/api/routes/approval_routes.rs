@@ -1,9 +1,12 @@
 use axum::{
+    handler::Handler,
+    middleware as axum_middleware,
     routing::{get, post},
     Router,
 };
 
-use crate::api::handlers::approval_handler;
+use crate::api::handlers::{approval_handler, approval_workflow_handler};
+use crate::api::middleware::rbac::require_permission;
 use crate::api::server::AppState;
 
 pub fn approval_routes(_state: AppState) -> Router<AppState> {
@@ -28,4 +31,38 @@ pub fn approval_routes(_state: AppState) -> Router<AppState> {
             "/api/approvals/:id/reject",
             post(approval_handler::reject_request),
         )
+        .route(
+            "/api/approvals/:id/actions",
+            get(approval_handler::list_request_actions),
+        )
+        // Workflow administration
+        .route(
+            "/api/approval-workflows",
+            get(approval_workflow_handler::list_approval_workflows.layer(
+                axum_middleware::from_fn(require_permission("approval_workflow.read")),
+            ))
+            .post(approval_workflow_handler::create_approval_workflow.layer(
+                axum_middleware::from_fn(require_permission("approval_workflow.manage")),
+            )),
+        )
+        .route(
+            "/api/approval-workflows/resolve",
+            get(approval_workflow_handler::resolve_approval_workflow.layer(
+                axum_middleware::from_fn(require_permission("approval_workflow.read")),
+            )),
+        )
+        .route(
+            "/api/approval-workflows/:id",
+            get(
+                approval_workflow_handler::get_approval_workflow.layer(axum_middleware::from_fn(
+                    require_permission("approval_workflow.read"),
+                )),
+            )
+            .put(approval_workflow_handler::update_approval_workflow.layer(
+                axum_middleware::from_fn(require_permission("approval_workflow.manage")),
+            ))
+            .delete(approval_workflow_handler::delete_approval_workflow.layer(
+                axum_middleware::from_fn(require_permission("approval_workflow.manage")),
+            )),
+        )
 }
@@ -0,0 +1,44 @@
+use axum::{handler::Handler, middleware as axum_middleware, routing::get, Router};
+
+use crate::api::handlers::budget_handler;
+use crate::api::middleware::rbac::require_permission;
+use crate::api::server::AppState;
+
+pub fn budget_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/budgets",
+            get(budget_handler::list_budget_lines
+                .layer(axum_middleware::from_fn(require_permission("budget.read"))))
+            .post(
+                budget_handler::create_budget_line.layer(axum_middleware::from_fn(
+                    require_permission("budget.manage"),
+                )),
+            ),
+        )
+        .route(
+            "/api/budgets/report",
+            get(budget_handler::budget_report
+                .layer(axum_middleware::from_fn(require_permission("budget.read")))),
+        )
+        .route(
+            "/api/budgets/:id",
+            get(budget_handler::get_budget_line
+                .layer(axum_middleware::from_fn(require_permission("budget.read"))))
+            .put(
+                budget_handler::update_budget_line.layer(axum_middleware::from_fn(
+                    require_permission("budget.manage"),
+                )),
+            )
+            .delete(
+                budget_handler::delete_budget_line.layer(axum_middleware::from_fn(
+                    require_permission("budget.manage"),
+                )),
+            ),
+        )
+        .route(
+            "/api/budgets/:id/entries",
+            get(budget_handler::list_budget_entries
+                .layer(axum_middleware::from_fn(require_permission("budget.read")))),
+        )
+}
@@ -0,0 +1,77 @@
+use axum::{
+    handler::Handler,
+    middleware as axum_middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::api::handlers::depreciation_handler;
+use crate::api::middleware::rbac::require_permission;
+use crate::api::server::AppState;
+
+pub fn depreciation_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/assets/:id/depreciation",
+            get(depreciation_handler::get_asset_depreciation_schedule
+                .layer(axum_middleware::from_fn(require_permission("asset.read")))),
+        )
+        .route(
+            "/api/assets/:id/depreciation/recalculate",
+            post(depreciation_handler::recalculate_asset_depreciation.layer(
+                axum_middleware::from_fn(require_permission("depreciation.manage")),
+            )),
+        )
+        .route(
+            "/api/depreciation/positions",
+            get(
+                depreciation_handler::list_depreciation_positions.layer(axum_middleware::from_fn(
+                    require_permission("depreciation.read"),
+                )),
+            ),
+        )
+        .route(
+            "/api/depreciation/totals",
+            get(
+                depreciation_handler::get_depreciation_totals.layer(axum_middleware::from_fn(
+                    require_permission("depreciation.read"),
+                )),
+            ),
+        )
+        .route(
+            "/api/depreciation/recalculate",
+            post(depreciation_handler::recalculate_all_depreciation.layer(
+                axum_middleware::from_fn(require_permission("depreciation.manage")),
+            )),
+        )
+        .route(
+            "/api/depreciation/periods",
+            get(
+                depreciation_handler::list_depreciation_periods.layer(axum_middleware::from_fn(
+                    require_permission("depreciation.read"),
+                )),
+            ),
+        )
+        .route(
+            "/api/depreciation/periods/:period/close",
+            post(
+                depreciation_handler::close_depreciation_period.layer(axum_middleware::from_fn(
+                    require_permission("depreciation.close"),
+                )),
+            ),
+        )
+        .route(
+            "/api/depreciation/periods/:period/reopen",
+            post(
+                depreciation_handler::reopen_depreciation_period.layer(axum_middleware::from_fn(
+                    require_permission("depreciation.close"),
+                )),
+            ),
+        )
+        .route(
+            "/api/depreciation/periods/:period/report",
+            get(depreciation_handler::get_depreciation_close_report.layer(
+                axum_middleware::from_fn(require_permission("depreciation.read")),
+            )),
+        )
+}
@@ -0,0 +1,23 @@
+use axum::{handler::Handler, middleware as axum_middleware, routing::get, Router};
+
+use crate::api::handlers::document_number_handler;
+use crate::api::middleware::rbac::admin_only_middleware;
+use crate::api::server::AppState;
+
+pub fn document_number_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/document-numbers",
+            get(document_number_handler::list_document_number_formats
+                .layer(axum_middleware::from_fn(admin_only_middleware))),
+        )
+        .route(
+            "/api/document-numbers/:document_type",
+            get(document_number_handler::get_document_number_format
+                .layer(axum_middleware::from_fn(admin_only_middleware)))
+            .put(
+                document_number_handler::update_document_number_format
+                    .layer(axum_middleware::from_fn(admin_only_middleware)),
+            ),
+        )
+}
@@ -0,0 +1,52 @@
+use axum::{
+    extract::DefaultBodyLimit, handler::Handler, middleware as axum_middleware, routing::get,
+    Router,
+};
+
+use crate::api::handlers::document_handler;
+use crate::api::handlers::upload_handler::MAX_UPLOAD_SIZE;
+use crate::api::middleware::rbac::require_permission;
+use crate::api::server::AppState;
+
+pub fn document_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/assets/:id/documents",
+            get(document_handler::list_asset_documents
+                .layer(axum_middleware::from_fn(require_permission("asset.read"))))
+            .post(
+                document_handler::upload_asset_document
+                    .layer(DefaultBodyLimit::max(MAX_UPLOAD_SIZE as usize + 64 * 1024))
+                    .layer(axum_middleware::from_fn(require_permission("asset.update"))),
+            ),
+        )
+        .route(
+            "/api/assets/:id/documents/:doc_id",
+            get(document_handler::get_asset_document
+                .layer(axum_middleware::from_fn(require_permission("asset.read"))))
+            .delete(
+                document_handler::delete_asset_document
+                    .layer(axum_middleware::from_fn(require_permission("asset.update"))),
+            ),
+        )
+        .route(
+            "/api/assets/:id/documents/:doc_id/versions",
+            get(document_handler::list_document_versions
+                .layer(axum_middleware::from_fn(require_permission("asset.read"))))
+            .post(
+                document_handler::upload_document_version
+                    .layer(DefaultBodyLimit::max(MAX_UPLOAD_SIZE as usize + 64 * 1024))
+                    .layer(axum_middleware::from_fn(require_permission("asset.update"))),
+            ),
+        )
+        .route(
+            "/api/assets/:id/documents/:doc_id/download",
+            get(document_handler::download_asset_document
+                .layer(axum_middleware::from_fn(require_permission("asset.read")))),
+        )
+        .route(
+            "/api/documents/expiring",
+            get(document_handler::list_expiring_documents
+                .layer(axum_middleware::from_fn(require_permission("asset.read")))),
+        )
+}
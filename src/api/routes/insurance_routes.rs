@@ -0,0 +1,86 @@
+use axum::{
+    handler::Handler,
+    middleware as axum_middleware,
+    routing::{get, put},
+    Router,
+};
+
+use crate::api::handlers::insurance_handler;
+use crate::api::middleware::rbac::require_permission;
+use crate::api::server::AppState;
+
+pub fn insurance_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/assets/:id/insurances",
+            get(
+                insurance_handler::list_asset_insurances.layer(axum_middleware::from_fn(
+                    require_permission("insurance.read"),
+                )),
+            )
+            .post(insurance_handler::create_asset_insurance.layer(
+                axum_middleware::from_fn(require_permission("insurance.manage")),
+            )),
+        )
+        .route(
+            "/api/insurances/expiring",
+            get(
+                insurance_handler::list_expiring_insurances.layer(axum_middleware::from_fn(
+                    require_permission("insurance.read"),
+                )),
+            ),
+        )
+        .route(
+            "/api/insurances/lapsed",
+            get(
+                insurance_handler::list_lapsed_insurance_assets.layer(axum_middleware::from_fn(
+                    require_permission("insurance.read"),
+                )),
+            ),
+        )
+        .route(
+            "/api/insurances/coverage-report",
+            get(
+                insurance_handler::get_insurance_coverage_report.layer(axum_middleware::from_fn(
+                    require_permission("insurance.read"),
+                )),
+            ),
+        )
+        .route(
+            "/api/insurances/:id",
+            get(
+                insurance_handler::get_insurance.layer(axum_middleware::from_fn(
+                    require_permission("insurance.read"),
+                )),
+            )
+            .put(
+                insurance_handler::update_insurance.layer(axum_middleware::from_fn(
+                    require_permission("insurance.manage"),
+                )),
+            )
+            .delete(
+                insurance_handler::delete_insurance.layer(axum_middleware::from_fn(
+                    require_permission("insurance.manage"),
+                )),
+            ),
+        )
+        .route(
+            "/api/insurances/:id/claims",
+            get(
+                insurance_handler::list_insurance_claims.layer(axum_middleware::from_fn(
+                    require_permission("insurance.read"),
+                )),
+            )
+            .post(insurance_handler::create_insurance_claim.layer(
+                axum_middleware::from_fn(require_permission("insurance.manage")),
+            )),
+        )
+        .route(
+            "/api/insurance-claims/:id/status",
+            put(
+                insurance_handler::update_insurance_claim_status.layer(axum_middleware::from_fn(
+                    require_permission("insurance.manage"),
+                )),
+            ),
+        )
+}
@@ -3,12 +3,23 @@
 pub mod analytics_routes;
 pub mod approval_routes;
 pub mod billing_routes;
+pub mod budget_routes;
 pub mod category_routes;
 pub mod client_routes;
 pub mod conversion_routes;
+pub mod depreciation_routes;
+pub mod document_number_routes;
+pub mod document_routes;
+pub mod insurance_routes;
+pub mod notification_routes;
+pub mod organization_routes;
+pub mod preventive_schedule_routes;
 pub mod rental_routes;
 pub mod routes;
+pub mod specification_routes;
 pub mod timesheet_routes;
+pub mod valuation_routes;
+pub mod webhook_routes;
 
 pub use routes::*;
 pub mod data_routes;
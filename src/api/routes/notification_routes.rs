@@ -0,0 +1,85 @@
+use axum::{
+    handler::Handler,
+    middleware as axum_middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::api::handlers::{notification_handler, notification_template_handler};
+use crate::api::middleware::rbac::require_permission;
+use crate::api::server::AppState;
+
+pub fn notification_routes() -> Router<AppState> {
+    Router::new()
+        // Current user's channel preferences
+        .route(
+            "/api/notifications/preferences",
+            get(notification_handler::list_my_notification_preferences)
+                .put(notification_handler::update_my_notification_preference),
+        )
+        // Template administration
+        .route(
+            "/api/notification-templates",
+            get(
+                notification_template_handler::list_notification_templates.layer(
+                    axum_middleware::from_fn(require_permission("notification_template.read")),
+                ),
+            )
+            .post(
+                notification_template_handler::create_notification_template.layer(
+                    axum_middleware::from_fn(require_permission("notification_template.manage")),
+                ),
+            ),
+        )
+        .route(
+            "/api/notification-templates/:id",
+            get(
+                notification_template_handler::get_notification_template.layer(
+                    axum_middleware::from_fn(require_permission("notification_template.read")),
+                ),
+            )
+            .put(
+                notification_template_handler::update_notification_template.layer(
+                    axum_middleware::from_fn(require_permission("notification_template.manage")),
+                ),
+            )
+            .delete(
+                notification_template_handler::delete_notification_template.layer(
+                    axum_middleware::from_fn(require_permission("notification_template.manage")),
+                ),
+            ),
+        )
+        .route(
+            "/api/notification-templates/:id/preview",
+            post(
+                notification_template_handler::preview_notification_template.layer(
+                    axum_middleware::from_fn(require_permission("notification_template.read")),
+                ),
+            ),
+        )
+        // Delivery log
+        .route(
+            "/api/notification-deliveries",
+            get(
+                notification_template_handler::list_notification_deliveries.layer(
+                    axum_middleware::from_fn(require_permission("notification_template.read")),
+                ),
+            ),
+        )
+        .route(
+            "/api/notification-deliveries/:id/retry",
+            post(
+                notification_template_handler::retry_notification_delivery.layer(
+                    axum_middleware::from_fn(require_permission("notification_template.manage")),
+                ),
+            ),
+        )
+        .route(
+            "/api/notification-digests/:frequency/send",
+            post(
+                notification_template_handler::send_notification_digests.layer(
+                    axum_middleware::from_fn(require_permission("notification_template.manage")),
+                ),
+            ),
+        )
+}
@@ -0,0 +1,61 @@
+use axum::{
+    handler::Handler,
+    middleware as axum_middleware,
+    routing::{delete, get, post},
+    Router,
+};
+
+use crate::api::handlers::{auth_handler, organization_handler};
+use crate::api::middleware::rbac::admin_only_middleware;
+use crate::api::server::AppState;
+
+pub fn organization_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/organizations",
+            get(organization_handler::list_organizations
+                .layer(axum_middleware::from_fn(admin_only_middleware)))
+            .post(
+                organization_handler::create_organization
+                    .layer(axum_middleware::from_fn(admin_only_middleware)),
+            ),
+        )
+        .route(
+            "/api/organizations/:id",
+            get(organization_handler::get_organization
+                .layer(axum_middleware::from_fn(admin_only_middleware)))
+            .put(
+                organization_handler::update_organization
+                    .layer(axum_middleware::from_fn(admin_only_middleware)),
+            )
+            .delete(
+                organization_handler::delete_organization
+                    .layer(axum_middleware::from_fn(admin_only_middleware)),
+            ),
+        )
+        .route(
+            "/api/organizations/:id/members",
+            get(organization_handler::list_organization_members
+                .layer(axum_middleware::from_fn(admin_only_middleware)))
+            .post(
+                organization_handler::add_organization_member
+                    .layer(axum_middleware::from_fn(admin_only_middleware)),
+            ),
+        )
+        .route(
+            "/api/organizations/:id/members/:user_id",
+            delete(
+                organization_handler::remove_organization_member
+                    .layer(axum_middleware::from_fn(admin_only_middleware)),
+            ),
+        )
+        // Any user: own memberships and switching the active organization
+        .route(
+            "/api/me/organizations",
+            get(organization_handler::list_my_organizations),
+        )
+        .route(
+            "/api/auth/switch-organization",
+            post(auth_handler::switch_organization),
+        )
+}
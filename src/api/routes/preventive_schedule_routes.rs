@@ -0,0 +1,59 @@
+//! Preventive Schedule Routes
+
+use axum::{
+    handler::Handler,
+    middleware as axum_middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::api::handlers::preventive_schedule_handler::*;
+use crate::api::middleware::rbac::require_permission;
+use crate::api::server::AppState;
+
+pub fn preventive_schedule_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/assets/:asset_id/preventive-schedules",
+            get(
+                list_asset_preventive_schedules.layer(axum_middleware::from_fn(
+                    require_permission("preventive_schedule.read"),
+                )),
+            )
+            .post(create_preventive_schedule.layer(axum_middleware::from_fn(
+                require_permission("preventive_schedule.create"),
+            ))),
+        )
+        .route(
+            "/api/preventive-schedules/due",
+            get(
+                list_due_preventive_schedules.layer(axum_middleware::from_fn(require_permission(
+                    "preventive_schedule.read",
+                ))),
+            ),
+        )
+        .route(
+            "/api/preventive-schedules/generate",
+            post(
+                generate_preventive_work_orders.layer(axum_middleware::from_fn(
+                    require_permission("preventive_schedule.create"),
+                )),
+            ),
+        )
+        .route(
+            "/api/preventive-schedules/:id",
+            get(
+                get_preventive_schedule.layer(axum_middleware::from_fn(require_permission(
+                    "preventive_schedule.read",
+                ))),
+            )
+            .put(
+                update_preventive_schedule.layer(axum_middleware::from_fn(require_permission(
+                    "preventive_schedule.update",
+                ))),
+            )
+            .delete(delete_preventive_schedule.layer(axum_middleware::from_fn(
+                require_permission("preventive_schedule.delete"),
+            ))),
+        )
+}
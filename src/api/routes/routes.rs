@@ -10,17 +10,66 @@ use axum::{
 
 use crate::api::handlers::*;
 use crate::api::middleware::{
-    auth_middleware,
-    rbac::{admin_only_middleware, require_permission},
+    auth_middleware, event_context_middleware,
+    rbac::{admin_only_middleware, org_scope_middleware, require_permission},
+    RateLimitLayer,
 };
 use crate::api::server::AppState;
-use crate::domain::entities::UserClaims;
+use crate::domain::entities::{UserClaims, API_RATE_LIMIT, LOGIN_RATE_LIMIT};
 
 pub fn create_router(state: AppState) -> Router {
     // Public routes
     let public_routes = Router::new()
         .route("/health", get(health_check))
-        .route("/api/auth/login", post(login))
+        .route(
+            "/api/auth/login",
+            post(login).layer(RateLimitLayer::per_ip(
+                state.rate_limiter.clone(),
+                LOGIN_RATE_LIMIT,
+            )),
+        )
+        .route(
+            "/api/auth/refresh",
+            post(refresh_token).layer(RateLimitLayer::per_ip(
+                state.rate_limiter.clone(),
+                LOGIN_RATE_LIMIT,
+            )),
+        )
+        .route(
+            "/api/auth/2fa/setup",
+            post(two_factor_handler::setup_challenge).layer(RateLimitLayer::per_ip(
+                state.rate_limiter.clone(),
+                LOGIN_RATE_LIMIT,
+            )),
+        )
+        .route(
+            "/api/auth/2fa/verify",
+            post(two_factor_handler::verify).layer(RateLimitLayer::per_ip(
+                state.rate_limiter.clone(),
+                LOGIN_RATE_LIMIT,
+            )),
+        )
+        .route(
+            "/api/auth/password/forgot",
+            post(password_handler::forgot).layer(RateLimitLayer::per_ip(
+                state.rate_limiter.clone(),
+                LOGIN_RATE_LIMIT,
+            )),
+        )
+        .route(
+            "/api/auth/password/reset",
+            post(password_handler::reset).layer(RateLimitLayer::per_ip(
+                state.rate_limiter.clone(),
+                LOGIN_RATE_LIMIT,
+            )),
+        )
+        .route(
+            "/api/auth/password/change",
+            post(password_handler::change_required).layer(RateLimitLayer::per_ip(
+                state.rate_limiter.clone(),
+                LOGIN_RATE_LIMIT,
+            )),
+        )
         .route(
             "/api/upload",
             post(upload_handler::upload_file).layer(tower_http::limit::RequestBodyLimitLayer::new(
@@ -115,7 +164,7 @@ pub fn create_router(state: AppState) -> Router {
             "/api/loans/my",
             get(
                 |state, Extension(claims): Extension<UserClaims>| async move {
-                    list_my_loans(state, Path(claims.user_id())).await
+                    list_my_loans(state, Extension(claims.clone()), Path(claims.user_id())).await
                 },
             ),
         )
@@ -150,6 +199,7 @@ pub fn create_router(state: AppState) -> Router {
             get(list_users.layer(axum_middleware::from_fn(admin_only_middleware)))
                 .post(create_user.layer(axum_middleware::from_fn(admin_only_middleware))),
         )
+        .route("/api/auth/logout", post(logout))
         // Profile Routes (Checked for protected_routes and auth_middleware coverage)
         .route(
             "/api/me",
@@ -157,11 +207,66 @@ pub fn create_router(state: AppState) -> Router {
         )
         .route("/api/me/password", put(profile_handler::change_password))
         .route("/api/me/avatar", post(profile_handler::upload_avatar))
+        .route("/api/me/2fa", get(two_factor_handler::get_status))
+        .route("/api/me/2fa/setup", post(two_factor_handler::setup))
+        .route("/api/me/2fa/enable", post(two_factor_handler::enable))
+        .route("/api/me/2fa/disable", post(two_factor_handler::disable))
+        .route(
+            "/api/me/2fa/recovery-codes",
+            post(two_factor_handler::regenerate_recovery_codes),
+        )
         .route(
             "/api/users/:id",
             put(update_user.layer(axum_middleware::from_fn(admin_only_middleware)))
                 .delete(delete_user.layer(axum_middleware::from_fn(admin_only_middleware))),
         )
+        .route(
+            "/api/users/:id/revoke-sessions",
+            post(revoke_user_sessions.layer(axum_middleware::from_fn(admin_only_middleware))),
+        )
+        .route(
+            "/api/users/:id/unlock",
+            post(unlock_user.layer(axum_middleware::from_fn(admin_only_middleware))),
+        )
+        // API keys and service accounts
+        .route(
+            "/api/me/api-keys",
+            get(api_key_handler::list_my_keys).post(api_key_handler::create_my_key),
+        )
+        .route("/api/api-keys/:id", delete(api_key_handler::revoke_key))
+        .route(
+            "/api/api-keys/:id/rotate",
+            post(api_key_handler::rotate_key),
+        )
+        .route(
+            "/api/service-accounts",
+            get(api_key_handler::list_service_accounts
+                .layer(axum_middleware::from_fn(admin_only_middleware)))
+            .post(
+                api_key_handler::create_service_account
+                    .layer(axum_middleware::from_fn(admin_only_middleware)),
+            ),
+        )
+        .route(
+            "/api/service-accounts/:id/api-keys",
+            get(api_key_handler::list_service_account_keys
+                .layer(axum_middleware::from_fn(admin_only_middleware)))
+            .post(
+                api_key_handler::create_service_account_key
+                    .layer(axum_middleware::from_fn(admin_only_middleware)),
+            ),
+        )
+        .route(
+            "/api/users/:id/2fa/reset",
+            post(two_factor_handler::reset.layer(axum_middleware::from_fn(admin_only_middleware))),
+        )
+        .route(
+            "/api/users/:id/force-password-reset",
+            post(
+                password_handler::force_reset
+                    .layer(axum_middleware::from_fn(admin_only_middleware)),
+            ),
+        )
         // Employees
         .route("/api/employees", get(list_employees).post(create_employee))
         .route(
@@ -185,17 +290,44 @@ pub fn create_router(state: AppState) -> Router {
         )
         // I'll rewrite this block more cleanly
         // RBAC
-        .route("/api/rbac/roles", get(list_roles))
+        .route(
+            "/api/rbac/roles",
+            get(list_roles)
+                .post(create_role.layer(axum_middleware::from_fn(admin_only_middleware))),
+        )
         .route("/api/rbac/permissions", get(list_permissions))
+        .route(
+            "/api/rbac/roles/:role_id",
+            get(get_role)
+                .put(update_role.layer(axum_middleware::from_fn(admin_only_middleware)))
+                .delete(delete_role.layer(axum_middleware::from_fn(admin_only_middleware))),
+        )
+        .route(
+            "/api/rbac/roles/:role_id/clone",
+            post(clone_role.layer(axum_middleware::from_fn(admin_only_middleware))),
+        )
         .route(
             "/api/rbac/roles/:role_id/permissions",
-            get(get_role_permissions),
+            get(get_role_permissions)
+                .put(set_role_permissions.layer(axum_middleware::from_fn(admin_only_middleware)))
+                .post(
+                    attach_role_permission.layer(axum_middleware::from_fn(admin_only_middleware)),
+                ),
+        )
+        .route(
+            "/api/rbac/roles/:role_id/permissions/preview",
+            post(preview_role_permissions.layer(axum_middleware::from_fn(admin_only_middleware))),
+        )
+        .route(
+            "/api/rbac/roles/:role_id/permissions/:permission_code",
+            delete(detach_role_permission.layer(axum_middleware::from_fn(admin_only_middleware))),
         )
         .route("/api/users/:user_id/roles", get(get_user_roles))
         .route("/api/users/:user_id/permissions", get(get_user_permissions))
         .route(
             "/api/users/:user_id/roles/:role_code",
-            post(assign_role).delete(remove_role),
+            post(assign_role.layer(axum_middleware::from_fn(admin_only_middleware)))
+                .delete(remove_role.layer(axum_middleware::from_fn(admin_only_middleware))),
         )
         // Sensors
         .route(
@@ -288,6 +420,15 @@ pub fn create_router(state: AppState) -> Router {
             state.clone(),
         ))
         .merge(crate::api::routes::location_routes::location_routes())
+        .merge(crate::api::routes::notification_routes::notification_routes())
+        .merge(crate::api::routes::depreciation_routes::depreciation_routes())
+        .merge(crate::api::routes::valuation_routes::valuation_routes())
+        .merge(crate::api::routes::insurance_routes::insurance_routes())
+        .merge(crate::api::routes::document_routes::document_routes())
+        .merge(crate::api::routes::document_number_routes::document_number_routes())
+        .merge(crate::api::routes::specification_routes::specification_routes())
+        .merge(crate::api::routes::organization_routes::organization_routes())
+        .merge(crate::api::routes::budget_routes::budget_routes())
         .merge(crate::api::routes::approval_routes::approval_routes(
             state.clone(),
         ))
@@ -297,10 +438,24 @@ pub fn create_router(state: AppState) -> Router {
         )
         .merge(crate::api::routes::rental_routes::rental_routes())
         .merge(crate::api::routes::client_routes::client_routes())
+        .merge(crate::api::routes::preventive_schedule_routes::preventive_schedule_routes())
         .merge(crate::api::routes::timesheet_routes::timesheet_routes())
         .merge(crate::api::routes::billing_routes::billing_routes())
         .merge(crate::api::routes::analytics_routes::routes())
-        .layer(axum_middleware::from_fn(auth_middleware));
+        .merge(crate::api::routes::webhook_routes::webhook_routes())
+        .layer(axum_middleware::from_fn(event_context_middleware))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            org_scope_middleware,
+        ))
+        .layer(RateLimitLayer::per_user(
+            state.rate_limiter.clone(),
+            API_RATE_LIMIT,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            auth_middleware,
+        ));
 
     Router::new()
         .merge(public_routes)
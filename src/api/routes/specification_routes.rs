@@ -0,0 +1,29 @@
+use axum::{
+    handler::Handler,
+    middleware as axum_middleware,
+    routing::{get, put},
+    Router,
+};
+
+use crate::api::handlers::specification_handler;
+use crate::api::middleware::rbac::require_permission;
+use crate::api::server::AppState;
+
+pub fn specification_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/assets/:id/specifications",
+            put(specification_handler::update_specifications
+                .layer(axum_middleware::from_fn(require_permission("asset.update")))),
+        )
+        .route(
+            "/api/assets/:id/specifications/history",
+            get(specification_handler::list_specification_history
+                .layer(axum_middleware::from_fn(require_permission("asset.read")))),
+        )
+        .route(
+            "/api/assets/:id/specifications/diff",
+            get(specification_handler::diff_specifications
+                .layer(axum_middleware::from_fn(require_permission("asset.read")))),
+        )
+}
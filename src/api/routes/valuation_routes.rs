@@ -0,0 +1,46 @@
+use axum::{
+    handler::Handler,
+    middleware as axum_middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::api::handlers::valuation_handler;
+use crate::api::middleware::rbac::require_permission;
+use crate::api::server::AppState;
+
+pub fn valuation_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/assets/:id/valuations",
+            get(
+                valuation_handler::list_asset_valuations.layer(axum_middleware::from_fn(
+                    require_permission("valuation.read"),
+                )),
+            ),
+        )
+        .route(
+            "/api/assets/:id/valuations/appraisals",
+            post(
+                valuation_handler::record_asset_appraisal.layer(axum_middleware::from_fn(
+                    require_permission("valuation.manage"),
+                )),
+            ),
+        )
+        .route(
+            "/api/assets/:id/valuations/impairments",
+            post(
+                valuation_handler::record_asset_impairment.layer(axum_middleware::from_fn(
+                    require_permission("valuation.manage"),
+                )),
+            ),
+        )
+        .route(
+            "/api/valuations/snapshots",
+            post(
+                valuation_handler::create_valuation_snapshot.layer(axum_middleware::from_fn(
+                    require_permission("valuation.manage"),
+                )),
+            ),
+        )
+}
@@ -0,0 +1,54 @@
+use axum::{
+    handler::Handler,
+    middleware as axum_middleware,
+    routing::{get, post},
+    Router,
+};
+
+use crate::api::handlers::webhook_handler;
+use crate::api::middleware::rbac::admin_only_middleware;
+use crate::api::server::AppState;
+
+pub fn webhook_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/webhooks",
+            get(webhook_handler::list_webhooks
+                .layer(axum_middleware::from_fn(admin_only_middleware)))
+            .post(
+                webhook_handler::create_webhook
+                    .layer(axum_middleware::from_fn(admin_only_middleware)),
+            ),
+        )
+        .route(
+            "/api/webhooks/:id",
+            get(webhook_handler::get_webhook
+                .layer(axum_middleware::from_fn(admin_only_middleware)))
+            .put(
+                webhook_handler::update_webhook
+                    .layer(axum_middleware::from_fn(admin_only_middleware)),
+            )
+            .delete(
+                webhook_handler::delete_webhook
+                    .layer(axum_middleware::from_fn(admin_only_middleware)),
+            ),
+        )
+        // Delivery log and dead letters
+        .route(
+            "/api/webhooks/deliveries",
+            get(webhook_handler::list_webhook_deliveries
+                .layer(axum_middleware::from_fn(admin_only_middleware))),
+        )
+        .route(
+            "/api/webhooks/dead-letters",
+            get(webhook_handler::list_webhook_dead_letters
+                .layer(axum_middleware::from_fn(admin_only_middleware))),
+        )
+        .route(
+            "/api/webhooks/deliveries/:id/redeliver",
+            post(
+                webhook_handler::redeliver_webhook_delivery
+                    .layer(axum_middleware::from_fn(admin_only_middleware)),
+            ),
+        )
+}
@@ -7,37 +7,74 @@ use tower_http::services::ServeDir;
 use crate::api::routes::create_router;
 use crate::application::services::{
     AnalyticsService,
+    ApiKeyService,
+    ApprovalExecutorRegistry,
     ApprovalService,
+    AssetCreateExecutor,
+    AssetDeleteExecutor,
     AssetService,
+    AssetUpdateExecutor,
     AuditService,
     AuthService,
     BillingService,
+    BudgetOverrunExecutor,
+    BudgetService,
     CategoryService,
     ClientService,
+    ConversionExecutor,
     ConversionService,
     DataService,
+    DepreciationReopenExecutor,
+    DepreciationService,
+    DocumentNumberService,
+    DocumentService,
     EmployeeService,
+    InsuranceService,
     LifecycleService,
+    LifecycleTransitionExecutor,
+    LoanExecutor,
     LoanService,
     LocationService, // Added
     MaintenanceService,
     NotificationService,
+    OrganizationService,
+    OutboxRelay,
+    PasswordService,
+    PreventiveMaintenanceService,
     RbacService,
+    RentalExecutor,
     RentalService,
     ReportService,
     SchedulerService,
     SensorService,
+    SpecificationService,
     TimesheetService,
     UserService,
+    ValuationService,
+    WebhookService,
+    WorkOrderRepositories,
     WorkOrderService,
 };
-use crate::infrastructure::cache::{CacheOperations, RedisCache, RedisConfig};
+use crate::domain::entities::{
+    BUDGET_OVERRUN_ACTION, BUDGET_RESOURCE_TYPE, CLOSING_REOPEN_ACTION, CLOSING_RESOURCE_TYPE,
+    LIFECYCLE_RESOURCE_TYPE,
+};
+use crate::infrastructure::cache::{CacheOperations, RateLimiter, RedisCache, RedisConfig};
+use crate::infrastructure::messaging::{
+    ClusterBus, EventPublisher, WebSocketManager, WebhookSender,
+};
+use crate::infrastructure::notifications::{NotificationChannels, NotificationConfig};
 use crate::infrastructure::repositories::{
-    ApprovalRepository, AssetRepository, AuditRepository, CategoryRepository, ClientRepository,
-    ConversionRepository, EmployeeRepository, LifecycleRepository, LoanRepository,
-    MaintenanceRepository, NotificationRepository, RbacRepository, RentalRepository,
-    SensorRepository, TimesheetRepository, UserRepository, WorkOrderRepository,
+    ApiKeyRepository, ApprovalRepository, ApprovalWorkflowRepository, AssetRepository,
+    AuditRepository, BudgetRepository, CategoryRepository, ClientRepository, ConversionRepository,
+    DepreciationRepository, DocumentNumberRepository, DocumentRepository, EmployeeRepository,
+    InsuranceRepository, LifecycleRepository, LoanRepository, MaintenanceRepository,
+    NotificationRepository, OrganizationRepository, OutboxRepository, PasswordRepository,
+    PreventiveScheduleRepository, RbacRepository, RentalRepository, SensorRepository,
+    SessionRepository, SpecificationRepository, TimesheetRepository, TwoFactorRepository,
+    UserRepository, ValuationRepository, WebhookRepository, WorkOrderRepository,
 };
+use crate::infrastructure::storage::LocalStorage;
 use crate::shared::utils::jwt::JwtConfig;
 use std::sync::Arc;
 
@@ -46,17 +83,29 @@ use std::sync::Arc;
 pub struct AppState {
     pub asset_service: AssetService,
     pub auth_service: AuthService,
+    pub api_key_service: ApiKeyService,
     pub approval_service: ApprovalService,
+    pub approval_executors: ApprovalExecutorRegistry,
     pub audit_service: AuditService,
     pub billing_service: BillingService,
+    pub budget_service: BudgetService,
     pub category_service: CategoryService,
     pub client_service: ClientService,
     pub conversion_service: ConversionService,
+    pub depreciation_service: DepreciationService,
+    pub document_number_service: DocumentNumberService,
+    pub valuation_service: ValuationService,
+    pub insurance_service: InsuranceService,
+    pub document_service: DocumentService,
+    pub specification_service: SpecificationService,
     pub lifecycle_service: LifecycleService,
     pub loan_service: LoanService,
     pub maintenance_service: MaintenanceService,
     pub work_order_service: WorkOrderService,
+    pub preventive_maintenance_service: PreventiveMaintenanceService,
     pub notification_service: NotificationService,
+    pub organization_service: OrganizationService,
+    pub password_service: PasswordService,
     pub rbac_service: RbacService,
     pub rental_service: RentalService,
     pub sensor_service: SensorService,
@@ -68,8 +117,13 @@ pub struct AppState {
     pub analytics_service: AnalyticsService,
     pub employee_service: EmployeeService,
     pub location_service: LocationService, // Added
+    pub webhook_service: WebhookService,
+    pub rate_limiter: RateLimiter,
     pub pool: PgPool,
-    pub ws_manager: Arc<crate::api::handlers::notification_ws::WebSocketManager>,
+    pub ws_manager: Arc<WebSocketManager>,
+    pub event_publisher: EventPublisher,
+    pub outbox_relay: OutboxRelay,
+    pub cluster: ClusterBus,
 }
 
 impl AppState {
@@ -77,14 +131,17 @@ impl AppState {
         // Create repositories
         let asset_repo = AssetRepository::new(pool.clone());
         let user_repo = UserRepository::new(pool.clone());
+        let session_repo = SessionRepository::new(pool.clone());
         let category_repo = CategoryRepository::new(pool.clone());
         let loan_repo = LoanRepository::new(pool.clone());
         let maintenance_repo = MaintenanceRepository::new(pool.clone());
         let work_order_repo = WorkOrderRepository::new(pool.clone());
+        let preventive_repo = PreventiveScheduleRepository::new(pool.clone());
         let employee_repo = EmployeeRepository::new(pool.clone());
         let notification_repo = NotificationRepository::new(pool.clone());
         let rbac_repo = RbacRepository::new(pool.clone());
         let approval_repo = ApprovalRepository::new(pool.clone());
+        let approval_workflow_repo = ApprovalWorkflowRepository::new(pool.clone());
         let audit_repo = AuditRepository::new(pool.clone());
         let lifecycle_repo = LifecycleRepository::new(pool.clone());
         let conversion_repo = ConversionRepository::new(pool.clone());
@@ -92,58 +149,191 @@ impl AppState {
         let client_repo = ClientRepository::new(pool.clone());
         let rental_repo = RentalRepository::new(pool.clone());
         let timesheet_repo = TimesheetRepository::new(pool.clone());
+        let depreciation_repo = DepreciationRepository::new(pool.clone());
+        let valuation_repo = ValuationRepository::new(pool.clone());
+        let insurance_repo = InsuranceRepository::new(pool.clone());
+        let document_repo = DocumentRepository::new(pool.clone());
+        let specification_repo = SpecificationRepository::new(pool.clone());
+        let organization_repo = OrganizationRepository::new(pool.clone());
+        let budget_repo = BudgetRepository::new(pool.clone());
 
         // Create cache
         let redis_config = RedisConfig::from_env();
         let redis_cache = RedisCache::new(&redis_config);
+        let rate_limiter = RateLimiter::new(Some(redis_cache.clone()));
+        // Events reach the sockets and subscribers of every instance sharing Redis
+        let cluster = ClusterBus::redis(&redis_config, redis_cache.clone());
+        let ws_manager = WebSocketManager::with_cluster(cluster.clone());
+        let event_publisher = EventPublisher::with_cluster(1000, cluster.clone());
         let cache: Arc<dyn CacheOperations> = Arc::new(redis_cache);
 
         // Create services
-        let approval_service = ApprovalService::new(approval_repo);
-        let asset_service =
-            AssetService::new(asset_repo.clone(), cache.clone(), approval_service.clone());
+        let document_number_service =
+            DocumentNumberService::new(DocumentNumberRepository::new(pool.clone()));
+        let approval_service = ApprovalService::new(
+            approval_repo,
+            approval_workflow_repo,
+            rbac_repo.clone(),
+            ws_manager.clone(),
+        );
+        let organization_service =
+            OrganizationService::new(organization_repo.clone(), user_repo.clone());
+        let budget_service = BudgetService::new(
+            budget_repo,
+            organization_service.clone(),
+            approval_service.clone(),
+        );
+        let depreciation_service =
+            DepreciationService::new(depreciation_repo, approval_service.clone());
+        let valuation_service = ValuationService::new(
+            valuation_repo,
+            asset_repo.clone(),
+            depreciation_service.clone(),
+        );
+        let rbac_service = RbacService::new(rbac_repo.clone());
+        let specification_service = SpecificationService::new(
+            specification_repo,
+            asset_repo.clone(),
+            category_repo.clone(),
+        );
+        let asset_service = AssetService::new(
+            asset_repo.clone(),
+            cache.clone(),
+            approval_service.clone(),
+            depreciation_service.clone(),
+            specification_service.clone(),
+            budget_service.clone(),
+            rbac_service.clone(),
+        );
         let audit_service = AuditService::new(audit_repo); // Added
         let auth_service = AuthService::new(
             user_repo.clone(),
             rbac_repo.clone(),
             employee_repo.clone(),
+            organization_repo.clone(),
+            session_repo,
+            TwoFactorRepository::new(pool.clone()),
             jwt_config,
         );
-        let category_service = CategoryService::new(category_repo);
-        let notification_service = NotificationService::new(notification_repo);
-        let loan_service =
-            LoanService::new(loan_repo, asset_repo.clone(), notification_service.clone());
+        let api_key_service = ApiKeyService::new(
+            ApiKeyRepository::new(pool.clone()),
+            user_repo.clone(),
+            rbac_repo.clone(),
+            employee_repo.clone(),
+        );
+        let category_service = CategoryService::new(category_repo, depreciation_service.clone());
+        let notification_channels = NotificationChannels::from_config(
+            &NotificationConfig::from_env(),
+            notification_repo.clone(),
+        );
+        let notification_service =
+            NotificationService::new(notification_repo, notification_channels);
+        let loan_service = LoanService::new(
+            loan_repo,
+            asset_repo.clone(),
+            notification_service.clone(),
+            approval_service.clone(),
+            ws_manager.clone(),
+            document_number_service.clone(),
+            rbac_service.clone(),
+        );
         let maintenance_service = MaintenanceService::new(
             maintenance_repo.clone(),
             asset_repo.clone(),
             approval_service.clone(),
         );
         let work_order_service = WorkOrderService::new(
-            work_order_repo,
+            WorkOrderRepositories {
+                work_orders: work_order_repo.clone(),
+                lifecycle: lifecycle_repo.clone(),
+                assets: asset_repo.clone(),
+                preventive: preventive_repo.clone(),
+            },
+            budget_service.clone(),
+            cache.clone(),
+            ws_manager.clone(),
+            document_number_service.clone(),
+            rbac_service.clone(),
+        );
+        let preventive_maintenance_service = PreventiveMaintenanceService::new(
+            preventive_repo,
+            asset_repo.clone(),
+            work_order_service.clone(),
+        );
+        let sensor_service = SensorService::new(sensor_repo, ws_manager.clone());
+        let conversion_service = ConversionService::new(
+            conversion_repo.clone(),
+            asset_repo.clone(),
+            approval_service.clone(),
+            depreciation_service.clone(),
+            specification_service.clone(),
+            budget_service.clone(),
+            document_number_service.clone(),
+        );
+        let rental_service = RentalService::new(
+            rental_repo.clone(),
+            client_repo.clone(),
+            asset_repo.clone(),
+            approval_service.clone(),
+            document_number_service.clone(),
+        );
+        let data_service = DataService::new(asset_repo.clone(), rbac_service.clone());
+        let insurance_service = InsuranceService::new(
+            insurance_repo,
+            asset_repo.clone(),
+            work_order_repo.clone(),
             lifecycle_repo.clone(),
+            rbac_repo.clone(),
+            notification_service.clone(),
+        );
+        let document_service = DocumentService::new(
+            document_repo,
             asset_repo.clone(),
-            cache.clone(),
+            rbac_repo.clone(),
+            notification_service.clone(),
+            LocalStorage::from_env(),
+        );
+        let webhook_service =
+            WebhookService::new(WebhookRepository::new(pool.clone()), WebhookSender::new());
+        let outbox_relay = OutboxRelay::new(
+            OutboxRepository::new(pool.clone()),
+            event_publisher.clone(),
+            ws_manager.clone(),
+        )
+        .with_subscriber(Arc::new(webhook_service.clone()));
+        let scheduler_service = SchedulerService {
+            loan_service: loan_service.clone(),
+            maintenance_service: maintenance_service.clone(),
+            preventive_maintenance_service: preventive_maintenance_service.clone(),
+            notification_service: notification_service.clone(),
+            depreciation_service: depreciation_service.clone(),
+            valuation_service: valuation_service.clone(),
+            insurance_service: insurance_service.clone(),
+            document_service: document_service.clone(),
+            webhook_service: webhook_service.clone(),
+        };
+        let password_service = PasswordService::new(
+            PasswordRepository::new(pool.clone()),
+            user_repo.clone(),
+            notification_service.clone(),
         );
-        let rbac_service = RbacService::new(rbac_repo.clone());
-        // Approval service moved up
-        let sensor_service = SensorService::new(sensor_repo);
-        let conversion_service =
-            ConversionService::new(conversion_repo.clone(), asset_repo.clone());
-        let rental_service =
-            RentalService::new(rental_repo.clone(), client_repo.clone(), asset_repo.clone());
-        let data_service = DataService::new(asset_repo.clone());
-        let scheduler_service =
-            SchedulerService::new(loan_service.clone(), maintenance_service.clone());
         let user_service = UserService::new(user_repo, rbac_repo);
         let report_service = ReportService::new(
             asset_repo.clone(),
             maintenance_repo.clone(),
             rental_repo.clone(),
             timesheet_repo.clone(),
+            depreciation_service.clone(),
+            rbac_service.clone(),
         );
-        let lifecycle_service = LifecycleService::new(lifecycle_repo.clone());
+        let lifecycle_service =
+            LifecycleService::new(lifecycle_repo.clone(), approval_service.clone());
         let timesheet_service = TimesheetService::new(timesheet_repo.clone(), rental_repo.clone());
-        let billing_service = BillingService::new(timesheet_repo.clone(), rental_repo.clone());
+        let billing_service = BillingService::new(
+            timesheet_repo.clone(),
+            rental_repo.clone(),
+            document_number_service.clone(),
+        );
         let client_service = ClientService::new(client_repo.clone());
         let analytics_service = AnalyticsService::new(pool.clone());
         let employee_service = EmployeeService::new(employee_repo, user_service.clone());
@@ -151,21 +341,76 @@ impl AppState {
             crate::infrastructure::repositories::LocationRepository::new(pool.clone());
         let location_service = LocationService::new(location_repo);
 
+        // Apply approved requests through the services that own the resource
+        let approval_executors = ApprovalExecutorRegistry::new(approval_service.clone())
+            .register(
+                "asset",
+                "CREATE",
+                AssetCreateExecutor(asset_service.clone()),
+            )
+            .register(
+                "asset",
+                "UPDATE",
+                AssetUpdateExecutor(asset_service.clone()),
+            )
+            .register(
+                "asset",
+                "DELETE",
+                AssetDeleteExecutor(asset_service.clone()),
+            )
+            .register(
+                LIFECYCLE_RESOURCE_TYPE,
+                "*",
+                LifecycleTransitionExecutor(lifecycle_service.clone()),
+            )
+            .register(
+                "conversion",
+                "*",
+                ConversionExecutor(conversion_service.clone()),
+            )
+            .register(
+                CLOSING_RESOURCE_TYPE,
+                CLOSING_REOPEN_ACTION,
+                DepreciationReopenExecutor(depreciation_service.clone()),
+            )
+            .register(
+                BUDGET_RESOURCE_TYPE,
+                BUDGET_OVERRUN_ACTION,
+                BudgetOverrunExecutor {
+                    work_orders: work_order_service.clone(),
+                    conversions: conversion_service.clone(),
+                },
+            )
+            .register("loan", "*", LoanExecutor(loan_service.clone()))
+            .register("rental", "*", RentalExecutor(rental_service.clone()));
+
         Self {
             asset_service,
             audit_service,
             auth_service,
+            api_key_service,
+            budget_service,
             category_service,
             client_service,
             conversion_service,
+            depreciation_service,
+            document_number_service,
+            valuation_service,
+            insurance_service,
+            document_service,
+            specification_service,
             lifecycle_service,
             loan_service,
             maintenance_service,
             work_order_service,
+            preventive_maintenance_service,
             notification_service,
+            organization_service,
+            password_service,
             rbac_service,
             rental_service,
             approval_service,
+            approval_executors,
             sensor_service,
             timesheet_service,
             billing_service,
@@ -176,8 +421,13 @@ impl AppState {
             analytics_service,
             employee_service,
             location_service,
+            webhook_service,
             pool,
-            ws_manager: Arc::new(crate::api::handlers::notification_ws::WebSocketManager::new()),
+            rate_limiter,
+            ws_manager,
+            event_publisher,
+            outbox_relay,
+            cluster,
         }
     }
 }
@@ -0,0 +1,18 @@
+//! API Key DTOs
+
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    pub scopes: Vec<String>, // permission codes, a subset of the owner's
+    pub expires_in_days: Option<i64>, // default 90, at most 365
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateServiceAccountRequest {
+    pub name: String,
+    pub role_id: Uuid,
+    pub organization_id: Option<Uuid>,
+}
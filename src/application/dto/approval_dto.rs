@@ -0,0 +1,41 @@
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct WorkflowLevelInput {
+    pub level_number: i32,
+    pub role_id: Option<Uuid>,
+    pub role_code: Option<String>, // Alternative to role_id
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateApprovalWorkflowRequest {
+    pub resource_type: String, // asset, loan, rental, conversion, lifecycle_transition, * ...
+    pub action_type: String,   // CREATE, UPDATE, DELETE, HIGH_COST, * ...
+    pub asset_type: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub need_approval: Option<bool>,
+    pub is_active: Option<bool>,
+    #[serde(default)]
+    pub levels: Vec<WorkflowLevelInput>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateApprovalWorkflowRequest {
+    pub resource_type: Option<String>,
+    pub action_type: Option<String>,
+    pub asset_type: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub need_approval: Option<bool>,
+    pub is_active: Option<bool>,
+    pub levels: Option<Vec<WorkflowLevelInput>>, // Replaces all levels when present
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResolveWorkflowParams {
+    pub resource_type: String,
+    pub action_type: String,
+    pub category_id: Option<Uuid>,
+}
@@ -35,6 +35,7 @@ pub struct CreateAssetRequest {
     pub department_id: Option<Uuid>,
     pub assigned_to: Option<Uuid>,
     pub vendor_id: Option<Uuid>,
+    pub organization_id: Option<Uuid>, // defaults to the active organization
     pub is_rental: Option<bool>,
     pub asset_class: Option<String>,
     pub status: Option<String>,
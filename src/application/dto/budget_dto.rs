@@ -0,0 +1,63 @@
+//! Budget DTOs
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::entities::BudgetPosition;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateBudgetLineRequest {
+    pub organization_id: Option<Uuid>, // defaults to the active organization
+    pub department_id: Option<Uuid>,
+    pub name: String,
+    pub fiscal_year: Option<i32>, // defaults to the year of period_start
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub amount: Decimal,
+    pub over_budget_action: Option<String>, // block (default), escalate
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateBudgetLineRequest {
+    pub department_id: Option<Uuid>,
+    pub name: Option<String>,
+    pub fiscal_year: Option<i32>,
+    pub period_start: Option<NaiveDate>,
+    pub period_end: Option<NaiveDate>,
+    pub amount: Option<Decimal>,
+    pub over_budget_action: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BudgetQueryParams {
+    pub fiscal_year: Option<i32>,
+}
+
+/// Budget-vs-actual report over the caller's organizations
+#[derive(Debug, Serialize)]
+pub struct BudgetReport {
+    pub fiscal_year: Option<i32>,
+    pub lines: Vec<BudgetPosition>,
+    pub total_budget: Decimal,
+    pub total_committed: Decimal,
+    pub total_actual: Decimal,
+    pub total_available: Decimal,
+}
+
+impl BudgetReport {
+    pub fn new(fiscal_year: Option<i32>, lines: Vec<BudgetPosition>) -> Self {
+        let sum = |f: fn(&BudgetPosition) -> Decimal| lines.iter().map(f).sum::<Decimal>();
+        Self {
+            fiscal_year,
+            total_budget: sum(|p| p.amount),
+            total_committed: sum(|p| p.committed),
+            total_actual: sum(|p| p.actual),
+            total_available: sum(|p| p.available),
+            lines,
+        }
+    }
+}
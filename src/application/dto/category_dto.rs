@@ -2,7 +2,9 @@
 //!
 //! Data Transfer Objects for category operations.
 
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 use uuid::Uuid;
 
 /// Request to create a new category
@@ -18,6 +20,13 @@ pub struct CreateCategoryRequest {
     pub function_description: Option<String>,
     pub example_assets: Option<Vec<String>>,
     pub display_order: Option<i32>,
+    // Depreciation
+    pub depreciation_method: Option<String>, // straight_line, declining_balance, double_declining_balance, sum_of_years_digits, units_of_production
+    pub depreciation_period_months: Option<i32>,
+    pub residual_rate: Option<Decimal>,
+    pub declining_balance_rate: Option<Decimal>,
+    pub estimated_total_hours: Option<Decimal>,
+    pub specification_schema: Option<JsonValue>, // see SpecificationSchema
 }
 
 /// Request to update a category
@@ -33,6 +42,13 @@ pub struct UpdateCategoryRequest {
     pub function_description: Option<String>,
     pub example_assets: Option<Vec<String>>,
     pub display_order: Option<i32>,
+    // Depreciation
+    pub depreciation_method: Option<String>, // straight_line, declining_balance, double_declining_balance, sum_of_years_digits, units_of_production
+    pub depreciation_period_months: Option<i32>,
+    pub residual_rate: Option<Decimal>,
+    pub declining_balance_rate: Option<Decimal>,
+    pub estimated_total_hours: Option<Decimal>,
+    pub specification_schema: Option<JsonValue>, // see SpecificationSchema; {} clears it on update
 }
 
 /// Category response with all fields
@@ -49,6 +65,12 @@ pub struct CategoryResponse {
     pub function_description: Option<String>,
     pub example_assets: Option<Vec<String>>,
     pub display_order: i32,
+    pub depreciation_method: Option<String>,
+    pub depreciation_period_months: Option<i32>,
+    pub residual_rate: Option<Decimal>,
+    pub declining_balance_rate: Option<Decimal>,
+    pub estimated_total_hours: Option<Decimal>,
+    pub specification_schema: Option<JsonValue>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
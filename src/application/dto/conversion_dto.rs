@@ -1,4 +1,5 @@
 use crate::domain::entities::conversion::AssetConversion;
+use chrono::NaiveDate;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
@@ -18,6 +19,8 @@ pub struct CreateConversionRequest {
 #[derive(Debug, Deserialize)]
 pub struct ExecuteConversionRequest {
     pub notes: Option<String>,
+    /// Back-dates the execution; must not fall in a closed depreciation period
+    pub execution_date: Option<NaiveDate>,
 }
 
 #[derive(Debug, Serialize)]
@@ -0,0 +1,12 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct DepreciationAsOfParams {
+    pub as_of: Option<NaiveDate>, // defaults to today
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReopenDepreciationPeriodRequest {
+    pub reason: String,
+}
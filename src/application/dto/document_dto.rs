@@ -0,0 +1,26 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+/// Parsed multipart upload of a document or a new version of one
+#[derive(Debug, Default)]
+pub struct UploadDocumentRequest {
+    pub name: Option<String>, // defaults to the file name (new version: previous name)
+    pub type_: Option<String>, // e.g. CERTIFICATE, WARRANTY, PERMIT (new version: previous type)
+    pub expiry_date: Option<NaiveDate>,
+    pub notes: Option<String>,
+    pub original_name: Option<String>,
+    pub mime_type: Option<String>,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DocumentListParams {
+    /// Include superseded versions
+    #[serde(default)]
+    pub include_history: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExpiringDocumentsParams {
+    pub days: Option<i64>, // defaults to DOCUMENT_EXPIRY_REMINDER_DAYS
+}
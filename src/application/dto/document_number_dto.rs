@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateDocumentNumberFormatRequest {
+    pub prefix: Option<String>,
+    pub reset_period: Option<String>, // never, yearly, monthly
+    pub padding: Option<i32>,
+    pub segment: Option<String>, // none, department, location
+}
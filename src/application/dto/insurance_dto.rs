@@ -0,0 +1,61 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateInsuranceRequest {
+    pub policy_number: String,
+    pub insurance_provider: String,
+    pub coverage_type: Option<String>, // All Risk, TLO, etc.
+    pub coverage_amount: Option<Decimal>,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub premium_amount: Option<Decimal>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateInsuranceRequest {
+    pub policy_number: Option<String>,
+    pub insurance_provider: Option<String>,
+    pub coverage_type: Option<String>,
+    pub coverage_amount: Option<Decimal>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub premium_amount: Option<Decimal>,
+    pub status: Option<String>, // active, cancelled
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExpiringInsuranceParams {
+    pub days: Option<i64>, // defaults to the warning window
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InsuranceCoverageParams {
+    pub as_of: Option<NaiveDate>, // defaults to today
+}
+
+/// Claims are raised from exactly one of a work order or a lost/stolen transition
+#[derive(Debug, Deserialize)]
+pub struct CreateInsuranceClaimRequest {
+    pub claim_number: Option<String>,
+    pub incident_date: NaiveDate,
+    pub work_order_id: Option<Uuid>,
+    pub lifecycle_history_id: Option<Uuid>,
+    pub claimed_amount: Decimal,
+    pub description: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateInsuranceClaimStatusRequest {
+    pub status: String, // approved, rejected, paid
+    pub approved_amount: Option<Decimal>,
+    pub payout_amount: Option<Decimal>,
+    pub payout_date: Option<NaiveDate>,
+    pub claim_number: Option<String>,
+    pub notes: Option<String>,
+}
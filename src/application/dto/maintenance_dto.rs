@@ -39,3 +39,28 @@ pub struct MaintenanceSearchParams {
     pub page: i64,
     pub per_page: i64,
 }
+
+#[derive(Deserialize)]
+pub struct CreatePreventiveScheduleRequest {
+    pub name: String,
+    pub interval_type: String, // days, km, hours
+    pub interval_value: i32,
+    pub maintenance_type_id: Option<i32>,
+    pub last_execution_date: Option<chrono::NaiveDate>,
+    pub last_execution_odometer: Option<i32>,
+    pub next_due_date: Option<chrono::NaiveDate>,
+    pub next_due_odometer: Option<i32>,
+    pub notification_days_before: Option<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdatePreventiveScheduleRequest {
+    pub name: Option<String>,
+    pub interval_type: Option<String>,
+    pub interval_value: Option<i32>,
+    pub maintenance_type_id: Option<i32>,
+    pub next_due_date: Option<chrono::NaiveDate>,
+    pub next_due_odometer: Option<i32>,
+    pub is_active: Option<bool>,
+    pub notification_days_before: Option<i32>,
+}
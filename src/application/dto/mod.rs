@@ -1,21 +1,47 @@
+pub mod api_key_dto;
+pub mod approval_dto;
 pub mod asset_dto;
+pub mod budget_dto;
 pub mod category_dto;
 pub mod common;
 pub mod conversion_dto;
+pub mod depreciation_dto;
+pub mod document_dto;
+pub mod document_number_dto;
 pub mod employee_dto;
+pub mod insurance_dto;
 pub mod loan_dto;
 pub mod maintenance_dto;
+pub mod notification_dto;
+pub mod organization_dto;
+pub mod rbac_dto;
 pub mod rental_dto;
 pub mod rental_timesheet_dto;
+pub mod specification_dto;
 pub mod user_dto;
+pub mod valuation_dto;
+pub mod webhook_dto;
 
+pub use api_key_dto::*;
+pub use approval_dto::*;
 pub use asset_dto::*;
+pub use budget_dto::*;
 pub use category_dto::*;
 pub use common::*;
 pub use conversion_dto::*;
+pub use depreciation_dto::*;
+pub use document_dto::*;
+pub use document_number_dto::*;
 pub use employee_dto::*;
+pub use insurance_dto::*;
 pub use loan_dto::*;
 pub use maintenance_dto::*;
+pub use notification_dto::*;
+pub use organization_dto::*;
+pub use rbac_dto::*;
 pub use rental_dto::*;
 pub use rental_timesheet_dto::*;
+pub use specification_dto::*;
 pub use user_dto::*;
+pub use valuation_dto::*;
+pub use webhook_dto::*;
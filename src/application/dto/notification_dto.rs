@@ -0,0 +1,51 @@
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateNotificationTemplateRequest {
+    pub code: String,
+    pub name: String,
+    pub event_type: String, // e.g. loan.approved
+    pub subject_template: Option<String>,
+    pub body_template: String,
+    pub channels: Option<Vec<String>>, // in_app, email, push, sms, webhook
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateNotificationTemplateRequest {
+    pub code: Option<String>,
+    pub name: Option<String>,
+    pub event_type: Option<String>,
+    pub subject_template: Option<String>,
+    pub body_template: Option<String>,
+    pub channels: Option<Vec<String>>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PreviewNotificationTemplateRequest {
+    #[serde(default)]
+    pub variables: JsonValue,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationDeliveryParams {
+    pub status: Option<String>,
+    pub channel: Option<String>,
+    pub user_id: Option<Uuid>,
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateNotificationPreferenceRequest {
+    pub event_type: String,
+    pub email_enabled: Option<bool>,
+    pub push_enabled: Option<bool>,
+    pub sms_enabled: Option<bool>,
+    pub in_app_enabled: Option<bool>,
+    pub webhook_enabled: Option<bool>,
+    pub digest_frequency: Option<String>, // immediate, hourly, daily, weekly
+}
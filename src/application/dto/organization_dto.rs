@@ -0,0 +1,41 @@
+//! Organization DTOs
+
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateOrganizationRequest {
+    pub code: String,
+    pub name: String,
+    pub parent_id: Option<Uuid>, // required unless the caller is unscoped
+    pub org_type: Option<String>, // company, division, department, team
+    pub cost_center: Option<String>,
+    pub budget: Option<Decimal>,
+    pub manager_id: Option<Uuid>,
+    pub metadata: Option<JsonValue>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateOrganizationRequest {
+    pub code: Option<String>,
+    pub name: Option<String>,
+    pub parent_id: Option<Uuid>, // moves the organization with its subtree
+    pub org_type: Option<String>,
+    pub cost_center: Option<String>,
+    pub budget: Option<Decimal>,
+    pub manager_id: Option<Uuid>,
+    pub is_active: Option<bool>,
+    pub metadata: Option<JsonValue>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddOrganizationMemberRequest {
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SwitchOrganizationRequest {
+    pub organization_id: Uuid,
+}
@@ -0,0 +1,35 @@
+//! RBAC DTOs
+
+use serde::Deserialize;
+
+use crate::domain::entities::PermissionGrant;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateRoleRequest {
+    pub code: String, // lowercase letters, digits and underscores
+    pub name: String,
+    pub description: Option<String>,
+    pub role_level: Option<i32>, // 1 (highest) to 5, default 5
+    pub requires_two_factor: Option<bool>,
+    pub permissions: Option<Vec<PermissionGrant>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateRoleRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub role_level: Option<i32>,
+    pub requires_two_factor: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CloneRoleRequest {
+    pub code: String,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetRolePermissionsRequest {
+    pub permissions: Vec<PermissionGrant>, // replaces the role's grants
+}
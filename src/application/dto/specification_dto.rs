@@ -0,0 +1,19 @@
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+/// Manual edit of an asset's specifications
+#[derive(Debug, Deserialize)]
+pub struct UpdateSpecificationsRequest {
+    pub specifications: JsonValue,
+    pub change_type: Option<String>, // upgrade, modification (default), correction
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SpecificationDiffParams {
+    /// History entry whose resulting specifications are the base
+    pub from: Uuid,
+    /// History entry to compare with; defaults to the current specifications
+    pub to: Option<Uuid>,
+}
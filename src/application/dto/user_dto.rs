@@ -10,6 +10,7 @@ pub struct CreateUserRequest {
     pub department: Option<String>,
     pub department_id: Option<Uuid>,
     pub organization_id: Option<Uuid>,
+    pub location_id: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -18,6 +19,7 @@ pub struct UpdateUserRequest {
     pub role_code: Option<String>,
     pub department: Option<String>,
     pub department_id: Option<Uuid>,
+    pub location_id: Option<Uuid>,
     pub is_active: Option<bool>,
     pub password: Option<String>, // Optional password update
 }
@@ -0,0 +1,35 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct RecordAppraisalRequest {
+    pub valuation_date: NaiveDate,
+    pub market_value: Decimal,
+    pub replacement_cost: Option<Decimal>,
+    pub valuation_type: Option<String>, // appraisal (default), market
+    pub appraiser: Option<String>,
+    pub notes: Option<String>,
+    /// Book the market value as the new carrying amount (revaluation model)
+    #[serde(default)]
+    pub apply_revaluation: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecordImpairmentRequest {
+    pub valuation_date: NaiveDate,
+    /// Recoverable amount; becomes the carrying amount
+    pub recoverable_amount: Decimal,
+    pub appraiser: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ValuationHistoryParams {
+    pub valuation_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ValuationSnapshotRequest {
+    pub as_of: Option<NaiveDate>, // defaults to the end of last month
+}
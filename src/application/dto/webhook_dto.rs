@@ -0,0 +1,31 @@
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub name: String,
+    pub url: String,
+    pub event_types: Option<Vec<String>>, // e.g. asset.created, rental.*; all events when empty
+    pub secret: Option<String>,           // Generated when omitted
+    pub max_attempts: Option<i32>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateWebhookRequest {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub event_types: Option<Vec<String>>,
+    pub secret: Option<String>,
+    pub max_attempts: Option<i32>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WebhookDeliveryParams {
+    pub subscription_id: Option<Uuid>,
+    pub status: Option<String>,
+    pub event_type: Option<String>,
+    pub page: Option<i64>,
+    pub per_page: Option<i64>,
+}
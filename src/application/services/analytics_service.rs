@@ -15,6 +15,8 @@ pub struct AssetRoiResponse {
     pub purchase_date: String,
     pub book_value: Decimal,
     pub accumulated_depreciation: Decimal,
+    pub valuation_adjustment: Decimal, // Net revaluations and impairments to date
+    pub cost_basis: Decimal,           // Purchase Price + Valuation Adjustment
 
     // Revenue
     pub total_rental_revenue: Decimal,
@@ -26,11 +28,14 @@ pub struct AssetRoiResponse {
     pub work_order_count: i64,
 
     // ROI Metrics
-    pub net_profit: Decimal, // Revenue - (Maintenance + Accumulated Depreciation)
-    pub roi_percentage: Decimal, // (Net Profit / Purchase Price) * 100
+    pub net_profit: Decimal, // Revenue - (Maintenance + Accumulated Depreciation + Impairment Losses)
+    pub roi_percentage: Decimal, // (Net Profit / Cost Basis) * 100
     pub utilization_days: i64,
 }
 
+/// Accumulated depreciation, book value, net valuation adjustment, impairment losses
+type ScheduleBookFigures = (Decimal, Decimal, Decimal, Decimal);
+
 #[derive(Clone)]
 pub struct AnalyticsService {
     pool: PgPool,
@@ -98,16 +103,47 @@ impl AnalyticsService {
             message: e.to_string(),
         })?;
 
+        // 4. Book figures from the depreciation schedule, which carries revaluations
+        // and impairments; assets without one fall back to the straight-line function
+        let schedule: Option<ScheduleBookFigures> = sqlx::query_as(
+            r#"SELECT
+                s.accumulated_depreciation, s.closing_value,
+                (SELECT COALESCE(SUM(valuation_adjustment), 0) FROM depreciation_schedules
+                 WHERE asset_id = $1 AND period_start <= CURRENT_DATE),
+                (SELECT COALESCE(SUM(LEAST(valuation_adjustment, 0)), 0) FROM depreciation_schedules
+                 WHERE asset_id = $1 AND period_start <= CURRENT_DATE)
+            FROM depreciation_schedules s
+            WHERE s.asset_id = $1 AND s.period_start <= CURRENT_DATE
+            ORDER BY s.period_start DESC
+            LIMIT 1"#,
+        )
+        .bind(asset_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::ExternalServiceError {
+            service: "db".into(),
+            message: e.to_string(),
+        })?;
+
         let purchase_price = asset_info.purchase_price.unwrap_or(Decimal::ZERO);
         let total_revenue = revenue.total_revenue.unwrap_or(Decimal::ZERO);
         let m_cost = (maintenance.total_labor.unwrap_or(Decimal::ZERO))
             + (maintenance.total_parts.unwrap_or(Decimal::ZERO));
-        let accum_dep = asset_info.accumulated_depreciation.unwrap_or(Decimal::ZERO);
+        let (accum_dep, book_value, valuation_adjustment, impairment_loss) = match schedule {
+            Some(figures) => figures,
+            None => (
+                asset_info.accumulated_depreciation.unwrap_or(Decimal::ZERO),
+                asset_info.book_value.unwrap_or(Decimal::ZERO),
+                Decimal::ZERO,
+                Decimal::ZERO,
+            ),
+        };
+        let cost_basis = purchase_price + valuation_adjustment;
 
-        let net_profit = total_revenue - (m_cost + accum_dep);
+        let net_profit = total_revenue - (m_cost + accum_dep - impairment_loss);
 
-        let roi_percentage = if !purchase_price.is_zero() {
-            (net_profit / purchase_price) * Decimal::from(100)
+        let roi_percentage = if cost_basis > Decimal::ZERO {
+            (net_profit / cost_basis) * Decimal::from(100)
         } else {
             Decimal::ZERO
         };
@@ -121,8 +157,10 @@ impl AnalyticsService {
                 .purchase_date
                 .map(|d| d.to_string())
                 .unwrap_or_default(),
-            book_value: asset_info.book_value.unwrap_or(Decimal::ZERO),
+            book_value,
             accumulated_depreciation: accum_dep,
+            valuation_adjustment,
+            cost_basis,
             total_rental_revenue: total_revenue,
             billing_count: revenue.billing_count.unwrap_or(0),
             maintenance_cost: maintenance.total_labor.unwrap_or(Decimal::ZERO),
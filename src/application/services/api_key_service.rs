@@ -0,0 +1,335 @@
+//! API Key Service
+//!
+//! Service accounts and API keys for integrations. Keys authenticate in place
+//! of an access token; the claims they produce carry the key's effective
+//! permissions and its id, so actions can be attributed to it, but not the
+//! owner's role.
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::application::dto::{CreateApiKeyRequest, CreateServiceAccountRequest};
+use crate::domain::entities::{
+    api_key_prefix, ApiKey, IssuedApiKey, ServiceAccount, User, UserClaims, UserRole,
+    API_KEY_DEFAULT_EXPIRY_DAYS, API_KEY_MAX_EXPIRY_DAYS, API_KEY_ROLE, API_KEY_ROLE_LEVEL,
+};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::repositories::{
+    ApiKeyRepository, ApiKeyRequestLog, EmployeeRepository, RbacRepository, UserRepository,
+};
+use crate::shared::utils::crypto::{generate_api_key, hash_token};
+
+fn db_error(e: sqlx::Error) -> DomainError {
+    DomainError::ExternalServiceError {
+        service: "database".to_string(),
+        message: e.to_string(),
+    }
+}
+
+#[derive(Clone)]
+pub struct ApiKeyService {
+    repository: ApiKeyRepository,
+    user_repository: UserRepository,
+    rbac_repository: RbacRepository,
+    employee_repository: EmployeeRepository,
+}
+
+impl ApiKeyService {
+    pub fn new(
+        repository: ApiKeyRepository,
+        user_repository: UserRepository,
+        rbac_repository: RbacRepository,
+        employee_repository: EmployeeRepository,
+    ) -> Self {
+        Self {
+            repository,
+            user_repository,
+            rbac_repository,
+            employee_repository,
+        }
+    }
+
+    // ==================== AUTHENTICATION ====================
+
+    /// Claims for a presented key: the scopes its owner's role still grants
+    pub async fn authenticate(
+        &self,
+        key: &str,
+        ip_address: Option<&str>,
+    ) -> DomainResult<UserClaims> {
+        let invalid = || DomainError::unauthorized("Invalid credentials");
+
+        let prefix = api_key_prefix(key).ok_or_else(invalid)?;
+        let api_key = self
+            .repository
+            .find_by_prefix(prefix)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(invalid)?;
+        if api_key.key_hash != hash_token(key) || !api_key.is_usable(Utc::now()) {
+            return Err(invalid());
+        }
+
+        let user = self
+            .user_repository
+            .find_by_id(api_key.user_id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(invalid)?;
+        if !user.is_active {
+            return Err(DomainError::unauthorized("Account is disabled"));
+        }
+
+        if let Err(e) = self.repository.touch(api_key.id, ip_address).await {
+            tracing::warn!("Failed to record use of API key {}: {}", api_key.prefix, e);
+        }
+
+        let permissions = api_key.effective_permissions(&self.role_permissions(&user).await);
+        let employee_id = self
+            .employee_repository
+            .find_by_user_id(user.id)
+            .await
+            .unwrap_or(None)
+            .map(|e| e.id);
+
+        Ok(UserClaims {
+            sub: user.id.to_string(),
+            email: user.email,
+            name: user.name,
+            role: API_KEY_ROLE.to_string(),
+            role_level: API_KEY_ROLE_LEVEL,
+            department: user.department,
+            org: user.organization_id.map(|id| id.to_string()),
+            employee_id,
+            permissions,
+            exp: api_key.expires_at.timestamp(),
+            iat: Utc::now().timestamp(),
+            jti: Uuid::new_v4().to_string(),
+            sid: None,
+            api_key_id: Some(api_key.id),
+        })
+    }
+
+    /// Audit a state-changing request made with a key
+    pub async fn record_request(&self, claims: &UserClaims, request: &ApiKeyRequestLog<'_>) {
+        let Some(key_id) = claims.api_key_id else {
+            return;
+        };
+        if let Err(e) = self
+            .repository
+            .record_request(key_id, claims.user_id(), request)
+            .await
+        {
+            tracing::error!("Failed to audit API key request: {}", e);
+        }
+    }
+
+    async fn role_permissions(&self, user: &User) -> Vec<String> {
+        match user.role_id {
+            Some(role_id) => self
+                .rbac_repository
+                .get_permissions_for_role(role_id)
+                .await
+                .unwrap_or_default(),
+            None => UserRole::from_str(&user.role)
+                .unwrap_or(UserRole::User)
+                .default_permissions()
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        }
+    }
+
+    // ==================== SERVICE ACCOUNTS ====================
+
+    pub async fn list_service_accounts(&self) -> DomainResult<Vec<ServiceAccount>> {
+        self.repository
+            .list_service_accounts()
+            .await
+            .map_err(db_error)
+    }
+
+    pub async fn create_service_account(
+        &self,
+        request: CreateServiceAccountRequest,
+        created_by: Uuid,
+    ) -> DomainResult<ServiceAccount> {
+        let name = request.name.trim();
+        if name.is_empty() {
+            return Err(DomainError::validation("name", "Name is required"));
+        }
+
+        self.repository
+            .create_service_account(name, request.role_id, request.organization_id, created_by)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| DomainError::not_found("Role", request.role_id))
+    }
+
+    pub async fn list_service_account_keys(&self, id: Uuid) -> DomainResult<Vec<ApiKey>> {
+        self.find_service_account(id).await?;
+        self.list_keys(id).await
+    }
+
+    pub async fn create_service_account_key(
+        &self,
+        id: Uuid,
+        request: CreateApiKeyRequest,
+        claims: &UserClaims,
+    ) -> DomainResult<IssuedApiKey> {
+        self.find_service_account(id).await?;
+        self.create_key(id, request, claims).await
+    }
+
+    async fn find_service_account(&self, id: Uuid) -> DomainResult<ServiceAccount> {
+        self.repository
+            .find_service_account(id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| DomainError::not_found("Service account", id))
+    }
+
+    // ==================== KEYS ====================
+
+    pub async fn list_keys(&self, user_id: Uuid) -> DomainResult<Vec<ApiKey>> {
+        self.repository
+            .list_for_user(user_id)
+            .await
+            .map_err(db_error)
+    }
+
+    /// Issue a key for `owner_id`; the plain key is only returned here
+    pub async fn create_key(
+        &self,
+        owner_id: Uuid,
+        request: CreateApiKeyRequest,
+        claims: &UserClaims,
+    ) -> DomainResult<IssuedApiKey> {
+        Self::ensure_not_api_key(claims)?;
+
+        let name = request.name.trim();
+        if name.is_empty() {
+            return Err(DomainError::validation("name", "Name is required"));
+        }
+        let days = request
+            .expires_in_days
+            .unwrap_or(API_KEY_DEFAULT_EXPIRY_DAYS);
+        if !(1..=API_KEY_MAX_EXPIRY_DAYS).contains(&days) {
+            return Err(DomainError::validation(
+                "expires_in_days",
+                &format!("Must be between 1 and {}", API_KEY_MAX_EXPIRY_DAYS),
+            ));
+        }
+
+        let owner = self
+            .user_repository
+            .find_by_id(owner_id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| DomainError::not_found("User", owner_id))?;
+        let mut scopes = request.scopes;
+        scopes.sort();
+        scopes.dedup();
+        if scopes.is_empty() {
+            return Err(DomainError::validation(
+                "scopes",
+                "At least one permission is required",
+            ));
+        }
+        let granted = self.role_permissions(&owner).await;
+        let ungranted: Vec<&str> = scopes
+            .iter()
+            .filter(|s| !granted.contains(s))
+            .map(String::as_str)
+            .collect();
+        if !ungranted.is_empty() {
+            return Err(DomainError::validation(
+                "scopes",
+                &format!("Not granted to the owner: {}", ungranted.join(", ")),
+            ));
+        }
+
+        let key = generate_api_key();
+        let api_key = ApiKey {
+            id: Uuid::new_v4(),
+            user_id: owner.id,
+            name: name.to_string(),
+            prefix: Self::prefix_of(&key)?,
+            key_hash: hash_token(&key),
+            scopes,
+            expires_at: Utc::now() + Duration::days(days),
+            last_used_at: None,
+            last_used_ip: None,
+            revoked_at: None,
+            created_by: Some(claims.user_id()),
+            created_at: None,
+            updated_at: None,
+        };
+        let api_key = self.repository.create(&api_key).await.map_err(db_error)?;
+
+        Ok(IssuedApiKey { api_key, key })
+    }
+
+    /// Replace a key's secret, keeping its name, scopes and expiry
+    pub async fn rotate_key(&self, id: Uuid, claims: &UserClaims) -> DomainResult<IssuedApiKey> {
+        self.managed_key(id, claims).await?;
+
+        let key = generate_api_key();
+        let api_key = self
+            .repository
+            .rotate(
+                id,
+                &Self::prefix_of(&key)?,
+                &hash_token(&key),
+                claims.user_id(),
+            )
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| {
+                DomainError::business_rule("api_key_revoked", "A revoked key cannot be rotated")
+            })?;
+
+        Ok(IssuedApiKey { api_key, key })
+    }
+
+    pub async fn revoke_key(&self, id: Uuid, claims: &UserClaims) -> DomainResult<ApiKey> {
+        self.managed_key(id, claims).await?;
+
+        self.repository
+            .revoke(id, claims.user_id())
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| DomainError::conflict("API key is already revoked"))
+    }
+
+    /// A key the caller may manage: their own, or any key for admins
+    async fn managed_key(&self, id: Uuid, claims: &UserClaims) -> DomainResult<ApiKey> {
+        Self::ensure_not_api_key(claims)?;
+
+        let key = self
+            .repository
+            .find(id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| DomainError::not_found("API key", id))?;
+        let is_admin = claims.role == "admin" || claims.role == "super_admin";
+        if key.user_id != claims.user_id() && !is_admin {
+            return Err(DomainError::not_found("API key", id));
+        }
+        Ok(key)
+    }
+
+    /// Keys are managed from a login session, never with another key
+    fn ensure_not_api_key(claims: &UserClaims) -> DomainResult<()> {
+        if claims.is_api_key() {
+            return Err(DomainError::unauthorized("manage API keys with an API key"));
+        }
+        Ok(())
+    }
+
+    fn prefix_of(key: &str) -> DomainResult<String> {
+        api_key_prefix(key)
+            .map(str::to_string)
+            .ok_or_else(|| DomainError::internal("malformed API key"))
+    }
+}
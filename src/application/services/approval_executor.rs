@@ -0,0 +1,547 @@
+//! Approval Executors
+//!
+//! Apply the change stored in an approval request's `data_snapshot` once the
+//! request reaches its final level. Executors are registered per
+//! `resource_type`/`action_type` and go through the service that owns the
+//! resource, so the same domain rules apply as for a direct change.
+//!
+//! The final signature, the change and its outcome are written in one
+//! transaction. The change runs under a savepoint: when it fails, its writes
+//! are rolled back and the request is stored as FAILED instead.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sqlx::{Acquire, Postgres, Transaction};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::dto::{CreateAssetRequest, RejectRentalRequest, UpdateAssetRequest};
+use crate::application::services::{
+    ApprovalService, AssetService, BudgetedResult, ConversionService, DepreciationService,
+    LifecycleService, LoanService, RentalService, WorkOrderService,
+};
+use crate::domain::entities::{SPEND_CONVERSION, SPEND_WORK_ORDER, WORKFLOW_WILDCARD};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::repositories::approval_repository::ApprovalRequest;
+
+/// What an executor changed
+#[derive(Debug, Default)]
+pub struct ExecutionOutcome {
+    /// ID of a resource created by the request (its approval carried a placeholder)
+    pub resource_id: Option<Uuid>,
+    /// State of the resource after the change
+    pub result: Option<JsonValue>,
+    /// Approval request the change opened in turn (a budget overrun); its
+    /// approvers are notified once the transaction commits
+    pub submitted: Option<ApprovalRequest>,
+}
+
+impl ExecutionOutcome {
+    fn from_value<T: Serialize>(value: &T) -> Self {
+        Self {
+            resource_id: None,
+            result: serde_json::to_value(value).ok(),
+            submitted: None,
+        }
+    }
+
+    fn from_budgeted<T: Serialize>(value: BudgetedResult<T>) -> Self {
+        let mut outcome = Self::from_value(&value);
+        if let BudgetedResult::PendingApproval(request) = value {
+            outcome.submitted = Some(*request);
+        }
+        outcome
+    }
+}
+
+/// Applies approved requests of one resource/action
+#[async_trait::async_trait]
+pub trait ApprovalExecutor: Send + Sync {
+    /// Apply the approved change inside `tx`. An error rolls the change back and
+    /// is recorded as the request's failure reason.
+    async fn apply(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        request: &ApprovalRequest,
+        approved_by: Uuid,
+    ) -> DomainResult<ExecutionOutcome>;
+
+    /// Side effects outside the database (cache, schedules, notifications),
+    /// run once the applied change is committed
+    async fn applied(&self, _request: &ApprovalRequest) {}
+
+    /// Release whatever the request held once it is rejected
+    async fn reject(
+        &self,
+        _request: &ApprovalRequest,
+        _rejected_by: Uuid,
+        _notes: Option<String>,
+    ) -> DomainResult<()> {
+        Ok(())
+    }
+}
+
+/// Executors keyed by (resource_type, action_type); `*` matches any action
+#[derive(Clone)]
+pub struct ApprovalExecutorRegistry {
+    approval_service: ApprovalService,
+    executors: HashMap<(String, String), Arc<dyn ApprovalExecutor>>,
+}
+
+impl ApprovalExecutorRegistry {
+    pub fn new(approval_service: ApprovalService) -> Self {
+        Self {
+            approval_service,
+            executors: HashMap::new(),
+        }
+    }
+
+    fn key(resource_type: &str, action_type: &str) -> (String, String) {
+        (resource_type.to_lowercase(), action_type.to_lowercase())
+    }
+
+    pub fn register(
+        mut self,
+        resource_type: &str,
+        action_type: &str,
+        executor: impl ApprovalExecutor + 'static,
+    ) -> Self {
+        self.executors
+            .insert(Self::key(resource_type, action_type), Arc::new(executor));
+        self
+    }
+
+    pub fn find(
+        &self,
+        resource_type: &str,
+        action_type: &str,
+    ) -> Option<Arc<dyn ApprovalExecutor>> {
+        self.executors
+            .get(&Self::key(resource_type, action_type))
+            .or_else(|| {
+                self.executors
+                    .get(&Self::key(resource_type, WORKFLOW_WILDCARD))
+            })
+            .cloned()
+    }
+
+    /// Sign a request and, when that was its final level, apply the change.
+    ///
+    /// When the owning service refuses the change the request is moved to FAILED with
+    /// the reason, so it no longer reads as approved. Requests without a registered
+    /// executor are only signed.
+    pub async fn approve(
+        &self,
+        request_id: Uuid,
+        approved_by: Uuid,
+        role_level: i32,
+        notes: Option<String>,
+    ) -> DomainResult<ApprovalRequest> {
+        let mut tx = self.approval_service.begin().await?;
+        let request = self
+            .approval_service
+            .approve_request(&mut tx, request_id, approved_by, role_level, notes)
+            .await?;
+
+        let executor = match self.find(&request.resource_type, &request.action_type) {
+            Some(executor) if request.status == "APPROVED" => executor,
+            _ => {
+                commit(tx).await?;
+                self.approval_service.announce(&request).await;
+                return Ok(request);
+            }
+        };
+
+        let mut savepoint = tx.begin().await.map_err(db_error)?;
+        let (request, applied) = match executor.apply(&mut savepoint, &request, approved_by).await {
+            Ok(mut outcome) => {
+                commit(savepoint).await?;
+                let request = self
+                    .approval_service
+                    .record_execution(
+                        &mut tx,
+                        request.id,
+                        outcome.resource_id,
+                        outcome.result.take(),
+                    )
+                    .await?;
+                (request, Some(outcome))
+            }
+            Err(e) => {
+                savepoint.rollback().await.map_err(db_error)?;
+                tracing::warn!(
+                    "Approved request {} ({} {}) could not be applied: {}",
+                    request.id,
+                    request.resource_type,
+                    request.action_type,
+                    e
+                );
+                let request = self
+                    .approval_service
+                    .record_execution_failure(&mut tx, request.id, &e.to_string())
+                    .await?;
+                (request, None)
+            }
+        };
+        commit(tx).await?;
+
+        if let Some(outcome) = applied {
+            executor.applied(&request).await;
+            if let Some(submitted) = &outcome.submitted {
+                self.approval_service.announce(submitted).await;
+            }
+        }
+        self.approval_service.announce(&request).await;
+        Ok(request)
+    }
+
+    /// Let the owning service react to a rejected request
+    pub async fn apply_rejected(
+        &self,
+        request: &ApprovalRequest,
+        rejected_by: Uuid,
+        notes: Option<String>,
+    ) -> DomainResult<()> {
+        match self.find(&request.resource_type, &request.action_type) {
+            Some(executor) => executor.reject(request, rejected_by, notes).await,
+            None => Ok(()),
+        }
+    }
+}
+
+async fn commit(tx: Transaction<'_, Postgres>) -> DomainResult<()> {
+    tx.commit().await.map_err(db_error)
+}
+
+fn db_error(e: sqlx::Error) -> DomainError {
+    DomainError::ExternalServiceError {
+        service: "database".to_string(),
+        message: e.to_string(),
+    }
+}
+
+/// Deserialize the change stored on a request
+fn snapshot<T: DeserializeOwned>(request: &ApprovalRequest) -> DomainResult<T> {
+    let data = request
+        .data_snapshot
+        .clone()
+        .ok_or_else(|| DomainError::validation("data_snapshot", "Request carries no data"))?;
+
+    serde_json::from_value(data).map_err(|e| {
+        DomainError::validation(
+            "data_snapshot",
+            &format!("Snapshot does not match {}: {}", request.action_type, e),
+        )
+    })
+}
+
+// ==================== ASSET ====================
+
+/// ASSET CREATE: insert the asset described by the snapshot
+pub struct AssetCreateExecutor(pub AssetService);
+
+#[async_trait::async_trait]
+impl ApprovalExecutor for AssetCreateExecutor {
+    async fn apply(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        request: &ApprovalRequest,
+        _approved_by: Uuid,
+    ) -> DomainResult<ExecutionOutcome> {
+        let create: CreateAssetRequest = snapshot(request)?;
+        let asset = self.0.apply_create_in(tx, create).await?;
+
+        let mut outcome = ExecutionOutcome::from_value(&asset);
+        outcome.resource_id = Some(asset.id);
+        Ok(outcome)
+    }
+
+    async fn applied(&self, request: &ApprovalRequest) {
+        // resource_id now holds the created asset
+        if let Ok(asset) = self.0.get_by_id(request.resource_id).await {
+            self.0.after_create(&asset).await;
+        }
+    }
+}
+
+/// ASSET UPDATE: apply the snapshot as a partial update of `resource_id`
+pub struct AssetUpdateExecutor(pub AssetService);
+
+#[async_trait::async_trait]
+impl ApprovalExecutor for AssetUpdateExecutor {
+    async fn apply(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        request: &ApprovalRequest,
+        _approved_by: Uuid,
+    ) -> DomainResult<ExecutionOutcome> {
+        let update: UpdateAssetRequest = snapshot(request)?;
+        let asset = self
+            .0
+            .update_in(tx, request.resource_id, update, Some(request.requested_by))
+            .await?;
+        Ok(ExecutionOutcome::from_value(&asset))
+    }
+
+    async fn applied(&self, request: &ApprovalRequest) {
+        self.0.after_update(request.resource_id).await;
+    }
+}
+
+/// ASSET DELETE: delete `resource_id`, keeping its last state as the result
+pub struct AssetDeleteExecutor(pub AssetService);
+
+#[async_trait::async_trait]
+impl ApprovalExecutor for AssetDeleteExecutor {
+    async fn apply(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        request: &ApprovalRequest,
+        _approved_by: Uuid,
+    ) -> DomainResult<ExecutionOutcome> {
+        let asset = self.0.get_by_id(request.resource_id).await?;
+
+        if !self.0.delete_in(tx, request.resource_id).await? {
+            return Err(DomainError::not_found("Asset", request.resource_id));
+        }
+        Ok(ExecutionOutcome::from_value(&asset))
+    }
+
+    async fn applied(&self, request: &ApprovalRequest) {
+        self.0.after_delete(request.resource_id).await;
+    }
+}
+
+// ==================== LIFECYCLE ====================
+
+#[derive(Debug, Deserialize)]
+struct TransitionSnapshot {
+    from_state: String,
+    to_state: String,
+    reason: Option<String>,
+}
+
+/// Lifecycle transition: move the asset if it is still in the state the request saw
+pub struct LifecycleTransitionExecutor(pub LifecycleService);
+
+#[async_trait::async_trait]
+impl ApprovalExecutor for LifecycleTransitionExecutor {
+    async fn apply(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        request: &ApprovalRequest,
+        approved_by: Uuid,
+    ) -> DomainResult<ExecutionOutcome> {
+        let transition: TransitionSnapshot = snapshot(request)?;
+        let history = self
+            .0
+            .execute_approved_transition(
+                tx,
+                request.resource_id,
+                &transition.from_state,
+                &transition.to_state,
+                transition.reason,
+                approved_by,
+            )
+            .await?;
+        Ok(ExecutionOutcome::from_value(&history))
+    }
+}
+
+// ==================== DEPRECIATION ====================
+
+/// Depreciation period REOPEN: unlock the closed month the request points at
+pub struct DepreciationReopenExecutor(pub DepreciationService);
+
+#[async_trait::async_trait]
+impl ApprovalExecutor for DepreciationReopenExecutor {
+    async fn apply(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        request: &ApprovalRequest,
+        approved_by: Uuid,
+    ) -> DomainResult<ExecutionOutcome> {
+        let closing = self
+            .0
+            .apply_reopen_in(tx, request.resource_id, approved_by)
+            .await?;
+        Ok(ExecutionOutcome::from_value(&closing))
+    }
+
+    async fn reject(
+        &self,
+        request: &ApprovalRequest,
+        _rejected_by: Uuid,
+        _notes: Option<String>,
+    ) -> DomainResult<()> {
+        self.0.cancel_reopen(request.resource_id).await?;
+        Ok(())
+    }
+}
+
+// ==================== CONVERSION / LOAN / RENTAL ====================
+
+/// Conversion request: approve or reject the conversion the request points at
+pub struct ConversionExecutor(pub ConversionService);
+
+#[async_trait::async_trait]
+impl ApprovalExecutor for ConversionExecutor {
+    async fn apply(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        request: &ApprovalRequest,
+        approved_by: Uuid,
+    ) -> DomainResult<ExecutionOutcome> {
+        let conversion = self
+            .0
+            .approve_request_in(tx, request.resource_id, approved_by)
+            .await?;
+        Ok(ExecutionOutcome::from_budgeted(conversion))
+    }
+
+    async fn reject(
+        &self,
+        request: &ApprovalRequest,
+        _rejected_by: Uuid,
+        _notes: Option<String>,
+    ) -> DomainResult<()> {
+        self.0.reject_request(request.resource_id).await?;
+        Ok(())
+    }
+}
+
+pub struct LoanExecutor(pub LoanService);
+
+#[async_trait::async_trait]
+impl ApprovalExecutor for LoanExecutor {
+    async fn apply(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        request: &ApprovalRequest,
+        approved_by: Uuid,
+    ) -> DomainResult<ExecutionOutcome> {
+        let loan = self
+            .0
+            .approve_in(tx, request.resource_id, approved_by)
+            .await?;
+        Ok(ExecutionOutcome::from_value(&loan))
+    }
+
+    async fn applied(&self, request: &ApprovalRequest) {
+        if let Ok(loan) = self.0.get_by_id(request.resource_id).await {
+            self.0.notify_approved(&loan).await;
+        }
+    }
+
+    async fn reject(
+        &self,
+        request: &ApprovalRequest,
+        _rejected_by: Uuid,
+        notes: Option<String>,
+    ) -> DomainResult<()> {
+        self.0.apply_reject(request.resource_id, notes).await?;
+        Ok(())
+    }
+}
+
+/// Rental request: approve on the terms the rental was requested with
+pub struct RentalExecutor(pub RentalService);
+
+#[async_trait::async_trait]
+impl ApprovalExecutor for RentalExecutor {
+    async fn apply(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        request: &ApprovalRequest,
+        approved_by: Uuid,
+    ) -> DomainResult<ExecutionOutcome> {
+        let rental = self.0.get_by_id(request.resource_id).await?;
+        let terms = RentalService::approval_terms(&rental).ok_or_else(|| {
+            DomainError::validation(
+                "rental",
+                "Rental is missing start date, end date or daily rate",
+            )
+        })?;
+
+        let rental = self
+            .0
+            .approve_rental_in(tx, request.resource_id, approved_by, terms)
+            .await?;
+        Ok(ExecutionOutcome::from_value(&rental))
+    }
+
+    async fn reject(
+        &self,
+        request: &ApprovalRequest,
+        _rejected_by: Uuid,
+        notes: Option<String>,
+    ) -> DomainResult<()> {
+        let reason = notes.unwrap_or_else(|| "Rejected in approval workflow".to_string());
+        self.0
+            .reject_rental(request.resource_id, RejectRentalRequest { reason })
+            .await?;
+        Ok(())
+    }
+}
+
+// ==================== BUDGET ====================
+
+#[derive(Deserialize)]
+struct BudgetOverrunSnapshot {
+    source_type: String,
+}
+
+/// Budget overrun: approve the held-up work order or conversion above its
+/// budget. A rejected overrun rejects the conversion; the work order stays
+/// pending so it can be re-estimated or approved once the budget is raised.
+pub struct BudgetOverrunExecutor {
+    pub work_orders: WorkOrderService,
+    pub conversions: ConversionService,
+}
+
+#[async_trait::async_trait]
+impl ApprovalExecutor for BudgetOverrunExecutor {
+    async fn apply(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        request: &ApprovalRequest,
+        approved_by: Uuid,
+    ) -> DomainResult<ExecutionOutcome> {
+        let data: BudgetOverrunSnapshot = snapshot(request)?;
+        match data.source_type.as_str() {
+            SPEND_WORK_ORDER => {
+                let wo = self
+                    .work_orders
+                    .approve_over_budget(tx, request.resource_id, approved_by, request.id)
+                    .await?;
+                Ok(ExecutionOutcome::from_value(&wo))
+            }
+            SPEND_CONVERSION => {
+                let conversion = self
+                    .conversions
+                    .approve_over_budget(tx, request.resource_id, approved_by, request.id)
+                    .await?;
+                Ok(ExecutionOutcome::from_value(&conversion))
+            }
+            other => Err(DomainError::validation(
+                "source_type",
+                &format!("No budget overrun handling for {}", other),
+            )),
+        }
+    }
+
+    async fn reject(
+        &self,
+        request: &ApprovalRequest,
+        _rejected_by: Uuid,
+        _notes: Option<String>,
+    ) -> DomainResult<()> {
+        let data: BudgetOverrunSnapshot = snapshot(request)?;
+        if data.source_type == SPEND_CONVERSION {
+            self.conversions.reject_request(request.resource_id).await?;
+        }
+        Ok(())
+    }
+}
@@ -1,21 +1,161 @@
+use crate::application::dto::{
+    CreateApprovalWorkflowRequest, UpdateApprovalWorkflowRequest, WorkflowLevelInput,
+};
+use crate::domain::entities::{ApprovalAction, ApprovalWorkflow, WORKFLOW_WILDCARD};
 use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::messaging::{NotificationMessage, WebSocketManager};
 use crate::infrastructure::repositories::{
     approval_repository::scan_approval_request::CreateApprovalRequest,
-    approval_repository::ApprovalRequest, ApprovalRepository,
+    approval_repository::ApprovalRequest, ApprovalRepository, ApprovalWorkflowRepository,
+    RbacRepository,
 };
 use serde_json::Value as JsonValue;
+use sqlx::{Postgres, Transaction};
+use std::sync::Arc;
 use uuid::Uuid;
 
+/// Role level that may sign any outstanding approval slot
+const ROLE_SUPER_ADMIN: i32 = 1;
+
+/// Outcome of submitting a change for approval
+#[derive(Debug, Clone)]
+pub enum ApprovalSubmission {
+    /// The matching workflow does not require approval; apply the change directly
+    NotRequired,
+    /// An approval request was opened
+    Pending(Box<ApprovalRequest>),
+}
+
+fn approval_payload(request: &ApprovalRequest) -> JsonValue {
+    serde_json::json!({
+        "id": request.id,
+        "resource_type": request.resource_type,
+        "resource_id": request.resource_id,
+        "action_type": request.action_type,
+        "status": request.status,
+        "current_approval_level": request.current_approval_level,
+    })
+}
+
 #[derive(Clone)]
 pub struct ApprovalService {
     pub repository: ApprovalRepository,
+    workflow_repo: ApprovalWorkflowRepository,
+    rbac_repo: RbacRepository,
+    ws_manager: Arc<WebSocketManager>,
 }
 
 impl ApprovalService {
-    pub fn new(repository: ApprovalRepository) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: ApprovalRepository,
+        workflow_repo: ApprovalWorkflowRepository,
+        rbac_repo: RbacRepository,
+        ws_manager: Arc<WebSocketManager>,
+    ) -> Self {
+        Self {
+            repository,
+            workflow_repo,
+            rbac_repo,
+            ws_manager,
+        }
+    }
+
+    // ==================== WORKFLOW RESOLUTION ====================
+
+    /// Resolve the workflow (with levels) applicable to a resource/action.
+    ///
+    /// `category_id` lets category-specific workflows (`asset_type`) take precedence.
+    pub async fn resolve_workflow(
+        &self,
+        resource_type: &str,
+        action_type: &str,
+        category_id: Option<Uuid>,
+    ) -> DomainResult<Option<ApprovalWorkflow>> {
+        let workflow = self
+            .workflow_repo
+            .resolve(resource_type, action_type, category_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+
+        match workflow {
+            Some(wf) => Ok(Some(self.with_levels(wf).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Whether a resource/action needs approval under the configured workflows
+    pub async fn requires_approval(
+        &self,
+        resource_type: &str,
+        action_type: &str,
+        category_id: Option<Uuid>,
+    ) -> DomainResult<bool> {
+        Ok(self
+            .resolve_workflow(resource_type, action_type, category_id)
+            .await?
+            .map(|wf| wf.requires_approval())
+            .unwrap_or(false))
+    }
+
+    async fn with_levels(&self, mut workflow: ApprovalWorkflow) -> DomainResult<ApprovalWorkflow> {
+        workflow.levels = self
+            .workflow_repo
+            .find_levels(workflow.id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        Ok(workflow)
     }
 
+    /// Workflow a request runs against (re-resolved if its workflow was deleted)
+    async fn workflow_for(&self, request: &ApprovalRequest) -> DomainResult<ApprovalWorkflow> {
+        let workflow = match request.workflow_id {
+            Some(id) => self.workflow_repo.find_by_id(id).await.map_err(|e| {
+                DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                }
+            })?,
+            None => None,
+        };
+
+        let workflow = match workflow {
+            Some(wf) => Some(self.with_levels(wf).await?),
+            None => {
+                self.resolve_workflow(&request.resource_type, &request.action_type, None)
+                    .await?
+            }
+        };
+
+        workflow.ok_or_else(|| {
+            DomainError::business_rule(
+                "approval_workflow",
+                "No approval workflow is configured for this request",
+            )
+        })
+    }
+
+    async fn user_role_ids(&self, user_id: Uuid) -> DomainResult<Vec<Uuid>> {
+        let roles = self.rbac_repo.get_user_roles(user_id).await.map_err(|e| {
+            DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            }
+        })?;
+        Ok(roles.into_iter().map(|r| r.id).collect())
+    }
+
+    // ==================== REQUESTS ====================
+
+    /// Submit a change for approval.
+    ///
+    /// Returns `NotRequired` when no active workflow matches or the matching workflow
+    /// has `need_approval = false`; the caller then applies the change itself.
     pub async fn create_request(
         &self,
         resource_type: &str,
@@ -23,17 +163,128 @@ impl ApprovalService {
         action_type: &str,
         requested_by: Uuid,
         data: Option<JsonValue>,
-    ) -> DomainResult<ApprovalRequest> {
+        category_id: Option<Uuid>,
+    ) -> DomainResult<ApprovalSubmission> {
+        let (workflow, req) = match self
+            .prepare_request(
+                resource_type,
+                resource_id,
+                action_type,
+                requested_by,
+                data,
+                category_id,
+            )
+            .await?
+        {
+            Some(prepared) => prepared,
+            None => return Ok(ApprovalSubmission::NotRequired),
+        };
+
+        let request =
+            self.repository
+                .create(&req)
+                .await
+                .map_err(|e| DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                })?;
+
+        self.notify_approvers(&request, &workflow).await;
+        Ok(ApprovalSubmission::Pending(Box::new(request)))
+    }
+
+    /// Submit a change for approval inside `tx`. Approvers are not notified;
+    /// call `announce` once the transaction is committed.
+    pub async fn create_request_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        resource_type: &str,
+        resource_id: Uuid,
+        action_type: &str,
+        requested_by: Uuid,
+        data: Option<JsonValue>,
+    ) -> DomainResult<ApprovalSubmission> {
+        let req = match self
+            .prepare_request(
+                resource_type,
+                resource_id,
+                action_type,
+                requested_by,
+                data,
+                None,
+            )
+            .await?
+        {
+            Some((_, req)) => req,
+            None => return Ok(ApprovalSubmission::NotRequired),
+        };
+
+        let request = ApprovalRepository::create_with(&mut **tx, &req)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        Ok(ApprovalSubmission::Pending(Box::new(request)))
+    }
+
+    /// The request to insert, or None when the change needs no approval
+    async fn prepare_request(
+        &self,
+        resource_type: &str,
+        resource_id: Uuid,
+        action_type: &str,
+        requested_by: Uuid,
+        data: Option<JsonValue>,
+        category_id: Option<Uuid>,
+    ) -> DomainResult<Option<(ApprovalWorkflow, CreateApprovalRequest)>> {
+        let workflow = match self
+            .resolve_workflow(resource_type, action_type, category_id)
+            .await?
+        {
+            Some(wf) if wf.requires_approval() => wf,
+            _ => return Ok(None),
+        };
+
+        let levels = workflow.level_numbers();
         let req = CreateApprovalRequest {
             resource_type: resource_type.to_string(),
             resource_id,
             action_type: action_type.to_string(),
             requested_by,
             data_snapshot: data,
+            workflow_id: Some(workflow.id),
+            current_approval_level: levels.first().copied().unwrap_or(1),
+            total_levels: levels.len() as i32,
         };
+        Ok(Some((workflow, req)))
+    }
 
+    /// Requests waiting on the user's roles (all pending requests for super admin)
+    pub async fn list_pending(
+        &self,
+        user_id: Uuid,
+        role_level: i32,
+    ) -> DomainResult<Vec<ApprovalRequest>> {
+        let result = if role_level == ROLE_SUPER_ADMIN {
+            self.repository.list_pending_all().await
+        } else {
+            let role_ids = self.user_role_ids(user_id).await?;
+            if role_ids.is_empty() {
+                return Ok(vec![]);
+            }
+            self.repository.list_pending_for_roles(&role_ids).await
+        };
+
+        result.map_err(|e| DomainError::ExternalServiceError {
+            service: "database".to_string(),
+            message: e.to_string(),
+        })
+    }
+
+    pub async fn list_my_requests(&self, user_id: Uuid) -> DomainResult<Vec<ApprovalRequest>> {
         self.repository
-            .create(&req)
+            .list_by_requester(user_id)
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
@@ -41,23 +292,30 @@ impl ApprovalService {
             })
     }
 
-    pub async fn list_pending(&self, role_level: i32) -> DomainResult<Vec<ApprovalRequest>> {
-        // Supervisor (3) approves Level 1
-        // Manager (2) approves Level 2
-        let target_level = if role_level == 3 {
-            1
-        } else if role_level == 2 {
-            2
-        } else {
-            0
-        };
+    pub async fn get_request(&self, request_id: Uuid) -> DomainResult<ApprovalRequest> {
+        self.repository
+            .find_by_id(request_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("ApprovalRequest", request_id.to_string()))
+    }
 
-        if target_level == 0 {
-            return Ok(vec![]); // Or return all if superadmin?
-        }
+    pub async fn list_actions(&self, request_id: Uuid) -> DomainResult<Vec<ApprovalAction>> {
+        self.repository.list_actions(request_id).await.map_err(|e| {
+            DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            }
+        })
+    }
 
+    /// Resource IDs of a type that currently have an open request
+    pub async fn open_resource_ids(&self, resource_type: &str) -> DomainResult<Vec<Uuid>> {
         self.repository
-            .list_pending(target_level)
+            .open_resource_ids(resource_type)
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
@@ -65,9 +323,64 @@ impl ApprovalService {
             })
     }
 
-    pub async fn list_my_requests(&self, user_id: Uuid) -> DomainResult<Vec<ApprovalRequest>> {
+    /// Pick the role slot the approver signs at the request's current level
+    async fn signing_slot(
+        &self,
+        request: &ApprovalRequest,
+        workflow: &ApprovalWorkflow,
+        approver_id: Uuid,
+        role_level: i32,
+    ) -> DomainResult<Uuid> {
+        if request.status != "PENDING" {
+            return Err(DomainError::business_rule(
+                "approval_status",
+                &format!("Request is already {}", request.status),
+            ));
+        }
+
+        let level = request.current_approval_level;
+        let actions = self.list_actions(request.id).await?;
+        let is_super_admin = role_level == ROLE_SUPER_ADMIN;
+
+        if !is_super_admin {
+            if request.requested_by == approver_id {
+                return Err(DomainError::unauthorized(
+                    "approve a request you submitted yourself",
+                ));
+            }
+            if actions
+                .iter()
+                .any(|a| a.level_number == level && a.approver_id == approver_id)
+            {
+                return Err(DomainError::conflict(
+                    "You have already signed this approval level",
+                ));
+            }
+        }
+
+        let pending = workflow.pending_roles(level, &actions);
+        let role_ids = self.user_role_ids(approver_id).await?;
+
+        pending
+            .iter()
+            .find(|role_id| role_ids.contains(role_id))
+            .or_else(|| {
+                if is_super_admin {
+                    pending.first()
+                } else {
+                    None
+                }
+            })
+            .copied()
+            .ok_or_else(|| {
+                DomainError::unauthorized(&format!("approve this request at level {}", level))
+            })
+    }
+
+    /// Start the transaction an approval and the change it applies share
+    pub async fn begin(&self) -> DomainResult<Transaction<'static, Postgres>> {
         self.repository
-            .list_by_requester(user_id)
+            .begin()
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
@@ -75,105 +388,373 @@ impl ApprovalService {
             })
     }
 
+    /// Sign the request's current level inside `tx`. Nobody is notified until
+    /// the caller commits and calls `announce`.
     pub async fn approve_request(
         &self,
+        tx: &mut Transaction<'_, Postgres>,
         request_id: Uuid,
         approver_id: Uuid,
-        _role_level: i32,
+        role_level: i32,
         notes: Option<String>,
     ) -> DomainResult<ApprovalRequest> {
-        let request = self
+        let request = self.get_request(request_id).await?;
+        let workflow = self.workflow_for(&request).await?;
+        let role_id = self
+            .signing_slot(&request, &workflow, approver_id, role_level)
+            .await?;
+
+        ApprovalRepository::record_approval(
+            tx,
+            request_id,
+            workflow.id,
+            request.current_approval_level,
+            role_id,
+            approver_id,
+            notes,
+        )
+        .await
+        .map_err(|e| DomainError::ExternalServiceError {
+            service: "database".to_string(),
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| DomainError::conflict("Request was updated by another approver"))
+    }
+
+    /// Tell the next approvers about a pending request, or the requester about
+    /// a decided one
+    pub async fn announce(&self, request: &ApprovalRequest) {
+        if request.status != "PENDING" {
+            self.notify_requester(request).await;
+            return;
+        }
+        match self.workflow_for(request).await {
+            Ok(workflow) => self.notify_approvers(request, &workflow).await,
+            Err(e) => tracing::warn!("Approvers of request {} not notified: {}", request.id, e),
+        }
+    }
+
+    pub async fn reject_request(
+        &self,
+        request_id: Uuid,
+        approver_id: Uuid,
+        role_level: i32,
+        notes: String,
+    ) -> DomainResult<ApprovalRequest> {
+        let request = self.get_request(request_id).await?;
+        let workflow = self.workflow_for(&request).await?;
+        let role_id = self
+            .signing_slot(&request, &workflow, approver_id, role_level)
+            .await?;
+
+        let updated = self
             .repository
-            .find_by_id(request_id)
+            .record_rejection(
+                request_id,
+                request.current_approval_level,
+                role_id,
+                approver_id,
+                notes,
+            )
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
             })?
-            .ok_or_else(|| DomainError::not_found("ApprovalRequest", request_id.to_string()))?;
+            .ok_or_else(|| DomainError::conflict("Request was updated by another approver"))?;
 
-        // Verify Level
-        // If Request is L1, Approver must be Supervisor (3) or higher (1, 2)
-        // Ideally we check strict level match or "can_approve_level_1" permission
+        self.notify_requester(&updated).await;
+        Ok(updated)
+    }
 
-        if request.current_approval_level == 1 {
-            // L1 Approval
-            // Update status to APPROVED_L1 if logic requires 2 steps.
-            // If fully approved after L1 (workflow dependent), set APPROVED_L2/COMPLETED logic?
-            // For now assume 2 steps:
-            // L1 -> APPROVED_L1 -> Increment Level -> 2
+    /// Tell the users who can sign the request's current level that it waits
+    /// on them
+    async fn notify_approvers(&self, request: &ApprovalRequest, workflow: &ApprovalWorkflow) {
+        let actions = match self.list_actions(request.id).await {
+            Ok(actions) => actions,
+            Err(e) => {
+                tracing::warn!("Approvers of request {} not notified: {}", request.id, e);
+                return;
+            }
+        };
+        let role_ids = workflow.pending_roles(request.current_approval_level, &actions);
+        let approvers: Vec<Uuid> = match self.rbac_repo.list_user_ids_with_roles(&role_ids).await {
+            Ok(users) => users
+                .into_iter()
+                .filter(|id| *id != request.requested_by)
+                .collect(),
+            Err(e) => {
+                tracing::warn!("Approvers of request {} not notified: {}", request.id, e);
+                return;
+            }
+        };
+        self.ws_manager
+            .send_to_users(
+                &approvers,
+                &NotificationMessage::new("APPROVAL_REQUESTED", approval_payload(request)),
+            )
+            .await;
+    }
 
-            self.repository
-                .update_status(request_id, "APPROVED_L1", 1, Some(approver_id), notes)
-                .await
-                .map_err(|e| DomainError::ExternalServiceError {
-                    service: "database".to_string(),
-                    message: e.to_string(),
-                })?;
+    async fn notify_requester(&self, request: &ApprovalRequest) {
+        self.ws_manager
+            .send_to_user(
+                request.requested_by,
+                &NotificationMessage::new("APPROVAL_DECIDED", approval_payload(request)),
+            )
+            .await;
+    }
 
-            self.repository
-                .increment_level(request_id)
+    /// Store the result of applying an approved request
+    pub async fn record_execution(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        request_id: Uuid,
+        resource_id: Option<Uuid>,
+        result: Option<JsonValue>,
+    ) -> DomainResult<ApprovalRequest> {
+        ApprovalRepository::record_execution(tx, request_id, resource_id, result)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| {
+                DomainError::business_rule("approval_status", "Request is no longer approved")
+            })
+    }
+
+    /// Move an approved request to FAILED with the reason its change was refused
+    pub async fn record_execution_failure(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        request_id: Uuid,
+        reason: &str,
+    ) -> DomainResult<ApprovalRequest> {
+        ApprovalRepository::record_execution_failure(tx, request_id, reason)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| {
+                DomainError::business_rule("approval_status", "Request is no longer approved")
+            })
+    }
+
+    // ==================== WORKFLOW ADMINISTRATION ====================
+
+    pub async fn list_workflows(&self) -> DomainResult<Vec<ApprovalWorkflow>> {
+        let workflows =
+            self.workflow_repo
+                .list()
                 .await
                 .map_err(|e| DomainError::ExternalServiceError {
                     service: "database".to_string(),
                     message: e.to_string(),
                 })?;
 
-            // Re-fetch updated
-            let updated = self
-                .repository
-                .find_by_id(request_id)
-                .await
-                .map_err(|e| DomainError::ExternalServiceError {
+        let mut result = Vec::with_capacity(workflows.len());
+        for wf in workflows {
+            result.push(self.with_levels(wf).await?);
+        }
+        Ok(result)
+    }
+
+    pub async fn get_workflow(&self, id: Uuid) -> DomainResult<ApprovalWorkflow> {
+        let workflow = self
+            .workflow_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("ApprovalWorkflow", id))?;
+        self.with_levels(workflow).await
+    }
+
+    /// Turn level inputs into (level_number, role_id) pairs, resolving role codes
+    async fn resolve_levels(
+        &self,
+        levels: &[WorkflowLevelInput],
+    ) -> DomainResult<Vec<(i32, Uuid)>> {
+        let mut resolved = Vec::with_capacity(levels.len());
+
+        for level in levels {
+            if level.level_number <= 0 {
+                return Err(DomainError::validation(
+                    "levels",
+                    "level_number must be greater than zero",
+                ));
+            }
+
+            let role_id = match (level.role_id, &level.role_code) {
+                (Some(id), _) => id,
+                (None, Some(code)) => {
+                    self.rbac_repo
+                        .find_role_by_code(code)
+                        .await
+                        .map_err(|e| DomainError::ExternalServiceError {
+                            service: "database".to_string(),
+                            message: e.to_string(),
+                        })?
+                        .ok_or_else(|| DomainError::not_found("Role", code))?
+                        .id
+                }
+                (None, None) => {
+                    return Err(DomainError::validation(
+                        "levels",
+                        "Each level needs a role_id or role_code",
+                    ))
+                }
+            };
+
+            resolved.push((level.level_number, role_id));
+        }
+
+        Ok(resolved)
+    }
+
+    fn validate_key(resource_type: &str, action_type: &str) -> DomainResult<()> {
+        if resource_type.trim().is_empty() {
+            return Err(DomainError::validation("resource_type", "Required"));
+        }
+        if action_type.trim().is_empty() {
+            return Err(DomainError::validation("action_type", "Required"));
+        }
+        Ok(())
+    }
+
+    pub async fn create_workflow(
+        &self,
+        request: CreateApprovalWorkflowRequest,
+    ) -> DomainResult<ApprovalWorkflow> {
+        Self::validate_key(&request.resource_type, &request.action_type)?;
+        let levels = self.resolve_levels(&request.levels).await?;
+
+        let need_approval = request.need_approval.unwrap_or(true);
+        if need_approval && levels.is_empty() {
+            return Err(DomainError::validation(
+                "levels",
+                "At least one level is required when need_approval is true",
+            ));
+        }
+
+        let workflow = ApprovalWorkflow {
+            id: Uuid::new_v4(),
+            resource_type: request.resource_type,
+            action_type: request.action_type,
+            asset_type: request.asset_type,
+            name: request.name,
+            description: request.description,
+            need_approval: Some(need_approval),
+            is_active: Some(request.is_active.unwrap_or(true)),
+            created_at: None,
+            updated_at: None,
+            levels: vec![],
+        };
+
+        let created = self
+            .workflow_repo
+            .create(&workflow, &levels)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(ref db) if db.code().as_deref() == Some("23505") => {
+                    DomainError::conflict("A workflow for this resource/action already exists")
+                }
+                _ => DomainError::ExternalServiceError {
                     service: "database".to_string(),
                     message: e.to_string(),
-                })?
-                .unwrap();
+                },
+            })?;
 
-            Ok(updated)
-        } else if request.current_approval_level == 2 {
-            // L2 Approval (Final)
-            self.repository
-                .update_status(request_id, "APPROVED_L2", 2, Some(approver_id), notes)
+        self.with_levels(created).await
+    }
+
+    pub async fn update_workflow(
+        &self,
+        id: Uuid,
+        request: UpdateApprovalWorkflowRequest,
+    ) -> DomainResult<ApprovalWorkflow> {
+        let mut workflow = self.get_workflow(id).await?;
+
+        if let Some(v) = request.resource_type {
+            workflow.resource_type = v;
+        }
+        if let Some(v) = request.action_type {
+            workflow.action_type = v;
+        }
+        if let Some(v) = request.asset_type {
+            workflow.asset_type = if v.is_empty() { None } else { Some(v) };
+        }
+        if let Some(v) = request.name {
+            workflow.name = Some(v);
+        }
+        if let Some(v) = request.description {
+            workflow.description = Some(v);
+        }
+        if let Some(v) = request.need_approval {
+            workflow.need_approval = Some(v);
+        }
+        if let Some(v) = request.is_active {
+            workflow.is_active = Some(v);
+        }
+        Self::validate_key(&workflow.resource_type, &workflow.action_type)?;
+
+        let levels = match &request.levels {
+            Some(levels) => Some(self.resolve_levels(levels).await?),
+            None => None,
+        };
+
+        // Changing levels under running requests would strand them mid-chain
+        if levels.is_some() {
+            let pending = self
+                .workflow_repo
+                .count_pending_requests(id)
                 .await
                 .map_err(|e| DomainError::ExternalServiceError {
                     service: "database".to_string(),
                     message: e.to_string(),
-                })
-        } else {
-            Err(DomainError::validation(
-                "approval_level",
-                "Invalid approval level state",
-            ))
+                })?;
+            if pending > 0 {
+                return Err(DomainError::business_rule(
+                    "workflow_in_use",
+                    &format!(
+                        "{} pending requests use this workflow; resolve them before changing levels",
+                        pending
+                    ),
+                ));
+            }
         }
-    }
 
-    pub async fn reject_request(
-        &self,
-        request_id: Uuid,
-        approver_id: Uuid,
-        notes: String,
-    ) -> DomainResult<ApprovalRequest> {
-        // Find request
-        let request = self
-            .repository
-            .find_by_id(request_id)
+        let updated = self
+            .workflow_repo
+            .update(&workflow, levels.as_deref())
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
-            })?
-            .ok_or_else(|| DomainError::not_found("ApprovalRequest", request_id.to_string()))?;
+            })?;
 
-        // Update to REJECTED
-        self.repository
-            .update_status(
-                request_id,
-                "REJECTED",
-                request.current_approval_level,
-                Some(approver_id),
-                Some(notes),
-            )
+        self.with_levels(updated).await
+    }
+
+    pub async fn delete_workflow(&self, id: Uuid) -> DomainResult<bool> {
+        let workflow = self.get_workflow(id).await?;
+
+        if workflow.resource_type == WORKFLOW_WILDCARD
+            && workflow.action_type == WORKFLOW_WILDCARD
+            && workflow.asset_type.is_none()
+        {
+            return Err(DomainError::business_rule(
+                "default_workflow",
+                "The default workflow cannot be deleted; deactivate it instead",
+            ));
+        }
+
+        self.workflow_repo
+            .delete(id)
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
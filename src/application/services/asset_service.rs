@@ -3,20 +3,28 @@
 use crate::domain::entities::asset_details::VehicleDetails;
 use chrono::Utc;
 use serde::Serialize;
+use sqlx::{Postgres, Transaction};
 use uuid::Uuid;
 
 use crate::application::dto::{
     AssetSearchParams, BulkCreateAssetRequest, CreateAssetRequest, PaginatedResponse,
     UpdateAssetRequest,
 };
-use crate::domain::entities::{Asset, AssetHistory, AssetState, AssetSummary};
+use crate::domain::entities::{
+    Asset, AssetHistory, AssetState, AssetSummary, UserClaims, SPEC_CHANGE_MODIFICATION,
+    SPEND_ASSET_PURCHASE,
+};
 use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::events::{AssetCreated, EventEnvelope};
 use crate::infrastructure::repositories::AssetRepository;
 
 use crate::infrastructure::cache::{CacheJson, CacheKey, CacheOperations};
 use std::sync::Arc;
 
-use crate::application::services::ApprovalService;
+use crate::application::services::{
+    ApprovalService, ApprovalSubmission, BudgetService, BudgetSpend, DepreciationService,
+    RbacService, SpecificationService,
+};
 use crate::infrastructure::repositories::approval_repository::ApprovalRequest;
 
 /// Result of an asset creation/update attempt
@@ -33,6 +41,10 @@ pub struct AssetService {
     repository: AssetRepository,
     cache: Arc<dyn CacheOperations>,
     approval_service: ApprovalService,
+    depreciation_service: DepreciationService,
+    specification_service: SpecificationService,
+    budget_service: BudgetService,
+    rbac_service: RbacService,
 }
 
 impl AssetService {
@@ -40,44 +52,84 @@ impl AssetService {
         repository: AssetRepository,
         cache: Arc<dyn CacheOperations>,
         approval_service: ApprovalService,
+        depreciation_service: DepreciationService,
+        specification_service: SpecificationService,
+        budget_service: BudgetService,
+        rbac_service: RbacService,
     ) -> Self {
         Self {
             repository,
             cache,
             approval_service,
+            depreciation_service,
+            specification_service,
+            budget_service,
+            rbac_service,
         }
     }
 
-    /// List assets with pagination
+    /// List assets with pagination, within the caller's organization and data scope
     pub async fn list(
         &self,
         page: i64,
         per_page: i64,
-        department: Option<&str>,
+        claims: &UserClaims,
     ) -> DomainResult<PaginatedResponse<AssetSummary>> {
+        let org_scope = claims.organization_id();
+        let scope = self.rbac_service.data_scope(claims, "asset.read").await?;
         let offset = (page - 1) * per_page;
         let assets = self
             .repository
-            .list(per_page, offset, department)
+            .list(per_page, offset, org_scope, &scope)
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
             })?;
 
-        let total =
-            self.repository
-                .count()
-                .await
-                .map_err(|e| DomainError::ExternalServiceError {
-                    service: "database".to_string(),
-                    message: e.to_string(),
-                })?;
+        let total = self
+            .repository
+            .count(org_scope, &scope)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
 
         Ok(PaginatedResponse::new(assets, total, page, per_page))
     }
 
-    /// Get asset by ID
+    /// Assets outside the caller's data scope of `permission` are reported as not found
+    async fn ensure_in_scope(
+        &self,
+        id: Uuid,
+        claims: &UserClaims,
+        permission: &str,
+    ) -> DomainResult<()> {
+        let scope = self.rbac_service.data_scope(claims, permission).await?;
+        if scope.all_records {
+            return Ok(());
+        }
+        let visible = self
+            .repository
+            .in_data_scope(id, &scope)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        if !visible {
+            return Err(DomainError::not_found("Asset", id));
+        }
+        Ok(())
+    }
+
+    /// Get asset by ID within the data scope of the caller
+    pub async fn get_in_scope(&self, id: Uuid, claims: &UserClaims) -> DomainResult<Asset> {
+        self.ensure_in_scope(id, claims, "asset.read").await?;
+        self.get_by_id(id).await
+    }
+
     /// Get asset by ID
     pub async fn get_by_id(&self, id: Uuid) -> DomainResult<Asset> {
         let cache_key = CacheKey::asset(&id);
@@ -115,40 +167,35 @@ impl AssetService {
             .ok_or_else(|| DomainError::not_found("Asset", code))
     }
 
-    /// Search assets
+    /// Search assets within the caller's organization and data scope
     pub async fn search(
         &self,
         params: AssetSearchParams,
+        claims: &UserClaims,
     ) -> DomainResult<PaginatedResponse<AssetSummary>> {
+        let org_scope = claims.organization_id();
+        let scope = self.rbac_service.data_scope(claims, "asset.read").await?;
         let page = params.page.unwrap_or(1).max(1);
         let per_page = params.per_page.unwrap_or(20).clamp(1, 100);
         let offset = (page - 1) * per_page;
 
         let assets = self
             .repository
-            .search(
-                params.query.as_deref().unwrap_or(""),
-                params.category_id,
-                params.location_id,
-                params.department.as_deref(),
-                params.status.as_deref(),
-                per_page,
-                offset,
-            )
+            .search(&params, per_page, offset, org_scope, &scope)
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
             })?;
 
-        let total =
-            self.repository
-                .count()
-                .await
-                .map_err(|e| DomainError::ExternalServiceError {
-                    service: "database".to_string(),
-                    message: e.to_string(),
-                })?;
+        let total = self
+            .repository
+            .count(org_scope, &scope)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
 
         Ok(PaginatedResponse::new(assets, total, page, per_page))
     }
@@ -169,7 +216,7 @@ impl AssetService {
                 )
             })?;
 
-            let approval_request = self
+            let submission = self
                 .approval_service
                 .create_request(
                     "Asset",
@@ -177,14 +224,51 @@ impl AssetService {
                     "CREATE",
                     user_id,
                     Some(data_json),
+                    Some(request.category_id),
                 )
                 .await?;
 
-            return Ok(AssetOperationResult::PendingApproval(approval_request));
+            if let ApprovalSubmission::Pending(approval_request) = submission {
+                return Ok(AssetOperationResult::PendingApproval(*approval_request));
+            }
         }
 
-        // --- Normal Creation Logic ---
+        let created_asset = self.apply_create(request).await?;
+        Ok(AssetOperationResult::Success(created_asset))
+    }
+
+    /// Insert an asset without approval checks
+    pub async fn apply_create(&self, request: CreateAssetRequest) -> DomainResult<Asset> {
+        let mut tx = self.begin().await?;
+        let created_asset = self.apply_create_in(&mut tx, request).await?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
 
+        self.after_create(&created_asset).await;
+        Ok(created_asset)
+    }
+
+    async fn begin(&self) -> DomainResult<Transaction<'static, Postgres>> {
+        self.repository
+            .begin()
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    /// Insert an asset inside the caller's transaction (direct creation or an
+    /// approved request); call `after_create` once it commits
+    pub async fn apply_create_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        request: CreateAssetRequest,
+    ) -> DomainResult<Asset> {
         // Check if code already exists
         if let Some(_) = self
             .repository
@@ -206,6 +290,7 @@ impl AssetService {
         asset.department_id = request.department_id;
         asset.assigned_to = request.assigned_to;
         asset.vendor_id = request.vendor_id;
+        asset.organization_id = request.organization_id;
         asset.is_rental = request.is_rental.unwrap_or(false);
         asset.asset_class = request.asset_class;
         asset.condition_id = request.condition_id;
@@ -225,13 +310,23 @@ impl AssetService {
             asset.status = s;
         }
         asset.notes = request.notes;
-
-        let created_asset = self.repository.create(&asset).await.map_err(|e| {
-            DomainError::ExternalServiceError {
+        self.specification_service
+            .validate(asset.category_id, asset.specifications.as_ref())
+            .await?;
+
+        let event = EventEnvelope::from_event(AssetCreated {
+            asset_id: asset.id,
+            asset_code: asset.asset_code.clone(),
+            name: asset.name.clone(),
+            category_id: asset.category_id,
+            occurred_at: Utc::now(),
+        });
+        let created_asset = AssetRepository::create(tx, &asset, event)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
-            }
-        })?;
+            })?;
 
         // Handle Vehicle Details
         if let Some(vd) = request.vehicle_details {
@@ -254,8 +349,7 @@ impl AssetService {
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
             };
-            self.repository
-                .upsert_vehicle_details(&details)
+            AssetRepository::upsert_vehicle_details(&mut **tx, &details)
                 .await
                 .map_err(|e| DomainError::ExternalServiceError {
                     service: "database".to_string(),
@@ -263,7 +357,37 @@ impl AssetService {
                 })?;
         }
 
-        Ok(AssetOperationResult::Success(created_asset))
+        Ok(created_asset)
+    }
+
+    /// Depreciation schedule and purchase spend of a newly created asset
+    pub async fn after_create(&self, created_asset: &Asset) {
+        if created_asset.purchase_price.is_some() && created_asset.purchase_date.is_some() {
+            self.depreciation_service
+                .refresh_asset(created_asset.id)
+                .await;
+        }
+
+        // The purchase is actual spend of the owning cost center
+        if let Some(price) = created_asset.purchase_price {
+            let spend = BudgetSpend {
+                source_type: SPEND_ASSET_PURCHASE,
+                source_id: created_asset.id,
+                asset_id: created_asset.id,
+                amount: price,
+                date: created_asset
+                    .purchase_date
+                    .unwrap_or_else(|| Utc::now().date_naive()),
+                reference: created_asset.asset_code.clone(),
+            };
+            if let Err(e) = self.budget_service.record_actual(&spend, None).await {
+                tracing::warn!(
+                    "Failed to post purchase of asset {} to its budget: {}",
+                    created_asset.asset_code,
+                    e
+                );
+            }
+        }
     }
 
     /// Bulk create assets
@@ -287,9 +411,59 @@ impl AssetService {
         Ok(results)
     }
 
-    /// Update asset
-    pub async fn update(&self, id: Uuid, request: UpdateAssetRequest) -> DomainResult<Asset> {
+    /// Update an asset within the caller's data scope; category/specification
+    /// changes are recorded in the specification history
+    pub async fn update(
+        &self,
+        id: Uuid,
+        request: UpdateAssetRequest,
+        claims: &UserClaims,
+    ) -> DomainResult<Asset> {
+        self.ensure_in_scope(id, claims, "asset.update").await?;
+        let changed_by = Some(claims.user_id());
+        let depreciation_basis = Self::depreciation_basis(&self.get_by_id(id).await?);
+
+        let mut tx = self.begin().await?;
+        let result = self.update_in(&mut tx, id, request, changed_by).await?;
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+
+        if Self::depreciation_basis(&result) != depreciation_basis {
+            self.depreciation_service.refresh_asset(id).await;
+        }
+
+        // Invalidate cache
+        let _ = self.cache.delete(&CacheKey::asset(&id)).await;
+
+        Ok(result)
+    }
+
+    /// Recalculate the schedule of an asset updated by `update_in` and drop it
+    /// from the cache
+    pub async fn after_update(&self, id: Uuid) {
+        self.depreciation_service.refresh_asset(id).await;
+        let _ = self.cache.delete(&CacheKey::asset(&id)).await;
+    }
+
+    /// `update` inside the caller's transaction; call `after_update` once it commits
+    pub async fn update_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        request: UpdateAssetRequest,
+        changed_by: Option<Uuid>,
+    ) -> DomainResult<Asset> {
         let mut asset = self.get_by_id(id).await?;
+        let previous = asset.clone();
+        let financials = (
+            asset.purchase_price,
+            asset.residual_value,
+            asset.useful_life_months,
+        );
 
         // Update fields if provided
         if let Some(code) = request.asset_code {
@@ -368,12 +542,32 @@ impl AssetService {
             asset.notes = Some(n);
         }
 
-        let result = self.repository.update(&asset).await.map_err(|e| {
-            DomainError::ExternalServiceError {
-                service: "database".to_string(),
-                message: e.to_string(),
-            }
-        })?;
+        // Closed depreciation periods lock the figures they were posted from
+        if (
+            asset.purchase_price,
+            asset.residual_value,
+            asset.useful_life_months,
+        ) != financials
+        {
+            self.depreciation_service
+                .ensure_financials_unlocked(id)
+                .await?;
+        }
+
+        let result = if asset.category_id != previous.category_id
+            || asset.specifications != previous.specifications
+        {
+            self.specification_service
+                .save_change_in(tx, &previous, &asset, SPEC_CHANGE_MODIFICATION, changed_by)
+                .await?
+        } else {
+            AssetRepository::update_with(&mut **tx, &asset)
+                .await
+                .map_err(|e| DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                })?
+        };
 
         // Handle Vehicle Details
         if let Some(vd) = request.vehicle_details {
@@ -396,8 +590,7 @@ impl AssetService {
                 created_at: asset.created_at, // Preserve original creation? No, this struct is new or updated.
                 updated_at: Utc::now(),
             };
-            self.repository
-                .upsert_vehicle_details(&details)
+            AssetRepository::upsert_vehicle_details(&mut **tx, &details)
                 .await
                 .map_err(|e| DomainError::ExternalServiceError {
                     service: "database".to_string(),
@@ -405,12 +598,28 @@ impl AssetService {
                 })?;
         }
 
-        // Invalidate cache
-        let _ = self.cache.delete(&CacheKey::asset(&id)).await;
-
         Ok(result)
     }
 
+    /// Fields the depreciation schedule is computed from
+    fn depreciation_basis(
+        asset: &Asset,
+    ) -> (
+        Uuid,
+        Option<chrono::NaiveDate>,
+        Option<rust_decimal::Decimal>,
+        Option<rust_decimal::Decimal>,
+        Option<i32>,
+    ) {
+        (
+            asset.category_id,
+            asset.purchase_date,
+            asset.purchase_price,
+            asset.residual_value,
+            asset.useful_life_months,
+        )
+    }
+
     /// Change asset state
     pub async fn change_state(
         &self,
@@ -446,8 +655,9 @@ impl AssetService {
         self.get_by_id(id).await
     }
 
-    /// Delete asset
-    pub async fn delete(&self, id: Uuid) -> DomainResult<bool> {
+    /// Delete an asset within the caller's data scope
+    pub async fn delete(&self, id: Uuid, claims: &UserClaims) -> DomainResult<bool> {
+        self.ensure_in_scope(id, claims, "asset.delete").await?;
         let result =
             self.repository
                 .delete(id)
@@ -465,6 +675,25 @@ impl AssetService {
         Ok(result)
     }
 
+    /// Delete an asset inside the caller's transaction; call `after_delete`
+    /// once it commits
+    pub async fn delete_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+    ) -> DomainResult<bool> {
+        AssetRepository::delete_with(&mut **tx, id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    pub async fn after_delete(&self, id: Uuid) {
+        let _ = self.cache.delete(&CacheKey::asset(&id)).await;
+    }
+
     /// Get asset history
     pub async fn get_history(&self, id: Uuid) -> DomainResult<Vec<AssetHistory>> {
         self.repository
@@ -3,11 +3,72 @@
 use chrono::{Duration, Utc};
 use uuid::Uuid;
 
-use crate::domain::entities::{User, UserClaims, UserRole};
+use crate::domain::entities::{
+    normalize_recovery_code, provisioning_uri, LoginChallengeClaims, LoginLockoutPolicy,
+    PasswordPolicy, RefreshToken, RefreshTokenState, TwoFactorSetup, TwoFactorStatus, User,
+    UserClaims, UserRole, UserTwoFactor, CHALLENGE_PASSWORD_CHANGE, CHALLENGE_TWO_FACTOR,
+    LOGIN_CHALLENGE_MINUTES, RECOVERY_CODE_COUNT, REVOKED_BY_ADMIN, REVOKED_LOGOUT,
+    REVOKED_TOKEN_REUSE,
+};
 use crate::domain::errors::{DomainError, DomainResult};
-use crate::infrastructure::repositories::{EmployeeRepository, RbacRepository, UserRepository};
-use crate::shared::utils::crypto::{hash_password, verify_password};
-use crate::shared::utils::jwt::{create_token, JwtConfig};
+use crate::infrastructure::repositories::{
+    EmployeeRepository, OrganizationRepository, RbacRepository, SessionRepository,
+    TwoFactorRepository, UserRepository,
+};
+use crate::shared::utils::crypto::{
+    generate_recovery_code, generate_token, generate_totp_secret, hash_password, hash_token,
+    verify_password,
+};
+use crate::shared::utils::jwt::{create_token, decode_token, JwtConfig};
+
+/// Access token and the refresh token of the same session
+#[derive(Debug, Clone)]
+pub struct AuthTokens {
+    pub access_token: String,
+    pub refresh_token: String,
+    /// Access token lifetime in seconds
+    pub expires_in: i64,
+}
+
+/// Where a login comes from, for sessions and the audit log
+#[derive(Debug, Clone, Default)]
+pub struct ClientInfo {
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+/// Result of the password step of a login
+#[derive(Debug, Clone)]
+pub enum LoginOutcome {
+    Authenticated(User, AuthTokens),
+    /// A TOTP or recovery code must follow; `setup_required` when the user
+    /// must enrol first
+    TwoFactorRequired {
+        user: User,
+        challenge_token: String,
+        setup_required: bool,
+    },
+    /// An admin asked for a new password before anything else
+    PasswordChangeRequired {
+        user: User,
+        challenge_token: String,
+    },
+}
+
+/// Two-factor settings; which roles need 2FA is configured in the RBAC tables
+#[derive(Debug, Clone)]
+pub struct TwoFactorConfig {
+    /// Issuer shown in authenticator apps
+    pub issuer: String,
+}
+
+impl TwoFactorConfig {
+    pub fn from_env() -> Self {
+        Self {
+            issuer: std::env::var("TOTP_ISSUER").unwrap_or_else(|_| "Asset Manager".to_string()),
+        }
+    }
+}
 
 /// Auth service
 #[derive(Clone)]
@@ -15,7 +76,19 @@ pub struct AuthService {
     repository: UserRepository,
     rbac_repository: RbacRepository,
     employee_repository: EmployeeRepository,
+    organization_repository: OrganizationRepository,
+    session_repository: SessionRepository,
+    two_factor_repository: TwoFactorRepository,
     jwt_config: JwtConfig,
+    two_factor_config: TwoFactorConfig,
+    lockout_policy: LoginLockoutPolicy,
+}
+
+fn db_error(e: sqlx::Error) -> DomainError {
+    DomainError::ExternalServiceError {
+        service: "database".to_string(),
+        message: e.to_string(),
+    }
 }
 
 impl AuthService {
@@ -23,18 +96,33 @@ impl AuthService {
         repository: UserRepository,
         rbac_repository: RbacRepository,
         employee_repository: EmployeeRepository,
+        organization_repository: OrganizationRepository,
+        session_repository: SessionRepository,
+        two_factor_repository: TwoFactorRepository,
         jwt_config: JwtConfig,
     ) -> Self {
         Self {
             repository,
             rbac_repository,
             employee_repository,
+            organization_repository,
+            session_repository,
+            two_factor_repository,
             jwt_config,
+            two_factor_config: TwoFactorConfig::from_env(),
+            lockout_policy: LoginLockoutPolicy::default(),
         }
     }
 
-    /// Login user
-    pub async fn login(&self, email: &str, password: &str) -> DomainResult<(User, String)> {
+    /// Login user. Repeated failures delay further attempts and eventually lock
+    /// the account; users with (or required to have) 2FA get a challenge token
+    /// instead of a session.
+    pub async fn login(
+        &self,
+        email: &str,
+        password: &str,
+        client: &ClientInfo,
+    ) -> DomainResult<LoginOutcome> {
         let user = self
             .repository
             .find_by_email(email)
@@ -48,15 +136,673 @@ impl AuthService {
         if !user.is_active {
             return Err(DomainError::unauthorized("Account is disabled"));
         }
+        // Service accounts authenticate with API keys only
+        if user.is_service_account {
+            return Err(DomainError::unauthorized("Invalid credentials"));
+        }
+        self.check_lockout(&user).await?;
 
         // Verify password
         if !verify_password(password, &user.password_hash) {
-            return Err(DomainError::unauthorized("Invalid credentials"));
+            return Err(self
+                .failed_login(&user, client, "Invalid credentials")
+                .await);
+        }
+
+        if user.must_change_password {
+            let challenge_token = self.issue_challenge(&user, CHALLENGE_PASSWORD_CHANGE)?;
+            return Ok(LoginOutcome::PasswordChangeRequired {
+                user,
+                challenge_token,
+            });
+        }
+
+        self.continue_login(user, client).await
+    }
+
+    /// User of a password change challenge
+    pub async fn password_change_user(&self, challenge_token: &str) -> DomainResult<User> {
+        self.challenged_user(challenge_token, CHALLENGE_PASSWORD_CHANGE)
+            .await
+    }
+
+    /// Resume a login once the password asked for has been changed
+    pub async fn finish_password_change(
+        &self,
+        challenge_token: &str,
+        client: &ClientInfo,
+    ) -> DomainResult<LoginOutcome> {
+        let user = self.password_change_user(challenge_token).await?;
+        if user.must_change_password {
+            return Err(DomainError::business_rule(
+                "password_change_required",
+                "The password has not been changed yet",
+            ));
+        }
+        self.continue_login(user, client).await
+    }
+
+    /// What follows a verified password: a second factor or the session
+    async fn continue_login(&self, user: User, client: &ClientInfo) -> DomainResult<LoginOutcome> {
+        // The failure count is kept until the second factor is verified too
+        let enabled = self
+            .two_factor_repository
+            .find(user.id)
+            .await
+            .map_err(db_error)?
+            .is_some_and(|tf| tf.is_enabled());
+        if enabled || self.two_factor_required(&user).await? {
+            let challenge_token = self.issue_challenge(&user, CHALLENGE_TWO_FACTOR)?;
+            return Ok(LoginOutcome::TwoFactorRequired {
+                user,
+                challenge_token,
+                setup_required: !enabled,
+            });
+        }
+
+        let tokens = self.open_session(&user, client).await?;
+        Ok(LoginOutcome::Authenticated(user, tokens))
+    }
+
+    /// Second login step: a TOTP or recovery code for the challenged user. A
+    /// pending enrolment is completed by its first code, returning the new
+    /// recovery codes.
+    pub async fn verify_two_factor(
+        &self,
+        challenge_token: &str,
+        code: &str,
+        client: &ClientInfo,
+    ) -> DomainResult<(User, AuthTokens, Option<Vec<String>>)> {
+        let user = self
+            .challenged_user(challenge_token, CHALLENGE_TWO_FACTOR)
+            .await?;
+        self.check_lockout(&user).await?;
+
+        let two_factor = self
+            .two_factor_repository
+            .find(user.id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| {
+                DomainError::business_rule(
+                    "two_factor_setup_required",
+                    "Set up two-factor authentication first",
+                )
+            })?;
+
+        let recovery_codes = if two_factor.is_enabled() {
+            if !self.check_second_factor(&two_factor, code).await? {
+                return Err(self
+                    .failed_login(&user, client, "Invalid two-factor code")
+                    .await);
+            }
+            None
+        } else {
+            match two_factor.matching_step(code, Utc::now()) {
+                Some(step) => Some(self.enable(&two_factor, step).await?),
+                None => {
+                    return Err(self
+                        .failed_login(&user, client, "Invalid two-factor code")
+                        .await)
+                }
+            }
+        };
+
+        let tokens = self.open_session(&user, client).await?;
+        Ok((user, tokens, recovery_codes))
+    }
+
+    /// Refuse attempts while the account is locked or a delay is pending
+    async fn check_lockout(&self, user: &User) -> DomainResult<()> {
+        let attempts = self
+            .repository
+            .login_attempts(user.id)
+            .await
+            .map_err(db_error)?;
+        if let Some(wait) = self.lockout_policy.retry_after(&attempts, Utc::now()) {
+            let message = if self.lockout_policy.is_locked(&attempts, Utc::now()) {
+                "Account is locked after repeated failed logins"
+            } else {
+                "Too many failed logins, try again later"
+            };
+            return Err(DomainError::too_many_requests(message, wait as u64));
+        }
+        Ok(())
+    }
+
+    /// Count a failed password or code; the error to return to the client
+    async fn failed_login(&self, user: &User, client: &ClientInfo, reason: &str) -> DomainError {
+        let attempts = match self
+            .repository
+            .record_failed_login(
+                user.id,
+                &self.lockout_policy,
+                client.ip.as_deref(),
+                client.user_agent.as_deref(),
+            )
+            .await
+        {
+            Ok(attempts) => attempts,
+            Err(e) => return db_error(e),
+        };
+        if self
+            .lockout_policy
+            .locks_after(attempts.failed_login_attempts)
+        {
+            tracing::warn!(
+                "Account {} locked after {} failed logins",
+                user.id,
+                attempts.failed_login_attempts
+            );
+            return DomainError::too_many_requests(
+                "Account is locked after repeated failed logins",
+                (self.lockout_policy.lockout_minutes * 60) as u64,
+            );
+        }
+        DomainError::unauthorized(reason)
+    }
+
+    // ==================== TWO-FACTOR ====================
+
+    /// Whether 2FA is mandatory for the user's role
+    async fn two_factor_required(&self, user: &User) -> DomainResult<bool> {
+        match user.role_id {
+            Some(role_id) => self
+                .two_factor_repository
+                .role_requires_two_factor(role_id)
+                .await
+                .map_err(db_error),
+            _ => Ok(false),
+        }
+    }
+
+    fn issue_challenge(&self, user: &User, purpose: &str) -> DomainResult<String> {
+        let claims = LoginChallengeClaims {
+            sub: user.id.to_string(),
+            purpose: purpose.to_string(),
+            exp: (Utc::now() + Duration::minutes(LOGIN_CHALLENGE_MINUTES)).timestamp(),
+            iat: Utc::now().timestamp(),
+            jti: Uuid::new_v4().to_string(),
+        };
+        create_token(&claims, &self.jwt_config).map_err(|e| DomainError::ExternalServiceError {
+            service: "jwt".to_string(),
+            message: e,
+        })
+    }
+
+    async fn challenged_user(&self, challenge_token: &str, purpose: &str) -> DomainResult<User> {
+        let invalid = || DomainError::unauthorized("Invalid credentials");
+        let claims: LoginChallengeClaims =
+            decode_token(challenge_token, &self.jwt_config).map_err(|_| invalid())?;
+        if claims.purpose != purpose {
+            return Err(invalid());
+        }
+        let user_id = Uuid::parse_str(&claims.sub).map_err(|_| invalid())?;
+
+        let user = self
+            .repository
+            .find_by_id(user_id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(invalid)?;
+        if !user.is_active {
+            return Err(DomainError::unauthorized("Account is disabled"));
+        }
+        Ok(user)
+    }
+
+    /// A current TOTP code (each accepted once) or an unused recovery code
+    async fn check_second_factor(
+        &self,
+        two_factor: &UserTwoFactor,
+        code: &str,
+    ) -> DomainResult<bool> {
+        if let Some(step) = two_factor.matching_step(code, Utc::now()) {
+            return self
+                .two_factor_repository
+                .consume_step(two_factor.user_id, step)
+                .await
+                .map_err(db_error);
         }
+        self.two_factor_repository
+            .use_recovery_code(
+                two_factor.user_id,
+                &hash_token(&normalize_recovery_code(code)),
+            )
+            .await
+            .map_err(db_error)
+    }
+
+    /// Complete a pending enrolment; returns the recovery codes, shown once
+    async fn enable(&self, two_factor: &UserTwoFactor, step: i64) -> DomainResult<Vec<String>> {
+        let codes = Self::recovery_codes();
+        let hashes: Vec<String> = codes
+            .iter()
+            .map(|c| hash_token(&normalize_recovery_code(c)))
+            .collect();
+        let enabled = self
+            .two_factor_repository
+            .enable(two_factor.user_id, step, &hashes)
+            .await
+            .map_err(db_error)?;
+        if !enabled {
+            return Err(DomainError::conflict(
+                "Two-factor authentication is already enabled",
+            ));
+        }
+        Ok(codes)
+    }
+
+    fn recovery_codes() -> Vec<String> {
+        (0..RECOVERY_CODE_COUNT)
+            .map(|_| generate_recovery_code())
+            .collect()
+    }
+
+    async fn start_setup(&self, user: &User) -> DomainResult<TwoFactorSetup> {
+        let secret = generate_totp_secret();
+        self.two_factor_repository
+            .save_pending(user.id, &secret)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| DomainError::conflict("Two-factor authentication is already enabled"))?;
 
+        Ok(TwoFactorSetup {
+            otpauth_uri: provisioning_uri(&self.two_factor_config.issuer, &user.email, &secret),
+            secret,
+        })
+    }
+
+    async fn find_user(&self, user_id: Uuid) -> DomainResult<User> {
+        self.repository
+            .find_by_id(user_id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| DomainError::not_found("User", user_id))
+    }
+
+    async fn enabled_two_factor(&self, user_id: Uuid) -> DomainResult<UserTwoFactor> {
+        self.two_factor_repository
+            .find(user_id)
+            .await
+            .map_err(db_error)?
+            .filter(|tf| tf.is_enabled())
+            .ok_or_else(|| {
+                DomainError::business_rule(
+                    "two_factor_not_enabled",
+                    "Two-factor authentication is not enabled",
+                )
+            })
+    }
+
+    /// Enrolment during login, for users whose role requires 2FA
+    pub async fn setup_two_factor_challenge(
+        &self,
+        challenge_token: &str,
+    ) -> DomainResult<TwoFactorSetup> {
+        let user = self
+            .challenged_user(challenge_token, CHALLENGE_TWO_FACTOR)
+            .await?;
+        self.start_setup(&user).await
+    }
+
+    pub async fn two_factor_status(&self, user_id: Uuid) -> DomainResult<TwoFactorStatus> {
+        let user = self.find_user(user_id).await?;
+        let enabled = self
+            .two_factor_repository
+            .find(user_id)
+            .await
+            .map_err(db_error)?
+            .is_some_and(|tf| tf.is_enabled());
+        let recovery_codes_left = self
+            .two_factor_repository
+            .recovery_codes_left(user_id)
+            .await
+            .map_err(db_error)?;
+
+        Ok(TwoFactorStatus {
+            enabled,
+            required: self.two_factor_required(&user).await?,
+            recovery_codes_left,
+        })
+    }
+
+    /// Start optional enrolment of a signed-in user
+    pub async fn setup_two_factor(&self, user_id: Uuid) -> DomainResult<TwoFactorSetup> {
+        let user = self.find_user(user_id).await?;
+        self.start_setup(&user).await
+    }
+
+    /// Confirm enrolment with the first code; returns the recovery codes
+    pub async fn enable_two_factor(&self, user_id: Uuid, code: &str) -> DomainResult<Vec<String>> {
+        let two_factor = self
+            .two_factor_repository
+            .find(user_id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| {
+                DomainError::business_rule(
+                    "two_factor_setup_required",
+                    "Set up two-factor authentication first",
+                )
+            })?;
+        let step = two_factor
+            .matching_step(code, Utc::now())
+            .ok_or_else(|| DomainError::validation("code", "Invalid two-factor code"))?;
+        self.enable(&two_factor, step).await
+    }
+
+    /// Turn 2FA off, unless the user's role requires it
+    pub async fn disable_two_factor(&self, user_id: Uuid, code: &str) -> DomainResult<()> {
+        let user = self.find_user(user_id).await?;
+        if self.two_factor_required(&user).await? {
+            return Err(DomainError::business_rule(
+                "two_factor_required",
+                "Two-factor authentication is mandatory for your role",
+            ));
+        }
+        let two_factor = self.enabled_two_factor(user_id).await?;
+        if !self.check_second_factor(&two_factor, code).await? {
+            return Err(DomainError::validation("code", "Invalid two-factor code"));
+        }
+
+        self.two_factor_repository
+            .delete(user_id)
+            .await
+            .map_err(db_error)?;
+        Ok(())
+    }
+
+    /// Replace the recovery codes; needs a current code
+    pub async fn regenerate_recovery_codes(
+        &self,
+        user_id: Uuid,
+        code: &str,
+    ) -> DomainResult<Vec<String>> {
+        let two_factor = self.enabled_two_factor(user_id).await?;
+        if !self.check_second_factor(&two_factor, code).await? {
+            return Err(DomainError::validation("code", "Invalid two-factor code"));
+        }
+
+        let codes = Self::recovery_codes();
+        let hashes: Vec<String> = codes
+            .iter()
+            .map(|c| hash_token(&normalize_recovery_code(c)))
+            .collect();
+        self.two_factor_repository
+            .replace_recovery_codes(user_id, &hashes)
+            .await
+            .map_err(db_error)?;
+        Ok(codes)
+    }
+
+    /// Drop a user's enrolment (admin action, e.g. a lost device); they enrol
+    /// again at their next login when their role requires 2FA
+    pub async fn reset_two_factor(&self, user_id: Uuid) -> DomainResult<()> {
+        if !self
+            .two_factor_repository
+            .delete(user_id)
+            .await
+            .map_err(db_error)?
+        {
+            return Err(DomainError::not_found("Two-factor enrolment", user_id));
+        }
+        Ok(())
+    }
+
+    /// Open a session with the home organization active
+    async fn open_session(&self, user: &User, client: &ClientInfo) -> DomainResult<AuthTokens> {
         // Update last login
+        self.repository
+            .reset_failed_logins(user.id)
+            .await
+            .map_err(db_error)?;
         let _ = self.repository.update_last_login(user.id).await;
 
+        let refresh_token = generate_token();
+        let token = RefreshToken::new(
+            Uuid::new_v4(),
+            hash_token(&refresh_token),
+            Duration::days(self.jwt_config.refresh_expiry_days),
+        );
+        let session = self
+            .session_repository
+            .create(
+                user.id,
+                user.organization_id,
+                client.user_agent.as_deref(),
+                &token,
+            )
+            .await
+            .map_err(db_error)?;
+
+        let access_token = self
+            .issue_token(user, session.organization_id, session.id)
+            .await?;
+
+        Ok(self.tokens(access_token, refresh_token))
+    }
+
+    /// Exchange a refresh token for a new access/refresh pair. A token is
+    /// accepted once; presenting it again revokes the whole session.
+    pub async fn refresh(&self, refresh_token: &str) -> DomainResult<(User, AuthTokens)> {
+        let invalid = || DomainError::unauthorized("Invalid refresh token");
+
+        let token = self
+            .session_repository
+            .find_refresh_token(&hash_token(refresh_token))
+            .await
+            .map_err(db_error)?
+            .ok_or_else(invalid)?;
+        let session = self
+            .session_repository
+            .find_session(token.session_id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(invalid)?;
+
+        match token.state(&session, Utc::now()) {
+            RefreshTokenState::Valid => {}
+            RefreshTokenState::Reused => {
+                tracing::warn!("Refresh token reuse on session {}, revoking it", session.id);
+                self.session_repository
+                    .revoke(session.id, REVOKED_TOKEN_REUSE)
+                    .await
+                    .map_err(db_error)?;
+                return Err(invalid());
+            }
+            RefreshTokenState::Expired | RefreshTokenState::Revoked => return Err(invalid()),
+        }
+
+        // Password, role or activation changes since login end the session
+        if !self
+            .session_repository
+            .is_active(session.id, session.user_id)
+            .await
+            .map_err(db_error)?
+        {
+            return Err(invalid());
+        }
+        let user = self
+            .repository
+            .find_by_id(session.user_id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(invalid)?;
+
+        let refresh_token = generate_token();
+        let next = RefreshToken::new(
+            session.id,
+            hash_token(&refresh_token),
+            Duration::days(self.jwt_config.refresh_expiry_days),
+        );
+        if !self
+            .session_repository
+            .rotate(token.id, &next)
+            .await
+            .map_err(db_error)?
+        {
+            // Lost a race against another exchange of the same token
+            self.session_repository
+                .revoke(session.id, REVOKED_TOKEN_REUSE)
+                .await
+                .map_err(db_error)?;
+            return Err(invalid());
+        }
+
+        let access_token = self
+            .issue_token(&user, session.organization_id, session.id)
+            .await?;
+        Ok((user, self.tokens(access_token, refresh_token)))
+    }
+
+    /// Revoke the session of the token, or every session of the user
+    pub async fn logout(&self, claims: &UserClaims, all_sessions: bool) -> DomainResult<()> {
+        if all_sessions {
+            self.session_repository
+                .revoke_all(claims.user_id(), REVOKED_LOGOUT)
+                .await
+                .map_err(db_error)?;
+        } else if let Some(session_id) = claims.session_id() {
+            self.session_repository
+                .revoke(session_id, REVOKED_LOGOUT)
+                .await
+                .map_err(db_error)?;
+        }
+        Ok(())
+    }
+
+    /// Revoke every session of a user (admin action); returns the number revoked
+    pub async fn revoke_sessions(&self, user_id: Uuid) -> DomainResult<u64> {
+        self.session_repository
+            .revoke_all(user_id, REVOKED_BY_ADMIN)
+            .await
+            .map_err(db_error)
+    }
+
+    /// Lift a login lockout (admin action)
+    pub async fn unlock_account(&self, user_id: Uuid, unlocked_by: Uuid) -> DomainResult<()> {
+        if !self
+            .repository
+            .unlock(user_id, unlocked_by)
+            .await
+            .map_err(db_error)?
+        {
+            return Err(DomainError::not_found("User", user_id));
+        }
+        Ok(())
+    }
+
+    /// Claims of an access token whose session is still live; tokens of
+    /// revoked or outdated sessions are rejected before their expiry
+    pub async fn authenticate(&self, token: &str) -> DomainResult<UserClaims> {
+        let claims: UserClaims = decode_token(token, &self.jwt_config)
+            .map_err(|_| DomainError::unauthorized("Invalid credentials"))?;
+        let session_id = claims
+            .session_id()
+            .ok_or_else(|| DomainError::unauthorized("Invalid credentials"))?;
+
+        let active = self
+            .session_repository
+            .is_active(session_id, claims.user_id())
+            .await
+            .map_err(db_error)?;
+        if !active {
+            return Err(DomainError::unauthorized("Invalid credentials"));
+        }
+        Ok(claims)
+    }
+
+    /// Whether the login session behind `claims` is still live, for
+    /// connections that outlast their access token
+    pub async fn session_is_active(&self, claims: &UserClaims) -> DomainResult<bool> {
+        let Some(session_id) = claims.session_id() else {
+            return Ok(false);
+        };
+        self.session_repository
+            .is_active(session_id, claims.user_id())
+            .await
+            .map_err(db_error)
+    }
+
+    fn tokens(&self, access_token: String, refresh_token: String) -> AuthTokens {
+        AuthTokens {
+            access_token,
+            refresh_token,
+            expires_in: self.jwt_config.access_expiry_minutes * 60,
+        }
+    }
+
+    /// Re-issue the token of a user with another active organization: one they
+    /// are a member of or below one; super admins may pick any organization
+    pub async fn switch_organization(
+        &self,
+        claims: &UserClaims,
+        organization_id: Uuid,
+    ) -> DomainResult<(User, String)> {
+        let session_id = claims
+            .session_id()
+            .ok_or_else(|| DomainError::unauthorized("Invalid credentials"))?;
+        let user = self
+            .repository
+            .find_by_id(claims.user_id())
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::unauthorized("Invalid credentials"))?;
+        if !user.is_active {
+            return Err(DomainError::unauthorized("Account is disabled"));
+        }
+
+        let organization = self
+            .organization_repository
+            .find_by_id(organization_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("Organization", organization_id))?;
+        if !organization.is_active {
+            return Err(DomainError::business_rule(
+                "organization_inactive",
+                "Organization is inactive",
+            ));
+        }
+
+        let allowed = user.role == UserRole::SuperAdmin.as_str()
+            || self
+                .organization_repository
+                .user_can_access(user.id, organization_id)
+                .await
+                .map_err(|e| DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                })?;
+        if !allowed {
+            return Err(DomainError::unauthorized("switch to this organization"));
+        }
+
+        // Later refreshes keep the organization
+        self.session_repository
+            .set_organization(session_id, Some(organization_id))
+            .await
+            .map_err(db_error)?;
+        let token = self
+            .issue_token(&user, Some(organization_id), session_id)
+            .await?;
+        Ok((user, token))
+    }
+
+    /// Build and sign the claims of a user with the given active organization
+    async fn issue_token(
+        &self,
+        user: &User,
+        organization_id: Option<Uuid>,
+        session_id: Uuid,
+    ) -> DomainResult<String> {
         // Fetch permissions from DB
         let permissions = if let Some(role_id) = user.role_id {
             self.rbac_repository
@@ -89,22 +835,21 @@ impl AuthService {
             role: user.role.clone(),
             role_level: user.role_level,
             department: user.department.clone(),
-            org: user.organization_id.map(|id| id.to_string()),
+            org: organization_id.map(|id| id.to_string()),
             employee_id,
             permissions,
-            exp: (Utc::now() + Duration::hours(24)).timestamp(),
+            exp: (Utc::now() + Duration::minutes(self.jwt_config.access_expiry_minutes))
+                .timestamp(),
             iat: Utc::now().timestamp(),
             jti: Uuid::new_v4().to_string(),
+            sid: Some(session_id),
+            api_key_id: None,
         };
 
-        let token = create_token(&claims, &self.jwt_config).map_err(|e| {
-            DomainError::ExternalServiceError {
-                service: "jwt".to_string(),
-                message: e,
-            }
-        })?;
-
-        Ok((user, token))
+        create_token(&claims, &self.jwt_config).map_err(|e| DomainError::ExternalServiceError {
+            service: "jwt".to_string(),
+            message: e,
+        })
     }
 
     /// Register new user
@@ -119,6 +864,7 @@ impl AuthService {
             return Err(DomainError::conflict("Email already registered"));
         }
 
+        PasswordPolicy::from_env().validate_for(password, email, name)?;
         let password_hash =
             hash_password(password).map_err(|e| DomainError::ExternalServiceError {
                 service: "password_hash".to_string(),
@@ -11,21 +11,29 @@ use crate::application::dto::{
     ApproveBillingRequest, BillingSummaryResponse, CalculateBillingRequest,
     CreateBillingPeriodRequest, GenerateInvoiceRequest,
 };
-use crate::domain::entities::RentalBillingPeriod;
+use crate::application::services::DocumentNumberService;
+use crate::domain::entities::{NumberingScope, RentalBillingPeriod, DOC_INVOICE};
 use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::events::{BillingApproved, EventEnvelope};
 use crate::infrastructure::repositories::{RentalRepository, TimesheetRepository};
 
 #[derive(Clone)]
 pub struct BillingService {
     timesheet_repo: TimesheetRepository,
     rental_repo: RentalRepository,
+    document_numbers: DocumentNumberService,
 }
 
 impl BillingService {
-    pub fn new(timesheet_repo: TimesheetRepository, rental_repo: RentalRepository) -> Self {
+    pub fn new(
+        timesheet_repo: TimesheetRepository,
+        rental_repo: RentalRepository,
+        document_numbers: DocumentNumberService,
+    ) -> Self {
         Self {
             timesheet_repo,
             rental_repo,
+            document_numbers,
         }
     }
 
@@ -198,8 +206,16 @@ impl BillingService {
             ));
         }
 
+        let event = EventEnvelope::from_event(BillingApproved {
+            billing_id,
+            rental_id: billing.rental_id,
+            total_amount: billing.total_amount,
+            approved_by,
+            occurred_at: Utc::now(),
+        })
+        .with_user(approved_by);
         self.timesheet_repo
-            .approve_billing(billing_id, approved_by, request.notes)
+            .approve_billing(billing_id, approved_by, request.notes, event)
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
@@ -225,11 +241,19 @@ impl BillingService {
             ));
         }
 
-        // Generate invoice number
-        let invoice_number = request.invoice_number.unwrap_or_else(|| {
-            let now = Utc::now();
-            format!("INV-{}", now.format("%Y%m%d%H%M%S"))
-        });
+        // Invoice numbers are gap-free: drawn in the transaction that stores them
+        let scope = match self
+            .rental_repo
+            .find_by_id(billing.rental_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })? {
+            Some(rental) => NumberingScope::asset(rental.asset_id),
+            None => NumberingScope::default(),
+        };
+        let series = self.document_numbers.series(DOC_INVOICE, scope).await?;
 
         // Calculate due date
         let due_days = request.due_days.unwrap_or(30);
@@ -237,14 +261,29 @@ impl BillingService {
         let due_date = today + chrono::Duration::days(due_days as i64);
 
         self.timesheet_repo
-            .generate_invoice(billing_id, &invoice_number, due_date)
+            .generate_invoice(
+                billing_id,
+                request.invoice_number.as_deref(),
+                &series,
+                due_date,
+            )
             .await
-            .map_err(|e| DomainError::ExternalServiceError {
-                service: "database".to_string(),
-                message: e.to_string(),
-            })?;
-
-        Ok(invoice_number)
+            .map_err(|e| match e {
+                sqlx::Error::Database(ref db) if db.code().as_deref() == Some("23505") => {
+                    DomainError::conflict("Invoice number already exists")
+                }
+                _ => DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                },
+            })?
+            // Another request invoiced the period first
+            .ok_or_else(|| {
+                DomainError::business_rule(
+                    "billing_status",
+                    "Only approved billing can generate invoice",
+                )
+            })
     }
 
     /// Get billing by ID
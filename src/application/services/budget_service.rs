@@ -0,0 +1,454 @@
+//! Budget Service
+//!
+//! Budget lines per cost center and fiscal period, and the spend booked
+//! against them. Work orders and conversions commit their estimated cost when
+//! approved; spend above the line is refused (`block`) or escalated as a
+//! `budget` OVERRUN approval request (`escalate`). Completion or execution
+//! posts the actual spend and releases the commitment.
+
+use chrono::{Datelike, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use serde_json::json;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::application::dto::{BudgetReport, CreateBudgetLineRequest, UpdateBudgetLineRequest};
+use crate::application::services::{ApprovalService, ApprovalSubmission, OrganizationService};
+use crate::domain::entities::{
+    BudgetEntry, BudgetLine, BudgetPosition, BUDGET_OVERRUN_ACTION, BUDGET_RESOURCE_TYPE,
+    ENTRY_ACTUAL, ENTRY_COMMITMENT, OVER_BUDGET_BLOCK,
+};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::repositories::approval_repository::ApprovalRequest;
+use crate::infrastructure::repositories::BudgetRepository;
+
+/// Spend of a work order, conversion or purchase on an asset
+#[derive(Debug, Clone)]
+pub struct BudgetSpend {
+    pub source_type: &'static str,
+    pub source_id: Uuid,
+    pub asset_id: Uuid,
+    pub amount: Decimal,
+    pub date: NaiveDate,
+    pub reference: String, // work order number, conversion number, asset code
+}
+
+/// Outcome of committing spend against the asset's budget
+#[derive(Debug, Clone)]
+pub enum BudgetCommitment {
+    Committed(Box<BudgetEntry>),
+    /// No budget line covers the asset, or there is nothing to commit
+    NotBudgeted,
+    /// Over budget; an OVERRUN approval request was opened
+    Escalated(Box<ApprovalRequest>),
+}
+
+/// Result of an approval that may be held up by a budget overrun
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum BudgetedResult<T> {
+    Applied(T),
+    PendingApproval(Box<ApprovalRequest>),
+}
+
+#[derive(Clone)]
+pub struct BudgetService {
+    repository: BudgetRepository,
+    organization_service: OrganizationService,
+    approval_service: ApprovalService,
+}
+
+impl BudgetService {
+    pub fn new(
+        repository: BudgetRepository,
+        organization_service: OrganizationService,
+        approval_service: ApprovalService,
+    ) -> Self {
+        Self {
+            repository,
+            organization_service,
+            approval_service,
+        }
+    }
+
+    // ==================== BUDGET LINES ====================
+
+    pub async fn list(
+        &self,
+        scope: Option<Uuid>,
+        fiscal_year: Option<i32>,
+    ) -> DomainResult<Vec<BudgetLine>> {
+        self.repository
+            .list(scope, fiscal_year)
+            .await
+            .map_err(db_error)
+    }
+
+    /// Budget line by id; lines of organizations outside the scope are not found
+    pub async fn get(&self, id: Uuid, scope: Option<Uuid>) -> DomainResult<BudgetLine> {
+        let line = self
+            .repository
+            .find_by_id(id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| DomainError::not_found("BudgetLine", id))?;
+        self.organization_service
+            .get(line.organization_id, scope)
+            .await
+            .map_err(|_| DomainError::not_found("BudgetLine", id))?;
+        Ok(line)
+    }
+
+    pub async fn create(
+        &self,
+        request: CreateBudgetLineRequest,
+        scope: Option<Uuid>,
+        created_by: Uuid,
+    ) -> DomainResult<BudgetLine> {
+        let organization_id = request
+            .organization_id
+            .or(scope)
+            .ok_or_else(|| DomainError::validation("organization_id", "Required"))?;
+
+        let line = BudgetLine {
+            id: Uuid::new_v4(),
+            organization_id,
+            department_id: request.department_id,
+            name: request.name,
+            fiscal_year: request
+                .fiscal_year
+                .unwrap_or_else(|| request.period_start.year()),
+            period_start: request.period_start,
+            period_end: request.period_end,
+            amount: request.amount,
+            over_budget_action: request
+                .over_budget_action
+                .unwrap_or_else(|| OVER_BUDGET_BLOCK.to_string()),
+            notes: request.notes,
+            created_by: Some(created_by),
+            created_at: Some(Utc::now()),
+            updated_at: Some(Utc::now()),
+        };
+        self.check_line(&line, scope).await?;
+
+        self.repository.create(&line).await.map_err(db_error)
+    }
+
+    pub async fn update(
+        &self,
+        id: Uuid,
+        request: UpdateBudgetLineRequest,
+        scope: Option<Uuid>,
+    ) -> DomainResult<BudgetLine> {
+        let mut line = self.get(id, scope).await?;
+
+        if let Some(department_id) = request.department_id {
+            line.department_id = Some(department_id);
+        }
+        if let Some(name) = request.name {
+            line.name = name;
+        }
+        if let Some(fiscal_year) = request.fiscal_year {
+            line.fiscal_year = fiscal_year;
+        }
+        if let Some(period_start) = request.period_start {
+            line.period_start = period_start;
+        }
+        if let Some(period_end) = request.period_end {
+            line.period_end = period_end;
+        }
+        if let Some(amount) = request.amount {
+            line.amount = amount;
+        }
+        if let Some(action) = request.over_budget_action {
+            line.over_budget_action = action;
+        }
+        if request.notes.is_some() {
+            line.notes = request.notes;
+        }
+        self.check_line(&line, scope).await?;
+
+        self.repository.update(&line).await.map_err(db_error)
+    }
+
+    pub async fn delete(&self, id: Uuid, scope: Option<Uuid>) -> DomainResult<()> {
+        self.get(id, scope).await?;
+        let deleted = self.repository.delete_unused(id).await.map_err(db_error)?;
+        if !deleted {
+            return Err(DomainError::conflict(
+                "Budget line already has commitments or actual spend",
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn entries(&self, id: Uuid, scope: Option<Uuid>) -> DomainResult<Vec<BudgetEntry>> {
+        self.get(id, scope).await?;
+        self.repository.entries(id).await.map_err(db_error)
+    }
+
+    /// Budget vs. committed and actual spend of every line in the scope
+    pub async fn report(
+        &self,
+        scope: Option<Uuid>,
+        fiscal_year: Option<i32>,
+    ) -> DomainResult<BudgetReport> {
+        let lines = self
+            .repository
+            .report(scope, fiscal_year)
+            .await
+            .map_err(db_error)?;
+        Ok(BudgetReport::new(fiscal_year, lines))
+    }
+
+    /// Validate a line: visible cost center, department of that organization,
+    /// no overlapping period and within the organization's annual budget
+    async fn check_line(&self, line: &BudgetLine, scope: Option<Uuid>) -> DomainResult<()> {
+        line.validate()?;
+
+        let org = self
+            .organization_service
+            .get(line.organization_id, scope)
+            .await?;
+        if org.cost_center.as_deref().unwrap_or("").trim().is_empty() {
+            return Err(DomainError::business_rule(
+                "budget_cost_center",
+                &format!("Organization {} has no cost center", org.code),
+            ));
+        }
+        if let Some(department_id) = line.department_id {
+            let belongs = self
+                .repository
+                .department_in_organization(department_id, org.id)
+                .await
+                .map_err(db_error)?;
+            if !belongs {
+                return Err(DomainError::validation(
+                    "department_id",
+                    "Department does not belong to the organization",
+                ));
+            }
+        }
+
+        if self.repository.has_overlap(line).await.map_err(db_error)? {
+            return Err(DomainError::conflict(
+                "Another budget line of this cost center overlaps the period",
+            ));
+        }
+
+        if let Some(budget) = org.budget {
+            let allocated = self
+                .repository
+                .allocated(org.id, line.fiscal_year, line.id)
+                .await
+                .map_err(db_error)?;
+            if allocated + line.amount > budget {
+                return Err(DomainError::business_rule(
+                    "budget_allocation",
+                    &format!(
+                        "Budget lines of {} for {} would total {} of its {} budget",
+                        org.code,
+                        line.fiscal_year,
+                        allocated + line.amount,
+                        budget
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // ==================== SPEND ====================
+
+    /// Commit spend against the budget line of the asset. Over budget, a
+    /// `block` line refuses it and an `escalate` line opens an OVERRUN request.
+    pub async fn commit(
+        &self,
+        spend: &BudgetSpend,
+        requested_by: Uuid,
+    ) -> DomainResult<BudgetCommitment> {
+        let mut tx = self.repository.begin().await.map_err(db_error)?;
+        let commitment = self.commit_in(&mut tx, spend, requested_by).await?;
+        tx.commit().await.map_err(db_error)?;
+
+        if let BudgetCommitment::Escalated(request) = &commitment {
+            self.approval_service.announce(request).await;
+        }
+        Ok(commitment)
+    }
+
+    /// `commit` inside `tx`; an OVERRUN request it opens is announced by the caller
+    pub async fn commit_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        spend: &BudgetSpend,
+        requested_by: Uuid,
+    ) -> DomainResult<BudgetCommitment> {
+        let line = match self.line_for(spend).await? {
+            Some(line) => line,
+            None => return Ok(BudgetCommitment::NotBudgeted),
+        };
+
+        let entry = Self::entry(&line, spend, ENTRY_COMMITMENT, Some(requested_by));
+        if let Some(entry) = BudgetRepository::commit(tx, &entry, false)
+            .await
+            .map_err(db_error)?
+        {
+            return Ok(BudgetCommitment::Committed(Box::new(entry)));
+        }
+
+        let position = self.position(line.id).await?;
+        if !line.escalates() {
+            return Err(DomainError::business_rule(
+                "budget_exceeded",
+                &format!(
+                    "{} of {} exceeds the {} available on budget line {}",
+                    spend.amount, spend.reference, position.available, line.name
+                ),
+            ));
+        }
+
+        let open = self
+            .approval_service
+            .open_resource_ids(BUDGET_RESOURCE_TYPE)
+            .await?;
+        if open.contains(&spend.source_id) {
+            return Err(DomainError::conflict(&format!(
+                "Budget overrun of {} is already awaiting approval",
+                spend.reference
+            )));
+        }
+
+        let snapshot = json!({
+            "source_type": spend.source_type,
+            "source_id": spend.source_id,
+            "asset_id": spend.asset_id,
+            "reference": spend.reference,
+            "amount": spend.amount,
+            "budget_line_id": line.id,
+            "budget_line": line.name,
+            "available": position.available,
+        });
+        match self
+            .approval_service
+            .create_request_in(
+                tx,
+                BUDGET_RESOURCE_TYPE,
+                spend.source_id,
+                BUDGET_OVERRUN_ACTION,
+                requested_by,
+                Some(snapshot),
+            )
+            .await?
+        {
+            ApprovalSubmission::Pending(request) => Ok(BudgetCommitment::Escalated(request)),
+            ApprovalSubmission::NotRequired => {
+                self.commit_overrun(tx, spend, requested_by, None).await
+            }
+        }
+    }
+
+    /// Commit spend regardless of the available budget (an approved overrun)
+    pub async fn commit_overrun(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        spend: &BudgetSpend,
+        approved_by: Uuid,
+        approval_request_id: Option<Uuid>,
+    ) -> DomainResult<BudgetCommitment> {
+        let line = match self.line_for(spend).await? {
+            Some(line) => line,
+            None => return Ok(BudgetCommitment::NotBudgeted),
+        };
+
+        let mut entry = Self::entry(&line, spend, ENTRY_COMMITMENT, Some(approved_by));
+        entry.approval_request_id = approval_request_id;
+        match BudgetRepository::commit(tx, &entry, true)
+            .await
+            .map_err(db_error)?
+        {
+            Some(entry) => Ok(BudgetCommitment::Committed(Box::new(entry))),
+            None => Ok(BudgetCommitment::NotBudgeted),
+        }
+    }
+
+    /// Post actual spend to the line covering its date and release the
+    /// source's commitment. Actuals are never refused.
+    pub async fn record_actual(
+        &self,
+        spend: &BudgetSpend,
+        recorded_by: Option<Uuid>,
+    ) -> DomainResult<Option<BudgetEntry>> {
+        let line = match self.line_for(spend).await? {
+            Some(line) => line,
+            None => {
+                self.release(spend.source_type, spend.source_id).await?;
+                return Ok(None);
+            }
+        };
+
+        let entry = Self::entry(&line, spend, ENTRY_ACTUAL, recorded_by);
+        self.repository
+            .post_actual(&entry)
+            .await
+            .map(Some)
+            .map_err(db_error)
+    }
+
+    /// Release the open commitment of spend that will not happen
+    pub async fn release(&self, source_type: &str, source_id: Uuid) -> DomainResult<()> {
+        self.repository
+            .release(source_type, source_id)
+            .await
+            .map(|_| ())
+            .map_err(db_error)
+    }
+
+    async fn line_for(&self, spend: &BudgetSpend) -> DomainResult<Option<BudgetLine>> {
+        if spend.amount <= Decimal::ZERO {
+            return Ok(None);
+        }
+        self.repository
+            .find_line_for_asset(spend.asset_id, spend.date)
+            .await
+            .map_err(db_error)
+    }
+
+    async fn position(&self, line_id: Uuid) -> DomainResult<BudgetPosition> {
+        self.repository
+            .position(line_id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| DomainError::not_found("BudgetLine", line_id))
+    }
+
+    fn entry(
+        line: &BudgetLine,
+        spend: &BudgetSpend,
+        entry_type: &str,
+        created_by: Option<Uuid>,
+    ) -> BudgetEntry {
+        BudgetEntry {
+            id: Uuid::new_v4(),
+            budget_line_id: line.id,
+            source_type: spend.source_type.to_string(),
+            source_id: spend.source_id,
+            asset_id: Some(spend.asset_id),
+            entry_type: entry_type.to_string(),
+            amount: spend.amount,
+            entry_date: spend.date,
+            released_at: None,
+            approval_request_id: None,
+            created_by,
+            created_at: Some(Utc::now()),
+        }
+    }
+}
+
+fn db_error(e: sqlx::Error) -> DomainError {
+    DomainError::ExternalServiceError {
+        service: "database".to_string(),
+        message: e.to_string(),
+    }
+}
@@ -2,25 +2,41 @@
 //!
 //! Handles category operations including hierarchical tree structure.
 
+use rust_decimal::Decimal;
+use serde_json::Value as JsonValue;
 use uuid::Uuid;
 
 use crate::application::dto::{
     CategoryClassification, CategoryResponse, CategoryTreeNode, CreateCategoryRequest,
     SubCategoryItem, UpdateCategoryRequest,
 };
-use crate::domain::entities::Category;
+use crate::application::services::DepreciationService;
+use crate::domain::entities::{Category, DepreciationMethod, SpecificationSchema};
 use crate::domain::errors::{DomainError, DomainResult};
 use crate::infrastructure::repositories::CategoryRepository;
 
+/// Method, period, residual rate, declining rate, total hours
+type DepreciationSettings = (
+    Option<String>,
+    Option<i32>,
+    Option<Decimal>,
+    Option<Decimal>,
+    Option<Decimal>,
+);
+
 /// Category service for managing asset categories
 #[derive(Clone)]
 pub struct CategoryService {
     repository: CategoryRepository,
+    depreciation_service: DepreciationService,
 }
 
 impl CategoryService {
-    pub fn new(repository: CategoryRepository) -> Self {
-        Self { repository }
+    pub fn new(repository: CategoryRepository, depreciation_service: DepreciationService) -> Self {
+        Self {
+            repository,
+            depreciation_service,
+        }
     }
 
     /// Get all categories as a flat list
@@ -157,6 +173,18 @@ impl CategoryService {
         if let Some(assets) = request.example_assets {
             category.example_assets = Some(serde_json::to_value(assets).unwrap_or_default());
         }
+        if let Some(method) = request.depreciation_method {
+            category.depreciation_method = Some(method);
+        }
+        category.depreciation_period = request.depreciation_period_months;
+        category.residual_rate = request.residual_rate;
+        category.declining_balance_rate = request.declining_balance_rate;
+        category.estimated_total_hours = request.estimated_total_hours;
+        Self::validate_depreciation(&category)?;
+        category.specification_schema = request
+            .specification_schema
+            .map(Self::checked_specification_schema)
+            .transpose()?;
 
         let created = self.repository.create(&category).await.map_err(|e| {
             DomainError::ExternalServiceError {
@@ -222,6 +250,31 @@ impl CategoryService {
             category.example_assets = Some(serde_json::to_value(assets).unwrap_or_default());
         }
 
+        let depreciation_settings = Self::depreciation_settings(&category);
+        if let Some(method) = request.depreciation_method {
+            category.depreciation_method = Some(method);
+        }
+        if let Some(months) = request.depreciation_period_months {
+            category.depreciation_period = Some(months);
+        }
+        if let Some(rate) = request.residual_rate {
+            category.residual_rate = Some(rate);
+        }
+        if let Some(rate) = request.declining_balance_rate {
+            category.declining_balance_rate = Some(rate);
+        }
+        if let Some(hours) = request.estimated_total_hours {
+            category.estimated_total_hours = Some(hours);
+        }
+        Self::validate_depreciation(&category)?;
+        if let Some(schema) = request.specification_schema {
+            // An empty object removes the schema
+            category.specification_schema = Some(schema)
+                .filter(|s| s.as_object().is_none_or(|o| !o.is_empty()))
+                .map(Self::checked_specification_schema)
+                .transpose()?;
+        }
+
         let updated = self.repository.update(&category).await.map_err(|e| {
             DomainError::ExternalServiceError {
                 service: "database".to_string(),
@@ -229,6 +282,17 @@ impl CategoryService {
             }
         })?;
 
+        if Self::depreciation_settings(&updated) != depreciation_settings {
+            let run = self.depreciation_service.recalculate_category(id).await?;
+            for failure in run.failed {
+                tracing::warn!(
+                    "Depreciation for asset {} not recalculated: {}",
+                    failure.asset_id,
+                    failure.error
+                );
+            }
+        }
+
         Ok(Self::to_response(updated))
     }
 
@@ -280,6 +344,66 @@ impl CategoryService {
     }
 
     /// Convert entity to response DTO
+    fn validate_depreciation(category: &Category) -> DomainResult<()> {
+        if let Some(method) = &category.depreciation_method {
+            DepreciationMethod::validate(method)?;
+        }
+        if category.depreciation_period.is_some_and(|m| m <= 0) {
+            return Err(DomainError::validation(
+                "depreciation_period_months",
+                "Must be greater than zero",
+            ));
+        }
+        let unit_interval = Decimal::ZERO..=Decimal::ONE;
+        if category
+            .residual_rate
+            .is_some_and(|r| !unit_interval.contains(&r))
+        {
+            return Err(DomainError::validation(
+                "residual_rate",
+                "Must be between 0 and 1",
+            ));
+        }
+        if category
+            .declining_balance_rate
+            .is_some_and(|r| r <= Decimal::ZERO || r > Decimal::ONE)
+        {
+            return Err(DomainError::validation(
+                "declining_balance_rate",
+                "Must be greater than 0 and at most 1",
+            ));
+        }
+        if category.depreciation_method.as_deref()
+            == Some(DepreciationMethod::UnitsOfProduction.as_str())
+            && category
+                .estimated_total_hours
+                .is_none_or(|h| h <= Decimal::ZERO)
+        {
+            return Err(DomainError::validation(
+                "estimated_total_hours",
+                "Required for units_of_production depreciation",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Parse the schema to reject malformed ones before storing it as sent
+    fn checked_specification_schema(schema: JsonValue) -> DomainResult<JsonValue> {
+        SpecificationSchema::parse(&schema)?;
+        Ok(schema)
+    }
+
+    /// Settings the depreciation schedules of the category's assets depend on
+    fn depreciation_settings(category: &Category) -> DepreciationSettings {
+        (
+            category.depreciation_method.clone(),
+            category.depreciation_period,
+            category.residual_rate,
+            category.declining_balance_rate,
+            category.estimated_total_hours,
+        )
+    }
+
     fn to_response(category: Category) -> CategoryResponse {
         let example_assets: Option<Vec<String>> = category
             .example_assets
@@ -298,6 +422,12 @@ impl CategoryService {
             function_description: category.function_description,
             example_assets,
             display_order: category.display_order,
+            depreciation_method: category.depreciation_method,
+            depreciation_period_months: category.depreciation_period,
+            residual_rate: category.residual_rate,
+            declining_balance_rate: category.declining_balance_rate,
+            estimated_total_hours: category.estimated_total_hours,
+            specification_schema: category.specification_schema,
             created_at: category.created_at,
             updated_at: category.updated_at,
         }
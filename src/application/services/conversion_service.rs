@@ -2,24 +2,50 @@
 //! Business logic for asset conversions
 
 use crate::application::dto::{CreateConversionRequest, ExecuteConversionRequest};
+use crate::application::services::{
+    ApprovalService, ApprovalSubmission, BudgetCommitment, BudgetService, BudgetSpend,
+    BudgetedResult, DepreciationService, DocumentNumberService, SpecificationService,
+};
 use crate::domain::entities::conversion::AssetConversion;
-use crate::domain::entities::AssetHistory;
+use crate::domain::entities::{
+    AssetHistory, AssetSpecificationHistory, NumberingScope, DOC_CONVERSION,
+    SPEC_CHANGE_CONVERSION, SPEND_CONVERSION,
+};
 use crate::domain::errors::{DomainError, DomainResult};
 use crate::infrastructure::repositories::{AssetRepository, ConversionRepository};
 use chrono::Utc;
+use sqlx::{Postgres, Transaction};
 use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct ConversionService {
     conversion_repo: ConversionRepository,
     asset_repo: AssetRepository, // Added direct access for now
+    approval_service: ApprovalService,
+    depreciation_service: DepreciationService,
+    specification_service: SpecificationService,
+    budget_service: BudgetService,
+    document_numbers: DocumentNumberService,
 }
 
 impl ConversionService {
-    pub fn new(conversion_repo: ConversionRepository, asset_repo: AssetRepository) -> Self {
+    pub fn new(
+        conversion_repo: ConversionRepository,
+        asset_repo: AssetRepository,
+        approval_service: ApprovalService,
+        depreciation_service: DepreciationService,
+        specification_service: SpecificationService,
+        budget_service: BudgetService,
+        document_numbers: DocumentNumberService,
+    ) -> Self {
         Self {
             conversion_repo,
             asset_repo,
+            approval_service,
+            depreciation_service,
+            specification_service,
+            budget_service,
+            document_numbers,
         }
     }
 
@@ -40,8 +66,21 @@ impl ConversionService {
             })?
             .ok_or_else(|| DomainError::not_found("Asset", request.asset_id))?;
 
-        // Generate Request Number (Simple Timestamp based for MVP)
-        let request_number = format!("CNV-{}", Utc::now().format("%Y%m%d-%H%M%S"));
+        // Specifications the asset will have after conversion must fit the target category
+        self.specification_service
+            .validate(
+                request.to_category_id,
+                request
+                    .target_specifications
+                    .as_ref()
+                    .or(asset.specifications.as_ref()),
+            )
+            .await?;
+
+        let request_number = self
+            .document_numbers
+            .next_number(DOC_CONVERSION, NumberingScope::asset(asset.id))
+            .await?;
 
         let conversion = AssetConversion {
             id: Uuid::new_v4(),
@@ -78,7 +117,32 @@ impl ConversionService {
         // Update asset status to indicate conversion process?
         // For MVP, simplistic: Let's not lock it yet until approval or explicit status change.
 
-        Ok(created_conversion)
+        // Route through the configured approval workflow
+        let submission = self
+            .approval_service
+            .create_request(
+                "conversion",
+                created_conversion.id,
+                "CREATE",
+                requested_by,
+                serde_json::to_value(&created_conversion).ok(),
+                Some(asset.category_id),
+            )
+            .await?;
+
+        match submission {
+            ApprovalSubmission::Pending(_) => Ok(created_conversion),
+            ApprovalSubmission::NotRequired => {
+                match self
+                    .approve_request(created_conversion.id, requested_by)
+                    .await?
+                {
+                    BudgetedResult::Applied(conversion) => Ok(conversion),
+                    // Held up by a budget overrun request
+                    BudgetedResult::PendingApproval(_) => Ok(created_conversion),
+                }
+            }
+        }
     }
 
     /// Get pending requests
@@ -92,37 +156,106 @@ impl ConversionService {
             })
     }
 
-    /// Approve a conversion request
+    /// Approve a conversion request, committing its cost against the asset's
+    /// budget. Over budget on an `escalate` line it stays pending behind a
+    /// budget OVERRUN approval request.
     pub async fn approve_request(
         &self,
         id: Uuid,
         approved_by: Uuid,
-    ) -> DomainResult<AssetConversion> {
-        let mut conversion = self
-            .conversion_repo
-            .find_by_id(id)
+    ) -> DomainResult<BudgetedResult<AssetConversion>> {
+        let conversion = self.get_conversion(id).await?;
+
+        let spend = Self::budget_spend(&conversion, Utc::now().date_naive());
+        if let BudgetCommitment::Escalated(request) =
+            self.budget_service.commit(&spend, approved_by).await?
+        {
+            return Ok(BudgetedResult::PendingApproval(request));
+        }
+
+        let conversion = Self::approved(conversion, approved_by);
+        self.conversion_repo
+            .update(&conversion)
             .await
+            .map(BudgetedResult::Applied)
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
-            })?
-            .ok_or_else(|| DomainError::not_found("Conversion Request", id))?;
+            })
+    }
 
+    /// `approve_request` inside an approval's transaction; an OVERRUN request it
+    /// opens is announced by the caller
+    pub async fn approve_request_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        approved_by: Uuid,
+    ) -> DomainResult<BudgetedResult<AssetConversion>> {
+        let conversion = self.get_conversion(id).await?;
+
+        let spend = Self::budget_spend(&conversion, Utc::now().date_naive());
+        if let BudgetCommitment::Escalated(request) = self
+            .budget_service
+            .commit_in(tx, &spend, approved_by)
+            .await?
+        {
+            return Ok(BudgetedResult::PendingApproval(request));
+        }
+
+        Self::save_approved(tx, Self::approved(conversion, approved_by))
+            .await
+            .map(BudgetedResult::Applied)
+    }
+
+    /// Approve a conversion whose budget overrun was approved, inside the
+    /// approval's transaction
+    pub async fn approve_over_budget(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        approved_by: Uuid,
+        approval_request_id: Uuid,
+    ) -> DomainResult<AssetConversion> {
+        let conversion = self.get_conversion(id).await?;
+
+        let spend = Self::budget_spend(&conversion, Utc::now().date_naive());
+        self.budget_service
+            .commit_overrun(tx, &spend, approved_by, Some(approval_request_id))
+            .await?;
+
+        Self::save_approved(tx, Self::approved(conversion, approved_by)).await
+    }
+
+    fn approved(mut conversion: AssetConversion, approved_by: Uuid) -> AssetConversion {
         conversion.status = "approved".to_string();
         conversion.approved_by = Some(approved_by);
         conversion.approval_date = Some(Utc::now());
         conversion.updated_at = Utc::now();
+        conversion
+    }
 
-        let updated_conversion = self
-            .conversion_repo
-            .update(&conversion)
+    async fn save_approved(
+        tx: &mut Transaction<'_, Postgres>,
+        conversion: AssetConversion,
+    ) -> DomainResult<AssetConversion> {
+        ConversionRepository::update_with(&mut **tx, &conversion)
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
-            })?;
+            })
+    }
 
-        Ok(updated_conversion)
+    fn budget_spend(conversion: &AssetConversion, date: chrono::NaiveDate) -> BudgetSpend {
+        BudgetSpend {
+            source_type: SPEND_CONVERSION,
+            source_id: conversion.id,
+            asset_id: conversion.asset_id,
+            amount: conversion.conversion_cost,
+            date,
+            reference: conversion.request_number.clone(),
+        }
     }
 
     /// Reject a conversion request
@@ -152,6 +285,9 @@ impl ConversionService {
                 service: "database".to_string(),
                 message: e.to_string(),
             })?;
+        self.budget_service
+            .release(SPEND_CONVERSION, conversion.id)
+            .await?;
 
         Ok(updated_conversion)
     }
@@ -185,7 +321,26 @@ impl ConversionService {
             conversion.notes = Some(notes);
         }
 
-        let mut asset = self
+        // A back-dated execution must not land in a closed depreciation period
+        let execution_date = match request.execution_date {
+            Some(date) => {
+                if date > Utc::now().date_naive() {
+                    return Err(DomainError::validation(
+                        "execution_date",
+                        "Cannot be in the future",
+                    ));
+                }
+                date.and_hms_opt(0, 0, 0)
+                    .map(|dt| dt.and_utc())
+                    .unwrap_or_else(Utc::now)
+            }
+            None => Utc::now(),
+        };
+        self.depreciation_service
+            .ensure_period_open(execution_date.date_naive())
+            .await?;
+
+        let previous = self
             .asset_repo
             .find_by_id(conversion.asset_id)
             .await
@@ -194,6 +349,7 @@ impl ConversionService {
                 message: e.to_string(),
             })?
             .ok_or_else(|| DomainError::not_found("Asset", conversion.asset_id))?;
+        let mut asset = previous.clone();
 
         // 1. Update Asset Category
         asset.category_id = conversion.to_category_id;
@@ -202,6 +358,10 @@ impl ConversionService {
         if let Some(specs) = &conversion.target_specifications {
             asset.specifications = Some(specs.clone());
         }
+        // The schema may have changed since the request was raised
+        self.specification_service
+            .validate(asset.category_id, asset.specifications.as_ref())
+            .await?;
 
         // 3. Financial Treatment
         if conversion.cost_treatment == "capitalize" {
@@ -212,37 +372,65 @@ impl ConversionService {
             }
         }
 
-        // 4. Save Asset
-        self.asset_repo
-            .update(&asset)
-            .await
-            .map_err(|e| DomainError::ExternalServiceError {
-                service: "database".to_string(),
-                message: e.to_string(),
-            })?;
-
-        // 5. Update Conversion Status
+        // 4. Conversion status
         conversion.status = "executed".to_string();
         conversion.executed_by = Some(executed_by);
-        conversion.execution_date = Some(Utc::now());
+        conversion.execution_date = Some(execution_date);
         conversion.updated_at = Utc::now();
 
+        // 5. History
+        let mut spec_history = AssetSpecificationHistory::new(
+            asset.id,
+            SPEC_CHANGE_CONVERSION,
+            previous.category_id,
+            asset.category_id,
+            previous.specifications.clone(),
+            asset.specifications.clone(),
+            Some(executed_by),
+        );
+        spec_history.conversion_id = Some(conversion.id);
+        spec_history.notes = Some(format!(
+            "{}: {}",
+            conversion.request_number, conversion.title
+        ));
+        let mut history = AssetHistory::new(
+            asset.id,
+            &format!("converted_to_category_{}", conversion.to_category_id),
+            Some(executed_by),
+        );
+        history.notes = conversion.notes.clone();
+
+        // 6. Save asset, conversion and history together
         let updated_conversion = self
             .conversion_repo
-            .update(&conversion)
+            .execute(&conversion, &asset, &spec_history, &history)
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
+            })?
+            .ok_or_else(|| {
+                DomainError::conflict(
+                    "Conversion or asset was changed concurrently; reload and retry",
+                )
             })?;
 
-        // 6. Add History
-        let history = AssetHistory::new(
-            asset.id,
-            &format!("converted_to_category_{}", conversion.to_category_id),
-            Some(executed_by),
-        );
-        let _ = self.asset_repo.add_history(&history).await;
+        // 7. Capitalized cost and the new category both change the depreciation basis
+        self.depreciation_service.refresh_asset(asset.id).await;
+
+        // 8. Actual spend replaces the approval commitment
+        let spend = Self::budget_spend(&updated_conversion, execution_date.date_naive());
+        if let Err(e) = self
+            .budget_service
+            .record_actual(&spend, Some(executed_by))
+            .await
+        {
+            tracing::warn!(
+                "Failed to post actual spend of conversion {}: {}",
+                updated_conversion.request_number,
+                e
+            );
+        }
 
         Ok(updated_conversion)
     }
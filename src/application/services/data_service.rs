@@ -4,6 +4,8 @@
 
 use csv::WriterBuilder;
 
+use crate::application::services::RbacService;
+use crate::domain::entities::UserClaims;
 use crate::domain::errors::{DomainError, DomainResult};
 use crate::infrastructure::repositories::AssetRepository;
 
@@ -11,20 +13,25 @@ use crate::infrastructure::repositories::AssetRepository;
 #[derive(Clone)]
 pub struct DataService {
     pub asset_repository: AssetRepository,
+    rbac_service: RbacService,
 }
 
 impl DataService {
-    pub fn new(asset_repository: AssetRepository) -> Self {
-        Self { asset_repository }
+    pub fn new(asset_repository: AssetRepository, rbac_service: RbacService) -> Self {
+        Self {
+            asset_repository,
+            rbac_service,
+        }
     }
 
-    /// Export assets to CSV
-    pub async fn export_assets_csv(&self) -> DomainResult<String> {
+    /// Export assets in the caller's organization and data scope to CSV
+    pub async fn export_assets_csv(&self, claims: &UserClaims) -> DomainResult<String> {
+        let scope = self.rbac_service.data_scope(claims, "asset.read").await?;
         // Fetch all assets (pagination free for export, or batched)
         // For simplicity, we'll fetch a large page
         let assets = self
             .asset_repository
-            .list(10000, 0, None)
+            .list(10000, 0, claims.organization_id(), &scope)
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
@@ -0,0 +1,599 @@
+//! Depreciation Service
+//!
+//! Builds monthly depreciation schedules from asset and category settings and keeps
+//! them in `depreciation_schedules`. Reports and the dashboard read positions from
+//! the persisted schedule.
+//!
+//! Month-end close posts the schedule rows up to a period. Posted rows are never
+//! recalculated and the asset figures they were computed from are locked until the
+//! period is reopened through the approval workflow.
+
+use chrono::{Months, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use serde_json::json;
+use sqlx::{Postgres, Transaction};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::application::services::{ApprovalService, ApprovalSubmission};
+use crate::domain::entities::{
+    month_start, rebase_after_close, AssetDepreciationPosition, DataScope, DepreciationCloseLine,
+    DepreciationClosing, DepreciationMethod, DepreciationSchedule, CLOSING_REOPEN_ACTION,
+    CLOSING_RESOURCE_TYPE,
+};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::repositories::DepreciationRepository;
+
+/// Portfolio totals at a date
+#[derive(Debug, Clone, Serialize)]
+pub struct DepreciationTotals {
+    pub as_of: NaiveDate,
+    pub total_original_cost: Decimal,
+    pub total_accumulated_depreciation: Decimal,
+    pub total_valuation_adjustment: Decimal,
+    pub total_book_value: Decimal,
+    pub period_depreciation: Decimal,
+}
+
+/// Book figures of an asset at the start of a month
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct CarryingAmount {
+    pub book_value: Decimal,
+    pub accumulated_depreciation: Decimal,
+}
+
+/// Outcome of a bulk recalculation
+#[derive(Debug, Clone, Serialize)]
+pub struct DepreciationRun {
+    pub recalculated: usize,
+    pub failed: Vec<DepreciationFailure>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DepreciationFailure {
+    pub asset_id: Uuid,
+    pub error: String,
+}
+
+/// Close report: posted positions at the period by category, department and location
+#[derive(Debug, Clone, Serialize)]
+pub struct DepreciationCloseReport {
+    pub closing: DepreciationClosing,
+    pub by_category: Vec<DepreciationCloseLine>,
+    pub by_department: Vec<DepreciationCloseLine>,
+    pub by_location: Vec<DepreciationCloseLine>,
+}
+
+#[derive(Clone)]
+pub struct DepreciationService {
+    repository: DepreciationRepository,
+    approval_service: ApprovalService,
+}
+
+impl DepreciationService {
+    pub fn new(repository: DepreciationRepository, approval_service: ApprovalService) -> Self {
+        Self {
+            repository,
+            approval_service,
+        }
+    }
+
+    pub async fn get_schedule(&self, asset_id: Uuid) -> DomainResult<Vec<DepreciationSchedule>> {
+        self.repository.list_schedule(asset_id).await.map_err(|e| {
+            DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            }
+        })
+    }
+
+    /// Rebuild one asset's schedule. Assets without a purchase price or date lose
+    /// any open schedule they had; rows of closed periods are kept.
+    pub async fn recalculate_asset(
+        &self,
+        asset_id: Uuid,
+    ) -> DomainResult<Vec<DepreciationSchedule>> {
+        let profile = self
+            .repository
+            .find_profile(asset_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("Asset", asset_id))?;
+
+        let monthly_units = if profile.method() == DepreciationMethod::UnitsOfProduction {
+            self.repository
+                .monthly_usage(asset_id)
+                .await
+                .map_err(|e| DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                })?
+                .into_iter()
+                .collect()
+        } else {
+            BTreeMap::new()
+        };
+
+        let revaluations = self
+            .repository
+            .list_revaluations(asset_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .into_iter()
+            .collect();
+
+        let input = match profile.input(monthly_units, revaluations, Utc::now().date_naive()) {
+            Some(input) => input,
+            None => {
+                self.repository
+                    .delete_schedule(asset_id)
+                    .await
+                    .map_err(|e| DomainError::ExternalServiceError {
+                        service: "database".to_string(),
+                        message: e.to_string(),
+                    })?;
+                return Ok(vec![]);
+            }
+        };
+
+        let mut periods = input.schedule()?;
+        let closed_through = self
+            .repository
+            .find_closed_through(asset_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        if let Some((through, posted_accumulated)) = closed_through {
+            periods = rebase_after_close(periods, through, posted_accumulated);
+        }
+
+        self.repository
+            .replace_schedule(asset_id, input.method.as_str(), &periods)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    /// Recalculate after a change to an asset's financials, logging instead of
+    /// failing the change that triggered it
+    pub async fn refresh_asset(&self, asset_id: Uuid) {
+        if let Err(e) = self.recalculate_asset(asset_id).await {
+            tracing::warn!(
+                "Depreciation for asset {} not recalculated: {}",
+                asset_id,
+                e
+            );
+        }
+    }
+
+    /// Recalculate every asset in a category (after its depreciation settings change)
+    pub async fn recalculate_category(&self, category_id: Uuid) -> DomainResult<DepreciationRun> {
+        let ids = self
+            .repository
+            .list_assets_in_category(category_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        Ok(self.recalculate_many(ids).await)
+    }
+
+    /// Recalculate every depreciable asset (Background Task)
+    pub async fn recalculate_all(&self) -> DomainResult<DepreciationRun> {
+        let ids = self
+            .repository
+            .list_depreciable_asset_ids()
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        Ok(self.recalculate_many(ids).await)
+    }
+
+    async fn recalculate_many(&self, ids: Vec<Uuid>) -> DepreciationRun {
+        let mut run = DepreciationRun {
+            recalculated: 0,
+            failed: Vec::new(),
+        };
+        for asset_id in ids {
+            match self.recalculate_asset(asset_id).await {
+                Ok(_) => run.recalculated += 1,
+                Err(e) => run.failed.push(DepreciationFailure {
+                    asset_id,
+                    error: e.to_string(),
+                }),
+            }
+        }
+        run
+    }
+
+    /// Per-asset positions as of a date, limited to an organization subtree
+    pub async fn positions(
+        &self,
+        as_of: NaiveDate,
+        org_scope: Option<Uuid>,
+    ) -> DomainResult<Vec<AssetDepreciationPosition>> {
+        self.positions_in_scope(as_of, org_scope, &DataScope::unrestricted())
+            .await
+    }
+
+    /// Per-asset positions as of a date, limited to an organization subtree
+    /// and the data scope of the caller
+    pub async fn positions_in_scope(
+        &self,
+        as_of: NaiveDate,
+        org_scope: Option<Uuid>,
+        scope: &DataScope,
+    ) -> DomainResult<Vec<AssetDepreciationPosition>> {
+        self.repository
+            .list_positions(as_of, org_scope, scope)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    pub async fn totals(
+        &self,
+        as_of: NaiveDate,
+        org_scope: Option<Uuid>,
+    ) -> DomainResult<DepreciationTotals> {
+        let positions = self.positions(as_of, org_scope).await?;
+        Ok(DepreciationTotals {
+            as_of,
+            total_original_cost: positions.iter().map(|p| p.purchase_price).sum(),
+            total_accumulated_depreciation: positions
+                .iter()
+                .map(|p| p.accumulated_depreciation)
+                .sum(),
+            total_valuation_adjustment: positions.iter().map(|p| p.valuation_adjustment).sum(),
+            total_book_value: positions.iter().map(|p| p.book_value).sum(),
+            period_depreciation: positions.iter().map(|p| p.period_depreciation).sum(),
+        })
+    }
+
+    /// Carrying amount of an asset at the start of the month containing `date`,
+    /// before any revaluation booked in that month
+    pub async fn carrying_amount_at(
+        &self,
+        asset_id: Uuid,
+        date: NaiveDate,
+    ) -> DomainResult<CarryingAmount> {
+        let month = month_start(date);
+        let schedule = self.get_schedule(asset_id).await?;
+
+        if let Some(row) = schedule.iter().find(|r| r.period_start == month) {
+            return Ok(CarryingAmount {
+                book_value: row.opening_value,
+                accumulated_depreciation: row.accumulated_depreciation - row.depreciation_amount,
+            });
+        }
+        schedule
+            .iter()
+            .rev()
+            .find(|r| r.period_start < month)
+            .map(|r| CarryingAmount {
+                book_value: r.closing_value,
+                accumulated_depreciation: r.accumulated_depreciation,
+            })
+            .ok_or_else(|| {
+                DomainError::validation(
+                    "valuation_date",
+                    "Asset has no depreciation schedule at that date (no purchase price or date, or before purchase)",
+                )
+            })
+    }
+
+    // ==================== PERIOD CLOSE ====================
+
+    pub async fn list_closings(&self) -> DomainResult<Vec<DepreciationClosing>> {
+        self.repository
+            .list_closings()
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    /// Close of the month containing `period`
+    pub async fn get_closing(&self, period: NaiveDate) -> DomainResult<DepreciationClosing> {
+        let period_start = month_start(period);
+        self.repository
+            .find_closing(period_start)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| {
+                DomainError::not_found("DepreciationPeriod", period_start.format("%Y-%m"))
+            })
+    }
+
+    async fn get_closing_by_id(&self, id: Uuid) -> DomainResult<DepreciationClosing> {
+        self.repository
+            .find_closing_by_id(id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("DepreciationPeriod", id))
+    }
+
+    /// Close the month containing `period`: bring every schedule up to date, then
+    /// post all rows up to the month and lock them
+    pub async fn close_period(
+        &self,
+        period: NaiveDate,
+        closed_by: Option<Uuid>,
+    ) -> DomainResult<DepreciationClosing> {
+        let period_start = month_start(period);
+        let closings = self.list_closings().await?;
+        DepreciationClosing::check_close(period_start, Utc::now().date_naive(), &closings)?;
+
+        let run = self.recalculate_all().await?;
+        for failure in &run.failed {
+            tracing::warn!(
+                "Asset {} closed with its previous schedule: {}",
+                failure.asset_id,
+                failure.error
+            );
+        }
+
+        let period_end = period_start
+            .checked_add_months(Months::new(1))
+            .and_then(|d| d.pred_opt())
+            .unwrap_or(period_start);
+
+        let closing = self
+            .repository
+            .close_period(period_start, period_end, closed_by)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+
+        tracing::info!(
+            "Depreciation period {} closed: {} assets, {} depreciation",
+            period_start.format("%Y-%m"),
+            closing.asset_count,
+            closing.total_depreciation
+        );
+        Ok(closing)
+    }
+
+    /// Close last month unless it already is (Background Task)
+    pub async fn close_previous_month(&self) -> DomainResult<Option<DepreciationClosing>> {
+        let previous = month_start(Utc::now().date_naive())
+            .checked_sub_months(Months::new(1))
+            .ok_or_else(|| DomainError::validation("period", "Out of range"))?;
+
+        let closings = self.list_closings().await?;
+        if closings
+            .iter()
+            .any(|c| c.period_start == previous && c.is_closed())
+        {
+            return Ok(None);
+        }
+        self.close_period(previous, None).await.map(Some)
+    }
+
+    /// Ask to reopen a closed month. The reopen only takes effect once the
+    /// `depreciation_period` REOPEN approval goes through.
+    pub async fn request_reopen(
+        &self,
+        period: NaiveDate,
+        reason: String,
+        requested_by: Uuid,
+    ) -> DomainResult<DepreciationClosing> {
+        if reason.trim().is_empty() {
+            return Err(DomainError::validation("reason", "Reason is required"));
+        }
+
+        let closing = self.get_closing(period).await?;
+        closing.check_reopen(&self.list_closings().await?)?;
+
+        if let Some(request_id) = closing.reopen_request_id {
+            let request = self.approval_service.get_request(request_id).await?;
+            if request.status == "PENDING" {
+                return Err(DomainError::conflict(
+                    "A reopen request for this period is already pending",
+                ));
+            }
+        }
+
+        let submission = self
+            .approval_service
+            .create_request(
+                CLOSING_RESOURCE_TYPE,
+                closing.id,
+                CLOSING_REOPEN_ACTION,
+                requested_by,
+                Some(json!({
+                    "period_start": closing.period_start,
+                    "reason": reason,
+                })),
+                None,
+            )
+            .await?;
+
+        match submission {
+            ApprovalSubmission::Pending(request) => self
+                .repository
+                .set_reopen_request(closing.id, Some(request.id), Some(&reason))
+                .await
+                .map_err(|e| DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                }),
+            ApprovalSubmission::NotRequired => {
+                self.repository
+                    .set_reopen_request(closing.id, None, Some(&reason))
+                    .await
+                    .map_err(|e| DomainError::ExternalServiceError {
+                        service: "database".to_string(),
+                        message: e.to_string(),
+                    })?;
+                self.apply_reopen(closing.id, requested_by).await
+            }
+        }
+    }
+
+    /// Reopen an approved close; its rows become recalculable again
+    pub async fn apply_reopen(
+        &self,
+        closing_id: Uuid,
+        approved_by: Uuid,
+    ) -> DomainResult<DepreciationClosing> {
+        self.check_reopen(closing_id).await?;
+
+        let closing = self
+            .repository
+            .reopen(closing_id, approved_by)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+
+        tracing::info!(
+            "Depreciation period {} reopened by {}",
+            closing.period_start.format("%Y-%m"),
+            approved_by
+        );
+        Ok(closing)
+    }
+
+    /// `apply_reopen` inside an approval's transaction
+    pub async fn apply_reopen_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        closing_id: Uuid,
+        approved_by: Uuid,
+    ) -> DomainResult<DepreciationClosing> {
+        self.check_reopen(closing_id).await?;
+
+        let closing = DepreciationRepository::reopen_with(&mut **tx, closing_id, approved_by)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+
+        tracing::info!(
+            "Depreciation period {} reopened by {}",
+            closing.period_start.format("%Y-%m"),
+            approved_by
+        );
+        Ok(closing)
+    }
+
+    async fn check_reopen(&self, closing_id: Uuid) -> DomainResult<()> {
+        let closing = self.get_closing_by_id(closing_id).await?;
+        closing.check_reopen(&self.list_closings().await?)
+    }
+
+    /// Forget a rejected reopen request
+    pub async fn cancel_reopen(&self, closing_id: Uuid) -> DomainResult<DepreciationClosing> {
+        self.repository
+            .set_reopen_request(closing_id, None, None)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    pub async fn close_report(&self, period: NaiveDate) -> DomainResult<DepreciationCloseReport> {
+        let closing = self.get_closing(period).await?;
+        let map_err = |e: sqlx::Error| DomainError::ExternalServiceError {
+            service: "database".to_string(),
+            message: e.to_string(),
+        };
+
+        Ok(DepreciationCloseReport {
+            by_category: self
+                .repository
+                .close_report_by_category(closing.period_start)
+                .await
+                .map_err(map_err)?,
+            by_department: self
+                .repository
+                .close_report_by_department(closing.period_start)
+                .await
+                .map_err(map_err)?,
+            by_location: self
+                .repository
+                .close_report_by_location(closing.period_start)
+                .await
+                .map_err(map_err)?,
+            closing,
+        })
+    }
+
+    /// Refuse changes to purchase price, residual value or useful life of an asset
+    /// with depreciation posted in a closed period
+    pub async fn ensure_financials_unlocked(&self, asset_id: Uuid) -> DomainResult<()> {
+        let closed_through = self
+            .repository
+            .find_closed_through(asset_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+
+        match closed_through {
+            Some((through, _)) => Err(DomainError::business_rule(
+                "depreciation_period_closed",
+                &format!(
+                    "Depreciation is closed through {}; reopen the period to change purchase price, residual value or useful life",
+                    through.format("%Y-%m")
+                ),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Refuse changes dated in a closed month. A close covers every earlier month
+    /// too, so the date is closed while any later or equal month is closed.
+    pub async fn ensure_period_open(&self, date: NaiveDate) -> DomainResult<()> {
+        let period_start = month_start(date);
+        let closed_through = self
+            .list_closings()
+            .await?
+            .into_iter()
+            .filter(|c| c.is_closed() && c.period_start >= period_start)
+            .map(|c| c.period_start)
+            .max();
+
+        match closed_through {
+            Some(through) => Err(DomainError::business_rule(
+                "depreciation_period_closed",
+                &format!(
+                    "Depreciation is closed through {}; an approved reopen is required for changes dated {}",
+                    through.format("%Y-%m"),
+                    period_start.format("%Y-%m")
+                ),
+            )),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,387 @@
+//! Document Service
+//!
+//! Files attached to assets (certificates, warranties, permits, manuals). Files
+//! live in private storage and are only handed out through the download
+//! endpoint. Replacing a document adds a version; the previous one is kept.
+
+use chrono::{Duration, Utc};
+use serde::Serialize;
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::application::dto::UploadDocumentRequest;
+use crate::application::services::NotificationService;
+use crate::domain::entities::{AssetDocument, DOCUMENT_EXPIRY_REMINDER_DAYS};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::repositories::{AssetRepository, DocumentRepository, RbacRepository};
+use crate::infrastructure::storage::LocalStorage;
+
+/// Permission of the users the reminder job notifies
+const ASSET_UPDATE_PERMISSION: &str = "asset.update";
+
+/// Outcome of a reminder job run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DocumentReminderRun {
+    pub reminded: usize,
+}
+
+#[derive(Clone)]
+pub struct DocumentService {
+    repository: DocumentRepository,
+    asset_repo: AssetRepository,
+    rbac_repo: RbacRepository,
+    notification_service: NotificationService,
+    storage: LocalStorage,
+}
+
+impl DocumentService {
+    pub fn new(
+        repository: DocumentRepository,
+        asset_repo: AssetRepository,
+        rbac_repo: RbacRepository,
+        notification_service: NotificationService,
+        storage: LocalStorage,
+    ) -> Self {
+        Self {
+            repository,
+            asset_repo,
+            rbac_repo,
+            notification_service,
+            storage,
+        }
+    }
+
+    pub async fn list_by_asset(
+        &self,
+        asset_id: Uuid,
+        include_history: bool,
+    ) -> DomainResult<Vec<AssetDocument>> {
+        self.repository
+            .list_by_asset(asset_id, include_history)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    /// A document of the given asset (any version)
+    pub async fn get(&self, asset_id: Uuid, id: Uuid) -> DomainResult<AssetDocument> {
+        self.repository
+            .find_by_id(id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .filter(|d| d.asset_id == asset_id)
+            .ok_or_else(|| DomainError::not_found("AssetDocument", id))
+    }
+
+    pub async fn list_versions(
+        &self,
+        asset_id: Uuid,
+        id: Uuid,
+    ) -> DomainResult<Vec<AssetDocument>> {
+        let document = self.get(asset_id, id).await?;
+        self.repository
+            .list_versions(document.document_group_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    pub async fn upload(
+        &self,
+        asset_id: Uuid,
+        request: UploadDocumentRequest,
+        uploaded_by: Uuid,
+    ) -> DomainResult<AssetDocument> {
+        self.asset_repo
+            .find_by_id(asset_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("Asset", asset_id))?;
+
+        let name = request
+            .name
+            .clone()
+            .or_else(|| request.original_name.clone())
+            .filter(|n| !n.trim().is_empty())
+            .ok_or_else(|| DomainError::validation("name", "Required"))?;
+        let type_ = request
+            .type_
+            .as_deref()
+            .map(Self::normalize_type)
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| DomainError::validation("type", "Required"))?;
+
+        let document = Self::build(asset_id, None, 1, name, type_, &request, uploaded_by);
+        self.store(&document, &request.data).await?;
+
+        match self.repository.create(&document).await {
+            Ok(created) => Ok(created),
+            Err(e) => {
+                self.discard(&document.file_path).await;
+                Err(DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                })
+            }
+        }
+    }
+
+    /// Upload a new version of a document; unset fields carry over
+    pub async fn replace(
+        &self,
+        asset_id: Uuid,
+        id: Uuid,
+        request: UploadDocumentRequest,
+        uploaded_by: Uuid,
+    ) -> DomainResult<AssetDocument> {
+        let previous = self.get(asset_id, id).await?;
+        if !previous.is_current {
+            return Err(DomainError::business_rule(
+                "document_version",
+                "Only the current version of a document can be replaced",
+            ));
+        }
+
+        let name = request
+            .name
+            .clone()
+            .filter(|n| !n.trim().is_empty())
+            .unwrap_or_else(|| previous.name.clone());
+        let type_ = request
+            .type_
+            .as_deref()
+            .map(Self::normalize_type)
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| previous.type_.clone());
+
+        let mut document = Self::build(
+            asset_id,
+            Some(previous.document_group_id),
+            previous.version + 1,
+            name,
+            type_,
+            &request,
+            uploaded_by,
+        );
+        if request.expiry_date.is_none() {
+            document.expiry_date = previous.expiry_date;
+        }
+        if document.notes.is_none() {
+            document.notes = previous.notes.clone();
+        }
+        self.store(&document, &request.data).await?;
+
+        let result = self.repository.replace(previous.id, &document).await;
+        match result {
+            Ok(Some(created)) => Ok(created),
+            Ok(None) => {
+                self.discard(&document.file_path).await;
+                Err(DomainError::conflict(
+                    "Document was replaced concurrently; reload and retry",
+                ))
+            }
+            Err(e) => {
+                self.discard(&document.file_path).await;
+                Err(DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                })
+            }
+        }
+    }
+
+    /// File contents of a document version
+    pub async fn download(
+        &self,
+        asset_id: Uuid,
+        id: Uuid,
+    ) -> DomainResult<(AssetDocument, Vec<u8>)> {
+        let document = self.get(asset_id, id).await?;
+        let data = self.storage.read(&document.file_path).await.map_err(|e| {
+            DomainError::ExternalServiceError {
+                service: "storage".to_string(),
+                message: e.to_string(),
+            }
+        })?;
+        Ok((document, data))
+    }
+
+    /// Delete a document with all of its versions
+    pub async fn delete(&self, asset_id: Uuid, id: Uuid) -> DomainResult<()> {
+        let document = self.get(asset_id, id).await?;
+        let keys = self
+            .repository
+            .delete_group(document.document_group_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        for key in keys {
+            self.discard(&key).await;
+        }
+        Ok(())
+    }
+
+    /// Current documents expiring within `days`
+    pub async fn list_expiring(&self, days: i64) -> DomainResult<Vec<AssetDocument>> {
+        if days < 0 {
+            return Err(DomainError::validation("days", "Cannot be negative"));
+        }
+        let today = Utc::now().date_naive();
+        self.repository
+            .list_expiring(today, today + Duration::days(days), true)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    /// Remind once about current documents nearing `expiry_date` (Background Task)
+    pub async fn remind_expiring_documents(&self) -> DomainResult<DocumentReminderRun> {
+        let today = Utc::now().date_naive();
+        let mut run = DocumentReminderRun::default();
+
+        let expiring = self
+            .repository
+            .list_expiring(
+                today,
+                today + Duration::days(DOCUMENT_EXPIRY_REMINDER_DAYS),
+                false,
+            )
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        if expiring.is_empty() {
+            return Ok(run);
+        }
+
+        let recipients = self
+            .rbac_repo
+            .list_user_ids_with_permission(ASSET_UPDATE_PERMISSION)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+
+        for document in &expiring {
+            let asset_name = self
+                .asset_repo
+                .find_by_id(document.asset_id)
+                .await
+                .map_err(|e| DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                })?
+                .map(|a| a.name)
+                .unwrap_or_else(|| "Unknown Asset".to_string());
+            for user_id in &recipients {
+                if let Err(e) = self
+                    .notification_service
+                    .notify_document_expiring(*user_id, &asset_name, document)
+                    .await
+                {
+                    tracing::warn!("Expiry reminder for document {} failed: {}", document.id, e);
+                }
+            }
+            self.repository
+                .mark_expiry_notified(document.id)
+                .await
+                .map_err(|e| DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                })?;
+            run.reminded += 1;
+        }
+
+        Ok(run)
+    }
+
+    /// New current version; starts a new group unless `document_group_id` is given
+    fn build(
+        asset_id: Uuid,
+        document_group_id: Option<Uuid>,
+        version: i32,
+        name: String,
+        type_: String,
+        request: &UploadDocumentRequest,
+        uploaded_by: Uuid,
+    ) -> AssetDocument {
+        let id = Uuid::new_v4();
+        let now = Utc::now();
+        AssetDocument {
+            id,
+            asset_id,
+            document_group_id: document_group_id.unwrap_or(id),
+            version,
+            is_current: true,
+            name: name.trim().to_string(),
+            type_,
+            file_path: format!(
+                "{}/{}.{}",
+                asset_id,
+                id,
+                Self::extension(request.original_name.as_deref())
+            ),
+            original_name: request.original_name.clone(),
+            mime_type: request.mime_type.clone(),
+            size_bytes: Some(request.data.len() as i64),
+            expiry_date: request.expiry_date,
+            notes: request.notes.clone(),
+            uploaded_by: Some(uploaded_by),
+            replaced_by: None,
+            replaced_at: None,
+            expiry_notified_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    async fn store(&self, document: &AssetDocument, data: &[u8]) -> DomainResult<()> {
+        if data.is_empty() {
+            return Err(DomainError::validation("file", "File is empty"));
+        }
+        self.storage
+            .save(&document.file_path, data)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "storage".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    /// Best-effort removal of a stored file
+    async fn discard(&self, key: &str) {
+        if let Err(e) = self.storage.remove(key).await {
+            tracing::warn!("Failed to remove stored document {}: {}", key, e);
+        }
+    }
+
+    fn normalize_type(type_: &str) -> String {
+        type_.trim().to_uppercase()
+    }
+
+    /// Extension of the uploaded file name, if it is a plain one
+    fn extension(original_name: Option<&str>) -> String {
+        original_name
+            .and_then(|n| Path::new(n).extension())
+            .and_then(|e| e.to_str())
+            .filter(|e| e.len() <= 10 && e.chars().all(|c| c.is_ascii_alphanumeric()))
+            .map(|e| e.to_ascii_lowercase())
+            .unwrap_or_else(|| "bin".to_string())
+    }
+}
@@ -114,6 +114,7 @@ impl EmployeeService {
         &self,
         id: Uuid,
         req: CreateEmployeeUserRequest,
+        organization_id: Option<Uuid>,
     ) -> Result<Employee, AppError> {
         // 1. Get employee data
         let mut employee = self.get_by_id(id).await?;
@@ -133,7 +134,8 @@ impl EmployeeService {
             role_code: req.role,
             department: employee.department_name.clone(),
             department_id: employee.department_id,
-            organization_id: None, // Default organization
+            organization_id,
+            location_id: None,
         };
 
         let user = self.user_service.create_user(user_req).await?;
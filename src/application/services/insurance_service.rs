@@ -0,0 +1,594 @@
+//! Insurance Service
+//!
+//! Policies per asset, claims against them, the expiry job and the coverage
+//! report. The expiry job warns once per policy ahead of `end_date`, expires
+//! ended policies and flags assets left without cover.
+
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::application::dto::{
+    CreateInsuranceClaimRequest, CreateInsuranceRequest, UpdateInsuranceClaimStatusRequest,
+    UpdateInsuranceRequest,
+};
+use crate::application::services::NotificationService;
+use crate::domain::entities::{
+    AssetState, Insurance, InsuranceClaim, InsuranceCoverageLine, LapsedInsuranceAsset,
+    CLAIM_SUBMITTED, INSURANCE_EXPIRY_WARNING_DAYS, POLICY_ACTIVE, POLICY_CANCELLED,
+    POLICY_EXPIRED,
+};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::repositories::{
+    AssetRepository, InsuranceRepository, LifecycleRepository, RbacRepository, WorkOrderRepository,
+};
+
+/// Permission of the users the expiry job notifies
+const INSURANCE_MANAGE_PERMISSION: &str = "insurance.manage";
+
+/// Outcome of an expiry job run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct InsuranceExpiryRun {
+    pub expired: u64,
+    pub warned: usize,
+    pub lapsed: usize,
+    pub covered_again: u64,
+}
+
+#[derive(Clone)]
+pub struct InsuranceService {
+    repository: InsuranceRepository,
+    asset_repo: AssetRepository,
+    work_order_repo: WorkOrderRepository,
+    lifecycle_repo: LifecycleRepository,
+    rbac_repo: RbacRepository,
+    notification_service: NotificationService,
+}
+
+impl InsuranceService {
+    pub fn new(
+        repository: InsuranceRepository,
+        asset_repo: AssetRepository,
+        work_order_repo: WorkOrderRepository,
+        lifecycle_repo: LifecycleRepository,
+        rbac_repo: RbacRepository,
+        notification_service: NotificationService,
+    ) -> Self {
+        Self {
+            repository,
+            asset_repo,
+            work_order_repo,
+            lifecycle_repo,
+            rbac_repo,
+            notification_service,
+        }
+    }
+
+    // ==================== POLICIES ====================
+
+    pub async fn list_by_asset(&self, asset_id: Uuid) -> DomainResult<Vec<Insurance>> {
+        self.repository.list_by_asset(asset_id).await.map_err(|e| {
+            DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            }
+        })
+    }
+
+    pub async fn get_by_id(&self, id: Uuid) -> DomainResult<Insurance> {
+        self.repository
+            .find_by_id(id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("Insurance", id))
+    }
+
+    pub async fn create(
+        &self,
+        asset_id: Uuid,
+        request: CreateInsuranceRequest,
+        created_by: Uuid,
+    ) -> DomainResult<Insurance> {
+        if request.policy_number.trim().is_empty() {
+            return Err(DomainError::validation("policy_number", "Required"));
+        }
+        if request.insurance_provider.trim().is_empty() {
+            return Err(DomainError::validation("insurance_provider", "Required"));
+        }
+        Self::validate_amounts(request.coverage_amount, request.premium_amount)?;
+        Insurance::validate_period(request.start_date, request.end_date)?;
+
+        self.asset_repo
+            .find_by_id(asset_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("Asset", asset_id))?;
+
+        let today = Utc::now().date_naive();
+        let now = Utc::now();
+        let insurance = Insurance {
+            id: Uuid::new_v4(),
+            asset_id,
+            policy_number: request.policy_number.trim().to_string(),
+            insurance_provider: request.insurance_provider.trim().to_string(),
+            coverage_type: request.coverage_type,
+            coverage_amount: request.coverage_amount,
+            start_date: request.start_date,
+            end_date: request.end_date,
+            premium_amount: request.premium_amount,
+            // Policies recorded after the fact are stored as already expired
+            status: if request.end_date < today {
+                POLICY_EXPIRED.to_string()
+            } else {
+                POLICY_ACTIVE.to_string()
+            },
+            notes: request.notes,
+            expiry_notified_at: None,
+            created_by: Some(created_by),
+            created_at: now,
+            updated_at: now,
+        };
+
+        let created = self
+            .repository
+            .create(&insurance)
+            .await
+            .map_err(Self::write_error)?;
+        self.refresh_lapse(asset_id).await?;
+        Ok(created)
+    }
+
+    pub async fn update(
+        &self,
+        id: Uuid,
+        request: UpdateInsuranceRequest,
+    ) -> DomainResult<Insurance> {
+        let mut insurance = self.get_by_id(id).await?;
+        let previous_end = insurance.end_date;
+
+        if let Some(policy_number) = request.policy_number {
+            insurance.policy_number = policy_number.trim().to_string();
+        }
+        if let Some(provider) = request.insurance_provider {
+            insurance.insurance_provider = provider.trim().to_string();
+        }
+        if let Some(coverage_type) = request.coverage_type {
+            insurance.coverage_type = Some(coverage_type);
+        }
+        if let Some(amount) = request.coverage_amount {
+            insurance.coverage_amount = Some(amount);
+        }
+        if let Some(start_date) = request.start_date {
+            insurance.start_date = start_date;
+        }
+        if let Some(end_date) = request.end_date {
+            insurance.end_date = end_date;
+        }
+        if let Some(premium) = request.premium_amount {
+            insurance.premium_amount = Some(premium);
+        }
+        if let Some(notes) = request.notes {
+            insurance.notes = Some(notes);
+        }
+        if let Some(status) = request.status {
+            if status != POLICY_ACTIVE && status != POLICY_CANCELLED {
+                return Err(DomainError::validation(
+                    "status",
+                    "Must be active or cancelled; policies expire automatically",
+                ));
+            }
+            insurance.status = status;
+        }
+
+        if insurance.policy_number.is_empty() || insurance.insurance_provider.is_empty() {
+            return Err(DomainError::validation(
+                "policy_number",
+                "Policy number and provider are required",
+            ));
+        }
+        Self::validate_amounts(insurance.coverage_amount, insurance.premium_amount)?;
+        Insurance::validate_period(insurance.start_date, insurance.end_date)?;
+
+        // A moved end date (renewal or correction) gets its own expiry warning
+        if insurance.end_date != previous_end {
+            insurance.expiry_notified_at = None;
+        }
+        let today = Utc::now().date_naive();
+        if insurance.status != POLICY_CANCELLED {
+            insurance.status = if insurance.end_date < today {
+                POLICY_EXPIRED.to_string()
+            } else {
+                POLICY_ACTIVE.to_string()
+            };
+        }
+
+        let updated = self
+            .repository
+            .update(&insurance)
+            .await
+            .map_err(Self::write_error)?;
+        self.refresh_lapse(updated.asset_id).await?;
+        Ok(updated)
+    }
+
+    pub async fn delete(&self, id: Uuid) -> DomainResult<()> {
+        let deleted = self.repository.delete(id).await.map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.code().as_deref() == Some("23503") => {
+                DomainError::conflict("Policy has claims; cancel it instead of deleting")
+            }
+            _ => DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            },
+        })?;
+        if !deleted {
+            return Err(DomainError::not_found("Insurance", id));
+        }
+        Ok(())
+    }
+
+    /// Active policies ending within `days` (default: the warning window)
+    pub async fn list_expiring(&self, days: Option<i64>) -> DomainResult<Vec<Insurance>> {
+        let days = days.unwrap_or(INSURANCE_EXPIRY_WARNING_DAYS);
+        if !(0..=366).contains(&days) {
+            return Err(DomainError::validation("days", "Must be between 0 and 366"));
+        }
+        let today = Utc::now().date_naive();
+        self.repository
+            .list_expiring(today, today + Duration::days(days))
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    pub async fn list_lapsed(&self) -> DomainResult<Vec<LapsedInsuranceAsset>> {
+        self.repository
+            .list_lapsed()
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    pub async fn coverage_report(
+        &self,
+        as_of: Option<chrono::NaiveDate>,
+    ) -> DomainResult<Vec<InsuranceCoverageLine>> {
+        let as_of = as_of.unwrap_or_else(|| Utc::now().date_naive());
+        self.repository.coverage_report(as_of).await.map_err(|e| {
+            DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            }
+        })
+    }
+
+    // ==================== CLAIMS ====================
+
+    pub async fn list_claims(&self, insurance_id: Uuid) -> DomainResult<Vec<InsuranceClaim>> {
+        self.get_by_id(insurance_id).await?;
+        self.repository
+            .list_claims(insurance_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    pub async fn create_claim(
+        &self,
+        insurance_id: Uuid,
+        request: CreateInsuranceClaimRequest,
+        created_by: Uuid,
+    ) -> DomainResult<InsuranceClaim> {
+        let insurance = self.get_by_id(insurance_id).await?;
+
+        if request.claimed_amount <= Decimal::ZERO {
+            return Err(DomainError::validation(
+                "claimed_amount",
+                "Must be greater than zero",
+            ));
+        }
+        if request.incident_date > Utc::now().date_naive() {
+            return Err(DomainError::validation(
+                "incident_date",
+                "Cannot be in the future",
+            ));
+        }
+        if !insurance.covers(request.incident_date) {
+            return Err(DomainError::business_rule(
+                "insurance_coverage",
+                &format!(
+                    "Policy {} does not cover {} (cover {} to {}, status {})",
+                    insurance.policy_number,
+                    request.incident_date,
+                    insurance.start_date,
+                    insurance.end_date,
+                    insurance.status
+                ),
+            ));
+        }
+        if insurance
+            .coverage_amount
+            .is_some_and(|cover| request.claimed_amount > cover)
+        {
+            return Err(DomainError::business_rule(
+                "insurance_coverage",
+                "Claimed amount exceeds the policy's coverage amount",
+            ));
+        }
+
+        match (request.work_order_id, request.lifecycle_history_id) {
+            (Some(work_order_id), None) => {
+                let work_order = self
+                    .work_order_repo
+                    .find_by_id(work_order_id)
+                    .await
+                    .map_err(|e| DomainError::ExternalServiceError {
+                        service: "database".to_string(),
+                        message: e.to_string(),
+                    })?
+                    .ok_or_else(|| DomainError::not_found("WorkOrder", work_order_id))?;
+                if work_order.asset_id != insurance.asset_id {
+                    return Err(DomainError::validation(
+                        "work_order_id",
+                        "Work order belongs to a different asset",
+                    ));
+                }
+            }
+            (None, Some(history_id)) => {
+                let entry = self
+                    .lifecycle_repo
+                    .find_history_entry(history_id)
+                    .await?
+                    .ok_or_else(|| DomainError::not_found("LifecycleHistory", history_id))?;
+                if entry.asset_id != insurance.asset_id {
+                    return Err(DomainError::validation(
+                        "lifecycle_history_id",
+                        "Transition belongs to a different asset",
+                    ));
+                }
+                if entry.to_state != AssetState::LostStolen.as_str() {
+                    return Err(DomainError::validation(
+                        "lifecycle_history_id",
+                        "Only lost/stolen transitions can be claimed",
+                    ));
+                }
+            }
+            _ => {
+                return Err(DomainError::validation(
+                    "work_order_id",
+                    "Provide exactly one of work_order_id or lifecycle_history_id",
+                ))
+            }
+        }
+
+        let claim = InsuranceClaim {
+            id: Uuid::new_v4(),
+            insurance_id,
+            asset_id: insurance.asset_id,
+            claim_number: request.claim_number,
+            incident_date: request.incident_date,
+            work_order_id: request.work_order_id,
+            lifecycle_history_id: request.lifecycle_history_id,
+            claimed_amount: request.claimed_amount,
+            approved_amount: None,
+            payout_amount: None,
+            payout_date: None,
+            status: CLAIM_SUBMITTED.to_string(),
+            description: request.description,
+            notes: request.notes,
+            created_by: Some(created_by),
+            created_at: None,
+            updated_at: None,
+        };
+
+        self.repository
+            .create_claim(&claim)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    pub async fn update_claim_status(
+        &self,
+        claim_id: Uuid,
+        request: UpdateInsuranceClaimStatusRequest,
+    ) -> DomainResult<InsuranceClaim> {
+        let mut claim = self
+            .repository
+            .find_claim(claim_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("InsuranceClaim", claim_id))?;
+
+        let from_status = claim.status.clone();
+        claim.transition(
+            &request.status,
+            request.approved_amount,
+            request.payout_amount,
+            request.payout_date,
+        )?;
+        if let Some(claim_number) = request.claim_number {
+            claim.claim_number = Some(claim_number);
+        }
+        if let Some(notes) = request.notes {
+            claim.notes = Some(notes);
+        }
+
+        self.repository
+            .update_claim_status(&claim, &from_status)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| {
+                DomainError::conflict("Claim was changed concurrently; reload and retry")
+            })
+    }
+
+    // ==================== EXPIRY JOB ====================
+
+    /// Warn about policies nearing `end_date`, expire ended ones and flag assets
+    /// without cover (Background Task)
+    pub async fn check_expiring_policies(&self) -> DomainResult<InsuranceExpiryRun> {
+        let today = Utc::now().date_naive();
+        let mut run = InsuranceExpiryRun {
+            expired: self.repository.expire_ended(today).await.map_err(|e| {
+                DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                }
+            })?,
+            ..Default::default()
+        };
+
+        let recipients = self
+            .rbac_repo
+            .list_user_ids_with_permission(INSURANCE_MANAGE_PERMISSION)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+
+        let expiring = self
+            .repository
+            .list_expiring(today, today + Duration::days(INSURANCE_EXPIRY_WARNING_DAYS))
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        for policy in expiring.iter().filter(|p| p.expiry_notified_at.is_none()) {
+            let asset_name = self
+                .asset_repo
+                .find_by_id(policy.asset_id)
+                .await
+                .map_err(|e| DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                })?
+                .map(|a| a.name)
+                .unwrap_or_else(|| "Unknown Asset".to_string());
+            for user_id in &recipients {
+                if let Err(e) = self
+                    .notification_service
+                    .notify_insurance_expiring(*user_id, &asset_name, policy)
+                    .await
+                {
+                    tracing::warn!(
+                        "Expiry notice for policy {} failed: {}",
+                        policy.policy_number,
+                        e
+                    );
+                }
+            }
+            self.repository
+                .mark_expiry_notified(policy.id)
+                .await
+                .map_err(|e| DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                })?;
+            run.warned += 1;
+        }
+
+        run.covered_again = self
+            .repository
+            .clear_lapsed(None, today)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+
+        let lapsed = self.repository.flag_lapsed(today).await.map_err(|e| {
+            DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            }
+        })?;
+        for asset in &lapsed {
+            let lapsed_since = asset
+                .last_policy_end
+                .map(|d| (d + Duration::days(1)).to_string())
+                .unwrap_or_else(|| today.to_string());
+            for user_id in &recipients {
+                if let Err(e) = self
+                    .notification_service
+                    .notify_insurance_lapsed(
+                        *user_id,
+                        &asset.name,
+                        &asset.asset_code,
+                        &lapsed_since,
+                        asset.asset_id,
+                    )
+                    .await
+                {
+                    tracing::warn!("Lapse notice for asset {} failed: {}", asset.asset_code, e);
+                }
+            }
+        }
+        run.lapsed = lapsed.len();
+
+        Ok(run)
+    }
+
+    /// Clear the lapse flag as soon as a policy covers the asset again
+    async fn refresh_lapse(&self, asset_id: Uuid) -> DomainResult<()> {
+        self.repository
+            .clear_lapsed(Some(asset_id), Utc::now().date_naive())
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        Ok(())
+    }
+
+    fn validate_amounts(coverage: Option<Decimal>, premium: Option<Decimal>) -> DomainResult<()> {
+        if coverage.is_some_and(|a| a < Decimal::ZERO) {
+            return Err(DomainError::validation(
+                "coverage_amount",
+                "Cannot be negative",
+            ));
+        }
+        if premium.is_some_and(|a| a < Decimal::ZERO) {
+            return Err(DomainError::validation(
+                "premium_amount",
+                "Cannot be negative",
+            ));
+        }
+        Ok(())
+    }
+
+    fn write_error(e: sqlx::Error) -> DomainError {
+        match e {
+            sqlx::Error::Database(ref db) if db.code().as_deref() == Some("23505") => {
+                DomainError::conflict("Asset already has a policy with this number")
+            }
+            _ => DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            },
+        }
+    }
+}
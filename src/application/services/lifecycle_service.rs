@@ -2,16 +2,23 @@
 //!
 //! Business logic for asset lifecycle state transitions.
 
+use chrono::Utc;
 use serde_json::json;
+use sqlx::{Postgres, Transaction};
 use uuid::Uuid;
 
-use crate::domain::entities::{AssetState, LifecycleHistory};
+use crate::application::services::ApprovalService;
+use crate::domain::entities::{
+    ApprovalWorkflow, AssetState, LifecycleHistory, LIFECYCLE_RESOURCE_TYPE,
+};
 use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::events::{AssetStateChanged, EventEnvelope};
 use crate::infrastructure::repositories::LifecycleRepository;
 
 #[derive(Clone)]
 pub struct LifecycleService {
     repository: LifecycleRepository,
+    approval_service: ApprovalService,
 }
 
 /// Result of a transition request
@@ -27,47 +34,30 @@ pub enum TransitionResult {
     },
 }
 
-/// Approval level for transitions
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum ApprovalLevel {
-    /// No approval needed
-    None,
-    /// Supervisor (L1) approval
-    Supervisor,
-    /// Manager (L2) approval
-    Manager,
-}
-
 impl LifecycleService {
-    pub fn new(repository: LifecycleRepository) -> Self {
-        Self { repository }
-    }
-
-    /// Check if a transition requires approval
-    pub fn requires_approval(target_state: &AssetState) -> bool {
-        matches!(
-            target_state,
-            AssetState::Deployed
-                | AssetState::UnderConversion
-                | AssetState::Retired
-                | AssetState::Disposed
-                | AssetState::LostStolen
-        )
+    pub fn new(repository: LifecycleRepository, approval_service: ApprovalService) -> Self {
+        Self {
+            repository,
+            approval_service,
+        }
     }
 
-    /// Get the approval level required for a transition
-    pub fn get_approval_level(target_state: &AssetState) -> ApprovalLevel {
-        match target_state {
-            // Manager approval (L2) for critical transitions
-            AssetState::Retired
-            | AssetState::Disposed
-            | AssetState::UnderConversion
-            | AssetState::LostStolen => ApprovalLevel::Manager,
-            // Supervisor approval (L1) for deployment
-            AssetState::Deployed => ApprovalLevel::Supervisor,
-            // No approval for other states
-            _ => ApprovalLevel::None,
-        }
+    /// The workflow a transition into `target_state` needs, or None when it
+    /// needs no approval
+    async fn approval_workflow(
+        &self,
+        target_state: &AssetState,
+        category_id: Uuid,
+    ) -> DomainResult<Option<ApprovalWorkflow>> {
+        Ok(self
+            .approval_service
+            .resolve_workflow(
+                LIFECYCLE_RESOURCE_TYPE,
+                &target_state.approval_action(),
+                Some(category_id),
+            )
+            .await?
+            .filter(|wf| wf.requires_approval()))
     }
 
     /// Request a transition (may require approval)
@@ -108,27 +98,26 @@ impl LifecycleService {
             ));
         }
 
-        // Check if approval is required
-        if Self::requires_approval(&new_state) {
-            let approval_level = Self::get_approval_level(&new_state);
-            let level_num = match approval_level {
-                ApprovalLevel::Supervisor => 1,
-                ApprovalLevel::Manager => 2,
-                ApprovalLevel::None => 0,
-            };
-
+        // Check if a workflow requires approval
+        let category_id = self.repository.get_asset_category(asset_id).await?;
+        if self
+            .approval_workflow(&new_state, category_id)
+            .await?
+            .is_some()
+        {
             // Create approval request data
             let data = json!({
                 "asset_id": asset_id,
                 "from_state": current_state.as_str(),
                 "to_state": new_state.as_str(),
-                "reason": reason,
-                "approval_level": level_num
+                "reason": reason
             });
 
             Ok(TransitionRequestResult::RequiresApproval {
                 from_state: current_state.as_str().to_string(),
                 to_state: new_state.as_str().to_string(),
+                action_type: new_state.approval_action(),
+                category_id,
                 data,
                 requested_by,
             })
@@ -157,23 +146,35 @@ impl LifecycleService {
         reason: Option<String>,
         performed_by: Option<Uuid>,
     ) -> DomainResult<LifecycleHistory> {
-        // Update asset status
-        self.repository
-            .update_asset_status(asset_id, to_state.as_str())
-            .await?;
+        let event = Self::state_changed(asset_id, from_state, to_state, &reason, performed_by);
 
-        // Record in history
-        let history = self
-            .repository
-            .record_transition(asset_id, from_state, to_state, reason, performed_by, None)
-            .await?;
+        // Update asset status and record it in history
+        self.repository
+            .transition(asset_id, from_state, to_state, reason, performed_by, event)
+            .await
+    }
 
-        Ok(history)
+    fn state_changed(
+        asset_id: Uuid,
+        from_state: &AssetState,
+        to_state: &AssetState,
+        reason: &Option<String>,
+        performed_by: Option<Uuid>,
+    ) -> EventEnvelope<AssetStateChanged> {
+        EventEnvelope::from_event(AssetStateChanged {
+            asset_id,
+            from_state: from_state.as_str().to_string(),
+            to_state: to_state.as_str().to_string(),
+            reason: reason.clone(),
+            performed_by,
+            occurred_at: Utc::now(),
+        })
     }
 
-    /// Execute an approved transition (called by approval service)
+    /// Execute an approved transition inside the approval's transaction
     pub async fn execute_approved_transition(
         &self,
+        tx: &mut Transaction<'_, Postgres>,
         asset_id: Uuid,
         from_state_str: &str,
         to_state_str: &str,
@@ -189,7 +190,7 @@ impl LifecycleService {
         })?;
 
         // Verify current state matches expected from_state
-        let current_status = self.repository.get_asset_status(asset_id).await?;
+        let current_status = LifecycleRepository::lock_asset_status(tx, asset_id).await?;
         if current_status != from_state_str {
             return Err(DomainError::business_rule(
                 "Lifecycle",
@@ -200,8 +201,18 @@ impl LifecycleService {
             ));
         }
 
-        self.execute_transition(asset_id, &from_state, &to_state, reason, Some(approved_by))
-            .await
+        let event =
+            Self::state_changed(asset_id, &from_state, &to_state, &reason, Some(approved_by));
+        LifecycleRepository::transition_in(
+            tx,
+            asset_id,
+            &from_state,
+            &to_state,
+            reason,
+            Some(approved_by),
+            event,
+        )
+        .await
     }
 
     /// Legacy method - Transition an asset to a new state with validation (direct execution)
@@ -256,26 +267,20 @@ impl LifecycleService {
             DomainError::bad_request(&format!("Invalid state: {}", current_status))
         })?;
 
-        Ok(current_state
-            .valid_transitions()
-            .into_iter()
-            .map(|s| {
-                let requires_approval = Self::requires_approval(&s);
-                let approval_level = Self::get_approval_level(&s);
-                StateInfoWithApproval {
-                    value: s.as_str().to_string(),
-                    label: s.display_name().to_string(),
-                    color: s.color().to_string(),
-                    is_terminal: s.is_terminal(),
-                    requires_approval,
-                    approval_level: match approval_level {
-                        ApprovalLevel::None => 0,
-                        ApprovalLevel::Supervisor => 1,
-                        ApprovalLevel::Manager => 2,
-                    },
-                }
-            })
-            .collect())
+        let category_id = self.repository.get_asset_category(asset_id).await?;
+        let mut transitions = Vec::new();
+        for s in current_state.valid_transitions() {
+            let workflow = self.approval_workflow(&s, category_id).await?;
+            transitions.push(StateInfoWithApproval {
+                value: s.as_str().to_string(),
+                label: s.display_name().to_string(),
+                color: s.color().to_string(),
+                is_terminal: s.is_terminal(),
+                requires_approval: workflow.is_some(),
+                approval_level: workflow.map_or(0, |wf| wf.level_numbers().len() as i32),
+            });
+        }
+        Ok(transitions)
     }
 
     /// Get valid transitions for an asset
@@ -343,6 +348,8 @@ pub enum TransitionRequestResult {
     RequiresApproval {
         from_state: String,
         to_state: String,
+        action_type: String,
+        category_id: Uuid,
         data: serde_json::Value,
         requested_by: Uuid,
     },
@@ -365,5 +372,6 @@ pub struct StateInfoWithApproval {
     pub color: String,
     pub is_terminal: bool,
     pub requires_approval: bool,
+    /// Number of approval levels of the workflow; 0 without approval
     pub approval_level: i32,
 }
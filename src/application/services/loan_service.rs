@@ -1,11 +1,18 @@
 //! Loan Service
 
 use chrono::Utc;
+use sqlx::{Postgres, Transaction};
+use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::application::dto::CreateLoanRequest;
-use crate::domain::entities::{Loan, LoanStatus};
+use crate::application::services::{
+    ApprovalService, ApprovalSubmission, DocumentNumberService, RbacService,
+};
+use crate::domain::entities::{DataScope, Loan, LoanStatus, NumberingScope, UserClaims, DOC_LOAN};
 use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::events::{EventEnvelope, LoanCheckedOut};
+use crate::infrastructure::messaging::{NotificationMessage, WebSocketManager};
 use crate::infrastructure::repositories::{AssetRepository, LoanRepository};
 
 #[derive(Clone)]
@@ -13,6 +20,10 @@ pub struct LoanService {
     loan_repo: LoanRepository,
     asset_repo: AssetRepository,
     notification_service: crate::application::services::NotificationService,
+    approval_service: ApprovalService,
+    ws_manager: Arc<WebSocketManager>,
+    document_numbers: DocumentNumberService,
+    rbac_service: RbacService,
 }
 
 impl LoanService {
@@ -20,16 +31,28 @@ impl LoanService {
         loan_repo: LoanRepository,
         asset_repo: AssetRepository,
         notification_service: crate::application::services::NotificationService,
+        approval_service: ApprovalService,
+        ws_manager: Arc<WebSocketManager>,
+        document_numbers: DocumentNumberService,
+        rbac_service: RbacService,
     ) -> Self {
         Self {
             loan_repo,
             asset_repo,
             notification_service,
+            approval_service,
+            ws_manager,
+            document_numbers,
+            rbac_service,
         }
     }
 
     /// Create loan request
-    pub async fn create(&self, request: CreateLoanRequest) -> DomainResult<Loan> {
+    pub async fn create(
+        &self,
+        request: CreateLoanRequest,
+        requested_by: Uuid,
+    ) -> DomainResult<Loan> {
         // Check if asset exists and is available
         let asset = self
             .asset_repo
@@ -48,7 +71,12 @@ impl LoanService {
             ));
         }
 
+        let loan_number = self
+            .document_numbers
+            .next_number(DOC_LOAN, NumberingScope::asset(asset.id))
+            .await?;
         let mut loan = Loan::new(
+            loan_number,
             request.asset_id,
             request.borrower_id,
             request.employee_id,
@@ -66,9 +94,23 @@ impl LoanService {
                     message: e.to_string(),
                 })?;
 
-        // Optional: Notify admins here
-
-        Ok(created_loan)
+        // Route through the configured approval workflow
+        let submission = self
+            .approval_service
+            .create_request(
+                "loan",
+                created_loan.id,
+                "CREATE",
+                requested_by,
+                serde_json::to_value(&created_loan).ok(),
+                Some(asset.category_id),
+            )
+            .await?;
+
+        match submission {
+            ApprovalSubmission::Pending(_) => Ok(created_loan),
+            ApprovalSubmission::NotRequired => self.approve_as(created_loan.id, requested_by).await,
+        }
     }
 
     /// Get loan by ID
@@ -83,11 +125,48 @@ impl LoanService {
             .ok_or_else(|| DomainError::not_found("Loan", id))
     }
 
-    /// List loans
-    pub async fn list(&self, page: i64, per_page: i64) -> DomainResult<Vec<Loan>> {
+    /// Get loan by ID within the data scope of the caller
+    pub async fn get_in_scope(&self, id: Uuid, claims: &UserClaims) -> DomainResult<Loan> {
+        self.ensure_in_scope(id, claims, "loan.read").await?;
+        self.get_by_id(id).await
+    }
+
+    /// Loans outside the caller's data scope of `permission` are reported as not found
+    async fn ensure_in_scope(
+        &self,
+        id: Uuid,
+        claims: &UserClaims,
+        permission: &str,
+    ) -> DomainResult<()> {
+        let scope = self.rbac_service.data_scope(claims, permission).await?;
+        if scope.all_records {
+            return Ok(());
+        }
+        let visible = self
+            .loan_repo
+            .in_data_scope(id, &scope)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        if !visible {
+            return Err(DomainError::not_found("Loan", id));
+        }
+        Ok(())
+    }
+
+    /// List loans within the caller's organization and data scope
+    pub async fn list(
+        &self,
+        page: i64,
+        per_page: i64,
+        claims: &UserClaims,
+    ) -> DomainResult<Vec<Loan>> {
+        let scope = self.rbac_service.data_scope(claims, "loan.read").await?;
         let offset = (page - 1) * per_page;
         self.loan_repo
-            .list(per_page, offset)
+            .list(per_page, offset, claims.organization_id(), &scope)
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
@@ -95,10 +174,28 @@ impl LoanService {
             })
     }
 
-    /// List overdue loans
-    pub async fn list_overdue(&self) -> DomainResult<Vec<Loan>> {
+    /// Loan requests the caller may approve
+    pub async fn list_requested(&self, claims: &UserClaims) -> DomainResult<Vec<Loan>> {
+        let scope = self.rbac_service.data_scope(claims, "loan.approve").await?;
+        let loans = self
+            .loan_repo
+            .list(100, 0, claims.organization_id(), &scope)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        Ok(loans
+            .into_iter()
+            .filter(|l| l.status == LoanStatus::Requested.as_str())
+            .collect())
+    }
+
+    /// List overdue loans within the caller's organization and data scope
+    pub async fn list_overdue(&self, claims: &UserClaims) -> DomainResult<Vec<Loan>> {
+        let scope = self.rbac_service.data_scope(claims, "loan.read").await?;
         self.loan_repo
-            .list_overdue()
+            .list_overdue(claims.organization_id(), &scope)
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
@@ -106,26 +203,30 @@ impl LoanService {
             })
     }
 
-    /// List loans by user
-    pub async fn list_by_user(&self, user_id: Uuid) -> DomainResult<Vec<Loan>> {
-        self.loan_repo.list_by_borrower(user_id).await.map_err(|e| {
-            DomainError::ExternalServiceError {
+    /// List loans of a borrower within the caller's organization and data scope
+    pub async fn list_by_user(
+        &self,
+        user_id: Uuid,
+        claims: &UserClaims,
+    ) -> DomainResult<Vec<Loan>> {
+        let scope = self.rbac_service.data_scope(claims, "loan.read").await?;
+        self.loan_repo
+            .list_by_borrower(user_id, claims.organization_id(), &scope)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
-            }
-        })
+            })
     }
 
-    /// Approve loan
-    pub async fn approve(&self, id: Uuid, approver_id: Uuid) -> DomainResult<Loan> {
-        let loan = self.get_by_id(id).await?;
+    /// Approve a loan within the caller's data scope
+    pub async fn approve(&self, id: Uuid, claims: &UserClaims) -> DomainResult<Loan> {
+        self.ensure_in_scope(id, claims, "loan.approve").await?;
+        self.approve_as(id, claims.user_id()).await
+    }
 
-        if loan.status != LoanStatus::Requested.as_str() {
-            return Err(DomainError::business_rule(
-                "loan_status",
-                "Can only approve loans with 'requested' status",
-            ));
-        }
+    async fn approve_as(&self, id: Uuid, approver_id: Uuid) -> DomainResult<Loan> {
+        self.get_requested(id).await?;
 
         self.loan_repo.approve(id, approver_id).await.map_err(|e| {
             DomainError::ExternalServiceError {
@@ -135,11 +236,52 @@ impl LoanService {
         })?;
 
         let updated_loan = self.get_by_id(id).await?;
+        self.notify_approved(&updated_loan).await;
+        Ok(updated_loan)
+    }
 
-        // Notify Borrower
+    /// Approve a loan inside an approval's transaction; the borrower is told
+    /// through `notify_approved` once it commits
+    pub async fn approve_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        approver_id: Uuid,
+    ) -> DomainResult<Loan> {
+        let mut loan = self.get_requested(id).await?;
+
+        let approved = LoanRepository::approve_with(&mut **tx, id, approver_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        if !approved {
+            return Err(DomainError::conflict("Loan was updated concurrently"));
+        }
+
+        loan.status = LoanStatus::Approved.as_str().to_string();
+        loan.approver_id = Some(approver_id);
+        Ok(loan)
+    }
+
+    async fn get_requested(&self, id: Uuid) -> DomainResult<Loan> {
+        let loan = self.get_by_id(id).await?;
+
+        if loan.status != LoanStatus::Requested.as_str() {
+            return Err(DomainError::business_rule(
+                "loan_status",
+                "Can only approve loans with 'requested' status",
+            ));
+        }
+        Ok(loan)
+    }
+
+    /// Tell the borrower their loan was approved
+    pub async fn notify_approved(&self, loan: &Loan) {
         let asset = self
             .asset_repo
-            .find_by_id(updated_loan.asset_id)
+            .find_by_id(loan.asset_id)
             .await
             .ok()
             .flatten();
@@ -147,18 +289,27 @@ impl LoanService {
             .map(|a| a.name)
             .unwrap_or_else(|| "Unknown Asset".to_string());
 
-        if let Some(borrower_id) = updated_loan.borrower_id {
+        if let Some(borrower_id) = loan.borrower_id {
             let _ = self
                 .notification_service
-                .notify_loan_approved(borrower_id, &asset_name, updated_loan.id)
+                .notify_loan_approved(borrower_id, &asset_name, loan.id, loan.asset_id)
                 .await;
         }
+    }
 
-        Ok(updated_loan)
+    /// Reject a loan request within the caller's data scope
+    pub async fn reject(
+        &self,
+        id: Uuid,
+        reason: Option<String>,
+        claims: &UserClaims,
+    ) -> DomainResult<Loan> {
+        self.ensure_in_scope(id, claims, "loan.approve").await?;
+        self.apply_reject(id, reason).await
     }
 
-    /// Reject loan request
-    pub async fn reject(&self, id: Uuid, reason: Option<String>) -> DomainResult<Loan> {
+    /// Reject a loan request without scope checks (a rejected approval request)
+    pub async fn apply_reject(&self, id: Uuid, reason: Option<String>) -> DomainResult<Loan> {
         let loan = self.get_by_id(id).await?;
 
         if loan.status != LoanStatus::Requested.as_str() {
@@ -209,13 +360,15 @@ impl LoanService {
         Ok(updated_loan)
     }
 
-    /// Checkout loan
+    /// Check out a loan within the caller's data scope
     pub async fn checkout(
         &self,
         id: Uuid,
-        checked_out_by: Uuid,
         condition: &str,
+        claims: &UserClaims,
     ) -> DomainResult<Loan> {
+        self.ensure_in_scope(id, claims, "loan.checkout").await?;
+        let checked_out_by = claims.user_id();
         let loan = self.get_by_id(id).await?;
 
         if !loan.can_checkout() {
@@ -225,13 +378,30 @@ impl LoanService {
             ));
         }
 
-        self.loan_repo
-            .checkout(id, checked_out_by, condition)
+        let event = EventEnvelope::from_event(LoanCheckedOut {
+            loan_id: loan.id,
+            loan_number: loan.loan_number.clone(),
+            asset_id: loan.asset_id,
+            borrower_id: loan.borrower_id,
+            employee_id: loan.employee_id,
+            checked_out_by,
+            occurred_at: Utc::now(),
+        })
+        .with_user(checked_out_by);
+        let checked_out = self
+            .loan_repo
+            .checkout(id, checked_out_by, condition, event)
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
             })?;
+        if !checked_out {
+            return Err(DomainError::business_rule(
+                "loan_checkout",
+                "Loan cannot be checked out in current state",
+            ));
+        }
 
         // Update asset status
         let _ = self.asset_repo.update_status(loan.asset_id, "in_use").await;
@@ -239,13 +409,15 @@ impl LoanService {
         self.get_by_id(id).await
     }
 
-    /// Return/checkin loan
+    /// Return/checkin a loan within the caller's data scope
     pub async fn checkin(
         &self,
         id: Uuid,
-        checked_in_by: Uuid,
         condition: &str,
+        claims: &UserClaims,
     ) -> DomainResult<Loan> {
+        self.ensure_in_scope(id, claims, "loan.checkin").await?;
+        let checked_in_by = claims.user_id();
         let loan = self.get_by_id(id).await?;
 
         if !loan.can_return() {
@@ -284,9 +456,92 @@ impl LoanService {
             })
     }
 
-    /// Check and update overdue loans (Background Task)
+    /// Notify borrowers of overdue loans (Background Task)
     pub async fn check_overdue_loans(&self) -> DomainResult<()> {
-        // Placeholder for background logic
+        let overdue = self
+            .loan_repo
+            .list_overdue(None, &DataScope::unrestricted())
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+
+        let today = Utc::now().date_naive();
+        for loan in overdue {
+            if let Some(borrower_id) = loan.borrower_id {
+                let asset_name = loan
+                    .asset_name
+                    .clone()
+                    .unwrap_or_else(|| "Unknown Asset".to_string());
+                let days_overdue = (today - loan.expected_return_date).num_days();
+
+                self.ws_manager
+                    .send_to_user(
+                        borrower_id,
+                        &NotificationMessage::new(
+                            "LOAN_OVERDUE",
+                            serde_json::json!({
+                                "id": loan.id,
+                                "loan_number": loan.loan_number,
+                                "asset_id": loan.asset_id,
+                                "asset_name": asset_name,
+                                "expected_return_date": loan.expected_return_date,
+                                "days_overdue": days_overdue,
+                            }),
+                        ),
+                    )
+                    .await;
+
+                if let Err(e) = self
+                    .notification_service
+                    .notify_loan_overdue(
+                        borrower_id,
+                        &asset_name,
+                        days_overdue,
+                        loan.id,
+                        loan.asset_id,
+                    )
+                    .await
+                {
+                    tracing::warn!("Overdue notice for loan {} failed: {}", loan.loan_number, e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remind borrowers of loans due back today (Background Task)
+    pub async fn check_due_loans(&self) -> DomainResult<()> {
+        let today = Utc::now().date_naive();
+        let due = self.loan_repo.list_due_on(today).await.map_err(|e| {
+            DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            }
+        })?;
+
+        for loan in due {
+            if let Some(borrower_id) = loan.borrower_id {
+                self.ws_manager
+                    .send_to_user(
+                        borrower_id,
+                        &NotificationMessage::new(
+                            "LOAN_DUE",
+                            serde_json::json!({
+                                "id": loan.id,
+                                "loan_number": loan.loan_number,
+                                "asset_id": loan.asset_id,
+                                "asset_name": loan.asset_name,
+                                "expected_return_date": loan.expected_return_date,
+                            }),
+                        ),
+                    )
+                    .await;
+            }
+        }
+
         Ok(())
     }
 }
@@ -5,7 +5,7 @@ use serde::Serialize;
 use uuid::Uuid;
 
 use crate::application::dto::{CreateMaintenanceRequest, UpdateMaintenanceRequest};
-use crate::application::services::ApprovalService;
+use crate::application::services::{ApprovalService, ApprovalSubmission};
 use crate::domain::entities::{MaintenanceRecord, MaintenanceSummary};
 use crate::domain::errors::{DomainError, DomainResult};
 use crate::infrastructure::repositories::{AssetRepository, MaintenanceRepository};
@@ -97,10 +97,13 @@ impl MaintenanceService {
         record.created_by = Some(user_id);
 
         // Check if cost exceeds threshold and user is not Manager/SuperAdmin
-        let needs_approval = if let Some(cost) = request.cost {
-            cost > COST_APPROVAL_THRESHOLD && role_level > 2
-        } else {
-            false
+        let needs_approval = match request.cost {
+            Some(cost) if cost > COST_APPROVAL_THRESHOLD && role_level > 2 => {
+                self.approval_service
+                    .requires_approval("WorkOrder", "HIGH_COST", None)
+                    .await?
+            }
+            _ => false,
         };
 
         if needs_approval {
@@ -120,7 +123,7 @@ impl MaintenanceService {
                 DomainError::validation("request_data", &format!("Failed to serialize: {}", e))
             })?;
 
-            let approval_request = self
+            let submission = self
                 .approval_service
                 .create_request(
                     "WorkOrder",
@@ -128,12 +131,16 @@ impl MaintenanceService {
                     "HIGH_COST",
                     user_id,
                     Some(data_json),
+                    None,
                 )
                 .await?;
 
-            return Ok(MaintenanceOperationResult::PendingApproval(
-                approval_request,
-            ));
+            return Ok(match submission {
+                ApprovalSubmission::Pending(approval_request) => {
+                    MaintenanceOperationResult::PendingApproval(*approval_request)
+                }
+                ApprovalSubmission::NotRequired => MaintenanceOperationResult::Success(created),
+            });
         }
 
         let created = self.repository.create(&record).await.map_err(|e| {
@@ -1,43 +1,71 @@
 //! Application Services
 
 pub mod analytics_service;
+pub mod api_key_service;
+pub mod approval_executor;
 pub mod approval_service; // Added
 pub mod asset_service;
 pub mod audit_service; // Added
 pub mod auth_service;
 pub mod billing_service;
+pub mod budget_service;
 pub mod category_service;
 pub mod client_service;
 pub mod conversion_service;
+pub mod depreciation_service;
+pub mod document_number_service;
+pub mod document_service;
 pub mod employee_service;
+pub mod insurance_service;
 pub mod lifecycle_service;
 pub mod loan_service;
 pub mod maintenance_service;
 pub mod notification_service;
+pub mod organization_service;
+pub mod outbox_relay;
+pub mod password_service;
+pub mod preventive_maintenance_service;
 pub mod rbac_service;
 pub mod rental_service;
 pub mod sensor_service;
+pub mod specification_service;
 pub mod timesheet_service;
+pub mod valuation_service;
+pub mod webhook_service;
 pub mod work_order_service;
 
 pub use analytics_service::*;
+pub use api_key_service::*;
+pub use approval_executor::*;
 pub use approval_service::*; // Added
 pub use asset_service::*;
 pub use audit_service::*;
 pub use auth_service::*;
 pub use billing_service::*;
+pub use budget_service::*;
 pub use category_service::*;
 pub use client_service::*;
 pub use conversion_service::*;
+pub use depreciation_service::*;
+pub use document_number_service::*;
+pub use document_service::*;
 pub use employee_service::*;
+pub use insurance_service::*;
 pub use lifecycle_service::*;
 pub use loan_service::*;
 pub use maintenance_service::*;
 pub use notification_service::*;
+pub use organization_service::*;
+pub use outbox_relay::*;
+pub use password_service::*;
+pub use preventive_maintenance_service::*;
 pub use rbac_service::*;
 pub use rental_service::*;
 pub use sensor_service::*;
+pub use specification_service::*;
 pub use timesheet_service::*;
+pub use valuation_service::*;
+pub use webhook_service::*;
 pub use work_order_service::*;
 pub mod data_service;
 pub use data_service::*;
@@ -1,18 +1,112 @@
 //! Notification Service
+//!
+//! Renders notification templates for events and fans them out to the channels a
+//! user accepts. Every message is logged per channel in `notification_deliveries`;
+//! transient failures are retried with backoff by the scheduler. Users on an
+//! hourly/daily/weekly digest get their deliveries queued and combined into one
+//! message per channel by the scheduler.
 
+use chrono::Utc;
+use serde_json::{json, Value as JsonValue};
 use uuid::Uuid;
 
+use crate::application::dto::{
+    CreateNotificationTemplateRequest, NotificationDeliveryParams,
+    UpdateNotificationPreferenceRequest, UpdateNotificationTemplateRequest,
+};
+use crate::domain::entities::{
+    AssetDocument, Insurance, NotificationDelivery, NotificationDigest, NotificationPreference,
+    NotificationTemplate, RenderedMessage, CHANNEL_EMAIL, CHANNEL_IN_APP, DELIVERY_DIGESTED,
+    DELIVERY_FAILED, DELIVERY_PENDING, DELIVERY_QUEUED, DELIVERY_RETRYING, DELIVERY_SENT,
+    DELIVERY_SKIPPED, DIGEST_FREQUENCIES, DIGEST_IMMEDIATE, EVENT_NOTIFICATION_DIGEST,
+    EVENT_PASSWORD_RESET, NOTIFICATION_CHANNELS, REDACTED_VALUE,
+};
 use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::notifications::{NotificationChannels, OutboundMessage};
 use crate::infrastructure::repositories::{Notification, NotificationRepository};
 
+/// Attempts per delivery before it is marked failed
+const DEFAULT_MAX_ATTEMPTS: i32 = 5;
+
+/// Deliveries retried per scheduler run
+const RETRY_BATCH_SIZE: i64 = 50;
+
+/// Something that happened which a user should hear about
+#[derive(Debug, Clone)]
+pub struct NotificationEvent {
+    pub event_type: String,
+    pub user_id: Uuid,
+    pub variables: JsonValue,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<Uuid>,
+    /// Asset the event concerns, used to group digests
+    pub asset_id: Option<Uuid>,
+    /// Variables that are sent but never stored (see `with_secrets`)
+    pub secrets: Vec<String>,
+}
+
+impl NotificationEvent {
+    pub fn new(event_type: &str, user_id: Uuid, variables: JsonValue) -> Self {
+        Self {
+            event_type: event_type.to_string(),
+            user_id,
+            variables,
+            entity_type: None,
+            entity_id: None,
+            asset_id: None,
+            secrets: Vec::new(),
+        }
+    }
+
+    /// Attach the entity the event is about
+    pub fn about(mut self, entity_type: &str, entity_id: Uuid) -> Self {
+        self.entity_type = Some(entity_type.to_string());
+        self.entity_id = Some(entity_id);
+        self
+    }
+
+    pub fn for_asset(mut self, asset_id: Uuid) -> Self {
+        self.asset_id = Some(asset_id);
+        self
+    }
+
+    /// Mark variables as secret. The delivery log keeps a redacted copy of the
+    /// message, the delivery bypasses digests and it is sent once, without retries.
+    pub fn with_secrets(mut self, names: &[&str]) -> Self {
+        self.secrets = names.iter().map(|n| n.to_string()).collect();
+        self
+    }
+
+    fn has_secrets(&self) -> bool {
+        !self.secrets.is_empty()
+    }
+
+    /// The variables as they may be stored, with secret values redacted
+    fn stored_variables(&self) -> JsonValue {
+        let mut variables = self.variables.clone();
+        if let Some(map) = variables.as_object_mut() {
+            for name in &self.secrets {
+                if let Some(value) = map.get_mut(name) {
+                    *value = JsonValue::String(REDACTED_VALUE.to_string());
+                }
+            }
+        }
+        variables
+    }
+}
+
 #[derive(Clone)]
 pub struct NotificationService {
     repository: NotificationRepository,
+    channels: NotificationChannels,
 }
 
 impl NotificationService {
-    pub fn new(repository: NotificationRepository) -> Self {
-        Self { repository }
+    pub fn new(repository: NotificationRepository, channels: NotificationChannels) -> Self {
+        Self {
+            repository,
+            channels,
+        }
     }
 
     /// Create a new notification
@@ -122,22 +216,738 @@ impl NotificationService {
             })
     }
 
+    // ==================== DISPATCH ====================
+
+    /// Render the active templates for an event and deliver them.
+    ///
+    /// In-app messages are written before returning; other channels are sent in the
+    /// background. Users on a digest get the deliveries queued instead. Returns the
+    /// delivery log entries that were created.
+    pub async fn dispatch(
+        &self,
+        event: NotificationEvent,
+    ) -> DomainResult<Vec<NotificationDelivery>> {
+        let templates = self.active_templates(&event.event_type).await?;
+        self.dispatch_templates(&templates, &event).await
+    }
+
+    async fn active_templates(&self, event_type: &str) -> DomainResult<Vec<NotificationTemplate>> {
+        self.repository
+            .find_active_templates_by_event(event_type)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    async fn dispatch_templates(
+        &self,
+        templates: &[NotificationTemplate],
+        event: &NotificationEvent,
+    ) -> DomainResult<Vec<NotificationDelivery>> {
+        let mut deliveries = Vec::new();
+        let mut email: Option<Option<String>> = None;
+
+        for template in templates {
+            let preference = self
+                .find_preference(event.user_id, Some(template.id), &event.event_type)
+                .await?;
+            let rendered = template.render(&event.variables);
+            let (stored, digest) = if event.has_secrets() {
+                (template.render(&event.stored_variables()), None)
+            } else {
+                (
+                    rendered.clone(),
+                    preference.as_ref().and_then(|p| p.digest()),
+                )
+            };
+
+            for channel in template.channels() {
+                if !NotificationPreference::permits(preference.as_ref(), &channel) {
+                    continue;
+                }
+
+                let recipient = if channel == CHANNEL_EMAIL {
+                    if email.is_none() {
+                        email = Some(
+                            self.repository
+                                .find_user_email(event.user_id)
+                                .await
+                                .map_err(|e| DomainError::ExternalServiceError {
+                                    service: "database".to_string(),
+                                    message: e.to_string(),
+                                })?,
+                        );
+                    }
+                    email.clone().flatten()
+                } else {
+                    None
+                };
+
+                let delivery = self
+                    .log_delivery(
+                        Some(template.id),
+                        event,
+                        &channel,
+                        recipient,
+                        &stored,
+                        digest,
+                    )
+                    .await?;
+
+                // The log holds the redacted copy; the channel gets the real message
+                let outgoing = if event.has_secrets() {
+                    NotificationDelivery {
+                        subject: Some(rendered.subject.clone()),
+                        body: rendered.body.clone(),
+                        payload: Some(event.variables.clone()),
+                        ..delivery.clone()
+                    }
+                } else {
+                    delivery.clone()
+                };
+
+                if delivery.status == DELIVERY_QUEUED {
+                    deliveries.push(delivery);
+                } else if channel == CHANNEL_IN_APP {
+                    let sent = self.attempt_delivery(outgoing).await?;
+                    deliveries.push(sent);
+                } else {
+                    let service = self.clone();
+                    let pending = outgoing;
+                    tokio::spawn(async move {
+                        if let Err(e) = service.attempt_delivery(pending).await {
+                            tracing::error!("Notification delivery failed to record: {}", e);
+                        }
+                    });
+                    deliveries.push(delivery);
+                }
+            }
+        }
+
+        Ok(deliveries)
+    }
+
+    async fn find_preference(
+        &self,
+        user_id: Uuid,
+        template_id: Option<Uuid>,
+        event_type: &str,
+    ) -> DomainResult<Option<NotificationPreference>> {
+        self.repository
+            .find_preference(user_id, template_id, event_type)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    async fn log_delivery(
+        &self,
+        template_id: Option<Uuid>,
+        event: &NotificationEvent,
+        channel: &str,
+        recipient: Option<String>,
+        rendered: &RenderedMessage,
+        digest: Option<&str>,
+    ) -> DomainResult<NotificationDelivery> {
+        self.insert_delivery(NotificationDelivery {
+            id: Uuid::new_v4(),
+            notification_id: None,
+            template_id,
+            user_id: event.user_id,
+            event_type: event.event_type.clone(),
+            channel: channel.to_string(),
+            recipient,
+            subject: Some(rendered.subject.clone()),
+            body: rendered.body.clone(),
+            payload: Some(event.stored_variables()),
+            entity_type: event.entity_type.clone(),
+            entity_id: event.entity_id,
+            asset_id: event.asset_id,
+            status: match digest {
+                Some(_) => DELIVERY_QUEUED,
+                None => DELIVERY_PENDING,
+            }
+            .to_string(),
+            attempts: 0,
+            // A redacted message cannot be sent again
+            max_attempts: if event.has_secrets() {
+                1
+            } else {
+                DEFAULT_MAX_ATTEMPTS
+            },
+            last_error: None,
+            next_attempt_at: None,
+            sent_at: None,
+            digest_frequency: digest.map(str::to_string),
+            digest_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        })
+        .await
+    }
+
+    async fn insert_delivery(
+        &self,
+        delivery: NotificationDelivery,
+    ) -> DomainResult<NotificationDelivery> {
+        self.repository
+            .create_delivery(&delivery)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    /// Send one delivery through its channel adapter and record the outcome
+    async fn attempt_delivery(
+        &self,
+        delivery: NotificationDelivery,
+    ) -> DomainResult<NotificationDelivery> {
+        let adapter = match self.channels.get(&delivery.channel) {
+            Some(adapter) => adapter,
+            None => {
+                return self
+                    .record_attempt(
+                        delivery.id,
+                        DELIVERY_SKIPPED,
+                        None,
+                        Some("No adapter configured for this channel"),
+                        None,
+                    )
+                    .await
+            }
+        };
+
+        let message = OutboundMessage {
+            delivery_id: delivery.id,
+            user_id: delivery.user_id,
+            template_id: delivery.template_id,
+            event_type: delivery.event_type.clone(),
+            channel: delivery.channel.clone(),
+            recipient: delivery.recipient.clone(),
+            subject: delivery.subject.clone().unwrap_or_default(),
+            body: delivery.body.clone(),
+            data: delivery.payload.clone(),
+            entity_type: delivery.entity_type.clone(),
+            entity_id: delivery.entity_id,
+        };
+
+        match adapter.send(&message).await {
+            Ok(notification_id) => {
+                self.record_attempt(delivery.id, DELIVERY_SENT, notification_id, None, None)
+                    .await
+            }
+            Err(e) => {
+                let attempts = delivery.attempts + 1;
+                let error = e.to_string();
+                if e.is_retryable() && attempts < delivery.max_attempts {
+                    let next = Utc::now() + NotificationDelivery::retry_delay(attempts);
+                    self.record_attempt(
+                        delivery.id,
+                        DELIVERY_RETRYING,
+                        None,
+                        Some(&error),
+                        Some(next),
+                    )
+                    .await
+                } else {
+                    self.record_attempt(delivery.id, DELIVERY_FAILED, None, Some(&error), None)
+                        .await
+                }
+            }
+        }
+    }
+
+    async fn record_attempt(
+        &self,
+        id: Uuid,
+        status: &str,
+        notification_id: Option<Uuid>,
+        error: Option<&str>,
+        next_attempt_at: Option<chrono::DateTime<Utc>>,
+    ) -> DomainResult<NotificationDelivery> {
+        self.repository
+            .record_delivery_attempt(id, status, notification_id, error, next_attempt_at)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    /// Retry deliveries whose backoff has elapsed (Background Task)
+    pub async fn retry_due_deliveries(&self) -> DomainResult<usize> {
+        let due = self
+            .repository
+            .list_due_deliveries(RETRY_BATCH_SIZE)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+
+        let mut sent = 0;
+        for delivery in due {
+            if self.attempt_delivery(delivery).await?.status == DELIVERY_SENT {
+                sent += 1;
+            }
+        }
+        Ok(sent)
+    }
+
+    /// Retry a single delivery now, regardless of its backoff
+    pub async fn retry_delivery(&self, id: Uuid) -> DomainResult<NotificationDelivery> {
+        let delivery = self
+            .repository
+            .find_delivery(id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("NotificationDelivery", id))?;
+
+        if delivery.status == DELIVERY_SENT {
+            return Err(DomainError::business_rule(
+                "delivery_status",
+                "Delivery was already sent",
+            ));
+        }
+        if delivery.status == DELIVERY_QUEUED || delivery.status == DELIVERY_DIGESTED {
+            return Err(DomainError::business_rule(
+                "delivery_status",
+                "Delivery belongs to a digest",
+            ));
+        }
+        if delivery.is_redacted() {
+            return Err(DomainError::business_rule(
+                "delivery_status",
+                "Delivery held a secret that was not stored; trigger a new message instead",
+            ));
+        }
+
+        self.attempt_delivery(delivery).await
+    }
+
+    // ==================== DIGESTS ====================
+
+    /// Combine queued deliveries of one frequency into a digest per user and channel
+    /// (Background Task). Returns the number of digests created.
+    pub async fn send_digests(&self, frequency: &str) -> DomainResult<usize> {
+        if frequency == DIGEST_IMMEDIATE || !DIGEST_FREQUENCIES.contains(&frequency) {
+            return Err(DomainError::validation(
+                "frequency",
+                "Must be one of: hourly, daily, weekly",
+            ));
+        }
+
+        let queued = self
+            .repository
+            .list_queued_deliveries(frequency)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+
+        // Rows are ordered by user and channel, so each batch is a contiguous run
+        let mut batches: Vec<Vec<NotificationDelivery>> = Vec::new();
+        for item in queued {
+            match batches.last_mut() {
+                Some(batch)
+                    if batch[0].user_id == item.user_id && batch[0].channel == item.channel =>
+                {
+                    batch.push(item)
+                }
+                _ => batches.push(vec![item]),
+            }
+        }
+
+        let template = self
+            .active_templates(EVENT_NOTIFICATION_DIGEST)
+            .await?
+            .into_iter()
+            .next();
+
+        let mut created = 0;
+        for batch in batches {
+            if self
+                .send_digest(frequency, template.as_ref(), &batch)
+                .await?
+                .is_some()
+            {
+                created += 1;
+            }
+        }
+        Ok(created)
+    }
+
+    /// Claim the items still queued, log the digest delivery and mark them as
+    /// included in one transaction, then send it on the items' channel. `None`
+    /// when another run (replica or retry) already took every item.
+    async fn send_digest(
+        &self,
+        frequency: &str,
+        template: Option<&NotificationTemplate>,
+        batch: &[NotificationDelivery],
+    ) -> DomainResult<Option<NotificationDelivery>> {
+        let db_error = |e: sqlx::Error| DomainError::ExternalServiceError {
+            service: "database".to_string(),
+            message: e.to_string(),
+        };
+
+        let ids: Vec<Uuid> = batch.iter().map(|i| i.id).collect();
+        let mut tx = self.repository.begin().await.map_err(db_error)?;
+        let items = NotificationRepository::claim_queued(&mut tx, &ids)
+            .await
+            .map_err(db_error)?;
+        if items.is_empty() {
+            return Ok(None);
+        }
+
+        let digest = NotificationDigest::build(frequency, &items);
+        let rendered = match template {
+            Some(t) => t.render(&digest.variables()),
+            None => digest.render(),
+        };
+        let first = &items[0];
+
+        let delivery = NotificationRepository::create_delivery_with(
+            &mut *tx,
+            &NotificationDelivery {
+                id: Uuid::new_v4(),
+                notification_id: None,
+                template_id: template.map(|t| t.id),
+                user_id: first.user_id,
+                event_type: EVENT_NOTIFICATION_DIGEST.to_string(),
+                channel: first.channel.clone(),
+                recipient: first.recipient.clone(),
+                subject: Some(rendered.subject),
+                body: rendered.body,
+                payload: serde_json::to_value(&digest).ok(),
+                entity_type: None,
+                entity_id: None,
+                asset_id: None,
+                status: DELIVERY_PENDING.to_string(),
+                attempts: 0,
+                max_attempts: DEFAULT_MAX_ATTEMPTS,
+                last_error: None,
+                next_attempt_at: None,
+                sent_at: None,
+                digest_frequency: Some(frequency.to_string()),
+                digest_id: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            },
+        )
+        .await
+        .map_err(db_error)?;
+
+        let claimed: Vec<Uuid> = items.iter().map(|i| i.id).collect();
+        NotificationRepository::mark_digested(&mut tx, delivery.id, &claimed)
+            .await
+            .map_err(db_error)?;
+        tx.commit().await.map_err(db_error)?;
+
+        self.attempt_delivery(delivery).await.map(Some)
+    }
+
+    pub async fn list_deliveries(
+        &self,
+        params: &NotificationDeliveryParams,
+    ) -> DomainResult<Vec<NotificationDelivery>> {
+        let per_page = params.per_page.unwrap_or(50).clamp(1, 200);
+        let offset = (params.page.unwrap_or(1).max(1) - 1) * per_page;
+
+        self.repository
+            .list_deliveries(
+                params.status.as_deref(),
+                params.channel.as_deref(),
+                params.user_id,
+                per_page,
+                offset,
+            )
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    // ==================== TEMPLATES ====================
+
+    pub async fn list_templates(&self) -> DomainResult<Vec<NotificationTemplate>> {
+        self.repository
+            .list_templates()
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    pub async fn get_template(&self, id: Uuid) -> DomainResult<NotificationTemplate> {
+        self.repository
+            .find_template(id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("NotificationTemplate", id))
+    }
+
+    fn validate_template(template: &NotificationTemplate) -> DomainResult<()> {
+        if template.code.trim().is_empty() {
+            return Err(DomainError::validation("code", "Required"));
+        }
+        if template.name.trim().is_empty() {
+            return Err(DomainError::validation("name", "Required"));
+        }
+        if template.event_type.trim().is_empty() {
+            return Err(DomainError::validation("event_type", "Required"));
+        }
+
+        let channels = template.channels();
+        if channels.is_empty() {
+            return Err(DomainError::validation(
+                "channels",
+                "At least one channel is required",
+            ));
+        }
+        if let Some(unknown) = channels
+            .iter()
+            .find(|c| !NOTIFICATION_CHANNELS.contains(&c.as_str()))
+        {
+            return Err(DomainError::validation(
+                "channels",
+                &format!(
+                    "Unknown channel '{}'. Valid channels: {}",
+                    unknown,
+                    NOTIFICATION_CHANNELS.join(", ")
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn map_template_error(e: sqlx::Error) -> DomainError {
+        match e {
+            sqlx::Error::Database(ref db) if db.code().as_deref() == Some("23505") => {
+                DomainError::conflict("A template with this code already exists")
+            }
+            sqlx::Error::Database(ref db) if db.code().as_deref() == Some("23503") => {
+                DomainError::conflict(
+                    "Template has been used for notifications; deactivate it instead",
+                )
+            }
+            _ => DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            },
+        }
+    }
+
+    pub async fn create_template(
+        &self,
+        request: CreateNotificationTemplateRequest,
+    ) -> DomainResult<NotificationTemplate> {
+        let template = NotificationTemplate {
+            id: Uuid::new_v4(),
+            code: request.code,
+            name: request.name,
+            subject_template: request.subject_template,
+            body_template: Some(request.body_template),
+            channels: Some(
+                request
+                    .channels
+                    .unwrap_or_else(|| vec![CHANNEL_IN_APP.to_string()]),
+            ),
+            event_type: request.event_type,
+            is_active: Some(request.is_active.unwrap_or(true)),
+            created_at: None,
+            updated_at: None,
+        };
+        Self::validate_template(&template)?;
+
+        self.repository
+            .create_template(&template)
+            .await
+            .map_err(Self::map_template_error)
+    }
+
+    pub async fn update_template(
+        &self,
+        id: Uuid,
+        request: UpdateNotificationTemplateRequest,
+    ) -> DomainResult<NotificationTemplate> {
+        let mut template = self.get_template(id).await?;
+
+        if let Some(v) = request.code {
+            template.code = v;
+        }
+        if let Some(v) = request.name {
+            template.name = v;
+        }
+        if let Some(v) = request.event_type {
+            template.event_type = v;
+        }
+        if let Some(v) = request.subject_template {
+            template.subject_template = Some(v);
+        }
+        if let Some(v) = request.body_template {
+            template.body_template = Some(v);
+        }
+        if let Some(v) = request.channels {
+            template.channels = Some(v);
+        }
+        if let Some(v) = request.is_active {
+            template.is_active = Some(v);
+        }
+        Self::validate_template(&template)?;
+
+        self.repository
+            .update_template(&template)
+            .await
+            .map_err(Self::map_template_error)
+    }
+
+    pub async fn delete_template(&self, id: Uuid) -> DomainResult<bool> {
+        self.repository
+            .delete_template(id)
+            .await
+            .map_err(Self::map_template_error)
+    }
+
+    /// Render a template with sample variables without sending anything
+    pub async fn preview_template(
+        &self,
+        id: Uuid,
+        variables: &JsonValue,
+    ) -> DomainResult<RenderedMessage> {
+        Ok(self.get_template(id).await?.render(variables))
+    }
+
+    // ==================== PREFERENCES ====================
+
+    pub async fn list_preferences(
+        &self,
+        user_id: Uuid,
+    ) -> DomainResult<Vec<NotificationPreference>> {
+        self.repository
+            .list_preferences(user_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    pub async fn update_preference(
+        &self,
+        user_id: Uuid,
+        request: UpdateNotificationPreferenceRequest,
+    ) -> DomainResult<NotificationPreference> {
+        if request.event_type.trim().is_empty() {
+            return Err(DomainError::validation("event_type", "Required"));
+        }
+        if let Some(freq) = &request.digest_frequency {
+            if !DIGEST_FREQUENCIES.contains(&freq.as_str()) {
+                return Err(DomainError::validation(
+                    "digest_frequency",
+                    &format!("Must be one of: {}", DIGEST_FREQUENCIES.join(", ")),
+                ));
+            }
+        }
+
+        let preference = NotificationPreference {
+            id: Uuid::new_v4(),
+            user_id,
+            template_id: None,
+            event_type: Some(request.event_type),
+            email_enabled: request.email_enabled,
+            push_enabled: request.push_enabled,
+            sms_enabled: request.sms_enabled,
+            in_app_enabled: request.in_app_enabled,
+            webhook_enabled: request.webhook_enabled,
+            digest_frequency: request.digest_frequency,
+            created_at: None,
+            updated_at: None,
+        };
+
+        self.repository
+            .upsert_preference(&preference)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
     // Helper methods for specific notification types
+
+    /// Dispatch through templates, or write the built-in in-app message when the
+    /// event has no active template (queued instead if the user is on a digest)
+    async fn notify(
+        &self,
+        event: NotificationEvent,
+        fallback_title: &str,
+        fallback_message: &str,
+    ) -> DomainResult<Vec<NotificationDelivery>> {
+        let templates = self.active_templates(&event.event_type).await?;
+        if templates.is_empty() {
+            let preference = self
+                .find_preference(event.user_id, None, &event.event_type)
+                .await?;
+            if let Some(digest) = preference.as_ref().and_then(|p| p.digest()) {
+                let rendered = RenderedMessage {
+                    subject: fallback_title.to_string(),
+                    body: fallback_message.to_string(),
+                };
+                let queued = self
+                    .log_delivery(None, &event, CHANNEL_IN_APP, None, &rendered, Some(digest))
+                    .await?;
+                return Ok(vec![queued]);
+            }
+
+            self.create(
+                event.user_id,
+                fallback_title,
+                fallback_message,
+                event.entity_type.as_deref(),
+                event.entity_id,
+            )
+            .await?;
+            return Ok(vec![]);
+        }
+        self.dispatch_templates(&templates, &event).await
+    }
+
     pub async fn notify_loan_approved(
         &self,
         user_id: Uuid,
         asset_name: &str,
         loan_id: Uuid,
-    ) -> DomainResult<Notification> {
-        self.create(
-            user_id,
+        asset_id: Uuid,
+    ) -> DomainResult<Vec<NotificationDelivery>> {
+        self.notify(
+            NotificationEvent::new(
+                "loan.approved",
+                user_id,
+                json!({ "asset_name": asset_name }),
+            )
+            .about("loan", loan_id)
+            .for_asset(asset_id),
             &format!("Loan Approved: {}", asset_name),
             &format!(
                 "Your loan request for {} has been approved. Please pick up the asset.",
                 asset_name
             ),
-            Some("loan"),
-            Some(loan_id),
         )
         .await
     }
@@ -148,16 +958,21 @@ impl NotificationService {
         asset_name: &str,
         days_overdue: i64,
         loan_id: Uuid,
-    ) -> DomainResult<Notification> {
-        self.create(
-            user_id,
+        asset_id: Uuid,
+    ) -> DomainResult<Vec<NotificationDelivery>> {
+        self.notify(
+            NotificationEvent::new(
+                "loan.overdue",
+                user_id,
+                json!({ "asset_name": asset_name, "days_overdue": days_overdue }),
+            )
+            .about("loan", loan_id)
+            .for_asset(asset_id),
             &format!("OVERDUE: {}", asset_name),
             &format!(
                 "Your loan for {} is {} days overdue. Please return immediately.",
                 asset_name, days_overdue
             ),
-            Some("loan"),
-            Some(loan_id),
         )
         .await
     }
@@ -168,16 +983,19 @@ impl NotificationService {
         wo_number: &str,
         asset_name: &str,
         wo_id: Uuid,
-    ) -> DomainResult<Notification> {
-        self.create(
-            technician_id,
+    ) -> DomainResult<Vec<NotificationDelivery>> {
+        self.notify(
+            NotificationEvent::new(
+                "workorder.assigned",
+                technician_id,
+                json!({ "wo_number": wo_number, "asset_name": asset_name }),
+            )
+            .about("work_order", wo_id),
             &format!("Work Order Assigned: {}", wo_number),
             &format!(
                 "You have been assigned work order {} for {}.",
                 wo_number, asset_name
             ),
-            Some("work_order"),
-            Some(wo_id),
         )
         .await
     }
@@ -188,16 +1006,137 @@ impl NotificationService {
         asset_name: &str,
         due_date: &str,
         asset_id: Uuid,
-    ) -> DomainResult<Notification> {
-        self.create(
-            user_id,
+    ) -> DomainResult<Vec<NotificationDelivery>> {
+        self.notify(
+            NotificationEvent::new(
+                "maintenance.due",
+                user_id,
+                json!({ "asset_name": asset_name, "due_date": due_date }),
+            )
+            .about("asset", asset_id)
+            .for_asset(asset_id),
             &format!("Maintenance Due: {}", asset_name),
             &format!(
                 "Scheduled maintenance for {} is due on {}.",
                 asset_name, due_date
             ),
-            Some("asset"),
-            Some(asset_id),
+        )
+        .await
+    }
+
+    pub async fn notify_insurance_expiring(
+        &self,
+        user_id: Uuid,
+        asset_name: &str,
+        policy: &Insurance,
+    ) -> DomainResult<Vec<NotificationDelivery>> {
+        let end_date = policy.end_date.to_string();
+        self.notify(
+            NotificationEvent::new(
+                "insurance.expiring",
+                user_id,
+                json!({
+                    "asset_name": asset_name,
+                    "policy_number": policy.policy_number,
+                    "insurance_provider": policy.insurance_provider,
+                    "end_date": end_date
+                }),
+            )
+            .about("insurance", policy.id)
+            .for_asset(policy.asset_id),
+            &format!("Insurance Expiring: {}", asset_name),
+            &format!(
+                "Policy {} ({}) for {} expires on {}.",
+                policy.policy_number, policy.insurance_provider, asset_name, end_date
+            ),
+        )
+        .await
+    }
+
+    pub async fn notify_insurance_lapsed(
+        &self,
+        user_id: Uuid,
+        asset_name: &str,
+        asset_code: &str,
+        lapsed_since: &str,
+        asset_id: Uuid,
+    ) -> DomainResult<Vec<NotificationDelivery>> {
+        self.notify(
+            NotificationEvent::new(
+                "insurance.lapsed",
+                user_id,
+                json!({
+                    "asset_name": asset_name,
+                    "asset_code": asset_code,
+                    "lapsed_since": lapsed_since
+                }),
+            )
+            .about("asset", asset_id)
+            .for_asset(asset_id),
+            &format!("Coverage Lapsed: {}", asset_name),
+            &format!(
+                "{} ({}) has no active insurance policy since {}.",
+                asset_name, asset_code, lapsed_since
+            ),
+        )
+        .await
+    }
+
+    pub async fn notify_document_expiring(
+        &self,
+        user_id: Uuid,
+        asset_name: &str,
+        document: &AssetDocument,
+    ) -> DomainResult<Vec<NotificationDelivery>> {
+        let expiry_date = document
+            .expiry_date
+            .map(|d| d.to_string())
+            .unwrap_or_default();
+        self.notify(
+            NotificationEvent::new(
+                "document.expiring",
+                user_id,
+                json!({
+                    "document_name": document.name,
+                    "document_type": document.type_,
+                    "asset_name": asset_name,
+                    "expiry_date": expiry_date
+                }),
+            )
+            .about("asset_document", document.id)
+            .for_asset(document.asset_id),
+            &format!("Document Expiring: {}", document.name),
+            &format!(
+                "{} ({}) for {} expires on {}.",
+                document.name, document.type_, asset_name, expiry_date
+            ),
+        )
+        .await
+    }
+
+    /// Deliver a password reset link through the `password_reset` templates only;
+    /// unlike other events there is no in-app fallback holding the link
+    pub async fn notify_password_reset(
+        &self,
+        user_id: Uuid,
+        name: &str,
+        token: &str,
+        reset_url: &str,
+        expires_minutes: i64,
+    ) -> DomainResult<Vec<NotificationDelivery>> {
+        self.dispatch(
+            NotificationEvent::new(
+                EVENT_PASSWORD_RESET,
+                user_id,
+                json!({
+                    "name": name,
+                    "token": token,
+                    "reset_url": reset_url,
+                    "expires_minutes": expires_minutes
+                }),
+            )
+            .about("user", user_id)
+            .with_secrets(&["token", "reset_url"]),
         )
         .await
     }
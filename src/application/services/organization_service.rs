@@ -0,0 +1,340 @@
+//! Organization Service
+//!
+//! Organization hierarchy and memberships. Every method takes the caller's
+//! scope, the root of the organization subtree they may see (`None` for
+//! unscoped callers), so admins only manage organizations below their active
+//! organization.
+
+use uuid::Uuid;
+
+use crate::application::dto::{CreateOrganizationRequest, UpdateOrganizationRequest};
+use crate::domain::entities::{Organization, OrganizationMember, OrganizationType, TenantResource};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::repositories::{OrganizationRepository, UserRepository};
+
+#[derive(Clone)]
+pub struct OrganizationService {
+    repository: OrganizationRepository,
+    user_repo: UserRepository,
+}
+
+impl OrganizationService {
+    pub fn new(repository: OrganizationRepository, user_repo: UserRepository) -> Self {
+        Self {
+            repository,
+            user_repo,
+        }
+    }
+
+    // ==================== HIERARCHY ====================
+
+    pub async fn list(&self, scope: Option<Uuid>) -> DomainResult<Vec<Organization>> {
+        self.repository
+            .list(scope)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    /// Organization by id; organizations outside the scope are not found
+    pub async fn get(&self, id: Uuid, scope: Option<Uuid>) -> DomainResult<Organization> {
+        if !self.in_scope(id, scope).await? {
+            return Err(DomainError::not_found("Organization", id));
+        }
+        self.repository
+            .find_by_id(id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("Organization", id))
+    }
+
+    pub async fn create(
+        &self,
+        request: CreateOrganizationRequest,
+        scope: Option<Uuid>,
+    ) -> DomainResult<Organization> {
+        if request.code.trim().is_empty() {
+            return Err(DomainError::validation("code", "Required"));
+        }
+        if request.name.trim().is_empty() {
+            return Err(DomainError::validation("name", "Required"));
+        }
+        let org_type = match request.org_type.as_deref() {
+            Some(t) => OrganizationType::parse(t).ok_or_else(|| {
+                DomainError::validation("org_type", "Must be company, division, department or team")
+            })?,
+            None => OrganizationType::Company,
+        };
+
+        // Scoped admins create organizations below their own
+        match (request.parent_id, scope) {
+            (None, Some(_)) => {
+                return Err(DomainError::validation(
+                    "parent_id",
+                    "Required to create an organization within the active organization",
+                ))
+            }
+            (Some(parent_id), _) => {
+                self.get(parent_id, scope).await?;
+            }
+            (None, None) => {}
+        }
+        self.ensure_code_available(&request.code, None).await?;
+
+        let mut org = Organization::new(request.code, request.name, org_type);
+        org.parent_id = request.parent_id;
+        org.cost_center = request.cost_center;
+        org.budget = request.budget;
+        org.manager_id = request.manager_id;
+        org.metadata = request.metadata;
+
+        self.repository
+            .create(&org)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    pub async fn update(
+        &self,
+        id: Uuid,
+        request: UpdateOrganizationRequest,
+        scope: Option<Uuid>,
+    ) -> DomainResult<Organization> {
+        let mut org = self.get(id, scope).await?;
+
+        if let Some(code) = request.code {
+            if code.trim().is_empty() {
+                return Err(DomainError::validation("code", "Required"));
+            }
+            self.ensure_code_available(&code, Some(id)).await?;
+            org.code = code;
+        }
+        if let Some(name) = request.name {
+            if name.trim().is_empty() {
+                return Err(DomainError::validation("name", "Required"));
+            }
+            org.name = name;
+        }
+        if let Some(org_type) = request.org_type {
+            let org_type = OrganizationType::parse(&org_type).ok_or_else(|| {
+                DomainError::validation("org_type", "Must be company, division, department or team")
+            })?;
+            org.org_type = Some(org_type.as_str().to_string());
+        }
+        if let Some(parent_id) = request.parent_id.filter(|p| Some(*p) != org.parent_id) {
+            if scope == Some(id) {
+                return Err(DomainError::business_rule(
+                    "organization_hierarchy",
+                    "The active organization cannot be moved",
+                ));
+            }
+            self.get(parent_id, scope).await?;
+            let cycle = self
+                .repository
+                .in_subtree(id, parent_id)
+                .await
+                .map_err(|e| DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                })?;
+            if cycle {
+                return Err(DomainError::business_rule(
+                    "organization_hierarchy",
+                    "An organization cannot be moved below itself or its descendants",
+                ));
+            }
+            org.parent_id = Some(parent_id);
+        }
+        if request.cost_center.is_some() {
+            org.cost_center = request.cost_center;
+        }
+        if request.budget.is_some() {
+            org.budget = request.budget;
+        }
+        if request.manager_id.is_some() {
+            org.manager_id = request.manager_id;
+        }
+        if let Some(is_active) = request.is_active {
+            org.is_active = is_active;
+        }
+        if request.metadata.is_some() {
+            org.metadata = request.metadata;
+        }
+
+        self.repository
+            .update(&org)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    pub async fn delete(&self, id: Uuid, scope: Option<Uuid>) -> DomainResult<()> {
+        self.get(id, scope).await?;
+        if scope == Some(id) {
+            return Err(DomainError::business_rule(
+                "organization_hierarchy",
+                "The active organization cannot be deleted",
+            ));
+        }
+        let deleted = self.repository.delete_unused(id).await.map_err(|e| {
+            DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            }
+        })?;
+        if !deleted {
+            return Err(DomainError::conflict(
+                "Organization still has sub-organizations, assets, users, departments or budgets",
+            ));
+        }
+        Ok(())
+    }
+
+    // ==================== MEMBERSHIP ====================
+
+    pub async fn members(
+        &self,
+        id: Uuid,
+        scope: Option<Uuid>,
+    ) -> DomainResult<Vec<OrganizationMember>> {
+        self.get(id, scope).await?;
+        self.repository
+            .list_members(id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    pub async fn add_member(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        scope: Option<Uuid>,
+        added_by: Uuid,
+    ) -> DomainResult<Vec<OrganizationMember>> {
+        self.get(id, scope).await?;
+        self.user_repo
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("User", user_id))?;
+
+        let added = self
+            .repository
+            .add_member(id, user_id, added_by)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        if !added {
+            return Err(DomainError::conflict("User is already a member"));
+        }
+        self.members(id, scope).await
+    }
+
+    /// Remove a membership; the home organization of a user is kept
+    pub async fn remove_member(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        scope: Option<Uuid>,
+    ) -> DomainResult<()> {
+        let member = self
+            .members(id, scope)
+            .await?
+            .into_iter()
+            .find(|m| m.user_id == user_id)
+            .ok_or_else(|| DomainError::not_found("OrganizationMember", user_id))?;
+        if member.is_home {
+            return Err(DomainError::business_rule(
+                "organization_membership",
+                "Cannot remove a user from their home organization",
+            ));
+        }
+        self.repository
+            .remove_member(id, user_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        Ok(())
+    }
+
+    /// Organizations a user is a member of
+    pub async fn memberships(&self, user_id: Uuid) -> DomainResult<Vec<Organization>> {
+        self.repository.list_for_user(user_id).await.map_err(|e| {
+            DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            }
+        })
+    }
+
+    // ==================== TENANT SCOPE ====================
+
+    /// Whether a tenant resource is visible in the scope; missing resources
+    /// count as visible so the handler reports them as not found
+    pub async fn resource_visible(
+        &self,
+        resource: TenantResource,
+        id: Uuid,
+        scope: Option<Uuid>,
+    ) -> DomainResult<bool> {
+        let Some(root) = scope else {
+            return Ok(true);
+        };
+        let visible = self
+            .repository
+            .resource_in_scope(resource, id, root)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        Ok(visible.unwrap_or(true))
+    }
+
+    async fn in_scope(&self, id: Uuid, scope: Option<Uuid>) -> DomainResult<bool> {
+        match scope {
+            None => Ok(true),
+            Some(root) => self.repository.in_subtree(root, id).await.map_err(|e| {
+                DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                }
+            }),
+        }
+    }
+
+    async fn ensure_code_available(&self, code: &str, except: Option<Uuid>) -> DomainResult<()> {
+        let existing = self.repository.find_by_code(code).await.map_err(|e| {
+            DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            }
+        })?;
+        match existing {
+            Some(org) if Some(org.id) != except => {
+                Err(DomainError::conflict("Organization code already exists"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Outbox Relay
+//!
+//! Delivers the domain events stored in the outbox to the in-process event
+//! bus, the WebSocket followers of the records they concern and registered
+//! subscribers. Events are marked delivered only once every subscriber took
+//! them; otherwise they are retried with backoff, so delivery is at least once.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use crate::domain::entities::OutboxEvent;
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::messaging::{
+    EventPublisher, NotificationMessage, Topic, WebSocketManager,
+};
+use crate::infrastructure::repositories::OutboxRepository;
+
+/// Events claimed per round
+const BATCH_SIZE: i64 = 100;
+/// How long a claimed event is reserved for this relay
+const LEASE_SECONDS: i64 = 60;
+/// Pause between rounds when the outbox is drained
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A consumer of domain events outside the request that raised them
+#[async_trait]
+pub trait EventSubscriber: Send + Sync {
+    /// Name in logs and delivery errors
+    fn name(&self) -> &str;
+
+    /// Take an event. On error the event is delivered again later, to every
+    /// subscriber, so subscribers ignore envelope ids they have seen.
+    async fn handle(&self, event: &OutboxEvent) -> Result<(), String>;
+}
+
+#[derive(Clone)]
+pub struct OutboxRelay {
+    repository: OutboxRepository,
+    event_publisher: EventPublisher,
+    ws_manager: Arc<WebSocketManager>,
+    subscribers: Vec<Arc<dyn EventSubscriber>>,
+}
+
+fn db_error(e: sqlx::Error) -> DomainError {
+    DomainError::ExternalServiceError {
+        service: "database".to_string(),
+        message: e.to_string(),
+    }
+}
+
+impl OutboxRelay {
+    pub fn new(
+        repository: OutboxRepository,
+        event_publisher: EventPublisher,
+        ws_manager: Arc<WebSocketManager>,
+    ) -> Self {
+        Self {
+            repository,
+            event_publisher,
+            ws_manager,
+            subscribers: Vec::new(),
+        }
+    }
+
+    pub fn with_subscriber(mut self, subscriber: Arc<dyn EventSubscriber>) -> Self {
+        self.subscribers.push(subscriber);
+        self
+    }
+
+    /// Relay events in the background until the process exits
+    pub fn start(&self) {
+        let relay = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match relay.relay_pending().await {
+                    Ok(delivered) if delivered as i64 >= BATCH_SIZE => continue,
+                    Ok(_) => {}
+                    Err(e) => error!("Outbox relay failed: {}", e),
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        });
+    }
+
+    /// Deliver one batch of due events; returns how many were delivered
+    pub async fn relay_pending(&self) -> DomainResult<usize> {
+        let events = self
+            .repository
+            .claim(BATCH_SIZE, LEASE_SECONDS)
+            .await
+            .map_err(db_error)?;
+
+        let mut delivered = 0;
+        // Later events of an aggregate wait for an earlier one that failed
+        let mut blocked: HashSet<Uuid> = HashSet::new();
+        for event in events {
+            let result = if blocked.contains(&event.aggregate_id) {
+                Err("An earlier event of the aggregate is pending".to_string())
+            } else {
+                self.deliver(&event).await
+            };
+
+            match result {
+                Ok(()) => {
+                    self.repository
+                        .mark_delivered(event.id)
+                        .await
+                        .map_err(db_error)?;
+                    delivered += 1;
+                }
+                Err(e) => {
+                    warn!(
+                        "Delivery of event {} ({}) failed: {}",
+                        event.id, event.event_type, e
+                    );
+                    blocked.insert(event.aggregate_id);
+                    let retry_at = Utc::now() + OutboxEvent::retry_delay(event.attempts);
+                    self.repository
+                        .mark_failed(event.id, &e, retry_at)
+                        .await
+                        .map_err(db_error)?;
+                }
+            }
+        }
+        Ok(delivered)
+    }
+
+    async fn deliver(&self, event: &OutboxEvent) -> Result<(), String> {
+        // Nobody listening in-process is not a failure
+        let _ = self
+            .event_publisher
+            .publish(&event.event_type, &event.envelope);
+
+        let message = NotificationMessage::new(&event.event_type, event.envelope.clone());
+        for topic in topics(event) {
+            self.ws_manager.publish(&topic, &message).await;
+        }
+
+        let mut failures = Vec::new();
+        for subscriber in &self.subscribers {
+            if let Err(e) = subscriber.handle(event).await {
+                failures.push(format!("{}: {}", subscriber.name(), e));
+            }
+        }
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures.join("; "))
+        }
+    }
+}
+
+/// Topics of the records named in an event's payload
+fn topics(event: &OutboxEvent) -> Vec<Topic> {
+    let payload = event.payload();
+    let id = |field: &str| {
+        payload
+            .get(field)
+            .and_then(|v| v.as_str())
+            .and_then(|s| Uuid::parse_str(s).ok())
+    };
+
+    let mut topics = Vec::new();
+    if let Some(id) = id("asset_id") {
+        topics.push(Topic::Asset(id));
+    }
+    if let Some(id) = id("work_order_id") {
+        topics.push(Topic::WorkOrder(id));
+    }
+    if let Some(id) = id("rental_id") {
+        topics.push(Topic::Rental(id));
+    }
+    topics
+}
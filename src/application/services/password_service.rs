@@ -0,0 +1,213 @@
+//! Password Service
+//!
+//! Password changes under the configured policy, the forgot-password flow
+//! (single-use reset tokens sent through the notification channels) and
+//! admin-forced password changes at the next login.
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use crate::application::dto::ChangePasswordRequest;
+use crate::application::services::NotificationService;
+use crate::domain::entities::{PasswordPolicy, User};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::repositories::{PasswordRepository, UserRepository};
+use crate::shared::utils::crypto::{generate_token, hash_password, hash_token, verify_password};
+
+fn db_error(e: sqlx::Error) -> DomainError {
+    DomainError::ExternalServiceError {
+        service: "database".to_string(),
+        message: e.to_string(),
+    }
+}
+
+/// Page of the web app that takes the reset token
+const DEFAULT_RESET_URL: &str = "http://localhost:5173/reset-password";
+
+#[derive(Clone)]
+pub struct PasswordService {
+    repository: PasswordRepository,
+    user_repository: UserRepository,
+    notification_service: NotificationService,
+    policy: PasswordPolicy,
+    reset_url: String,
+}
+
+impl PasswordService {
+    pub fn new(
+        repository: PasswordRepository,
+        user_repository: UserRepository,
+        notification_service: NotificationService,
+    ) -> Self {
+        Self {
+            repository,
+            user_repository,
+            notification_service,
+            policy: PasswordPolicy::from_env(),
+            reset_url: std::env::var("PASSWORD_RESET_URL")
+                .unwrap_or_else(|_| DEFAULT_RESET_URL.to_string()),
+        }
+    }
+
+    async fn find_user(&self, id: Uuid) -> DomainResult<User> {
+        self.user_repository
+            .find_by_id(id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| DomainError::not_found("User", id))
+    }
+
+    /// Hash of a new password for `user`, checked against the policy and the
+    /// user's recent passwords
+    async fn new_password_hash(&self, user: &User, password: &str) -> DomainResult<String> {
+        self.policy
+            .validate_for(password, &user.email, &user.name)?;
+
+        if self.policy.history > 0 {
+            let recent = self
+                .repository
+                .recent_hashes(user.id, self.policy.history as i64)
+                .await
+                .map_err(db_error)?;
+            if recent.iter().any(|hash| verify_password(password, hash)) {
+                return Err(DomainError::validation(
+                    "password",
+                    &format!(
+                        "Must not reuse one of your last {} passwords",
+                        self.policy.history
+                    ),
+                ));
+            }
+        }
+
+        hash_password(password).map_err(|e| DomainError::ExternalServiceError {
+            service: "crypto".to_string(),
+            message: e,
+        })
+    }
+
+    /// Change the password of a signed-in user
+    pub async fn change_password(&self, id: Uuid, req: ChangePasswordRequest) -> DomainResult<()> {
+        let user = self.find_user(id).await?;
+
+        if !verify_password(&req.old_password, &user.password_hash) {
+            return Err(DomainError::bad_request("Invalid old password"));
+        }
+
+        let hash = self.new_password_hash(&user, &req.new_password).await?;
+        self.repository
+            .set_password(id, &hash)
+            .await
+            .map_err(db_error)
+    }
+
+    /// Set the new password an admin asked `user` for during login
+    pub async fn change_required_password(&self, user: &User, password: &str) -> DomainResult<()> {
+        if !user.must_change_password {
+            return Err(DomainError::business_rule(
+                "password_change_not_required",
+                "No password change is pending",
+            ));
+        }
+        let hash = self.new_password_hash(user, password).await?;
+        self.repository
+            .set_password(user.id, &hash)
+            .await
+            .map_err(db_error)
+    }
+
+    // ==================== FORGOT PASSWORD ====================
+
+    /// Send a reset link to the user with this email, if there is one. The
+    /// outcome is not revealed to the caller.
+    pub async fn request_reset(&self, email: &str, requested_ip: Option<&str>) -> DomainResult<()> {
+        let user = self
+            .user_repository
+            .find_by_email(email.trim())
+            .await
+            .map_err(db_error)?;
+        let Some(user) = user.filter(|u| u.is_active && !u.is_service_account) else {
+            return Ok(());
+        };
+
+        let token = generate_token();
+        let expires_at = Utc::now() + Duration::minutes(self.policy.reset_token_minutes);
+        self.repository
+            .create_reset_token(user.id, &hash_token(&token), expires_at, requested_ip)
+            .await
+            .map_err(db_error)?;
+
+        let separator = if self.reset_url.contains('?') {
+            '&'
+        } else {
+            '?'
+        };
+        let reset_url = format!("{}{}token={}", self.reset_url, separator, token);
+        let deliveries = self
+            .notification_service
+            .notify_password_reset(
+                user.id,
+                &user.name,
+                &token,
+                &reset_url,
+                self.policy.reset_token_minutes,
+            )
+            .await?;
+        if deliveries.is_empty() {
+            tracing::warn!(
+                "No active 'auth.password_reset' template; reset link for user {} not sent",
+                user.id
+            );
+        }
+        Ok(())
+    }
+
+    /// Set a new password with a reset token; the token is spent
+    pub async fn reset_password(
+        &self,
+        token: &str,
+        password: &str,
+        ip_address: Option<&str>,
+    ) -> DomainResult<()> {
+        let invalid = || DomainError::bad_request("Invalid or expired reset token");
+
+        let reset_token = self
+            .repository
+            .find_reset_token(&hash_token(token.trim()))
+            .await
+            .map_err(db_error)?
+            .filter(|t| t.is_usable(Utc::now()))
+            .ok_or_else(invalid)?;
+        let user = self.find_user(reset_token.user_id).await?;
+        if !user.is_active {
+            return Err(invalid());
+        }
+
+        let hash = self.new_password_hash(&user, password).await?;
+        if !self
+            .repository
+            .reset_password(&reset_token, &hash, ip_address)
+            .await
+            .map_err(db_error)?
+        {
+            return Err(invalid());
+        }
+        Ok(())
+    }
+
+    /// Make a user choose a new password at the next login; their sessions end
+    pub async fn force_reset(&self, id: Uuid, forced_by: Uuid) -> DomainResult<()> {
+        let user = self.find_user(id).await?;
+        if user.is_service_account {
+            return Err(DomainError::business_rule(
+                "service_account",
+                "Service accounts have no password",
+            ));
+        }
+        self.repository
+            .force_reset(id, forced_by)
+            .await
+            .map_err(db_error)?;
+        Ok(())
+    }
+}
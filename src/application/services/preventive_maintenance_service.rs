@@ -0,0 +1,299 @@
+//! Preventive Maintenance Service
+//!
+//! Manages preventive schedules and raises work orders when they fall due.
+
+use chrono::{Duration, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::application::dto::{CreatePreventiveScheduleRequest, UpdatePreventiveScheduleRequest};
+use crate::application::services::{CreateWorkOrderRequest, WorkOrderService};
+use crate::domain::entities::{IntervalType, PreventiveSchedule, WorkOrder};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::repositories::{AssetRepository, PreventiveScheduleRepository};
+
+/// A schedule that is currently due, with the reading it was evaluated against
+#[derive(Debug, Clone, Serialize)]
+pub struct DueSchedule {
+    pub schedule: PreventiveSchedule,
+    pub current_reading: Option<i64>,
+    pub has_open_work_order: bool,
+}
+
+#[derive(Clone)]
+pub struct PreventiveMaintenanceService {
+    repository: PreventiveScheduleRepository,
+    asset_repo: AssetRepository,
+    work_order_service: WorkOrderService,
+}
+
+impl PreventiveMaintenanceService {
+    pub fn new(
+        repository: PreventiveScheduleRepository,
+        asset_repo: AssetRepository,
+        work_order_service: WorkOrderService,
+    ) -> Self {
+        Self {
+            repository,
+            asset_repo,
+            work_order_service,
+        }
+    }
+
+    fn parse_interval(value: &str, interval_value: i32) -> DomainResult<IntervalType> {
+        let interval: IntervalType = value.parse().map_err(|_| {
+            DomainError::validation("interval_type", "Must be one of: days, km, hours")
+        })?;
+
+        if interval_value <= 0 {
+            return Err(DomainError::validation(
+                "interval_value",
+                "Interval must be greater than zero",
+            ));
+        }
+
+        Ok(interval)
+    }
+
+    /// Create a schedule for an asset
+    pub async fn create(
+        &self,
+        asset_id: Uuid,
+        request: CreatePreventiveScheduleRequest,
+        created_by: Uuid,
+    ) -> DomainResult<PreventiveSchedule> {
+        let interval = Self::parse_interval(&request.interval_type, request.interval_value)?;
+
+        self.asset_repo
+            .find_by_id(asset_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("Asset", asset_id))?;
+
+        let mut schedule =
+            PreventiveSchedule::new(asset_id, &request.name, interval, request.interval_value);
+        schedule.maintenance_type_id = request.maintenance_type_id;
+        schedule.last_execution_date = request.last_execution_date;
+        schedule.last_execution_odometer = request.last_execution_odometer;
+        schedule.next_due_date = request.next_due_date;
+        schedule.next_due_odometer = request.next_due_odometer;
+        if let Some(days) = request.notification_days_before {
+            schedule.notification_days_before = Some(days);
+        }
+        schedule.created_by = Some(created_by);
+
+        // Derive the first due point when not given explicitly
+        match interval {
+            IntervalType::Days if schedule.next_due_date.is_none() => {
+                let base = schedule
+                    .last_execution_date
+                    .unwrap_or_else(|| Utc::now().date_naive());
+                schedule.next_due_date = Some(base + Duration::days(request.interval_value as i64));
+            }
+            IntervalType::Km | IntervalType::Hours if schedule.next_due_odometer.is_none() => {
+                let base = match schedule.last_execution_odometer {
+                    Some(reading) => Some(reading as i64),
+                    None => self.current_reading(asset_id, interval).await?,
+                };
+                schedule.next_due_odometer =
+                    Some(base.unwrap_or(0) as i32 + request.interval_value);
+            }
+            _ => {}
+        }
+
+        self.repository
+            .create(&schedule)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    pub async fn get_by_id(&self, id: Uuid) -> DomainResult<PreventiveSchedule> {
+        self.repository
+            .find_by_id(id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("PreventiveSchedule", id))
+    }
+
+    pub async fn list_by_asset(&self, asset_id: Uuid) -> DomainResult<Vec<PreventiveSchedule>> {
+        self.repository.list_by_asset(asset_id).await.map_err(|e| {
+            DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            }
+        })
+    }
+
+    pub async fn update(
+        &self,
+        id: Uuid,
+        request: UpdatePreventiveScheduleRequest,
+    ) -> DomainResult<PreventiveSchedule> {
+        let mut schedule = self.get_by_id(id).await?;
+
+        if let Some(name) = request.name {
+            schedule.name = name;
+        }
+        if let Some(t) = request.maintenance_type_id {
+            schedule.maintenance_type_id = Some(t);
+        }
+        if request.interval_type.is_some() || request.interval_value.is_some() {
+            let interval_type = request
+                .interval_type
+                .unwrap_or_else(|| schedule.interval_type.clone());
+            let interval_value = request.interval_value.unwrap_or(schedule.interval_value);
+            let interval = Self::parse_interval(&interval_type, interval_value)?;
+            schedule.interval_type = interval.as_str().to_string();
+            schedule.interval_value = interval_value;
+        }
+        if let Some(d) = request.next_due_date {
+            schedule.next_due_date = Some(d);
+        }
+        if let Some(o) = request.next_due_odometer {
+            schedule.next_due_odometer = Some(o);
+        }
+        if let Some(a) = request.is_active {
+            schedule.is_active = Some(a);
+        }
+        if let Some(n) = request.notification_days_before {
+            schedule.notification_days_before = Some(n);
+        }
+
+        self.repository
+            .update(&schedule)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    pub async fn delete(&self, id: Uuid) -> DomainResult<bool> {
+        self.repository
+            .delete(id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    async fn current_reading(
+        &self,
+        asset_id: Uuid,
+        interval: IntervalType,
+    ) -> DomainResult<Option<i64>> {
+        self.repository
+            .current_reading(asset_id, interval)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    /// Evaluate all active schedules against the calendar and current meter readings
+    pub async fn list_due(&self) -> DomainResult<Vec<DueSchedule>> {
+        let schedules =
+            self.repository
+                .list_active()
+                .await
+                .map_err(|e| DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                })?;
+
+        let today = Utc::now().date_naive();
+        let mut due = Vec::new();
+
+        for schedule in schedules {
+            let interval = schedule.interval();
+            let current_reading = if interval.is_meter_based() {
+                self.current_reading(schedule.asset_id, interval).await?
+            } else {
+                None
+            };
+
+            if schedule.is_due(today, current_reading) {
+                let has_open_work_order = self
+                    .work_order_service
+                    .has_open_for_schedule(schedule.id)
+                    .await?;
+                due.push(DueSchedule {
+                    schedule,
+                    current_reading,
+                    has_open_work_order,
+                });
+            }
+        }
+
+        Ok(due)
+    }
+
+    /// Raise work orders for every due schedule that has none in flight (Background Task)
+    pub async fn generate_due_work_orders(&self) -> DomainResult<Vec<WorkOrder>> {
+        let today = Utc::now().date_naive();
+        let mut created = Vec::new();
+
+        for item in self.list_due().await? {
+            if item.has_open_work_order {
+                continue;
+            }
+
+            let schedule = item.schedule;
+            let description = match (schedule.interval(), item.current_reading) {
+                (IntervalType::Days, _) => format!(
+                    "Preventive maintenance: {} (every {} days)",
+                    schedule.name, schedule.interval_value
+                ),
+                (interval, reading) => format!(
+                    "Preventive maintenance: {} (every {} {}, due at {}, current {})",
+                    schedule.name,
+                    schedule.interval_value,
+                    interval.as_str(),
+                    schedule.next_due_odometer.unwrap_or_default(),
+                    reading.unwrap_or_default()
+                ),
+            };
+
+            let request = CreateWorkOrderRequest {
+                asset_id: schedule.asset_id,
+                wo_type: "preventive".to_string(),
+                priority: Some("medium".to_string()),
+                scheduled_date: Some(schedule.next_due_date.unwrap_or(today).max(today)),
+                due_date: Some(schedule.next_due_date.unwrap_or(today)),
+                problem_description: Some(description),
+                estimated_hours: None,
+                estimated_cost: None,
+                safety_requirements: None,
+                lockout_tagout_required: None,
+                location_id: None,
+                preventive_schedule_id: Some(schedule.id),
+            };
+
+            match self
+                .work_order_service
+                .create(request, schedule.created_by)
+                .await
+            {
+                Ok(wo) => created.push(wo),
+                Err(e) => tracing::error!(
+                    "Failed to create work order for preventive schedule {}: {}",
+                    schedule.id,
+                    e
+                ),
+            }
+        }
+
+        Ok(created)
+    }
+}
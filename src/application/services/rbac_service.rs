@@ -1,11 +1,68 @@
 //! RBAC Service
 
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
 use uuid::Uuid;
 
-use crate::domain::entities::{Permission, Role, UserRoleAssignment};
+use crate::application::dto::{
+    CloneRoleRequest, CreateRoleRequest, SetRolePermissionsRequest, UpdateRoleRequest,
+};
+use crate::domain::entities::{
+    diff_grants, DataScope, Permission, PermissionGrant, PermissionScope, Role, RolePermission,
+    RolePermissionPreview, UserClaims, UserPermission, UserPermissionDiff, UserRoleAssignment,
+};
 use crate::domain::errors::{DomainError, DomainResult};
 use crate::infrastructure::repositories::RbacRepository;
 
+fn db_error(e: sqlx::Error) -> DomainError {
+    DomainError::ExternalServiceError {
+        service: "database".to_string(),
+        message: e.to_string(),
+    }
+}
+
+fn role_write_error(e: sqlx::Error, code: &str) -> DomainError {
+    match e {
+        sqlx::Error::Database(ref db) if db.code().as_deref() == Some("23505") => {
+            DomainError::conflict(&format!("Role '{}' already exists", code))
+        }
+        e => db_error(e),
+    }
+}
+
+fn validate_role_code(code: &str) -> DomainResult<()> {
+    let mut chars = code.chars();
+    let valid = (2..=50).contains(&code.len())
+        && chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        return Err(DomainError::validation(
+            "code",
+            "Must be 2 to 50 lowercase letters, digits or underscores, starting with a letter",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_role_level(level: i32) -> DomainResult<()> {
+    if !(1..=5).contains(&level) {
+        return Err(DomainError::validation(
+            "role_level",
+            "Must be between 1 and 5",
+        ));
+    }
+    Ok(())
+}
+
+fn required_name(name: &str) -> DomainResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(DomainError::validation("name", "Name is required"));
+    }
+    Ok(name.to_string())
+}
+
 #[derive(Clone)]
 pub struct RbacService {
     repository: RbacRepository,
@@ -51,7 +108,7 @@ impl RbacService {
     }
 
     /// Get permissions for a role
-    pub async fn get_role_permissions(&self, role_id: Uuid) -> DomainResult<Vec<Permission>> {
+    pub async fn get_role_permissions(&self, role_id: Uuid) -> DomainResult<Vec<RolePermission>> {
         self.repository
             .get_role_permissions(role_id)
             .await
@@ -61,6 +118,297 @@ impl RbacService {
             })
     }
 
+    // ==================== ROLE MANAGEMENT ====================
+
+    pub async fn get_role(&self, id: Uuid) -> DomainResult<Role> {
+        self.repository
+            .find_role(id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| DomainError::not_found("Role", id))
+    }
+
+    /// Create a custom role, optionally with its permissions
+    pub async fn create_role(
+        &self,
+        req: CreateRoleRequest,
+        created_by: Uuid,
+    ) -> DomainResult<Role> {
+        let code = req.code.trim().to_string();
+        validate_role_code(&code)?;
+        let role_level = req.role_level.unwrap_or(5);
+        validate_role_level(role_level)?;
+        let grants = req.permissions.unwrap_or_default();
+        self.validate_grants(&grants).await?;
+
+        let now = Utc::now();
+        let role = Role {
+            id: Uuid::new_v4(),
+            code,
+            name: required_name(&req.name)?,
+            description: req.description,
+            role_level: Some(role_level),
+            is_system: false,
+            requires_two_factor: req.requires_two_factor.unwrap_or(false),
+            created_at: now,
+            updated_at: now,
+        };
+        self.repository
+            .create_role(&role, &grants, created_by)
+            .await
+            .map_err(|e| role_write_error(e, &role.code))
+    }
+
+    /// Copy a role and its grants into a new custom role
+    pub async fn clone_role(
+        &self,
+        source_id: Uuid,
+        req: CloneRoleRequest,
+        created_by: Uuid,
+    ) -> DomainResult<Role> {
+        let source = self.get_role(source_id).await?;
+        let code = req.code.trim().to_string();
+        validate_role_code(&code)?;
+
+        let now = Utc::now();
+        let role = Role {
+            id: Uuid::new_v4(),
+            code,
+            name: required_name(&req.name)?,
+            description: req.description,
+            role_level: source.role_level,
+            is_system: false,
+            requires_two_factor: source.requires_two_factor,
+            created_at: now,
+            updated_at: now,
+        };
+        self.repository
+            .clone_role(source.id, &role, created_by)
+            .await
+            .map_err(|e| role_write_error(e, &role.code))
+    }
+
+    /// Edit the name, description, level or 2FA requirement of a role. The
+    /// code identifies the role in tokens and cannot change.
+    pub async fn update_role(
+        &self,
+        id: Uuid,
+        req: UpdateRoleRequest,
+        updated_by: Uuid,
+    ) -> DomainResult<Role> {
+        let mut role = self.get_role(id).await?;
+        if let Some(name) = req.name {
+            role.name = required_name(&name)?;
+        }
+        if let Some(description) = req.description {
+            role.description = Some(description);
+        }
+        if let Some(role_level) = req.role_level {
+            validate_role_level(role_level)?;
+            role.role_level = Some(role_level);
+        }
+        if let Some(requires_two_factor) = req.requires_two_factor {
+            role.requires_two_factor = requires_two_factor;
+        }
+        self.repository
+            .update_role(&role, updated_by)
+            .await
+            .map_err(db_error)
+    }
+
+    /// Delete a custom role. System roles, and roles still held as someone's
+    /// primary role or used by approval workflows, are kept.
+    pub async fn delete_role(&self, id: Uuid, deleted_by: Uuid) -> DomainResult<()> {
+        let role = self.get_role(id).await?;
+        if role.is_system {
+            return Err(DomainError::business_rule(
+                "system_role",
+                &format!("'{}' is a system role and cannot be deleted", role.code),
+            ));
+        }
+        let holders = self
+            .repository
+            .count_primary_holders(id)
+            .await
+            .map_err(db_error)?;
+        if holders > 0 {
+            return Err(DomainError::conflict(&format!(
+                "Role '{}' is the primary role of {} user(s)",
+                role.code, holders
+            )));
+        }
+
+        match self.repository.delete_role(id, deleted_by).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(DomainError::not_found("Role", id)),
+            Err(sqlx::Error::Database(ref db)) if db.code().as_deref() == Some("23503") => {
+                Err(DomainError::conflict(&format!(
+                    "Role '{}' is used by approval workflows",
+                    role.code
+                )))
+            }
+            Err(e) => Err(db_error(e)),
+        }
+    }
+
+    /// Replace the permissions of a role. Primary holders of the role sign in
+    /// again if anything changed.
+    pub async fn set_role_permissions(
+        &self,
+        role_id: Uuid,
+        req: SetRolePermissionsRequest,
+        updated_by: Uuid,
+    ) -> DomainResult<Vec<RolePermission>> {
+        self.get_role(role_id).await?;
+        self.validate_grants(&req.permissions).await?;
+        self.repository
+            .set_role_permissions(role_id, &req.permissions, updated_by)
+            .await
+            .map_err(db_error)?;
+        self.get_role_permissions(role_id).await
+    }
+
+    /// Grant a permission to a role, or change the scope of its grant
+    pub async fn attach_permission(
+        &self,
+        role_id: Uuid,
+        grant: PermissionGrant,
+        updated_by: Uuid,
+    ) -> DomainResult<Vec<RolePermission>> {
+        self.get_role(role_id).await?;
+        self.validate_grants(std::slice::from_ref(&grant)).await?;
+        self.repository
+            .attach_permission(role_id, &grant, updated_by)
+            .await
+            .map_err(db_error)?;
+        self.get_role_permissions(role_id).await
+    }
+
+    pub async fn detach_permission(
+        &self,
+        role_id: Uuid,
+        permission_code: &str,
+        updated_by: Uuid,
+    ) -> DomainResult<()> {
+        self.get_role(role_id).await?;
+        if !self
+            .repository
+            .detach_permission(role_id, permission_code, updated_by)
+            .await
+            .map_err(db_error)?
+        {
+            return Err(DomainError::not_found("Role permission", permission_code));
+        }
+        Ok(())
+    }
+
+    /// Effective permission changes of every holder of the role if its grants
+    /// were replaced by `req`, without saving anything
+    pub async fn preview_role_permissions(
+        &self,
+        role_id: Uuid,
+        req: SetRolePermissionsRequest,
+    ) -> DomainResult<RolePermissionPreview> {
+        self.get_role(role_id).await?;
+        self.validate_grants(&req.permissions).await?;
+
+        let holders = self
+            .repository
+            .list_role_holders(role_id)
+            .await
+            .map_err(db_error)?;
+        let user_ids: Vec<Uuid> = holders.iter().map(|(id, _, _)| *id).collect();
+        let mut grants: HashMap<Uuid, (Vec<PermissionGrant>, Vec<PermissionGrant>)> =
+            HashMap::new();
+        for row in self
+            .repository
+            .list_user_role_grants(&user_ids)
+            .await
+            .map_err(db_error)?
+        {
+            let Some(scope) = PermissionScope::parse(&row.scope) else {
+                continue;
+            };
+            let grant = PermissionGrant {
+                code: row.code,
+                scope,
+            };
+            let (before, after) = grants.entry(row.user_id).or_default();
+            if row.role_id != role_id {
+                after.push(grant.clone());
+            }
+            before.push(grant);
+        }
+
+        let current: HashSet<(String, PermissionScope)> = self
+            .get_role_permissions(role_id)
+            .await?
+            .into_iter()
+            .filter_map(|p| Some((p.permission.code, PermissionScope::parse(&p.scope)?)))
+            .collect();
+        let proposed: HashSet<(String, PermissionScope)> = req
+            .permissions
+            .iter()
+            .map(|g| (g.code.clone(), g.scope))
+            .collect();
+        let sessions_revoked = if current == proposed {
+            0
+        } else {
+            self.repository
+                .count_primary_holder_sessions(role_id)
+                .await
+                .map_err(db_error)?
+        };
+
+        let users = holders
+            .into_iter()
+            .filter_map(|(user_id, email, name)| {
+                let (before, mut after) = grants.remove(&user_id).unwrap_or_default();
+                after.extend(req.permissions.iter().cloned());
+                let changes = diff_grants(&before, &after);
+                (!changes.is_empty()).then_some(UserPermissionDiff {
+                    user_id,
+                    email,
+                    name,
+                    changes,
+                })
+            })
+            .collect();
+
+        Ok(RolePermissionPreview {
+            role_id,
+            affected_users: user_ids.len(),
+            sessions_revoked,
+            users,
+        })
+    }
+
+    /// Grants must name existing permissions, each once
+    async fn validate_grants(&self, grants: &[PermissionGrant]) -> DomainResult<()> {
+        let known: HashSet<String> = self
+            .list_permissions()
+            .await?
+            .into_iter()
+            .map(|p| p.code)
+            .collect();
+        let mut seen = HashSet::new();
+        for grant in grants {
+            if !known.contains(&grant.code) {
+                return Err(DomainError::validation(
+                    "permissions",
+                    &format!("Unknown permission '{}'", grant.code),
+                ));
+            }
+            if !seen.insert(grant.code.as_str()) {
+                return Err(DomainError::validation(
+                    "permissions",
+                    &format!("Permission '{}' is listed more than once", grant.code),
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// Get user's roles
     pub async fn get_user_roles(&self, user_id: Uuid) -> DomainResult<Vec<Role>> {
         self.repository.get_user_roles(user_id).await.map_err(|e| {
@@ -71,15 +419,70 @@ impl RbacService {
         })
     }
 
-    /// Get user's permissions (aggregated from all roles)
-    pub async fn get_user_permissions(&self, user_id: Uuid) -> DomainResult<Vec<Permission>> {
-        self.repository
+    /// Get user's permissions (aggregated from all roles) with the effective
+    /// scopes of their grants
+    pub async fn get_user_permissions(&self, user_id: Uuid) -> DomainResult<Vec<UserPermission>> {
+        let mut permissions = self
+            .repository
             .get_user_permissions(user_id)
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
-            })
+            })?;
+
+        for permission in permissions.iter_mut() {
+            let grants: Vec<PermissionScope> = permission
+                .scopes
+                .iter()
+                .filter_map(|s| PermissionScope::parse(s))
+                .collect();
+            permission.scopes = PermissionScope::effective(&grants)
+                .iter()
+                .map(|s| s.as_str().to_string())
+                .collect();
+        }
+        Ok(permissions)
+    }
+
+    /// Records a permission reaches for the signed-in user. Super admins see
+    /// everything; others combine the scopes of their grants, rooted at their
+    /// department and location.
+    pub async fn data_scope(
+        &self,
+        claims: &UserClaims,
+        permission_code: &str,
+    ) -> DomainResult<DataScope> {
+        if claims.role == "super_admin" {
+            return Ok(DataScope::unrestricted());
+        }
+        let user_id = claims.user_id();
+        let db_error = |e: sqlx::Error| DomainError::ExternalServiceError {
+            service: "database".to_string(),
+            message: e.to_string(),
+        };
+
+        let grants: Vec<PermissionScope> = self
+            .repository
+            .get_permission_scopes(user_id, permission_code)
+            .await
+            .map_err(db_error)?
+            .iter()
+            .filter_map(|s| PermissionScope::parse(s))
+            .collect();
+        let (department_id, location_id) = self
+            .repository
+            .get_scope_anchors(user_id)
+            .await
+            .map_err(db_error)?
+            .unwrap_or_default();
+
+        Ok(DataScope::from_grants(
+            &grants,
+            user_id,
+            department_id,
+            location_id,
+        ))
     }
 
     /// Check if user has specific permission
@@ -145,6 +548,6 @@ impl RbacService {
     /// Get permission codes for user (for JWT claims)
     pub async fn get_user_permission_codes(&self, user_id: Uuid) -> DomainResult<Vec<String>> {
         let permissions = self.get_user_permissions(user_id).await?;
-        Ok(permissions.into_iter().map(|p| p.code).collect())
+        Ok(permissions.into_iter().map(|p| p.permission.code).collect())
     }
 }
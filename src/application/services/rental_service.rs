@@ -4,21 +4,30 @@
 
 use chrono::Utc;
 use rust_decimal::Decimal;
+use sqlx::{Postgres, Transaction};
 use uuid::Uuid;
 
 use crate::application::dto::{
     ApproveRentalRequest, CreateClientRequest, CreateRentalRateRequest, CreateRentalRequest,
     DispatchRentalRequest, RejectRentalRequest, ReturnRentalRequest, UpdateRentalRateRequest,
 };
-use crate::domain::entities::{Client, Rental, RentalHandover, RentalRate};
+use crate::application::services::{ApprovalService, ApprovalSubmission, DocumentNumberService};
+use crate::domain::entities::{
+    Client, NumberingScope, Rental, RentalHandover, RentalRate, DOC_CLIENT, DOC_RENTAL,
+};
 use crate::domain::errors::{DomainError, DomainResult};
-use crate::infrastructure::repositories::{AssetRepository, ClientRepository, RentalRepository};
+use crate::domain::events::{EventEnvelope, RentalReturned};
+use crate::infrastructure::repositories::{
+    AssetRepository, ClientRepository, RentalRepository, RentalSettlement,
+};
 
 #[derive(Clone)]
 pub struct RentalService {
     rental_repo: RentalRepository,
     client_repo: ClientRepository,
     asset_repo: AssetRepository,
+    approval_service: ApprovalService,
+    document_numbers: DocumentNumberService,
 }
 
 impl RentalService {
@@ -26,11 +35,15 @@ impl RentalService {
         rental_repo: RentalRepository,
         client_repo: ClientRepository,
         asset_repo: AssetRepository,
+        approval_service: ApprovalService,
+        document_numbers: DocumentNumberService,
     ) -> Self {
         Self {
             rental_repo,
             client_repo,
             asset_repo,
+            approval_service,
+            document_numbers,
         }
     }
 
@@ -83,7 +96,16 @@ impl RentalService {
         }
 
         // 3. Create rental
-        let mut rental = Rental::new(request.asset_id, request.client_id, requested_by);
+        let rental_number = self
+            .document_numbers
+            .next_number(DOC_RENTAL, NumberingScope::asset(request.asset_id))
+            .await?;
+        let mut rental = Rental::new(
+            rental_number,
+            request.asset_id,
+            request.client_id,
+            requested_by,
+        );
         rental.start_date = request.start_date;
         rental.expected_end_date = request.expected_end_date;
         rental.daily_rate = request.daily_rate;
@@ -97,9 +119,41 @@ impl RentalService {
             }
         })?;
 
+        // 4. Route through the configured approval workflow
+        let submission = self
+            .approval_service
+            .create_request(
+                "rental",
+                created_rental.id,
+                "CREATE",
+                requested_by,
+                serde_json::to_value(&created_rental).ok(),
+                Some(asset.category_id),
+            )
+            .await?;
+
+        if let ApprovalSubmission::NotRequired = submission {
+            // Terms are needed to approve; incomplete requests stay for manual approval
+            if let Some(terms) = Self::approval_terms(&created_rental) {
+                return self
+                    .approve_rental(created_rental.id, requested_by, terms)
+                    .await;
+            }
+        }
+
         Ok(created_rental)
     }
 
+    /// Approval terms taken from the rental as requested
+    pub fn approval_terms(rental: &Rental) -> Option<ApproveRentalRequest> {
+        Some(ApproveRentalRequest {
+            start_date: rental.start_date?,
+            expected_end_date: rental.expected_end_date?,
+            daily_rate: rental.daily_rate?,
+            deposit_amount: rental.deposit_amount,
+        })
+    }
+
     /// Approve a rental request
     pub async fn approve_rental(
         &self,
@@ -107,14 +161,7 @@ impl RentalService {
         approved_by: Uuid,
         request: ApproveRentalRequest,
     ) -> DomainResult<Rental> {
-        let rental = self.get_by_id(id).await?;
-
-        if !rental.can_approve() {
-            return Err(DomainError::business_rule(
-                "rental_status",
-                &format!("Cannot approve rental with status '{}'", rental.status),
-            ));
-        }
+        self.get_approvable(id).await?;
 
         self.rental_repo
             .approve(
@@ -133,6 +180,51 @@ impl RentalService {
         self.get_by_id(id).await
     }
 
+    /// Approve a rental inside an approval's transaction
+    pub async fn approve_rental_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        approved_by: Uuid,
+        request: ApproveRentalRequest,
+    ) -> DomainResult<Rental> {
+        let mut rental = self.get_approvable(id).await?;
+
+        RentalRepository::approve_with(
+            &mut **tx,
+            id,
+            approved_by,
+            request.start_date,
+            request.expected_end_date,
+            request.daily_rate,
+        )
+        .await
+        .map_err(|e| DomainError::ExternalServiceError {
+            service: "database".to_string(),
+            message: e.to_string(),
+        })?;
+
+        rental.status = "approved".to_string();
+        rental.approved_by = Some(approved_by);
+        rental.approved_at = Some(Utc::now());
+        rental.start_date = Some(request.start_date);
+        rental.expected_end_date = Some(request.expected_end_date);
+        rental.daily_rate = Some(request.daily_rate);
+        Ok(rental)
+    }
+
+    async fn get_approvable(&self, id: Uuid) -> DomainResult<Rental> {
+        let rental = self.get_by_id(id).await?;
+
+        if !rental.can_approve() {
+            return Err(DomainError::business_rule(
+                "rental_status",
+                &format!("Cannot approve rental with status '{}'", rental.status),
+            ));
+        }
+        Ok(rental)
+    }
+
     /// Reject a rental request
     pub async fn reject_rental(
         &self,
@@ -275,16 +367,26 @@ impl RentalService {
         let total_amount = subtotal + penalty;
 
         // 3. Update rental
+        let event = EventEnvelope::from_event(RentalReturned {
+            rental_id: rental.id,
+            rental_number: rental.rental_number.clone(),
+            asset_id: rental.asset_id,
+            client_id: rental.client_id,
+            returned_by,
+            total_days,
+            total_amount,
+            occurred_at: Utc::now(),
+        })
+        .with_user(returned_by);
+        let settlement = RentalSettlement {
+            actual_end_date,
+            total_days,
+            subtotal,
+            penalty_amount: penalty,
+            total_amount,
+        };
         self.rental_repo
-            .return_rental(
-                id,
-                returned_by,
-                actual_end_date,
-                total_days,
-                subtotal,
-                penalty,
-                total_amount,
-            )
+            .return_rental(id, returned_by, &settlement, event)
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
@@ -321,36 +423,40 @@ impl RentalService {
     }
 
     /// List rentals with pagination
-    pub async fn list(&self, page: i64, per_page: i64) -> DomainResult<Vec<Rental>> {
+    pub async fn list(
+        &self,
+        page: i64,
+        per_page: i64,
+        org_scope: Option<Uuid>,
+    ) -> DomainResult<Vec<Rental>> {
         let offset = (page - 1) * per_page;
-        self.rental_repo.list(per_page, offset).await.map_err(|e| {
-            DomainError::ExternalServiceError {
+        self.rental_repo
+            .list(per_page, offset, org_scope)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
-            }
-        })
+            })
     }
 
     /// List pending rentals
-    pub async fn list_pending(&self) -> DomainResult<Vec<Rental>> {
-        self.rental_repo
-            .list_pending()
-            .await
-            .map_err(|e| DomainError::ExternalServiceError {
+    pub async fn list_pending(&self, org_scope: Option<Uuid>) -> DomainResult<Vec<Rental>> {
+        self.rental_repo.list_pending(org_scope).await.map_err(|e| {
+            DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
-            })
+            }
+        })
     }
 
     /// List overdue rentals
-    pub async fn list_overdue(&self) -> DomainResult<Vec<Rental>> {
-        self.rental_repo
-            .list_overdue()
-            .await
-            .map_err(|e| DomainError::ExternalServiceError {
+    pub async fn list_overdue(&self, org_scope: Option<Uuid>) -> DomainResult<Vec<Rental>> {
+        self.rental_repo.list_overdue(org_scope).await.map_err(|e| {
+            DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
-            })
+            }
+        })
     }
 
     /// Get handovers for a rental
@@ -368,7 +474,11 @@ impl RentalService {
 
     /// Create a new client
     pub async fn create_client(&self, request: CreateClientRequest) -> DomainResult<Client> {
-        let mut client = Client::new(request.name, request.company_name);
+        let client_code = self
+            .document_numbers
+            .next_number(DOC_CLIENT, NumberingScope::default())
+            .await?;
+        let mut client = Client::new(client_code, request.name, request.company_name);
         client.email = request.email;
         client.phone = request.phone;
         client.address = request.address;
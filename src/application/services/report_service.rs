@@ -1,6 +1,18 @@
+use crate::application::services::{DepreciationService, RbacService};
+use crate::domain::entities::{DataScope, RentalBillingPeriod, UserClaims};
 use crate::domain::errors::{DomainError, DomainResult};
 use crate::infrastructure::repositories::{AssetRepository, MaintenanceRepository};
-use chrono::NaiveDate;
+use chrono::{NaiveDate, Utc};
+
+/// Billed rental period with the names shown in the revenue report
+#[derive(sqlx::FromRow)]
+struct RentalRevenueRow {
+    #[sqlx(flatten)]
+    period: RentalBillingPeriod,
+    rental_number: String,
+    asset_name: String,
+    client_name: String,
+}
 
 #[derive(Clone)]
 pub struct ReportService {
@@ -8,6 +20,8 @@ pub struct ReportService {
     maintenance_repo: MaintenanceRepository,
     rental_repo: crate::infrastructure::repositories::RentalRepository,
     timesheet_repo: crate::infrastructure::repositories::TimesheetRepository,
+    depreciation_service: DepreciationService,
+    rbac_service: RbacService,
 }
 
 impl ReportService {
@@ -16,19 +30,29 @@ impl ReportService {
         maintenance_repo: MaintenanceRepository,
         rental_repo: crate::infrastructure::repositories::RentalRepository,
         timesheet_repo: crate::infrastructure::repositories::TimesheetRepository,
+        depreciation_service: DepreciationService,
+        rbac_service: RbacService,
     ) -> Self {
         Self {
             asset_repo,
             maintenance_repo,
             rental_repo,
             timesheet_repo,
+            depreciation_service,
+            rbac_service,
         }
     }
 
-    pub async fn generate_asset_inventory_csv(&self) -> DomainResult<String> {
+    /// Records the caller may export
+    async fn export_scope(&self, claims: &UserClaims) -> DomainResult<DataScope> {
+        self.rbac_service.data_scope(claims, "report.export").await
+    }
+
+    pub async fn generate_asset_inventory_csv(&self, claims: &UserClaims) -> DomainResult<String> {
+        let scope = self.export_scope(claims).await?;
         let assets = self
             .asset_repo
-            .find_all()
+            .find_all(claims.organization_id(), &scope)
             .await
             .map_err(|e| DomainError::internal(e.to_string()))?;
 
@@ -89,10 +113,12 @@ impl ReportService {
         &self,
         start_date: NaiveDate,
         end_date: NaiveDate,
+        claims: &UserClaims,
     ) -> DomainResult<String> {
+        let scope = self.export_scope(claims).await?;
         let logs = self
             .maintenance_repo
-            .find_by_date_range(start_date, end_date)
+            .find_by_date_range(start_date, end_date, claims.organization_id(), &scope)
             .await
             .map_err(|e| DomainError::internal(e.to_string()))?;
 
@@ -136,10 +162,12 @@ impl ReportService {
         &self,
         start_date: NaiveDate,
         end_date: NaiveDate,
+        claims: &UserClaims,
     ) -> DomainResult<String> {
+        let scope = self.export_scope(claims).await?;
         // Query billing periods within date range
-        let periods = sqlx::query!(
-            r#"SELECT 
+        let periods = sqlx::query_as::<_, RentalRevenueRow>(
+            r#"SELECT
                 bp.*,
                 r.rental_number,
                 a.name as asset_name,
@@ -150,10 +178,14 @@ impl ReportService {
             JOIN clients c ON r.client_id = c.id
             WHERE bp.period_start >= $1 AND bp.period_end <= $2
             AND bp.status IN ('approved', 'invoiced', 'paid')
+            AND ($3::uuid IS NULL OR a.organization_id IN (SELECT organization_subtree($3)))
+            AND data_scope_allows($4, ARRAY[a.assigned_to], a.department_id, a.location_id)
             ORDER BY bp.period_start ASC"#,
-            start_date,
-            end_date
         )
+        .bind(start_date)
+        .bind(end_date)
+        .bind(claims.organization_id())
+        .bind(&scope)
         .fetch_all(self.rental_repo.pool())
         .await
         .map_err(|e| DomainError::internal(e.to_string()))?;
@@ -176,13 +208,14 @@ impl ReportService {
         ])
         .map_err(|e| DomainError::internal(e.to_string()))?;
 
-        for p in periods {
+        for row in periods {
+            let p = row.period;
             wtr.write_record(&[
                 p.period_start.to_string(),
                 p.period_end.to_string(),
-                p.rental_number,
-                p.client_name,
-                p.asset_name,
+                row.rental_number,
+                row.client_name,
+                row.asset_name,
                 p.total_operating_hours
                     .map(|d| d.to_string())
                     .unwrap_or_default(),
@@ -204,12 +237,15 @@ impl ReportService {
         Ok(data)
     }
 
-    pub async fn generate_asset_depreciation_csv(&self) -> DomainResult<String> {
-        let assets = self
-            .asset_repo
-            .find_all()
-            .await
-            .map_err(|e| DomainError::internal(e.to_string()))?;
+    pub async fn generate_asset_depreciation_csv(
+        &self,
+        claims: &UserClaims,
+    ) -> DomainResult<String> {
+        let scope = self.export_scope(claims).await?;
+        let positions = self
+            .depreciation_service
+            .positions_in_scope(Utc::now().date_naive(), claims.organization_id(), &scope)
+            .await?;
 
         let mut wtr = csv::Writer::from_writer(vec![]);
 
@@ -221,38 +257,38 @@ impl ReportService {
             "Purchase Price",
             "Useful Life (Months)",
             "Residual Value",
+            "Method",
+            "Depreciation This Month",
+            "Accumulated Depreciation",
+            "Revaluation / Impairment",
             "Current Book Value",
         ])
         .map_err(|e| DomainError::internal(e.to_string()))?;
 
-        for asset in assets {
-            // Only include assets with financial data
-            if asset.purchase_price.is_some() && asset.useful_life_months.is_some() {
-                let book_value = asset.calculate_book_value();
-
-                wtr.write_record(&[
-                    asset.asset_code,
-                    asset.name,
-                    asset
-                        .purchase_date
-                        .map(|d| d.to_string())
-                        .unwrap_or_default(),
-                    asset
-                        .purchase_price
-                        .map(|d| d.to_string())
-                        .unwrap_or_default(),
-                    asset
-                        .useful_life_months
-                        .map(|d| d.to_string())
-                        .unwrap_or_default(),
-                    asset
-                        .residual_value
-                        .map(|d| d.to_string())
-                        .unwrap_or_default(),
-                    book_value.map(|d| d.to_string()).unwrap_or_default(),
-                ])
-                .map_err(|e| DomainError::internal(e.to_string()))?;
-            }
+        for position in positions {
+            wtr.write_record(&[
+                position.asset_code,
+                position.name,
+                position
+                    .purchase_date
+                    .map(|d| d.to_string())
+                    .unwrap_or_default(),
+                position.purchase_price.to_string(),
+                position
+                    .useful_life_months
+                    .map(|d| d.to_string())
+                    .unwrap_or_default(),
+                position
+                    .residual_value
+                    .map(|d| d.to_string())
+                    .unwrap_or_default(),
+                position.depreciation_method.unwrap_or_default(),
+                position.period_depreciation.to_string(),
+                position.accumulated_depreciation.to_string(),
+                position.valuation_adjustment.to_string(),
+                position.book_value.to_string(),
+            ])
+            .map_err(|e| DomainError::internal(e.to_string()))?;
         }
 
         let data = String::from_utf8(
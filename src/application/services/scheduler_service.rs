@@ -5,23 +5,29 @@
 use tokio_cron_scheduler::{Job, JobScheduler};
 use tracing::{error, info};
 
-use crate::application::services::{LoanService, MaintenanceService};
+use crate::application::services::{
+    DepreciationService, DocumentService, InsuranceService, LoanService, MaintenanceService,
+    NotificationService, PreventiveMaintenanceService, ValuationService, WebhookService,
+};
+use crate::domain::entities::{DIGEST_DAILY, DIGEST_HOURLY, DIGEST_WEEKLY};
 
 /// Scheduler service
+///
+/// Built with a struct literal; each field is a service that owns some jobs.
 #[derive(Clone)]
 pub struct SchedulerService {
-    loan_service: LoanService,
-    maintenance_service: MaintenanceService,
+    pub loan_service: LoanService,
+    pub maintenance_service: MaintenanceService,
+    pub preventive_maintenance_service: PreventiveMaintenanceService,
+    pub notification_service: NotificationService,
+    pub depreciation_service: DepreciationService,
+    pub valuation_service: ValuationService,
+    pub insurance_service: InsuranceService,
+    pub document_service: DocumentService,
+    pub webhook_service: WebhookService,
 }
 
 impl SchedulerService {
-    pub fn new(loan_service: LoanService, maintenance_service: MaintenanceService) -> Self {
-        Self {
-            loan_service,
-            maintenance_service,
-        }
-    }
-
     /// Start the scheduler
     pub async fn start(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let sched = JobScheduler::new().await?;
@@ -41,6 +47,9 @@ impl SchedulerService {
                         Ok(_) => info!("Overdue loans check completed"),
                         Err(e) => error!("Error checking overdue loans: {}", e),
                     }
+                    if let Err(e) = service.check_due_loans().await {
+                        error!("Error sending loan due reminders: {}", e);
+                    }
                 })
             })?)
             .await?;
@@ -61,6 +70,165 @@ impl SchedulerService {
             })?)
             .await?;
 
+        // Job 3: Generate preventive maintenance work orders daily at 02:00
+        let preventive_service = self.preventive_maintenance_service.clone();
+        sched
+            .add(Job::new_async("0 0 2 * * *", move |_uuid, _l| {
+                let service = preventive_service.clone();
+                Box::pin(async move {
+                    info!("Running scheduled job: Generate Preventive Work Orders");
+                    match service.generate_due_work_orders().await {
+                        Ok(created) => info!(
+                            "Preventive maintenance check completed, {} work orders created",
+                            created.len()
+                        ),
+                        Err(e) => error!("Error generating preventive work orders: {}", e),
+                    }
+                })
+            })?)
+            .await?;
+
+        // Job 4: Retry failed notification deliveries every 5 minutes
+        let notification_service = self.notification_service.clone();
+        sched
+            .add(Job::new_async("0 */5 * * * *", move |_uuid, _l| {
+                let service = notification_service.clone();
+                Box::pin(async move {
+                    match service.retry_due_deliveries().await {
+                        Ok(0) => {}
+                        Ok(sent) => info!("Notification retry: {} deliveries sent", sent),
+                        Err(e) => error!("Error retrying notification deliveries: {}", e),
+                    }
+                })
+            })?)
+            .await?;
+
+        // Jobs 5-7: Send notification digests hourly, daily at 07:00, Mondays at 07:00
+        for (schedule, frequency) in [
+            ("0 0 * * * *", DIGEST_HOURLY),
+            ("0 0 7 * * *", DIGEST_DAILY),
+            ("0 0 7 * * Mon", DIGEST_WEEKLY),
+        ] {
+            let notification_service = self.notification_service.clone();
+            sched
+                .add(Job::new_async(schedule, move |_uuid, _l| {
+                    let service = notification_service.clone();
+                    Box::pin(async move {
+                        match service.send_digests(frequency).await {
+                            Ok(0) => {}
+                            Ok(sent) => info!("{} notification digests sent: {}", frequency, sent),
+                            Err(e) => error!("Error sending {} digests: {}", frequency, e),
+                        }
+                    })
+                })?)
+                .await?;
+        }
+
+        // Job 8: Recalculate depreciation schedules on the 1st of each month at 03:00
+        // (picks up the previous month's hour meter usage)
+        let depreciation_service = self.depreciation_service.clone();
+        sched
+            .add(Job::new_async("0 0 3 1 * *", move |_uuid, _l| {
+                let service = depreciation_service.clone();
+                Box::pin(async move {
+                    info!("Running scheduled job: Recalculate Depreciation");
+                    match service.recalculate_all().await {
+                        Ok(run) => info!(
+                            "Depreciation recalculated for {} assets, {} failed",
+                            run.recalculated,
+                            run.failed.len()
+                        ),
+                        Err(e) => error!("Error recalculating depreciation: {}", e),
+                    }
+                })
+            })?)
+            .await?;
+
+        // Job 9: Month-end close of the previous month on the 1st at 04:00
+        let depreciation_service = self.depreciation_service.clone();
+        sched
+            .add(Job::new_async("0 0 4 1 * *", move |_uuid, _l| {
+                let service = depreciation_service.clone();
+                Box::pin(async move {
+                    info!("Running scheduled job: Close Depreciation Period");
+                    match service.close_previous_month().await {
+                        Ok(Some(closing)) => info!(
+                            "Depreciation period {} closed",
+                            closing.period_start.format("%Y-%m")
+                        ),
+                        Ok(None) => info!("Previous depreciation period already closed"),
+                        Err(e) => error!("Error closing depreciation period: {}", e),
+                    }
+                })
+            })?)
+            .await?;
+
+        // Job 10: Book value snapshot at the end of the previous month, after the close
+        let valuation_service = self.valuation_service.clone();
+        sched
+            .add(Job::new_async("0 30 4 1 * *", move |_uuid, _l| {
+                let service = valuation_service.clone();
+                Box::pin(async move {
+                    info!("Running scheduled job: Valuation Snapshot");
+                    match service.snapshot(None).await {
+                        Ok(snapshot) => info!(
+                            "Recorded {} calculated valuations as of {}",
+                            snapshot.recorded, snapshot.as_of
+                        ),
+                        Err(e) => error!("Error recording valuation snapshot: {}", e),
+                    }
+                })
+            })?)
+            .await?;
+
+        // Job 11: Insurance expiry warnings and lapsed coverage daily at 07:00
+        let insurance_service = self.insurance_service.clone();
+        sched
+            .add(Job::new_async("0 0 7 * * *", move |_uuid, _l| {
+                let service = insurance_service.clone();
+                Box::pin(async move {
+                    info!("Running scheduled job: Check Insurance Expiry");
+                    match service.check_expiring_policies().await {
+                        Ok(run) => info!(
+                            "Insurance: {} expiring warned, {} expired, {} assets lapsed",
+                            run.warned, run.expired, run.lapsed
+                        ),
+                        Err(e) => error!("Error checking insurance expiry: {}", e),
+                    }
+                })
+            })?)
+            .await?;
+
+        // Job 12: Document expiry reminders daily at 07:15
+        let document_service = self.document_service.clone();
+        sched
+            .add(Job::new_async("0 15 7 * * *", move |_uuid, _l| {
+                let service = document_service.clone();
+                Box::pin(async move {
+                    info!("Running scheduled job: Remind Expiring Documents");
+                    match service.remind_expiring_documents().await {
+                        Ok(run) => info!("Documents: {} expiry reminders sent", run.reminded),
+                        Err(e) => error!("Error sending document expiry reminders: {}", e),
+                    }
+                })
+            })?)
+            .await?;
+
+        // Job 13: Retry webhook deliveries whose backoff has elapsed, every minute
+        let webhook_service = self.webhook_service.clone();
+        sched
+            .add(Job::new_async("0 * * * * *", move |_uuid, _l| {
+                let service = webhook_service.clone();
+                Box::pin(async move {
+                    match service.retry_due_deliveries().await {
+                        Ok(0) => {}
+                        Ok(sent) => info!("Webhook retry: {} deliveries sent", sent),
+                        Err(e) => error!("Error retrying webhook deliveries: {}", e),
+                    }
+                })
+            })?)
+            .await?;
+
         sched.start().await?;
         info!("Scheduler started");
 
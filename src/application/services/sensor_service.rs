@@ -1,20 +1,26 @@
 //! Sensor Service
 
 use chrono::{DateTime, Utc};
+use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::domain::entities::SensorReading;
 use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::messaging::{NotificationMessage, Topic, WebSocketManager};
 use crate::infrastructure::repositories::{SensorAlert, SensorRepository, SensorThreshold};
 
 #[derive(Clone)]
 pub struct SensorService {
     repository: SensorRepository,
+    ws_manager: Arc<WebSocketManager>,
 }
 
 impl SensorService {
-    pub fn new(repository: SensorRepository) -> Self {
-        Self { repository }
+    pub fn new(repository: SensorRepository, ws_manager: Arc<WebSocketManager>) -> Self {
+        Self {
+            repository,
+            ws_manager,
+        }
     }
 
     /// Record sensor reading
@@ -224,12 +230,29 @@ impl SensorService {
             created_at: Utc::now(),
         };
 
-        self.repository
-            .create_alert(&alert)
-            .await
-            .map_err(|e| DomainError::ExternalServiceError {
+        let alert = self.repository.create_alert(&alert).await.map_err(|e| {
+            DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
-            })
+            }
+        })?;
+
+        let msg = NotificationMessage::new(
+            "SENSOR_ALERT",
+            serde_json::json!({
+                "id": alert.id,
+                "asset_id": alert.asset_id,
+                "sensor_id": alert.sensor_id,
+                "alert_type": alert.alert_type,
+                "severity": alert.severity,
+                "sensor_value": alert.sensor_value,
+            }),
+        );
+        self.ws_manager.publish(&Topic::Alerts, &msg).await;
+        self.ws_manager
+            .publish(&Topic::Asset(alert.asset_id), &msg)
+            .await;
+
+        Ok(alert)
     }
 }
@@ -0,0 +1,239 @@
+//! Specification Service
+//!
+//! Validates asset specifications against the category's specification schema
+//! and records every category/specification change of an asset in
+//! `asset_specification_history`, in the same transaction as the change.
+
+use serde_json::Value as JsonValue;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::application::dto::UpdateSpecificationsRequest;
+use crate::domain::entities::{
+    diff_specifications, Asset, AssetHistory, AssetSpecificationHistory, SpecificationDiff,
+    SpecificationSchema, MANUAL_SPEC_CHANGE_TYPES, SPEC_CHANGE_MODIFICATION,
+};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::repositories::{
+    AssetRepository, CategoryRepository, SpecificationRepository,
+};
+
+#[derive(Clone)]
+pub struct SpecificationService {
+    repository: SpecificationRepository,
+    asset_repo: AssetRepository,
+    category_repo: CategoryRepository,
+}
+
+impl SpecificationService {
+    pub fn new(
+        repository: SpecificationRepository,
+        asset_repo: AssetRepository,
+        category_repo: CategoryRepository,
+    ) -> Self {
+        Self {
+            repository,
+            asset_repo,
+            category_repo,
+        }
+    }
+
+    /// Check specifications against the schema of a category (if it has one)
+    pub async fn validate(
+        &self,
+        category_id: Uuid,
+        specifications: Option<&JsonValue>,
+    ) -> DomainResult<()> {
+        let category = self
+            .category_repo
+            .find_by_id(category_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("Category", category_id))?;
+
+        match &category.specification_schema {
+            Some(schema) => SpecificationSchema::parse(schema)?.validate(specifications),
+            None => Ok(()),
+        }
+    }
+
+    pub async fn history(&self, asset_id: Uuid) -> DomainResult<Vec<AssetSpecificationHistory>> {
+        self.repository.list_by_asset(asset_id).await.map_err(|e| {
+            DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            }
+        })
+    }
+
+    /// Compare the specifications after two history entries, or after one
+    /// entry and the current specifications
+    pub async fn diff(
+        &self,
+        asset_id: Uuid,
+        from: Uuid,
+        to: Option<Uuid>,
+    ) -> DomainResult<SpecificationDiff> {
+        let base = self.entry(asset_id, from).await?;
+        let (to_category_id, to_specifications) = match to {
+            Some(to) => {
+                let target = self.entry(asset_id, to).await?;
+                (target.new_category_id, target.new_specifications)
+            }
+            None => {
+                let asset = self
+                    .asset_repo
+                    .find_by_id(asset_id)
+                    .await
+                    .map_err(|e| DomainError::ExternalServiceError {
+                        service: "database".to_string(),
+                        message: e.to_string(),
+                    })?
+                    .ok_or_else(|| DomainError::not_found("Asset", asset_id))?;
+                (Some(asset.category_id), asset.specifications)
+            }
+        };
+
+        Ok(SpecificationDiff {
+            asset_id,
+            from_history_id: base.id,
+            to_history_id: to,
+            from_category_id: base.new_category_id,
+            to_category_id,
+            changes: diff_specifications(
+                base.new_specifications.as_ref(),
+                to_specifications.as_ref(),
+            ),
+        })
+    }
+
+    /// Manual specification edit
+    pub async fn update_specifications(
+        &self,
+        asset_id: Uuid,
+        request: UpdateSpecificationsRequest,
+        changed_by: Uuid,
+    ) -> DomainResult<Asset> {
+        let change_type = request
+            .change_type
+            .as_deref()
+            .unwrap_or(SPEC_CHANGE_MODIFICATION);
+        if !MANUAL_SPEC_CHANGE_TYPES.contains(&change_type) {
+            return Err(DomainError::validation(
+                "change_type",
+                &format!("Must be one of {}", MANUAL_SPEC_CHANGE_TYPES.join(", ")),
+            ));
+        }
+
+        let previous = self
+            .asset_repo
+            .find_by_id(asset_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("Asset", asset_id))?;
+
+        let mut asset = previous.clone();
+        asset.specifications = Some(request.specifications).filter(|s| !s.is_null());
+        self.save_change(
+            &previous,
+            &asset,
+            change_type,
+            request.notes,
+            Some(changed_by),
+        )
+        .await
+    }
+
+    /// Validate and save an asset whose category or specifications differ from
+    /// `previous`, recording the change
+    pub async fn save_change(
+        &self,
+        previous: &Asset,
+        asset: &Asset,
+        change_type: &str,
+        notes: Option<String>,
+        changed_by: Option<Uuid>,
+    ) -> DomainResult<Asset> {
+        self.validate(asset.category_id, asset.specifications.as_ref())
+            .await?;
+
+        let (entry, history) =
+            Self::change_entries(previous, asset, change_type, notes, changed_by);
+        let saved = self
+            .repository
+            .save_asset_change(asset, &entry, &history)
+            .await;
+        Self::saved_change(saved)
+    }
+
+    /// `save_change` inside the caller's transaction
+    pub async fn save_change_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        previous: &Asset,
+        asset: &Asset,
+        change_type: &str,
+        changed_by: Option<Uuid>,
+    ) -> DomainResult<Asset> {
+        self.validate(asset.category_id, asset.specifications.as_ref())
+            .await?;
+
+        let (entry, history) = Self::change_entries(previous, asset, change_type, None, changed_by);
+        let saved =
+            SpecificationRepository::save_asset_change_in(tx, asset, &entry, &history).await;
+        Self::saved_change(saved)
+    }
+
+    fn change_entries(
+        previous: &Asset,
+        asset: &Asset,
+        change_type: &str,
+        notes: Option<String>,
+        changed_by: Option<Uuid>,
+    ) -> (AssetSpecificationHistory, AssetHistory) {
+        let mut entry = AssetSpecificationHistory::new(
+            asset.id,
+            change_type,
+            previous.category_id,
+            asset.category_id,
+            previous.specifications.clone(),
+            asset.specifications.clone(),
+            changed_by,
+        );
+        entry.notes = notes.clone();
+        let mut history = AssetHistory::new(asset.id, "specifications_changed", changed_by);
+        history.notes = notes;
+        (entry, history)
+    }
+
+    fn saved_change(saved: Result<Option<Asset>, sqlx::Error>) -> DomainResult<Asset> {
+        saved
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| {
+                DomainError::conflict(
+                    "Asset specifications were changed concurrently; reload and retry",
+                )
+            })
+    }
+
+    async fn entry(&self, asset_id: Uuid, id: Uuid) -> DomainResult<AssetSpecificationHistory> {
+        self.repository
+            .find_by_id(id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .filter(|entry| entry.asset_id == asset_id)
+            .ok_or_else(|| DomainError::not_found("AssetSpecificationHistory", id))
+    }
+}
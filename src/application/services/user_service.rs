@@ -1,17 +1,16 @@
 use uuid::Uuid;
 
-use crate::application::dto::{
-    ChangePasswordRequest, CreateUserRequest, UpdateProfileRequest, UpdateUserRequest,
-};
-use crate::domain::entities::{User, UserSummary};
+use crate::application::dto::{CreateUserRequest, UpdateProfileRequest, UpdateUserRequest};
+use crate::domain::entities::{PasswordPolicy, User, UserSummary};
 use crate::domain::errors::{DomainError, DomainResult};
 use crate::infrastructure::repositories::{RbacRepository, UserRepository};
-use crate::shared::utils::crypto::{hash_password, verify_password};
+use crate::shared::utils::crypto::hash_password;
 
 #[derive(Clone)]
 pub struct UserService {
     repository: UserRepository,
     rbac_repo: RbacRepository,
+    password_policy: PasswordPolicy,
 }
 
 impl UserService {
@@ -19,6 +18,7 @@ impl UserService {
         Self {
             repository,
             rbac_repo,
+            password_policy: PasswordPolicy::from_env(),
         }
     }
 
@@ -59,6 +59,8 @@ impl UserService {
             })?
             .ok_or_else(|| DomainError::bad_request("Invalid role code"))?;
 
+        self.password_policy
+            .validate_for(&req.password, &req.email, &req.name)?;
         let password_hash =
             hash_password(&req.password).map_err(|e| DomainError::ExternalServiceError {
                 service: "crypto".to_string(),
@@ -70,6 +72,7 @@ impl UserService {
         user.role_id = Some(role.id);
         user.department_id = req.department_id;
         user.organization_id = req.organization_id;
+        user.location_id = req.location_id;
 
         self.repository
             .create(&user)
@@ -97,6 +100,9 @@ impl UserService {
         }
 
         let password_hash = if let Some(pwd) = req.password {
+            let user = self.get_profile(id).await?;
+            self.password_policy
+                .validate_for(&pwd, &user.email, &user.name)?;
             Some(
                 hash_password(&pwd).map_err(|e| DomainError::ExternalServiceError {
                     service: "crypto".to_string(),
@@ -124,6 +130,16 @@ impl UserService {
                 message: e.to_string(),
             })?;
 
+        if let Some(location_id) = req.location_id {
+            self.repository
+                .set_location(id, location_id)
+                .await
+                .map_err(|e| DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                })?;
+        }
+
         // If password needs update
         if let Some(hash) = password_hash {
             self.repository
@@ -187,28 +203,6 @@ impl UserService {
             })
     }
 
-    /// Change password
-    pub async fn change_password(&self, id: Uuid, req: ChangePasswordRequest) -> DomainResult<()> {
-        let user = self.get_profile(id).await?;
-
-        if !verify_password(&req.old_password, &user.password_hash) {
-            return Err(DomainError::bad_request("Invalid old password"));
-        }
-
-        let new_hash =
-            hash_password(&req.new_password).map_err(|e| DomainError::ExternalServiceError {
-                service: "crypto".to_string(),
-                message: e,
-            })?;
-
-        self.repository
-            .update_password(id, &new_hash)
-            .await
-            .map_err(|e| DomainError::ExternalServiceError {
-                service: "database".to_string(),
-                message: e.to_string(),
-            })
-    }
     /// Upload avatar
     pub async fn upload_avatar(
         &self,
@@ -0,0 +1,269 @@
+//! Valuation Service
+//!
+//! Records appraisals, market quotes and impairments against the depreciation
+//! schedule, and takes the monthly calculated snapshots. Revaluations and
+//! impairments change the carrying amount the depreciation engine continues from.
+
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::application::dto::{RecordAppraisalRequest, RecordImpairmentRequest};
+use crate::application::services::DepreciationService;
+use crate::domain::entities::{
+    month_start, AssetValuation, VALUATION_APPRAISAL, VALUATION_IMPAIRMENT, VALUATION_MARKET,
+    VALUATION_TYPES,
+};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::repositories::{AssetRepository, ValuationRepository};
+
+/// Outcome of a snapshot run
+#[derive(Debug, Clone, Serialize)]
+pub struct ValuationSnapshot {
+    pub as_of: NaiveDate,
+    pub recorded: usize,
+}
+
+#[derive(Clone)]
+pub struct ValuationService {
+    repository: ValuationRepository,
+    asset_repo: AssetRepository,
+    depreciation_service: DepreciationService,
+}
+
+impl ValuationService {
+    pub fn new(
+        repository: ValuationRepository,
+        asset_repo: AssetRepository,
+        depreciation_service: DepreciationService,
+    ) -> Self {
+        Self {
+            repository,
+            asset_repo,
+            depreciation_service,
+        }
+    }
+
+    pub async fn history(
+        &self,
+        asset_id: Uuid,
+        valuation_type: Option<String>,
+    ) -> DomainResult<Vec<AssetValuation>> {
+        if let Some(t) = valuation_type.as_deref() {
+            if !VALUATION_TYPES.contains(&t) {
+                return Err(DomainError::validation(
+                    "valuation_type",
+                    &format!("Must be one of: {}", VALUATION_TYPES.join(", ")),
+                ));
+            }
+        }
+
+        self.repository
+            .list_by_asset(asset_id, valuation_type.as_deref())
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    /// Record an appraisal or market quote; with `apply_revaluation` the market
+    /// value becomes the carrying amount from the valuation month on
+    pub async fn record_appraisal(
+        &self,
+        asset_id: Uuid,
+        request: RecordAppraisalRequest,
+        created_by: Uuid,
+    ) -> DomainResult<AssetValuation> {
+        let valuation_type = request
+            .valuation_type
+            .unwrap_or_else(|| VALUATION_APPRAISAL.to_string());
+        if valuation_type != VALUATION_APPRAISAL && valuation_type != VALUATION_MARKET {
+            return Err(DomainError::validation(
+                "valuation_type",
+                "Must be appraisal or market",
+            ));
+        }
+        if request.market_value < Decimal::ZERO
+            || request.replacement_cost.is_some_and(|c| c < Decimal::ZERO)
+        {
+            return Err(DomainError::validation(
+                "market_value",
+                "Values cannot be negative",
+            ));
+        }
+        Self::validate_date(request.valuation_date)?;
+
+        let original_cost = self.purchase_price(asset_id).await?;
+        let carrying = if request.apply_revaluation {
+            self.depreciation_service
+                .ensure_period_open(request.valuation_date)
+                .await?;
+            Some(
+                self.depreciation_service
+                    .carrying_amount_at(asset_id, request.valuation_date)
+                    .await?,
+            )
+        } else {
+            // Informational only; book figures when the asset has a schedule
+            self.depreciation_service
+                .carrying_amount_at(asset_id, request.valuation_date)
+                .await
+                .ok()
+        };
+
+        let revalued_amount = request.apply_revaluation.then_some(request.market_value);
+        let valuation = AssetValuation {
+            id: Uuid::new_v4(),
+            asset_id,
+            valuation_date: request.valuation_date,
+            original_cost,
+            accumulated_depreciation: carrying.map(|c| c.accumulated_depreciation),
+            book_value: carrying.map(|c| c.book_value),
+            market_value: Some(request.market_value),
+            replacement_cost: request.replacement_cost,
+            valuation_type,
+            appraiser: request.appraiser,
+            notes: request.notes,
+            revalued_amount,
+            adjustment_amount: revalued_amount
+                .zip(carrying)
+                .map(|(amount, c)| amount - c.book_value),
+            created_by: Some(created_by),
+            created_at: None,
+            updated_at: None,
+        };
+
+        self.save(valuation).await
+    }
+
+    /// Write the asset down to its recoverable amount
+    pub async fn record_impairment(
+        &self,
+        asset_id: Uuid,
+        request: RecordImpairmentRequest,
+        created_by: Uuid,
+    ) -> DomainResult<AssetValuation> {
+        if request.recoverable_amount < Decimal::ZERO {
+            return Err(DomainError::validation(
+                "recoverable_amount",
+                "Cannot be negative",
+            ));
+        }
+        Self::validate_date(request.valuation_date)?;
+
+        let original_cost = self.purchase_price(asset_id).await?;
+        self.depreciation_service
+            .ensure_period_open(request.valuation_date)
+            .await?;
+        let carrying = self
+            .depreciation_service
+            .carrying_amount_at(asset_id, request.valuation_date)
+            .await?;
+
+        if request.recoverable_amount >= carrying.book_value {
+            return Err(DomainError::business_rule(
+                "impairment",
+                &format!(
+                    "Recoverable amount must be below the book value of {}",
+                    carrying.book_value
+                ),
+            ));
+        }
+
+        let valuation = AssetValuation {
+            id: Uuid::new_v4(),
+            asset_id,
+            valuation_date: request.valuation_date,
+            original_cost,
+            accumulated_depreciation: Some(carrying.accumulated_depreciation),
+            book_value: Some(carrying.book_value),
+            market_value: Some(request.recoverable_amount),
+            replacement_cost: None,
+            valuation_type: VALUATION_IMPAIRMENT.to_string(),
+            appraiser: request.appraiser,
+            notes: request.notes,
+            revalued_amount: Some(request.recoverable_amount),
+            adjustment_amount: Some(request.recoverable_amount - carrying.book_value),
+            created_by: Some(created_by),
+            created_at: None,
+            updated_at: None,
+        };
+
+        self.save(valuation).await
+    }
+
+    /// Record calculated book values of every asset at `as_of` (default: end of last
+    /// month). Re-running for the same date refreshes the snapshot.
+    pub async fn snapshot(&self, as_of: Option<NaiveDate>) -> DomainResult<ValuationSnapshot> {
+        let as_of = match as_of {
+            Some(date) => date,
+            None => month_start(Utc::now().date_naive())
+                .pred_opt()
+                .ok_or_else(|| DomainError::validation("as_of", "Out of range"))?,
+        };
+
+        let positions = self.depreciation_service.positions(as_of, None).await?;
+        let recorded = self
+            .repository
+            .upsert_calculated(as_of, &positions)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+
+        Ok(ValuationSnapshot { as_of, recorded })
+    }
+
+    async fn save(&self, valuation: AssetValuation) -> DomainResult<AssetValuation> {
+        let saved = self
+            .repository
+            .create(&valuation)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(ref db) if db.code().as_deref() == Some("23505") => {
+                    DomainError::conflict(&format!(
+                        "Asset already has a {} valuation dated {}",
+                        valuation.valuation_type, valuation.valuation_date
+                    ))
+                }
+                _ => DomainError::ExternalServiceError {
+                    service: "database".to_string(),
+                    message: e.to_string(),
+                },
+            })?;
+
+        if saved.is_revaluation() {
+            self.depreciation_service
+                .recalculate_asset(saved.asset_id)
+                .await?;
+        }
+        Ok(saved)
+    }
+
+    async fn purchase_price(&self, asset_id: Uuid) -> DomainResult<Option<Decimal>> {
+        let asset = self
+            .asset_repo
+            .find_by_id(asset_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?
+            .ok_or_else(|| DomainError::not_found("Asset", asset_id))?;
+        Ok(asset.purchase_price)
+    }
+
+    fn validate_date(date: NaiveDate) -> DomainResult<()> {
+        let today = Utc::now().date_naive();
+        if date > today {
+            return Err(DomainError::validation(
+                "valuation_date",
+                "Cannot be in the future",
+            ));
+        }
+        Ok(())
+    }
+}
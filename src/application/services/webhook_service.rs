@@ -0,0 +1,394 @@
+//! Webhook Service
+//!
+//! Pushes domain events from the outbox to admin-managed webhook subscriptions.
+//! Each matching event is logged once per subscription in `webhook_deliveries`
+//! and POSTed as its JSON envelope, signed with the subscription's secret.
+//! Failed attempts are retried with exponential backoff by the scheduler; a
+//! delivery out of attempts becomes a dead letter until an admin redelivers it.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::future::join_all;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use crate::application::dto::{CreateWebhookRequest, UpdateWebhookRequest, WebhookDeliveryParams};
+use crate::application::services::EventSubscriber;
+use crate::domain::entities::{
+    is_valid_event_filter, OutboxEvent, WebhookDelivery, WebhookSubscription,
+    WebhookSubscriptionSecret, WEBHOOK_DEAD, WEBHOOK_DEFAULT_MAX_ATTEMPTS, WEBHOOK_PENDING,
+    WEBHOOK_RETRYING, WEBHOOK_SENT,
+};
+use crate::domain::errors::{DomainError, DomainResult};
+use crate::infrastructure::messaging::WebhookSender;
+use crate::infrastructure::repositories::WebhookRepository;
+use crate::shared::utils::crypto::generate_token;
+
+/// Deliveries retried per scheduler run
+const RETRY_BATCH_SIZE: i64 = 50;
+/// How long an attempt holds its delivery; longer than the request timeout
+const LEASE_SECONDS: i64 = 60;
+/// Shortest secret accepted from an admin
+const MIN_SECRET_LENGTH: usize = 16;
+
+#[derive(Clone)]
+pub struct WebhookService {
+    repository: WebhookRepository,
+    sender: WebhookSender,
+}
+
+fn db_error(e: sqlx::Error) -> DomainError {
+    DomainError::ExternalServiceError {
+        service: "database".to_string(),
+        message: e.to_string(),
+    }
+}
+
+impl WebhookService {
+    pub fn new(repository: WebhookRepository, sender: WebhookSender) -> Self {
+        Self { repository, sender }
+    }
+
+    // ==================== SUBSCRIPTIONS ====================
+
+    pub async fn list_subscriptions(&self) -> DomainResult<Vec<WebhookSubscription>> {
+        self.repository.list_subscriptions().await.map_err(db_error)
+    }
+
+    pub async fn get_subscription(&self, id: Uuid) -> DomainResult<WebhookSubscription> {
+        self.repository
+            .find_subscription(id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| DomainError::not_found("WebhookSubscription", id))
+    }
+
+    fn validate_subscription(subscription: &WebhookSubscription) -> DomainResult<()> {
+        if subscription.name.trim().is_empty() {
+            return Err(DomainError::validation("name", "Required"));
+        }
+
+        match reqwest::Url::parse(&subscription.url) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => {}
+            _ => {
+                return Err(DomainError::validation(
+                    "url",
+                    "Must be an absolute http or https URL",
+                ))
+            }
+        }
+
+        if let Some(invalid) = subscription
+            .event_types
+            .iter()
+            .find(|filter| !is_valid_event_filter(filter))
+        {
+            return Err(DomainError::validation(
+                "event_types",
+                &format!(
+                    "Invalid event type '{}'. Use e.g. asset.created or rental.*",
+                    invalid
+                ),
+            ));
+        }
+
+        if subscription.secret.len() < MIN_SECRET_LENGTH {
+            return Err(DomainError::validation(
+                "secret",
+                &format!("Must be at least {} characters", MIN_SECRET_LENGTH),
+            ));
+        }
+
+        if !(1..=20).contains(&subscription.max_attempts) {
+            return Err(DomainError::validation(
+                "max_attempts",
+                "Must be between 1 and 20",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Create a subscription. The secret is returned here only; a random one
+    /// is generated when the request leaves it out.
+    pub async fn create_subscription(
+        &self,
+        request: CreateWebhookRequest,
+        created_by: Uuid,
+    ) -> DomainResult<WebhookSubscriptionSecret> {
+        let subscription = WebhookSubscription {
+            id: Uuid::new_v4(),
+            name: request.name,
+            url: request.url,
+            event_types: request.event_types.unwrap_or_default(),
+            secret: request.secret.unwrap_or_else(generate_token),
+            is_active: request.is_active.unwrap_or(true),
+            max_attempts: request.max_attempts.unwrap_or(WEBHOOK_DEFAULT_MAX_ATTEMPTS),
+            created_by: Some(created_by),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        Self::validate_subscription(&subscription)?;
+
+        let subscription = self
+            .repository
+            .create_subscription(&subscription)
+            .await
+            .map_err(db_error)?;
+
+        Ok(WebhookSubscriptionSecret {
+            secret: subscription.secret.clone(),
+            subscription,
+        })
+    }
+
+    pub async fn update_subscription(
+        &self,
+        id: Uuid,
+        request: UpdateWebhookRequest,
+    ) -> DomainResult<WebhookSubscription> {
+        let mut subscription = self.get_subscription(id).await?;
+
+        if let Some(v) = request.name {
+            subscription.name = v;
+        }
+        if let Some(v) = request.url {
+            subscription.url = v;
+        }
+        if let Some(v) = request.event_types {
+            subscription.event_types = v;
+        }
+        if let Some(v) = request.secret {
+            subscription.secret = v;
+        }
+        if let Some(v) = request.max_attempts {
+            subscription.max_attempts = v;
+        }
+        if let Some(v) = request.is_active {
+            subscription.is_active = v;
+        }
+        Self::validate_subscription(&subscription)?;
+
+        self.repository
+            .update_subscription(&subscription)
+            .await
+            .map_err(db_error)
+    }
+
+    pub async fn delete_subscription(&self, id: Uuid) -> DomainResult<bool> {
+        self.repository
+            .delete_subscription(id)
+            .await
+            .map_err(db_error)
+    }
+
+    // ==================== DELIVERIES ====================
+
+    /// Log an event for every active subscription that wants it and send it.
+    /// Events seen before are not logged again. Returns the new deliveries.
+    pub async fn dispatch(&self, event: &OutboxEvent) -> DomainResult<Vec<WebhookDelivery>> {
+        let subscriptions = self
+            .repository
+            .list_active_subscriptions()
+            .await
+            .map_err(db_error)?;
+
+        let mut deliveries = Vec::new();
+        for subscription in subscriptions
+            .iter()
+            .filter(|s| s.matches(&event.event_type))
+        {
+            if let Some(delivery) = self
+                .repository
+                .enqueue_delivery(
+                    subscription.id,
+                    event.id,
+                    &event.event_type,
+                    &event.envelope,
+                )
+                .await
+                .map_err(db_error)?
+            {
+                deliveries.push(delivery);
+            }
+        }
+
+        // Receivers answer in their own time; the relay moves on
+        for delivery in &deliveries {
+            let service = self.clone();
+            let id = delivery.id;
+            tokio::spawn(async move {
+                if let Err(e) = service.send_now(id).await {
+                    error!("Webhook delivery {} failed: {}", id, e);
+                }
+            });
+        }
+        Ok(deliveries)
+    }
+
+    /// Attempt a delivery if it is due and no other attempt holds it
+    async fn send_now(&self, id: Uuid) -> DomainResult<Option<WebhookDelivery>> {
+        match self
+            .repository
+            .claim_delivery(id, LEASE_SECONDS)
+            .await
+            .map_err(db_error)?
+        {
+            Some(delivery) => self.attempt_delivery(delivery).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// POST a claimed delivery to its subscription and record the outcome
+    async fn attempt_delivery(&self, delivery: WebhookDelivery) -> DomainResult<WebhookDelivery> {
+        let subscription = self.get_subscription(delivery.subscription_id).await?;
+        if !subscription.is_active {
+            return self
+                .record_attempt(
+                    &delivery,
+                    WEBHOOK_DEAD,
+                    None,
+                    Some("Subscription is disabled"),
+                    None,
+                )
+                .await;
+        }
+
+        let result = self
+            .sender
+            .send(&subscription.url, &subscription.secret, &delivery)
+            .await;
+
+        let (status_code, error) = match result {
+            Ok(code) if (200..300).contains(&code) => {
+                return self
+                    .record_attempt(&delivery, WEBHOOK_SENT, Some(code), None, None)
+                    .await
+            }
+            Ok(code) => (Some(code), format!("Receiver responded with {}", code)),
+            Err(e) => (None, e),
+        };
+
+        let attempts = delivery.attempts + 1;
+        if attempts < subscription.max_attempts {
+            let next = Utc::now() + WebhookDelivery::retry_delay(attempts);
+            self.record_attempt(
+                &delivery,
+                WEBHOOK_RETRYING,
+                status_code,
+                Some(&error),
+                Some(next),
+            )
+            .await
+        } else {
+            warn!(
+                "Webhook delivery {} to '{}' is dead after {} attempts: {}",
+                delivery.id, subscription.name, attempts, error
+            );
+            self.record_attempt(&delivery, WEBHOOK_DEAD, status_code, Some(&error), None)
+                .await
+        }
+    }
+
+    async fn record_attempt(
+        &self,
+        delivery: &WebhookDelivery,
+        status: &str,
+        status_code: Option<u16>,
+        error: Option<&str>,
+        next_attempt_at: Option<chrono::DateTime<Utc>>,
+    ) -> DomainResult<WebhookDelivery> {
+        self.repository
+            .record_attempt(
+                delivery.id,
+                status,
+                status_code.map(i32::from),
+                error,
+                next_attempt_at,
+            )
+            .await
+            .map_err(db_error)
+    }
+
+    /// Send deliveries whose backoff has elapsed (Background Task)
+    pub async fn retry_due_deliveries(&self) -> DomainResult<usize> {
+        let due = self
+            .repository
+            .claim_due_deliveries(RETRY_BATCH_SIZE, LEASE_SECONDS)
+            .await
+            .map_err(db_error)?;
+
+        let results = join_all(due.into_iter().map(|d| self.attempt_delivery(d))).await;
+        let mut sent = 0;
+        for result in results {
+            if result?.status == WEBHOOK_SENT {
+                sent += 1;
+            }
+        }
+        Ok(sent)
+    }
+
+    /// Send a dead or retrying delivery again now, with a fresh set of attempts.
+    /// Receivers may see a delivery twice and deduplicate on the envelope id.
+    pub async fn redeliver(&self, id: Uuid) -> DomainResult<WebhookDelivery> {
+        let delivery = self
+            .repository
+            .find_delivery(id)
+            .await
+            .map_err(db_error)?
+            .ok_or_else(|| DomainError::not_found("WebhookDelivery", id))?;
+
+        if delivery.status == WEBHOOK_SENT {
+            return Err(DomainError::business_rule(
+                "delivery_status",
+                "Delivery was already sent",
+            ));
+        }
+        if delivery.status == WEBHOOK_PENDING {
+            return Err(DomainError::business_rule(
+                "delivery_status",
+                "Delivery is waiting for its first attempt",
+            ));
+        }
+
+        self.repository.reset_delivery(id).await.map_err(db_error)?;
+        match self.send_now(id).await? {
+            Some(delivery) => Ok(delivery),
+            None => Err(DomainError::conflict(
+                "Delivery is being attempted; try again shortly",
+            )),
+        }
+    }
+
+    pub async fn list_deliveries(
+        &self,
+        params: &WebhookDeliveryParams,
+    ) -> DomainResult<Vec<WebhookDelivery>> {
+        let per_page = params.per_page.unwrap_or(50).clamp(1, 200);
+        let offset = (params.page.unwrap_or(1).max(1) - 1) * per_page;
+
+        self.repository
+            .list_deliveries(
+                params.subscription_id,
+                params.status.as_deref(),
+                params.event_type.as_deref(),
+                per_page,
+                offset,
+            )
+            .await
+            .map_err(db_error)
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for WebhookService {
+    fn name(&self) -> &str {
+        "webhooks"
+    }
+
+    async fn handle(&self, event: &OutboxEvent) -> Result<(), String> {
+        self.dispatch(event)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
@@ -2,17 +2,25 @@
 
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
+use sqlx::{Postgres, Transaction};
 use uuid::Uuid;
 
+use crate::application::services::{
+    BudgetCommitment, BudgetService, BudgetSpend, BudgetedResult, DocumentNumberService,
+    RbacService,
+};
 use crate::domain::entities::{
-    AssetState, ChecklistItem, WorkOrder, WorkOrderPart, WorkOrderStatus,
+    AssetState, ChecklistItem, NumberingScope, UserClaims, WorkOrder, WorkOrderPart,
+    WorkOrderStatus, DOC_WORK_ORDER, SPEND_WORK_ORDER,
 };
 use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::events::{EventEnvelope, WorkOrderCompleted};
 use crate::infrastructure::repositories::{
-    AssetRepository, LifecycleRepository, WorkOrderRepository,
+    AssetRepository, LifecycleRepository, PreventiveScheduleRepository, WorkOrderRepository,
 };
 
 use crate::infrastructure::cache::{CacheKey, CacheOperations};
+use crate::infrastructure::messaging::{NotificationMessage, Topic, WebSocketManager};
 use std::sync::Arc;
 
 /// Create work order request
@@ -29,6 +37,16 @@ pub struct CreateWorkOrderRequest {
     pub safety_requirements: Option<Vec<String>>,
     pub lockout_tagout_required: Option<bool>,
     pub location_id: Option<Uuid>,
+    pub preventive_schedule_id: Option<Uuid>,
+}
+
+/// Repositories a work order reads and writes through
+#[derive(Clone)]
+pub struct WorkOrderRepositories {
+    pub work_orders: WorkOrderRepository,
+    pub lifecycle: LifecycleRepository,
+    pub assets: AssetRepository,
+    pub preventive: PreventiveScheduleRepository,
 }
 
 #[derive(Clone)]
@@ -36,21 +54,33 @@ pub struct WorkOrderService {
     repository: WorkOrderRepository,
     lifecycle_repo: LifecycleRepository,
     asset_repo: AssetRepository,
+    preventive_repo: PreventiveScheduleRepository,
+    budget_service: BudgetService,
     cache: Arc<dyn CacheOperations>,
+    ws_manager: Arc<WebSocketManager>,
+    document_numbers: DocumentNumberService,
+    rbac_service: RbacService,
 }
 
 impl WorkOrderService {
     pub fn new(
-        repository: WorkOrderRepository,
-        lifecycle_repo: LifecycleRepository,
-        asset_repo: AssetRepository,
+        repositories: WorkOrderRepositories,
+        budget_service: BudgetService,
         cache: Arc<dyn CacheOperations>,
+        ws_manager: Arc<WebSocketManager>,
+        document_numbers: DocumentNumberService,
+        rbac_service: RbacService,
     ) -> Self {
         Self {
-            repository,
-            lifecycle_repo,
-            asset_repo,
+            repository: repositories.work_orders,
+            lifecycle_repo: repositories.lifecycle,
+            asset_repo: repositories.assets,
+            preventive_repo: repositories.preventive,
+            budget_service,
             cache,
+            ws_manager,
+            document_numbers,
+            rbac_service,
         }
     }
 
@@ -68,7 +98,15 @@ impl WorkOrderService {
         request: CreateWorkOrderRequest,
         created_by: Option<Uuid>,
     ) -> DomainResult<WorkOrder> {
-        let mut wo = WorkOrder::new(request.asset_id, &request.wo_type);
+        let scope = NumberingScope {
+            location_id: request.location_id,
+            ..NumberingScope::asset(request.asset_id)
+        };
+        let wo_number = self
+            .document_numbers
+            .next_number(DOC_WORK_ORDER, scope)
+            .await?;
+        let mut wo = WorkOrder::new(wo_number, request.asset_id, &request.wo_type);
         wo.priority = request.priority;
         wo.scheduled_date = request.scheduled_date;
         wo.due_date = request.due_date;
@@ -78,6 +116,7 @@ impl WorkOrderService {
         wo.safety_requirements = request.safety_requirements;
         wo.lockout_tagout_required = request.lockout_tagout_required.unwrap_or(false);
         wo.location_id = request.location_id;
+        wo.preventive_schedule_id = request.preventive_schedule_id;
         wo.created_by = created_by;
 
         self.repository
@@ -100,14 +139,57 @@ impl WorkOrderService {
             .ok_or_else(|| DomainError::not_found("WorkOrder", id))
     }
 
-    pub async fn list(&self, page: i64, per_page: i64) -> DomainResult<Vec<WorkOrder>> {
+    /// Get work order by ID within the data scope of the caller
+    pub async fn get_in_scope(&self, id: Uuid, claims: &UserClaims) -> DomainResult<WorkOrder> {
+        self.ensure_in_scope(id, claims, "work_order.read").await?;
+        self.get_by_id(id).await
+    }
+
+    /// Work orders outside the caller's data scope of `permission` are reported
+    /// as not found
+    async fn ensure_in_scope(
+        &self,
+        id: Uuid,
+        claims: &UserClaims,
+        permission: &str,
+    ) -> DomainResult<()> {
+        let scope = self.rbac_service.data_scope(claims, permission).await?;
+        if scope.all_records {
+            return Ok(());
+        }
+        let visible = self
+            .repository
+            .in_data_scope(id, &scope)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        if !visible {
+            return Err(DomainError::not_found("WorkOrder", id));
+        }
+        Ok(())
+    }
+
+    /// List work orders within the caller's organization and data scope
+    pub async fn list(
+        &self,
+        page: i64,
+        per_page: i64,
+        claims: &UserClaims,
+    ) -> DomainResult<Vec<WorkOrder>> {
+        let scope = self
+            .rbac_service
+            .data_scope(claims, "work_order.read")
+            .await?;
         let offset = (page - 1) * per_page;
-        self.repository.list(per_page, offset).await.map_err(|e| {
-            DomainError::ExternalServiceError {
+        self.repository
+            .list(per_page, offset, claims.organization_id(), &scope)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
-            }
-        })
+            })
     }
 
     pub async fn list_by_asset(&self, asset_id: Uuid) -> DomainResult<Vec<WorkOrder>> {
@@ -119,9 +201,27 @@ impl WorkOrderService {
         })
     }
 
-    pub async fn list_pending(&self) -> DomainResult<Vec<WorkOrder>> {
+    /// Pending work orders the caller may see
+    pub async fn list_pending(&self, claims: &UserClaims) -> DomainResult<Vec<WorkOrder>> {
+        self.pending_in_scope(claims, "work_order.read").await
+    }
+
+    /// Pending work orders the caller may approve
+    pub async fn list_awaiting_approval(
+        &self,
+        claims: &UserClaims,
+    ) -> DomainResult<Vec<WorkOrder>> {
+        self.pending_in_scope(claims, "work_order.update").await
+    }
+
+    async fn pending_in_scope(
+        &self,
+        claims: &UserClaims,
+        permission: &str,
+    ) -> DomainResult<Vec<WorkOrder>> {
+        let scope = self.rbac_service.data_scope(claims, permission).await?;
         self.repository
-            .list_pending()
+            .list_pending(claims.organization_id(), &scope)
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
@@ -129,9 +229,14 @@ impl WorkOrderService {
             })
     }
 
-    pub async fn list_overdue(&self) -> DomainResult<Vec<WorkOrder>> {
+    /// Overdue work orders within the caller's organization and data scope
+    pub async fn list_overdue(&self, claims: &UserClaims) -> DomainResult<Vec<WorkOrder>> {
+        let scope = self
+            .rbac_service
+            .data_scope(claims, "work_order.read")
+            .await?;
         self.repository
-            .list_overdue()
+            .list_overdue(claims.organization_id(), &scope)
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
@@ -149,7 +254,67 @@ impl WorkOrderService {
             })
     }
 
-    pub async fn approve(&self, id: Uuid, _approved_by: Uuid) -> DomainResult<WorkOrder> {
+    /// Check whether a preventive schedule already has an open work order
+    pub async fn has_open_for_schedule(&self, schedule_id: Uuid) -> DomainResult<bool> {
+        self.repository
+            .has_open_for_schedule(schedule_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    /// Approve a pending work order within the caller's data scope, committing
+    /// its estimated cost against the asset's budget. Over budget on an `escalate` line the work order stays
+    /// pending behind a budget OVERRUN approval request.
+    pub async fn approve(
+        &self,
+        id: Uuid,
+        claims: &UserClaims,
+    ) -> DomainResult<BudgetedResult<WorkOrder>> {
+        self.ensure_in_scope(id, claims, "work_order.update")
+            .await?;
+        let approved_by = claims.user_id();
+        let wo = self.get_pending(id).await?;
+
+        let spend = Self::budget_spend(&wo, wo.estimated_cost.unwrap_or_default());
+        if let BudgetCommitment::Escalated(request) =
+            self.budget_service.commit(&spend, approved_by).await?
+        {
+            return Ok(BudgetedResult::PendingApproval(request));
+        }
+
+        self.mark_approved(id).await.map(BudgetedResult::Applied)
+    }
+
+    /// Approve a work order whose budget overrun was approved, inside the
+    /// approval's transaction
+    pub async fn approve_over_budget(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        approved_by: Uuid,
+        approval_request_id: Uuid,
+    ) -> DomainResult<WorkOrder> {
+        let mut wo = self.get_pending(id).await?;
+
+        let spend = Self::budget_spend(&wo, wo.estimated_cost.unwrap_or_default());
+        self.budget_service
+            .commit_overrun(tx, &spend, approved_by, Some(approval_request_id))
+            .await?;
+
+        WorkOrderRepository::update_status_with(&mut **tx, id, "approved")
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+        wo.status = "approved".to_string();
+        Ok(wo)
+    }
+
+    async fn get_pending(&self, id: Uuid) -> DomainResult<WorkOrder> {
         let wo = self.get_by_id(id).await?;
 
         if wo.status != WorkOrderStatus::Pending.as_str() {
@@ -158,7 +323,10 @@ impl WorkOrderService {
                 "Can only approve pending work orders",
             ));
         }
+        Ok(wo)
+    }
 
+    async fn mark_approved(&self, id: Uuid) -> DomainResult<WorkOrder> {
         self.repository
             .update_status(id, "approved")
             .await
@@ -170,20 +338,85 @@ impl WorkOrderService {
         self.get_by_id(id).await
     }
 
-    pub async fn assign(&self, id: Uuid, technician_id: Uuid) -> DomainResult<WorkOrder> {
+    fn budget_spend(wo: &WorkOrder, amount: Decimal) -> BudgetSpend {
+        BudgetSpend {
+            source_type: SPEND_WORK_ORDER,
+            source_id: wo.id,
+            asset_id: wo.asset_id,
+            amount,
+            date: chrono::Utc::now().date_naive(),
+            reference: wo.wo_number.clone(),
+        }
+    }
+
+    /// Cancel an open work order and release its budget commitment
+    pub async fn cancel(&self, id: Uuid, claims: &UserClaims) -> DomainResult<WorkOrder> {
+        self.ensure_in_scope(id, claims, "work_order.update")
+            .await?;
+        let wo = self.get_by_id(id).await?;
+
+        if wo.status == WorkOrderStatus::Completed.as_str()
+            || wo.status == WorkOrderStatus::Cancelled.as_str()
+        {
+            return Err(DomainError::business_rule(
+                "work_order_status",
+                "Completed or cancelled work orders cannot be cancelled",
+            ));
+        }
+
         self.repository
-            .assign_technician(id, technician_id)
+            .update_status(id, WorkOrderStatus::Cancelled.as_str())
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
             })?;
+        self.budget_service.release(SPEND_WORK_ORDER, id).await?;
 
         self.get_by_id(id).await
     }
 
+    pub async fn assign(
+        &self,
+        id: Uuid,
+        technician_id: Uuid,
+        claims: &UserClaims,
+    ) -> DomainResult<WorkOrder> {
+        self.ensure_in_scope(id, claims, "work_order.assign")
+            .await?;
+        self.repository
+            .assign_technician(id, technician_id)
+            .await
+            .map_err(|e| DomainError::ExternalServiceError {
+                service: "database".to_string(),
+                message: e.to_string(),
+            })?;
+
+        let order = self.get_by_id(id).await?;
+
+        // The technician hears about it wherever they are signed in
+        let msg = NotificationMessage::new(
+            "WORK_ORDER_ASSIGNED",
+            serde_json::json!({
+                "id": order.id,
+                "wo_number": order.wo_number,
+                "asset_id": order.asset_id,
+                "priority": order.priority,
+                "assigned_technician": technician_id,
+            }),
+        );
+        self.ws_manager.send_to_user(technician_id, &msg).await;
+        self.ws_manager
+            .publish(&Topic::WorkOrder(order.id), &msg)
+            .await;
+
+        Ok(order)
+    }
+
     /// Start work on a work order - also transitions asset lifecycle
-    pub async fn start_work(&self, id: Uuid) -> DomainResult<WorkOrder> {
+    pub async fn start_work(&self, id: Uuid, claims: &UserClaims) -> DomainResult<WorkOrder> {
+        self.ensure_in_scope(id, claims, "work_order.update")
+            .await?;
         let wo = self.get_by_id(id).await?;
 
         // Update WO status
@@ -241,20 +474,39 @@ impl WorkOrderService {
     pub async fn complete(
         &self,
         id: Uuid,
-        completed_by: Uuid,
         work_performed: &str,
         actual_cost: Option<Decimal>,
+        claims: &UserClaims,
     ) -> DomainResult<WorkOrder> {
+        self.ensure_in_scope(id, claims, "work_order.update")
+            .await?;
+        let completed_by = claims.user_id();
         let wo = self.get_by_id(id).await?;
 
         // Complete WO in database
-        self.repository
-            .complete(id, completed_by, work_performed, actual_cost)
+        let event = EventEnvelope::from_event(WorkOrderCompleted {
+            work_order_id: wo.id,
+            asset_id: wo.asset_id,
+            completed_by,
+            actual_cost,
+            actual_hours: wo.actual_hours,
+            occurred_at: chrono::Utc::now(),
+        })
+        .with_user(completed_by);
+        let completed = self
+            .repository
+            .complete(id, completed_by, work_performed, actual_cost, event)
             .await
             .map_err(|e| DomainError::ExternalServiceError {
                 service: "database".to_string(),
                 message: e.to_string(),
             })?;
+        if !completed {
+            return Err(DomainError::business_rule(
+                "work_order_status",
+                "Only work orders in progress can be completed",
+            ));
+        }
 
         // Transition asset back to deployed
         if let Ok(current_status) = self.lifecycle_repo.get_asset_status(wo.asset_id).await {
@@ -300,11 +552,63 @@ impl WorkOrderService {
             }
         }
 
-        self.get_by_id(id).await
+        // Roll the originating preventive schedule forward
+        if let Some(schedule_id) = wo.preventive_schedule_id {
+            if let Err(e) = self.roll_preventive_schedule(schedule_id).await {
+                tracing::warn!(
+                    "Failed to roll preventive schedule {} forward: {}",
+                    schedule_id,
+                    e
+                );
+            }
+        }
+
+        // Post the actual spend; the approval commitment is released with it
+        let completed = self.get_by_id(id).await?;
+        let actual = completed
+            .actual_cost
+            .unwrap_or_else(|| completed.total_cost());
+        let spend = Self::budget_spend(&completed, actual);
+        if let Err(e) = self
+            .budget_service
+            .record_actual(&spend, Some(completed_by))
+            .await
+        {
+            tracing::warn!(
+                "Failed to post actual spend of work order {}: {}",
+                completed.wo_number,
+                e
+            );
+        }
+
+        Ok(completed)
+    }
+
+    /// Record a preventive schedule execution and compute its next due point
+    async fn roll_preventive_schedule(&self, schedule_id: Uuid) -> Result<(), sqlx::Error> {
+        let mut schedule = match self.preventive_repo.find_by_id(schedule_id).await? {
+            Some(s) => s,
+            None => return Ok(()),
+        };
+
+        let reading = self
+            .preventive_repo
+            .current_reading(schedule.asset_id, schedule.interval())
+            .await?;
+        schedule.roll_forward(chrono::Utc::now().date_naive(), reading);
+        self.preventive_repo.update(&schedule).await?;
+
+        Ok(())
     }
 
     // Checklist methods
-    pub async fn get_checklist(&self, work_order_id: Uuid) -> DomainResult<Vec<ChecklistItem>> {
+    pub async fn get_checklist(
+        &self,
+        work_order_id: Uuid,
+        claims: &UserClaims,
+    ) -> DomainResult<Vec<ChecklistItem>> {
+        self.ensure_in_scope(work_order_id, claims, "work_order.read")
+            .await?;
         self.repository
             .get_checklists(work_order_id)
             .await
@@ -319,7 +623,10 @@ impl WorkOrderService {
         work_order_id: Uuid,
         task_number: i32,
         description: String,
+        claims: &UserClaims,
     ) -> DomainResult<ChecklistItem> {
+        self.ensure_in_scope(work_order_id, claims, "work_order.update")
+            .await?;
         let item = ChecklistItem::new(work_order_id, task_number, description);
         self.repository
             .add_checklist_item(&item)
@@ -345,7 +652,14 @@ impl WorkOrderService {
             })
     }
 
-    pub async fn remove_checklist_item(&self, id: Uuid) -> DomainResult<bool> {
+    pub async fn remove_checklist_item(
+        &self,
+        id: Uuid,
+        work_order_id: Uuid,
+        claims: &UserClaims,
+    ) -> DomainResult<bool> {
+        self.ensure_in_scope(work_order_id, claims, "work_order.update")
+            .await?;
         self.repository
             .remove_checklist_item(id)
             .await
@@ -356,7 +670,13 @@ impl WorkOrderService {
     }
 
     // Parts methods
-    pub async fn get_parts(&self, work_order_id: Uuid) -> DomainResult<Vec<WorkOrderPart>> {
+    pub async fn get_parts(
+        &self,
+        work_order_id: Uuid,
+        claims: &UserClaims,
+    ) -> DomainResult<Vec<WorkOrderPart>> {
+        self.ensure_in_scope(work_order_id, claims, "work_order.read")
+            .await?;
         self.repository.get_parts(work_order_id).await.map_err(|e| {
             DomainError::ExternalServiceError {
                 service: "database".to_string(),
@@ -371,7 +691,10 @@ impl WorkOrderService {
         part_name: String,
         quantity: Decimal,
         unit_cost: Decimal,
+        claims: &UserClaims,
     ) -> DomainResult<WorkOrderPart> {
+        self.ensure_in_scope(work_order_id, claims, "work_order.update")
+            .await?;
         let part = WorkOrderPart::new(work_order_id, &part_name, quantity, unit_cost);
 
         let created = self.repository.add_part(&part).await.map_err(|e| {
@@ -387,7 +710,14 @@ impl WorkOrderService {
         Ok(created)
     }
 
-    pub async fn remove_part(&self, id: Uuid, work_order_id: Uuid) -> DomainResult<bool> {
+    pub async fn remove_part(
+        &self,
+        id: Uuid,
+        work_order_id: Uuid,
+        claims: &UserClaims,
+    ) -> DomainResult<bool> {
+        self.ensure_in_scope(work_order_id, claims, "work_order.update")
+            .await?;
         let result = self.repository.remove_part(id).await.map_err(|e| {
             DomainError::ExternalServiceError {
                 service: "database".to_string(),
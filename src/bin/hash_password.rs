@@ -1,12 +0,0 @@
-use argon2::{
-    password_hash::{rand_core::OsRng, SaltString},
-    Argon2, PasswordHasher,
-};
-
-fn main() {
-    let password = b"admin123";
-    let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
-    let hash = argon2.hash_password(password, &salt).unwrap();
-    println!("{}", hash.to_string());
-}
@@ -0,0 +1,138 @@
+//! API Key Entity
+//!
+//! Keys for integrations, owned by a service account or a person. A key is
+//! `amk_<prefix>_<secret>`: the prefix identifies it in lists and logs, the
+//! whole key is stored only as a SHA-256 hash. A key grants the permission
+//! codes in its scopes that the owner's role still holds.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const API_KEY_PREFIX: &str = "amk_";
+pub const API_KEY_DEFAULT_EXPIRY_DAYS: i64 = 90;
+pub const API_KEY_MAX_EXPIRY_DAYS: i64 = 365;
+
+/// Role and role level of key claims. A key never takes on its owner's role,
+/// so it does not pass super admin, admin or approval level checks and reaches
+/// only what its scopes grant.
+pub const API_KEY_ROLE: &str = "api_key";
+pub const API_KEY_ROLE_LEVEL: i32 = 5;
+
+/// Audit log actions of API keys
+pub const AUDIT_API_KEY_CREATED: &str = "API_KEY_CREATED";
+pub const AUDIT_API_KEY_ROTATED: &str = "API_KEY_ROTATED";
+pub const AUDIT_API_KEY_REVOKED: &str = "API_KEY_REVOKED";
+/// A state-changing request made with a key
+pub const AUDIT_API_KEY_REQUEST: &str = "API_KEY_REQUEST";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub user_id: Uuid, // Owner: a service account or a person
+    pub name: String,
+    pub prefix: String, // amk_<8 hex>, shown in lists
+    #[serde(skip_serializing)]
+    pub key_hash: String, // SHA-256 of the whole key
+    pub scopes: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub last_used_ip: Option<String>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_by: Option<Uuid>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// A newly created or rotated key; `key` is shown this once
+#[derive(Debug, Clone, Serialize)]
+pub struct IssuedApiKey {
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+    pub key: String,
+}
+
+/// Service account as listed to admins
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ServiceAccount {
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub role_id: Option<Uuid>,
+    pub role: String,
+    pub organization_id: Option<Uuid>,
+    pub is_active: bool,
+    pub active_keys: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiKey {
+    pub fn is_usable(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+
+    /// Scopes the owner's role still grants
+    pub fn effective_permissions(&self, role_permissions: &[String]) -> Vec<String> {
+        self.scopes
+            .iter()
+            .filter(|scope| role_permissions.contains(scope))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Public prefix of a presented key, `None` when it is not shaped like one
+pub fn api_key_prefix(key: &str) -> Option<&str> {
+    let rest = key.strip_prefix(API_KEY_PREFIX)?;
+    let (id, secret) = rest.split_once('_')?;
+    if id.is_empty() || secret.is_empty() {
+        return None;
+    }
+    Some(&key[..API_KEY_PREFIX.len() + id.len()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_api_key_prefix() {
+        assert_eq!(
+            api_key_prefix("amk_1a2b3c4d_deadbeef"),
+            Some("amk_1a2b3c4d")
+        );
+        assert_eq!(api_key_prefix("amk_1a2b3c4d_"), None);
+        assert_eq!(api_key_prefix("amk_1a2b3c4d"), None);
+        assert_eq!(api_key_prefix("eyJhbGciOiJIUzI1NiJ9.e30.x"), None);
+    }
+
+    #[test]
+    fn test_api_key_scopes_and_expiry() {
+        let now = Utc::now();
+        let mut key = ApiKey {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            name: "ERP".to_string(),
+            prefix: "amk_1a2b3c4d".to_string(),
+            key_hash: String::new(),
+            scopes: vec!["asset.read".to_string(), "asset.delete".to_string()],
+            expires_at: now + Duration::days(1),
+            last_used_at: None,
+            last_used_ip: None,
+            revoked_at: None,
+            created_by: None,
+            created_at: None,
+            updated_at: None,
+        };
+
+        let role = vec!["asset.read".to_string(), "asset.create".to_string()];
+        assert_eq!(key.effective_permissions(&role), vec!["asset.read"]);
+
+        assert!(key.is_usable(now));
+        assert!(!key.is_usable(now + Duration::days(2)));
+        key.revoked_at = Some(now);
+        assert!(!key.is_usable(now));
+    }
+}
@@ -0,0 +1,195 @@
+//! Approval Workflow Entity
+//!
+//! Configurable approval chains keyed on resource/action. Each level is bound to
+//! one or more RBAC roles; several roles on the same level are signed in parallel.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Wildcard used for resource_type / action_type
+pub const WORKFLOW_WILDCARD: &str = "*";
+
+/// Approval workflow definition
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ApprovalWorkflow {
+    pub id: Uuid,
+    pub resource_type: String,
+    pub action_type: String,
+    pub asset_type: Option<String>, // Optional category code qualifier (e.g. HEAVY_EQ)
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub need_approval: Option<bool>,
+    pub is_active: Option<bool>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub levels: Vec<ApprovalWorkflowLevel>,
+}
+
+/// A role bound to one level of a workflow
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ApprovalWorkflowLevel {
+    pub id: Uuid,
+    pub workflow_id: Uuid,
+    pub level_number: i32,
+    pub role_id: Uuid,
+    pub created_at: Option<DateTime<Utc>>,
+
+    // Joined fields
+    #[sqlx(default)]
+    pub role_code: Option<String>,
+    #[sqlx(default)]
+    pub role_name: Option<String>,
+}
+
+/// Sign-off recorded against an approval request
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ApprovalAction {
+    pub id: Uuid,
+    pub request_id: Uuid,
+    pub level_number: i32,
+    pub role_id: Uuid,
+    pub approver_id: Uuid,
+    pub action: String, // approved, rejected
+    pub notes: Option<String>,
+    pub acted_at: Option<DateTime<Utc>>,
+
+    // Joined fields
+    #[sqlx(default)]
+    pub approver_name: Option<String>,
+    #[sqlx(default)]
+    pub role_code: Option<String>,
+}
+
+impl ApprovalWorkflow {
+    /// Whether requests matching this workflow must be approved at all
+    pub fn requires_approval(&self) -> bool {
+        self.need_approval.unwrap_or(true) && !self.levels.is_empty()
+    }
+
+    /// Distinct level numbers in ascending order
+    pub fn level_numbers(&self) -> Vec<i32> {
+        let mut levels: Vec<i32> = self.levels.iter().map(|l| l.level_number).collect();
+        levels.sort_unstable();
+        levels.dedup();
+        levels
+    }
+
+    pub fn first_level(&self) -> Option<i32> {
+        self.level_numbers().first().copied()
+    }
+
+    /// Level that follows `current`, or None when `current` is the final level
+    pub fn next_level(&self, current: i32) -> Option<i32> {
+        self.level_numbers().into_iter().find(|l| *l > current)
+    }
+
+    /// Roles that must sign at a level
+    pub fn roles_at(&self, level: i32) -> Vec<Uuid> {
+        self.levels
+            .iter()
+            .filter(|l| l.level_number == level)
+            .map(|l| l.role_id)
+            .collect()
+    }
+
+    /// Roles at a level that have not approved yet
+    pub fn pending_roles(&self, level: i32, actions: &[ApprovalAction]) -> Vec<Uuid> {
+        self.roles_at(level)
+            .into_iter()
+            .filter(|role_id| {
+                !actions.iter().any(|a| {
+                    a.level_number == level && a.role_id == *role_id && a.action == "approved"
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workflow(levels: &[(i32, Uuid)]) -> ApprovalWorkflow {
+        let id = Uuid::new_v4();
+        ApprovalWorkflow {
+            id,
+            resource_type: "asset".to_string(),
+            action_type: "CREATE".to_string(),
+            asset_type: None,
+            name: None,
+            description: None,
+            need_approval: Some(true),
+            is_active: Some(true),
+            created_at: None,
+            updated_at: None,
+            levels: levels
+                .iter()
+                .map(|(level_number, role_id)| ApprovalWorkflowLevel {
+                    id: Uuid::new_v4(),
+                    workflow_id: id,
+                    level_number: *level_number,
+                    role_id: *role_id,
+                    created_at: None,
+                    role_code: None,
+                    role_name: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn approved(level_number: i32, role_id: Uuid) -> ApprovalAction {
+        ApprovalAction {
+            id: Uuid::new_v4(),
+            request_id: Uuid::new_v4(),
+            level_number,
+            role_id,
+            approver_id: Uuid::new_v4(),
+            action: "approved".to_string(),
+            notes: None,
+            acted_at: None,
+            approver_name: None,
+            role_code: None,
+        }
+    }
+
+    #[test]
+    fn test_sequential_levels() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let wf = workflow(&[(3, c), (1, a), (2, b)]);
+
+        assert_eq!(wf.first_level(), Some(1));
+        assert_eq!(wf.next_level(1), Some(2));
+        assert_eq!(wf.next_level(2), Some(3));
+        assert_eq!(wf.next_level(3), None);
+    }
+
+    #[test]
+    fn test_parallel_level_requires_every_role() {
+        let (finance, ops, manager) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let wf = workflow(&[(1, finance), (1, ops), (2, manager)]);
+
+        assert_eq!(wf.pending_roles(1, &[]).len(), 2);
+        let actions = vec![approved(1, finance)];
+        assert_eq!(wf.pending_roles(1, &actions), vec![ops]);
+        let actions = vec![approved(1, finance), approved(1, ops)];
+        assert!(wf.pending_roles(1, &actions).is_empty());
+        assert_eq!(wf.next_level(1), Some(2));
+    }
+
+    #[test]
+    fn test_need_approval_flag() {
+        let mut wf = workflow(&[(1, Uuid::new_v4())]);
+        assert!(wf.requires_approval());
+
+        wf.need_approval = Some(false);
+        assert!(!wf.requires_approval());
+
+        let empty = workflow(&[]);
+        assert!(!empty.requires_approval());
+    }
+}
@@ -23,6 +23,7 @@ pub struct Asset {
     pub department_id: Option<Uuid>,
     pub assigned_to: Option<Uuid>,
     pub vendor_id: Option<Uuid>,
+    pub organization_id: Option<Uuid>, // owning tenant
 
     // Classification
     pub is_rental: bool,
@@ -53,6 +54,9 @@ pub struct Asset {
     // QR Code
     pub qr_code_url: Option<String>,
 
+    // Set by the insurance expiry job while no policy covers the asset
+    pub insurance_lapsed_at: Option<DateTime<Utc>>,
+
     // Notes
     pub notes: Option<String>,
 
@@ -75,6 +79,7 @@ impl Asset {
             department_id: None,
             assigned_to: None,
             vendor_id: None,
+            organization_id: None,
             is_rental: false,
             asset_class: None,
             status: AssetState::Planning.as_str().to_string(),
@@ -92,6 +97,7 @@ impl Asset {
             residual_value: None,
             useful_life_months: None,
             qr_code_url: None,
+            insurance_lapsed_at: None,
             notes: None,
             created_at: now,
             updated_at: now,
@@ -42,28 +42,52 @@ pub struct Insurance {
     pub start_date: NaiveDate,
     pub end_date: NaiveDate,
     pub premium_amount: Option<Decimal>,
-    pub status: Option<String>,
+    pub status: String, // active, expired, cancelled
     pub notes: Option<String>,
+    pub expiry_notified_at: Option<DateTime<Utc>>,
+    pub created_by: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Days before `expiry_date` the scheduler reminds about a document
+pub const DOCUMENT_EXPIRY_REMINDER_DAYS: i64 = 30;
+
 /// Asset Document (1:N with Asset)
+///
+/// Replacing a document adds a new version to its group; earlier versions stay
+/// downloadable with `is_current = false`.
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct AssetDocument {
     pub id: Uuid,
     pub asset_id: Uuid,
+    pub document_group_id: Uuid,
+    pub version: i32,
+    pub is_current: bool,
     pub name: String,
+    #[sqlx(rename = "type")]
+    #[serde(rename = "type")]
     pub type_: String, // type is reserved keyword
-    pub file_path: String,
+    #[serde(skip_serializing)]
+    pub file_path: String, // storage key, never exposed
+    pub original_name: Option<String>,
     pub mime_type: Option<String>,
     pub size_bytes: Option<i64>,
     pub expiry_date: Option<NaiveDate>,
     pub notes: Option<String>,
     pub uploaded_by: Option<Uuid>,
+    pub replaced_by: Option<Uuid>,
+    pub replaced_at: Option<DateTime<Utc>>,
+    pub expiry_notified_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-// Renaming for SQLx mapping if needed, usually type needs rename
-// but sqlx generic rename is per struct field
+impl AssetDocument {
+    /// Name offered to the client on download
+    pub fn download_name(&self) -> String {
+        self.original_name
+            .clone()
+            .unwrap_or_else(|| format!("{}-v{}", self.name, self.version))
+    }
+}
@@ -5,6 +5,9 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+/// Approval resource type of lifecycle transitions
+pub const LIFECYCLE_RESOURCE_TYPE: &str = "lifecycle_transition";
+
 /// Asset lifecycle states
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -45,6 +48,11 @@ impl AssetState {
         }
     }
 
+    /// Approval action of a transition into this state
+    pub fn approval_action(&self) -> String {
+        format!("transition_to_{}", self.as_str())
+    }
+
     /// Parse state from string
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
@@ -366,6 +374,18 @@ mod tests {
         assert!(AssetState::Retired.can_transition_to(&AssetState::LostStolen));
     }
 
+    #[test]
+    fn test_approval_action() {
+        assert_eq!(
+            AssetState::Deployed.approval_action(),
+            "transition_to_deployed"
+        );
+        assert_eq!(
+            AssetState::LostStolen.approval_action(),
+            "transition_to_lost_stolen"
+        );
+    }
+
     #[test]
     fn test_terminal_states() {
         assert!(AssetState::Disposed.is_terminal());
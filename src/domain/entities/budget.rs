@@ -0,0 +1,211 @@
+//! Budget Entity
+//!
+//! Budget lines of a cost center (an organization with a `cost_center`) or one of
+//! its departments for a fiscal period, and the spend booked against them.
+//! Approving a work order or conversion commits its estimated cost; completing or
+//! executing it posts the actual spend and releases the commitment.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::domain::errors::{DomainError, DomainResult};
+
+/// Over-budget commitments are escalated as `budget` OVERRUN approval requests
+pub const BUDGET_RESOURCE_TYPE: &str = "budget";
+pub const BUDGET_OVERRUN_ACTION: &str = "OVERRUN";
+
+pub const SPEND_WORK_ORDER: &str = "work_order";
+pub const SPEND_CONVERSION: &str = "conversion";
+pub const SPEND_ASSET_PURCHASE: &str = "asset_purchase";
+
+pub const ENTRY_COMMITMENT: &str = "commitment";
+pub const ENTRY_ACTUAL: &str = "actual";
+
+pub const OVER_BUDGET_BLOCK: &str = "block";
+pub const OVER_BUDGET_ESCALATE: &str = "escalate";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BudgetLine {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub department_id: Option<Uuid>, // None covers the whole cost center
+    pub name: String,
+
+    // Fiscal period
+    pub fiscal_year: i32,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+
+    pub amount: Decimal,
+    pub over_budget_action: String, // block, escalate
+    pub notes: Option<String>,
+
+    pub created_by: Option<Uuid>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl BudgetLine {
+    pub fn validate(&self) -> DomainResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(DomainError::validation("name", "Required"));
+        }
+        if self.amount < Decimal::ZERO {
+            return Err(DomainError::validation("amount", "Cannot be negative"));
+        }
+        if self.period_end < self.period_start {
+            return Err(DomainError::validation(
+                "period_end",
+                "Must not be before period_start",
+            ));
+        }
+        if ![OVER_BUDGET_BLOCK, OVER_BUDGET_ESCALATE].contains(&self.over_budget_action.as_str()) {
+            return Err(DomainError::validation(
+                "over_budget_action",
+                "Must be block or escalate",
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn escalates(&self) -> bool {
+        self.over_budget_action == OVER_BUDGET_ESCALATE
+    }
+}
+
+/// Commitment or actual spend of one source (work order, conversion, purchase)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BudgetEntry {
+    pub id: Uuid,
+    pub budget_line_id: Uuid,
+    pub source_type: String, // work_order, conversion, asset_purchase
+    pub source_id: Uuid,
+    pub asset_id: Option<Uuid>,
+    pub entry_type: String, // commitment, actual
+    pub amount: Decimal,
+    pub entry_date: NaiveDate,
+
+    pub released_at: Option<DateTime<Utc>>,
+    pub approval_request_id: Option<Uuid>,
+
+    pub created_by: Option<Uuid>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// Budget against open commitments and actual spend for one line
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BudgetPosition {
+    pub budget_line_id: Uuid,
+    pub organization_id: Uuid,
+    pub organization_code: String,
+    pub cost_center: Option<String>,
+    pub department_id: Option<Uuid>,
+    pub department_name: Option<String>,
+    pub name: String,
+    pub fiscal_year: i32,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub over_budget_action: String,
+
+    pub amount: Decimal,
+    /// Commitments not yet released by an actual
+    pub committed: Decimal,
+    pub actual: Decimal,
+    /// Amount less commitments and actual spend; negative when overrun
+    pub available: Decimal,
+}
+
+impl BudgetPosition {
+    /// Whether `spend` still fits, ignoring an earlier commitment of the same source
+    pub fn can_absorb(&self, spend: Decimal, replaced_commitment: Decimal) -> bool {
+        spend <= self.available + replaced_commitment
+    }
+
+    /// Share of the budget used by commitments and actual spend, in percent
+    pub fn utilization(&self) -> Option<Decimal> {
+        if self.amount.is_zero() {
+            return None;
+        }
+        Some(((self.committed + self.actual) * Decimal::from(100) / self.amount).round_dp(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line() -> BudgetLine {
+        BudgetLine {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::new_v4(),
+            department_id: None,
+            name: "Maintenance 2026".to_string(),
+            fiscal_year: 2026,
+            period_start: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2026, 12, 31).unwrap(),
+            amount: Decimal::from(1_000),
+            over_budget_action: OVER_BUDGET_BLOCK.to_string(),
+            notes: None,
+            created_by: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn position(amount: i64, committed: i64, actual: i64) -> BudgetPosition {
+        BudgetPosition {
+            budget_line_id: Uuid::new_v4(),
+            organization_id: Uuid::new_v4(),
+            organization_code: "HQ".to_string(),
+            cost_center: Some("CC-100".to_string()),
+            department_id: None,
+            department_name: None,
+            name: "Maintenance 2026".to_string(),
+            fiscal_year: 2026,
+            period_start: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2026, 12, 31).unwrap(),
+            over_budget_action: OVER_BUDGET_BLOCK.to_string(),
+            amount: Decimal::from(amount),
+            committed: Decimal::from(committed),
+            actual: Decimal::from(actual),
+            available: Decimal::from(amount - committed - actual),
+        }
+    }
+
+    #[test]
+    fn test_budget_line_validation() {
+        assert!(line().validate().is_ok());
+
+        let mut l = line();
+        l.period_end = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+        assert!(l.validate().is_err());
+
+        let mut l = line();
+        l.amount = Decimal::from(-1);
+        assert!(l.validate().is_err());
+
+        let mut l = line();
+        l.over_budget_action = "ignore".to_string();
+        assert!(l.validate().is_err());
+
+        let mut l = line();
+        l.over_budget_action = OVER_BUDGET_ESCALATE.to_string();
+        assert!(l.validate().is_ok());
+        assert!(l.escalates());
+    }
+
+    #[test]
+    fn test_budget_position_absorbs_spend() {
+        let p = position(1_000, 300, 500);
+        assert!(p.can_absorb(Decimal::from(200), Decimal::ZERO));
+        assert!(!p.can_absorb(Decimal::from(201), Decimal::ZERO));
+        // Re-committing a source replaces its earlier commitment
+        assert!(p.can_absorb(Decimal::from(450), Decimal::from(300)));
+        assert_eq!(p.utilization(), Some(Decimal::from(80)));
+
+        assert_eq!(position(0, 0, 0).utilization(), None);
+    }
+}
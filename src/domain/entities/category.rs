@@ -23,10 +23,14 @@ pub struct Category {
     #[sqlx(rename = "depreciation_period_months")]
     pub depreciation_period: Option<i32>, // in months
     pub residual_rate: Option<rust_decimal::Decimal>,
+    pub declining_balance_rate: Option<rust_decimal::Decimal>, // annual
+    pub estimated_total_hours: Option<rust_decimal::Decimal>,  // units of production
 
     // Custom attributes schema for this category
     #[sqlx(rename = "attributes")]
     pub attributes_schema: Option<JsonValue>,
+    /// `SpecificationSchema` asset specifications are validated against
+    pub specification_schema: Option<JsonValue>,
 
     // Classification fields
     pub main_category: Option<String>,
@@ -53,7 +57,10 @@ impl Category {
             depreciation_method: Some("straight_line".to_string()),
             depreciation_period: None,
             residual_rate: None,
+            declining_balance_rate: None,
+            estimated_total_hours: None,
             attributes_schema: None,
+            specification_schema: None,
             main_category: None,
             sub_category_letter: None,
             example_assets: None,
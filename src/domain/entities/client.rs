@@ -27,10 +27,9 @@ pub struct Client {
 }
 
 impl Client {
-    /// Create a new client with auto-generated code
-    pub fn new(name: String, company_name: Option<String>) -> Self {
+    /// Create a new client with a code from the document number service
+    pub fn new(client_code: String, name: String, company_name: Option<String>) -> Self {
         let now = Utc::now();
-        let client_code = format!("CLT-{}", now.format("%Y%m%d%H%M%S"));
 
         Self {
             id: Uuid::new_v4(),
@@ -0,0 +1,791 @@
+//! Depreciation Entity
+//!
+//! Monthly depreciation schedules. Depreciation starts in the month the asset was
+//! purchased (full-month convention) and never takes the book value below residual.
+
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::domain::errors::{DomainError, DomainResult};
+
+/// Useful life when neither the asset nor its category sets one
+pub const DEFAULT_USEFUL_LIFE_MONTHS: i32 = 60;
+
+/// Declining balance multiple of the straight-line rate when the category sets no rate
+const DECLINING_BALANCE_FACTOR: Decimal = dec!(1.5);
+
+/// Depreciation method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepreciationMethod {
+    StraightLine,
+    DecliningBalance,
+    DoubleDecliningBalance,
+    SumOfYearsDigits,
+    UnitsOfProduction,
+}
+
+impl DepreciationMethod {
+    pub const ALL: [DepreciationMethod; 5] = [
+        Self::StraightLine,
+        Self::DecliningBalance,
+        Self::DoubleDecliningBalance,
+        Self::SumOfYearsDigits,
+        Self::UnitsOfProduction,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::StraightLine => "straight_line",
+            Self::DecliningBalance => "declining_balance",
+            Self::DoubleDecliningBalance => "double_declining_balance",
+            Self::SumOfYearsDigits => "sum_of_years_digits",
+            Self::UnitsOfProduction => "units_of_production",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.as_str() == s)
+    }
+
+    /// Validate a method name from a request
+    pub fn validate(s: &str) -> DomainResult<Self> {
+        Self::parse(s).ok_or_else(|| {
+            DomainError::validation(
+                "depreciation_method",
+                &format!(
+                    "Must be one of: {}",
+                    Self::ALL.map(|m| m.as_str()).join(", ")
+                ),
+            )
+        })
+    }
+}
+
+/// Everything needed to build one asset's schedule
+#[derive(Debug, Clone)]
+pub struct DepreciationInput {
+    pub method: DepreciationMethod,
+    pub cost: Decimal,
+    pub residual: Decimal,
+    pub useful_life_months: i32,
+    /// Purchase date; the first period is its month
+    pub start: NaiveDate,
+    /// Annual rate for declining balance (None = 150% of straight-line)
+    pub declining_rate: Option<Decimal>,
+    /// Lifetime units for units-of-production
+    pub total_units: Option<Decimal>,
+    /// Units used per month, keyed by the first day of the month
+    pub monthly_units: BTreeMap<NaiveDate, Decimal>,
+    /// Units-of-production schedules run up to this date (usage is not forecast)
+    pub through: NaiveDate,
+    /// Carrying amounts set by revaluations or impairments, keyed by the first day
+    /// of the month they take effect
+    pub revaluations: BTreeMap<NaiveDate, Decimal>,
+}
+
+/// One month of a computed schedule
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DepreciationPeriod {
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub opening_value: Decimal,
+    pub depreciation_amount: Decimal,
+    pub accumulated_depreciation: Decimal,
+    pub closing_value: Decimal,
+    pub usage_units: Option<Decimal>,
+    /// Change in carrying amount booked at the start of the month (revaluation or impairment)
+    pub valuation_adjustment: Decimal,
+}
+
+/// First day of the month containing `date`
+pub fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+fn add_months(date: NaiveDate, months: u32) -> NaiveDate {
+    date.checked_add_months(Months::new(months))
+        .unwrap_or(NaiveDate::MAX)
+}
+
+/// Whole months from the month of `from` to the month of `to`
+fn months_between(from: NaiveDate, to: NaiveDate) -> i32 {
+    (to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32
+}
+
+impl DepreciationInput {
+    fn validate(&self) -> DomainResult<()> {
+        if self.cost <= Decimal::ZERO {
+            return Err(DomainError::validation(
+                "purchase_price",
+                "Must be greater than zero",
+            ));
+        }
+        if self.residual < Decimal::ZERO || self.residual > self.cost {
+            return Err(DomainError::validation(
+                "residual_value",
+                "Must be between zero and the purchase price",
+            ));
+        }
+        if self.useful_life_months <= 0 {
+            return Err(DomainError::validation(
+                "useful_life_months",
+                "Must be greater than zero",
+            ));
+        }
+        if self.method == DepreciationMethod::UnitsOfProduction
+            && self.total_units.is_none_or(|u| u <= Decimal::ZERO)
+        {
+            return Err(DomainError::validation(
+                "estimated_total_hours",
+                "Units of production needs the category's estimated total hours",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Build the monthly schedule.
+    ///
+    /// Time-based methods cover the whole useful life; units-of-production covers the
+    /// months up to `through`, stopping early once fully depreciated. A revaluation
+    /// restarts the schedule from the new carrying amount over the remaining life.
+    pub fn schedule(&self) -> DomainResult<Vec<DepreciationPeriod>> {
+        self.validate()?;
+
+        let mut periods = self.base_schedule();
+        for (&month, &carrying) in self.revaluations.range(month_start(self.start)..) {
+            periods = self.revalue(periods, month, carrying);
+        }
+        Ok(periods)
+    }
+
+    /// Replace the periods from `month` on with a schedule that starts from `carrying`
+    fn revalue(
+        &self,
+        mut periods: Vec<DepreciationPeriod>,
+        month: NaiveDate,
+        carrying: Decimal,
+    ) -> Vec<DepreciationPeriod> {
+        periods.retain(|p| p.period_start < month);
+        let (opening, accumulated) = periods
+            .last()
+            .map(|p| (p.closing_value, p.accumulated_depreciation))
+            .unwrap_or((self.cost, Decimal::ZERO));
+
+        let carrying = carrying.max(Decimal::ZERO);
+        let life_left = self.useful_life_months - months_between(self.start, month);
+        let units_left = self.total_units.map(|total| {
+            total
+                - self
+                    .monthly_units
+                    .range(..month)
+                    .map(|(_, u)| *u)
+                    .sum::<Decimal>()
+        });
+        // Nothing left to depreciate: carry the new amount as is
+        let exhausted = match self.method {
+            DepreciationMethod::UnitsOfProduction => units_left.is_none_or(|u| u <= Decimal::ZERO),
+            _ => life_left <= 0,
+        };
+
+        let rest = DepreciationInput {
+            method: self.method,
+            cost: carrying,
+            residual: if exhausted {
+                carrying
+            } else {
+                self.residual.min(carrying)
+            },
+            useful_life_months: life_left.max(1),
+            start: month,
+            declining_rate: self.declining_rate,
+            total_units: units_left.filter(|u| *u > Decimal::ZERO),
+            monthly_units: self.monthly_units.clone(),
+            through: self.through,
+            revaluations: BTreeMap::new(),
+        };
+
+        let mut rest_periods = rest.base_schedule();
+        for period in rest_periods.iter_mut() {
+            period.accumulated_depreciation += accumulated;
+        }
+        if let Some(first) = rest_periods.first_mut() {
+            first.opening_value = opening;
+            first.valuation_adjustment = carrying - opening;
+        }
+        periods.extend(rest_periods);
+        periods
+    }
+
+    fn base_schedule(&self) -> Vec<DepreciationPeriod> {
+        let first = month_start(self.start);
+        let life = self.useful_life_months as u32;
+        let months = match self.method {
+            DepreciationMethod::UnitsOfProduction => {
+                let last = month_start(self.through);
+                if last < first {
+                    0
+                } else {
+                    (months_between(first, last) + 1) as u32
+                }
+            }
+            _ => life,
+        };
+
+        let depreciable = self.cost - self.residual;
+        let mut periods = Vec::with_capacity(months as usize);
+        let mut book = self.cost;
+
+        for m in 0..months {
+            let period_start = add_months(first, m);
+            let period_end = add_months(first, m + 1).pred_opt().unwrap_or(period_start);
+            let remaining = book - self.residual;
+            let units = self.monthly_units.get(&period_start).copied();
+
+            let amount = if m + 1 == life && self.method != DepreciationMethod::UnitsOfProduction {
+                // Final month absorbs rounding
+                remaining
+            } else {
+                self.monthly_amount(m, book, depreciable, units)
+                    .round_dp(2)
+                    .clamp(Decimal::ZERO, remaining)
+            };
+
+            book -= amount;
+            periods.push(DepreciationPeriod {
+                period_start,
+                period_end,
+                opening_value: book + amount,
+                depreciation_amount: amount,
+                accumulated_depreciation: self.cost - book,
+                closing_value: book,
+                usage_units: match self.method {
+                    DepreciationMethod::UnitsOfProduction => Some(units.unwrap_or_default()),
+                    _ => None,
+                },
+                valuation_adjustment: Decimal::ZERO,
+            });
+
+            if self.method == DepreciationMethod::UnitsOfProduction && book <= self.residual {
+                break;
+            }
+        }
+
+        periods
+    }
+
+    fn monthly_amount(
+        &self,
+        month: u32,
+        book: Decimal,
+        depreciable: Decimal,
+        units: Option<Decimal>,
+    ) -> Decimal {
+        let life = Decimal::from(self.useful_life_months);
+        match self.method {
+            DepreciationMethod::StraightLine => depreciable / life,
+            DepreciationMethod::DecliningBalance | DepreciationMethod::DoubleDecliningBalance => {
+                let annual = match (self.method, self.declining_rate) {
+                    (DepreciationMethod::DecliningBalance, Some(rate)) => rate,
+                    (DepreciationMethod::DecliningBalance, None) => {
+                        DECLINING_BALANCE_FACTOR * dec!(12) / life
+                    }
+                    _ => dec!(2) * dec!(12) / life,
+                };
+                let declining = book * annual / dec!(12);
+                // Switch to straight-line over the remaining life once that is larger
+                let remaining_months = Decimal::from(self.useful_life_months as u32 - month);
+                let straight = (book - self.residual) / remaining_months;
+                declining.max(straight)
+            }
+            DepreciationMethod::SumOfYearsDigits => {
+                let life = self.useful_life_months as u32;
+                let years = life.div_ceil(12);
+                let year = month / 12;
+                let digits = Decimal::from(years * (years + 1) / 2);
+                let months_in_year = Decimal::from((life - year * 12).min(12));
+                depreciable * Decimal::from(years - year) / digits / months_in_year
+            }
+            DepreciationMethod::UnitsOfProduction => match self.total_units {
+                Some(total) => depreciable * units.unwrap_or_default() / total,
+                None => Decimal::ZERO,
+            },
+        }
+    }
+}
+
+/// Asset and category data the engine reads
+#[derive(Debug, Clone, FromRow)]
+pub struct DepreciationProfile {
+    pub asset_id: Uuid,
+    pub purchase_date: Option<NaiveDate>,
+    pub purchase_price: Option<Decimal>,
+    pub residual_value: Option<Decimal>,
+    pub useful_life_months: Option<i32>,
+    pub depreciation_method: Option<String>,
+    pub depreciation_period_months: Option<i32>,
+    pub residual_rate: Option<Decimal>,
+    pub declining_balance_rate: Option<Decimal>,
+    pub estimated_total_hours: Option<Decimal>,
+}
+
+impl DepreciationProfile {
+    pub fn method(&self) -> DepreciationMethod {
+        self.depreciation_method
+            .as_deref()
+            .and_then(DepreciationMethod::parse)
+            .unwrap_or(DepreciationMethod::StraightLine)
+    }
+
+    /// Engine input, or None when the asset has no purchase price or date
+    pub fn input(
+        &self,
+        monthly_units: BTreeMap<NaiveDate, Decimal>,
+        revaluations: BTreeMap<NaiveDate, Decimal>,
+        through: NaiveDate,
+    ) -> Option<DepreciationInput> {
+        let cost = self.purchase_price?;
+        let start = self.purchase_date?;
+
+        Some(DepreciationInput {
+            method: self.method(),
+            cost,
+            residual: self
+                .residual_value
+                .or_else(|| self.residual_rate.map(|r| (cost * r).round_dp(2)))
+                .unwrap_or(Decimal::ZERO),
+            useful_life_months: self
+                .useful_life_months
+                .or(self.depreciation_period_months)
+                .unwrap_or(DEFAULT_USEFUL_LIFE_MONTHS),
+            start,
+            declining_rate: self.declining_balance_rate,
+            total_units: self.estimated_total_hours,
+            monthly_units,
+            through,
+            revaluations,
+        })
+    }
+}
+
+/// Persisted schedule row
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DepreciationSchedule {
+    pub id: Uuid,
+    pub asset_id: Uuid,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub opening_value: Decimal,
+    pub depreciation_amount: Decimal,
+    pub accumulated_depreciation: Decimal,
+    pub closing_value: Decimal,
+    pub depreciation_method: String,
+    pub usage_units: Option<Decimal>,
+    pub valuation_adjustment: Decimal,
+    pub is_calculated: Option<bool>,
+    pub calculated_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+    /// Close that posted the row
+    pub closing_id: Option<Uuid>,
+    pub posted_at: Option<DateTime<Utc>>,
+}
+
+/// An asset's depreciation position at a date, from its schedule
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct AssetDepreciationPosition {
+    pub asset_id: Uuid,
+    pub asset_code: String,
+    pub name: String,
+    pub purchase_date: Option<NaiveDate>,
+    pub purchase_price: Decimal,
+    pub useful_life_months: Option<i32>,
+    pub residual_value: Option<Decimal>,
+    pub depreciation_method: Option<String>,
+    /// Depreciation charged in the month of the as-of date
+    pub period_depreciation: Decimal,
+    pub accumulated_depreciation: Decimal,
+    /// Net revaluations and impairments booked up to the as-of date
+    pub valuation_adjustment: Decimal,
+    pub book_value: Decimal,
+}
+
+/// Keep the periods after `closed_through`, the last month already posted for the
+/// asset. The first open month absorbs any difference between the recomputed and
+/// the posted accumulated depreciation (cumulative catch-up), so closed months
+/// never change.
+pub fn rebase_after_close(
+    periods: Vec<DepreciationPeriod>,
+    closed_through: NaiveDate,
+    posted_accumulated: Decimal,
+) -> Vec<DepreciationPeriod> {
+    let mut open: Vec<DepreciationPeriod> = periods
+        .into_iter()
+        .filter(|p| p.period_start > closed_through)
+        .collect();
+
+    if let Some(first) = open.first_mut() {
+        first.depreciation_amount = first.accumulated_depreciation - posted_accumulated;
+        first.opening_value =
+            first.closing_value + first.depreciation_amount - first.valuation_adjustment;
+    }
+    open
+}
+
+// ==================== PERIOD CLOSE ====================
+
+pub const CLOSING_CLOSED: &str = "closed";
+pub const CLOSING_REOPENED: &str = "reopened";
+
+/// Approval resource/action for reopening a closed period
+pub const CLOSING_RESOURCE_TYPE: &str = "depreciation_period";
+pub const CLOSING_REOPEN_ACTION: &str = "REOPEN";
+
+/// A month-end depreciation close
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DepreciationClosing {
+    pub id: Uuid,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub status: String,
+    pub asset_count: i32,
+    pub total_depreciation: Decimal,
+    pub total_accumulated_depreciation: Decimal,
+    pub total_book_value: Decimal,
+    pub closed_by: Option<Uuid>,
+    pub closed_at: DateTime<Utc>,
+    pub reopen_request_id: Option<Uuid>,
+    pub reopen_reason: Option<String>,
+    pub reopened_by: Option<Uuid>,
+    pub reopened_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl DepreciationClosing {
+    pub fn is_closed(&self) -> bool {
+        self.status == CLOSING_CLOSED
+    }
+
+    /// Check that `period_start` may be closed given the existing closes.
+    ///
+    /// Only finished months can be closed. Reopened months are closed again in
+    /// order before any later month, and a new month must follow the last close.
+    pub fn check_close(
+        period_start: NaiveDate,
+        today: NaiveDate,
+        closings: &[DepreciationClosing],
+    ) -> DomainResult<()> {
+        if period_start >= month_start(today) {
+            return Err(DomainError::business_rule(
+                "depreciation_close",
+                "Only months that have ended can be closed",
+            ));
+        }
+        if closings
+            .iter()
+            .any(|c| c.period_start == period_start && c.is_closed())
+        {
+            return Err(DomainError::conflict(&format!(
+                "Depreciation period {} is already closed",
+                period_start.format("%Y-%m")
+            )));
+        }
+        if let Some(reopened) = closings
+            .iter()
+            .filter(|c| !c.is_closed())
+            .map(|c| c.period_start)
+            .min()
+        {
+            if reopened != period_start {
+                return Err(DomainError::business_rule(
+                    "depreciation_close",
+                    &format!(
+                        "Reopened period {} must be closed first",
+                        reopened.format("%Y-%m")
+                    ),
+                ));
+            }
+            return Ok(());
+        }
+        if let Some(last) = closings
+            .iter()
+            .filter(|c| c.is_closed())
+            .map(|c| c.period_start)
+            .max()
+        {
+            if period_start < last {
+                return Err(DomainError::business_rule(
+                    "depreciation_close",
+                    &format!("Periods up to {} are already closed", last.format("%Y-%m")),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that this close may be reopened: only the latest closed month, one
+    /// month at a time, so posted rows always form a closed prefix of each schedule
+    pub fn check_reopen(&self, closings: &[DepreciationClosing]) -> DomainResult<()> {
+        if !self.is_closed() {
+            return Err(DomainError::business_rule(
+                "depreciation_reopen",
+                &format!("Period {} is not closed", self.period_start.format("%Y-%m")),
+            ));
+        }
+        if closings
+            .iter()
+            .any(|c| c.is_closed() && c.period_start > self.period_start)
+        {
+            return Err(DomainError::business_rule(
+                "depreciation_reopen",
+                "Later closed periods must be reopened first",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Close report line: one category, department or location
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct DepreciationCloseLine {
+    pub group_id: Option<Uuid>,
+    pub group_name: String,
+    pub asset_count: i64,
+    pub original_cost: Decimal,
+    pub period_depreciation: Decimal,
+    pub accumulated_depreciation: Decimal,
+    pub book_value: Decimal,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(
+        method: DepreciationMethod,
+        cost: Decimal,
+        residual: Decimal,
+        life: i32,
+    ) -> DepreciationInput {
+        DepreciationInput {
+            method,
+            cost,
+            residual,
+            useful_life_months: life,
+            start: NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+            declining_rate: None,
+            total_units: None,
+            monthly_units: BTreeMap::new(),
+            through: NaiveDate::from_ymd_opt(2025, 12, 31).unwrap(),
+            revaluations: BTreeMap::new(),
+        }
+    }
+
+    fn total(periods: &[DepreciationPeriod]) -> Decimal {
+        periods.iter().map(|p| p.depreciation_amount).sum()
+    }
+
+    #[test]
+    fn test_straight_line() {
+        let periods = input(
+            DepreciationMethod::StraightLine,
+            dec!(10000),
+            dec!(1000),
+            36,
+        )
+        .schedule()
+        .unwrap();
+        assert_eq!(periods.len(), 36);
+        assert_eq!(
+            periods[0].period_start,
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()
+        );
+        assert_eq!(
+            periods[0].period_end,
+            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap()
+        );
+        assert_eq!(periods[0].depreciation_amount, dec!(250));
+        assert_eq!(periods[35].closing_value, dec!(1000));
+        assert_eq!(total(&periods), dec!(9000));
+    }
+
+    #[test]
+    fn test_declining_balance_reaches_residual() {
+        for method in [
+            DepreciationMethod::DecliningBalance,
+            DepreciationMethod::DoubleDecliningBalance,
+        ] {
+            let periods = input(method, dec!(10000), dec!(1000), 60)
+                .schedule()
+                .unwrap();
+            assert_eq!(periods.len(), 60);
+            assert_eq!(periods[59].closing_value, dec!(1000));
+            assert!(periods[0].depreciation_amount > periods[59].depreciation_amount);
+        }
+
+        let ddb = input(
+            DepreciationMethod::DoubleDecliningBalance,
+            dec!(10000),
+            dec!(1000),
+            60,
+        )
+        .schedule()
+        .unwrap();
+        // 40% a year on the opening balance
+        assert_eq!(ddb[0].depreciation_amount, dec!(333.33));
+    }
+
+    #[test]
+    fn test_sum_of_years_digits() {
+        let periods = input(
+            DepreciationMethod::SumOfYearsDigits,
+            dec!(15000),
+            dec!(0),
+            60,
+        )
+        .schedule()
+        .unwrap();
+        // Year 1 carries 5/15 of the base, year 5 carries 1/15
+        assert_eq!(periods[0].depreciation_amount, dec!(416.67));
+        assert_eq!(periods[59].closing_value, dec!(0));
+        assert_eq!(total(&periods[0..12]).round_dp(0), dec!(5000));
+        assert_eq!(total(&periods[48..60]).round_dp(0), dec!(1000));
+    }
+
+    #[test]
+    fn test_units_of_production() {
+        let mut uop = input(
+            DepreciationMethod::UnitsOfProduction,
+            dec!(10000),
+            dec!(0),
+            60,
+        );
+        assert!(uop.schedule().is_err());
+
+        uop.total_units = Some(dec!(1000));
+        uop.monthly_units
+            .insert(NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(), dec!(150));
+        uop.through = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+
+        let periods = uop.schedule().unwrap();
+        assert_eq!(periods.len(), 3);
+        assert_eq!(periods[0].depreciation_amount, dec!(0));
+        assert_eq!(periods[1].depreciation_amount, dec!(1500));
+        assert_eq!(periods[1].usage_units, Some(dec!(150)));
+        assert_eq!(periods[2].closing_value, dec!(8500));
+    }
+
+    #[test]
+    fn test_revaluation_restarts_schedule() {
+        let mut sl = input(
+            DepreciationMethod::StraightLine,
+            dec!(10000),
+            dec!(1000),
+            36,
+        );
+        // After 12 months the book value is 7000; an appraisal puts it at 8200
+        let month = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        sl.revaluations.insert(month, dec!(8200));
+
+        let periods = sl.schedule().unwrap();
+        assert_eq!(periods.len(), 36);
+        assert_eq!(periods[11].closing_value, dec!(7000));
+        assert_eq!(periods[12].period_start, month);
+        assert_eq!(periods[12].opening_value, dec!(7000));
+        assert_eq!(periods[12].valuation_adjustment, dec!(1200));
+        // 7200 left to depreciate over the remaining 24 months
+        assert_eq!(periods[12].depreciation_amount, dec!(300));
+        assert_eq!(periods[12].closing_value, dec!(7900));
+        assert_eq!(periods[12].accumulated_depreciation, dec!(3300));
+        assert_eq!(periods[35].closing_value, dec!(1000));
+
+        // Impairment below residual: nothing left to depreciate
+        sl.revaluations.insert(month, dec!(500));
+        let periods = sl.schedule().unwrap();
+        assert_eq!(periods[12].valuation_adjustment, dec!(-6500));
+        assert_eq!(periods[12].depreciation_amount, dec!(0));
+        assert_eq!(periods[35].closing_value, dec!(500));
+    }
+
+    fn closing(month: u32, status: &str) -> DepreciationClosing {
+        let period_start = NaiveDate::from_ymd_opt(2025, month, 1).unwrap();
+        DepreciationClosing {
+            id: Uuid::new_v4(),
+            period_start,
+            period_end: add_months(period_start, 1).pred_opt().unwrap(),
+            status: status.to_string(),
+            asset_count: 0,
+            total_depreciation: Decimal::ZERO,
+            total_accumulated_depreciation: Decimal::ZERO,
+            total_book_value: Decimal::ZERO,
+            closed_by: None,
+            closed_at: Utc::now(),
+            reopen_request_id: None,
+            reopen_reason: None,
+            reopened_by: None,
+            reopened_at: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_rebase_after_close() {
+        let periods = input(
+            DepreciationMethod::StraightLine,
+            dec!(10000),
+            dec!(1000),
+            36,
+        )
+        .schedule()
+        .unwrap();
+        let closed_through = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+
+        // Posted 3 x 200 before the basis changed to 250 a month
+        let open = rebase_after_close(periods, closed_through, dec!(600));
+        assert_eq!(open.len(), 33);
+        assert_eq!(
+            open[0].period_start,
+            NaiveDate::from_ymd_opt(2025, 4, 1).unwrap()
+        );
+        assert_eq!(open[0].depreciation_amount, dec!(400));
+        assert_eq!(open[0].accumulated_depreciation, dec!(1000));
+        assert_eq!(open[0].opening_value, dec!(9400));
+        assert_eq!(open[1].depreciation_amount, dec!(250));
+        assert_eq!(open[32].closing_value, dec!(1000));
+    }
+
+    #[test]
+    fn test_close_order() {
+        let today = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        let may = NaiveDate::from_ymd_opt(2025, 5, 1).unwrap();
+        let april = NaiveDate::from_ymd_opt(2025, 4, 1).unwrap();
+
+        assert!(DepreciationClosing::check_close(may, today, &[]).is_ok());
+        assert!(DepreciationClosing::check_close(month_start(today), today, &[]).is_err());
+
+        let closed = vec![closing(3, CLOSING_CLOSED), closing(4, CLOSING_CLOSED)];
+        assert!(DepreciationClosing::check_close(april, today, &closed).is_err());
+        assert!(DepreciationClosing::check_close(may, today, &closed).is_ok());
+        assert!(closed[0].check_reopen(&closed).is_err());
+        assert!(closed[1].check_reopen(&closed).is_ok());
+
+        let reopened = vec![closing(3, CLOSING_CLOSED), closing(4, CLOSING_REOPENED)];
+        assert!(DepreciationClosing::check_close(may, today, &reopened).is_err());
+        assert!(DepreciationClosing::check_close(april, today, &reopened).is_ok());
+        assert!(reopened[1].check_reopen(&reopened).is_err());
+    }
+
+    #[test]
+    fn test_method_names() {
+        for method in DepreciationMethod::ALL {
+            assert_eq!(DepreciationMethod::parse(method.as_str()), Some(method));
+        }
+        assert!(DepreciationMethod::validate("linear").is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! Document Number Entity
+//!
+//! Numbers of loans, work orders, rentals, clients, conversions and invoices
+//! come from per-type counters in the database. A format decides the prefix,
+//! an optional department or location segment, when the counter restarts and
+//! how far it is zero-padded: `LN-2026-00042`, `WO-IT-202610-00007`.
+
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const DOC_LOAN: &str = "loan";
+pub const DOC_WORK_ORDER: &str = "work_order";
+pub const DOC_RENTAL: &str = "rental";
+pub const DOC_CLIENT: &str = "client";
+pub const DOC_CONVERSION: &str = "conversion";
+pub const DOC_INVOICE: &str = "invoice";
+
+/// Numbered without gaps: the number is taken in the transaction that stores
+/// the document, so a failed write gives it back
+pub const GAP_FREE_DOCUMENT_TYPES: [&str; 1] = [DOC_INVOICE];
+
+pub const RESET_NEVER: &str = "never";
+pub const RESET_YEARLY: &str = "yearly";
+pub const RESET_MONTHLY: &str = "monthly";
+pub const RESET_PERIODS: [&str; 3] = [RESET_NEVER, RESET_YEARLY, RESET_MONTHLY];
+
+pub const SEGMENT_NONE: &str = "none";
+pub const SEGMENT_DEPARTMENT: &str = "department";
+pub const SEGMENT_LOCATION: &str = "location";
+pub const NUMBER_SEGMENTS: [&str; 3] = [SEGMENT_NONE, SEGMENT_DEPARTMENT, SEGMENT_LOCATION];
+
+/// Longest department or location code put in a number
+pub const MAX_SEGMENT_LENGTH: usize = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DocumentNumberFormat {
+    pub document_type: String,
+    pub prefix: String,
+    pub reset_period: String, // never, yearly, monthly
+    pub padding: i32,
+    pub segment: String, // none, department, location
+    pub updated_by: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl DocumentNumberFormat {
+    pub fn is_gap_free(&self) -> bool {
+        GAP_FREE_DOCUMENT_TYPES.contains(&self.document_type.as_str())
+    }
+
+    /// Counter period a date falls in; empty when the counter never restarts
+    pub fn period_key(&self, date: NaiveDate) -> String {
+        match self.reset_period.as_str() {
+            RESET_YEARLY => format!("{:04}", date.year()),
+            RESET_MONTHLY => format!("{:04}{:02}", date.year(), date.month()),
+            _ => String::new(),
+        }
+    }
+
+    /// Build the number for a counter value
+    pub fn format(&self, segment: Option<&str>, period_key: &str, value: i64) -> String {
+        let counter = format!("{:0width$}", value, width = self.padding.max(1) as usize);
+        let mut parts = vec![self.prefix.as_str()];
+        parts.extend(segment.filter(|s| !s.is_empty()));
+        if !period_key.is_empty() {
+            parts.push(period_key);
+        }
+        parts.push(&counter);
+        parts.join("-")
+    }
+}
+
+/// Department and location code as it appears in a number: upper-case
+/// letters and digits only. None when nothing is left.
+pub fn segment_code(code: &str) -> Option<String> {
+    let cleaned: String = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .take(MAX_SEGMENT_LENGTH)
+        .collect();
+    (!cleaned.is_empty()).then_some(cleaned)
+}
+
+/// What a document is numbered for. The segment comes from the department or
+/// location given here, else from the asset's.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NumberingScope {
+    pub asset_id: Option<Uuid>,
+    pub department_id: Option<Uuid>,
+    pub location_id: Option<Uuid>,
+}
+
+impl NumberingScope {
+    pub fn asset(asset_id: Uuid) -> Self {
+        Self {
+            asset_id: Some(asset_id),
+            ..Self::default()
+        }
+    }
+}
+
+/// The counter a number is drawn from and how to spell its values
+#[derive(Debug, Clone)]
+pub struct NumberSeries {
+    pub format: DocumentNumberFormat,
+    pub segment: Option<String>,
+    pub period_key: String,
+}
+
+impl NumberSeries {
+    pub fn document_type(&self) -> &str {
+        &self.format.document_type
+    }
+
+    pub fn segment_key(&self) -> &str {
+        self.segment.as_deref().unwrap_or_default()
+    }
+
+    pub fn number(&self, value: i64) -> String {
+        self.format
+            .format(self.segment.as_deref(), &self.period_key, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(document_type: &str, reset_period: &str, padding: i32) -> DocumentNumberFormat {
+        DocumentNumberFormat {
+            document_type: document_type.to_string(),
+            prefix: "WO".to_string(),
+            reset_period: reset_period.to_string(),
+            padding,
+            segment: SEGMENT_NONE.to_string(),
+            updated_by: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_period_keys() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 9).unwrap();
+        assert_eq!(format(DOC_WORK_ORDER, RESET_NEVER, 5).period_key(date), "");
+        assert_eq!(
+            format(DOC_WORK_ORDER, RESET_YEARLY, 5).period_key(date),
+            "2026"
+        );
+        assert_eq!(
+            format(DOC_WORK_ORDER, RESET_MONTHLY, 5).period_key(date),
+            "202603"
+        );
+    }
+
+    #[test]
+    fn test_number_layout() {
+        let yearly = format(DOC_WORK_ORDER, RESET_YEARLY, 5);
+        assert_eq!(yearly.format(None, "2026", 42), "WO-2026-00042");
+        assert_eq!(yearly.format(Some("IT"), "2026", 42), "WO-IT-2026-00042");
+        assert_eq!(yearly.format(Some(""), "2026", 7), "WO-2026-00007");
+
+        let never = format(DOC_WORK_ORDER, RESET_NEVER, 3);
+        assert_eq!(never.format(None, "", 7), "WO-007");
+        // Counters outgrow their padding rather than wrap
+        assert_eq!(never.format(None, "", 12345), "WO-12345");
+    }
+
+    #[test]
+    fn test_segment_codes() {
+        assert_eq!(segment_code("it"), Some("IT".to_string()));
+        assert_eq!(segment_code("WH-01 north"), Some("WH01NORTH".to_string()));
+        assert_eq!(segment_code(" - "), None);
+        assert_eq!(
+            segment_code(&"x".repeat(40)).unwrap().len(),
+            MAX_SEGMENT_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_gap_free_types() {
+        assert!(format(DOC_INVOICE, RESET_YEARLY, 6).is_gap_free());
+        assert!(!format(DOC_LOAN, RESET_YEARLY, 5).is_gap_free());
+    }
+}
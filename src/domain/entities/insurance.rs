@@ -0,0 +1,196 @@
+//! Insurance Entities
+//!
+//! Policy status rules and claims. A claim is raised against one policy for
+//! damage handled by a work order or for a lost/stolen transition, and moves
+//! submitted -> approved -> paid (or submitted -> rejected).
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use super::Insurance;
+use crate::domain::errors::{DomainError, DomainResult};
+
+pub const POLICY_ACTIVE: &str = "active";
+pub const POLICY_EXPIRED: &str = "expired";
+pub const POLICY_CANCELLED: &str = "cancelled";
+
+pub const CLAIM_SUBMITTED: &str = "submitted";
+pub const CLAIM_APPROVED: &str = "approved";
+pub const CLAIM_REJECTED: &str = "rejected";
+pub const CLAIM_PAID: &str = "paid";
+
+/// Days before `end_date` the expiry job warns
+pub const INSURANCE_EXPIRY_WARNING_DAYS: i64 = 30;
+
+impl Insurance {
+    /// Whether the policy covers an incident on `date`
+    pub fn covers(&self, date: NaiveDate) -> bool {
+        self.status != POLICY_CANCELLED && self.start_date <= date && date <= self.end_date
+    }
+
+    pub fn validate_period(start_date: NaiveDate, end_date: NaiveDate) -> DomainResult<()> {
+        if end_date < start_date {
+            return Err(DomainError::validation(
+                "end_date",
+                "Must be on or after start_date",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct InsuranceClaim {
+    pub id: Uuid,
+    pub insurance_id: Uuid,
+    pub asset_id: Uuid,
+    pub claim_number: Option<String>,
+    pub incident_date: NaiveDate,
+
+    // Source of the claim (exactly one is set when raised)
+    pub work_order_id: Option<Uuid>,
+    pub lifecycle_history_id: Option<Uuid>,
+
+    pub claimed_amount: Decimal,
+    pub approved_amount: Option<Decimal>,
+    pub payout_amount: Option<Decimal>,
+    pub payout_date: Option<NaiveDate>,
+
+    pub status: String, // submitted, approved, rejected, paid
+    pub description: Option<String>,
+    pub notes: Option<String>,
+
+    pub created_by: Option<Uuid>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl InsuranceClaim {
+    /// Apply a status change with its amounts
+    pub fn transition(
+        &mut self,
+        to: &str,
+        approved_amount: Option<Decimal>,
+        payout_amount: Option<Decimal>,
+        payout_date: Option<NaiveDate>,
+    ) -> DomainResult<()> {
+        match (self.status.as_str(), to) {
+            (CLAIM_SUBMITTED, CLAIM_APPROVED) => {
+                let approved = approved_amount.ok_or_else(|| {
+                    DomainError::validation("approved_amount", "Required to approve a claim")
+                })?;
+                if approved < Decimal::ZERO || approved > self.claimed_amount {
+                    return Err(DomainError::validation(
+                        "approved_amount",
+                        "Must be between 0 and the claimed amount",
+                    ));
+                }
+                self.approved_amount = Some(approved);
+            }
+            (CLAIM_SUBMITTED, CLAIM_REJECTED) => {}
+            (CLAIM_APPROVED, CLAIM_PAID) => {
+                let payout = payout_amount.ok_or_else(|| {
+                    DomainError::validation("payout_amount", "Required to record a payout")
+                })?;
+                if payout < Decimal::ZERO || Some(payout) > self.approved_amount {
+                    return Err(DomainError::validation(
+                        "payout_amount",
+                        "Must be between 0 and the approved amount",
+                    ));
+                }
+                self.payout_amount = Some(payout);
+                self.payout_date = Some(payout_date.unwrap_or_else(|| Utc::now().date_naive()));
+            }
+            (from, to) => return Err(DomainError::invalid_transition(from, to)),
+        }
+        self.status = to.to_string();
+        Ok(())
+    }
+}
+
+/// Insured value against book value for one category
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct InsuranceCoverageLine {
+    pub category_id: Uuid,
+    pub category_name: String,
+    pub asset_count: i64,
+    pub insured_asset_count: i64,
+    /// Coverage of policies in force at the report date
+    pub insured_value: Decimal,
+    pub book_value: Decimal,
+    /// Book value not covered, summed per asset
+    pub underinsured_value: Decimal,
+}
+
+/// Asset flagged by the expiry job as no longer covered
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LapsedInsuranceAsset {
+    pub asset_id: Uuid,
+    pub asset_code: String,
+    pub name: String,
+    pub insurance_lapsed_at: DateTime<Utc>,
+    pub last_policy_end: Option<NaiveDate>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claim() -> InsuranceClaim {
+        InsuranceClaim {
+            id: Uuid::new_v4(),
+            insurance_id: Uuid::new_v4(),
+            asset_id: Uuid::new_v4(),
+            claim_number: None,
+            incident_date: NaiveDate::from_ymd_opt(2026, 3, 10).unwrap(),
+            work_order_id: Some(Uuid::new_v4()),
+            lifecycle_history_id: None,
+            claimed_amount: Decimal::from(1_000),
+            approved_amount: None,
+            payout_amount: None,
+            payout_date: None,
+            status: CLAIM_SUBMITTED.to_string(),
+            description: None,
+            notes: None,
+            created_by: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_claim_transitions() {
+        let mut c = claim();
+        assert!(c
+            .transition(CLAIM_PAID, None, Some(Decimal::from(10)), None)
+            .is_err());
+        assert!(c.transition(CLAIM_APPROVED, None, None, None).is_err());
+        assert!(c
+            .transition(CLAIM_APPROVED, Some(Decimal::from(1_500)), None, None)
+            .is_err());
+
+        c.transition(CLAIM_APPROVED, Some(Decimal::from(800)), None, None)
+            .unwrap();
+        assert_eq!(c.status, CLAIM_APPROVED);
+        assert!(c
+            .transition(CLAIM_PAID, None, Some(Decimal::from(900)), None)
+            .is_err());
+
+        let paid_on = NaiveDate::from_ymd_opt(2026, 4, 1).unwrap();
+        c.transition(CLAIM_PAID, None, Some(Decimal::from(800)), Some(paid_on))
+            .unwrap();
+        assert_eq!(c.payout_date, Some(paid_on));
+        assert!(c.transition(CLAIM_REJECTED, None, None, None).is_err());
+
+        let mut rejected = claim();
+        rejected
+            .transition(CLAIM_REJECTED, None, None, None)
+            .unwrap();
+        assert!(rejected
+            .transition(CLAIM_APPROVED, Some(Decimal::ONE), None, None)
+            .is_err());
+    }
+}
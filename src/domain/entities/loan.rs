@@ -106,6 +106,7 @@ pub struct Loan {
 
 impl Loan {
     pub fn new(
+        loan_number: String,
         asset_id: Uuid,
         borrower_id: Option<Uuid>,
         employee_id: Option<Uuid>,
@@ -113,7 +114,6 @@ impl Loan {
         expected_return_date: NaiveDate,
     ) -> Self {
         let now = Utc::now();
-        let loan_number = format!("LN-{}", now.format("%Y%m%d%H%M%S"));
 
         Self {
             id: Uuid::new_v4(),
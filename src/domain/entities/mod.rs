@@ -2,49 +2,79 @@
 //!
 //! Core business entities representing the main concepts in the asset management domain.
 
+pub mod api_key;
+pub mod approval_workflow;
 pub mod asset;
 pub mod asset_details;
 pub mod asset_lifecycle;
 pub mod audit;
+pub mod budget;
 pub mod category;
 pub mod client;
 pub mod conversion;
 pub mod department;
+pub mod depreciation;
+pub mod document_number;
 pub mod employee;
+pub mod insurance;
 pub mod loan;
 pub mod location;
 pub mod maintenance;
 pub mod notification;
 pub mod organization;
+pub mod outbox;
+pub mod password_policy;
+pub mod preventive_schedule;
+pub mod rate_limit;
 pub mod rbac;
 pub mod rental;
 pub mod rental_billing;
 pub mod rental_timesheet;
 pub mod sensor;
+pub mod session;
+pub mod specification;
+pub mod two_factor;
 pub mod user;
+pub mod valuation;
 pub mod vendor;
+pub mod webhook;
 pub mod work_order;
 
+pub use api_key::*;
+pub use approval_workflow::*;
 pub use asset::{Asset, AssetHistory, AssetSummary};
 pub use asset_details::*;
 pub use asset_lifecycle::*;
 pub use audit::*;
+pub use budget::*;
 pub use category::Category;
 pub use client::*;
 pub use department::*;
+pub use depreciation::*;
+pub use document_number::*;
 pub use employee::*;
+pub use insurance::*;
 pub use loan::*;
 pub use location::Location;
 pub use maintenance::*;
 pub use maintenance::{MaintenanceRecord, MaintenanceType};
 pub use notification::*;
 pub use organization::*;
+pub use outbox::*;
+pub use password_policy::*;
+pub use preventive_schedule::*;
+pub use rate_limit::*;
 pub use rbac::*;
 pub use rental::*;
 pub use rental_billing::*;
 pub use rental_timesheet::*;
 pub use sensor::*;
+pub use session::*;
+pub use specification::*;
+pub use two_factor::*;
 pub use user::User;
 pub use user::*;
+pub use valuation::*;
 pub use vendor::*;
+pub use webhook::*;
 pub use work_order::*;
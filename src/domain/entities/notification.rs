@@ -14,3 +14,510 @@ pub struct Notification {
     pub link: Option<String>,
     pub metadata: Option<serde_json::Value>,
 }
+
+/// Delivery channels a template can fan out to
+pub const CHANNEL_IN_APP: &str = "in_app";
+pub const CHANNEL_EMAIL: &str = "email";
+pub const CHANNEL_PUSH: &str = "push";
+pub const CHANNEL_SMS: &str = "sms";
+pub const CHANNEL_WEBHOOK: &str = "webhook";
+
+pub const NOTIFICATION_CHANNELS: [&str; 5] = [
+    CHANNEL_IN_APP,
+    CHANNEL_EMAIL,
+    CHANNEL_PUSH,
+    CHANNEL_SMS,
+    CHANNEL_WEBHOOK,
+];
+
+/// Notification template with `{{variable}}` placeholders
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct NotificationTemplate {
+    pub id: Uuid,
+    pub code: String,
+    pub name: String,
+    pub subject_template: Option<String>,
+    pub body_template: Option<String>,
+    pub channels: Option<Vec<String>>,
+    pub event_type: String,
+    pub is_active: Option<bool>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Subject and body of a rendered template
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RenderedMessage {
+    pub subject: String,
+    pub body: String,
+}
+
+impl NotificationTemplate {
+    pub fn render(&self, variables: &serde_json::Value) -> RenderedMessage {
+        RenderedMessage {
+            subject: render_template(
+                self.subject_template.as_deref().unwrap_or(&self.name),
+                variables,
+            ),
+            body: render_template(self.body_template.as_deref().unwrap_or_default(), variables),
+        }
+    }
+
+    pub fn channels(&self) -> Vec<String> {
+        self.channels
+            .clone()
+            .unwrap_or_else(|| vec![CHANNEL_IN_APP.to_string()])
+    }
+}
+
+/// Replace `{{name}}` placeholders with values from a JSON object.
+///
+/// Unknown variables render as an empty string; non-string values use their JSON form.
+pub fn render_template(template: &str, variables: &serde_json::Value) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                let name = after[..end].trim();
+                match variables.get(name) {
+                    Some(serde_json::Value::String(s)) => output.push_str(s),
+                    Some(serde_json::Value::Null) | None => {}
+                    Some(other) => output.push_str(&other.to_string()),
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                output.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Per-user channel preferences for an event (or template)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct NotificationPreference {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub template_id: Option<Uuid>,
+    pub event_type: Option<String>,
+    pub email_enabled: Option<bool>,
+    pub push_enabled: Option<bool>,
+    pub sms_enabled: Option<bool>,
+    pub in_app_enabled: Option<bool>,
+    pub webhook_enabled: Option<bool>,
+    pub digest_frequency: Option<String>, // immediate, hourly, daily, weekly
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Digest frequencies; anything other than immediate queues deliveries for a digest
+pub const DIGEST_IMMEDIATE: &str = "immediate";
+pub const DIGEST_HOURLY: &str = "hourly";
+pub const DIGEST_DAILY: &str = "daily";
+pub const DIGEST_WEEKLY: &str = "weekly";
+
+pub const DIGEST_FREQUENCIES: [&str; 4] =
+    [DIGEST_IMMEDIATE, DIGEST_HOURLY, DIGEST_DAILY, DIGEST_WEEKLY];
+
+/// Event type of the digest message itself; an active template for it overrides the
+/// built-in layout
+pub const EVENT_NOTIFICATION_DIGEST: &str = "notification.digest";
+
+impl NotificationPreference {
+    /// Whether a channel may be used, given the user's preference if they set one
+    pub fn permits(preference: Option<&Self>, channel: &str) -> bool {
+        match preference {
+            Some(p) => p.allows(channel),
+            None => NOTIFICATION_CHANNELS.contains(&channel) && channel != CHANNEL_SMS,
+        }
+    }
+
+    /// Whether the user accepts a channel; mirrors the column defaults when unset
+    pub fn allows(&self, channel: &str) -> bool {
+        match channel {
+            CHANNEL_IN_APP => self.in_app_enabled.unwrap_or(true),
+            CHANNEL_EMAIL => self.email_enabled.unwrap_or(true),
+            CHANNEL_PUSH => self.push_enabled.unwrap_or(true),
+            CHANNEL_SMS => self.sms_enabled.unwrap_or(false),
+            CHANNEL_WEBHOOK => self.webhook_enabled.unwrap_or(true),
+            _ => false,
+        }
+    }
+
+    /// The batching frequency, or None when messages go out immediately
+    pub fn digest(&self) -> Option<&str> {
+        self.digest_frequency
+            .as_deref()
+            .filter(|f| *f != DIGEST_IMMEDIATE && DIGEST_FREQUENCIES.contains(f))
+    }
+}
+
+/// Delivery status values
+pub const DELIVERY_PENDING: &str = "pending";
+pub const DELIVERY_SENT: &str = "sent";
+pub const DELIVERY_RETRYING: &str = "retrying";
+pub const DELIVERY_FAILED: &str = "failed";
+pub const DELIVERY_SKIPPED: &str = "skipped";
+/// Held back for the user's next digest
+pub const DELIVERY_QUEUED: &str = "queued";
+/// Sent as part of a digest (see `digest_id`)
+pub const DELIVERY_DIGESTED: &str = "digested";
+
+/// Stored in place of secret variables, such as a password reset token
+pub const REDACTED_VALUE: &str = "[redacted]";
+
+/// One message on one channel, logged with its delivery attempts
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct NotificationDelivery {
+    pub id: Uuid,
+    pub notification_id: Option<Uuid>,
+    pub template_id: Option<Uuid>,
+    pub user_id: Uuid,
+    pub event_type: String,
+    pub channel: String,
+    pub recipient: Option<String>,
+    pub subject: Option<String>,
+    pub body: String,
+    pub payload: Option<serde_json::Value>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<Uuid>,
+    pub asset_id: Option<Uuid>,
+    pub status: String,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub digest_frequency: Option<String>,
+    pub digest_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl NotificationDelivery {
+    /// Backoff before the next attempt: 1, 2, 4, ... minutes, capped at one hour
+    pub fn retry_delay(attempts: i32) -> chrono::Duration {
+        let exponent = attempts.clamp(1, 7) - 1;
+        chrono::Duration::minutes((1i64 << exponent).min(60))
+    }
+
+    /// Whether secret variables were left out of the stored message
+    pub fn is_redacted(&self) -> bool {
+        self.payload
+            .as_ref()
+            .and_then(|p| p.as_object())
+            .is_some_and(|map| map.values().any(|v| v == REDACTED_VALUE))
+    }
+
+    /// Label of the asset this item is about, for digest grouping
+    fn asset_label(&self) -> Option<String> {
+        let payload = self.payload.as_ref();
+        let name = payload
+            .and_then(|p| p.get("asset_name"))
+            .and_then(|v| v.as_str());
+        match (name, self.asset_id) {
+            (Some(name), _) => Some(name.to_string()),
+            (None, Some(id)) => Some(id.to_string()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Queued deliveries combined into one message, grouped by event type and asset
+#[derive(Debug, Clone, Serialize)]
+pub struct NotificationDigest {
+    pub frequency: String,
+    pub item_count: usize,
+    pub period_start: Option<DateTime<Utc>>,
+    pub period_end: Option<DateTime<Utc>>,
+    pub groups: Vec<DigestGroup>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DigestGroup {
+    pub event_type: String,
+    pub assets: Vec<DigestAssetGroup>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DigestAssetGroup {
+    pub asset_id: Option<Uuid>,
+    pub asset: Option<String>,
+    pub items: Vec<DigestItem>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DigestItem {
+    pub delivery_id: Uuid,
+    pub subject: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl NotificationDigest {
+    /// Group items by event type, then asset, keeping the order they were queued in
+    pub fn build(frequency: &str, items: &[NotificationDelivery]) -> Self {
+        let mut groups: Vec<DigestGroup> = Vec::new();
+
+        for item in items {
+            let group = match groups.iter().position(|g| g.event_type == item.event_type) {
+                Some(i) => &mut groups[i],
+                None => {
+                    groups.push(DigestGroup {
+                        event_type: item.event_type.clone(),
+                        assets: Vec::new(),
+                    });
+                    groups.last_mut().unwrap()
+                }
+            };
+
+            let label = item.asset_label();
+            let asset = match group
+                .assets
+                .iter()
+                .position(|a| a.asset_id == item.asset_id && a.asset == label)
+            {
+                Some(i) => &mut group.assets[i],
+                None => {
+                    group.assets.push(DigestAssetGroup {
+                        asset_id: item.asset_id,
+                        asset: label,
+                        items: Vec::new(),
+                    });
+                    group.assets.last_mut().unwrap()
+                }
+            };
+
+            asset.items.push(DigestItem {
+                delivery_id: item.id,
+                subject: item
+                    .subject
+                    .clone()
+                    .filter(|s| !s.is_empty())
+                    .unwrap_or_else(|| item.body.clone()),
+                created_at: item.created_at,
+            });
+        }
+
+        Self {
+            frequency: frequency.to_string(),
+            item_count: items.len(),
+            period_start: items.iter().map(|i| i.created_at).min(),
+            period_end: items.iter().map(|i| i.created_at).max(),
+            groups,
+        }
+    }
+
+    /// Built-in plain-text layout
+    pub fn render(&self) -> RenderedMessage {
+        let mut body = format!(
+            "You have {} new notification{}.\n",
+            self.item_count,
+            if self.item_count == 1 { "" } else { "s" }
+        );
+
+        for group in &self.groups {
+            let count: usize = group.assets.iter().map(|a| a.items.len()).sum();
+            body.push_str(&format!("\n{} ({})\n", group.event_type, count));
+            for asset in &group.assets {
+                let indent = match &asset.asset {
+                    Some(label) => {
+                        body.push_str(&format!("  {}\n", label));
+                        "    "
+                    }
+                    None => "  ",
+                };
+                for item in &asset.items {
+                    body.push_str(&format!("{}- {}\n", indent, item.subject));
+                }
+            }
+        }
+
+        RenderedMessage {
+            subject: format!(
+                "Your {} digest: {} notification{}",
+                self.frequency,
+                self.item_count,
+                if self.item_count == 1 { "" } else { "s" }
+            ),
+            body,
+        }
+    }
+
+    /// Variables for a `notification.digest` template
+    pub fn variables(&self) -> serde_json::Value {
+        serde_json::json!({
+            "frequency": self.frequency,
+            "count": self.item_count,
+            "period_start": self.period_start,
+            "period_end": self.period_end,
+            "summary": self.render().body,
+            "groups": self.groups,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_template() {
+        let vars = json!({"asset_name": "Excavator 01", "days_overdue": 3, "note": null});
+        assert_eq!(
+            render_template("{{asset_name}} is {{ days_overdue }} days overdue", &vars),
+            "Excavator 01 is 3 days overdue"
+        );
+        assert_eq!(render_template("[{{missing}}][{{note}}]", &vars), "[][]");
+        assert_eq!(
+            render_template("open {{asset_name", &vars),
+            "open {{asset_name"
+        );
+    }
+
+    #[test]
+    fn test_redacted_delivery() {
+        let now = Utc::now();
+        let mut delivery = NotificationDelivery {
+            id: Uuid::new_v4(),
+            notification_id: None,
+            template_id: None,
+            user_id: Uuid::new_v4(),
+            event_type: "auth.password_reset".to_string(),
+            channel: CHANNEL_EMAIL.to_string(),
+            recipient: None,
+            subject: None,
+            body: "Reset link: [redacted]".to_string(),
+            payload: Some(json!({"name": "Dina", "token": REDACTED_VALUE})),
+            entity_type: None,
+            entity_id: None,
+            asset_id: None,
+            status: DELIVERY_FAILED.to_string(),
+            attempts: 1,
+            max_attempts: 1,
+            last_error: None,
+            next_attempt_at: None,
+            sent_at: None,
+            digest_frequency: None,
+            digest_id: None,
+            created_at: now,
+            updated_at: now,
+        };
+        assert!(delivery.is_redacted());
+        delivery.payload = Some(json!({"name": "Dina"}));
+        assert!(!delivery.is_redacted());
+        delivery.payload = None;
+        assert!(!delivery.is_redacted());
+    }
+
+    #[test]
+    fn test_preference_defaults() {
+        let pref = NotificationPreference {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            template_id: None,
+            event_type: Some("loan.approved".to_string()),
+            email_enabled: Some(false),
+            push_enabled: None,
+            sms_enabled: None,
+            in_app_enabled: None,
+            webhook_enabled: None,
+            digest_frequency: None,
+            created_at: None,
+            updated_at: None,
+        };
+        assert!(pref.allows(CHANNEL_IN_APP));
+        assert!(!pref.allows(CHANNEL_EMAIL));
+        assert!(!pref.allows(CHANNEL_SMS));
+        assert!(!pref.allows("fax"));
+
+        assert!(NotificationPreference::permits(None, CHANNEL_EMAIL));
+        assert!(!NotificationPreference::permits(None, CHANNEL_SMS));
+        assert!(!NotificationPreference::permits(Some(&pref), CHANNEL_EMAIL));
+    }
+
+    fn queued(event_type: &str, asset_id: Option<Uuid>, asset_name: &str) -> NotificationDelivery {
+        NotificationDelivery {
+            id: Uuid::new_v4(),
+            notification_id: None,
+            template_id: None,
+            user_id: Uuid::nil(),
+            event_type: event_type.to_string(),
+            channel: CHANNEL_EMAIL.to_string(),
+            recipient: None,
+            subject: Some(format!("{} {}", event_type, asset_name)),
+            body: String::new(),
+            payload: Some(json!({ "asset_name": asset_name })),
+            entity_type: None,
+            entity_id: None,
+            asset_id,
+            status: DELIVERY_QUEUED.to_string(),
+            attempts: 0,
+            max_attempts: 5,
+            last_error: None,
+            next_attempt_at: None,
+            sent_at: None,
+            digest_frequency: Some(DIGEST_DAILY.to_string()),
+            digest_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_digest_grouping() {
+        let excavator = Some(Uuid::new_v4());
+        let items = vec![
+            queued("loan.overdue", excavator, "Excavator 01"),
+            queued("maintenance.due", excavator, "Excavator 01"),
+            queued("loan.overdue", None, "Laptop 7"),
+            queued("loan.overdue", excavator, "Excavator 01"),
+        ];
+
+        let digest = NotificationDigest::build(DIGEST_DAILY, &items);
+        assert_eq!(digest.item_count, 4);
+        assert_eq!(digest.groups.len(), 2);
+        assert_eq!(digest.groups[0].event_type, "loan.overdue");
+        assert_eq!(digest.groups[0].assets.len(), 2);
+        assert_eq!(digest.groups[0].assets[0].items.len(), 2);
+
+        let rendered = digest.render();
+        assert_eq!(rendered.subject, "Your daily digest: 4 notifications");
+        assert!(rendered.body.contains("loan.overdue (3)\n  Excavator 01\n"));
+    }
+
+    #[test]
+    fn test_digest_frequency() {
+        let mut pref = NotificationPreference {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            template_id: None,
+            event_type: None,
+            email_enabled: None,
+            push_enabled: None,
+            sms_enabled: None,
+            in_app_enabled: None,
+            webhook_enabled: None,
+            digest_frequency: Some(DIGEST_IMMEDIATE.to_string()),
+            created_at: None,
+            updated_at: None,
+        };
+        assert_eq!(pref.digest(), None);
+        pref.digest_frequency = Some(DIGEST_WEEKLY.to_string());
+        assert_eq!(pref.digest(), Some(DIGEST_WEEKLY));
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(NotificationDelivery::retry_delay(1).num_minutes(), 1);
+        assert_eq!(NotificationDelivery::retry_delay(3).num_minutes(), 4);
+        assert_eq!(NotificationDelivery::retry_delay(20).num_minutes(), 60);
+    }
+}
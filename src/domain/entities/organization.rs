@@ -28,6 +28,16 @@ impl OrganizationType {
             Self::Team => "team",
         }
     }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "company" => Some(Self::Company),
+            "division" => Some(Self::Division),
+            "department" => Some(Self::Department),
+            "team" => Some(Self::Team),
+            _ => None,
+        }
+    }
 }
 
 /// Organization entity
@@ -66,3 +76,37 @@ impl Organization {
         }
     }
 }
+
+/// User belonging to an organization
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OrganizationMember {
+    pub user_id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub email: String,
+    /// Whether this is the user's home organization (active at login)
+    pub is_home: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Tenant data addressed by id under `/api/<segment>/:id`; each is scoped to
+/// the organization of its asset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TenantResource {
+    Asset,
+    Loan,
+    Rental,
+    WorkOrder,
+}
+
+impl TenantResource {
+    pub fn from_path_segment(segment: &str) -> Option<Self> {
+        match segment {
+            "assets" => Some(Self::Asset),
+            "loans" => Some(Self::Loan),
+            "rentals" => Some(Self::Rental),
+            "work-orders" => Some(Self::WorkOrder),
+            _ => None,
+        }
+    }
+}
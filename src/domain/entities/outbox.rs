@@ -0,0 +1,60 @@
+//! Outbox Entity
+//!
+//! Domain events are stored in the outbox in the same transaction as the
+//! change they describe, then relayed to subscribers. Delivery is at least
+//! once: an event is retried until every subscriber has taken it, so
+//! subscribers deduplicate on the envelope id.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// First retry delay after a failed delivery; doubles with every attempt
+pub const OUTBOX_RETRY_BASE_SECONDS: i64 = 5;
+/// Longest wait between two delivery attempts
+pub const OUTBOX_RETRY_MAX_SECONDS: i64 = 3600;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct OutboxEvent {
+    pub id: Uuid, // The envelope id
+    pub seq: i64, // Insertion order
+    pub event_type: String,
+    pub aggregate_id: Uuid,
+    pub envelope: serde_json::Value, // The serialized EventEnvelope
+    pub occurred_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl OutboxEvent {
+    /// The event payload within the envelope
+    pub fn payload(&self) -> &serde_json::Value {
+        &self.envelope["payload"]
+    }
+
+    /// When to try again after `attempts` failed deliveries
+    pub fn retry_delay(attempts: i32) -> Duration {
+        let exponent = attempts.saturating_sub(1).clamp(0, 20) as u32;
+        let seconds = OUTBOX_RETRY_BASE_SECONDS.saturating_mul(1 << exponent);
+        Duration::seconds(seconds.min(OUTBOX_RETRY_MAX_SECONDS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_backs_off_exponentially() {
+        assert_eq!(OutboxEvent::retry_delay(1), Duration::seconds(5));
+        assert_eq!(OutboxEvent::retry_delay(2), Duration::seconds(10));
+        assert_eq!(OutboxEvent::retry_delay(4), Duration::seconds(40));
+        assert_eq!(OutboxEvent::retry_delay(11), Duration::seconds(3600));
+        assert_eq!(OutboxEvent::retry_delay(1000), Duration::seconds(3600));
+        assert_eq!(OutboxEvent::retry_delay(0), Duration::seconds(5));
+    }
+}
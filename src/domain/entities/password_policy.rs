@@ -0,0 +1,190 @@
+//! Password Policy Entity
+//!
+//! Rules for new passwords (length, character classes, a bundled list of
+//! common/breached passwords, reuse of recent passwords) and single-use reset
+//! tokens for the forgot-password flow.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::domain::errors::{DomainError, DomainResult};
+
+/// Notification event carrying a reset link
+pub const EVENT_PASSWORD_RESET: &str = "auth.password_reset";
+
+/// Audit log actions of password management
+pub const AUDIT_PASSWORD_RESET: &str = "PASSWORD_RESET";
+pub const AUDIT_PASSWORD_RESET_FORCED: &str = "PASSWORD_RESET_FORCED";
+
+const COMMON_PASSWORDS: &str = include_str!("common_passwords.txt");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    /// Of lowercase, uppercase, digits and symbols
+    pub min_classes: usize,
+    /// Recent passwords (the current one included) that may not be reused
+    pub history: usize,
+    pub reject_common: bool,
+    pub reset_token_minutes: i64,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 10,
+            min_classes: 3,
+            history: 5,
+            reject_common: true,
+            reset_token_minutes: 30,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    pub fn from_env() -> Self {
+        fn var<T: std::str::FromStr>(name: &str, default: T) -> T {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        }
+
+        let default = Self::default();
+        Self {
+            min_length: var("PASSWORD_MIN_LENGTH", default.min_length),
+            min_classes: var("PASSWORD_MIN_CLASSES", default.min_classes).min(4),
+            history: var("PASSWORD_HISTORY", default.history),
+            reject_common: var("PASSWORD_REJECT_COMMON", default.reject_common),
+            reset_token_minutes: var("PASSWORD_RESET_TOKEN_MINUTES", default.reset_token_minutes),
+        }
+    }
+
+    /// Check a new password; `personal` are values it must not contain, such
+    /// as the user's email name
+    pub fn validate(&self, password: &str, personal: &[&str]) -> DomainResult<()> {
+        if password.chars().count() < self.min_length {
+            return Err(DomainError::validation(
+                "password",
+                &format!("Must be at least {} characters", self.min_length),
+            ));
+        }
+        if password.len() > 128 {
+            return Err(DomainError::validation(
+                "password",
+                "Must be at most 128 characters",
+            ));
+        }
+        if character_classes(password) < self.min_classes {
+            return Err(DomainError::validation(
+                "password",
+                &format!(
+                    "Must mix at least {} of lowercase, uppercase, digits and symbols",
+                    self.min_classes
+                ),
+            ));
+        }
+        if self.reject_common && is_common_password(password) {
+            return Err(DomainError::validation(
+                "password",
+                "This password is too common",
+            ));
+        }
+
+        let lower = password.to_lowercase();
+        if personal
+            .iter()
+            .map(|p| p.trim().to_lowercase())
+            .any(|p| p.chars().count() >= 4 && lower.contains(&p))
+        {
+            return Err(DomainError::validation(
+                "password",
+                "Must not contain your name or email",
+            ));
+        }
+        Ok(())
+    }
+
+    /// `validate` against the user's email name and the parts of their name
+    pub fn validate_for(&self, password: &str, email: &str, name: &str) -> DomainResult<()> {
+        let mut personal: Vec<&str> = name.split_whitespace().collect();
+        personal.push(name);
+        personal.push(email.split('@').next().unwrap_or(email));
+        self.validate(password, &personal)
+    }
+}
+
+fn character_classes(password: &str) -> usize {
+    [
+        password.chars().any(|c| c.is_lowercase()),
+        password.chars().any(|c| c.is_uppercase()),
+        password.chars().any(|c| c.is_ascii_digit()),
+        password.chars().any(|c| !c.is_alphanumeric()),
+    ]
+    .iter()
+    .filter(|present| **present)
+    .count()
+}
+
+/// Whether the password is on the bundled list, ignoring case
+pub fn is_common_password(password: &str) -> bool {
+    let password = password.to_lowercase();
+    COMMON_PASSWORDS
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .any(|line| line == password)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PasswordResetToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    #[serde(skip_serializing)]
+    pub token_hash: String, // SHA-256 of the token sent to the user
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub requested_ip: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl PasswordResetToken {
+    pub fn is_usable(&self, now: DateTime<Utc>) -> bool {
+        self.used_at.is_none() && self.expires_at > now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_policy() {
+        let policy = PasswordPolicy::default();
+
+        assert!(policy.validate("Tr4ctor-Fleet", &[]).is_ok());
+        assert!(policy.validate("Short1!", &[]).is_err());
+        assert!(policy.validate("alllowercaseletters", &[]).is_err());
+        assert!(policy.validate("Password123!", &[]).is_err());
+        assert!(policy.validate("QWERTYuiop1", &[]).is_err());
+        assert!(policy
+            .validate("Budi.Santoso-2026", &["budi.santoso"])
+            .is_err());
+        // Too short personal values are ignored
+        assert!(policy.validate("Tr4ctor-Fleet", &["tr"]).is_ok());
+        assert!(policy
+            .validate_for("Santoso#Fleet9", "budi@example.com", "Budi Santoso")
+            .is_err());
+        assert!(policy
+            .validate_for("Tr4ctor-Fleet", "budi@example.com", "Budi Santoso")
+            .is_ok());
+
+        let lenient = PasswordPolicy {
+            min_classes: 1,
+            reject_common: false,
+            ..policy
+        };
+        assert!(lenient.validate("password123", &[]).is_ok());
+    }
+}
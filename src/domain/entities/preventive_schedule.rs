@@ -0,0 +1,208 @@
+//! Preventive Schedule Entity
+//!
+//! Recurring maintenance plans driven by calendar days, odometer (km) or hour meter.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// What drives a preventive schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntervalType {
+    Days,
+    Km,
+    Hours,
+}
+
+impl IntervalType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Days => "days",
+            Self::Km => "km",
+            Self::Hours => "hours",
+        }
+    }
+
+    /// Whether the schedule is driven by a meter reading instead of the calendar
+    pub fn is_meter_based(&self) -> bool {
+        matches!(self, Self::Km | Self::Hours)
+    }
+}
+
+impl std::str::FromStr for IntervalType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "days" => Ok(Self::Days),
+            "km" => Ok(Self::Km),
+            "hours" => Ok(Self::Hours),
+            _ => Err(format!("Unknown interval type: {}", s)),
+        }
+    }
+}
+
+/// Preventive maintenance schedule for an asset
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PreventiveSchedule {
+    pub id: Uuid,
+    pub asset_id: Uuid,
+    pub maintenance_type_id: Option<i32>,
+
+    pub name: String,
+    pub interval_type: String,
+    pub interval_value: i32,
+
+    // Tracking (odometer columns hold km or hour meter depending on interval_type)
+    pub last_execution_date: Option<NaiveDate>,
+    pub last_execution_odometer: Option<i32>,
+    pub next_due_date: Option<NaiveDate>,
+    pub next_due_odometer: Option<i32>,
+
+    pub is_active: Option<bool>,
+    pub notification_days_before: Option<i32>,
+
+    pub created_by: Option<Uuid>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl PreventiveSchedule {
+    pub fn new(
+        asset_id: Uuid,
+        name: &str,
+        interval_type: IntervalType,
+        interval_value: i32,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            asset_id,
+            maintenance_type_id: None,
+            name: name.to_string(),
+            interval_type: interval_type.as_str().to_string(),
+            interval_value,
+            last_execution_date: None,
+            last_execution_odometer: None,
+            next_due_date: None,
+            next_due_odometer: None,
+            is_active: Some(true),
+            notification_days_before: Some(7),
+            created_by: None,
+            created_at: Some(now),
+            updated_at: Some(now),
+        }
+    }
+
+    pub fn interval(&self) -> IntervalType {
+        self.interval_type.parse().unwrap_or(IntervalType::Days)
+    }
+
+    /// Check whether a work order should be raised.
+    ///
+    /// Calendar schedules become due `notification_days_before` days ahead of
+    /// `next_due_date` so the work can be planned; meter schedules become due
+    /// once the current reading reaches `next_due_odometer`.
+    pub fn is_due(&self, today: NaiveDate, current_reading: Option<i64>) -> bool {
+        if !self.is_active.unwrap_or(true) {
+            return false;
+        }
+
+        match self.interval() {
+            IntervalType::Days => match self.next_due_date {
+                Some(due) => {
+                    let lead = self.notification_days_before.unwrap_or(0).max(0) as i64;
+                    today >= due - Duration::days(lead)
+                }
+                None => false,
+            },
+            IntervalType::Km | IntervalType::Hours => {
+                match (self.next_due_odometer, current_reading) {
+                    (Some(due), Some(reading)) => reading >= due as i64,
+                    _ => false,
+                }
+            }
+        }
+    }
+
+    /// Record an execution and roll the next due point forward
+    pub fn roll_forward(&mut self, executed_on: NaiveDate, reading: Option<i64>) {
+        self.last_execution_date = Some(executed_on);
+
+        match self.interval() {
+            IntervalType::Days => {
+                self.next_due_date =
+                    Some(executed_on + Duration::days(self.interval_value.max(1) as i64));
+            }
+            IntervalType::Km | IntervalType::Hours => {
+                // Fall back to the planned due point when no reading was captured
+                let base = reading
+                    .map(|r| r as i32)
+                    .or(self.next_due_odometer)
+                    .or(self.last_execution_odometer)
+                    .unwrap_or(0);
+                self.last_execution_odometer = Some(base);
+                self.next_due_odometer = Some(base + self.interval_value.max(1));
+            }
+        }
+
+        self.updated_at = Some(Utc::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_calendar_schedule_due_within_lead_time() {
+        let mut schedule = PreventiveSchedule::new(Uuid::new_v4(), "Oil", IntervalType::Days, 90);
+        schedule.next_due_date = Some(date(2026, 3, 10));
+        schedule.notification_days_before = Some(7);
+
+        assert!(!schedule.is_due(date(2026, 3, 2), None));
+        assert!(schedule.is_due(date(2026, 3, 3), None));
+        assert!(schedule.is_due(date(2026, 3, 20), None));
+    }
+
+    #[test]
+    fn test_meter_schedule_due_on_reading() {
+        let mut schedule = PreventiveSchedule::new(Uuid::new_v4(), "Svc", IntervalType::Km, 5000);
+        schedule.next_due_odometer = Some(15000);
+
+        assert!(!schedule.is_due(date(2026, 1, 1), Some(14999)));
+        assert!(schedule.is_due(date(2026, 1, 1), Some(15000)));
+        assert!(!schedule.is_due(date(2026, 1, 1), None));
+    }
+
+    #[test]
+    fn test_inactive_schedule_never_due() {
+        let mut schedule = PreventiveSchedule::new(Uuid::new_v4(), "Svc", IntervalType::Days, 30);
+        schedule.next_due_date = Some(date(2026, 1, 1));
+        schedule.is_active = Some(false);
+
+        assert!(!schedule.is_due(date(2026, 6, 1), None));
+    }
+
+    #[test]
+    fn test_roll_forward() {
+        let mut calendar = PreventiveSchedule::new(Uuid::new_v4(), "A", IntervalType::Days, 30);
+        calendar.roll_forward(date(2026, 1, 15), None);
+        assert_eq!(calendar.next_due_date, Some(date(2026, 2, 14)));
+
+        let mut meter = PreventiveSchedule::new(Uuid::new_v4(), "B", IntervalType::Hours, 250);
+        meter.next_due_odometer = Some(1000);
+        meter.roll_forward(date(2026, 1, 15), Some(1040));
+        assert_eq!(meter.last_execution_odometer, Some(1040));
+        assert_eq!(meter.next_due_odometer, Some(1290));
+
+        meter.roll_forward(date(2026, 2, 15), None);
+        assert_eq!(meter.next_due_odometer, Some(1540));
+    }
+}
@@ -0,0 +1,217 @@
+//! Rate Limit Entity
+//!
+//! Token bucket policies applied per client address or user, and the login
+//! lockout policy: failed logins beyond a few free attempts must wait
+//! progressively longer, and a run of failures locks the account for a while.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Audit log actions of the login lockout
+pub const AUDIT_ACCOUNT_LOCKED: &str = "ACCOUNT_LOCKED";
+pub const AUDIT_ACCOUNT_UNLOCKED: &str = "ACCOUNT_UNLOCKED";
+
+/// A bucket of `capacity` requests refilled at `refill_per_sec`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitPolicy {
+    pub name: &'static str, // Part of the bucket key, one per route group
+    pub capacity: u32,
+    pub refill_per_sec: f64,
+}
+
+/// Logins and token refreshes per client address
+pub const LOGIN_RATE_LIMIT: RateLimitPolicy = RateLimitPolicy {
+    name: "login",
+    capacity: 10,
+    refill_per_sec: 10.0 / 60.0,
+};
+
+/// Authenticated API calls per user
+pub const API_RATE_LIMIT: RateLimitPolicy = RateLimitPolicy {
+    name: "api",
+    capacity: 300,
+    refill_per_sec: 20.0,
+};
+
+/// Remaining tokens of a bucket as of `updated_at_ms`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TokenBucket {
+    pub tokens: f64,
+    pub updated_at_ms: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub remaining: u32,
+    /// Seconds until a token is available again; zero when allowed
+    pub retry_after_secs: u64,
+}
+
+impl RateLimitPolicy {
+    /// Take one token from `bucket` (a full bucket when new) at `now_ms`
+    pub fn take(
+        &self,
+        bucket: Option<TokenBucket>,
+        now_ms: i64,
+    ) -> (TokenBucket, RateLimitDecision) {
+        let capacity = f64::from(self.capacity);
+        let tokens = match bucket {
+            Some(b) => {
+                let elapsed = (now_ms - b.updated_at_ms).max(0) as f64 / 1000.0;
+                (b.tokens + elapsed * self.refill_per_sec).min(capacity)
+            }
+            None => capacity,
+        };
+
+        let allowed = tokens >= 1.0;
+        let tokens = if allowed { tokens - 1.0 } else { tokens };
+
+        (
+            TokenBucket {
+                tokens,
+                updated_at_ms: now_ms,
+            },
+            self.decision(allowed, tokens),
+        )
+    }
+
+    /// Decision for a bucket left with `tokens` after a take
+    pub fn decision(&self, allowed: bool, tokens: f64) -> RateLimitDecision {
+        if allowed {
+            RateLimitDecision {
+                allowed,
+                remaining: tokens.max(0.0).floor() as u32,
+                retry_after_secs: 0,
+            }
+        } else {
+            let wait = (1.0 - tokens) / self.refill_per_sec;
+            RateLimitDecision {
+                allowed,
+                remaining: 0,
+                retry_after_secs: wait.ceil().max(1.0) as u64,
+            }
+        }
+    }
+
+    /// How long an idle bucket takes to refill completely
+    pub fn ttl_secs(&self) -> u64 {
+        (f64::from(self.capacity) / self.refill_per_sec).ceil() as u64 + 1
+    }
+}
+
+/// Failed login bookkeeping of a user
+#[derive(Debug, Clone, Default, Serialize, Deserialize, FromRow)]
+pub struct LoginAttempts {
+    pub failed_login_attempts: i32,
+    pub last_failed_login_at: Option<DateTime<Utc>>,
+    pub locked_until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoginLockoutPolicy {
+    /// Failures allowed before delays apply
+    pub free_attempts: i32,
+    pub base_delay_secs: i64,
+    pub max_delay_secs: i64,
+    /// Failures that lock the account
+    pub lockout_threshold: i32,
+    pub lockout_minutes: i64,
+}
+
+impl Default for LoginLockoutPolicy {
+    fn default() -> Self {
+        Self {
+            free_attempts: 3,
+            base_delay_secs: 2,
+            max_delay_secs: 60,
+            lockout_threshold: 10,
+            lockout_minutes: 15,
+        }
+    }
+}
+
+impl LoginLockoutPolicy {
+    /// Wait imposed after `failures` consecutive failures, doubling per failure
+    pub fn delay_after(&self, failures: i32) -> Duration {
+        if failures < self.free_attempts {
+            return Duration::zero();
+        }
+        let exponent = (failures - self.free_attempts).min(16) as u32;
+        Duration::seconds((self.base_delay_secs << exponent).min(self.max_delay_secs))
+    }
+
+    pub fn locks_after(&self, failures: i32) -> bool {
+        failures >= self.lockout_threshold
+    }
+
+    /// Seconds the user must wait before the next attempt; `None` when free to try
+    pub fn retry_after(&self, attempts: &LoginAttempts, now: DateTime<Utc>) -> Option<i64> {
+        if let Some(locked_until) = attempts.locked_until.filter(|t| *t > now) {
+            return Some((locked_until - now).num_seconds().max(1));
+        }
+        let next_attempt =
+            attempts.last_failed_login_at? + self.delay_after(attempts.failed_login_attempts);
+        (next_attempt > now).then(|| (next_attempt - now).num_seconds().max(1))
+    }
+
+    pub fn is_locked(&self, attempts: &LoginAttempts, now: DateTime<Utc>) -> bool {
+        attempts.locked_until.is_some_and(|t| t > now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: RateLimitPolicy = RateLimitPolicy {
+        name: "test",
+        capacity: 2,
+        refill_per_sec: 0.5,
+    };
+
+    #[test]
+    fn test_token_bucket() {
+        let (bucket, first) = POLICY.take(None, 0);
+        assert!(first.allowed);
+        assert_eq!(first.remaining, 1);
+
+        let (bucket, second) = POLICY.take(Some(bucket), 100);
+        assert!(second.allowed);
+        let (bucket, third) = POLICY.take(Some(bucket), 200);
+        assert!(!third.allowed);
+        assert_eq!(third.retry_after_secs, 2);
+
+        // Two seconds later one token has been refilled
+        let (_, fourth) = POLICY.take(Some(bucket), 2_200);
+        assert!(fourth.allowed);
+        assert_eq!(fourth.remaining, 0);
+    }
+
+    #[test]
+    fn test_login_lockout_policy() {
+        let policy = LoginLockoutPolicy::default();
+        assert_eq!(policy.delay_after(2), Duration::zero());
+        assert_eq!(policy.delay_after(3), Duration::seconds(2));
+        assert_eq!(policy.delay_after(5), Duration::seconds(8));
+        assert_eq!(policy.delay_after(9), Duration::seconds(60));
+        assert!(!policy.locks_after(9));
+        assert!(policy.locks_after(10));
+
+        let now = Utc::now();
+        let mut attempts = LoginAttempts {
+            failed_login_attempts: 4,
+            last_failed_login_at: Some(now - Duration::seconds(1)),
+            locked_until: None,
+        };
+        assert_eq!(policy.retry_after(&attempts, now), Some(3));
+        attempts.last_failed_login_at = Some(now - Duration::seconds(10));
+        assert_eq!(policy.retry_after(&attempts, now), None);
+
+        attempts.locked_until = Some(now + Duration::minutes(15));
+        assert!(policy.is_locked(&attempts, now));
+        assert!(policy.retry_after(&attempts, now).unwrap() > 800);
+        assert_eq!(policy.retry_after(&LoginAttempts::default(), now), None);
+    }
+}
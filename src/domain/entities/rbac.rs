@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
@@ -11,6 +13,8 @@ pub struct Role {
     pub description: Option<String>,
     pub role_level: Option<i32>,
     pub is_system: bool,
+    #[sqlx(default)]
+    pub requires_two_factor: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -36,3 +40,288 @@ pub struct UserRoleAssignment {
     pub granted_at: DateTime<Utc>,
     pub expires_at: Option<DateTime<Utc>>,
 }
+
+/// Records a permission grant reaches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionScope {
+    /// Records the user owns, is assigned to or borrows
+    Own,
+    /// Records of the user's department and its sub-departments
+    Department,
+    /// Records at the user's location and the locations within it
+    Location,
+    #[default]
+    All,
+}
+
+impl PermissionScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PermissionScope::Own => "own",
+            PermissionScope::Department => "department",
+            PermissionScope::Location => "location",
+            PermissionScope::All => "all",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "own" => Some(PermissionScope::Own),
+            "department" => Some(PermissionScope::Department),
+            "location" => Some(PermissionScope::Location),
+            "all" => Some(PermissionScope::All),
+            _ => None,
+        }
+    }
+
+    /// Scopes that still matter once `grants` are combined: `all` covers the
+    /// rest and `own` is implied by any other scope
+    pub fn effective(grants: &[PermissionScope]) -> Vec<PermissionScope> {
+        if grants.contains(&PermissionScope::All) {
+            return vec![PermissionScope::All];
+        }
+        let wider: Vec<PermissionScope> = [PermissionScope::Department, PermissionScope::Location]
+            .into_iter()
+            .filter(|s| grants.contains(s))
+            .collect();
+        if wider.is_empty() {
+            vec![PermissionScope::Own]
+        } else {
+            wider
+        }
+    }
+}
+
+/// Scope of one permission resolved for a user, bound to queries as the
+/// `data_scope` composite. Users without a grant still reach their own records.
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "data_scope")]
+pub struct DataScope {
+    pub all_records: bool,
+    pub owner_id: Option<Uuid>,
+    /// Root of the department subtree in scope
+    pub department_id: Option<Uuid>,
+    /// Root of the location subtree in scope
+    pub location_id: Option<Uuid>,
+}
+
+impl DataScope {
+    pub fn unrestricted() -> Self {
+        Self {
+            all_records: true,
+            owner_id: None,
+            department_id: None,
+            location_id: None,
+        }
+    }
+
+    /// Combine the grants of a user, anchored at their department and location
+    pub fn from_grants(
+        grants: &[PermissionScope],
+        user_id: Uuid,
+        department_id: Option<Uuid>,
+        location_id: Option<Uuid>,
+    ) -> Self {
+        let effective = PermissionScope::effective(grants);
+        if effective.contains(&PermissionScope::All) {
+            return Self::unrestricted();
+        }
+        Self {
+            all_records: false,
+            owner_id: Some(user_id),
+            department_id: department_id
+                .filter(|_| effective.contains(&PermissionScope::Department)),
+            location_id: location_id.filter(|_| effective.contains(&PermissionScope::Location)),
+        }
+    }
+}
+
+/// Permission held by a user with the scopes of its grants
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct UserPermission {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub permission: Permission,
+    pub scopes: Vec<String>,
+}
+
+/// Permission granted to a role with the scope of the grant
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RolePermission {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub permission: Permission,
+    pub scope: String,
+}
+
+/// Grant of a permission, by code, as edited on a role
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionGrant {
+    pub code: String,
+    #[serde(default)]
+    pub scope: PermissionScope,
+}
+
+/// Grant of one of a user's roles
+#[derive(Debug, Clone, FromRow)]
+pub struct UserRoleGrant {
+    pub user_id: Uuid,
+    pub role_id: Uuid,
+    pub code: String,
+    pub scope: String,
+}
+
+/// Effective scopes of a permission before and after a role change; an empty
+/// side means the permission is gained or lost
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionChange {
+    pub code: String,
+    pub before: Vec<PermissionScope>,
+    pub after: Vec<PermissionScope>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserPermissionDiff {
+    pub user_id: Uuid,
+    pub email: String,
+    pub name: String,
+    pub changes: Vec<PermissionChange>,
+}
+
+/// What saving a role's permissions would change, per affected user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RolePermissionPreview {
+    pub role_id: Uuid,
+    pub affected_users: usize,          // holders of the role
+    pub sessions_revoked: i64,          // sessions of primary holders that end on save
+    pub users: Vec<UserPermissionDiff>, // only those whose permissions change
+}
+
+/// Changes to the effective permissions of a user going from the grants
+/// `before` to `after`, by permission code
+pub fn diff_grants(before: &[PermissionGrant], after: &[PermissionGrant]) -> Vec<PermissionChange> {
+    fn effective(grants: &[PermissionGrant]) -> BTreeMap<&str, Vec<PermissionScope>> {
+        let mut by_code: BTreeMap<&str, Vec<PermissionScope>> = BTreeMap::new();
+        for grant in grants {
+            by_code.entry(&grant.code).or_default().push(grant.scope);
+        }
+        by_code
+            .into_iter()
+            .map(|(code, scopes)| (code, PermissionScope::effective(&scopes)))
+            .collect()
+    }
+
+    let before = effective(before);
+    let after = effective(after);
+    let codes: BTreeSet<&str> = before.keys().chain(after.keys()).copied().collect();
+    codes
+        .into_iter()
+        .filter_map(|code| {
+            let was = before.get(code).cloned().unwrap_or_default();
+            let now = after.get(code).cloned().unwrap_or_default();
+            (was != now).then(|| PermissionChange {
+                code: code.to_string(),
+                before: was,
+                after: now,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effective_scopes() {
+        use PermissionScope::*;
+        assert_eq!(PermissionScope::effective(&[]), vec![Own]);
+        assert_eq!(
+            PermissionScope::effective(&[Own, Department]),
+            vec![Department]
+        );
+        assert_eq!(
+            PermissionScope::effective(&[Location, Own, Department]),
+            vec![Department, Location]
+        );
+        assert_eq!(PermissionScope::effective(&[Department, All]), vec![All]);
+        assert_eq!(PermissionScope::parse("department"), Some(Department));
+        assert_eq!(PermissionScope::parse("everything"), None);
+    }
+
+    #[test]
+    fn test_data_scope_from_grants() {
+        let user = Uuid::new_v4();
+        let dept = Some(Uuid::new_v4());
+        let loc = Some(Uuid::new_v4());
+
+        let own = DataScope::from_grants(&[], user, dept, loc);
+        assert!(!own.all_records);
+        assert_eq!(own.owner_id, Some(user));
+        assert_eq!((own.department_id, own.location_id), (None, None));
+
+        let department = DataScope::from_grants(&[PermissionScope::Department], user, dept, loc);
+        assert_eq!(department.department_id, dept);
+        assert_eq!(department.location_id, None);
+
+        // A department grant without a home department reaches own records only
+        let homeless = DataScope::from_grants(&[PermissionScope::Department], user, None, loc);
+        assert_eq!(homeless.department_id, None);
+        assert_eq!(homeless.owner_id, Some(user));
+
+        let all = DataScope::from_grants(
+            &[PermissionScope::Own, PermissionScope::All],
+            user,
+            dept,
+            loc,
+        );
+        assert_eq!(all, DataScope::unrestricted());
+    }
+
+    #[test]
+    fn test_diff_grants() {
+        use PermissionScope::*;
+        let grant = |code: &str, scope| PermissionGrant {
+            code: code.to_string(),
+            scope,
+        };
+        let before = vec![
+            grant("asset.read", Department),
+            grant("asset.read", Own),
+            grant("asset.update", Department),
+            grant("loan.read", All),
+        ];
+        let after = vec![
+            grant("asset.read", Department),
+            grant("asset.update", All),
+            grant("report.view", Location),
+        ];
+
+        // Dropping an `own` grant next to a department one changes nothing
+        assert_eq!(
+            diff_grants(&before, &after),
+            vec![
+                PermissionChange {
+                    code: "asset.update".to_string(),
+                    before: vec![Department],
+                    after: vec![All],
+                },
+                PermissionChange {
+                    code: "loan.read".to_string(),
+                    before: vec![All],
+                    after: vec![],
+                },
+                PermissionChange {
+                    code: "report.view".to_string(),
+                    before: vec![],
+                    after: vec![Location],
+                },
+            ]
+        );
+        assert!(diff_grants(&before, &before).is_empty());
+
+        let parsed: PermissionGrant = serde_json::from_str(r#"{"code":"asset.read"}"#).unwrap();
+        assert_eq!(parsed.scope, All);
+    }
+}
@@ -125,9 +125,8 @@ pub struct Rental {
 
 impl Rental {
     /// Create a new rental request
-    pub fn new(asset_id: Uuid, client_id: Uuid, requested_by: Uuid) -> Self {
+    pub fn new(rental_number: String, asset_id: Uuid, client_id: Uuid, requested_by: Uuid) -> Self {
         let now = Utc::now();
-        let rental_number = format!("RNT-{}", now.format("%Y%m%d%H%M%S"));
 
         Self {
             id: Uuid::new_v4(),
@@ -0,0 +1,120 @@
+//! Session Entity
+//!
+//! Login sessions and their rotating refresh tokens. Access tokens are
+//! short-lived and carry the session id; a refresh token is exchanged exactly
+//! once for a new pair, and presenting a used token again revokes the session.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const REVOKED_LOGOUT: &str = "logout";
+pub const REVOKED_TOKEN_REUSE: &str = "refresh_token_reuse";
+pub const REVOKED_TOKEN_VERSION: &str = "token_version";
+pub const REVOKED_BY_ADMIN: &str = "admin";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct UserSession {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub organization_id: Option<Uuid>, // Active organization
+    pub token_version: i32,            // users.token_version at login
+    pub user_agent: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub last_refreshed_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revoked_reason: Option<String>,
+}
+
+impl UserSession {
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RefreshToken {
+    pub id: Uuid,
+    pub session_id: Uuid,
+    #[serde(skip_serializing)]
+    pub token_hash: String, // SHA-256 of the token handed to the client
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// What presenting a refresh token amounts to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefreshTokenState {
+    Valid,
+    Expired,
+    /// Already exchanged: the token leaked, so the session is revoked
+    Reused,
+    Revoked,
+}
+
+impl RefreshToken {
+    pub fn new(session_id: Uuid, token_hash: String, ttl: Duration) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            session_id,
+            token_hash,
+            expires_at: now + ttl,
+            used_at: None,
+            created_at: Some(now),
+        }
+    }
+
+    pub fn state(&self, session: &UserSession, now: DateTime<Utc>) -> RefreshTokenState {
+        if session.is_revoked() {
+            RefreshTokenState::Revoked
+        } else if self.used_at.is_some() {
+            RefreshTokenState::Reused
+        } else if self.expires_at <= now {
+            RefreshTokenState::Expired
+        } else {
+            RefreshTokenState::Valid
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> UserSession {
+        UserSession {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            organization_id: None,
+            token_version: 0,
+            user_agent: None,
+            created_at: Some(Utc::now()),
+            last_refreshed_at: None,
+            revoked_at: None,
+            revoked_reason: None,
+        }
+    }
+
+    #[test]
+    fn test_refresh_token_state() {
+        let mut s = session();
+        let token = RefreshToken::new(s.id, "hash".to_string(), Duration::days(14));
+        let now = Utc::now();
+        assert_eq!(token.state(&s, now), RefreshTokenState::Valid);
+        assert_eq!(
+            token.state(&s, now + Duration::days(15)),
+            RefreshTokenState::Expired
+        );
+
+        let mut used = token.clone();
+        used.used_at = Some(now);
+        assert_eq!(used.state(&s, now), RefreshTokenState::Reused);
+
+        s.revoked_at = Some(now);
+        s.revoked_reason = Some(REVOKED_LOGOUT.to_string());
+        assert_eq!(used.state(&s, now), RefreshTokenState::Revoked);
+    }
+}
@@ -0,0 +1,387 @@
+//! Asset Specification Entities
+//!
+//! Per-category specification schema, the specification history written by
+//! conversions and manual edits, and the diff between two specification
+//! versions.
+//!
+//! A schema is stored on the category as JSON:
+//!
+//! ```json
+//! {
+//!   "fields": {
+//!     "capacity_ton": { "type": "number", "required": true, "min": 0 },
+//!     "fuel_type": { "type": "string", "enum": ["diesel", "electric"] }
+//!   },
+//!   "allow_additional": true
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::domain::errors::{DomainError, DomainResult};
+
+pub const SPEC_CHANGE_CONVERSION: &str = "conversion";
+pub const SPEC_CHANGE_UPGRADE: &str = "upgrade";
+pub const SPEC_CHANGE_MODIFICATION: &str = "modification";
+pub const SPEC_CHANGE_CORRECTION: &str = "correction";
+
+/// Change types a manual edit may be recorded as
+pub const MANUAL_SPEC_CHANGE_TYPES: [&str; 3] = [
+    SPEC_CHANGE_UPGRADE,
+    SPEC_CHANGE_MODIFICATION,
+    SPEC_CHANGE_CORRECTION,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecificationFieldType {
+    String,
+    Number,
+    Integer,
+    Boolean,
+    Date, // YYYY-MM-DD string
+    Object,
+    Array,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecificationField {
+    #[serde(rename = "type")]
+    pub field_type: SpecificationFieldType,
+    #[serde(default)]
+    pub required: bool,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    #[serde(rename = "enum")]
+    pub allowed_values: Option<Vec<JsonValue>>,
+    pub unit: Option<String>,
+    pub label: Option<String>,
+}
+
+/// Specification schema of a category
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecificationSchema {
+    #[serde(default)]
+    pub fields: BTreeMap<String, SpecificationField>,
+    /// Whether keys not listed in `fields` are accepted
+    #[serde(default = "default_allow_additional")]
+    pub allow_additional: bool,
+}
+
+fn default_allow_additional() -> bool {
+    true
+}
+
+impl SpecificationSchema {
+    /// Parse a schema as stored on a category
+    pub fn parse(value: &JsonValue) -> DomainResult<Self> {
+        let schema: Self = serde_json::from_value(value.clone()).map_err(|e| {
+            DomainError::validation("specification_schema", &format!("Invalid schema: {}", e))
+        })?;
+        for (key, field) in &schema.fields {
+            if let (Some(min), Some(max)) = (field.min, field.max) {
+                if min > max {
+                    return Err(DomainError::validation(
+                        "specification_schema",
+                        &format!("{}: min is greater than max", key),
+                    ));
+                }
+            }
+        }
+        Ok(schema)
+    }
+
+    /// Check specifications against the schema, reporting every violation
+    pub fn validate(&self, specifications: Option<&JsonValue>) -> DomainResult<()> {
+        let empty = serde_json::Map::new();
+        let values = match specifications {
+            None | Some(JsonValue::Null) => &empty,
+            Some(JsonValue::Object(map)) => map,
+            Some(_) => {
+                return Err(DomainError::validation(
+                    "specifications",
+                    "Must be a JSON object",
+                ))
+            }
+        };
+
+        let mut errors = Vec::new();
+        for (key, field) in &self.fields {
+            match values.get(key) {
+                None | Some(JsonValue::Null) => {
+                    if field.required {
+                        errors.push(format!("{} is required", key));
+                    }
+                }
+                Some(value) => {
+                    if let Err(message) = field.check(value) {
+                        errors.push(format!("{} {}", key, message));
+                    }
+                }
+            }
+        }
+        if !self.allow_additional {
+            for key in values.keys().filter(|k| !self.fields.contains_key(*k)) {
+                errors.push(format!("{} is not defined for this category", key));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(DomainError::validation(
+                "specifications",
+                &errors.join("; "),
+            ))
+        }
+    }
+}
+
+impl SpecificationField {
+    fn check(&self, value: &JsonValue) -> Result<(), String> {
+        let type_ok = match self.field_type {
+            SpecificationFieldType::String => value.is_string(),
+            SpecificationFieldType::Number => value.is_number(),
+            SpecificationFieldType::Integer => value.is_i64() || value.is_u64(),
+            SpecificationFieldType::Boolean => value.is_boolean(),
+            SpecificationFieldType::Date => value
+                .as_str()
+                .is_some_and(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok()),
+            SpecificationFieldType::Object => value.is_object(),
+            SpecificationFieldType::Array => value.is_array(),
+        };
+        if !type_ok {
+            return Err(format!("must be of type {}", json!(self.field_type)));
+        }
+
+        if let Some(allowed) = &self.allowed_values {
+            if !allowed.contains(value) {
+                return Err(format!(
+                    "must be one of {}",
+                    JsonValue::from(allowed.clone())
+                ));
+            }
+        }
+
+        // Numbers are bounded by value, strings and arrays by length
+        let measure = match value {
+            JsonValue::Number(n) => n.as_f64(),
+            JsonValue::String(s) => Some(s.chars().count() as f64),
+            JsonValue::Array(a) => Some(a.len() as f64),
+            _ => None,
+        };
+        if let Some(measure) = measure {
+            if self.min.is_some_and(|min| measure < min) {
+                return Err(format!("must be at least {}", self.min.unwrap_or_default()));
+            }
+            if self.max.is_some_and(|max| measure > max) {
+                return Err(format!("must be at most {}", self.max.unwrap_or_default()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One change of category and/or specifications of an asset
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AssetSpecificationHistory {
+    pub id: Uuid,
+    pub asset_id: Uuid,
+    pub conversion_id: Option<Uuid>,
+    pub change_type: String, // conversion, upgrade, modification, correction
+    pub old_category_id: Option<Uuid>,
+    pub new_category_id: Option<Uuid>,
+    pub old_subtype: Option<String>,
+    pub new_subtype: Option<String>,
+    pub old_specifications: Option<JsonValue>,
+    pub new_specifications: Option<JsonValue>,
+    /// `SpecificationDiffEntry` list from old to new specifications
+    pub diff: JsonValue,
+    pub changed_by: Option<Uuid>,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AssetSpecificationHistory {
+    pub fn new(
+        asset_id: Uuid,
+        change_type: &str,
+        old_category_id: Uuid,
+        new_category_id: Uuid,
+        old_specifications: Option<JsonValue>,
+        new_specifications: Option<JsonValue>,
+        changed_by: Option<Uuid>,
+    ) -> Self {
+        let diff = diff_specifications(old_specifications.as_ref(), new_specifications.as_ref());
+        Self {
+            id: Uuid::new_v4(),
+            asset_id,
+            conversion_id: None,
+            change_type: change_type.to_string(),
+            old_category_id: Some(old_category_id),
+            new_category_id: Some(new_category_id),
+            old_subtype: None,
+            new_subtype: None,
+            old_specifications,
+            new_specifications,
+            diff: json!(diff),
+            changed_by,
+            notes: None,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// A changed specification value; nested objects are compared per key and
+/// reported with dotted paths
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpecificationDiffEntry {
+    pub path: String,
+    pub change: String, // added, removed, changed
+    pub old_value: Option<JsonValue>,
+    pub new_value: Option<JsonValue>,
+}
+
+/// Difference between two specification versions of an asset
+#[derive(Debug, Clone, Serialize)]
+pub struct SpecificationDiff {
+    pub asset_id: Uuid,
+    /// History entries compared; `None` for `to` means the current specifications
+    pub from_history_id: Uuid,
+    pub to_history_id: Option<Uuid>,
+    pub from_category_id: Option<Uuid>,
+    pub to_category_id: Option<Uuid>,
+    pub changes: Vec<SpecificationDiffEntry>,
+}
+
+/// Compare two specification documents
+pub fn diff_specifications(
+    old: Option<&JsonValue>,
+    new: Option<&JsonValue>,
+) -> Vec<SpecificationDiffEntry> {
+    // Missing specifications compare as an empty object
+    let empty = json!({});
+    let mut entries = Vec::new();
+    diff_values(
+        "",
+        Some(old.filter(|v| !v.is_null()).unwrap_or(&empty)),
+        Some(new.filter(|v| !v.is_null()).unwrap_or(&empty)),
+        &mut entries,
+    );
+    entries
+}
+
+fn diff_values(
+    path: &str,
+    old: Option<&JsonValue>,
+    new: Option<&JsonValue>,
+    entries: &mut Vec<SpecificationDiffEntry>,
+) {
+    let old = old.filter(|v| !v.is_null());
+    let new = new.filter(|v| !v.is_null());
+    match (old, new) {
+        (Some(JsonValue::Object(a)), Some(JsonValue::Object(b))) => {
+            let mut keys: Vec<&String> = a.keys().chain(b.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                diff_values(&child, a.get(key), b.get(key), entries);
+            }
+        }
+        (None, None) => {}
+        (a, b) if a == b => {}
+        (a, b) => entries.push(SpecificationDiffEntry {
+            path: path.to_string(),
+            change: match (a, b) {
+                (None, _) => "added",
+                (_, None) => "removed",
+                _ => "changed",
+            }
+            .to_string(),
+            old_value: a.cloned(),
+            new_value: b.cloned(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_validation() {
+        let schema = SpecificationSchema::parse(&json!({
+            "fields": {
+                "capacity_ton": { "type": "number", "required": true, "min": 0, "max": 100 },
+                "fuel_type": { "type": "string", "enum": ["diesel", "electric"] },
+                "inspected_on": { "type": "date" }
+            },
+            "allow_additional": false
+        }))
+        .unwrap();
+
+        assert!(schema
+            .validate(Some(&json!({ "capacity_ton": 25, "fuel_type": "diesel" })))
+            .is_ok());
+        assert!(schema.validate(None).is_err());
+        assert!(schema
+            .validate(Some(&json!({ "capacity_ton": 250 })))
+            .is_err());
+        assert!(schema
+            .validate(Some(&json!({ "capacity_ton": 5, "fuel_type": "petrol" })))
+            .is_err());
+        assert!(schema
+            .validate(Some(
+                &json!({ "capacity_ton": 5, "inspected_on": "05/01/2026" })
+            ))
+            .is_err());
+        assert!(schema
+            .validate(Some(&json!({ "capacity_ton": 5, "color": "red" })))
+            .is_err());
+
+        assert!(SpecificationSchema::parse(&json!({
+            "fields": { "x": { "type": "number", "min": 5, "max": 1 } }
+        }))
+        .is_err());
+        assert!(SpecificationSchema::parse(&json!({
+            "fields": { "x": { "type": "decimal" } }
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn test_diff_specifications() {
+        let old = json!({ "engine": { "power_kw": 90, "cylinders": 4 }, "color": "yellow", "cabin": true });
+        let new = json!({ "engine": { "power_kw": 110, "cylinders": 4 }, "color": "yellow", "crane_ton": 5 });
+
+        let diff = diff_specifications(Some(&old), Some(&new));
+        let summary: Vec<(&str, &str)> = diff
+            .iter()
+            .map(|d| (d.path.as_str(), d.change.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("cabin", "removed"),
+                ("crane_ton", "added"),
+                ("engine.power_kw", "changed")
+            ]
+        );
+        assert_eq!(diff[2].old_value, Some(json!(90)));
+        assert_eq!(diff[2].new_value, Some(json!(110)));
+
+        assert!(diff_specifications(Some(&old), Some(&old)).is_empty());
+        assert_eq!(diff_specifications(None, Some(&new)).len(), 3);
+    }
+}
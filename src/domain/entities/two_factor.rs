@@ -0,0 +1,180 @@
+//! Two-Factor Entity
+//!
+//! TOTP (RFC 6238: HMAC-SHA1, 6 digits, 30 second steps) enrolment of a user
+//! and its one-time recovery codes. Roles flagged in the RBAC tables, or
+//! holding a flagged permission, must log in with a second factor.
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const TOTP_STEP_SECS: i64 = 30;
+pub const TOTP_DIGITS: u32 = 6;
+/// Steps accepted either side of the current one, for clock drift
+pub const TOTP_SKEW_STEPS: i64 = 1;
+pub const RECOVERY_CODE_COUNT: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct UserTwoFactor {
+    pub user_id: Uuid,
+    #[serde(skip_serializing)]
+    pub secret: String, // base32
+    pub enabled_at: Option<DateTime<Utc>>, // None while enrolment is pending
+    pub last_used_step: Option<i64>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Two-factor state of a user as shown to them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoFactorStatus {
+    pub enabled: bool,
+    pub required: bool,
+    pub recovery_codes_left: i64,
+}
+
+/// Secret handed out on enrolment, for authenticator apps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoFactorSetup {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+impl UserTwoFactor {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled_at.is_some()
+    }
+
+    /// Time step of `code` within the drift window, if it is valid and newer
+    /// than the last code used
+    pub fn matching_step(&self, code: &str, now: DateTime<Utc>) -> Option<i64> {
+        let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        if code.len() != TOTP_DIGITS as usize {
+            return None;
+        }
+        let secret = decode_secret(&self.secret)?;
+        let current = now.timestamp() / TOTP_STEP_SECS;
+
+        (current - TOTP_SKEW_STEPS..=current + TOTP_SKEW_STEPS)
+            .filter(|step| self.last_used_step.is_none_or(|last| *step > last))
+            .find(|step| totp_code(&secret, *step as u64) == code)
+    }
+}
+
+/// HOTP value (RFC 4226) of a counter, as used by TOTP
+pub fn totp_code(secret: &[u8], step: u64) -> String {
+    let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(&step.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+    format!(
+        "{:0width$}",
+        binary % 10u32.pow(TOTP_DIGITS),
+        width = TOTP_DIGITS as usize
+    )
+}
+
+pub fn decode_secret(secret: &str) -> Option<Vec<u8>> {
+    data_encoding::BASE32_NOPAD
+        .decode(secret.trim_end_matches('=').as_bytes())
+        .ok()
+}
+
+/// `otpauth://` URI understood by authenticator apps (usually shown as a QR code)
+pub fn provisioning_uri(issuer: &str, account: &str, secret: &str) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        uri_encode(issuer),
+        uri_encode(account),
+        secret,
+        uri_encode(issuer),
+        TOTP_DIGITS,
+        TOTP_STEP_SECS
+    )
+}
+
+fn uri_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Recovery codes are compared case-insensitively, ignoring separators
+pub fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn test_totp_rfc6238_vectors() {
+        // SHA-1 vectors of RFC 6238 appendix B, truncated to 6 digits
+        assert_eq!(totp_code(RFC_SECRET, 59 / 30), "287082");
+        assert_eq!(totp_code(RFC_SECRET, 1111111109 / 30), "081804");
+        assert_eq!(totp_code(RFC_SECRET, 1234567890 / 30), "005924");
+        assert_eq!(totp_code(RFC_SECRET, 2000000000 / 30), "279037");
+    }
+
+    #[test]
+    fn test_matching_step_window_and_replay() {
+        let secret = data_encoding::BASE32_NOPAD.encode(RFC_SECRET);
+        let mut tf = UserTwoFactor {
+            user_id: Uuid::new_v4(),
+            secret,
+            enabled_at: Some(Utc::now()),
+            last_used_step: None,
+            created_at: None,
+            updated_at: None,
+        };
+        let now = Utc.timestamp_opt(1111111109, 0).unwrap();
+        let step = 1111111109 / 30;
+
+        assert_eq!(tf.matching_step("081804", now), Some(step));
+        assert_eq!(tf.matching_step("081 804", now), Some(step));
+        // The previous step is still accepted, two steps back is not
+        let previous = totp_code(RFC_SECRET, step as u64 - 1);
+        assert_eq!(tf.matching_step(&previous, now), Some(step - 1));
+        let stale = totp_code(RFC_SECRET, step as u64 - 2);
+        assert_eq!(tf.matching_step(&stale, now), None);
+        assert_eq!(tf.matching_step("12345", now), None);
+
+        // A code is accepted once
+        tf.last_used_step = Some(step);
+        assert_eq!(tf.matching_step("081804", now), None);
+    }
+
+    #[test]
+    fn test_provisioning_uri() {
+        let uri = provisioning_uri("Asset Manager", "admin@example.com", "JBSWY3DPEHPK3PXP");
+        assert_eq!(
+            uri,
+            "otpauth://totp/Asset%20Manager:admin%40example.com?secret=JBSWY3DPEHPK3PXP\
+             &issuer=Asset%20Manager&algorithm=SHA1&digits=6&period=30"
+        );
+        assert_eq!(normalize_recovery_code("abcde-12345"), "ABCDE12345");
+    }
+}
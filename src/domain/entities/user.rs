@@ -80,6 +80,8 @@ pub struct User {
     pub department: Option<String>,
     pub department_id: Option<Uuid>,
     pub organization_id: Option<Uuid>,
+    #[sqlx(default)]
+    pub location_id: Option<Uuid>, // Root of location-scoped permission grants
 
     // Profile
     pub phone: Option<String>,
@@ -89,6 +91,10 @@ pub struct User {
     pub is_active: bool,
     pub email_verified: bool,
     pub last_login_at: Option<DateTime<Utc>>,
+    #[sqlx(default)]
+    pub is_service_account: bool, // API keys only, no password login
+    #[sqlx(default)]
+    pub must_change_password: bool, // Set by an admin; asked for at the next login
 
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -108,11 +114,14 @@ impl User {
             department: None,
             department_id: None,
             organization_id: None,
+            location_id: None,
             phone: None,
             avatar_url: None,
             is_active: true,
             email_verified: false,
             last_login_at: None,
+            is_service_account: false,
+            must_change_password: false,
             created_at: now,
             updated_at: now,
         }
@@ -177,10 +186,44 @@ pub struct UserClaims {
     pub exp: i64,
     pub iat: i64,
     pub jti: String, // JWT ID for revocation
+    #[serde(default)]
+    pub sid: Option<Uuid>, // Login session; revoked sessions are rejected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_id: Option<Uuid>, // Set when authenticated with an API key
+}
+
+/// Lifetime of a login challenge token
+pub const LOGIN_CHALLENGE_MINUTES: i64 = 5;
+/// Login steps that may follow the password
+pub const CHALLENGE_TWO_FACTOR: &str = "two_factor";
+pub const CHALLENGE_PASSWORD_CHANGE: &str = "password_change";
+
+/// Claims of a login challenge token: it identifies the user for one further
+/// login step only and is not accepted as an access token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoginChallengeClaims {
+    pub sub: String, // User ID
+    pub purpose: String,
+    pub exp: i64,
+    pub iat: i64,
+    pub jti: String,
 }
 
 impl UserClaims {
     pub fn user_id(&self) -> Uuid {
         Uuid::parse_str(&self.sub).unwrap_or_else(|_| Uuid::nil())
     }
+
+    /// Active organization; data access is limited to its subtree
+    pub fn organization_id(&self) -> Option<Uuid> {
+        self.org.as_deref().and_then(|id| Uuid::parse_str(id).ok())
+    }
+
+    pub fn session_id(&self) -> Option<Uuid> {
+        self.sid
+    }
+
+    pub fn is_api_key(&self) -> bool {
+        self.api_key_id.is_some()
+    }
 }
@@ -0,0 +1,59 @@
+//! Asset Valuation Entity
+//!
+//! Point-in-time values of an asset: monthly snapshots calculated from the
+//! depreciation schedule, appraisals and market quotes, and impairments. A
+//! valuation with a `revalued_amount` replaces the carrying amount from the start
+//! of its month and depreciation continues from it.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const VALUATION_CALCULATED: &str = "calculated";
+pub const VALUATION_APPRAISAL: &str = "appraisal";
+pub const VALUATION_MARKET: &str = "market";
+pub const VALUATION_IMPAIRMENT: &str = "impairment";
+
+pub const VALUATION_TYPES: [&str; 4] = [
+    VALUATION_CALCULATED,
+    VALUATION_APPRAISAL,
+    VALUATION_MARKET,
+    VALUATION_IMPAIRMENT,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AssetValuation {
+    pub id: Uuid,
+    pub asset_id: Uuid,
+    pub valuation_date: NaiveDate,
+
+    // Book figures at the valuation date (before any revaluation it books)
+    pub original_cost: Option<Decimal>,
+    pub accumulated_depreciation: Option<Decimal>,
+    pub book_value: Option<Decimal>,
+
+    // Appraised figures
+    pub market_value: Option<Decimal>,
+    pub replacement_cost: Option<Decimal>,
+
+    pub valuation_type: String, // calculated, appraisal, market, impairment
+    pub appraiser: Option<String>,
+    pub notes: Option<String>,
+
+    // Revaluation: new carrying amount and the change from book value
+    pub revalued_amount: Option<Decimal>,
+    pub adjustment_amount: Option<Decimal>,
+
+    pub created_by: Option<Uuid>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl AssetValuation {
+    /// Whether this valuation changed the carrying amount
+    pub fn is_revaluation(&self) -> bool {
+        self.revalued_amount.is_some()
+    }
+}
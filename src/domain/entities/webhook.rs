@@ -0,0 +1,194 @@
+//! Webhook Entity
+//!
+//! Subscriptions of external systems to domain events, and the log of event
+//! deliveries to them. Deliveries are signed with the subscription's secret:
+//! receivers compute HMAC-SHA256 over `<timestamp>.<body>` and compare it with
+//! the `X-Webhook-Signature` header.
+
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const WEBHOOK_PENDING: &str = "pending";
+pub const WEBHOOK_RETRYING: &str = "retrying";
+pub const WEBHOOK_SENT: &str = "sent";
+/// Out of attempts; a dead letter until redelivered by hand
+pub const WEBHOOK_DEAD: &str = "dead";
+
+pub const WEBHOOK_DEFAULT_MAX_ATTEMPTS: i32 = 8;
+/// First retry delay; doubles with every failed attempt
+pub const WEBHOOK_RETRY_BASE_SECONDS: i64 = 30;
+pub const WEBHOOK_RETRY_MAX_SECONDS: i64 = 6 * 3600;
+
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+pub const WEBHOOK_EVENT_HEADER: &str = "X-Webhook-Event";
+pub const WEBHOOK_DELIVERY_HEADER: &str = "X-Webhook-Delivery";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub name: String,
+    pub url: String,
+    pub event_types: Vec<String>, // Empty for every event
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub is_active: bool,
+    pub max_attempts: i32,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookSubscription {
+    /// Whether the subscription wants an event: an exact type, a `prefix.*`
+    /// pattern, or no filter at all
+    pub fn matches(&self, event_type: &str) -> bool {
+        self.event_types.is_empty()
+            || self
+                .event_types
+                .iter()
+                .any(|filter| event_type_matches(filter, event_type))
+    }
+}
+
+fn event_type_matches(filter: &str, event_type: &str) -> bool {
+    match filter.strip_suffix(".*") {
+        Some(prefix) => event_type
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('.')),
+        None => filter == "*" || filter == event_type,
+    }
+}
+
+/// Whether a filter is `*`, an event type or a `prefix.*` pattern
+pub fn is_valid_event_filter(filter: &str) -> bool {
+    let name = filter.strip_suffix(".*").unwrap_or(filter);
+    filter == "*"
+        || (!name.is_empty()
+            && name.len() <= 100
+            && name.split('.').all(|part| {
+                !part.is_empty() && part.chars().all(|c| c.is_ascii_lowercase() || c == '_')
+            }))
+}
+
+/// A subscription with its secret, returned once when created
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookSubscriptionSecret {
+    #[serde(flatten)]
+    pub subscription: WebhookSubscription,
+    pub secret: String,
+}
+
+/// One event sent to one subscription, logged with its attempts
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookDelivery {
+    /// Wait before the next attempt after `attempts` failed ones
+    pub fn retry_delay(attempts: i32) -> Duration {
+        let exponent = attempts.clamp(1, 20) as u32 - 1;
+        let seconds = WEBHOOK_RETRY_BASE_SECONDS.saturating_mul(1 << exponent);
+        Duration::seconds(seconds.min(WEBHOOK_RETRY_MAX_SECONDS))
+    }
+}
+
+/// `sha256=<hex>` signature of a delivery body sent at `timestamp` (Unix seconds)
+pub fn webhook_signature(secret: &str, timestamp: i64, body: &str) -> String {
+    let signed = format!("{}.{}", timestamp, body);
+    format!(
+        "sha256={}",
+        hmac_sha256_hex(secret.as_bytes(), signed.as_bytes())
+    )
+}
+
+fn hmac_sha256_hex(key: &[u8], message: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(message);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subscription(event_types: &[&str]) -> WebhookSubscription {
+        WebhookSubscription {
+            id: Uuid::new_v4(),
+            name: "ERP".to_string(),
+            url: "https://erp.example.com/hooks".to_string(),
+            event_types: event_types.iter().map(|s| s.to_string()).collect(),
+            secret: "secret".to_string(),
+            is_active: true,
+            max_attempts: WEBHOOK_DEFAULT_MAX_ATTEMPTS,
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_event_type_filters() {
+        assert!(subscription(&[]).matches("asset.created"));
+        assert!(subscription(&["*"]).matches("rental.returned"));
+
+        let s = subscription(&["asset.created", "rental.*"]);
+        assert!(s.matches("asset.created"));
+        assert!(s.matches("rental.returned"));
+        assert!(s.matches("rental.billing_approved"));
+        assert!(!s.matches("asset.state_changed"));
+        assert!(!s.matches("rentals.returned"));
+        assert!(!s.matches("rental"));
+
+        assert!(is_valid_event_filter("asset.created"));
+        assert!(is_valid_event_filter("maintenance.*"));
+        assert!(is_valid_event_filter("*"));
+        assert!(!is_valid_event_filter(""));
+        assert!(!is_valid_event_filter(".*"));
+        assert!(!is_valid_event_filter("asset..created"));
+        assert!(!is_valid_event_filter("Asset.Created"));
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_exponentially() {
+        assert_eq!(WebhookDelivery::retry_delay(1), Duration::seconds(30));
+        assert_eq!(WebhookDelivery::retry_delay(2), Duration::seconds(60));
+        assert_eq!(WebhookDelivery::retry_delay(5), Duration::seconds(480));
+        assert_eq!(WebhookDelivery::retry_delay(12), Duration::hours(6));
+        assert_eq!(WebhookDelivery::retry_delay(100), Duration::hours(6));
+    }
+
+    #[test]
+    fn test_signature() {
+        // RFC 4231, test case 2
+        assert_eq!(
+            hmac_sha256_hex(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            webhook_signature("Jefe", 1700000000, "{}"),
+            format!("sha256={}", hmac_sha256_hex(b"Jefe", b"1700000000.{}"))
+        );
+        assert_ne!(
+            webhook_signature("Jefe", 1700000000, "{}"),
+            webhook_signature("Jefe", 1700000001, "{}")
+        );
+    }
+}
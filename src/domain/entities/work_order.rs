@@ -116,14 +116,16 @@ pub struct WorkOrder {
     pub approved_by: Option<Uuid>,
     pub completed_by: Option<Uuid>,
 
+    // Preventive schedule that generated this work order
+    pub preventive_schedule_id: Option<Uuid>,
+
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 impl WorkOrder {
-    pub fn new(asset_id: Uuid, wo_type: &str) -> Self {
+    pub fn new(wo_number: String, asset_id: Uuid, wo_type: &str) -> Self {
         let now = Utc::now();
-        let wo_number = format!("WO-{}", now.format("%Y%m%d%H%M%S"));
 
         Self {
             id: Uuid::new_v4(),
@@ -156,6 +158,7 @@ impl WorkOrder {
             created_by: None,
             approved_by: None,
             completed_by: None,
+            preventive_schedule_id: None,
             created_at: now,
             updated_at: now,
         }
@@ -31,6 +31,12 @@ pub enum DomainError {
     /// Bad request
     BadRequest { message: String },
 
+    /// Rate limited or locked out; retry after the given seconds
+    TooManyRequests {
+        message: String,
+        retry_after_secs: u64,
+    },
+
     /// Internal error
     Internal { message: String },
 
@@ -85,6 +91,13 @@ impl DomainError {
         }
     }
 
+    pub fn too_many_requests(message: &str, retry_after_secs: u64) -> Self {
+        Self::TooManyRequests {
+            message: message.to_string(),
+            retry_after_secs,
+        }
+    }
+
     pub fn internal(message: impl ToString) -> Self {
         Self::Internal {
             message: message.to_string(),
@@ -119,6 +132,12 @@ impl fmt::Display for DomainError {
             Self::BadRequest { message } => {
                 write!(f, "Bad request: {}", message)
             }
+            Self::TooManyRequests {
+                message,
+                retry_after_secs,
+            } => {
+                write!(f, "{} (retry after {}s)", message, retry_after_secs)
+            }
             Self::Internal { message } => {
                 write!(f, "Internal error: {}", message)
             }
@@ -53,8 +53,10 @@ impl DomainEvent for LoanApproved {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoanCheckedOut {
     pub loan_id: Uuid,
+    pub loan_number: String,
     pub asset_id: Uuid,
-    pub borrower_id: Uuid,
+    pub borrower_id: Option<Uuid>, // Loans to employees have no borrowing user
+    pub employee_id: Option<Uuid>,
     pub checked_out_by: Uuid,
     pub occurred_at: DateTime<Utc>,
 }
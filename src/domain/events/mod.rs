@@ -5,10 +5,12 @@
 pub mod asset_events;
 pub mod loan_events;
 pub mod maintenance_events;
+pub mod rental_events;
 
 pub use asset_events::*;
 pub use loan_events::*;
 pub use maintenance_events::*;
+pub use rental_events::*;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -61,3 +63,12 @@ impl<T: Serialize> EventEnvelope<T> {
         self
     }
 }
+
+impl<T: DomainEvent> EventEnvelope<T> {
+    /// Envelope of a domain event, with its type, aggregate and time
+    pub fn from_event(event: T) -> Self {
+        let mut envelope = Self::new(event.event_type(), event.aggregate_id(), event);
+        envelope.occurred_at = envelope.payload.occurred_at();
+        envelope
+    }
+}
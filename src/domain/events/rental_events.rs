@@ -0,0 +1,55 @@
+//! Rental Domain Events
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::DomainEvent;
+
+/// Rental returned event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RentalReturned {
+    pub rental_id: Uuid,
+    pub rental_number: String,
+    pub asset_id: Uuid,
+    pub client_id: Uuid,
+    pub returned_by: Uuid,
+    pub total_days: i32,
+    pub total_amount: Decimal,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl DomainEvent for RentalReturned {
+    fn event_type(&self) -> &'static str {
+        "rental.returned"
+    }
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+    fn aggregate_id(&self) -> Uuid {
+        self.rental_id
+    }
+}
+
+/// Billing period approved event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillingApproved {
+    pub billing_id: Uuid,
+    pub rental_id: Uuid,
+    pub total_amount: Option<Decimal>,
+    pub approved_by: Uuid,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl DomainEvent for BillingApproved {
+    fn event_type(&self) -> &'static str {
+        "rental.billing_approved"
+    }
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+    fn aggregate_id(&self) -> Uuid {
+        self.billing_id
+    }
+}
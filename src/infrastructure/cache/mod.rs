@@ -1,7 +1,9 @@
 //! Cache Module
 //!
-//! Redis cache integration.
+//! Redis cache integration and rate limiting.
 
+pub mod rate_limiter;
 pub mod redis_client;
 
+pub use rate_limiter::*;
 pub use redis_client::*;
@@ -0,0 +1,107 @@
+//! Rate Limiter
+//!
+//! Token buckets kept in Redis so every instance shares them. When Redis is
+//! unreachable the limiter falls back to per-process buckets and retries Redis
+//! after a short pause.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+
+use super::{CacheKey, RedisCache};
+use crate::domain::entities::{RateLimitDecision, RateLimitPolicy, TokenBucket};
+
+/// Refill and take one token atomically; returns {allowed, tokens left}
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local capacity = tonumber(ARGV[1])
+local refill_per_sec = tonumber(ARGV[2])
+local now = tonumber(ARGV[3])
+local state = redis.call('HMGET', KEYS[1], 'tokens', 'ts')
+local tokens = tonumber(state[1]) or capacity
+local ts = tonumber(state[2]) or now
+tokens = math.min(capacity, tokens + math.max(0, now - ts) / 1000 * refill_per_sec)
+local allowed = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+end
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'ts', now)
+redis.call('EXPIRE', KEYS[1], tonumber(ARGV[4]))
+return {allowed, tostring(tokens)}
+"#;
+
+/// Pause before trying Redis again after a failure
+const REDIS_RETRY_MS: i64 = 30_000;
+/// Local buckets kept before idle ones are pruned
+const MAX_LOCAL_BUCKETS: usize = 10_000;
+
+#[derive(Clone)]
+pub struct RateLimiter {
+    redis: Option<RedisCache>,
+    script: Arc<redis::Script>,
+    local: Arc<Mutex<HashMap<String, TokenBucket>>>,
+    redis_retry_at_ms: Arc<AtomicI64>,
+}
+
+impl RateLimiter {
+    pub fn new(redis: Option<RedisCache>) -> Self {
+        Self {
+            redis,
+            script: Arc::new(redis::Script::new(TOKEN_BUCKET_SCRIPT)),
+            local: Arc::new(Mutex::new(HashMap::new())),
+            redis_retry_at_ms: Arc::new(AtomicI64::new(0)),
+        }
+    }
+
+    /// Limiter with per-process buckets only
+    pub fn in_memory() -> Self {
+        Self::new(None)
+    }
+
+    /// Take one request of `subject` (client address, user id) under `policy`
+    pub async fn check(&self, policy: &RateLimitPolicy, subject: &str) -> RateLimitDecision {
+        let key = CacheKey::rate_limit(policy.name, subject);
+        let now_ms = Utc::now().timestamp_millis();
+
+        if let Some(redis) = &self.redis {
+            if now_ms >= self.redis_retry_at_ms.load(Ordering::Relaxed) {
+                let args = [
+                    policy.capacity.to_string(),
+                    policy.refill_per_sec.to_string(),
+                    now_ms.to_string(),
+                    policy.ttl_secs().to_string(),
+                ];
+                match redis
+                    .eval::<(i64, String)>(&self.script, &[&key], &args)
+                    .await
+                {
+                    Ok((allowed, tokens)) => {
+                        return policy.decision(allowed == 1, tokens.parse().unwrap_or(0.0));
+                    }
+                    Err(e) => {
+                        tracing::warn!("Rate limiter falling back to local buckets: {}", e);
+                        self.redis_retry_at_ms
+                            .store(now_ms + REDIS_RETRY_MS, Ordering::Relaxed);
+                    }
+                }
+            }
+        }
+
+        self.check_local(policy, key, now_ms)
+    }
+
+    fn check_local(&self, policy: &RateLimitPolicy, key: String, now_ms: i64) -> RateLimitDecision {
+        let mut buckets = self.local.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() >= MAX_LOCAL_BUCKETS {
+            // Buckets idle long enough to be full again carry no state
+            let idle_ms = policy.ttl_secs() as i64 * 1000;
+            buckets.retain(|_, b| now_ms - b.updated_at_ms < idle_ms);
+        }
+
+        let (bucket, decision) = policy.take(buckets.get(&key).copied(), now_ms);
+        buckets.insert(key, bucket);
+        decision
+    }
+}
@@ -148,6 +148,45 @@ impl RedisCache {
             default_ttl: config.default_ttl,
         }
     }
+
+    /// Publish a message on a pub/sub channel; returns the number of
+    /// subscribers that received it
+    pub async fn publish(&self, channel: &str, message: &str) -> Result<i64, CacheError> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| CacheError::ConnectionError(e.to_string()))?;
+        conn.publish(channel, message)
+            .await
+            .map_err(|e| CacheError::OperationError(e.to_string()))
+    }
+
+    /// Run a Lua script atomically
+    pub async fn eval<T: redis::FromRedisValue>(
+        &self,
+        script: &redis::Script,
+        keys: &[&str],
+        args: &[String],
+    ) -> Result<T, CacheError> {
+        let mut conn = self
+            .pool
+            .get()
+            .await
+            .map_err(|e| CacheError::ConnectionError(e.to_string()))?;
+
+        let mut invocation = script.prepare_invoke();
+        for key in keys {
+            invocation.key(*key);
+        }
+        for arg in args {
+            invocation.arg(arg);
+        }
+        invocation
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| CacheError::OperationError(e.to_string()))
+    }
 }
 
 #[async_trait::async_trait]
@@ -288,4 +327,9 @@ impl CacheKey {
     pub fn user_session(user_id: &uuid::Uuid) -> String {
         format!("session:{}", user_id)
     }
+
+    /// Rate limit bucket of a policy and subject (client address or user)
+    pub fn rate_limit(policy: &str, subject: &str) -> String {
+        format!("ratelimit:{}:{}", policy, subject)
+    }
 }
@@ -0,0 +1,159 @@
+//! Cluster Bus
+//! Fans messages out to every backend instance over Redis pub/sub. Without
+//! Redis, or while it is unreachable, messages stay within this process.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::infrastructure::cache::{RedisCache, RedisConfig};
+
+/// Redis channels of the bus share this prefix; the bus channel follows it
+const CHANNEL_PREFIX: &str = "asset_management:cluster:";
+const RECONNECT_MIN: Duration = Duration::from_secs(1);
+const RECONNECT_MAX: Duration = Duration::from_secs(30);
+
+/// A message published on another instance
+#[derive(Debug, Clone)]
+pub struct ClusterMessage {
+    pub channel: String,
+    pub payload: String,
+}
+
+/// What goes over Redis: the payload and the instance it came from
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope {
+    origin: Uuid,
+    payload: String,
+}
+
+#[derive(Clone)]
+pub struct ClusterBus {
+    node_id: Uuid,
+    redis: Option<RedisCache>,
+    connected: Arc<AtomicBool>,
+    remote: broadcast::Sender<ClusterMessage>,
+}
+
+impl ClusterBus {
+    /// A bus of this process only
+    pub fn local() -> Self {
+        let (remote, _) = broadcast::channel(1024);
+        Self {
+            node_id: Uuid::new_v4(),
+            redis: None,
+            connected: Arc::new(AtomicBool::new(false)),
+            remote,
+        }
+    }
+
+    /// A bus shared with the instances using the same Redis. The subscription
+    /// is kept up in the background and re-established after Redis outages;
+    /// until it is up, publishing stays in-process.
+    pub fn redis(config: &RedisConfig, cache: RedisCache) -> Self {
+        let mut bus = Self::local();
+        let client = match redis::Client::open(config.url.as_str()) {
+            Ok(client) => client,
+            Err(e) => {
+                warn!("Invalid REDIS_URL, cluster fan-out disabled: {}", e);
+                return bus;
+            }
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            warn!("No async runtime, cluster fan-out disabled");
+            return bus;
+        };
+        bus.redis = Some(cache);
+        runtime.spawn(bus.clone().subscribe_loop(client));
+        bus
+    }
+
+    pub fn node_id(&self) -> Uuid {
+        self.node_id
+    }
+
+    /// Whether messages currently reach the other instances
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    /// Messages published on other instances
+    pub fn subscribe(&self) -> broadcast::Receiver<ClusterMessage> {
+        self.remote.subscribe()
+    }
+
+    /// Send a message to the other instances. Local subscribers are not
+    /// included; the caller handles the message in-process itself.
+    pub async fn publish(&self, channel: &str, payload: &str) {
+        let Some(redis) = self.redis.as_ref().filter(|_| self.is_connected()) else {
+            return;
+        };
+        let envelope = Envelope {
+            origin: self.node_id,
+            payload: payload.to_string(),
+        };
+        let Ok(json) = serde_json::to_string(&envelope) else {
+            return;
+        };
+        let channel = format!("{}{}", CHANNEL_PREFIX, channel);
+        if let Err(e) = redis.publish(&channel, &json).await {
+            warn!("Cluster publish on {} failed: {}", channel, e);
+        }
+    }
+
+    async fn subscribe_loop(self, client: redis::Client) {
+        let mut backoff = RECONNECT_MIN;
+        let mut reported = false;
+        loop {
+            match self.listen(&client).await {
+                Ok(()) => backoff = RECONNECT_MIN,
+                Err(e) if !reported => {
+                    info!("Redis unavailable, running in-process only: {}", e);
+                    reported = true;
+                }
+                Err(e) => debug!("Cluster subscription unavailable: {}", e),
+            }
+            if self.connected.swap(false, Ordering::Relaxed) {
+                warn!("Lost cluster subscription; WebSocket events stay on this instance");
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(RECONNECT_MAX);
+        }
+    }
+
+    /// Forward messages of other instances until the connection drops
+    async fn listen(&self, client: &redis::Client) -> redis::RedisResult<()> {
+        use futures::StreamExt;
+
+        let mut pubsub = client.get_async_pubsub().await?;
+        pubsub.psubscribe(format!("{}*", CHANNEL_PREFIX)).await?;
+        self.connected.store(true, Ordering::Relaxed);
+        info!("Cluster fan-out over Redis as node {}", self.node_id);
+
+        let mut messages = pubsub.on_message();
+        while let Some(msg) = messages.next().await {
+            let channel = msg.get_channel_name();
+            let Some(channel) = channel.strip_prefix(CHANNEL_PREFIX) else {
+                continue;
+            };
+            let Ok(payload) = msg.get_payload::<String>() else {
+                continue;
+            };
+            match serde_json::from_str::<Envelope>(&payload) {
+                Ok(envelope) if envelope.origin != self.node_id => {
+                    let _ = self.remote.send(ClusterMessage {
+                        channel: channel.to_string(),
+                        payload: envelope.payload,
+                    });
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Malformed cluster message on {}: {}", channel, e),
+            }
+        }
+        Ok(())
+    }
+}
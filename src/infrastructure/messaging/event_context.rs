@@ -0,0 +1,56 @@
+//! Event Context
+//! Correlation of the domain events raised while handling one request. The
+//! context is set per request and read when events are written to the outbox.
+
+use std::future::Future;
+use uuid::Uuid;
+
+use crate::domain::events::EventMetadata;
+
+tokio::task_local! {
+    static CURRENT: EventContext;
+}
+
+#[derive(Debug, Clone)]
+pub struct EventContext {
+    /// Shared by everything done for one business transaction, across requests
+    pub correlation_id: String,
+    /// The request that caused the events
+    pub causation_id: String,
+    pub user_id: Option<Uuid>,
+}
+
+impl EventContext {
+    /// Run `f` with this context
+    pub async fn scope<F: Future>(self, f: F) -> F::Output {
+        CURRENT.scope(self, f).await
+    }
+
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(|context| context.clone()).ok()
+    }
+
+    /// Fill in what the metadata of event `event_id` leaves open. Outside of a
+    /// request an event starts its own correlation and is its own cause.
+    pub fn fill(metadata: &mut EventMetadata, event_id: Uuid) {
+        let context = Self::current();
+        if metadata.user_id.is_none() {
+            metadata.user_id = context.as_ref().and_then(|c| c.user_id);
+        }
+        if metadata.correlation_id.is_none() {
+            metadata.correlation_id = Some(
+                context
+                    .as_ref()
+                    .map(|c| c.correlation_id.clone())
+                    .unwrap_or_else(|| event_id.to_string()),
+            );
+        }
+        if metadata.causation_id.is_none() {
+            metadata.causation_id = Some(
+                context
+                    .map(|c| c.causation_id)
+                    .unwrap_or_else(|| event_id.to_string()),
+            );
+        }
+    }
+}
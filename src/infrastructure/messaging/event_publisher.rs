@@ -1,28 +1,72 @@
 //! Event Publisher
-//! Simple in-memory event bus. Can be replaced with Redis/RabbitMQ/Kafka.
+//! In-memory event bus. With a cluster bus, events published on any instance
+//! reach the subscribers of every instance.
 
 use serde::Serialize;
 use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::infrastructure::messaging::ClusterBus;
+
+/// Cluster bus channel of domain events
+const CLUSTER_CHANNEL: &str = "events";
 
 /// Event publisher for domain events
 pub struct EventPublisher {
     sender: broadcast::Sender<String>,
+    cluster: Option<ClusterBus>,
 }
 
 impl EventPublisher {
     pub fn new(capacity: usize) -> Self {
         let (sender, _) = broadcast::channel(capacity);
-        Self { sender }
+        Self {
+            sender,
+            cluster: None,
+        }
     }
 
     pub fn default() -> Self {
         Self::new(1000)
     }
 
+    /// Publisher whose events also go to, and come from, the other instances
+    /// on the cluster bus
+    pub fn with_cluster(capacity: usize, cluster: ClusterBus) -> Self {
+        let mut publisher = Self::new(capacity);
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let sender = publisher.sender.clone();
+            let mut remote = cluster.subscribe();
+            runtime.spawn(async move {
+                loop {
+                    match remote.recv().await {
+                        Ok(message) if message.channel == CLUSTER_CHANNEL => {
+                            // No local subscribers is not an error here
+                            let _ = sender.send(message.payload);
+                        }
+                        Ok(_) => {}
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            warn!("Dropped {} events from the cluster", skipped);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            });
+        }
+        publisher.cluster = Some(cluster);
+        publisher
+    }
+
     /// Publish an event
     pub fn publish<T: Serialize>(&self, event_type: &str, payload: &T) -> Result<(), String> {
         let json = serde_json::to_string(payload).map_err(|e| e.to_string())?;
         let message = format!("{}:{}", event_type, json);
+        if let (Some(cluster), Ok(runtime)) =
+            (self.cluster.clone(), tokio::runtime::Handle::try_current())
+        {
+            let message = message.clone();
+            runtime.spawn(async move { cluster.publish(CLUSTER_CHANNEL, &message).await });
+        }
         self.sender.send(message).map_err(|e| e.to_string())?;
         Ok(())
     }
@@ -37,6 +81,7 @@ impl Clone for EventPublisher {
     fn clone(&self) -> Self {
         Self {
             sender: self.sender.clone(),
+            cluster: self.cluster.clone(),
         }
     }
 }
@@ -1,3 +1,13 @@
-//! Messaging Module - Event publishing
+//! Messaging Module - Event publishing and WebSocket push
 
+pub mod cluster_bus;
+pub mod event_context;
 pub mod event_publisher;
+pub mod webhook_sender;
+pub mod websocket_manager;
+
+pub use cluster_bus::{ClusterBus, ClusterMessage};
+pub use event_context::EventContext;
+pub use event_publisher::EventPublisher;
+pub use webhook_sender::WebhookSender;
+pub use websocket_manager::{Audience, NotificationMessage, Topic, WebSocketManager};
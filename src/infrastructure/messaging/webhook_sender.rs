@@ -0,0 +1,64 @@
+//! Webhook Sender - signed HTTP POSTs of event envelopes
+
+use chrono::Utc;
+use std::time::Duration;
+
+use crate::domain::entities::{
+    webhook_signature, WebhookDelivery, WEBHOOK_DELIVERY_HEADER, WEBHOOK_EVENT_HEADER,
+    WEBHOOK_SIGNATURE_HEADER, WEBHOOK_TIMESTAMP_HEADER,
+};
+
+/// Longest a receiver may take to answer
+pub const WEBHOOK_TIMEOUT_SECONDS: u64 = 10;
+
+#[derive(Clone)]
+pub struct WebhookSender {
+    client: reqwest::Client,
+}
+
+impl Default for WebhookSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WebhookSender {
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECONDS))
+            .build()
+            .unwrap_or_default();
+
+        Self { client }
+    }
+
+    /// POST a delivery's payload to `url`, signed with `secret`. Returns the
+    /// receiver's status code, or the transport error if there was no answer.
+    pub async fn send(
+        &self,
+        url: &str,
+        secret: &str,
+        delivery: &WebhookDelivery,
+    ) -> Result<u16, String> {
+        let body = delivery.payload.to_string();
+        let timestamp = Utc::now().timestamp();
+
+        let response = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(WEBHOOK_DELIVERY_HEADER, delivery.id.to_string())
+            .header(WEBHOOK_EVENT_HEADER, &delivery.event_type)
+            .header(WEBHOOK_TIMESTAMP_HEADER, timestamp.to_string())
+            .header(
+                WEBHOOK_SIGNATURE_HEADER,
+                webhook_signature(secret, timestamp, &body),
+            )
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(response.status().as_u16())
+    }
+}
@@ -0,0 +1,288 @@
+//! WebSocket Manager
+//! Open WebSocket connections of signed-in users and the topics they follow.
+//! Services push events to users or topics; each connection gets its copy,
+//! on this instance and, through the cluster bus, on the others.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc, Mutex};
+use tracing::{debug, error, warn};
+use uuid::Uuid;
+
+use crate::infrastructure::messaging::ClusterBus;
+
+/// Cluster bus channel of WebSocket deliveries
+const CLUSTER_CHANNEL: &str = "ws";
+
+/// Most topics a single connection may follow
+pub const MAX_TOPICS_PER_CONNECTION: usize = 100;
+
+/// Something a client can follow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Topic {
+    Asset(Uuid),
+    WorkOrder(Uuid),
+    Rental(Uuid),
+    /// Sensor alerts on any asset
+    Alerts,
+}
+
+impl Topic {
+    /// `asset:<id>`, `work_order:<id>`, `rental:<id>` or `alerts`
+    pub fn parse(s: &str) -> Option<Self> {
+        if s == "alerts" {
+            return Some(Topic::Alerts);
+        }
+        let (kind, id) = s.split_once(':')?;
+        let id = Uuid::parse_str(id).ok()?;
+        match kind {
+            "asset" => Some(Topic::Asset(id)),
+            "work_order" => Some(Topic::WorkOrder(id)),
+            "rental" => Some(Topic::Rental(id)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Topic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Topic::Asset(id) => write!(f, "asset:{}", id),
+            Topic::WorkOrder(id) => write!(f, "work_order:{}", id),
+            Topic::Rental(id) => write!(f, "rental:{}", id),
+            Topic::Alerts => write!(f, "alerts"),
+        }
+    }
+}
+
+/// A message sent to a specific user, the followers of a topic or everyone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationMessage {
+    pub event_type: String, // e.g., "WORK_ORDER_UPDATED"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>, // set on topic events
+    pub payload: serde_json::Value,
+}
+
+impl NotificationMessage {
+    pub fn new(event_type: &str, payload: serde_json::Value) -> Self {
+        Self {
+            event_type: event_type.to_string(),
+            topic: None,
+            payload,
+        }
+    }
+}
+
+/// Who a message is delivered to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Audience {
+    All,
+    Users { user_ids: Vec<Uuid> },
+    Topic { topic: String },
+}
+
+/// A message and its audience, as sent to the other instances
+#[derive(Debug, Serialize, Deserialize)]
+struct Delivery {
+    audience: Audience,
+    message: NotificationMessage,
+}
+
+struct Connection {
+    user_id: Uuid,
+    topics: HashSet<String>,
+    sender: mpsc::UnboundedSender<String>,
+}
+
+/// The global manager for WebSocket connections
+pub struct WebSocketManager {
+    connections: Mutex<HashMap<Uuid, Connection>>,
+    cluster: ClusterBus,
+}
+
+impl Default for WebSocketManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WebSocketManager {
+    /// Manager of this instance's connections only
+    pub fn new() -> Self {
+        Self {
+            connections: Mutex::new(HashMap::new()),
+            cluster: ClusterBus::local(),
+        }
+    }
+
+    /// Manager that also reaches the connections held by other instances on
+    /// the cluster bus
+    pub fn with_cluster(cluster: ClusterBus) -> Arc<Self> {
+        let manager = Arc::new(Self {
+            connections: Mutex::new(HashMap::new()),
+            cluster: cluster.clone(),
+        });
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return manager;
+        };
+
+        let weak = Arc::downgrade(&manager);
+        let mut remote = cluster.subscribe();
+        runtime.spawn(async move {
+            loop {
+                let message = match remote.recv().await {
+                    Ok(message) => message,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Dropped {} WebSocket deliveries from the cluster", skipped);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                if message.channel != CLUSTER_CHANNEL {
+                    continue;
+                }
+                let Some(manager) = weak.upgrade() else {
+                    break;
+                };
+                match serde_json::from_str::<Delivery>(&message.payload) {
+                    Ok(delivery) => {
+                        manager.deliver(&delivery.audience, &delivery.message).await;
+                    }
+                    Err(e) => warn!("Malformed WebSocket delivery from the cluster: {}", e),
+                }
+            }
+        });
+        manager
+    }
+
+    /// Register a connection of a user; messages for it are sent to `sender`
+    /// as JSON text. A user may hold any number of connections.
+    pub async fn register(&self, user_id: Uuid, sender: mpsc::UnboundedSender<String>) -> Uuid {
+        let connection_id = Uuid::new_v4();
+        self.connections.lock().await.insert(
+            connection_id,
+            Connection {
+                user_id,
+                topics: HashSet::new(),
+                sender,
+            },
+        );
+        connection_id
+    }
+
+    pub async fn unregister(&self, connection_id: Uuid) {
+        self.connections.lock().await.remove(&connection_id);
+    }
+
+    /// Follow a topic on a connection; false once the connection follows
+    /// `MAX_TOPICS_PER_CONNECTION` topics
+    pub async fn subscribe(&self, connection_id: Uuid, topic: &Topic) -> bool {
+        let mut connections = self.connections.lock().await;
+        let Some(connection) = connections.get_mut(&connection_id) else {
+            return false;
+        };
+        let topic = topic.to_string();
+        if !connection.topics.contains(&topic)
+            && connection.topics.len() >= MAX_TOPICS_PER_CONNECTION
+        {
+            return false;
+        }
+        connection.topics.insert(topic);
+        true
+    }
+
+    pub async fn unsubscribe(&self, connection_id: Uuid, topic: &Topic) {
+        if let Some(connection) = self.connections.lock().await.get_mut(&connection_id) {
+            connection.topics.remove(&topic.to_string());
+        }
+    }
+
+    /// Open connections of a user
+    pub async fn user_connection_count(&self, user_id: Uuid) -> usize {
+        self.connections
+            .lock()
+            .await
+            .values()
+            .filter(|c| c.user_id == user_id)
+            .count()
+    }
+
+    /// Broadcast a message to ALL connected clients
+    pub async fn broadcast(&self, msg: &NotificationMessage) {
+        self.dispatch(Audience::All, msg).await;
+    }
+
+    /// Send to every connection of a user
+    pub async fn send_to_user(&self, user_id: Uuid, msg: &NotificationMessage) {
+        self.send_to_users(&[user_id], msg).await;
+    }
+
+    pub async fn send_to_users(&self, user_ids: &[Uuid], msg: &NotificationMessage) {
+        if user_ids.is_empty() {
+            return;
+        }
+        let audience = Audience::Users {
+            user_ids: user_ids.to_vec(),
+        };
+        self.dispatch(audience, msg).await;
+    }
+
+    /// Send to the connections following a topic; the message carries the topic
+    pub async fn publish(&self, topic: &Topic, msg: &NotificationMessage) {
+        let topic = topic.to_string();
+        let msg = NotificationMessage {
+            topic: Some(topic.clone()),
+            ..msg.clone()
+        };
+        self.dispatch(Audience::Topic { topic }, &msg).await;
+    }
+
+    /// Deliver here and hand the message to the other instances
+    async fn dispatch(&self, audience: Audience, msg: &NotificationMessage) {
+        self.deliver(&audience, msg).await;
+
+        let delivery = Delivery {
+            audience,
+            message: msg.clone(),
+        };
+        match serde_json::to_string(&delivery) {
+            Ok(json) => self.cluster.publish(CLUSTER_CHANNEL, &json).await,
+            Err(e) => error!("Failed to serialize WebSocket delivery: {}", e),
+        }
+    }
+
+    /// Hand a message to the local connections of its audience; returns how
+    /// many connections it was queued on
+    pub async fn deliver(&self, audience: &Audience, msg: &NotificationMessage) -> usize {
+        let json = match serde_json::to_string(msg) {
+            Ok(j) => j,
+            Err(e) => {
+                error!("Failed to serialize WebSocket message: {}", e);
+                return 0;
+            }
+        };
+
+        let connections = self.connections.lock().await;
+        let mut delivered = 0;
+        for connection in connections.values() {
+            let addressed = match audience {
+                Audience::All => true,
+                Audience::Users { user_ids } => user_ids.contains(&connection.user_id),
+                Audience::Topic { topic } => connection.topics.contains(topic),
+            };
+            // A closed channel belongs to a socket that is being cleaned up
+            if addressed && connection.sender.send(json.clone()).is_ok() {
+                delivered += 1;
+            }
+        }
+        debug!(
+            "Delivered event '{}' to {} connections",
+            msg.event_type, delivered
+        );
+        delivered
+    }
+}
@@ -1,10 +1,11 @@
 //! Infrastructure Layer
 //!
-//! External system integrations: database, cache, storage, messaging.
+//! External system integrations: database, cache, storage, messaging, notifications.
 
 pub mod cache;
 pub mod database;
 pub mod messaging;
+pub mod notifications;
 pub mod repositories;
 pub mod storage;
 
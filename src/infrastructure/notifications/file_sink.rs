@@ -0,0 +1,39 @@
+//! File sink channel: appends each message as a JSON line (development and tests)
+
+use std::path::PathBuf;
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+use super::{ChannelError, NotificationChannel, OutboundMessage};
+
+pub struct FileSinkChannel {
+    path: PathBuf,
+}
+
+impl FileSinkChannel {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+#[async_trait::async_trait]
+impl NotificationChannel for FileSinkChannel {
+    async fn send(&self, message: &OutboundMessage) -> Result<Option<Uuid>, ChannelError> {
+        let mut line =
+            serde_json::to_string(message).map_err(|e| ChannelError::Transport(e.to_string()))?;
+        line.push('\n');
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| ChannelError::Transport(format!("{}: {}", self.path.display(), e)))?;
+
+        file.write_all(line.as_bytes())
+            .await
+            .map_err(|e| ChannelError::Transport(e.to_string()))?;
+
+        Ok(None)
+    }
+}
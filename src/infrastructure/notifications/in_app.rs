@@ -0,0 +1,46 @@
+//! In-app channel: writes a row to `notifications`
+
+use uuid::Uuid;
+
+use super::{ChannelError, NotificationChannel, OutboundMessage};
+use crate::infrastructure::repositories::{Notification, NotificationRepository};
+
+pub struct InAppChannel {
+    repository: NotificationRepository,
+}
+
+impl InAppChannel {
+    pub fn new(repository: NotificationRepository) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait::async_trait]
+impl NotificationChannel for InAppChannel {
+    async fn send(&self, message: &OutboundMessage) -> Result<Option<Uuid>, ChannelError> {
+        let notification = Notification {
+            id: Uuid::new_v4(),
+            user_id: message.user_id,
+            template_id: message.template_id,
+            title: message.subject.clone(),
+            message: message.body.clone(),
+            data: message.data.clone(),
+            channel: message.channel.clone(),
+            entity_type: message.entity_type.clone(),
+            entity_id: message.entity_id,
+            is_read: false,
+            read_at: None,
+            is_sent: false,
+            sent_at: None,
+            created_at: chrono::Utc::now(),
+        };
+
+        let created = self
+            .repository
+            .create(&notification)
+            .await
+            .map_err(|e| ChannelError::Transport(e.to_string()))?;
+
+        Ok(Some(created.id))
+    }
+}
@@ -0,0 +1,156 @@
+//! Notification Channels
+//!
+//! Delivery adapters used by the notification dispatcher. Each adapter sends one
+//! rendered message over one channel; retries and logging live in the service.
+
+pub mod file_sink;
+pub mod in_app;
+pub mod smtp;
+pub mod webhook;
+
+pub use file_sink::FileSinkChannel;
+pub use in_app::InAppChannel;
+pub use smtp::{SmtpChannel, SmtpConfig};
+pub use webhook::WebhookChannel;
+
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::entities::{
+    CHANNEL_EMAIL, CHANNEL_IN_APP, CHANNEL_PUSH, CHANNEL_SMS, CHANNEL_WEBHOOK,
+};
+use crate::infrastructure::repositories::NotificationRepository;
+
+/// A rendered message addressed to one user
+#[derive(Debug, Clone, Serialize)]
+pub struct OutboundMessage {
+    pub delivery_id: Uuid,
+    pub user_id: Uuid,
+    pub template_id: Option<Uuid>,
+    pub event_type: String,
+    pub channel: String,
+    pub recipient: Option<String>,
+    pub subject: String,
+    pub body: String,
+    pub data: Option<JsonValue>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<Uuid>,
+}
+
+/// Channel error types
+#[derive(Debug)]
+pub enum ChannelError {
+    /// The message cannot be addressed (e.g. user has no email); retrying will not help
+    InvalidRecipient(String),
+    /// The channel is misconfigured; retrying will not help
+    Configuration(String),
+    /// Transient transport failure
+    Transport(String),
+}
+
+impl ChannelError {
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::Transport(_))
+    }
+}
+
+impl std::fmt::Display for ChannelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidRecipient(msg) => write!(f, "Invalid recipient: {}", msg),
+            Self::Configuration(msg) => write!(f, "Channel configuration error: {}", msg),
+            Self::Transport(msg) => write!(f, "Delivery failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ChannelError {}
+
+/// Delivery adapter for one channel
+#[async_trait::async_trait]
+pub trait NotificationChannel: Send + Sync {
+    /// Send the message. Returns the in-app notification ID when one was written.
+    async fn send(&self, message: &OutboundMessage) -> Result<Option<Uuid>, ChannelError>;
+}
+
+/// Notification channel configuration
+#[derive(Debug, Clone, Default)]
+pub struct NotificationConfig {
+    pub smtp: Option<SmtpConfig>,
+    pub webhook_url: Option<String>,
+    pub webhook_token: Option<String>,
+    /// JSON-lines file capturing every channel without a real adapter (dev/test)
+    pub file_sink: Option<PathBuf>,
+}
+
+impl NotificationConfig {
+    pub fn from_env() -> Self {
+        let non_empty = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+
+        Self {
+            smtp: SmtpConfig::from_env(),
+            webhook_url: non_empty("NOTIFICATION_WEBHOOK_URL"),
+            webhook_token: non_empty("NOTIFICATION_WEBHOOK_TOKEN"),
+            file_sink: non_empty("NOTIFICATION_FILE_SINK").map(PathBuf::from),
+        }
+    }
+}
+
+/// Channel adapters keyed by channel name
+#[derive(Clone, Default)]
+pub struct NotificationChannels {
+    adapters: HashMap<String, Arc<dyn NotificationChannel>>,
+}
+
+impl NotificationChannels {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the adapters available under the given configuration.
+    ///
+    /// In-app is always available. Email and webhook use their real transports when
+    /// configured; any channel left without one falls back to the file sink if set.
+    pub fn from_config(config: &NotificationConfig, repository: NotificationRepository) -> Self {
+        let mut channels = Self::new().register(CHANNEL_IN_APP, InAppChannel::new(repository));
+
+        if let Some(smtp) = &config.smtp {
+            match SmtpChannel::new(smtp) {
+                Ok(adapter) => channels = channels.register(CHANNEL_EMAIL, adapter),
+                Err(e) => tracing::error!("SMTP channel disabled: {}", e),
+            }
+        }
+        if let Some(url) = &config.webhook_url {
+            channels = channels.register(
+                CHANNEL_WEBHOOK,
+                WebhookChannel::new(url.clone(), config.webhook_token.clone()),
+            );
+        }
+        if let Some(path) = &config.file_sink {
+            for channel in [CHANNEL_EMAIL, CHANNEL_PUSH, CHANNEL_SMS, CHANNEL_WEBHOOK] {
+                if !channels.supports(channel) {
+                    channels = channels.register(channel, FileSinkChannel::new(path.clone()));
+                }
+            }
+        }
+
+        channels
+    }
+
+    pub fn register(mut self, channel: &str, adapter: impl NotificationChannel + 'static) -> Self {
+        self.adapters.insert(channel.to_string(), Arc::new(adapter));
+        self
+    }
+
+    pub fn get(&self, channel: &str) -> Option<Arc<dyn NotificationChannel>> {
+        self.adapters.get(channel).cloned()
+    }
+
+    pub fn supports(&self, channel: &str) -> bool {
+        self.adapters.contains_key(channel)
+    }
+}
@@ -0,0 +1,103 @@
+//! SMTP email channel
+
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use uuid::Uuid;
+
+use super::{ChannelError, NotificationChannel, OutboundMessage};
+
+/// SMTP configuration
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    /// starttls (default), tls, or none
+    pub security: String,
+}
+
+impl SmtpConfig {
+    /// Read SMTP_* variables; None when SMTP_HOST is not set
+    pub fn from_env() -> Option<Self> {
+        let host = std::env::var("SMTP_HOST")
+            .ok()
+            .filter(|h| !h.trim().is_empty())?;
+
+        Some(Self {
+            host,
+            port: std::env::var("SMTP_PORT")
+                .ok()
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(587),
+            username: std::env::var("SMTP_USERNAME").ok(),
+            password: std::env::var("SMTP_PASSWORD").ok(),
+            from: std::env::var("SMTP_FROM")
+                .unwrap_or_else(|_| "Asset Management <no-reply@localhost>".to_string()),
+            security: std::env::var("SMTP_SECURITY").unwrap_or_else(|_| "starttls".to_string()),
+        })
+    }
+}
+
+pub struct SmtpChannel {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: lettre::message::Mailbox,
+}
+
+impl SmtpChannel {
+    pub fn new(config: &SmtpConfig) -> Result<Self, ChannelError> {
+        let builder = match config.security.as_str() {
+            "none" => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host),
+            "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)
+                .map_err(|e| ChannelError::Configuration(e.to_string()))?,
+            _ => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+                .map_err(|e| ChannelError::Configuration(e.to_string()))?,
+        };
+
+        let builder = match (&config.username, &config.password) {
+            (Some(user), Some(pass)) => {
+                builder.credentials(Credentials::new(user.clone(), pass.clone()))
+            }
+            _ => builder,
+        };
+
+        let from = config
+            .from
+            .parse()
+            .map_err(|e| ChannelError::Configuration(format!("SMTP_FROM: {}", e)))?;
+
+        Ok(Self {
+            transport: builder.port(config.port).build(),
+            from,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl NotificationChannel for SmtpChannel {
+    async fn send(&self, message: &OutboundMessage) -> Result<Option<Uuid>, ChannelError> {
+        let to = message
+            .recipient
+            .as_deref()
+            .ok_or_else(|| ChannelError::InvalidRecipient("user has no email".to_string()))?
+            .parse()
+            .map_err(|e| ChannelError::InvalidRecipient(format!("{}", e)))?;
+
+        let email = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(message.subject.clone())
+            .header(ContentType::TEXT_PLAIN)
+            .body(message.body.clone())
+            .map_err(|e| ChannelError::InvalidRecipient(e.to_string()))?;
+
+        self.transport
+            .send(email)
+            .await
+            .map_err(|e| ChannelError::Transport(e.to_string()))?;
+
+        Ok(None)
+    }
+}
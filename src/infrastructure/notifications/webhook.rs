@@ -0,0 +1,53 @@
+//! Webhook channel: POSTs the rendered message as JSON
+
+use std::time::Duration;
+use uuid::Uuid;
+
+use super::{ChannelError, NotificationChannel, OutboundMessage};
+
+pub struct WebhookChannel {
+    client: reqwest::Client,
+    url: String,
+    token: Option<String>,
+}
+
+impl WebhookChannel {
+    pub fn new(url: String, token: Option<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+
+        Self { client, url, token }
+    }
+}
+
+#[async_trait::async_trait]
+impl NotificationChannel for WebhookChannel {
+    async fn send(&self, message: &OutboundMessage) -> Result<Option<Uuid>, ChannelError> {
+        let mut request = self.client.post(&self.url).json(message);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ChannelError::Transport(e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(None)
+        } else if status.is_client_error() && status != reqwest::StatusCode::TOO_MANY_REQUESTS {
+            Err(ChannelError::Configuration(format!(
+                "webhook rejected the message with {}",
+                status
+            )))
+        } else {
+            Err(ChannelError::Transport(format!(
+                "webhook responded with {}",
+                status
+            )))
+        }
+    }
+}
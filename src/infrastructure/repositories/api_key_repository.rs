@@ -0,0 +1,308 @@
+//! API Key Repository
+//!
+//! Service accounts, their API keys and the audit entries of key usage.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::{
+    ApiKey, ServiceAccount, AUDIT_API_KEY_CREATED, AUDIT_API_KEY_REQUEST, AUDIT_API_KEY_REVOKED,
+    AUDIT_API_KEY_ROTATED,
+};
+
+const SERVICE_ACCOUNT_SELECT: &str = r#"
+    SELECT u.id, u.name, u.email, u.role_id, COALESCE(r.code, u.role) as role,
+           u.organization_id, u.is_active,
+           (SELECT COUNT(*) FROM api_keys k
+            WHERE k.user_id = u.id AND k.revoked_at IS NULL AND k.expires_at > NOW()) as active_keys,
+           u.created_at
+    FROM users u
+    LEFT JOIN roles r ON u.role_id = r.id
+    WHERE u.is_service_account
+"#;
+
+/// A request made with an API key, for the audit log
+pub struct ApiKeyRequestLog<'a> {
+    pub method: &'a str,
+    pub path: &'a str,
+    pub status: u16,
+    pub ip_address: Option<&'a str>,
+    pub user_agent: Option<&'a str>,
+}
+
+#[derive(Clone)]
+pub struct ApiKeyRepository {
+    pool: PgPool,
+}
+
+impl ApiKeyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // ==================== SERVICE ACCOUNTS ====================
+
+    pub async fn list_service_accounts(&self) -> Result<Vec<ServiceAccount>, sqlx::Error> {
+        sqlx::query_as::<_, ServiceAccount>(&format!("{} ORDER BY u.name", SERVICE_ACCOUNT_SELECT))
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    pub async fn find_service_account(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<ServiceAccount>, sqlx::Error> {
+        sqlx::query_as::<_, ServiceAccount>(&format!("{} AND u.id = $1", SERVICE_ACCOUNT_SELECT))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Create a service account with the given role; `None` when the role does not exist.
+    /// It gets no usable password.
+    pub async fn create_service_account(
+        &self,
+        name: &str,
+        role_id: Uuid,
+        organization_id: Option<Uuid>,
+        created_by: Uuid,
+    ) -> Result<Option<ServiceAccount>, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let mut tx = self.pool.begin().await?;
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO users (id, email, password_hash, name, role, role_id, organization_id, is_service_account)
+            SELECT $1, $2, '!', $3, r.code, r.id, $5, true
+            FROM roles r WHERE r.id = $4
+            "#,
+        )
+        .bind(id)
+        .bind(format!("svc-{}@service-accounts.local", id.simple()))
+        .bind(name)
+        .bind(role_id)
+        .bind(organization_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if inserted == 0 {
+            return Ok(None);
+        }
+
+        if let Some(organization_id) = organization_id {
+            sqlx::query(
+                "INSERT INTO user_organizations (user_id, organization_id, created_by) VALUES ($1, $2, $3)",
+            )
+            .bind(id)
+            .bind(organization_id)
+            .bind(created_by)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        self.find_service_account(id).await
+    }
+
+    // ==================== KEYS ====================
+
+    pub async fn find(&self, id: Uuid) -> Result<Option<ApiKey>, sqlx::Error> {
+        sqlx::query_as::<_, ApiKey>("SELECT * FROM api_keys WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    pub async fn find_by_prefix(&self, prefix: &str) -> Result<Option<ApiKey>, sqlx::Error> {
+        sqlx::query_as::<_, ApiKey>("SELECT * FROM api_keys WHERE prefix = $1")
+            .bind(prefix)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    pub async fn list_for_user(&self, user_id: Uuid) -> Result<Vec<ApiKey>, sqlx::Error> {
+        sqlx::query_as::<_, ApiKey>(
+            "SELECT * FROM api_keys WHERE user_id = $1 ORDER BY created_at DESC",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn create(&self, key: &ApiKey) -> Result<ApiKey, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let created = sqlx::query_as::<_, ApiKey>(
+            r#"
+            INSERT INTO api_keys (id, user_id, name, prefix, key_hash, scopes, expires_at, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            "#,
+        )
+        .bind(key.id)
+        .bind(key.user_id)
+        .bind(&key.name)
+        .bind(&key.prefix)
+        .bind(&key.key_hash)
+        .bind(&key.scopes)
+        .bind(key.expires_at)
+        .bind(key.created_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Self::audit(
+            &mut tx,
+            &created,
+            AUDIT_API_KEY_CREATED,
+            key.created_by,
+            serde_json::json!({
+                "name": created.name,
+                "prefix": created.prefix,
+                "owner": created.user_id,
+                "scopes": created.scopes,
+                "expires_at": created.expires_at,
+            }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(created)
+    }
+
+    /// Replace the secret of a live key; `None` when revoked or unknown
+    pub async fn rotate(
+        &self,
+        id: Uuid,
+        prefix: &str,
+        key_hash: &str,
+        rotated_by: Uuid,
+    ) -> Result<Option<ApiKey>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let rotated = sqlx::query_as::<_, ApiKey>(
+            r#"
+            UPDATE api_keys SET prefix = $2, key_hash = $3
+            WHERE id = $1 AND revoked_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(prefix)
+        .bind(key_hash)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(rotated) = rotated else {
+            return Ok(None);
+        };
+
+        Self::audit(
+            &mut tx,
+            &rotated,
+            AUDIT_API_KEY_ROTATED,
+            Some(rotated_by),
+            serde_json::json!({ "prefix": rotated.prefix }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(rotated))
+    }
+
+    /// Revoke a key; `None` when already revoked or unknown
+    pub async fn revoke(&self, id: Uuid, revoked_by: Uuid) -> Result<Option<ApiKey>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let revoked = sqlx::query_as::<_, ApiKey>(
+            r#"
+            UPDATE api_keys SET revoked_at = NOW()
+            WHERE id = $1 AND revoked_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(revoked) = revoked else {
+            return Ok(None);
+        };
+
+        Self::audit(
+            &mut tx,
+            &revoked,
+            AUDIT_API_KEY_REVOKED,
+            Some(revoked_by),
+            serde_json::json!({ "prefix": revoked.prefix }),
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(revoked))
+    }
+
+    async fn audit(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        key: &ApiKey,
+        action: &str,
+        user_id: Option<Uuid>,
+        values: serde_json::Value,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_logs (table_name, record_id, action, new_values, user_id, api_key_id)
+            VALUES ('api_keys', $1, $2, $3, $4, $1)
+            "#,
+        )
+        .bind(key.id)
+        .bind(action)
+        .bind(values)
+        .bind(user_id)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Record a use of the key; written at most once a minute per key
+    pub async fn touch(&self, id: Uuid, ip_address: Option<&str>) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE api_keys SET last_used_at = NOW(), last_used_ip = $2
+            WHERE id = $1
+              AND (last_used_at IS NULL OR last_used_at < NOW() - INTERVAL '1 minute'
+                   OR last_used_ip IS DISTINCT FROM $2)
+            "#,
+        )
+        .bind(id)
+        .bind(ip_address)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Attribute a state-changing request to the key that made it
+    pub async fn record_request(
+        &self,
+        key_id: Uuid,
+        user_id: Uuid,
+        request: &ApiKeyRequestLog<'_>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_logs (table_name, record_id, action, new_values, user_id, api_key_id, ip_address, user_agent)
+            VALUES ('api_keys', $1, $2, $3, $4, $1, $5, $6)
+            "#,
+        )
+        .bind(key_id)
+        .bind(AUDIT_API_KEY_REQUEST)
+        .bind(serde_json::json!({
+            "method": request.method,
+            "path": request.path,
+            "status": request.status,
+        }))
+        .bind(user_id)
+        .bind(request.ip_address)
+        .bind(request.user_agent)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -2,9 +2,11 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use sqlx::FromRow;
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
+use crate::domain::entities::ApprovalAction;
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct ApprovalRequest {
     pub id: Uuid,
@@ -21,6 +23,11 @@ pub struct ApprovalRequest {
     pub approved_by_l2: Option<Uuid>,
     pub approved_at_l2: Option<DateTime<Utc>>,
     pub notes_l2: Option<String>,
+    pub workflow_id: Option<Uuid>,
+    pub total_levels: Option<i32>,
+    pub executed_at: Option<DateTime<Utc>>,
+    pub execution_result: Option<JsonValue>,
+    pub execution_error: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 
@@ -39,25 +46,45 @@ impl ApprovalRepository {
         Self { pool }
     }
 
+    /// Start a transaction for deciding a request and applying its change
+    pub async fn begin(&self) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        self.pool.begin().await
+    }
+
     pub async fn create(
         &self,
         req: &scan_approval_request::CreateApprovalRequest,
     ) -> Result<ApprovalRequest, sqlx::Error> {
+        Self::create_with(&self.pool, req).await
+    }
+
+    /// Insert a request on a given connection or transaction
+    pub(crate) async fn create_with<'e, E>(
+        executor: E,
+        req: &scan_approval_request::CreateApprovalRequest,
+    ) -> Result<ApprovalRequest, sqlx::Error>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
         sqlx::query_as::<_, ApprovalRequest>(
             r#"
             INSERT INTO approval_requests (
-                resource_type, resource_id, action_type, requested_by, data_snapshot, status, current_approval_level
+                resource_type, resource_id, action_type, requested_by, data_snapshot, status,
+                current_approval_level, workflow_id, total_levels
             )
-            VALUES ($1, $2, $3, $4, $5, 'PENDING', 1)
+            VALUES ($1, $2, $3, $4, $5, 'PENDING', $6, $7, $8)
             RETURNING *, NULL as requester_name
-            "#
+            "#,
         )
         .bind(&req.resource_type)
         .bind(req.resource_id)
         .bind(&req.action_type)
         .bind(req.requested_by)
         .bind(&req.data_snapshot)
-        .fetch_one(&self.pool)
+        .bind(req.current_approval_level)
+        .bind(req.workflow_id)
+        .bind(req.total_levels)
+        .fetch_one(executor)
         .await
     }
 
@@ -75,22 +102,63 @@ impl ApprovalRepository {
         .await
     }
 
-    pub async fn list_pending(&self, level: i32) -> Result<Vec<ApprovalRequest>, sqlx::Error> {
+    /// All pending requests (super admin view)
+    pub async fn list_pending_all(&self) -> Result<Vec<ApprovalRequest>, sqlx::Error> {
         sqlx::query_as::<_, ApprovalRequest>(
             r#"
             SELECT ar.*, u.name as requester_name
             FROM approval_requests ar
             LEFT JOIN users u ON ar.requested_by = u.id
-            WHERE ar.status = 'PENDING' 
-            AND ar.current_approval_level = $1
+            WHERE ar.status = 'PENDING'
             ORDER BY ar.created_at ASC
             "#,
         )
-        .bind(level)
         .fetch_all(&self.pool)
         .await
     }
 
+    /// Pending requests whose current level still waits on one of the given roles
+    pub async fn list_pending_for_roles(
+        &self,
+        role_ids: &[Uuid],
+    ) -> Result<Vec<ApprovalRequest>, sqlx::Error> {
+        sqlx::query_as::<_, ApprovalRequest>(
+            r#"
+            SELECT ar.*, u.name as requester_name
+            FROM approval_requests ar
+            LEFT JOIN users u ON ar.requested_by = u.id
+            WHERE ar.status = 'PENDING'
+            AND EXISTS (
+                SELECT 1 FROM approval_workflow_levels l
+                WHERE l.workflow_id = ar.workflow_id
+                AND l.level_number = ar.current_approval_level
+                AND l.role_id = ANY($1)
+                AND NOT EXISTS (
+                    SELECT 1 FROM approval_request_actions x
+                    WHERE x.request_id = ar.id
+                    AND x.level_number = l.level_number
+                    AND x.role_id = l.role_id
+                )
+            )
+            ORDER BY ar.created_at ASC
+            "#,
+        )
+        .bind(role_ids)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// IDs of resources of a type that have an open request
+    pub async fn open_resource_ids(&self, resource_type: &str) -> Result<Vec<Uuid>, sqlx::Error> {
+        let rows: Vec<(Uuid,)> = sqlx::query_as(
+            "SELECT resource_id FROM approval_requests WHERE LOWER(resource_type) = LOWER($1) AND status = 'PENDING'",
+        )
+        .bind(resource_type)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+
     // List all requests for a user
     pub async fn list_by_requester(
         &self,
@@ -110,54 +178,233 @@ impl ApprovalRepository {
         .await
     }
 
-    pub async fn update_status(
-        &self,
-        id: Uuid,
-        status: &str,
+    pub async fn list_actions(&self, request_id: Uuid) -> Result<Vec<ApprovalAction>, sqlx::Error> {
+        sqlx::query_as::<_, ApprovalAction>(
+            r#"
+            SELECT x.*, u.name as approver_name, r.code as role_code
+            FROM approval_request_actions x
+            LEFT JOIN users u ON x.approver_id = u.id
+            LEFT JOIN roles r ON x.role_id = r.id
+            WHERE x.request_id = $1
+            ORDER BY x.level_number, x.acted_at
+            "#,
+        )
+        .bind(request_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Record an approval for one role slot and advance the request.
+    ///
+    /// Holds a row lock until `tx` ends, so parallel approvers at the same level
+    /// cannot both miss the level completing. Returns `None` when the request is
+    /// no longer pending at `level` (someone else moved it on).
+    pub async fn record_approval(
+        tx: &mut Transaction<'_, Postgres>,
+        request_id: Uuid,
+        workflow_id: Uuid,
         level: i32,
-        approver_id: Option<Uuid>,
+        role_id: Uuid,
+        approver_id: Uuid,
         notes: Option<String>,
-    ) -> Result<ApprovalRequest, sqlx::Error> {
-        // Dynamic update based on level
-        let query = if level == 1 {
+    ) -> Result<Option<ApprovalRequest>, sqlx::Error> {
+        let locked: Option<(String, i32)> = sqlx::query_as(
+            "SELECT status, current_approval_level FROM approval_requests WHERE id = $1 FOR UPDATE",
+        )
+        .bind(request_id)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        match locked {
+            Some((status, current)) if status == "PENDING" && current == level => {}
+            _ => return Ok(None),
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO approval_request_actions (request_id, level_number, role_id, approver_id, action, notes)
+            VALUES ($1, $2, $3, $4, 'approved', $5)
+            "#,
+        )
+        .bind(request_id)
+        .bind(level)
+        .bind(role_id)
+        .bind(approver_id)
+        .bind(&notes)
+        .execute(&mut **tx)
+        .await?;
+
+        let (remaining,): (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM approval_workflow_levels l
+            WHERE l.workflow_id = $1 AND l.level_number = $2
+            AND NOT EXISTS (
+                SELECT 1 FROM approval_request_actions x
+                WHERE x.request_id = $3 AND x.level_number = l.level_number
+                AND x.role_id = l.role_id AND x.action = 'approved'
+            )
+            "#,
+        )
+        .bind(workflow_id)
+        .bind(level)
+        .bind(request_id)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        let (status, next_level) = if remaining > 0 {
+            ("PENDING", level)
+        } else {
+            let (next,): (Option<i32>,) = sqlx::query_as(
+                "SELECT MIN(level_number) FROM approval_workflow_levels WHERE workflow_id = $1 AND level_number > $2",
+            )
+            .bind(workflow_id)
+            .bind(level)
+            .fetch_one(&mut **tx)
+            .await?;
+            match next {
+                Some(n) => ("PENDING", n),
+                None => ("APPROVED", level),
+            }
+        };
+
+        // approved_by_l1/l2 are kept filled for the first two levels
+        let updated = sqlx::query_as::<_, ApprovalRequest>(
             r#"
-            UPDATE approval_requests 
-            SET status = $2, 
-                approved_by_l1 = $3, 
-                approved_at_l1 = NOW(), 
-                notes_l1 = $4,
+            UPDATE approval_requests
+            SET status = $2,
+                current_approval_level = $3,
+                workflow_id = $4,
+                approved_by_l1 = CASE WHEN $5 = 1 THEN $6 ELSE approved_by_l1 END,
+                approved_at_l1 = CASE WHEN $5 = 1 THEN NOW() ELSE approved_at_l1 END,
+                notes_l1 = CASE WHEN $5 = 1 THEN $7 ELSE notes_l1 END,
+                approved_by_l2 = CASE WHEN $5 = 2 THEN $6 ELSE approved_by_l2 END,
+                approved_at_l2 = CASE WHEN $5 = 2 THEN NOW() ELSE approved_at_l2 END,
+                notes_l2 = CASE WHEN $5 = 2 THEN $7 ELSE notes_l2 END,
                 updated_at = NOW()
             WHERE id = $1
-            RETURNING *, NULL as requester_name -- Simplified return
-            "#
-        } else {
+            RETURNING *, NULL as requester_name
+            "#,
+        )
+        .bind(request_id)
+        .bind(status)
+        .bind(next_level)
+        .bind(workflow_id)
+        .bind(level)
+        .bind(approver_id)
+        .bind(&notes)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(Some(updated))
+    }
+
+    /// Record a rejection; a single rejection at any level rejects the request
+    pub async fn record_rejection(
+        &self,
+        request_id: Uuid,
+        level: i32,
+        role_id: Uuid,
+        approver_id: Uuid,
+        notes: String,
+    ) -> Result<Option<ApprovalRequest>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let locked: Option<(String,)> =
+            sqlx::query_as("SELECT status FROM approval_requests WHERE id = $1 FOR UPDATE")
+                .bind(request_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+        match locked {
+            Some((status,)) if status == "PENDING" => {}
+            _ => return Ok(None),
+        }
+
+        sqlx::query(
             r#"
-            UPDATE approval_requests 
-            SET status = $2, 
-                approved_by_l2 = $3, 
-                approved_at_l2 = NOW(), 
-                notes_l2 = $4,
+            INSERT INTO approval_request_actions (request_id, level_number, role_id, approver_id, action, notes)
+            VALUES ($1, $2, $3, $4, 'rejected', $5)
+            ON CONFLICT (request_id, level_number, role_id) DO NOTHING
+            "#,
+        )
+        .bind(request_id)
+        .bind(level)
+        .bind(role_id)
+        .bind(approver_id)
+        .bind(&notes)
+        .execute(&mut *tx)
+        .await?;
+
+        let updated = sqlx::query_as::<_, ApprovalRequest>(
+            r#"
+            UPDATE approval_requests
+            SET status = 'REJECTED',
+                notes_l1 = CASE WHEN $2 = 1 THEN $3 ELSE notes_l1 END,
+                notes_l2 = CASE WHEN $2 = 2 THEN $3 ELSE notes_l2 END,
                 updated_at = NOW()
             WHERE id = $1
             RETURNING *, NULL as requester_name
-            "#
-        };
+            "#,
+        )
+        .bind(request_id)
+        .bind(level)
+        .bind(&notes)
+        .fetch_one(&mut *tx)
+        .await?;
 
-        sqlx::query_as::<_, ApprovalRequest>(query)
-            .bind(id)
-            .bind(status)
-            .bind(approver_id)
-            .bind(notes)
-            .fetch_one(&self.pool)
-            .await
+        tx.commit().await?;
+        Ok(Some(updated))
     }
 
-    pub async fn increment_level(&self, id: Uuid) -> Result<(), sqlx::Error> {
-        sqlx::query("UPDATE approval_requests SET current_approval_level = current_approval_level + 1 WHERE id = $1")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
-        Ok(())
+    /// Record that an approved request's change was applied.
+    ///
+    /// `resource_id` replaces the placeholder ID of requests that created their resource.
+    pub async fn record_execution(
+        tx: &mut Transaction<'_, Postgres>,
+        request_id: Uuid,
+        resource_id: Option<Uuid>,
+        result: Option<JsonValue>,
+    ) -> Result<Option<ApprovalRequest>, sqlx::Error> {
+        sqlx::query_as::<_, ApprovalRequest>(
+            r#"
+            UPDATE approval_requests
+            SET resource_id = COALESCE($2, resource_id),
+                execution_result = $3,
+                execution_error = NULL,
+                executed_at = NOW(),
+                updated_at = NOW()
+            WHERE id = $1 AND status = 'APPROVED'
+            RETURNING *, NULL as requester_name
+            "#,
+        )
+        .bind(request_id)
+        .bind(resource_id)
+        .bind(result)
+        .fetch_optional(&mut **tx)
+        .await
+    }
+
+    /// Take back the final approval of a request whose change could not be applied
+    pub async fn record_execution_failure(
+        tx: &mut Transaction<'_, Postgres>,
+        request_id: Uuid,
+        reason: &str,
+    ) -> Result<Option<ApprovalRequest>, sqlx::Error> {
+        sqlx::query_as::<_, ApprovalRequest>(
+            r#"
+            UPDATE approval_requests
+            SET status = 'FAILED',
+                execution_error = $2,
+                executed_at = NOW(),
+                updated_at = NOW()
+            WHERE id = $1 AND status = 'APPROVED'
+            RETURNING *, NULL as requester_name
+            "#,
+        )
+        .bind(request_id)
+        .bind(reason)
+        .fetch_optional(&mut **tx)
+        .await
     }
 }
 
@@ -171,5 +418,8 @@ pub mod scan_approval_request {
         pub action_type: String,
         pub requested_by: Uuid,
         pub data_snapshot: Option<JsonValue>,
+        pub workflow_id: Option<Uuid>,
+        pub current_approval_level: i32,
+        pub total_levels: i32,
     }
 }
@@ -0,0 +1,215 @@
+//! Approval Workflow Repository
+//!
+//! Persistence and resolution of configurable approval workflows.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::{ApprovalWorkflow, ApprovalWorkflowLevel};
+
+#[derive(Clone)]
+pub struct ApprovalWorkflowRepository {
+    pool: PgPool,
+}
+
+impl ApprovalWorkflowRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list(&self) -> Result<Vec<ApprovalWorkflow>, sqlx::Error> {
+        sqlx::query_as::<_, ApprovalWorkflow>(
+            r#"
+            SELECT * FROM approval_workflows
+            ORDER BY resource_type = '*', resource_type, action_type, asset_type NULLS LAST
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<ApprovalWorkflow>, sqlx::Error> {
+        sqlx::query_as::<_, ApprovalWorkflow>("SELECT * FROM approval_workflows WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    pub async fn find_levels(
+        &self,
+        workflow_id: Uuid,
+    ) -> Result<Vec<ApprovalWorkflowLevel>, sqlx::Error> {
+        sqlx::query_as::<_, ApprovalWorkflowLevel>(
+            r#"
+            SELECT l.*, r.code as role_code, r.name as role_name
+            FROM approval_workflow_levels l
+            JOIN roles r ON l.role_id = r.id
+            WHERE l.workflow_id = $1
+            ORDER BY l.level_number, r.code
+            "#,
+        )
+        .bind(workflow_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Find the most specific active workflow for a resource/action.
+    ///
+    /// Exact resource and action matches win over `*`, and a workflow qualified by the
+    /// asset's category code (or its parent's) wins over an unqualified one.
+    pub async fn resolve(
+        &self,
+        resource_type: &str,
+        action_type: &str,
+        category_id: Option<Uuid>,
+    ) -> Result<Option<ApprovalWorkflow>, sqlx::Error> {
+        sqlx::query_as::<_, ApprovalWorkflow>(
+            r#"
+            SELECT * FROM approval_workflows
+            WHERE COALESCE(is_active, true) = true
+              AND (LOWER(resource_type) = LOWER($1) OR resource_type = '*')
+              AND (LOWER(action_type) = LOWER($2) OR action_type = '*')
+              AND (
+                  asset_type IS NULL
+                  OR UPPER(asset_type) IN (
+                      SELECT UPPER(c.code) FROM categories c WHERE c.id = $3
+                      UNION
+                      SELECT UPPER(p.code) FROM categories c
+                      JOIN categories p ON c.parent_id = p.id
+                      WHERE c.id = $3
+                  )
+              )
+            ORDER BY resource_type = '*', action_type = '*', asset_type IS NULL
+            LIMIT 1
+            "#,
+        )
+        .bind(resource_type)
+        .bind(action_type)
+        .bind(category_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Insert a workflow together with its levels
+    pub async fn create(
+        &self,
+        workflow: &ApprovalWorkflow,
+        levels: &[(i32, Uuid)],
+    ) -> Result<ApprovalWorkflow, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let created = sqlx::query_as::<_, ApprovalWorkflow>(
+            r#"
+            INSERT INTO approval_workflows (
+                id, resource_type, action_type, asset_type, name, description,
+                need_approval, is_active
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            "#,
+        )
+        .bind(workflow.id)
+        .bind(&workflow.resource_type)
+        .bind(&workflow.action_type)
+        .bind(&workflow.asset_type)
+        .bind(&workflow.name)
+        .bind(&workflow.description)
+        .bind(workflow.need_approval)
+        .bind(workflow.is_active)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Self::insert_levels(&mut tx, created.id, levels).await?;
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    /// Update a workflow and replace its levels
+    pub async fn update(
+        &self,
+        workflow: &ApprovalWorkflow,
+        levels: Option<&[(i32, Uuid)]>,
+    ) -> Result<ApprovalWorkflow, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let updated = sqlx::query_as::<_, ApprovalWorkflow>(
+            r#"
+            UPDATE approval_workflows SET
+                resource_type = $2,
+                action_type = $3,
+                asset_type = $4,
+                name = $5,
+                description = $6,
+                need_approval = $7,
+                is_active = $8,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(workflow.id)
+        .bind(&workflow.resource_type)
+        .bind(&workflow.action_type)
+        .bind(&workflow.asset_type)
+        .bind(&workflow.name)
+        .bind(&workflow.description)
+        .bind(workflow.need_approval)
+        .bind(workflow.is_active)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if let Some(levels) = levels {
+            sqlx::query("DELETE FROM approval_workflow_levels WHERE workflow_id = $1")
+                .bind(workflow.id)
+                .execute(&mut *tx)
+                .await?;
+            Self::insert_levels(&mut tx, workflow.id, levels).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(updated)
+    }
+
+    async fn insert_levels(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        workflow_id: Uuid,
+        levels: &[(i32, Uuid)],
+    ) -> Result<(), sqlx::Error> {
+        for (level_number, role_id) in levels {
+            sqlx::query(
+                r#"
+                INSERT INTO approval_workflow_levels (workflow_id, level_number, role_id)
+                VALUES ($1, $2, $3)
+                ON CONFLICT DO NOTHING
+                "#,
+            )
+            .bind(workflow_id)
+            .bind(level_number)
+            .bind(role_id)
+            .execute(&mut **tx)
+            .await?;
+        }
+        Ok(())
+    }
+
+    pub async fn delete(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM approval_workflows WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Number of pending requests still running against a workflow
+    pub async fn count_pending_requests(&self, workflow_id: Uuid) -> Result<i64, sqlx::Error> {
+        let row: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM approval_requests WHERE workflow_id = $1 AND status = 'PENDING'",
+        )
+        .bind(workflow_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.0)
+    }
+}
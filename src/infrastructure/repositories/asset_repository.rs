@@ -2,11 +2,14 @@
 //!
 //! Data access for Asset entities.
 
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
+use crate::application::dto::AssetSearchParams;
 use crate::domain::entities::asset_details::VehicleDetails;
-use crate::domain::entities::{Asset, AssetHistory, AssetSummary};
+use crate::domain::entities::{Asset, AssetHistory, AssetSummary, DataScope};
+use crate::domain::events::{AssetCreated, EventEnvelope};
+use crate::infrastructure::repositories::OutboxRepository;
 
 /// Asset repository
 #[derive(Clone)]
@@ -19,18 +22,22 @@ impl AssetRepository {
         Self { pool }
     }
 
+    pub async fn begin(&self) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        self.pool.begin().await
+    }
+
     /// Find asset by ID
     pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Asset>, sqlx::Error> {
         sqlx::query_as::<_, Asset>(
             r#"
             SELECT 
                 id, asset_code, name, category_id, location_id, department_id, department, assigned_to, vendor_id,
-                is_rental, asset_class, status, condition_id,
+                organization_id, is_rental, asset_class, status, condition_id,
                 serial_number, brand, model, year_manufacture,
                 specifications,
                 purchase_date, purchase_price, currency_id, unit_id, quantity,
                 residual_value, useful_life_months,
-                qr_code_url, notes,
+                qr_code_url, insurance_lapsed_at, notes,
                 created_at, updated_at
             FROM assets
             WHERE id = $1
@@ -47,12 +54,12 @@ impl AssetRepository {
             r#"
             SELECT 
                 id, asset_code, name, category_id, location_id, department_id, department, assigned_to, vendor_id,
-                is_rental, asset_class, status, condition_id,
+                organization_id, is_rental, asset_class, status, condition_id,
                 serial_number, brand, model, year_manufacture,
                 specifications,
                 purchase_date, purchase_price, currency_id, unit_id, quantity,
                 residual_value, useful_life_months,
-                qr_code_url, notes,
+                qr_code_url, insurance_lapsed_at, notes,
                 created_at, updated_at
             FROM assets
             WHERE asset_code = $1
@@ -63,12 +70,29 @@ impl AssetRepository {
         .await
     }
 
-    /// List assets with pagination and optional department filter
+    /// Whether an asset is within the data scope of a user
+    pub async fn in_data_scope(&self, id: Uuid, scope: &DataScope) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM assets
+                WHERE id = $1 AND data_scope_allows($2, ARRAY[assigned_to], department_id, location_id)
+            )
+            "#,
+        )
+        .bind(id)
+        .bind(scope)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// List assets with pagination within the organization and data scope
     pub async fn list(
         &self,
         limit: i64,
         offset: i64,
-        department: Option<&str>,
+        org_scope: Option<Uuid>,
+        scope: &DataScope,
     ) -> Result<Vec<AssetSummary>, sqlx::Error> {
         sqlx::query_as::<_, AssetSummary>(
             r#"
@@ -76,57 +100,77 @@ impl AssetRepository {
                    a.category_id, a.location_id, l.name as location_name, a.department, a.model, a.serial_number
             FROM assets a
             LEFT JOIN locations l ON a.location_id = l.id
-            WHERE ($3::text IS NULL OR a.department = $3)
+            WHERE ($3::uuid IS NULL OR a.organization_id IN (SELECT organization_subtree($3)))
+              AND data_scope_allows($4, ARRAY[a.assigned_to], a.department_id, a.location_id)
             ORDER BY a.created_at DESC
             LIMIT $1 OFFSET $2
             "#,
         )
         .bind(limit)
         .bind(offset)
-        .bind(department)
+        .bind(org_scope)
+        .bind(scope)
         .fetch_all(&self.pool)
         .await
     }
 
-    /// Find all assets (for export)
-    pub async fn find_all(&self) -> Result<Vec<Asset>, sqlx::Error> {
+    /// Find all assets in the organization and data scope (for export)
+    pub async fn find_all(
+        &self,
+        org_scope: Option<Uuid>,
+        scope: &DataScope,
+    ) -> Result<Vec<Asset>, sqlx::Error> {
         sqlx::query_as::<_, Asset>(
             r#"
             SELECT 
                 id, asset_code, name, category_id, location_id, department_id, department, assigned_to, vendor_id,
-                is_rental, asset_class, status, condition_id,
+                organization_id, is_rental, asset_class, status, condition_id,
                 serial_number, brand, model, year_manufacture,
                 specifications,
                 purchase_date, purchase_price, currency_id, unit_id, quantity,
                 residual_value, useful_life_months,
-                qr_code_url, notes,
+                qr_code_url, insurance_lapsed_at, notes,
                 created_at, updated_at
             FROM assets
+            WHERE ($1::uuid IS NULL OR organization_id IN (SELECT organization_subtree($1)))
+              AND data_scope_allows($2, ARRAY[assigned_to], department_id, location_id)
             ORDER BY created_at DESC
             "#,
         )
+        .bind(org_scope)
+        .bind(scope)
         .fetch_all(&self.pool)
         .await
     }
 
-    /// Count total assets
-    pub async fn count(&self) -> Result<i64, sqlx::Error> {
-        let result: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM assets")
-            .fetch_one(&self.pool)
-            .await?;
+    /// Count assets in the organization and data scope
+    pub async fn count(
+        &self,
+        org_scope: Option<Uuid>,
+        scope: &DataScope,
+    ) -> Result<i64, sqlx::Error> {
+        let result: (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*) FROM assets
+            WHERE ($1::uuid IS NULL OR organization_id IN (SELECT organization_subtree($1)))
+              AND data_scope_allows($2, ARRAY[assigned_to], department_id, location_id)
+            "#,
+        )
+        .bind(org_scope)
+        .bind(scope)
+        .fetch_one(&self.pool)
+        .await?;
         Ok(result.0)
     }
 
     /// Search assets
     pub async fn search(
         &self,
-        query: &str,
-        category_id: Option<Uuid>,
-        location_id: Option<Uuid>,
-        department: Option<&str>,
-        status: Option<&str>,
+        params: &AssetSearchParams,
         limit: i64,
         offset: i64,
+        org_scope: Option<Uuid>,
+        scope: &DataScope,
     ) -> Result<Vec<AssetSummary>, sqlx::Error> {
         sqlx::query_as::<_, AssetSummary>(
             r#"
@@ -140,35 +184,44 @@ impl AssetRepository {
                 AND ($3::uuid IS NULL OR a.location_id = $3)
                 AND ($4::text IS NULL OR a.department = $4)
                 AND ($5::text IS NULL OR a.status = $5)
+                AND ($8::uuid IS NULL OR a.organization_id IN (SELECT organization_subtree($8)))
+                AND data_scope_allows($9, ARRAY[a.assigned_to], a.department_id, a.location_id)
             ORDER BY a.created_at DESC
             LIMIT $6 OFFSET $7
             "#,
         )
-        .bind(query)
-        .bind(category_id)
-        .bind(location_id)
-        .bind(department)
-        .bind(status)
+        .bind(params.query.as_deref().unwrap_or(""))
+        .bind(params.category_id)
+        .bind(params.location_id)
+        .bind(params.department.as_deref())
+        .bind(params.status.as_deref())
         .bind(limit)
         .bind(offset)
+        .bind(org_scope)
+        .bind(scope)
         .fetch_all(&self.pool)
         .await
     }
 
-    /// Create new asset
-    pub async fn create(&self, asset: &Asset) -> Result<Asset, sqlx::Error> {
-        sqlx::query_as::<_, Asset>(
+    /// Insert an asset in the caller's transaction; its creation event is
+    /// stored with it
+    pub async fn create(
+        tx: &mut Transaction<'_, Postgres>,
+        asset: &Asset,
+        event: EventEnvelope<AssetCreated>,
+    ) -> Result<Asset, sqlx::Error> {
+        let created = sqlx::query_as::<_, Asset>(
             r#"
             INSERT INTO assets (
                 id, asset_code, name, category_id, location_id, department_id, department, assigned_to, vendor_id,
-                is_rental, asset_class, status, condition_id,
+                organization_id, is_rental, asset_class, status, condition_id,
                 serial_number, brand, model, year_manufacture,
                 specifications,
                 purchase_date, purchase_price, currency_id, unit_id, quantity,
                 residual_value, useful_life_months,
                 qr_code_url, notes
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $28, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27)
             RETURNING *
             "#,
         )
@@ -199,12 +252,25 @@ impl AssetRepository {
         .bind(asset.useful_life_months)
         .bind(&asset.qr_code_url)
         .bind(&asset.notes)
-        .fetch_one(&self.pool)
-        .await
+        .bind(asset.organization_id)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        OutboxRepository::append(tx, event).await?;
+
+        Ok(created)
     }
 
     /// Update asset
     pub async fn update(&self, asset: &Asset) -> Result<Asset, sqlx::Error> {
+        Self::update_with(&self.pool, asset).await
+    }
+
+    /// Update asset on a given connection or transaction
+    pub(crate) async fn update_with<'e, E>(executor: E, asset: &Asset) -> Result<Asset, sqlx::Error>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
         sqlx::query_as::<_, Asset>(
             r#"
             UPDATE assets SET
@@ -248,7 +314,7 @@ impl AssetRepository {
         .bind(asset.useful_life_months)
         .bind(&asset.qr_code_url)
         .bind(&asset.notes)
-        .fetch_one(&self.pool)
+        .fetch_one(executor)
         .await
     }
 
@@ -277,9 +343,17 @@ impl AssetRepository {
 
     /// Delete asset
     pub async fn delete(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        Self::delete_with(&self.pool, id).await
+    }
+
+    /// Delete asset on a given connection or transaction
+    pub(crate) async fn delete_with<'e, E>(executor: E, id: Uuid) -> Result<bool, sqlx::Error>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
         let result = sqlx::query("DELETE FROM assets WHERE id = $1")
             .bind(id)
-            .execute(&self.pool)
+            .execute(executor)
             .await?;
 
         Ok(result.rows_affected() > 0)
@@ -303,6 +377,17 @@ impl AssetRepository {
 
     /// Add history entry
     pub async fn add_history(&self, history: &AssetHistory) -> Result<AssetHistory, sqlx::Error> {
+        Self::add_history_with(&self.pool, history).await
+    }
+
+    /// Add history entry on a given connection or transaction
+    pub(crate) async fn add_history_with<'e, E>(
+        executor: E,
+        history: &AssetHistory,
+    ) -> Result<AssetHistory, sqlx::Error>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
         sqlx::query_as::<_, AssetHistory>(
             r#"
             INSERT INTO asset_history (id, asset_id, action, from_location_id, to_location_id,
@@ -320,15 +405,18 @@ impl AssetRepository {
         .bind(history.to_user_id)
         .bind(&history.notes)
         .bind(history.performed_by)
-        .fetch_one(&self.pool)
+        .fetch_one(executor)
         .await
     }
 
-    /// Upsert vehicle details
-    pub async fn upsert_vehicle_details(
-        &self,
+    /// Upsert vehicle details on a given connection or transaction
+    pub(crate) async fn upsert_vehicle_details<'e, E>(
+        executor: E,
         details: &VehicleDetails,
-    ) -> Result<VehicleDetails, sqlx::Error> {
+    ) -> Result<VehicleDetails, sqlx::Error>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
         sqlx::query_as::<_, VehicleDetails>(
             r#"
             INSERT INTO vehicle_details (
@@ -371,7 +459,7 @@ impl AssetRepository {
         .bind(&details.transmission)
         .bind(&details.capacity)
         .bind(details.odometer_last)
-        .fetch_one(&self.pool)
+        .fetch_one(executor)
         .await
     }
 
@@ -0,0 +1,392 @@
+//! Budget Repository
+//!
+//! Budget lines and the commitment/actual ledger. Commitments are checked
+//! against the line while it is locked so concurrent approvals cannot both
+//! spend the last of a budget.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::domain::entities::{BudgetEntry, BudgetLine, BudgetPosition, ENTRY_COMMITMENT};
+
+/// Budget lines with their committed and actual spend; binds organization
+/// scope ($1), fiscal year ($2) and line ($3), each optional
+const POSITION_QUERY: &str = r#"
+    SELECT p.*, p.amount - p.committed - p.actual AS available
+    FROM (
+        SELECT
+            bl.id AS budget_line_id, bl.organization_id, o.code AS organization_code,
+            o.cost_center, bl.department_id, d.name AS department_name, bl.name,
+            bl.fiscal_year, bl.period_start, bl.period_end, bl.over_budget_action, bl.amount,
+            COALESCE(SUM(e.amount) FILTER (
+                WHERE e.entry_type = 'commitment' AND e.released_at IS NULL
+            ), 0) AS committed,
+            COALESCE(SUM(e.amount) FILTER (WHERE e.entry_type = 'actual'), 0) AS actual
+        FROM budget_lines bl
+        JOIN organizations o ON o.id = bl.organization_id
+        LEFT JOIN departments d ON d.id = bl.department_id
+        LEFT JOIN budget_entries e ON e.budget_line_id = bl.id
+        WHERE ($1::uuid IS NULL OR bl.organization_id IN (SELECT organization_subtree($1)))
+          AND ($2::int IS NULL OR bl.fiscal_year = $2)
+          AND ($3::uuid IS NULL OR bl.id = $3)
+        GROUP BY bl.id, o.code, o.cost_center, d.name
+    ) p
+    ORDER BY p.organization_code, p.period_start, p.name
+"#;
+
+#[derive(Clone)]
+pub struct BudgetRepository {
+    pool: PgPool,
+}
+
+impl BudgetRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn begin(&self) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        self.pool.begin().await
+    }
+
+    // ==================== BUDGET LINES ====================
+
+    /// Budget lines in the subtree of `scope` (all when `None`)
+    pub async fn list(
+        &self,
+        scope: Option<Uuid>,
+        fiscal_year: Option<i32>,
+    ) -> Result<Vec<BudgetLine>, sqlx::Error> {
+        sqlx::query_as::<_, BudgetLine>(
+            r#"
+            SELECT * FROM budget_lines
+            WHERE ($1::uuid IS NULL OR organization_id IN (SELECT organization_subtree($1)))
+              AND ($2::int IS NULL OR fiscal_year = $2)
+            ORDER BY period_start, name
+            "#,
+        )
+        .bind(scope)
+        .bind(fiscal_year)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<BudgetLine>, sqlx::Error> {
+        sqlx::query_as::<_, BudgetLine>("SELECT * FROM budget_lines WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    pub async fn create(&self, line: &BudgetLine) -> Result<BudgetLine, sqlx::Error> {
+        sqlx::query_as::<_, BudgetLine>(
+            r#"
+            INSERT INTO budget_lines (
+                id, organization_id, department_id, name, fiscal_year,
+                period_start, period_end, amount, over_budget_action, notes, created_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING *
+            "#,
+        )
+        .bind(line.id)
+        .bind(line.organization_id)
+        .bind(line.department_id)
+        .bind(&line.name)
+        .bind(line.fiscal_year)
+        .bind(line.period_start)
+        .bind(line.period_end)
+        .bind(line.amount)
+        .bind(&line.over_budget_action)
+        .bind(&line.notes)
+        .bind(line.created_by)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn update(&self, line: &BudgetLine) -> Result<BudgetLine, sqlx::Error> {
+        sqlx::query_as::<_, BudgetLine>(
+            r#"
+            UPDATE budget_lines SET
+                department_id = $2, name = $3, fiscal_year = $4, period_start = $5,
+                period_end = $6, amount = $7, over_budget_action = $8, notes = $9
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(line.id)
+        .bind(line.department_id)
+        .bind(&line.name)
+        .bind(line.fiscal_year)
+        .bind(line.period_start)
+        .bind(line.period_end)
+        .bind(line.amount)
+        .bind(&line.over_budget_action)
+        .bind(&line.notes)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Delete a line nothing has been booked against
+    pub async fn delete_unused(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM budget_lines bl
+            WHERE bl.id = $1
+              AND NOT EXISTS (SELECT 1 FROM budget_entries e WHERE e.budget_line_id = bl.id)
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Whether another line of the same cost center/department overlaps the period
+    pub async fn has_overlap(&self, line: &BudgetLine) -> Result<bool, sqlx::Error> {
+        let (found,): (bool,) = sqlx::query_as(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM budget_lines
+                WHERE id <> $1
+                  AND organization_id = $2
+                  AND department_id IS NOT DISTINCT FROM $3
+                  AND period_start <= $5
+                  AND period_end >= $4
+            )
+            "#,
+        )
+        .bind(line.id)
+        .bind(line.organization_id)
+        .bind(line.department_id)
+        .bind(line.period_start)
+        .bind(line.period_end)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(found)
+    }
+
+    pub async fn department_in_organization(
+        &self,
+        department_id: Uuid,
+        organization_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let (found,): (bool,) = sqlx::query_as(
+            "SELECT EXISTS (SELECT 1 FROM departments WHERE id = $1 AND organization_id = $2)",
+        )
+        .bind(department_id)
+        .bind(organization_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(found)
+    }
+
+    /// Total of the organization's lines for a fiscal year, except `except`
+    pub async fn allocated(
+        &self,
+        organization_id: Uuid,
+        fiscal_year: i32,
+        except: Uuid,
+    ) -> Result<Decimal, sqlx::Error> {
+        let (total,): (Decimal,) = sqlx::query_as(
+            r#"
+            SELECT COALESCE(SUM(amount), 0) FROM budget_lines
+            WHERE organization_id = $1 AND fiscal_year = $2 AND id <> $3
+            "#,
+        )
+        .bind(organization_id)
+        .bind(fiscal_year)
+        .bind(except)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(total)
+    }
+
+    /// Line that pays for spend on an asset at `date`: the nearest organization
+    /// up the asset's hierarchy with a line covering the date, preferring the
+    /// asset's department and then the narrowest period
+    pub async fn find_line_for_asset(
+        &self,
+        asset_id: Uuid,
+        date: NaiveDate,
+    ) -> Result<Option<BudgetLine>, sqlx::Error> {
+        sqlx::query_as::<_, BudgetLine>(
+            r#"
+            WITH RECURSIVE chain AS (
+                SELECT o.id, o.parent_id, 0 AS depth
+                FROM organizations o
+                JOIN assets a ON a.organization_id = o.id
+                WHERE a.id = $1
+                UNION ALL
+                SELECT p.id, p.parent_id, c.depth + 1
+                FROM organizations p
+                JOIN chain c ON p.id = c.parent_id
+            )
+            SELECT bl.* FROM budget_lines bl
+            JOIN chain c ON c.id = bl.organization_id
+            WHERE $2 BETWEEN bl.period_start AND bl.period_end
+              AND (bl.department_id IS NULL
+                   OR bl.department_id = (SELECT department_id FROM assets WHERE id = $1))
+            ORDER BY c.depth, bl.department_id IS NULL, bl.period_end - bl.period_start
+            LIMIT 1
+            "#,
+        )
+        .bind(asset_id)
+        .bind(date)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    // ==================== POSITIONS ====================
+
+    /// Budget vs. committed and actual spend per line
+    pub async fn report(
+        &self,
+        scope: Option<Uuid>,
+        fiscal_year: Option<i32>,
+    ) -> Result<Vec<BudgetPosition>, sqlx::Error> {
+        sqlx::query_as::<_, BudgetPosition>(POSITION_QUERY)
+            .bind(scope)
+            .bind(fiscal_year)
+            .bind(None::<Uuid>)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    pub async fn position(&self, line_id: Uuid) -> Result<Option<BudgetPosition>, sqlx::Error> {
+        sqlx::query_as::<_, BudgetPosition>(POSITION_QUERY)
+            .bind(None::<Uuid>)
+            .bind(None::<i32>)
+            .bind(line_id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    // ==================== ENTRIES ====================
+
+    pub async fn entries(&self, line_id: Uuid) -> Result<Vec<BudgetEntry>, sqlx::Error> {
+        sqlx::query_as::<_, BudgetEntry>(
+            r#"
+            SELECT * FROM budget_entries
+            WHERE budget_line_id = $1
+            ORDER BY entry_date DESC, created_at DESC
+            "#,
+        )
+        .bind(line_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Book a commitment, replacing an earlier one of the same source. Returns
+    /// `None` when it does not fit the line and `allow_overrun` is false. The
+    /// line stays locked until `conn`'s transaction ends.
+    pub async fn commit(
+        conn: &mut PgConnection,
+        entry: &BudgetEntry,
+        allow_overrun: bool,
+    ) -> Result<Option<BudgetEntry>, sqlx::Error> {
+        sqlx::query("SELECT id FROM budget_lines WHERE id = $1 FOR UPDATE")
+            .bind(entry.budget_line_id)
+            .execute(&mut *conn)
+            .await?;
+
+        if !allow_overrun {
+            let position = sqlx::query_as::<_, BudgetPosition>(POSITION_QUERY)
+                .bind(None::<Uuid>)
+                .bind(None::<i32>)
+                .bind(entry.budget_line_id)
+                .fetch_one(&mut *conn)
+                .await?;
+            let (replaced,): (Decimal,) = sqlx::query_as(
+                r#"
+                SELECT COALESCE(SUM(amount), 0) FROM budget_entries
+                WHERE budget_line_id = $1 AND source_type = $2 AND source_id = $3
+                  AND entry_type = 'commitment' AND released_at IS NULL
+                "#,
+            )
+            .bind(entry.budget_line_id)
+            .bind(&entry.source_type)
+            .bind(entry.source_id)
+            .fetch_one(&mut *conn)
+            .await?;
+            if !position.can_absorb(entry.amount, replaced) {
+                return Ok(None);
+            }
+        }
+
+        let saved = Self::upsert(conn, entry).await?;
+        Ok(Some(saved))
+    }
+
+    /// Book actual spend and release the source's open commitment
+    pub async fn post_actual(&self, entry: &BudgetEntry) -> Result<BudgetEntry, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let saved = Self::upsert(&mut tx, entry).await?;
+        Self::release_in(&mut tx, &entry.source_type, entry.source_id).await?;
+        tx.commit().await?;
+        Ok(saved)
+    }
+
+    /// Release the open commitment of a source whose spend was dropped
+    pub async fn release(&self, source_type: &str, source_id: Uuid) -> Result<bool, sqlx::Error> {
+        let mut conn = self.pool.acquire().await?;
+        Self::release_in(&mut conn, source_type, source_id).await
+    }
+
+    async fn upsert(
+        conn: &mut PgConnection,
+        entry: &BudgetEntry,
+    ) -> Result<BudgetEntry, sqlx::Error> {
+        sqlx::query_as::<_, BudgetEntry>(
+            r#"
+            INSERT INTO budget_entries (
+                id, budget_line_id, source_type, source_id, asset_id, entry_type,
+                amount, entry_date, approval_request_id, created_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (source_type, source_id, entry_type) DO UPDATE SET
+                budget_line_id = EXCLUDED.budget_line_id,
+                asset_id = EXCLUDED.asset_id,
+                amount = EXCLUDED.amount,
+                entry_date = EXCLUDED.entry_date,
+                approval_request_id = EXCLUDED.approval_request_id,
+                created_by = EXCLUDED.created_by,
+                released_at = NULL
+            RETURNING *
+            "#,
+        )
+        .bind(entry.id)
+        .bind(entry.budget_line_id)
+        .bind(&entry.source_type)
+        .bind(entry.source_id)
+        .bind(entry.asset_id)
+        .bind(&entry.entry_type)
+        .bind(entry.amount)
+        .bind(entry.entry_date)
+        .bind(entry.approval_request_id)
+        .bind(entry.created_by)
+        .fetch_one(&mut *conn)
+        .await
+    }
+
+    async fn release_in(
+        conn: &mut PgConnection,
+        source_type: &str,
+        source_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE budget_entries SET released_at = NOW()
+            WHERE source_type = $1 AND source_id = $2
+              AND entry_type = $3 AND released_at IS NULL
+            "#,
+        )
+        .bind(source_type)
+        .bind(source_id)
+        .bind(ENTRY_COMMITMENT)
+        .execute(&mut *conn)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
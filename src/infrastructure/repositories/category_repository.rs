@@ -57,8 +57,10 @@ impl CategoryRepository {
     pub async fn create(&self, category: &Category) -> Result<Category, sqlx::Error> {
         sqlx::query_as::<_, Category>(
             r#"
-            INSERT INTO categories (id, parent_id, code, name, department, description, attributes, main_category, sub_category_letter, example_assets, function_description, display_order)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            INSERT INTO categories (id, parent_id, code, name, department, description, attributes, main_category, sub_category_letter, example_assets, function_description, display_order,
+                depreciation_method, depreciation_period_months, residual_rate, declining_balance_rate, estimated_total_hours,
+                specification_schema)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
             RETURNING *
             "#,
         )
@@ -74,6 +76,12 @@ impl CategoryRepository {
         .bind(&category.example_assets)
         .bind(&category.function_description)
         .bind(category.display_order)
+        .bind(&category.depreciation_method)
+        .bind(category.depreciation_period)
+        .bind(category.residual_rate)
+        .bind(category.declining_balance_rate)
+        .bind(category.estimated_total_hours)
+        .bind(&category.specification_schema)
         .fetch_one(&self.pool)
         .await
     }
@@ -84,7 +92,10 @@ impl CategoryRepository {
             UPDATE categories SET
                 parent_id = $2, code = $3, name = $4, department = $5, description = $6, attributes = $7,
                 main_category = $8, sub_category_letter = $9, example_assets = $10, 
-                function_description = $11, display_order = $12, updated_at = NOW()
+                function_description = $11, display_order = $12,
+                depreciation_method = $13, depreciation_period_months = $14, residual_rate = $15,
+                declining_balance_rate = $16, estimated_total_hours = $17,
+                specification_schema = $18, updated_at = NOW()
             WHERE id = $1
             RETURNING *
             "#,
@@ -101,6 +112,12 @@ impl CategoryRepository {
         .bind(&category.example_assets)
         .bind(&category.function_description)
         .bind(category.display_order)
+        .bind(&category.depreciation_method)
+        .bind(category.depreciation_period)
+        .bind(category.residual_rate)
+        .bind(category.declining_balance_rate)
+        .bind(category.estimated_total_hours)
+        .bind(&category.specification_schema)
         .fetch_one(&self.pool)
         .await
     }
@@ -1,6 +1,8 @@
 //! Conversion Repository
 
 use crate::domain::entities::conversion::AssetConversion;
+use crate::domain::entities::{Asset, AssetHistory, AssetSpecificationHistory};
+use crate::infrastructure::repositories::{AssetRepository, SpecificationRepository};
 use crate::shared::errors::AppError;
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -185,6 +187,17 @@ impl ConversionRepository {
     }
 
     pub async fn update(&self, conversion: &AssetConversion) -> Result<AssetConversion, AppError> {
+        Self::update_with(&self.pool, conversion).await
+    }
+
+    /// Update a conversion on a given connection or transaction
+    pub(crate) async fn update_with<'e, E>(
+        executor: E,
+        conversion: &AssetConversion,
+    ) -> Result<AssetConversion, AppError>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
         let rec = sqlx::query_as!(
             AssetConversion,
             r#"
@@ -231,13 +244,71 @@ impl ConversionRepository {
             conversion.notes,
             conversion.updated_at
         )
-        .fetch_one(&self.pool)
+        .fetch_one(executor)
         .await
         .map_err(|e| AppError::Database(e.to_string()))?;
 
         Ok(rec)
     }
 
+    /// Mark an approved conversion executed and apply it to the asset, with the
+    /// specification and asset history, in one transaction. Returns `None` when
+    /// the conversion is no longer approved or the asset changed concurrently.
+    pub async fn execute(
+        &self,
+        conversion: &AssetConversion,
+        asset: &Asset,
+        entry: &AssetSpecificationHistory,
+        history: &AssetHistory,
+    ) -> Result<Option<AssetConversion>, AppError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let executed = sqlx::query_as::<_, AssetConversion>(
+            r#"
+            UPDATE asset_conversions SET
+                status = 'executed', executed_by = $2, execution_date = $3, notes = $4,
+                updated_at = NOW()
+            WHERE id = $1 AND status = 'approved'
+            RETURNING *
+            "#,
+        )
+        .bind(conversion.id)
+        .bind(conversion.executed_by)
+        .bind(conversion.execution_date)
+        .bind(&conversion.notes)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        let Some(executed) = executed else {
+            return Ok(None);
+        };
+
+        if !SpecificationRepository::lock_unchanged(&mut tx, entry)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?
+        {
+            return Ok(None);
+        }
+        AssetRepository::update_with(&mut *tx, asset)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        SpecificationRepository::insert_with(&mut tx, entry)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        AssetRepository::add_history_with(&mut *tx, history)
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(Some(executed))
+    }
+
     // For counting pending reqs (for dashboards)
     pub async fn count_pending(&self) -> Result<i64, AppError> {
         let count = sqlx::query!(
@@ -0,0 +1,495 @@
+//! Depreciation Repository
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::{
+    AssetDepreciationPosition, DataScope, DepreciationCloseLine, DepreciationClosing,
+    DepreciationPeriod, DepreciationProfile, DepreciationSchedule,
+};
+
+/// Rows posted by a close that is still in force
+const LOCKED_ROW: &str = "EXISTS (SELECT 1 FROM depreciation_closings c \
+     WHERE c.id = depreciation_schedules.closing_id AND c.status = 'closed')";
+
+/// Report groupings: (group id, group name, joins)
+const CLOSE_GROUP_CATEGORY: (&str, &str, &str) = (
+    "a.category_id",
+    "COALESCE(c.name, 'Uncategorized')",
+    "LEFT JOIN categories c ON a.category_id = c.id",
+);
+const CLOSE_GROUP_DEPARTMENT: (&str, &str, &str) = (
+    "a.department_id",
+    "COALESCE(d.name, a.department, 'Unassigned')",
+    "LEFT JOIN departments d ON a.department_id = d.id",
+);
+const CLOSE_GROUP_LOCATION: (&str, &str, &str) = (
+    "a.location_id",
+    "COALESCE(l.name, 'Unassigned')",
+    "LEFT JOIN locations l ON a.location_id = l.id",
+);
+
+#[derive(Clone)]
+pub struct DepreciationRepository {
+    pool: PgPool,
+}
+
+impl DepreciationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Asset financials with the category's depreciation settings
+    pub async fn find_profile(
+        &self,
+        asset_id: Uuid,
+    ) -> Result<Option<DepreciationProfile>, sqlx::Error> {
+        sqlx::query_as::<_, DepreciationProfile>(
+            r#"
+            SELECT a.id AS asset_id, a.purchase_date, a.purchase_price, a.residual_value,
+                   a.useful_life_months, c.depreciation_method, c.depreciation_period_months,
+                   c.residual_rate, c.declining_balance_rate, c.estimated_total_hours
+            FROM assets a
+            LEFT JOIN categories c ON a.category_id = c.id
+            WHERE a.id = $1
+            "#,
+        )
+        .bind(asset_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Hour meter usage per month from rental timesheets (rejected entries excluded)
+    pub async fn monthly_usage(
+        &self,
+        asset_id: Uuid,
+    ) -> Result<Vec<(NaiveDate, Decimal)>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            SELECT date_trunc('month', t.work_date)::date AS month,
+                   COALESCE(SUM(COALESCE(t.hm_km_usage, t.hm_km_end - t.hm_km_start)), 0) AS units
+            FROM rental_timesheets t
+            JOIN rentals r ON t.rental_id = r.id
+            WHERE r.asset_id = $1
+              AND COALESCE(t.verifier_status, 'pending') <> 'rejected'
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+        )
+        .bind(asset_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Carrying amounts set by revaluations and impairments; the latest record of a
+    /// month wins
+    pub async fn list_revaluations(
+        &self,
+        asset_id: Uuid,
+    ) -> Result<Vec<(NaiveDate, Decimal)>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            SELECT DISTINCT ON (1) date_trunc('month', valuation_date)::date AS month,
+                   revalued_amount
+            FROM asset_valuations
+            WHERE asset_id = $1 AND revalued_amount IS NOT NULL
+            ORDER BY 1, valuation_date DESC, created_at DESC
+            "#,
+        )
+        .bind(asset_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Assets that have enough data to depreciate
+    pub async fn list_depreciable_asset_ids(&self) -> Result<Vec<Uuid>, sqlx::Error> {
+        let rows: Vec<(Uuid,)> = sqlx::query_as(
+            r#"
+            SELECT id FROM assets
+            WHERE purchase_price IS NOT NULL AND purchase_date IS NOT NULL
+            ORDER BY asset_code
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+
+    pub async fn list_assets_in_category(
+        &self,
+        category_id: Uuid,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        let rows: Vec<(Uuid,)> = sqlx::query_as(
+            r#"
+            SELECT id FROM assets
+            WHERE category_id = $1 AND purchase_price IS NOT NULL AND purchase_date IS NOT NULL
+            "#,
+        )
+        .bind(category_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.0).collect())
+    }
+
+    pub async fn list_schedule(
+        &self,
+        asset_id: Uuid,
+    ) -> Result<Vec<DepreciationSchedule>, sqlx::Error> {
+        sqlx::query_as::<_, DepreciationSchedule>(
+            "SELECT * FROM depreciation_schedules WHERE asset_id = $1 ORDER BY period_start",
+        )
+        .bind(asset_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Last row of the asset posted by a close still in force:
+    /// (period_start, accumulated_depreciation)
+    pub async fn find_closed_through(
+        &self,
+        asset_id: Uuid,
+    ) -> Result<Option<(NaiveDate, Decimal)>, sqlx::Error> {
+        sqlx::query_as(&format!(
+            r#"
+            SELECT period_start, accumulated_depreciation
+            FROM depreciation_schedules
+            WHERE asset_id = $1 AND {}
+            ORDER BY period_start DESC
+            LIMIT 1
+            "#,
+            LOCKED_ROW
+        ))
+        .bind(asset_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Replace the open part of an asset's schedule with freshly computed periods.
+    /// Rows of closed periods are kept; the full schedule is returned.
+    pub async fn replace_schedule(
+        &self,
+        asset_id: Uuid,
+        method: &str,
+        periods: &[DepreciationPeriod],
+    ) -> Result<Vec<DepreciationSchedule>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(&format!(
+            "DELETE FROM depreciation_schedules WHERE asset_id = $1 AND NOT {}",
+            LOCKED_ROW
+        ))
+        .bind(asset_id)
+        .execute(&mut *tx)
+        .await?;
+
+        for period in periods {
+            sqlx::query(
+                r#"
+                INSERT INTO depreciation_schedules (
+                    asset_id, period_start, period_end, opening_value, depreciation_amount,
+                    accumulated_depreciation, closing_value, depreciation_method, usage_units,
+                    valuation_adjustment, is_calculated, calculated_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, true, NOW())
+                "#,
+            )
+            .bind(asset_id)
+            .bind(period.period_start)
+            .bind(period.period_end)
+            .bind(period.opening_value)
+            .bind(period.depreciation_amount)
+            .bind(period.accumulated_depreciation)
+            .bind(period.closing_value)
+            .bind(method)
+            .bind(period.usage_units)
+            .bind(period.valuation_adjustment)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let rows = sqlx::query_as::<_, DepreciationSchedule>(
+            "SELECT * FROM depreciation_schedules WHERE asset_id = $1 ORDER BY period_start",
+        )
+        .bind(asset_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(rows)
+    }
+
+    /// Drop the open part of an asset's schedule
+    pub async fn delete_schedule(&self, asset_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(&format!(
+            "DELETE FROM depreciation_schedules WHERE asset_id = $1 AND NOT {}",
+            LOCKED_ROW
+        ))
+        .bind(asset_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Per-asset accumulated depreciation and book value as of a date.
+    ///
+    /// Assets without a schedule (or not yet in service) carry their full cost.
+    /// Positions of assets in the organization scope (all when `None`)
+    pub async fn list_positions(
+        &self,
+        as_of: NaiveDate,
+        org_scope: Option<Uuid>,
+        scope: &DataScope,
+    ) -> Result<Vec<AssetDepreciationPosition>, sqlx::Error> {
+        sqlx::query_as::<_, AssetDepreciationPosition>(
+            r#"
+            SELECT a.id AS asset_id, a.asset_code, a.name, a.purchase_date, a.purchase_price,
+                   a.useful_life_months, a.residual_value,
+                   COALESCE(ds.depreciation_method, c.depreciation_method) AS depreciation_method,
+                   CASE WHEN ds.period_start = date_trunc('month', $1::date)::date
+                        THEN ds.depreciation_amount ELSE 0 END AS period_depreciation,
+                   COALESCE(ds.accumulated_depreciation, 0) AS accumulated_depreciation,
+                   COALESCE((
+                       SELECT SUM(s.valuation_adjustment) FROM depreciation_schedules s
+                       WHERE s.asset_id = a.id AND s.period_start <= $1
+                   ), 0) AS valuation_adjustment,
+                   COALESCE(ds.closing_value, a.purchase_price) AS book_value
+            FROM assets a
+            LEFT JOIN categories c ON a.category_id = c.id
+            LEFT JOIN LATERAL (
+                SELECT s.period_start, s.depreciation_amount, s.accumulated_depreciation,
+                       s.closing_value, s.depreciation_method
+                FROM depreciation_schedules s
+                WHERE s.asset_id = a.id AND s.period_start <= $1
+                ORDER BY s.period_start DESC
+                LIMIT 1
+            ) ds ON true
+            WHERE a.purchase_price IS NOT NULL
+              AND ($2::uuid IS NULL OR a.organization_id IN (SELECT organization_subtree($2)))
+              AND data_scope_allows($3, ARRAY[a.assigned_to], a.department_id, a.location_id)
+            ORDER BY a.asset_code
+            "#,
+        )
+        .bind(as_of)
+        .bind(org_scope)
+        .bind(scope)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    // ==================== PERIOD CLOSE ====================
+
+    pub async fn list_closings(&self) -> Result<Vec<DepreciationClosing>, sqlx::Error> {
+        sqlx::query_as::<_, DepreciationClosing>(
+            "SELECT * FROM depreciation_closings ORDER BY period_start DESC",
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn find_closing(
+        &self,
+        period_start: NaiveDate,
+    ) -> Result<Option<DepreciationClosing>, sqlx::Error> {
+        sqlx::query_as::<_, DepreciationClosing>(
+            "SELECT * FROM depreciation_closings WHERE period_start = $1",
+        )
+        .bind(period_start)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    pub async fn find_closing_by_id(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<DepreciationClosing>, sqlx::Error> {
+        sqlx::query_as::<_, DepreciationClosing>(
+            "SELECT * FROM depreciation_closings WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Post every unposted row up to the period and record the close with its totals
+    pub async fn close_period(
+        &self,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+        closed_by: Option<Uuid>,
+    ) -> Result<DepreciationClosing, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let (closing_id,): (Uuid,) = sqlx::query_as(
+            r#"
+            INSERT INTO depreciation_closings (period_start, period_end, status, closed_by, closed_at)
+            VALUES ($1, $2, 'closed', $3, NOW())
+            ON CONFLICT (period_start) DO UPDATE
+            SET status = 'closed', closed_by = EXCLUDED.closed_by, closed_at = NOW(),
+                reopen_request_id = NULL, updated_at = NOW()
+            RETURNING id
+            "#,
+        )
+        .bind(period_start)
+        .bind(period_end)
+        .bind(closed_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE depreciation_schedules s
+            SET closing_id = $1, posted_at = NOW()
+            WHERE s.period_start <= $2
+              AND (s.closing_id IS NULL OR s.closing_id = $1 OR NOT EXISTS (
+                  SELECT 1 FROM depreciation_closings c
+                  WHERE c.id = s.closing_id AND c.status = 'closed'
+              ))
+            "#,
+        )
+        .bind(closing_id)
+        .bind(period_start)
+        .execute(&mut *tx)
+        .await?;
+
+        let closing = sqlx::query_as::<_, DepreciationClosing>(
+            r#"
+            UPDATE depreciation_closings
+            SET asset_count = t.asset_count,
+                total_depreciation = t.total_depreciation,
+                total_accumulated_depreciation = t.total_accumulated_depreciation,
+                total_book_value = t.total_book_value
+            FROM (
+                SELECT COUNT(*)::int AS asset_count,
+                       COALESCE(SUM(CASE WHEN ds.period_start = $2
+                                         THEN ds.depreciation_amount ELSE 0 END), 0) AS total_depreciation,
+                       COALESCE(SUM(ds.accumulated_depreciation), 0) AS total_accumulated_depreciation,
+                       COALESCE(SUM(ds.closing_value), 0) AS total_book_value
+                FROM (
+                    SELECT DISTINCT ON (asset_id) *
+                    FROM depreciation_schedules
+                    WHERE period_start <= $2
+                    ORDER BY asset_id, period_start DESC
+                ) ds
+            ) t
+            WHERE depreciation_closings.id = $1
+            RETURNING depreciation_closings.*
+            "#,
+        )
+        .bind(closing_id)
+        .bind(period_start)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(closing)
+    }
+
+    /// Remember the approval request a reopen is waiting on
+    pub async fn set_reopen_request(
+        &self,
+        id: Uuid,
+        request_id: Option<Uuid>,
+        reason: Option<&str>,
+    ) -> Result<DepreciationClosing, sqlx::Error> {
+        sqlx::query_as::<_, DepreciationClosing>(
+            r#"
+            UPDATE depreciation_closings
+            SET reopen_request_id = $2, reopen_reason = $3, updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(request_id)
+        .bind(reason)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn reopen(
+        &self,
+        id: Uuid,
+        reopened_by: Uuid,
+    ) -> Result<DepreciationClosing, sqlx::Error> {
+        Self::reopen_with(&self.pool, id, reopened_by).await
+    }
+
+    /// Reopen a close on a given connection or transaction
+    pub(crate) async fn reopen_with<'e, E>(
+        executor: E,
+        id: Uuid,
+        reopened_by: Uuid,
+    ) -> Result<DepreciationClosing, sqlx::Error>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, DepreciationClosing>(
+            r#"
+            UPDATE depreciation_closings
+            SET status = 'reopened', reopened_by = $2, reopened_at = NOW(), updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(reopened_by)
+        .fetch_one(executor)
+        .await
+    }
+
+    /// Posted position of each asset at the period, grouped by category
+    pub async fn close_report_by_category(
+        &self,
+        period_start: NaiveDate,
+    ) -> Result<Vec<DepreciationCloseLine>, sqlx::Error> {
+        self.close_report(period_start, CLOSE_GROUP_CATEGORY).await
+    }
+
+    pub async fn close_report_by_department(
+        &self,
+        period_start: NaiveDate,
+    ) -> Result<Vec<DepreciationCloseLine>, sqlx::Error> {
+        self.close_report(period_start, CLOSE_GROUP_DEPARTMENT)
+            .await
+    }
+
+    pub async fn close_report_by_location(
+        &self,
+        period_start: NaiveDate,
+    ) -> Result<Vec<DepreciationCloseLine>, sqlx::Error> {
+        self.close_report(period_start, CLOSE_GROUP_LOCATION).await
+    }
+
+    async fn close_report(
+        &self,
+        period_start: NaiveDate,
+        (group_id, group_name, joins): (&str, &str, &str),
+    ) -> Result<Vec<DepreciationCloseLine>, sqlx::Error> {
+        sqlx::query_as::<_, DepreciationCloseLine>(&format!(
+            r#"
+            SELECT {group_id} AS group_id, {group_name} AS group_name,
+                   COUNT(*) AS asset_count,
+                   COALESCE(SUM(a.purchase_price), 0) AS original_cost,
+                   COALESCE(SUM(CASE WHEN ds.period_start = $1
+                                     THEN ds.depreciation_amount ELSE 0 END), 0) AS period_depreciation,
+                   COALESCE(SUM(ds.accumulated_depreciation), 0) AS accumulated_depreciation,
+                   COALESCE(SUM(ds.closing_value), 0) AS book_value
+            FROM (
+                SELECT DISTINCT ON (asset_id) *
+                FROM depreciation_schedules
+                WHERE period_start <= $1
+                ORDER BY asset_id, period_start DESC
+            ) ds
+            JOIN assets a ON ds.asset_id = a.id
+            {joins}
+            GROUP BY 1, 2
+            ORDER BY 2
+            "#
+        ))
+        .bind(period_start)
+        .fetch_all(&self.pool)
+        .await
+    }
+}
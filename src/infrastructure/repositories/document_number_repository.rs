@@ -0,0 +1,121 @@
+//! Document Number Repository
+//!
+//! Number formats and the counters behind them. A counter is advanced with a
+//! single upsert, which holds the counter's row lock until the transaction
+//! ends: concurrent requests wait for each other instead of sharing a number.
+
+use sqlx::{PgPool, Postgres, Transaction};
+
+use crate::domain::entities::{
+    DocumentNumberFormat, NumberSeries, NumberingScope, SEGMENT_DEPARTMENT, SEGMENT_LOCATION,
+};
+
+const NEXT_VALUE_SQL: &str = r#"
+    INSERT INTO document_sequences (document_type, segment_key, period_key, last_value)
+    VALUES ($1, $2, $3, 1)
+    ON CONFLICT (document_type, segment_key, period_key)
+    DO UPDATE SET last_value = document_sequences.last_value + 1, updated_at = NOW()
+    RETURNING last_value
+"#;
+
+#[derive(Clone)]
+pub struct DocumentNumberRepository {
+    pool: PgPool,
+}
+
+impl DocumentNumberRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_formats(&self) -> Result<Vec<DocumentNumberFormat>, sqlx::Error> {
+        sqlx::query_as::<_, DocumentNumberFormat>(
+            "SELECT * FROM document_number_formats ORDER BY document_type",
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn find_format(
+        &self,
+        document_type: &str,
+    ) -> Result<Option<DocumentNumberFormat>, sqlx::Error> {
+        sqlx::query_as::<_, DocumentNumberFormat>(
+            "SELECT * FROM document_number_formats WHERE document_type = $1",
+        )
+        .bind(document_type)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    pub async fn update_format(
+        &self,
+        format: &DocumentNumberFormat,
+    ) -> Result<DocumentNumberFormat, sqlx::Error> {
+        sqlx::query_as::<_, DocumentNumberFormat>(
+            r#"
+            UPDATE document_number_formats SET
+                prefix = $2, reset_period = $3, padding = $4, segment = $5,
+                updated_by = $6, updated_at = NOW()
+            WHERE document_type = $1
+            RETURNING *
+            "#,
+        )
+        .bind(&format.document_type)
+        .bind(&format.prefix)
+        .bind(&format.reset_period)
+        .bind(format.padding)
+        .bind(&format.segment)
+        .bind(format.updated_by)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Code of the department or location a number is issued for, falling
+    /// back to the asset's
+    pub async fn find_segment_code(
+        &self,
+        segment: &str,
+        scope: &NumberingScope,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let (table, explicit, column) = match segment {
+            SEGMENT_DEPARTMENT => ("departments", scope.department_id, "department_id"),
+            SEGMENT_LOCATION => ("locations", scope.location_id, "location_id"),
+            _ => return Ok(None),
+        };
+
+        sqlx::query_scalar::<_, String>(&format!(
+            "SELECT code FROM {} WHERE id = COALESCE($1, (SELECT {} FROM assets WHERE id = $2))",
+            table, column
+        ))
+        .bind(explicit)
+        .bind(scope.asset_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Advance a counter on its own. A number taken here is lost if the
+    /// document is then not stored.
+    pub async fn next_value(&self, series: &NumberSeries) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar::<_, i64>(NEXT_VALUE_SQL)
+            .bind(series.document_type())
+            .bind(series.segment_key())
+            .bind(&series.period_key)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// Advance a counter in the transaction that stores the document, so
+    /// the number is only used up if the document is
+    pub async fn next_value_in(
+        tx: &mut Transaction<'_, Postgres>,
+        series: &NumberSeries,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar::<_, i64>(NEXT_VALUE_SQL)
+            .bind(series.document_type())
+            .bind(series.segment_key())
+            .bind(&series.period_key)
+            .fetch_one(&mut **tx)
+            .await
+    }
+}
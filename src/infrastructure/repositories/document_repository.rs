@@ -0,0 +1,183 @@
+//! Document Repository
+
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::AssetDocument;
+
+#[derive(Clone)]
+pub struct DocumentRepository {
+    pool: PgPool,
+}
+
+impl DocumentRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Documents of an asset; only current versions unless `include_history`
+    pub async fn list_by_asset(
+        &self,
+        asset_id: Uuid,
+        include_history: bool,
+    ) -> Result<Vec<AssetDocument>, sqlx::Error> {
+        sqlx::query_as::<_, AssetDocument>(
+            r#"
+            SELECT * FROM asset_documents
+            WHERE asset_id = $1 AND (is_current OR $2)
+            ORDER BY type, name, version DESC
+            "#,
+        )
+        .bind(asset_id)
+        .bind(include_history)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<AssetDocument>, sqlx::Error> {
+        sqlx::query_as::<_, AssetDocument>("SELECT * FROM asset_documents WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// All versions of a document, newest first
+    pub async fn list_versions(
+        &self,
+        document_group_id: Uuid,
+    ) -> Result<Vec<AssetDocument>, sqlx::Error> {
+        sqlx::query_as::<_, AssetDocument>(
+            "SELECT * FROM asset_documents WHERE document_group_id = $1 ORDER BY version DESC",
+        )
+        .bind(document_group_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn create(&self, document: &AssetDocument) -> Result<AssetDocument, sqlx::Error> {
+        sqlx::query_as::<_, AssetDocument>(
+            r#"
+            INSERT INTO asset_documents (
+                id, asset_id, document_group_id, version, is_current, name, type,
+                file_path, original_name, mime_type, size_bytes, expiry_date, notes, uploaded_by
+            )
+            VALUES ($1, $2, $3, $4, true, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            RETURNING *
+            "#,
+        )
+        .bind(document.id)
+        .bind(document.asset_id)
+        .bind(document.document_group_id)
+        .bind(document.version)
+        .bind(&document.name)
+        .bind(&document.type_)
+        .bind(&document.file_path)
+        .bind(&document.original_name)
+        .bind(&document.mime_type)
+        .bind(document.size_bytes)
+        .bind(document.expiry_date)
+        .bind(&document.notes)
+        .bind(document.uploaded_by)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Make `document` the current version in place of `previous_id`. Returns
+    /// `None` when `previous_id` is no longer the current version.
+    pub async fn replace(
+        &self,
+        previous_id: Uuid,
+        document: &AssetDocument,
+    ) -> Result<Option<AssetDocument>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let superseded = sqlx::query(
+            r#"
+            UPDATE asset_documents SET is_current = false, replaced_at = NOW()
+            WHERE id = $1 AND is_current
+            "#,
+        )
+        .bind(previous_id)
+        .execute(&mut *tx)
+        .await?;
+        if superseded.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        let created = sqlx::query_as::<_, AssetDocument>(
+            r#"
+            INSERT INTO asset_documents (
+                id, asset_id, document_group_id, version, is_current, name, type,
+                file_path, original_name, mime_type, size_bytes, expiry_date, notes, uploaded_by
+            )
+            VALUES ($1, $2, $3, $4, true, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            RETURNING *
+            "#,
+        )
+        .bind(document.id)
+        .bind(document.asset_id)
+        .bind(document.document_group_id)
+        .bind(document.version)
+        .bind(&document.name)
+        .bind(&document.type_)
+        .bind(&document.file_path)
+        .bind(&document.original_name)
+        .bind(&document.mime_type)
+        .bind(document.size_bytes)
+        .bind(document.expiry_date)
+        .bind(&document.notes)
+        .bind(document.uploaded_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE asset_documents SET replaced_by = $2 WHERE id = $1")
+            .bind(previous_id)
+            .bind(created.id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(Some(created))
+    }
+
+    /// Delete every version of a document; returns the storage keys to remove
+    pub async fn delete_group(&self, document_group_id: Uuid) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            "DELETE FROM asset_documents WHERE document_group_id = $1 RETURNING file_path",
+        )
+        .bind(document_group_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Current documents expiring within the window that have not been reminded
+    pub async fn list_expiring(
+        &self,
+        from: NaiveDate,
+        until: NaiveDate,
+        include_notified: bool,
+    ) -> Result<Vec<AssetDocument>, sqlx::Error> {
+        sqlx::query_as::<_, AssetDocument>(
+            r#"
+            SELECT * FROM asset_documents
+            WHERE is_current AND expiry_date BETWEEN $1 AND $2
+              AND ($3 OR expiry_notified_at IS NULL)
+            ORDER BY expiry_date
+            "#,
+        )
+        .bind(from)
+        .bind(until)
+        .bind(include_notified)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn mark_expiry_notified(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE asset_documents SET expiry_notified_at = NOW() WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
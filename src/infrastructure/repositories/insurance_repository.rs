@@ -0,0 +1,334 @@
+//! Insurance Repository
+
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::{
+    Insurance, InsuranceClaim, InsuranceCoverageLine, LapsedInsuranceAsset,
+};
+
+/// Assets that no longer need cover
+const UNINSURABLE_STATES: &str = "('disposed', 'retired', 'archived', 'lost_stolen')";
+
+#[derive(Clone)]
+pub struct InsuranceRepository {
+    pool: PgPool,
+}
+
+impl InsuranceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // ==================== POLICIES ====================
+
+    pub async fn list_by_asset(&self, asset_id: Uuid) -> Result<Vec<Insurance>, sqlx::Error> {
+        sqlx::query_as::<_, Insurance>(
+            "SELECT * FROM insurances WHERE asset_id = $1 ORDER BY end_date DESC, created_at DESC",
+        )
+        .bind(asset_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Insurance>, sqlx::Error> {
+        sqlx::query_as::<_, Insurance>("SELECT * FROM insurances WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    pub async fn create(&self, insurance: &Insurance) -> Result<Insurance, sqlx::Error> {
+        sqlx::query_as::<_, Insurance>(
+            r#"
+            INSERT INTO insurances (
+                id, asset_id, policy_number, insurance_provider, coverage_type,
+                coverage_amount, start_date, end_date, premium_amount, status, notes, created_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING *
+            "#,
+        )
+        .bind(insurance.id)
+        .bind(insurance.asset_id)
+        .bind(&insurance.policy_number)
+        .bind(&insurance.insurance_provider)
+        .bind(&insurance.coverage_type)
+        .bind(insurance.coverage_amount)
+        .bind(insurance.start_date)
+        .bind(insurance.end_date)
+        .bind(insurance.premium_amount)
+        .bind(&insurance.status)
+        .bind(&insurance.notes)
+        .bind(insurance.created_by)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn update(&self, insurance: &Insurance) -> Result<Insurance, sqlx::Error> {
+        sqlx::query_as::<_, Insurance>(
+            r#"
+            UPDATE insurances SET
+                policy_number = $2, insurance_provider = $3, coverage_type = $4,
+                coverage_amount = $5, start_date = $6, end_date = $7, premium_amount = $8,
+                status = $9, notes = $10, expiry_notified_at = $11
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(insurance.id)
+        .bind(&insurance.policy_number)
+        .bind(&insurance.insurance_provider)
+        .bind(&insurance.coverage_type)
+        .bind(insurance.coverage_amount)
+        .bind(insurance.start_date)
+        .bind(insurance.end_date)
+        .bind(insurance.premium_amount)
+        .bind(&insurance.status)
+        .bind(&insurance.notes)
+        .bind(insurance.expiry_notified_at)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn delete(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM insurances WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Active policies ending within the window that have not been renewed by a
+    /// follow-on policy of the same asset
+    pub async fn list_expiring(
+        &self,
+        from: NaiveDate,
+        until: NaiveDate,
+    ) -> Result<Vec<Insurance>, sqlx::Error> {
+        sqlx::query_as::<_, Insurance>(
+            r#"
+            SELECT i.* FROM insurances i
+            WHERE i.status = 'active' AND i.end_date BETWEEN $1 AND $2
+              AND NOT EXISTS (
+                  SELECT 1 FROM insurances n
+                  WHERE n.asset_id = i.asset_id AND n.id <> i.id AND n.status = 'active'
+                    AND n.start_date <= i.end_date + 1 AND n.end_date > i.end_date
+              )
+            ORDER BY i.end_date
+            "#,
+        )
+        .bind(from)
+        .bind(until)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn mark_expiry_notified(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE insurances SET expiry_notified_at = NOW() WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Move active policies whose end date has passed to expired
+    pub async fn expire_ended(&self, today: NaiveDate) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE insurances SET status = 'expired' WHERE status = 'active' AND end_date < $1",
+        )
+        .bind(today)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Flag insured assets without a policy in force; returns the newly flagged ones
+    pub async fn flag_lapsed(
+        &self,
+        today: NaiveDate,
+    ) -> Result<Vec<LapsedInsuranceAsset>, sqlx::Error> {
+        sqlx::query_as::<_, LapsedInsuranceAsset>(&format!(
+            r#"
+            UPDATE assets a SET insurance_lapsed_at = NOW()
+            WHERE a.insurance_lapsed_at IS NULL
+              AND a.status NOT IN {UNINSURABLE_STATES}
+              AND EXISTS (SELECT 1 FROM insurances i WHERE i.asset_id = a.id)
+              AND NOT EXISTS (
+                  SELECT 1 FROM insurances i
+                  WHERE i.asset_id = a.id AND i.status = 'active'
+                    AND $1 BETWEEN i.start_date AND i.end_date
+              )
+            RETURNING a.id AS asset_id, a.asset_code, a.name, a.insurance_lapsed_at,
+                (SELECT MAX(end_date) FROM insurances i WHERE i.asset_id = a.id) AS last_policy_end
+            "#
+        ))
+        .bind(today)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Clear the lapse flag of assets (or one asset) covered again
+    pub async fn clear_lapsed(
+        &self,
+        asset_id: Option<Uuid>,
+        today: NaiveDate,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE assets a SET insurance_lapsed_at = NULL
+            WHERE a.insurance_lapsed_at IS NOT NULL
+              AND ($1::uuid IS NULL OR a.id = $1)
+              AND EXISTS (
+                  SELECT 1 FROM insurances i
+                  WHERE i.asset_id = a.id AND i.status = 'active'
+                    AND $2 BETWEEN i.start_date AND i.end_date
+              )
+            "#,
+        )
+        .bind(asset_id)
+        .bind(today)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn list_lapsed(&self) -> Result<Vec<LapsedInsuranceAsset>, sqlx::Error> {
+        sqlx::query_as::<_, LapsedInsuranceAsset>(
+            r#"
+            SELECT a.id AS asset_id, a.asset_code, a.name, a.insurance_lapsed_at,
+                (SELECT MAX(end_date) FROM insurances i WHERE i.asset_id = a.id) AS last_policy_end
+            FROM assets a
+            WHERE a.insurance_lapsed_at IS NOT NULL
+            ORDER BY a.insurance_lapsed_at
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Insured value of policies in force against book value, per category
+    pub async fn coverage_report(
+        &self,
+        as_of: NaiveDate,
+    ) -> Result<Vec<InsuranceCoverageLine>, sqlx::Error> {
+        sqlx::query_as::<_, InsuranceCoverageLine>(&format!(
+            r#"
+            WITH book AS (
+                SELECT DISTINCT ON (asset_id) asset_id, closing_value
+                FROM depreciation_schedules
+                WHERE period_start <= $1
+                ORDER BY asset_id, period_start DESC
+            ),
+            cover AS (
+                SELECT asset_id, SUM(COALESCE(coverage_amount, 0)) AS insured
+                FROM insurances
+                WHERE status <> 'cancelled' AND $1 BETWEEN start_date AND end_date
+                GROUP BY asset_id
+            ),
+            lines AS (
+                SELECT a.category_id, cover.asset_id IS NOT NULL AS insured,
+                    COALESCE(cover.insured, 0) AS insured_value,
+                    COALESCE(book.closing_value, a.purchase_price, 0) AS book_value
+                FROM assets a
+                LEFT JOIN book ON book.asset_id = a.id
+                LEFT JOIN cover ON cover.asset_id = a.id
+                WHERE a.status NOT IN {UNINSURABLE_STATES}
+            )
+            SELECT c.id AS category_id, c.name AS category_name,
+                COUNT(*) AS asset_count,
+                COUNT(*) FILTER (WHERE l.insured) AS insured_asset_count,
+                COALESCE(SUM(l.insured_value), 0) AS insured_value,
+                COALESCE(SUM(l.book_value), 0) AS book_value,
+                COALESCE(SUM(GREATEST(l.book_value - l.insured_value, 0)), 0) AS underinsured_value
+            FROM lines l
+            JOIN categories c ON c.id = l.category_id
+            GROUP BY c.id, c.name
+            ORDER BY c.name
+            "#
+        ))
+        .bind(as_of)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    // ==================== CLAIMS ====================
+
+    pub async fn list_claims(
+        &self,
+        insurance_id: Uuid,
+    ) -> Result<Vec<InsuranceClaim>, sqlx::Error> {
+        sqlx::query_as::<_, InsuranceClaim>(
+            "SELECT * FROM insurance_claims WHERE insurance_id = $1 ORDER BY incident_date DESC, created_at DESC",
+        )
+        .bind(insurance_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn find_claim(&self, id: Uuid) -> Result<Option<InsuranceClaim>, sqlx::Error> {
+        sqlx::query_as::<_, InsuranceClaim>("SELECT * FROM insurance_claims WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    pub async fn create_claim(
+        &self,
+        claim: &InsuranceClaim,
+    ) -> Result<InsuranceClaim, sqlx::Error> {
+        sqlx::query_as::<_, InsuranceClaim>(
+            r#"
+            INSERT INTO insurance_claims (
+                id, insurance_id, asset_id, claim_number, incident_date,
+                work_order_id, lifecycle_history_id, claimed_amount, status,
+                description, notes, created_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING *
+            "#,
+        )
+        .bind(claim.id)
+        .bind(claim.insurance_id)
+        .bind(claim.asset_id)
+        .bind(&claim.claim_number)
+        .bind(claim.incident_date)
+        .bind(claim.work_order_id)
+        .bind(claim.lifecycle_history_id)
+        .bind(claim.claimed_amount)
+        .bind(&claim.status)
+        .bind(&claim.description)
+        .bind(&claim.notes)
+        .bind(claim.created_by)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Persist a status change; guarded on the previous status
+    pub async fn update_claim_status(
+        &self,
+        claim: &InsuranceClaim,
+        from_status: &str,
+    ) -> Result<Option<InsuranceClaim>, sqlx::Error> {
+        sqlx::query_as::<_, InsuranceClaim>(
+            r#"
+            UPDATE insurance_claims SET
+                status = $2, approved_amount = $3, payout_amount = $4, payout_date = $5,
+                claim_number = $6, notes = $7
+            WHERE id = $1 AND status = $8
+            RETURNING *
+            "#,
+        )
+        .bind(claim.id)
+        .bind(&claim.status)
+        .bind(claim.approved_amount)
+        .bind(claim.payout_amount)
+        .bind(claim.payout_date)
+        .bind(&claim.claim_number)
+        .bind(&claim.notes)
+        .bind(from_status)
+        .fetch_optional(&self.pool)
+        .await
+    }
+}
@@ -2,11 +2,13 @@
 //!
 //! Database operations for asset lifecycle history and transitions.
 
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
 use crate::domain::entities::{AssetState, LifecycleHistory};
 use crate::domain::errors::{DomainError, DomainResult};
+use crate::domain::events::{AssetStateChanged, EventEnvelope};
+use crate::infrastructure::repositories::OutboxRepository;
 
 #[derive(Clone)]
 pub struct LifecycleRepository {
@@ -18,6 +20,76 @@ impl LifecycleRepository {
         Self { pool }
     }
 
+    /// Move an asset to a new state and record it in history, together with
+    /// the state change event
+    pub async fn transition(
+        &self,
+        asset_id: Uuid,
+        from_state: &AssetState,
+        to_state: &AssetState,
+        reason: Option<String>,
+        performed_by: Option<Uuid>,
+        event: EventEnvelope<AssetStateChanged>,
+    ) -> DomainResult<LifecycleHistory> {
+        let db_error = |e: sqlx::Error| DomainError::Database(e.to_string());
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let record = Self::transition_in(
+            &mut tx,
+            asset_id,
+            from_state,
+            to_state,
+            reason,
+            performed_by,
+            event,
+        )
+        .await?;
+        tx.commit().await.map_err(db_error)?;
+        Ok(record)
+    }
+
+    /// `transition` inside the caller's transaction
+    pub async fn transition_in(
+        tx: &mut Transaction<'_, Postgres>,
+        asset_id: Uuid,
+        from_state: &AssetState,
+        to_state: &AssetState,
+        reason: Option<String>,
+        performed_by: Option<Uuid>,
+        event: EventEnvelope<AssetStateChanged>,
+    ) -> DomainResult<LifecycleHistory> {
+        let db_error = |e: sqlx::Error| DomainError::Database(e.to_string());
+
+        sqlx::query("UPDATE assets SET status = $1, updated_at = NOW() WHERE id = $2")
+            .bind(to_state.as_str())
+            .bind(asset_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(db_error)?;
+
+        let record = sqlx::query_as::<_, LifecycleHistory>(
+            r#"
+            INSERT INTO asset_lifecycle_history (id, asset_id, from_state, to_state, reason, performed_by)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, asset_id, from_state, to_state, reason, performed_by, metadata, created_at
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(asset_id)
+        .bind(from_state.as_str())
+        .bind(to_state.as_str())
+        .bind(reason)
+        .bind(performed_by)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(db_error)?;
+
+        OutboxRepository::append(tx, event)
+            .await
+            .map_err(db_error)?;
+
+        Ok(record)
+    }
+
     /// Record a state transition in history
     pub async fn record_transition(
         &self,
@@ -69,6 +141,15 @@ impl LifecycleRepository {
         Ok(records)
     }
 
+    /// Find one history entry
+    pub async fn find_history_entry(&self, id: Uuid) -> DomainResult<Option<LifecycleHistory>> {
+        sqlx::query_as::<_, LifecycleHistory>("SELECT * FROM asset_lifecycle_history WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::Database(e.to_string()))
+    }
+
     /// Update asset status in the assets table
     pub async fn update_asset_status(&self, asset_id: Uuid, new_status: &str) -> DomainResult<()> {
         sqlx::query!(
@@ -84,6 +165,31 @@ impl LifecycleRepository {
     }
 
     /// Get current asset status
+    /// Current status of an asset, locked until the end of `tx`
+    pub async fn lock_asset_status(
+        tx: &mut Transaction<'_, Postgres>,
+        asset_id: Uuid,
+    ) -> DomainResult<String> {
+        let status: Option<String> =
+            sqlx::query_scalar("SELECT status FROM assets WHERE id = $1 FOR UPDATE")
+                .bind(asset_id)
+                .fetch_optional(&mut **tx)
+                .await
+                .map_err(|e| DomainError::Database(e.to_string()))?;
+
+        status.ok_or_else(|| DomainError::not_found("Asset", asset_id))
+    }
+
+    /// Category of an asset, which picks category-specific approval workflows
+    pub async fn get_asset_category(&self, asset_id: Uuid) -> DomainResult<Uuid> {
+        sqlx::query_scalar::<_, Uuid>("SELECT category_id FROM assets WHERE id = $1")
+            .bind(asset_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::Database(e.to_string()))?
+            .ok_or_else(|| DomainError::not_found("Asset", asset_id))
+    }
+
     pub async fn get_asset_status(&self, asset_id: Uuid) -> DomainResult<String> {
         let result = sqlx::query_scalar!(r#"SELECT status FROM assets WHERE id = $1"#, asset_id)
             .fetch_one(&self.pool)
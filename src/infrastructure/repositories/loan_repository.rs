@@ -4,7 +4,9 @@ use chrono::NaiveDate;
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::domain::entities::Loan;
+use crate::domain::entities::{DataScope, Loan};
+use crate::domain::events::{EventEnvelope, LoanCheckedOut};
+use crate::infrastructure::repositories::OutboxRepository;
 
 #[derive(Clone)]
 pub struct LoanRepository {
@@ -48,7 +50,13 @@ impl LoanRepository {
         .await
     }
 
-    pub async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Loan>, sqlx::Error> {
+    pub async fn list(
+        &self,
+        limit: i64,
+        offset: i64,
+        org_scope: Option<Uuid>,
+        scope: &DataScope,
+    ) -> Result<Vec<Loan>, sqlx::Error> {
         sqlx::query_as::<_, Loan>(
             r#"
             SELECT al.*, u.name as borrower_name, e.name as employee_name, a.name as asset_name
@@ -56,17 +64,42 @@ impl LoanRepository {
             LEFT JOIN users u ON al.borrower_id = u.id
             LEFT JOIN employees e ON al.employee_id = e.id
             LEFT JOIN assets a ON al.asset_id = a.id
+            WHERE ($3::uuid IS NULL OR asset_in_organization(al.asset_id, $3))
+              AND asset_in_data_scope(al.asset_id, $4, ARRAY[al.borrower_id])
             ORDER BY al.created_at DESC
             LIMIT $1 OFFSET $2
             "#,
         )
         .bind(limit)
         .bind(offset)
+        .bind(org_scope)
+        .bind(scope)
         .fetch_all(&self.pool)
         .await
     }
 
-    pub async fn list_by_borrower(&self, borrower_id: Uuid) -> Result<Vec<Loan>, sqlx::Error> {
+    /// Whether a loan is within the data scope of a user
+    pub async fn in_data_scope(&self, id: Uuid, scope: &DataScope) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM asset_loans
+                WHERE id = $1 AND asset_in_data_scope(asset_id, $2, ARRAY[borrower_id])
+            )
+            "#,
+        )
+        .bind(id)
+        .bind(scope)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn list_by_borrower(
+        &self,
+        borrower_id: Uuid,
+        org_scope: Option<Uuid>,
+        scope: &DataScope,
+    ) -> Result<Vec<Loan>, sqlx::Error> {
         sqlx::query_as::<_, Loan>(
             r#"
             SELECT al.*, u.name as borrower_name, e.name as employee_name, a.name as asset_name
@@ -75,10 +108,14 @@ impl LoanRepository {
             LEFT JOIN employees e ON al.employee_id = e.id
             LEFT JOIN assets a ON al.asset_id = a.id
             WHERE al.borrower_id = $1
+              AND ($2::uuid IS NULL OR asset_in_organization(al.asset_id, $2))
+              AND asset_in_data_scope(al.asset_id, $3, ARRAY[al.borrower_id])
             ORDER BY al.created_at DESC
             "#,
         )
         .bind(borrower_id)
+        .bind(org_scope)
+        .bind(scope)
         .fetch_all(&self.pool)
         .await
     }
@@ -117,7 +154,11 @@ impl LoanRepository {
         .await
     }
 
-    pub async fn list_overdue(&self) -> Result<Vec<Loan>, sqlx::Error> {
+    pub async fn list_overdue(
+        &self,
+        org_scope: Option<Uuid>,
+        scope: &DataScope,
+    ) -> Result<Vec<Loan>, sqlx::Error> {
         sqlx::query_as::<_, Loan>(
             r#"
             SELECT al.*, u.name as borrower_name, e.name as employee_name, a.name as asset_name
@@ -128,9 +169,33 @@ impl LoanRepository {
             WHERE al.expected_return_date < CURRENT_DATE 
               AND al.actual_return_date IS NULL
               AND al.status NOT IN ('returned', 'lost', 'rejected')
+              AND ($1::uuid IS NULL OR asset_in_organization(al.asset_id, $1))
+              AND asset_in_data_scope(al.asset_id, $2, ARRAY[al.borrower_id])
             ORDER BY al.expected_return_date
             "#,
         )
+        .bind(org_scope)
+        .bind(scope)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Open loans expected back on `date`
+    pub async fn list_due_on(&self, date: NaiveDate) -> Result<Vec<Loan>, sqlx::Error> {
+        sqlx::query_as::<_, Loan>(
+            r#"
+            SELECT al.*, u.name as borrower_name, e.name as employee_name, a.name as asset_name
+            FROM asset_loans al
+            LEFT JOIN users u ON al.borrower_id = u.id
+            LEFT JOIN employees e ON al.employee_id = e.id
+            LEFT JOIN assets a ON al.asset_id = a.id
+            WHERE al.expected_return_date = $1
+              AND al.actual_return_date IS NULL
+              AND al.status NOT IN ('returned', 'lost', 'rejected')
+            ORDER BY al.loan_number
+            "#,
+        )
+        .bind(date)
         .fetch_all(&self.pool)
         .await
     }
@@ -203,6 +268,18 @@ impl LoanRepository {
     }
 
     pub async fn approve(&self, id: Uuid, approver_id: Uuid) -> Result<bool, sqlx::Error> {
+        Self::approve_with(&self.pool, id, approver_id).await
+    }
+
+    /// Approve a requested loan on a given connection or transaction
+    pub(crate) async fn approve_with<'e, E>(
+        executor: E,
+        id: Uuid,
+        approver_id: Uuid,
+    ) -> Result<bool, sqlx::Error>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
         let result = sqlx::query(
             r#"
             UPDATE asset_loans 
@@ -212,7 +289,7 @@ impl LoanRepository {
         )
         .bind(id)
         .bind(approver_id)
-        .execute(&self.pool)
+        .execute(executor)
         .await?;
         Ok(result.rows_affected() > 0)
     }
@@ -232,12 +309,15 @@ impl LoanRepository {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Hand out an approved loan; the checkout event is stored with it
     pub async fn checkout(
         &self,
         id: Uuid,
         checked_out_by: Uuid,
         condition_before: &str,
+        event: EventEnvelope<LoanCheckedOut>,
     ) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
         let result = sqlx::query(
             r#"
             UPDATE asset_loans 
@@ -249,9 +329,16 @@ impl LoanRepository {
         .bind(id)
         .bind(checked_out_by)
         .bind(condition_before)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
-        Ok(result.rows_affected() > 0)
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        OutboxRepository::append(&mut tx, event).await?;
+
+        tx.commit().await?;
+        Ok(true)
     }
 
     pub async fn checkin(
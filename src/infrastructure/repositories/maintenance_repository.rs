@@ -3,7 +3,7 @@
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::domain::entities::{MaintenanceRecord, MaintenanceSummary};
+use crate::domain::entities::{DataScope, MaintenanceRecord, MaintenanceSummary};
 
 #[derive(Clone)]
 pub struct MaintenanceRepository {
@@ -50,6 +50,8 @@ impl MaintenanceRepository {
         &self,
         start_date: chrono::NaiveDate,
         end_date: chrono::NaiveDate,
+        org_scope: Option<Uuid>,
+        scope: &DataScope,
     ) -> Result<Vec<MaintenanceSummary>, sqlx::Error> {
         sqlx::query_as::<_, MaintenanceSummary>(
             r#"
@@ -61,11 +63,15 @@ impl MaintenanceRepository {
             LEFT JOIN assets a ON m.asset_id = a.id
             LEFT JOIN maintenance_types t ON m.maintenance_type_id = t.id
             WHERE m.scheduled_date BETWEEN $1 AND $2
+              AND ($3::uuid IS NULL OR asset_in_organization(m.asset_id, $3))
+              AND asset_in_data_scope(m.asset_id, $4, ARRAY[m.assigned_to, m.created_by])
             ORDER BY m.scheduled_date DESC
             "#,
         )
         .bind(start_date)
         .bind(end_date)
+        .bind(org_scope)
+        .bind(scope)
         .fetch_all(&self.pool)
         .await
     }
@@ -2,44 +2,76 @@
 //!
 //! Data access layer implementations.
 
+pub mod api_key_repository;
 pub mod approval_repository;
+pub mod approval_workflow_repository;
 pub mod asset_repository;
 pub mod audit_repository;
+pub mod budget_repository;
 pub mod category_repository;
 pub mod client_repository;
 pub mod conversion_repository; // Added this line based on the example
+pub mod depreciation_repository;
+pub mod document_number_repository;
+pub mod document_repository;
 pub mod employee_repository;
+pub mod insurance_repository;
 pub mod lifecycle_repository;
 pub mod loan_repository;
 pub mod location_repository;
 pub mod maintenance_repository;
 pub mod notification_repository;
+pub mod organization_repository;
+pub mod outbox_repository;
+pub mod password_repository;
+pub mod preventive_schedule_repository;
 pub mod rbac_repository;
 pub mod rental_repository;
 pub mod sensor_repository;
+pub mod session_repository;
+pub mod specification_repository;
 pub mod timesheet_repository;
+pub mod two_factor_repository;
 pub mod user_repository;
+pub mod valuation_repository;
 pub mod vendor_repository;
+pub mod webhook_repository;
 pub mod work_order_repository;
 
+pub use api_key_repository::*;
 pub use approval_repository::*;
+pub use approval_workflow_repository::*;
 pub use asset_repository::*;
 pub use audit_repository::*;
+pub use budget_repository::*;
 pub use category_repository::*;
 pub use client_repository::*;
 pub use conversion_repository::*;
+pub use depreciation_repository::*;
+pub use document_number_repository::*;
+pub use document_repository::*;
 pub use employee_repository::*;
+pub use insurance_repository::*;
 pub use lifecycle_repository::*;
 pub use loan_repository::*;
 pub use location_repository::*;
 pub use maintenance_repository::*;
 pub use notification_repository::*;
+pub use organization_repository::*;
+pub use outbox_repository::*;
+pub use password_repository::*;
+pub use preventive_schedule_repository::*;
 pub use rbac_repository::*;
 pub use rental_repository::*;
 pub use sensor_repository::*;
+pub use session_repository::*;
+pub use specification_repository::*;
 pub use timesheet_repository::*;
+pub use two_factor_repository::*;
 pub use user_repository::*;
+pub use valuation_repository::*;
 pub use vendor_repository::*;
+pub use webhook_repository::*;
 pub use work_order_repository::*;
 
 /// Base repository trait
@@ -1,8 +1,11 @@
 //! Notification Repository
 
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
 use uuid::Uuid;
 
+use crate::domain::entities::{NotificationDelivery, NotificationPreference, NotificationTemplate};
+
 /// Notification model
 #[derive(Debug, Clone, sqlx::FromRow, serde::Serialize)]
 pub struct Notification {
@@ -130,4 +133,379 @@ impl NotificationRepository {
             .await?;
         Ok(result.rows_affected() > 0)
     }
+
+    // ==================== TEMPLATES ====================
+
+    pub async fn list_templates(&self) -> Result<Vec<NotificationTemplate>, sqlx::Error> {
+        sqlx::query_as::<_, NotificationTemplate>(
+            "SELECT * FROM notification_templates ORDER BY event_type, code",
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn find_template(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<NotificationTemplate>, sqlx::Error> {
+        sqlx::query_as::<_, NotificationTemplate>(
+            "SELECT * FROM notification_templates WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    pub async fn find_active_templates_by_event(
+        &self,
+        event_type: &str,
+    ) -> Result<Vec<NotificationTemplate>, sqlx::Error> {
+        sqlx::query_as::<_, NotificationTemplate>(
+            r#"
+            SELECT * FROM notification_templates
+            WHERE event_type = $1 AND COALESCE(is_active, true) = true
+            ORDER BY code
+            "#,
+        )
+        .bind(event_type)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn create_template(
+        &self,
+        template: &NotificationTemplate,
+    ) -> Result<NotificationTemplate, sqlx::Error> {
+        sqlx::query_as::<_, NotificationTemplate>(
+            r#"
+            INSERT INTO notification_templates (
+                id, code, name, subject_template, body_template, channels, event_type, is_active
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            "#,
+        )
+        .bind(template.id)
+        .bind(&template.code)
+        .bind(&template.name)
+        .bind(&template.subject_template)
+        .bind(&template.body_template)
+        .bind(&template.channels)
+        .bind(&template.event_type)
+        .bind(template.is_active)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn update_template(
+        &self,
+        template: &NotificationTemplate,
+    ) -> Result<NotificationTemplate, sqlx::Error> {
+        sqlx::query_as::<_, NotificationTemplate>(
+            r#"
+            UPDATE notification_templates SET
+                code = $2,
+                name = $3,
+                subject_template = $4,
+                body_template = $5,
+                channels = $6,
+                event_type = $7,
+                is_active = $8
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(template.id)
+        .bind(&template.code)
+        .bind(&template.name)
+        .bind(&template.subject_template)
+        .bind(&template.body_template)
+        .bind(&template.channels)
+        .bind(&template.event_type)
+        .bind(template.is_active)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn delete_template(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM notification_templates WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ==================== PREFERENCES ====================
+
+    /// Preference for a template, falling back to the event-wide preference
+    pub async fn find_preference(
+        &self,
+        user_id: Uuid,
+        template_id: Option<Uuid>,
+        event_type: &str,
+    ) -> Result<Option<NotificationPreference>, sqlx::Error> {
+        sqlx::query_as::<_, NotificationPreference>(
+            r#"
+            SELECT * FROM notification_preferences
+            WHERE user_id = $1 AND (template_id = $2 OR event_type = $3)
+            ORDER BY template_id IS NULL
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(template_id)
+        .bind(event_type)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    pub async fn list_preferences(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<NotificationPreference>, sqlx::Error> {
+        sqlx::query_as::<_, NotificationPreference>(
+            "SELECT * FROM notification_preferences WHERE user_id = $1 ORDER BY event_type",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Insert or replace a user's preference for an event type
+    pub async fn upsert_preference(
+        &self,
+        pref: &NotificationPreference,
+    ) -> Result<NotificationPreference, sqlx::Error> {
+        sqlx::query_as::<_, NotificationPreference>(
+            r#"
+            INSERT INTO notification_preferences (
+                user_id, event_type, email_enabled, push_enabled, sms_enabled,
+                in_app_enabled, webhook_enabled, digest_frequency
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (user_id, event_type) DO UPDATE SET
+                email_enabled = EXCLUDED.email_enabled,
+                push_enabled = EXCLUDED.push_enabled,
+                sms_enabled = EXCLUDED.sms_enabled,
+                in_app_enabled = EXCLUDED.in_app_enabled,
+                webhook_enabled = EXCLUDED.webhook_enabled,
+                digest_frequency = EXCLUDED.digest_frequency
+            RETURNING *
+            "#,
+        )
+        .bind(pref.user_id)
+        .bind(&pref.event_type)
+        .bind(pref.email_enabled)
+        .bind(pref.push_enabled)
+        .bind(pref.sms_enabled)
+        .bind(pref.in_app_enabled)
+        .bind(pref.webhook_enabled)
+        .bind(&pref.digest_frequency)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn find_user_email(&self, user_id: Uuid) -> Result<Option<String>, sqlx::Error> {
+        let row: Option<(Option<String>,)> =
+            sqlx::query_as("SELECT email FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.and_then(|r| r.0))
+    }
+
+    // ==================== DELIVERIES ====================
+
+    pub async fn begin(&self) -> Result<Transaction<'static, Postgres>, sqlx::Error> {
+        self.pool.begin().await
+    }
+
+    pub async fn create_delivery(
+        &self,
+        delivery: &NotificationDelivery,
+    ) -> Result<NotificationDelivery, sqlx::Error> {
+        Self::create_delivery_with(&self.pool, delivery).await
+    }
+
+    /// Log a delivery on a given connection or transaction
+    pub(crate) async fn create_delivery_with<'e, E>(
+        executor: E,
+        delivery: &NotificationDelivery,
+    ) -> Result<NotificationDelivery, sqlx::Error>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, NotificationDelivery>(
+            r#"
+            INSERT INTO notification_deliveries (
+                id, template_id, user_id, event_type, channel, recipient, subject, body,
+                payload, entity_type, entity_id, asset_id, status, max_attempts,
+                digest_frequency
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+            RETURNING *
+            "#,
+        )
+        .bind(delivery.id)
+        .bind(delivery.template_id)
+        .bind(delivery.user_id)
+        .bind(&delivery.event_type)
+        .bind(&delivery.channel)
+        .bind(&delivery.recipient)
+        .bind(&delivery.subject)
+        .bind(&delivery.body)
+        .bind(&delivery.payload)
+        .bind(&delivery.entity_type)
+        .bind(delivery.entity_id)
+        .bind(delivery.asset_id)
+        .bind(&delivery.status)
+        .bind(delivery.max_attempts)
+        .bind(&delivery.digest_frequency)
+        .fetch_one(executor)
+        .await
+    }
+
+    pub async fn find_delivery(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<NotificationDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, NotificationDelivery>(
+            "SELECT * FROM notification_deliveries WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Store the result of a delivery attempt
+    pub async fn record_delivery_attempt(
+        &self,
+        id: Uuid,
+        status: &str,
+        notification_id: Option<Uuid>,
+        error: Option<&str>,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<NotificationDelivery, sqlx::Error> {
+        sqlx::query_as::<_, NotificationDelivery>(
+            r#"
+            UPDATE notification_deliveries SET
+                status = $2,
+                notification_id = COALESCE($3, notification_id),
+                attempts = attempts + CASE WHEN $2 = 'skipped' THEN 0 ELSE 1 END,
+                last_error = $4,
+                next_attempt_at = $5,
+                sent_at = CASE WHEN $2 = 'sent' THEN NOW() ELSE sent_at END
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(notification_id)
+        .bind(error)
+        .bind(next_attempt_at)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Deliveries whose retry time has come
+    pub async fn list_due_deliveries(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<NotificationDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, NotificationDelivery>(
+            r#"
+            SELECT * FROM notification_deliveries
+            WHERE status = 'retrying' AND next_attempt_at <= NOW()
+            ORDER BY next_attempt_at
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Deliveries held for a digest of the given frequency, per user and channel
+    pub async fn list_queued_deliveries(
+        &self,
+        frequency: &str,
+    ) -> Result<Vec<NotificationDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, NotificationDelivery>(
+            r#"
+            SELECT * FROM notification_deliveries
+            WHERE status = 'queued' AND digest_frequency = $1
+            ORDER BY user_id, channel, created_at
+            "#,
+        )
+        .bind(frequency)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Lock the listed items that are still queued; items another run holds or
+    /// has already digested are skipped
+    pub async fn claim_queued(
+        tx: &mut Transaction<'_, Postgres>,
+        ids: &[Uuid],
+    ) -> Result<Vec<NotificationDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, NotificationDelivery>(
+            r#"
+            SELECT * FROM notification_deliveries
+            WHERE id = ANY($1) AND status = 'queued'
+            ORDER BY created_at
+            FOR UPDATE SKIP LOCKED
+            "#,
+        )
+        .bind(ids)
+        .fetch_all(&mut **tx)
+        .await
+    }
+
+    /// Mark claimed items as included in a digest delivery
+    pub async fn mark_digested(
+        tx: &mut Transaction<'_, Postgres>,
+        digest_id: Uuid,
+        ids: &[Uuid],
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE notification_deliveries
+            SET status = 'digested', digest_id = $1
+            WHERE id = ANY($2) AND status = 'queued'
+            "#,
+        )
+        .bind(digest_id)
+        .bind(ids)
+        .execute(&mut **tx)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn list_deliveries(
+        &self,
+        status: Option<&str>,
+        channel: Option<&str>,
+        user_id: Option<Uuid>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<NotificationDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, NotificationDelivery>(
+            r#"
+            SELECT * FROM notification_deliveries
+            WHERE ($1::varchar IS NULL OR status = $1)
+              AND ($2::varchar IS NULL OR channel = $2)
+              AND ($3::uuid IS NULL OR user_id = $3)
+            ORDER BY created_at DESC
+            LIMIT $4 OFFSET $5
+            "#,
+        )
+        .bind(status)
+        .bind(channel)
+        .bind(user_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+    }
 }
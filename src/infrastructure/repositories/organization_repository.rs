@@ -0,0 +1,250 @@
+//! Organization Repository
+//!
+//! Organization hierarchy, memberships and the subtree checks used to scope
+//! tenant data.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::{Organization, OrganizationMember, TenantResource};
+
+#[derive(Clone)]
+pub struct OrganizationRepository {
+    pool: PgPool,
+}
+
+impl OrganizationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // ==================== HIERARCHY ====================
+
+    /// Organizations in the subtree of `root` (all when `None`)
+    pub async fn list(&self, root: Option<Uuid>) -> Result<Vec<Organization>, sqlx::Error> {
+        sqlx::query_as::<_, Organization>(
+            r#"
+            SELECT * FROM organizations
+            WHERE $1::uuid IS NULL OR id IN (SELECT organization_subtree($1))
+            ORDER BY code
+            "#,
+        )
+        .bind(root)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Organization>, sqlx::Error> {
+        sqlx::query_as::<_, Organization>("SELECT * FROM organizations WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    pub async fn find_by_code(&self, code: &str) -> Result<Option<Organization>, sqlx::Error> {
+        sqlx::query_as::<_, Organization>("SELECT * FROM organizations WHERE code = $1")
+            .bind(code)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    pub async fn create(&self, org: &Organization) -> Result<Organization, sqlx::Error> {
+        sqlx::query_as::<_, Organization>(
+            r#"
+            INSERT INTO organizations (
+                id, code, name, parent_id, org_type, cost_center, budget,
+                manager_id, is_active, metadata
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING *
+            "#,
+        )
+        .bind(org.id)
+        .bind(&org.code)
+        .bind(&org.name)
+        .bind(org.parent_id)
+        .bind(&org.org_type)
+        .bind(&org.cost_center)
+        .bind(org.budget)
+        .bind(org.manager_id)
+        .bind(org.is_active)
+        .bind(&org.metadata)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn update(&self, org: &Organization) -> Result<Organization, sqlx::Error> {
+        sqlx::query_as::<_, Organization>(
+            r#"
+            UPDATE organizations SET
+                code = $2, name = $3, parent_id = $4, org_type = $5, cost_center = $6,
+                budget = $7, manager_id = $8, is_active = $9, metadata = $10
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(org.id)
+        .bind(&org.code)
+        .bind(&org.name)
+        .bind(org.parent_id)
+        .bind(&org.org_type)
+        .bind(&org.cost_center)
+        .bind(org.budget)
+        .bind(org.manager_id)
+        .bind(org.is_active)
+        .bind(&org.metadata)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Delete an organization that has no children, assets or users
+    pub async fn delete_unused(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM organizations o
+            WHERE o.id = $1
+              AND NOT EXISTS (SELECT 1 FROM organizations c WHERE c.parent_id = o.id)
+              AND NOT EXISTS (SELECT 1 FROM assets a WHERE a.organization_id = o.id)
+              AND NOT EXISTS (SELECT 1 FROM users u WHERE u.organization_id = o.id)
+              AND NOT EXISTS (SELECT 1 FROM departments d WHERE d.organization_id = o.id)
+              AND NOT EXISTS (SELECT 1 FROM budget_lines b WHERE b.organization_id = o.id)
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Whether `id` is `root` or one of its descendants
+    pub async fn in_subtree(&self, root: Uuid, id: Uuid) -> Result<bool, sqlx::Error> {
+        let (found,): (bool,) = sqlx::query_as("SELECT $2 IN (SELECT organization_subtree($1))")
+            .bind(root)
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(found)
+    }
+
+    // ==================== MEMBERSHIP ====================
+
+    /// Organizations a user is a member of
+    pub async fn list_for_user(&self, user_id: Uuid) -> Result<Vec<Organization>, sqlx::Error> {
+        sqlx::query_as::<_, Organization>(
+            r#"
+            SELECT o.* FROM organizations o
+            JOIN user_organizations uo ON uo.organization_id = o.id
+            WHERE uo.user_id = $1
+            ORDER BY o.code
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Whether a user is a member of `id` or of one of its ancestors
+    pub async fn user_can_access(&self, user_id: Uuid, id: Uuid) -> Result<bool, sqlx::Error> {
+        let (found,): (bool,) = sqlx::query_as(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM user_organizations uo
+                WHERE uo.user_id = $1 AND $2 IN (SELECT organization_subtree(uo.organization_id))
+            )
+            "#,
+        )
+        .bind(user_id)
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(found)
+    }
+
+    pub async fn list_members(&self, id: Uuid) -> Result<Vec<OrganizationMember>, sqlx::Error> {
+        sqlx::query_as::<_, OrganizationMember>(
+            r#"
+            SELECT uo.user_id, uo.organization_id, u.name, u.email,
+                   u.organization_id IS NOT DISTINCT FROM uo.organization_id AS is_home,
+                   uo.created_at
+            FROM user_organizations uo
+            JOIN users u ON u.id = uo.user_id
+            WHERE uo.organization_id = $1
+            ORDER BY u.name
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Add a membership; returns false if it already existed
+    pub async fn add_member(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        created_by: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO user_organizations (user_id, organization_id, created_by)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(user_id)
+        .bind(id)
+        .bind(created_by)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Remove a membership other than the user's home organization
+    pub async fn remove_member(&self, id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM user_organizations uo
+            USING users u
+            WHERE uo.organization_id = $1 AND uo.user_id = $2
+              AND u.id = uo.user_id AND u.organization_id IS DISTINCT FROM uo.organization_id
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ==================== TENANT SCOPE ====================
+
+    /// Whether a tenant resource lies in the subtree of `root`; `None` if it
+    /// does not exist
+    pub async fn resource_in_scope(
+        &self,
+        resource: TenantResource,
+        id: Uuid,
+        root: Uuid,
+    ) -> Result<Option<bool>, sqlx::Error> {
+        let sql = match resource {
+            TenantResource::Asset => {
+                "SELECT organization_id IN (SELECT organization_subtree($2)) FROM assets WHERE id = $1"
+            }
+            TenantResource::Loan => {
+                "SELECT asset_in_organization(asset_id, $2) FROM asset_loans WHERE id = $1"
+            }
+            TenantResource::Rental => {
+                "SELECT asset_in_organization(asset_id, $2) FROM rentals WHERE id = $1"
+            }
+            TenantResource::WorkOrder => {
+                "SELECT asset_in_organization(asset_id, $2) FROM maintenance_work_orders WHERE id = $1"
+            }
+        };
+        let row: Option<(Option<bool>,)> = sqlx::query_as(sql)
+            .bind(id)
+            .bind(root)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.map(|(visible,)| visible.unwrap_or(false)))
+    }
+}
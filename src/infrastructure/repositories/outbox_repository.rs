@@ -0,0 +1,137 @@
+//! Outbox Repository
+//!
+//! Domain events written with the change they describe, and their delivery
+//! state. Relays claim pending events under a lease, so an event whose relay
+//! dies mid-delivery is picked up again once the lease runs out.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::domain::entities::OutboxEvent;
+use crate::domain::events::EventEnvelope;
+use crate::infrastructure::messaging::EventContext;
+
+#[derive(Clone)]
+pub struct OutboxRepository {
+    pool: PgPool,
+}
+
+impl OutboxRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Store an event in the transaction of its change. Correlation and
+    /// causation ids, and the user, come from the request when not set.
+    pub async fn append<T: Serialize>(
+        tx: &mut Transaction<'_, Postgres>,
+        mut envelope: EventEnvelope<T>,
+    ) -> Result<(), sqlx::Error> {
+        EventContext::fill(&mut envelope.metadata, envelope.id);
+        let json = serde_json::to_value(&envelope)
+            .map_err(|e| sqlx::Error::Protocol(format!("Unserializable event: {}", e)))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO event_outbox (id, event_type, aggregate_id, envelope, occurred_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(envelope.id)
+        .bind(&envelope.event_type)
+        .bind(envelope.aggregate_id)
+        .bind(json)
+        .bind(envelope.occurred_at)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Take up to `limit` due events, oldest first, for `lease_seconds`.
+    /// Events wait while an earlier event of their aggregate is retrying or
+    /// leased to another relay, so each aggregate's events stay in order.
+    pub async fn claim(
+        &self,
+        limit: i64,
+        lease_seconds: i64,
+    ) -> Result<Vec<OutboxEvent>, sqlx::Error> {
+        let mut events = sqlx::query_as::<_, OutboxEvent>(
+            r#"
+            UPDATE event_outbox
+            SET locked_until = NOW() + make_interval(secs => $2), attempts = attempts + 1
+            WHERE id IN (
+                SELECT o.id FROM event_outbox o
+                WHERE o.delivered_at IS NULL AND o.next_attempt_at <= NOW()
+                  AND (o.locked_until IS NULL OR o.locked_until < NOW())
+                  AND NOT EXISTS (
+                      SELECT 1 FROM event_outbox e
+                      WHERE e.aggregate_id = o.aggregate_id AND e.seq < o.seq
+                        AND e.delivered_at IS NULL
+                        AND (e.next_attempt_at > NOW() OR e.locked_until >= NOW())
+                  )
+                ORDER BY o.seq
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *
+            "#,
+        )
+        .bind(limit)
+        .bind(lease_seconds as f64)
+        .fetch_all(&self.pool)
+        .await?;
+        events.sort_by_key(|e| e.seq);
+        Ok(events)
+    }
+
+    pub async fn mark_delivered(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE event_outbox
+            SET delivered_at = NOW(), locked_until = NULL, last_error = NULL
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Release a claimed event for another attempt at `retry_at`
+    pub async fn mark_failed(
+        &self,
+        id: Uuid,
+        error: &str,
+        retry_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE event_outbox
+            SET locked_until = NULL, last_error = $2, next_attempt_at = $3
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .bind(retry_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Events of an aggregate, in the order they happened
+    pub async fn list_by_aggregate(
+        &self,
+        aggregate_id: Uuid,
+    ) -> Result<Vec<OutboxEvent>, sqlx::Error> {
+        sqlx::query_as::<_, OutboxEvent>(
+            "SELECT * FROM event_outbox WHERE aggregate_id = $1 ORDER BY seq",
+        )
+        .bind(aggregate_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+}
@@ -0,0 +1,198 @@
+//! Password Repository
+//!
+//! Password history, reset tokens and admin-forced password changes.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::{
+    PasswordResetToken, AUDIT_PASSWORD_RESET, AUDIT_PASSWORD_RESET_FORCED,
+};
+
+#[derive(Clone)]
+pub struct PasswordRepository {
+    pool: PgPool,
+}
+
+impl PasswordRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// The current password hash followed by previous ones, newest first
+    pub async fn recent_hashes(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT password_hash FROM (
+                SELECT password_hash, 'infinity'::timestamptz AS created_at FROM users WHERE id = $1
+                UNION ALL
+                SELECT password_hash, created_at FROM password_history WHERE user_id = $1
+            ) h
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Store a new password chosen by the user, which satisfies a forced change
+    pub async fn set_password(
+        &self,
+        user_id: Uuid,
+        password_hash: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE users SET password_hash = $2, must_change_password = false, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(user_id)
+        .bind(password_hash)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Issue a reset token, superseding earlier unused ones of the user
+    pub async fn create_reset_token(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+        requested_ip: Option<&str>,
+    ) -> Result<PasswordResetToken, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM password_reset_tokens WHERE user_id = $1 AND used_at IS NULL")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        let token = sqlx::query_as::<_, PasswordResetToken>(
+            r#"
+            INSERT INTO password_reset_tokens (user_id, token_hash, expires_at, requested_ip)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(token_hash)
+        .bind(expires_at)
+        .bind(requested_ip)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(token)
+    }
+
+    pub async fn find_reset_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<PasswordResetToken>, sqlx::Error> {
+        sqlx::query_as::<_, PasswordResetToken>(
+            "SELECT * FROM password_reset_tokens WHERE token_hash = $1",
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Spend a reset token and set the new password; false when the token was
+    /// used or expired meanwhile. Also lifts a login lockout.
+    pub async fn reset_password(
+        &self,
+        token: &PasswordResetToken,
+        password_hash: &str,
+        ip_address: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let claimed = sqlx::query(
+            r#"
+            UPDATE password_reset_tokens SET used_at = NOW()
+            WHERE id = $1 AND used_at IS NULL AND expires_at > NOW()
+            "#,
+        )
+        .bind(token.id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if claimed == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE users SET password_hash = $2, must_change_password = false,
+                failed_login_attempts = 0, last_failed_login_at = NULL, locked_until = NULL,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(token.user_id)
+        .bind(password_hash)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO audit_logs (table_name, record_id, action, new_values, user_id, ip_address)
+            VALUES ('users', $1, $2, $3, $1, $4)
+            "#,
+        )
+        .bind(token.user_id)
+        .bind(AUDIT_PASSWORD_RESET)
+        .bind(serde_json::json!({ "reset_token_id": token.id }))
+        .bind(ip_address)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Require a new password at the next login, ending the user's sessions;
+    /// false when the user does not exist or is a service account
+    pub async fn force_reset(&self, user_id: Uuid, forced_by: Uuid) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let updated = sqlx::query(
+            r#"
+            UPDATE users SET must_change_password = true, updated_at = NOW()
+            WHERE id = $1 AND NOT is_service_account
+            "#,
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if updated == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO audit_logs (table_name, record_id, action, new_values, user_id)
+            VALUES ('users', $1, $2, $3, $4)
+            "#,
+        )
+        .bind(user_id)
+        .bind(AUDIT_PASSWORD_RESET_FORCED)
+        .bind(serde_json::json!({ "must_change_password": true }))
+        .bind(forced_by)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+}
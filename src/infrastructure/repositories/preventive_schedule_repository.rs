@@ -0,0 +1,165 @@
+//! Preventive Schedule Repository
+//!
+//! Data access for preventive maintenance schedules and the meter readings that drive them.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::{IntervalType, PreventiveSchedule};
+
+#[derive(Clone)]
+pub struct PreventiveScheduleRepository {
+    pool: PgPool,
+}
+
+impl PreventiveScheduleRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<PreventiveSchedule>, sqlx::Error> {
+        sqlx::query_as::<_, PreventiveSchedule>("SELECT * FROM preventive_schedules WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    pub async fn list_by_asset(
+        &self,
+        asset_id: Uuid,
+    ) -> Result<Vec<PreventiveSchedule>, sqlx::Error> {
+        sqlx::query_as::<_, PreventiveSchedule>(
+            "SELECT * FROM preventive_schedules WHERE asset_id = $1 ORDER BY next_due_date NULLS LAST, name",
+        )
+        .bind(asset_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// List active schedules (evaluated by the scheduler job)
+    pub async fn list_active(&self) -> Result<Vec<PreventiveSchedule>, sqlx::Error> {
+        sqlx::query_as::<_, PreventiveSchedule>(
+            r#"
+            SELECT ps.* FROM preventive_schedules ps
+            JOIN assets a ON ps.asset_id = a.id
+            WHERE COALESCE(ps.is_active, true) = true
+              AND a.status NOT IN ('retired', 'disposed', 'lost_stolen', 'archived')
+            ORDER BY ps.next_due_date NULLS LAST
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn create(
+        &self,
+        schedule: &PreventiveSchedule,
+    ) -> Result<PreventiveSchedule, sqlx::Error> {
+        sqlx::query_as::<_, PreventiveSchedule>(
+            r#"
+            INSERT INTO preventive_schedules (
+                id, asset_id, maintenance_type_id, name, interval_type, interval_value,
+                last_execution_date, last_execution_odometer, next_due_date, next_due_odometer,
+                is_active, notification_days_before, created_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            RETURNING *
+            "#,
+        )
+        .bind(schedule.id)
+        .bind(schedule.asset_id)
+        .bind(schedule.maintenance_type_id)
+        .bind(&schedule.name)
+        .bind(&schedule.interval_type)
+        .bind(schedule.interval_value)
+        .bind(schedule.last_execution_date)
+        .bind(schedule.last_execution_odometer)
+        .bind(schedule.next_due_date)
+        .bind(schedule.next_due_odometer)
+        .bind(schedule.is_active)
+        .bind(schedule.notification_days_before)
+        .bind(schedule.created_by)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn update(
+        &self,
+        schedule: &PreventiveSchedule,
+    ) -> Result<PreventiveSchedule, sqlx::Error> {
+        sqlx::query_as::<_, PreventiveSchedule>(
+            r#"
+            UPDATE preventive_schedules SET
+                maintenance_type_id = $2,
+                name = $3,
+                interval_type = $4,
+                interval_value = $5,
+                last_execution_date = $6,
+                last_execution_odometer = $7,
+                next_due_date = $8,
+                next_due_odometer = $9,
+                is_active = $10,
+                notification_days_before = $11
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(schedule.id)
+        .bind(schedule.maintenance_type_id)
+        .bind(&schedule.name)
+        .bind(&schedule.interval_type)
+        .bind(schedule.interval_value)
+        .bind(schedule.last_execution_date)
+        .bind(schedule.last_execution_odometer)
+        .bind(schedule.next_due_date)
+        .bind(schedule.next_due_odometer)
+        .bind(schedule.is_active)
+        .bind(schedule.notification_days_before)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn delete(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM preventive_schedules WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Latest meter reading for an asset.
+    ///
+    /// Odometer comes from `vehicle_details.odometer_last`; hour meter comes from the
+    /// highest `hm_km_end` recorded on the asset's rental timesheets.
+    pub async fn current_reading(
+        &self,
+        asset_id: Uuid,
+        interval_type: IntervalType,
+    ) -> Result<Option<i64>, sqlx::Error> {
+        match interval_type {
+            IntervalType::Km => {
+                let row: Option<(Option<i64>,)> =
+                    sqlx::query_as("SELECT odometer_last FROM vehicle_details WHERE asset_id = $1")
+                        .bind(asset_id)
+                        .fetch_optional(&self.pool)
+                        .await?;
+                Ok(row.and_then(|r| r.0))
+            }
+            IntervalType::Hours => {
+                let row: (Option<i64>,) = sqlx::query_as(
+                    r#"
+                    SELECT FLOOR(MAX(ts.hm_km_end))::BIGINT
+                    FROM rental_timesheets ts
+                    JOIN rentals r ON ts.rental_id = r.id
+                    WHERE r.asset_id = $1
+                    "#,
+                )
+                .bind(asset_id)
+                .fetch_one(&self.pool)
+                .await?;
+                Ok(row.0)
+            }
+            IntervalType::Days => Ok(None),
+        }
+    }
+}
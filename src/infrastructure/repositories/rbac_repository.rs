@@ -1,4 +1,7 @@
-use crate::domain::entities::{Permission, Role, UserRoleAssignment};
+use crate::domain::entities::{
+    Permission, PermissionGrant, Role, RolePermission, UserPermission, UserRoleAssignment,
+    UserRoleGrant,
+};
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -27,6 +30,368 @@ impl RbacRepository {
             .await
     }
 
+    pub async fn find_role(&self, id: Uuid) -> Result<Option<Role>, sqlx::Error> {
+        sqlx::query_as::<_, Role>("SELECT * FROM roles WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Insert a custom role with its grants
+    pub async fn create_role(
+        &self,
+        role: &Role,
+        grants: &[PermissionGrant],
+        created_by: Uuid,
+    ) -> Result<Role, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let created = sqlx::query_as::<_, Role>(
+            r#"
+            INSERT INTO roles (id, code, name, description, role_level, requires_two_factor, is_system)
+            VALUES ($1, $2, $3, $4, $5, $6, false)
+            RETURNING *
+            "#,
+        )
+        .bind(role.id)
+        .bind(&role.code)
+        .bind(&role.name)
+        .bind(&role.description)
+        .bind(role.role_level)
+        .bind(role.requires_two_factor)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Self::replace_grants(&mut tx, created.id, grants).await?;
+        Self::audit(
+            &mut tx,
+            created.id,
+            "ROLE_CREATED",
+            created_by,
+            serde_json::json!({ "code": created.code, "permissions": grants }),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(created)
+    }
+
+    /// Copy of a role and all its grants under a new code
+    pub async fn clone_role(
+        &self,
+        source_id: Uuid,
+        role: &Role,
+        created_by: Uuid,
+    ) -> Result<Role, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let created = sqlx::query_as::<_, Role>(
+            r#"
+            INSERT INTO roles (id, code, name, description, role_level, requires_two_factor, is_system)
+            SELECT $2, $3, $4, COALESCE($5, description), role_level, requires_two_factor, false
+            FROM roles WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(source_id)
+        .bind(role.id)
+        .bind(&role.code)
+        .bind(&role.name)
+        .bind(&role.description)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO role_permissions (role_id, permission_id, scope)
+            SELECT $2, permission_id, scope FROM role_permissions WHERE role_id = $1
+            "#,
+        )
+        .bind(source_id)
+        .bind(created.id)
+        .execute(&mut *tx)
+        .await?;
+
+        Self::audit(
+            &mut tx,
+            created.id,
+            "ROLE_CLONED",
+            created_by,
+            serde_json::json!({ "code": created.code, "source_role_id": source_id }),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(created)
+    }
+
+    pub async fn update_role(&self, role: &Role, updated_by: Uuid) -> Result<Role, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let updated = sqlx::query_as::<_, Role>(
+            r#"
+            UPDATE roles
+            SET name = $2, description = $3, role_level = $4, requires_two_factor = $5,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(role.id)
+        .bind(&role.name)
+        .bind(&role.description)
+        .bind(role.role_level)
+        .bind(role.requires_two_factor)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Self::audit(
+            &mut tx,
+            updated.id,
+            "ROLE_UPDATED",
+            updated_by,
+            serde_json::json!({
+                "name": updated.name,
+                "role_level": updated.role_level,
+                "requires_two_factor": updated.requires_two_factor,
+            }),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(updated)
+    }
+
+    /// Delete a role; secondary assignments and grants go with it
+    pub async fn delete_role(&self, id: Uuid, deleted_by: Uuid) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let code: Option<String> = sqlx::query_scalar(
+            "DELETE FROM roles WHERE id = $1 AND is_system = false RETURNING code",
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(code) = code else {
+            return Ok(false);
+        };
+
+        Self::audit(
+            &mut tx,
+            id,
+            "ROLE_DELETED",
+            deleted_by,
+            serde_json::json!({ "code": code }),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Users holding the role as primary role
+    pub async fn count_primary_holders(&self, role_id: Uuid) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE role_id = $1")
+            .bind(role_id)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// Replace the grants of a role; unchanged grants are left alone so the
+    /// sessions of its users only end when something changes
+    pub async fn set_role_permissions(
+        &self,
+        role_id: Uuid,
+        grants: &[PermissionGrant],
+        updated_by: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let codes: Vec<&str> = grants.iter().map(|g| g.code.as_str()).collect();
+        sqlx::query(
+            r#"
+            DELETE FROM role_permissions rp
+            USING permissions p
+            WHERE rp.permission_id = p.id AND rp.role_id = $1 AND NOT (p.code = ANY($2))
+            "#,
+        )
+        .bind(role_id)
+        .bind(&codes)
+        .execute(&mut *tx)
+        .await?;
+
+        Self::replace_grants(&mut tx, role_id, grants).await?;
+        Self::audit(
+            &mut tx,
+            role_id,
+            "ROLE_PERMISSIONS_SET",
+            updated_by,
+            serde_json::json!({ "permissions": grants }),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Grant a permission to a role, or change the scope of its grant
+    pub async fn attach_permission(
+        &self,
+        role_id: Uuid,
+        grant: &PermissionGrant,
+        updated_by: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        Self::replace_grants(&mut tx, role_id, std::slice::from_ref(grant)).await?;
+        Self::audit(
+            &mut tx,
+            role_id,
+            "ROLE_PERMISSION_ATTACHED",
+            updated_by,
+            serde_json::json!(grant),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn detach_permission(
+        &self,
+        role_id: Uuid,
+        permission_code: &str,
+        updated_by: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let result = sqlx::query(
+            r#"
+            DELETE FROM role_permissions rp
+            USING permissions p
+            WHERE rp.permission_id = p.id AND rp.role_id = $1 AND p.code = $2
+            "#,
+        )
+        .bind(role_id)
+        .bind(permission_code)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        Self::audit(
+            &mut tx,
+            role_id,
+            "ROLE_PERMISSION_DETACHED",
+            updated_by,
+            serde_json::json!({ "code": permission_code }),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Upsert grants by permission code; a grant whose scope is unchanged is
+    /// not rewritten
+    async fn replace_grants(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        role_id: Uuid,
+        grants: &[PermissionGrant],
+    ) -> Result<(), sqlx::Error> {
+        let codes: Vec<&str> = grants.iter().map(|g| g.code.as_str()).collect();
+        let scopes: Vec<&str> = grants.iter().map(|g| g.scope.as_str()).collect();
+        sqlx::query(
+            r#"
+            INSERT INTO role_permissions (role_id, permission_id, scope)
+            SELECT $1, p.id, g.scope
+            FROM UNNEST($2::text[], $3::text[]) AS g(code, scope)
+            JOIN permissions p ON p.code = g.code
+            ON CONFLICT (role_id, permission_id) DO UPDATE SET scope = EXCLUDED.scope
+            WHERE role_permissions.scope <> EXCLUDED.scope
+            "#,
+        )
+        .bind(role_id)
+        .bind(&codes)
+        .bind(&scopes)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    async fn audit(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        role_id: Uuid,
+        action: &str,
+        user_id: Uuid,
+        values: serde_json::Value,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_logs (table_name, record_id, action, new_values, user_id)
+            VALUES ('roles', $1, $2, $3, $4)
+            "#,
+        )
+        .bind(role_id)
+        .bind(action)
+        .bind(values)
+        .bind(user_id)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Active users holding the role as primary or current secondary role
+    pub async fn list_role_holders(
+        &self,
+        role_id: Uuid,
+    ) -> Result<Vec<(Uuid, String, String)>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            SELECT id, email, name FROM users
+            WHERE is_active = true
+            AND (
+                role_id = $1
+                OR id IN (
+                    SELECT user_id FROM user_roles
+                    WHERE role_id = $1 AND (expires_at IS NULL OR expires_at > NOW())
+                )
+            )
+            ORDER BY name
+            "#,
+        )
+        .bind(role_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Grants of all the roles the users hold
+    pub async fn list_user_role_grants(
+        &self,
+        user_ids: &[Uuid],
+    ) -> Result<Vec<UserRoleGrant>, sqlx::Error> {
+        sqlx::query_as::<_, UserRoleGrant>(
+            r#"
+            WITH held AS (
+                SELECT id AS user_id, role_id FROM users
+                WHERE id = ANY($1) AND role_id IS NOT NULL
+                UNION
+                SELECT user_id, role_id FROM user_roles
+                WHERE user_id = ANY($1) AND (expires_at IS NULL OR expires_at > NOW())
+            )
+            SELECT h.user_id, h.role_id, p.code, rp.scope::text AS scope
+            FROM held h
+            JOIN role_permissions rp ON rp.role_id = h.role_id
+            JOIN permissions p ON p.id = rp.permission_id
+            "#,
+        )
+        .bind(user_ids)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Open sessions of the role's primary holders, which end when its grants
+    /// change
+    pub async fn count_primary_holder_sessions(&self, role_id: Uuid) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM user_sessions s
+            JOIN users u ON u.id = s.user_id
+            WHERE u.role_id = $1 AND s.revoked_at IS NULL AND s.token_version = u.token_version
+            "#,
+        )
+        .bind(role_id)
+        .fetch_one(&self.pool)
+        .await
+    }
+
     // --- Permission Methods ---
 
     pub async fn list_permissions(&self) -> Result<Vec<Permission>, sqlx::Error> {
@@ -38,13 +403,14 @@ impl RbacRepository {
     pub async fn get_role_permissions(
         &self,
         role_id: Uuid,
-    ) -> Result<Vec<Permission>, sqlx::Error> {
-        sqlx::query_as::<_, Permission>(
+    ) -> Result<Vec<RolePermission>, sqlx::Error> {
+        sqlx::query_as::<_, RolePermission>(
             r#"
-            SELECT p.* 
+            SELECT p.*, rp.scope
             FROM permissions p
             JOIN role_permissions rp ON p.id = rp.permission_id
             WHERE rp.role_id = $1
+            ORDER BY p.resource, p.action
             "#,
         )
         .bind(role_id)
@@ -96,17 +462,19 @@ impl RbacRepository {
     pub async fn get_user_permissions(
         &self,
         user_id: Uuid,
-    ) -> Result<Vec<Permission>, sqlx::Error> {
+    ) -> Result<Vec<UserPermission>, sqlx::Error> {
         // Combine permissions from Primary Role and Secondary Roles
-        sqlx::query_as::<_, Permission>(
+        sqlx::query_as::<_, UserPermission>(
             r#"
-            SELECT DISTINCT p.* 
+            SELECT p.*, array_agg(DISTINCT rp.scope::text) AS scopes
             FROM permissions p
             JOIN role_permissions rp ON p.id = rp.permission_id
             JOIN roles r ON rp.role_id = r.id
             LEFT JOIN users u ON u.role_id = r.id
             LEFT JOIN user_roles ur ON ur.role_id = r.id
             WHERE u.id = $1 OR ur.user_id = $1
+            GROUP BY p.id
+            ORDER BY p.resource, p.action
             "#,
         )
         .bind(user_id)
@@ -139,6 +507,88 @@ impl RbacRepository {
         Ok(count > 0)
     }
 
+    /// Scopes of the grants of a permission across the user's roles
+    pub async fn get_permission_scopes(
+        &self,
+        user_id: Uuid,
+        permission_code: &str,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT DISTINCT rp.scope::text
+            FROM role_permissions rp
+            JOIN permissions p ON p.id = rp.permission_id
+            WHERE (p.code = $2 OR p.code = '*')
+            AND rp.role_id IN (
+                SELECT role_id FROM users WHERE id = $1
+                UNION
+                SELECT role_id FROM user_roles
+                WHERE user_id = $1 AND (expires_at IS NULL OR expires_at > NOW())
+            )
+            "#,
+        )
+        .bind(user_id)
+        .bind(permission_code)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Department and location that department- and location-scoped grants
+    /// of the user are rooted at
+    pub async fn get_scope_anchors(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<(Option<Uuid>, Option<Uuid>)>, sqlx::Error> {
+        sqlx::query_as("SELECT department_id, location_id FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Active users holding a permission through their primary or secondary roles
+    pub async fn list_user_ids_with_permission(
+        &self,
+        permission_code: &str,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT DISTINCT u.id
+            FROM users u
+            LEFT JOIN user_roles ur ON ur.user_id = u.id
+            JOIN role_permissions rp ON rp.role_id = u.role_id OR rp.role_id = ur.role_id
+            JOIN permissions p ON p.id = rp.permission_id
+            WHERE u.is_active = true
+            AND (p.code = $1 OR p.code = '*')
+            "#,
+        )
+        .bind(permission_code)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Active users holding any of the roles as primary or current secondary role
+    pub async fn list_user_ids_with_roles(
+        &self,
+        role_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT id FROM users
+            WHERE is_active = true
+            AND (
+                role_id = ANY($1)
+                OR id IN (
+                    SELECT user_id FROM user_roles
+                    WHERE role_id = ANY($1) AND (expires_at IS NULL OR expires_at > NOW())
+                )
+            )
+            "#,
+        )
+        .bind(role_ids)
+        .fetch_all(&self.pool)
+        .await
+    }
+
     // Assign secondary role
     pub async fn assign_role_to_user(
         &self,
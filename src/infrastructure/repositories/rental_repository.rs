@@ -8,6 +8,18 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::domain::entities::{Rental, RentalHandover, RentalRate};
+use crate::domain::events::{EventEnvelope, RentalReturned};
+use crate::infrastructure::repositories::OutboxRepository;
+
+/// Final billing of a returned rental
+#[derive(Debug, Clone)]
+pub struct RentalSettlement {
+    pub actual_end_date: NaiveDate,
+    pub total_days: i32,
+    pub subtotal: Decimal,
+    pub penalty_amount: Decimal,
+    pub total_amount: Decimal,
+}
 
 #[derive(Clone)]
 pub struct RentalRepository {
@@ -98,13 +110,22 @@ impl RentalRepository {
     }
 
     /// List all rentals with pagination
-    pub async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Rental>, sqlx::Error> {
-        sqlx::query_as!(
-            Rental,
-            r#"SELECT * FROM rentals ORDER BY created_at DESC LIMIT $1 OFFSET $2"#,
-            limit,
-            offset
+    pub async fn list(
+        &self,
+        limit: i64,
+        offset: i64,
+        org_scope: Option<Uuid>,
+    ) -> Result<Vec<Rental>, sqlx::Error> {
+        sqlx::query_as::<_, Rental>(
+            r#"
+            SELECT * FROM rentals
+            WHERE ($3::uuid IS NULL OR asset_in_organization(asset_id, $3))
+            ORDER BY created_at DESC LIMIT $1 OFFSET $2
+            "#,
         )
+        .bind(limit)
+        .bind(offset)
+        .bind(org_scope)
         .fetch_all(&self.pool)
         .await
     }
@@ -143,26 +164,32 @@ impl RentalRepository {
     }
 
     /// List pending rentals (waiting for approval)
-    pub async fn list_pending(&self) -> Result<Vec<Rental>, sqlx::Error> {
-        sqlx::query_as!(
-            Rental,
-            r#"SELECT * FROM rentals WHERE status = 'requested' ORDER BY created_at ASC"#
+    pub async fn list_pending(&self, org_scope: Option<Uuid>) -> Result<Vec<Rental>, sqlx::Error> {
+        sqlx::query_as::<_, Rental>(
+            r#"
+            SELECT * FROM rentals
+            WHERE status = 'requested'
+            AND ($1::uuid IS NULL OR asset_in_organization(asset_id, $1))
+            ORDER BY created_at ASC
+            "#,
         )
+        .bind(org_scope)
         .fetch_all(&self.pool)
         .await
     }
 
     /// List overdue rentals
-    pub async fn list_overdue(&self) -> Result<Vec<Rental>, sqlx::Error> {
-        sqlx::query_as!(
-            Rental,
+    pub async fn list_overdue(&self, org_scope: Option<Uuid>) -> Result<Vec<Rental>, sqlx::Error> {
+        sqlx::query_as::<_, Rental>(
             r#"
-            SELECT * FROM rentals 
-            WHERE status = 'rented_out' 
+            SELECT * FROM rentals
+            WHERE status = 'rented_out'
             AND expected_end_date < CURRENT_DATE
+            AND ($1::uuid IS NULL OR asset_in_organization(asset_id, $1))
             ORDER BY expected_end_date ASC
-            "#
+            "#,
         )
+        .bind(org_scope)
         .fetch_all(&self.pool)
         .await
     }
@@ -188,6 +215,29 @@ impl RentalRepository {
         expected_end_date: NaiveDate,
         daily_rate: Decimal,
     ) -> Result<(), sqlx::Error> {
+        Self::approve_with(
+            &self.pool,
+            id,
+            approved_by,
+            start_date,
+            expected_end_date,
+            daily_rate,
+        )
+        .await
+    }
+
+    /// Approve a rental on a given connection or transaction
+    pub(crate) async fn approve_with<'e, E>(
+        executor: E,
+        id: Uuid,
+        approved_by: Uuid,
+        start_date: NaiveDate,
+        expected_end_date: NaiveDate,
+        daily_rate: Decimal,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
         sqlx::query!(
             r#"
             UPDATE rentals SET 
@@ -206,7 +256,7 @@ impl RentalRepository {
             expected_end_date,
             daily_rate
         )
-        .execute(&self.pool)
+        .execute(executor)
         .await?;
         Ok(())
     }
@@ -255,17 +305,16 @@ impl RentalRepository {
         Ok(())
     }
 
-    /// Return rental (handover in)
+    /// Return rental (handover in): close it with its final amounts; the
+    /// return event is stored with it
     pub async fn return_rental(
         &self,
         id: Uuid,
         returned_by: Uuid,
-        actual_end_date: NaiveDate,
-        total_days: i32,
-        subtotal: Decimal,
-        penalty_amount: Decimal,
-        total_amount: Decimal,
+        settlement: &RentalSettlement,
+        event: EventEnvelope<RentalReturned>,
     ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
         sqlx::query!(
             r#"
             UPDATE rentals SET 
@@ -282,14 +331,18 @@ impl RentalRepository {
             "#,
             id,
             returned_by,
-            actual_end_date,
-            total_days,
-            subtotal,
-            penalty_amount,
-            total_amount
+            settlement.actual_end_date,
+            settlement.total_days,
+            settlement.subtotal,
+            settlement.penalty_amount,
+            settlement.total_amount
         )
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
+
+        OutboxRepository::append(&mut tx, event).await?;
+
+        tx.commit().await?;
         Ok(())
     }
 
@@ -0,0 +1,187 @@
+//! Session Repository
+//!
+//! Login sessions, refresh token rotation and revocation.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::{RefreshToken, UserSession, REVOKED_TOKEN_VERSION};
+
+#[derive(Clone)]
+pub struct SessionRepository {
+    pool: PgPool,
+}
+
+impl SessionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Open a session at the user's current token version with its first refresh token
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        organization_id: Option<Uuid>,
+        user_agent: Option<&str>,
+        token: &RefreshToken,
+    ) -> Result<UserSession, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let session = sqlx::query_as::<_, UserSession>(
+            r#"
+            INSERT INTO user_sessions (id, user_id, organization_id, token_version, user_agent)
+            SELECT $1, id, $3, token_version, $4 FROM users WHERE id = $2
+            RETURNING *
+            "#,
+        )
+        .bind(token.session_id)
+        .bind(user_id)
+        .bind(organization_id)
+        .bind(user_agent)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Self::insert_token(&mut tx, token).await?;
+
+        tx.commit().await?;
+        Ok(session)
+    }
+
+    pub async fn find_session(&self, id: Uuid) -> Result<Option<UserSession>, sqlx::Error> {
+        sqlx::query_as::<_, UserSession>("SELECT * FROM user_sessions WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    pub async fn find_refresh_token(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<RefreshToken>, sqlx::Error> {
+        sqlx::query_as::<_, RefreshToken>("SELECT * FROM refresh_tokens WHERE token_hash = $1")
+            .bind(token_hash)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Exchange a refresh token for `next`; false when it was used concurrently
+    pub async fn rotate(&self, used_id: Uuid, next: &RefreshToken) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let claimed = sqlx::query(
+            "UPDATE refresh_tokens SET used_at = NOW() WHERE id = $1 AND used_at IS NULL",
+        )
+        .bind(used_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if claimed == 0 {
+            return Ok(false);
+        }
+
+        Self::insert_token(&mut tx, next).await?;
+        sqlx::query("UPDATE user_sessions SET last_refreshed_at = NOW() WHERE id = $1")
+            .bind(next.session_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn insert_token(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        token: &RefreshToken,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO refresh_tokens (id, session_id, token_hash, expires_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(token.id)
+        .bind(token.session_id)
+        .bind(&token.token_hash)
+        .bind(token.expires_at)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_organization(
+        &self,
+        id: Uuid,
+        organization_id: Option<Uuid>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE user_sessions SET organization_id = $2 WHERE id = $1")
+            .bind(id)
+            .bind(organization_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn revoke(&self, id: Uuid, reason: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE user_sessions SET revoked_at = NOW(), revoked_reason = $2
+            WHERE id = $1 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .bind(reason)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Revoke every session of a user by bumping the token version
+    pub async fn revoke_all(&self, user_id: Uuid, reason: &str) -> Result<u64, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("UPDATE users SET token_version = token_version + 1 WHERE id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        let revoked = sqlx::query(
+            r#"
+            UPDATE user_sessions SET revoked_at = NOW(), revoked_reason = $2
+            WHERE user_id = $1 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(reason)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        tx.commit().await?;
+        Ok(revoked)
+    }
+
+    /// Whether the session is live: not revoked, user active and its token
+    /// version unchanged. A session outdated by the version is revoked here.
+    pub async fn is_active(&self, id: Uuid, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let row: Option<(bool, bool)> = sqlx::query_as(
+            r#"
+            SELECT s.revoked_at IS NULL, u.is_active AND u.token_version = s.token_version
+            FROM user_sessions s
+            JOIN users u ON u.id = s.user_id
+            WHERE s.id = $1 AND s.user_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some((true, true)) => Ok(true),
+            Some((true, false)) => {
+                self.revoke(id, REVOKED_TOKEN_VERSION).await?;
+                Ok(false)
+            }
+            _ => Ok(false),
+        }
+    }
+}
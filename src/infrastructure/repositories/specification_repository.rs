@@ -0,0 +1,134 @@
+//! Specification History Repository
+//!
+//! Category/specification changes of an asset are saved together with their
+//! history entry in one transaction; see also `ConversionRepository::execute`.
+
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::domain::entities::{Asset, AssetHistory, AssetSpecificationHistory};
+use crate::infrastructure::repositories::AssetRepository;
+
+#[derive(Clone)]
+pub struct SpecificationRepository {
+    pool: PgPool,
+}
+
+impl SpecificationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Specification history of an asset, latest first
+    pub async fn list_by_asset(
+        &self,
+        asset_id: Uuid,
+    ) -> Result<Vec<AssetSpecificationHistory>, sqlx::Error> {
+        sqlx::query_as::<_, AssetSpecificationHistory>(
+            r#"
+            SELECT * FROM asset_specification_history
+            WHERE asset_id = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(asset_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn find_by_id(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<AssetSpecificationHistory>, sqlx::Error> {
+        sqlx::query_as::<_, AssetSpecificationHistory>(
+            "SELECT * FROM asset_specification_history WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Save an asset whose category or specifications changed. Returns `None`
+    /// when they no longer match `entry`'s old values (changed concurrently).
+    pub async fn save_asset_change(
+        &self,
+        asset: &Asset,
+        entry: &AssetSpecificationHistory,
+        history: &AssetHistory,
+    ) -> Result<Option<Asset>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let saved = Self::save_asset_change_in(&mut tx, asset, entry, history).await?;
+        tx.commit().await?;
+        Ok(saved)
+    }
+
+    /// `save_asset_change` inside the caller's transaction
+    pub(crate) async fn save_asset_change_in(
+        conn: &mut PgConnection,
+        asset: &Asset,
+        entry: &AssetSpecificationHistory,
+        history: &AssetHistory,
+    ) -> Result<Option<Asset>, sqlx::Error> {
+        if !Self::lock_unchanged(conn, entry).await? {
+            return Ok(None);
+        }
+        let saved = AssetRepository::update_with(&mut *conn, asset).await?;
+        Self::insert_with(conn, entry).await?;
+        AssetRepository::add_history_with(&mut *conn, history).await?;
+        Ok(Some(saved))
+    }
+
+    /// Lock the asset row and check its category and specifications still
+    /// match the old values of `entry`
+    pub(crate) async fn lock_unchanged(
+        conn: &mut PgConnection,
+        entry: &AssetSpecificationHistory,
+    ) -> Result<bool, sqlx::Error> {
+        let unchanged: Option<bool> = sqlx::query_scalar(
+            r#"
+            SELECT category_id IS NOT DISTINCT FROM $2
+                AND specifications IS NOT DISTINCT FROM $3
+            FROM assets WHERE id = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(entry.asset_id)
+        .bind(entry.old_category_id)
+        .bind(&entry.old_specifications)
+        .fetch_optional(&mut *conn)
+        .await?;
+        Ok(unchanged.unwrap_or(false))
+    }
+
+    pub(crate) async fn insert_with(
+        conn: &mut PgConnection,
+        entry: &AssetSpecificationHistory,
+    ) -> Result<AssetSpecificationHistory, sqlx::Error> {
+        sqlx::query_as::<_, AssetSpecificationHistory>(
+            r#"
+            INSERT INTO asset_specification_history (
+                id, asset_id, conversion_id, change_type, old_category_id, new_category_id,
+                old_subtype, new_subtype, old_specifications, new_specifications, diff,
+                changed_by, notes
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            RETURNING *
+            "#,
+        )
+        .bind(entry.id)
+        .bind(entry.asset_id)
+        .bind(entry.conversion_id)
+        .bind(&entry.change_type)
+        .bind(entry.old_category_id)
+        .bind(entry.new_category_id)
+        .bind(&entry.old_subtype)
+        .bind(&entry.new_subtype)
+        .bind(&entry.old_specifications)
+        .bind(&entry.new_specifications)
+        .bind(&entry.diff)
+        .bind(entry.changed_by)
+        .bind(&entry.notes)
+        .fetch_one(&mut *conn)
+        .await
+    }
+}
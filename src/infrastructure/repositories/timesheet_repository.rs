@@ -8,7 +8,9 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::application::dto::TimesheetDetailResponse;
-use crate::domain::entities::{ClientContact, RentalBillingPeriod, RentalTimesheet};
+use crate::domain::entities::{ClientContact, NumberSeries, RentalBillingPeriod, RentalTimesheet};
+use crate::domain::events::{BillingApproved, EventEnvelope};
+use crate::infrastructure::repositories::{DocumentNumberRepository, OutboxRepository};
 
 #[derive(Clone)]
 pub struct TimesheetRepository {
@@ -391,8 +393,10 @@ impl TimesheetRepository {
         id: Uuid,
         approved_by: Uuid,
         notes: Option<String>,
+        event: EventEnvelope<BillingApproved>,
     ) -> Result<(), sqlx::Error> {
         let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
         sqlx::query!(
             r#"UPDATE rental_billing_periods 
             SET status = 'approved', approved_by = $2, approved_at = $3, notes = $4, updated_at = $3
@@ -402,33 +406,62 @@ impl TimesheetRepository {
             now,
             notes
         )
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
+
+        OutboxRepository::append(&mut tx, event).await?;
+
+        tx.commit().await?;
         Ok(())
     }
 
     /// Generate invoice
+    /// Invoice an approved billing period. Without an `invoice_number` the next
+    /// number of `series` is taken in the same transaction, so a failed
+    /// invoice leaves no gap. Returns None if the period is not approved.
     pub async fn generate_invoice(
         &self,
         id: Uuid,
-        invoice_number: &str,
+        invoice_number: Option<&str>,
+        series: &NumberSeries,
         due_date: NaiveDate,
-    ) -> Result<(), sqlx::Error> {
+    ) -> Result<Option<String>, sqlx::Error> {
         let now = Utc::now();
         let invoice_date = now.date_naive();
-        sqlx::query!(
-            r#"UPDATE rental_billing_periods 
+        let mut tx = self.pool.begin().await?;
+
+        // Lock the period first, so a concurrent request cannot take a
+        // number for it as well
+        let approved: Option<Uuid> = sqlx::query_scalar(
+            "SELECT id FROM rental_billing_periods WHERE id = $1 AND status = 'approved' FOR UPDATE",
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if approved.is_none() {
+            return Ok(None);
+        }
+
+        let invoice_number = match invoice_number {
+            Some(number) => number.to_string(),
+            None => series.number(DocumentNumberRepository::next_value_in(&mut tx, series).await?),
+        };
+
+        sqlx::query(
+            r#"UPDATE rental_billing_periods
             SET status = 'invoiced', invoice_number = $2, invoice_date = $3, due_date = $4, updated_at = $5
             WHERE id = $1"#,
-            id,
-            invoice_number,
-            invoice_date,
-            due_date,
-            now
         )
-        .execute(&self.pool)
+        .bind(id)
+        .bind(&invoice_number)
+        .bind(invoice_date)
+        .bind(due_date)
+        .bind(now)
+        .execute(&mut *tx)
         .await?;
-        Ok(())
+
+        tx.commit().await?;
+        Ok(Some(invoice_number))
     }
 
     /// List billing periods for rental
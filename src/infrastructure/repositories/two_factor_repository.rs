@@ -0,0 +1,186 @@
+//! Two-Factor Repository
+//!
+//! TOTP enrolments, recovery codes and the RBAC two-factor policy.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::UserTwoFactor;
+
+#[derive(Clone)]
+pub struct TwoFactorRepository {
+    pool: PgPool,
+}
+
+impl TwoFactorRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find(&self, user_id: Uuid) -> Result<Option<UserTwoFactor>, sqlx::Error> {
+        sqlx::query_as::<_, UserTwoFactor>("SELECT * FROM user_two_factor WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Start (or restart) a pending enrolment; `None` when 2FA is already enabled
+    pub async fn save_pending(
+        &self,
+        user_id: Uuid,
+        secret: &str,
+    ) -> Result<Option<UserTwoFactor>, sqlx::Error> {
+        sqlx::query_as::<_, UserTwoFactor>(
+            r#"
+            INSERT INTO user_two_factor (user_id, secret)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE SET secret = EXCLUDED.secret, last_used_step = NULL
+            WHERE user_two_factor.enabled_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(user_id)
+        .bind(secret)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Record a verified code's time step; false when it (or a later one) was used already
+    pub async fn consume_step(&self, user_id: Uuid, step: i64) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_two_factor SET last_used_step = $2
+            WHERE user_id = $1 AND (last_used_step IS NULL OR last_used_step < $2)
+            "#,
+        )
+        .bind(user_id)
+        .bind(step)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Complete enrolment with its first code, replacing any recovery codes
+    pub async fn enable(
+        &self,
+        user_id: Uuid,
+        step: i64,
+        recovery_code_hashes: &[String],
+    ) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let enabled = sqlx::query(
+            r#"
+            UPDATE user_two_factor SET enabled_at = NOW(), last_used_step = $2
+            WHERE user_id = $1 AND enabled_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(step)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected()
+            == 1;
+        if !enabled {
+            return Ok(false);
+        }
+
+        Self::insert_recovery_codes(&mut tx, user_id, recovery_code_hashes).await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    pub async fn replace_recovery_codes(
+        &self,
+        user_id: Uuid,
+        code_hashes: &[String],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        Self::insert_recovery_codes(&mut tx, user_id, code_hashes).await?;
+        tx.commit().await
+    }
+
+    async fn insert_recovery_codes(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: Uuid,
+        code_hashes: &[String],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM user_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut **tx)
+            .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO user_recovery_codes (user_id, code_hash)
+            SELECT $1, UNNEST($2::varchar[])
+            "#,
+        )
+        .bind(user_id)
+        .bind(code_hashes)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Spend a recovery code; false when unknown or already used
+    pub async fn use_recovery_code(
+        &self,
+        user_id: Uuid,
+        code_hash: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_recovery_codes SET used_at = NOW()
+            WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(code_hash)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    pub async fn recovery_codes_left(&self, user_id: Uuid) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM user_recovery_codes WHERE user_id = $1 AND used_at IS NULL",
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Remove the enrolment and recovery codes of a user
+    pub async fn delete(&self, user_id: Uuid) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM user_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        let deleted = sqlx::query("DELETE FROM user_two_factor WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        tx.commit().await?;
+        Ok(deleted > 0)
+    }
+
+    /// Whether the role, or a permission it holds, is flagged as requiring 2FA
+    pub async fn role_requires_two_factor(&self, role_id: Uuid) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (SELECT 1 FROM roles WHERE id = $1 AND requires_two_factor)
+                OR EXISTS (
+                    SELECT 1 FROM role_permissions rp
+                    JOIN permissions p ON p.id = rp.permission_id
+                    WHERE rp.role_id = $1 AND p.requires_two_factor
+                )
+            "#,
+        )
+        .bind(role_id)
+        .fetch_one(&self.pool)
+        .await
+    }
+}
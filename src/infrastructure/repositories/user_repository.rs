@@ -3,7 +3,10 @@
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::domain::entities::{User, UserSummary};
+use crate::domain::entities::{
+    LoginAttempts, LoginLockoutPolicy, User, UserSummary, AUDIT_ACCOUNT_LOCKED,
+    AUDIT_ACCOUNT_UNLOCKED,
+};
 
 #[derive(Clone)]
 pub struct UserRepository {
@@ -21,10 +24,10 @@ impl UserRepository {
             SELECT 
                 u.id, u.email, u.password_hash, u.name, 
                 u.role_id, COALESCE(r.code, u.role) as role_code, COALESCE(r.role_level, 5) as role_level,
-                u.department, u.department_id, u.organization_id,
+                u.department, u.department_id, u.organization_id, u.location_id,
                 u.phone, u.avatar_url,
                 u.is_active, false as email_verified, NULL::timestamptz as last_login_at,
-                u.created_at, u.updated_at
+                u.is_service_account, u.must_change_password, u.created_at, u.updated_at
             FROM users u
             LEFT JOIN roles r ON u.role_id = r.id
             WHERE u.id = $1
@@ -41,10 +44,10 @@ impl UserRepository {
             SELECT 
                 u.id, u.email, u.password_hash, u.name, 
                 u.role_id, COALESCE(r.code, u.role) as role_code, COALESCE(r.role_level, 5) as role_level,
-                u.department, u.department_id, u.organization_id,
+                u.department, u.department_id, u.organization_id, u.location_id,
                 u.phone, u.avatar_url,
                 u.is_active, false as email_verified, NULL::timestamptz as last_login_at,
-                u.created_at, u.updated_at
+                u.is_service_account, u.must_change_password, u.created_at, u.updated_at
             FROM users u
             LEFT JOIN roles r ON u.role_id = r.id
             WHERE u.email = $1
@@ -87,14 +90,18 @@ impl UserRepository {
         sqlx::query_as::<_, User>(
             r#"
             WITH inserted_user AS (
-                INSERT INTO users (id, email, password_hash, name, role, role_id, department, department_id, organization_id, is_active)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                INSERT INTO users (id, email, password_hash, name, role, role_id, department, department_id, organization_id, is_active, location_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
                 RETURNING *
+            ),
+            home_membership AS (
+                INSERT INTO user_organizations (user_id, organization_id)
+                SELECT id, organization_id FROM inserted_user WHERE organization_id IS NOT NULL
             )
             SELECT 
                 u.id, u.email, u.password_hash, u.name, 
                 u.role_id, COALESCE(r.code, u.role) as role_code, COALESCE(r.role_level, 5) as role_level,
-                u.department, u.department_id, u.organization_id,
+                u.department, u.department_id, u.organization_id, u.location_id,
                 NULL::text as phone, NULL::text as avatar_url,
                 u.is_active, false as email_verified, NULL::timestamptz as last_login_at,
                 u.created_at, u.updated_at
@@ -112,6 +119,7 @@ impl UserRepository {
         .bind(user.department_id)
         .bind(user.organization_id)
         .bind(user.is_active)
+        .bind(user.location_id)
         .fetch_one(&self.pool)
         .await
     }
@@ -140,6 +148,16 @@ impl UserRepository {
             .await?;
         Ok(result.rows_affected() > 0)
     }
+    /// Set the home location that location-scoped grants are rooted at
+    pub async fn set_location(&self, id: Uuid, location_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE users SET location_id = $2, updated_at = NOW() WHERE id = $1")
+            .bind(id)
+            .bind(location_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
     pub async fn update(
         &self,
         id: Uuid,
@@ -248,4 +266,121 @@ impl UserRepository {
         .fetch_one(&self.pool)
         .await
     }
+
+    // ==================== LOGIN LOCKOUT ====================
+
+    pub async fn login_attempts(&self, id: Uuid) -> Result<LoginAttempts, sqlx::Error> {
+        sqlx::query_as::<_, LoginAttempts>(
+            "SELECT failed_login_attempts, last_failed_login_at, locked_until FROM users WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Count a failed login; reaching the threshold locks the account and is
+    /// recorded in the audit log
+    pub async fn record_failed_login(
+        &self,
+        id: Uuid,
+        policy: &LoginLockoutPolicy,
+        ip_address: Option<&str>,
+        user_agent: Option<&str>,
+    ) -> Result<LoginAttempts, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let attempts = sqlx::query_as::<_, LoginAttempts>(
+            r#"
+            UPDATE users SET
+                failed_login_attempts = failed_login_attempts + 1,
+                last_failed_login_at = NOW(),
+                locked_until = CASE
+                    WHEN failed_login_attempts + 1 >= $2 THEN NOW() + make_interval(mins => $3)
+                    ELSE locked_until
+                END
+            WHERE id = $1
+            RETURNING failed_login_attempts, last_failed_login_at, locked_until
+            "#,
+        )
+        .bind(id)
+        .bind(policy.lockout_threshold)
+        .bind(policy.lockout_minutes as i32)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if policy.locks_after(attempts.failed_login_attempts) {
+            sqlx::query(
+                r#"
+                INSERT INTO audit_logs (table_name, record_id, action, new_values, ip_address, user_agent)
+                VALUES ('users', $1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(id)
+            .bind(AUDIT_ACCOUNT_LOCKED)
+            .bind(serde_json::json!({
+                "failed_login_attempts": attempts.failed_login_attempts,
+                "locked_until": attempts.locked_until,
+            }))
+            .bind(ip_address)
+            .bind(user_agent)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(attempts)
+    }
+
+    pub async fn reset_failed_logins(&self, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE users SET failed_login_attempts = 0, last_failed_login_at = NULL, locked_until = NULL
+            WHERE id = $1 AND (failed_login_attempts > 0 OR locked_until IS NOT NULL)
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Clear a lockout on behalf of an admin; false when the user does not exist
+    pub async fn unlock(&self, id: Uuid, unlocked_by: Uuid) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let previous = sqlx::query_as::<_, LoginAttempts>(
+            r#"
+            UPDATE users u SET failed_login_attempts = 0, last_failed_login_at = NULL, locked_until = NULL
+            FROM (SELECT id, failed_login_attempts, last_failed_login_at, locked_until
+                  FROM users WHERE id = $1 FOR UPDATE) old
+            WHERE u.id = old.id
+            RETURNING old.failed_login_attempts, old.last_failed_login_at, old.locked_until
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(previous) = previous else {
+            return Ok(false);
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO audit_logs (table_name, record_id, action, old_values, user_id)
+            VALUES ('users', $1, $2, $3, $4)
+            "#,
+        )
+        .bind(id)
+        .bind(AUDIT_ACCOUNT_UNLOCKED)
+        .bind(serde_json::json!({
+            "failed_login_attempts": previous.failed_login_attempts,
+            "locked_until": previous.locked_until,
+        }))
+        .bind(unlocked_by)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
 }
@@ -0,0 +1,103 @@
+//! Valuation Repository
+
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::{AssetDepreciationPosition, AssetValuation};
+
+#[derive(Clone)]
+pub struct ValuationRepository {
+    pool: PgPool,
+}
+
+impl ValuationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Valuation history of an asset, newest first
+    pub async fn list_by_asset(
+        &self,
+        asset_id: Uuid,
+        valuation_type: Option<&str>,
+    ) -> Result<Vec<AssetValuation>, sqlx::Error> {
+        sqlx::query_as::<_, AssetValuation>(
+            r#"
+            SELECT * FROM asset_valuations
+            WHERE asset_id = $1 AND ($2::text IS NULL OR valuation_type = $2)
+            ORDER BY valuation_date DESC, created_at DESC
+            "#,
+        )
+        .bind(asset_id)
+        .bind(valuation_type)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn create(&self, valuation: &AssetValuation) -> Result<AssetValuation, sqlx::Error> {
+        sqlx::query_as::<_, AssetValuation>(
+            r#"
+            INSERT INTO asset_valuations (
+                id, asset_id, valuation_date, original_cost, accumulated_depreciation,
+                book_value, market_value, replacement_cost, valuation_type, appraiser, notes,
+                revalued_amount, adjustment_amount, created_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            RETURNING *
+            "#,
+        )
+        .bind(valuation.id)
+        .bind(valuation.asset_id)
+        .bind(valuation.valuation_date)
+        .bind(valuation.original_cost)
+        .bind(valuation.accumulated_depreciation)
+        .bind(valuation.book_value)
+        .bind(valuation.market_value)
+        .bind(valuation.replacement_cost)
+        .bind(&valuation.valuation_type)
+        .bind(&valuation.appraiser)
+        .bind(&valuation.notes)
+        .bind(valuation.revalued_amount)
+        .bind(valuation.adjustment_amount)
+        .bind(valuation.created_by)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Record (or refresh) the calculated snapshot of each position at a date
+    pub async fn upsert_calculated(
+        &self,
+        as_of: NaiveDate,
+        positions: &[AssetDepreciationPosition],
+    ) -> Result<usize, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        for position in positions {
+            sqlx::query(
+                r#"
+                INSERT INTO asset_valuations (
+                    asset_id, valuation_date, original_cost, accumulated_depreciation,
+                    book_value, valuation_type
+                )
+                VALUES ($1, $2, $3, $4, $5, 'calculated')
+                ON CONFLICT (asset_id, valuation_date, valuation_type) DO UPDATE
+                SET original_cost = EXCLUDED.original_cost,
+                    accumulated_depreciation = EXCLUDED.accumulated_depreciation,
+                    book_value = EXCLUDED.book_value,
+                    updated_at = NOW()
+                "#,
+            )
+            .bind(position.asset_id)
+            .bind(as_of)
+            .bind(position.purchase_price)
+            .bind(position.accumulated_depreciation)
+            .bind(position.book_value)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(positions.len())
+    }
+}
@@ -0,0 +1,266 @@
+//! Webhook Repository
+//!
+//! Webhook subscriptions and their delivery log. A delivery is claimed for an
+//! attempt by pushing its `next_attempt_at` past the attempt's timeout, so two
+//! instances never send it at the same time and an attempt cut short by a
+//! restart is picked up again by the retry job.
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::{WebhookDelivery, WebhookSubscription};
+
+#[derive(Clone)]
+pub struct WebhookRepository {
+    pool: PgPool,
+}
+
+impl WebhookRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // ==================== SUBSCRIPTIONS ====================
+
+    pub async fn list_subscriptions(&self) -> Result<Vec<WebhookSubscription>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookSubscription>(
+            "SELECT * FROM webhook_subscriptions ORDER BY name",
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn list_active_subscriptions(&self) -> Result<Vec<WebhookSubscription>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookSubscription>(
+            "SELECT * FROM webhook_subscriptions WHERE is_active = TRUE",
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn find_subscription(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<WebhookSubscription>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookSubscription>(
+            "SELECT * FROM webhook_subscriptions WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    pub async fn create_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Result<WebhookSubscription, sqlx::Error> {
+        sqlx::query_as::<_, WebhookSubscription>(
+            r#"
+            INSERT INTO webhook_subscriptions (
+                id, name, url, event_types, secret, is_active, max_attempts, created_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            "#,
+        )
+        .bind(subscription.id)
+        .bind(&subscription.name)
+        .bind(&subscription.url)
+        .bind(&subscription.event_types)
+        .bind(&subscription.secret)
+        .bind(subscription.is_active)
+        .bind(subscription.max_attempts)
+        .bind(subscription.created_by)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn update_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Result<WebhookSubscription, sqlx::Error> {
+        sqlx::query_as::<_, WebhookSubscription>(
+            r#"
+            UPDATE webhook_subscriptions SET
+                name = $2, url = $3, event_types = $4, secret = $5, is_active = $6,
+                max_attempts = $7, updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(subscription.id)
+        .bind(&subscription.name)
+        .bind(&subscription.url)
+        .bind(&subscription.event_types)
+        .bind(&subscription.secret)
+        .bind(subscription.is_active)
+        .bind(subscription.max_attempts)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Remove a subscription with its delivery log
+    pub async fn delete_subscription(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM webhook_subscriptions WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ==================== DELIVERIES ====================
+
+    /// Log an event for a subscription. Returns None if the event was already
+    /// logged for it, as happens when the outbox relays an event again.
+    pub async fn enqueue_delivery(
+        &self,
+        subscription_id: Uuid,
+        event_id: Uuid,
+        event_type: &str,
+        payload: &serde_json::Value,
+    ) -> Result<Option<WebhookDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            INSERT INTO webhook_deliveries (subscription_id, event_id, event_type, payload)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (subscription_id, event_id) DO NOTHING
+            RETURNING *
+            "#,
+        )
+        .bind(subscription_id)
+        .bind(event_id)
+        .bind(event_type)
+        .bind(payload)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    pub async fn find_delivery(&self, id: Uuid) -> Result<Option<WebhookDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookDelivery>("SELECT * FROM webhook_deliveries WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Reserve a due delivery for one attempt of at most `lease_seconds`.
+    /// Returns None if it is not due or another attempt holds it.
+    pub async fn claim_delivery(
+        &self,
+        id: Uuid,
+        lease_seconds: i64,
+    ) -> Result<Option<WebhookDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            UPDATE webhook_deliveries
+            SET next_attempt_at = NOW() + make_interval(secs => $2), updated_at = NOW()
+            WHERE id = $1 AND status IN ('pending', 'retrying') AND next_attempt_at <= NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(lease_seconds as f64)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Reserve up to `limit` due deliveries, oldest first
+    pub async fn claim_due_deliveries(
+        &self,
+        limit: i64,
+        lease_seconds: i64,
+    ) -> Result<Vec<WebhookDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            UPDATE webhook_deliveries
+            SET next_attempt_at = NOW() + make_interval(secs => $2), updated_at = NOW()
+            WHERE id IN (
+                SELECT id FROM webhook_deliveries
+                WHERE status IN ('pending', 'retrying') AND next_attempt_at <= NOW()
+                ORDER BY next_attempt_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *
+            "#,
+        )
+        .bind(limit)
+        .bind(lease_seconds as f64)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Store the result of a delivery attempt
+    pub async fn record_attempt(
+        &self,
+        id: Uuid,
+        status: &str,
+        status_code: Option<i32>,
+        error: Option<&str>,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<WebhookDelivery, sqlx::Error> {
+        sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            UPDATE webhook_deliveries SET
+                status = $2,
+                attempts = attempts + 1,
+                last_status_code = $3,
+                last_error = $4,
+                next_attempt_at = $5,
+                sent_at = CASE WHEN $2 = 'sent' THEN NOW() ELSE sent_at END,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(status_code)
+        .bind(error)
+        .bind(next_attempt_at)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Give a failed delivery a fresh set of attempts, due now
+    pub async fn reset_delivery(&self, id: Uuid) -> Result<Option<WebhookDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'pending', attempts = 0, next_attempt_at = NOW(), updated_at = NOW()
+            WHERE id = $1 AND status IN ('retrying', 'dead')
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    pub async fn list_deliveries(
+        &self,
+        subscription_id: Option<Uuid>,
+        status: Option<&str>,
+        event_type: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<WebhookDelivery>, sqlx::Error> {
+        sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            SELECT * FROM webhook_deliveries
+            WHERE ($1::uuid IS NULL OR subscription_id = $1)
+              AND ($2::varchar IS NULL OR status = $2)
+              AND ($3::varchar IS NULL OR event_type = $3)
+            ORDER BY created_at DESC
+            LIMIT $4 OFFSET $5
+            "#,
+        )
+        .bind(subscription_id)
+        .bind(status)
+        .bind(event_type)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+    }
+}
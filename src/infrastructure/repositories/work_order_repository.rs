@@ -3,7 +3,9 @@
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::domain::entities::{ChecklistItem, WorkOrder, WorkOrderPart};
+use crate::domain::entities::{ChecklistItem, DataScope, WorkOrder, WorkOrderPart};
+use crate::domain::events::{EventEnvelope, WorkOrderCompleted};
+use crate::infrastructure::repositories::OutboxRepository;
 
 #[derive(Clone)]
 pub struct WorkOrderRepository {
@@ -29,16 +31,43 @@ impl WorkOrderRepository {
             .await
     }
 
-    pub async fn list(&self, limit: i64, offset: i64) -> Result<Vec<WorkOrder>, sqlx::Error> {
+    /// Whether a work order is within the data scope of a user
+    pub async fn in_data_scope(&self, id: Uuid, scope: &DataScope) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM maintenance_work_orders
+                WHERE id = $1
+                  AND asset_in_data_scope(asset_id, $2, ARRAY[assigned_technician, created_by])
+            )
+            "#,
+        )
+        .bind(id)
+        .bind(scope)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    pub async fn list(
+        &self,
+        limit: i64,
+        offset: i64,
+        org_scope: Option<Uuid>,
+        scope: &DataScope,
+    ) -> Result<Vec<WorkOrder>, sqlx::Error> {
         sqlx::query_as::<_, WorkOrder>(
             r#"
             SELECT * FROM maintenance_work_orders
+            WHERE ($3::uuid IS NULL OR asset_in_organization(asset_id, $3))
+              AND asset_in_data_scope(asset_id, $4, ARRAY[assigned_technician, created_by])
             ORDER BY created_at DESC
             LIMIT $1 OFFSET $2
             "#,
         )
         .bind(limit)
         .bind(offset)
+        .bind(org_scope)
+        .bind(scope)
         .fetch_all(&self.pool)
         .await
     }
@@ -64,22 +93,42 @@ impl WorkOrderRepository {
         .await
     }
 
-    pub async fn list_pending(&self) -> Result<Vec<WorkOrder>, sqlx::Error> {
+    pub async fn list_pending(
+        &self,
+        org_scope: Option<Uuid>,
+        scope: &DataScope,
+    ) -> Result<Vec<WorkOrder>, sqlx::Error> {
         sqlx::query_as::<_, WorkOrder>(
-            "SELECT * FROM maintenance_work_orders WHERE status = 'pending' ORDER BY priority DESC, created_at"
+            r#"
+            SELECT * FROM maintenance_work_orders
+            WHERE status = 'pending'
+              AND ($1::uuid IS NULL OR asset_in_organization(asset_id, $1))
+              AND asset_in_data_scope(asset_id, $2, ARRAY[assigned_technician, created_by])
+            ORDER BY priority DESC, created_at
+            "#,
         )
+        .bind(org_scope)
+        .bind(scope)
         .fetch_all(&self.pool)
         .await
     }
 
-    pub async fn list_overdue(&self) -> Result<Vec<WorkOrder>, sqlx::Error> {
+    pub async fn list_overdue(
+        &self,
+        org_scope: Option<Uuid>,
+        scope: &DataScope,
+    ) -> Result<Vec<WorkOrder>, sqlx::Error> {
         sqlx::query_as::<_, WorkOrder>(
             r#"
             SELECT * FROM maintenance_work_orders
             WHERE due_date < CURRENT_DATE AND status NOT IN ('completed', 'cancelled')
+              AND ($1::uuid IS NULL OR asset_in_organization(asset_id, $1))
+              AND asset_in_data_scope(asset_id, $2, ARRAY[assigned_technician, created_by])
             ORDER BY priority DESC, due_date
             "#,
         )
+        .bind(org_scope)
+        .bind(scope)
         .fetch_all(&self.pool)
         .await
     }
@@ -91,9 +140,10 @@ impl WorkOrderRepository {
                 id, wo_number, asset_id, wo_type, priority, status,
                 scheduled_date, due_date, assigned_technician, vendor_id,
                 estimated_hours, estimated_cost, problem_description,
-                safety_requirements, lockout_tagout_required, created_by, location_id
+                safety_requirements, lockout_tagout_required, created_by, location_id,
+                preventive_schedule_id
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
             RETURNING *
             "#,
         )
@@ -114,17 +164,46 @@ impl WorkOrderRepository {
         .bind(wo.lockout_tagout_required)
         .bind(wo.created_by)
         .bind(wo.location_id)
+        .bind(wo.preventive_schedule_id)
         .fetch_one(&self.pool)
         .await
     }
 
+    /// Check whether a preventive schedule already has a work order in flight
+    pub async fn has_open_for_schedule(&self, schedule_id: Uuid) -> Result<bool, sqlx::Error> {
+        let row: (bool,) = sqlx::query_as(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM maintenance_work_orders
+                WHERE preventive_schedule_id = $1 AND status NOT IN ('completed', 'cancelled')
+            )
+            "#,
+        )
+        .bind(schedule_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.0)
+    }
+
     pub async fn update_status(&self, id: Uuid, status: &str) -> Result<bool, sqlx::Error> {
+        Self::update_status_with(&self.pool, id, status).await
+    }
+
+    /// Update the status on a given connection or transaction
+    pub(crate) async fn update_status_with<'e, E>(
+        executor: E,
+        id: Uuid,
+        status: &str,
+    ) -> Result<bool, sqlx::Error>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
         let result = sqlx::query(
             "UPDATE maintenance_work_orders SET status = $2, updated_at = NOW() WHERE id = $1",
         )
         .bind(id)
         .bind(status)
-        .execute(&self.pool)
+        .execute(executor)
         .await?;
         Ok(result.rows_affected() > 0)
     }
@@ -162,13 +241,16 @@ impl WorkOrderRepository {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Complete a work order in progress; the completion event is stored with it
     pub async fn complete(
         &self,
         id: Uuid,
         completed_by: Uuid,
         work_performed: &str,
         actual_cost: Option<rust_decimal::Decimal>,
+        event: EventEnvelope<WorkOrderCompleted>,
     ) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
         let result = sqlx::query(
             r#"
             UPDATE maintenance_work_orders 
@@ -181,9 +263,16 @@ impl WorkOrderRepository {
         .bind(completed_by)
         .bind(work_performed)
         .bind(actual_cost)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
-        Ok(result.rows_affected() > 0)
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        OutboxRepository::append(&mut tx, event).await?;
+
+        tx.commit().await?;
+        Ok(true)
     }
 
     // Checklist methods
@@ -0,0 +1,60 @@
+//! Local file storage for private files
+//!
+//! Files are kept under `DOCUMENT_STORAGE_DIR` (default `storage/documents`),
+//! which, unlike `uploads/`, is not served statically; they are only reachable
+//! through endpoints that check permissions.
+
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(
+            std::env::var("DOCUMENT_STORAGE_DIR")
+                .unwrap_or_else(|_| "storage/documents".to_string()),
+        )
+    }
+
+    pub async fn save(&self, key: &str, data: &[u8]) -> std::io::Result<()> {
+        let path = self.resolve(key)?;
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        tokio::fs::write(path, data).await
+    }
+
+    pub async fn read(&self, key: &str) -> std::io::Result<Vec<u8>> {
+        tokio::fs::read(self.resolve(key)?).await
+    }
+
+    /// Remove a file; a file that is already gone is not an error
+    pub async fn remove(&self, key: &str) -> std::io::Result<()> {
+        match tokio::fs::remove_file(self.resolve(key)?).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Keys are relative paths without `..`
+    fn resolve(&self, key: &str) -> std::io::Result<PathBuf> {
+        let relative = Path::new(key);
+        if relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Invalid storage key: {}", key),
+            ));
+        }
+        Ok(self.root.join(relative))
+    }
+}
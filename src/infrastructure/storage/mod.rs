@@ -1,3 +1,6 @@
 //! Storage Module
 
+pub mod local_storage;
 pub mod s3_client;
+
+pub use local_storage::LocalStorage;
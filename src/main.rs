@@ -51,7 +51,11 @@ async fn main() {
     tracing::info!("Migrations applied successfully.");
 
     // JWT configuration
-    let jwt_config = JwtConfig::new(config.jwt_secret.clone(), config.jwt_expiry_hours);
+    let jwt_config = JwtConfig::new(
+        config.jwt_secret.clone(),
+        config.jwt_access_expiry_minutes,
+        config.jwt_refresh_expiry_days,
+    );
 
     // Create application state
     let state = AppState::new(pool, jwt_config);
@@ -59,6 +63,9 @@ async fn main() {
     // Start scheduler
     let _ = state.scheduler_service.start().await;
 
+    // Deliver outbox events
+    state.outbox_relay.start();
+
     // Create application
     let app = create_app(state);
 
@@ -73,5 +80,11 @@ async fn main() {
         .await
         .expect("Failed to bind to address");
 
-    axum::serve(listener, app).await.expect("Server error");
+    // Peer addresses feed the rate limiter and login audit
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .expect("Server error");
 }
@@ -9,7 +9,8 @@ pub struct AppConfig {
     pub server_host: String,
     pub server_port: u16,
     pub jwt_secret: String,
-    pub jwt_expiry_hours: i64,
+    pub jwt_access_expiry_minutes: i64,
+    pub jwt_refresh_expiry_days: i64,
     pub environment: String,
 }
 
@@ -27,10 +28,14 @@ impl AppConfig {
                 .expect("SERVER_PORT must be a number"),
             jwt_secret: env::var("JWT_SECRET")
                 .unwrap_or_else(|_| "super-secret-key-change-in-production".to_string()),
-            jwt_expiry_hours: env::var("JWT_EXPIRY_HOURS")
-                .unwrap_or_else(|_| "24".to_string())
+            jwt_access_expiry_minutes: env::var("JWT_ACCESS_EXPIRY_MINUTES")
+                .unwrap_or_else(|_| "15".to_string())
                 .parse()
-                .unwrap_or(24),
+                .unwrap_or(15),
+            jwt_refresh_expiry_days: env::var("JWT_REFRESH_EXPIRY_DAYS")
+                .unwrap_or_else(|_| "14".to_string())
+                .parse()
+                .unwrap_or(14),
             environment: env::var("ENVIRONMENT").unwrap_or_else(|_| "development".to_string()),
         }
     }
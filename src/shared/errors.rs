@@ -1,7 +1,7 @@
 //! Application Error Types
 
 use axum::{
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
@@ -47,6 +47,13 @@ struct ErrorResponse {
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        let retry_after = match &self {
+            Self::Domain(DomainError::TooManyRequests {
+                retry_after_secs, ..
+            }) => Some(*retry_after_secs),
+            _ => None,
+        };
+
         let (status, code, message) = match self {
             Self::Domain(DomainError::NotFound { entity, id }) => (
                 StatusCode::NOT_FOUND,
@@ -69,7 +76,13 @@ impl IntoResponse for AppError {
                 format!("Cannot transition from '{}' to '{}'", from, to),
             ),
             Self::Domain(DomainError::Unauthorized { action }) => {
-                if action == "Invalid credentials" || action == "Account is disabled" {
+                if matches!(
+                    action.as_str(),
+                    "Invalid credentials"
+                        | "Account is disabled"
+                        | "Invalid refresh token"
+                        | "Invalid two-factor code"
+                ) {
                     (StatusCode::UNAUTHORIZED, "UNAUTHORIZED", action)
                 } else {
                     (
@@ -85,6 +98,9 @@ impl IntoResponse for AppError {
             Self::Domain(DomainError::BadRequest { message }) => {
                 (StatusCode::BAD_REQUEST, "BAD_REQUEST", message)
             }
+            Self::Domain(DomainError::TooManyRequests { message, .. }) => {
+                (StatusCode::TOO_MANY_REQUESTS, "TOO_MANY_REQUESTS", message)
+            }
             Self::Domain(DomainError::ExternalServiceError { service, message }) => (
                 StatusCode::SERVICE_UNAVAILABLE,
                 "SERVICE_ERROR",
@@ -109,7 +125,10 @@ impl IntoResponse for AppError {
             code: code.to_string(),
         });
 
-        (status, body).into_response()
+        match retry_after {
+            Some(secs) => (status, [(header::RETRY_AFTER, secs.to_string())], body).into_response(),
+            None => (status, body).into_response(),
+        }
     }
 }
 
@@ -27,3 +27,48 @@ pub fn verify_password(password: &str, hash: &str) -> bool {
         .verify_password(password.as_bytes(), &parsed_hash)
         .is_ok()
 }
+
+/// Random opaque token (refresh tokens); 32 bytes, hex encoded
+pub fn generate_token() -> String {
+    use argon2::password_hash::rand_core::RngCore;
+
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// SHA-256 of an opaque token, the form it is stored and looked up in
+pub fn hash_token(token: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Random TOTP secret: 20 bytes (the HMAC-SHA1 block), base32 encoded
+pub fn generate_totp_secret() -> String {
+    use argon2::password_hash::rand_core::RngCore;
+
+    let mut bytes = [0u8; 20];
+    OsRng.fill_bytes(&mut bytes);
+    data_encoding::BASE32_NOPAD.encode(&bytes)
+}
+
+/// One-time recovery code, e.g. `K7QF2-XM4PD` (50 bits)
+pub fn generate_recovery_code() -> String {
+    use argon2::password_hash::rand_core::RngCore;
+
+    let mut bytes = [0u8; 7];
+    OsRng.fill_bytes(&mut bytes);
+    let code = data_encoding::BASE32_NOPAD.encode(&bytes);
+    format!("{}-{}", &code[..5], &code[5..10])
+}
+
+/// New API key `amk_<8 hex>_<64 hex>`; the part before the second `_` is its
+/// public prefix
+pub fn generate_api_key() -> String {
+    use argon2::password_hash::rand_core::RngCore;
+
+    let mut id = [0u8; 4];
+    OsRng.fill_bytes(&mut id);
+    format!("amk_{}_{}", hex::encode(id), generate_token())
+}
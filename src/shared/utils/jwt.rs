@@ -3,27 +3,34 @@
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use serde::{de::DeserializeOwned, Serialize};
 
+/// Access tokens are short-lived; sessions are kept alive with refresh tokens
 #[derive(Debug, Clone)]
 pub struct JwtConfig {
     pub secret: String,
-    pub expiry_hours: i64,
+    pub access_expiry_minutes: i64,
+    pub refresh_expiry_days: i64,
 }
 
 impl JwtConfig {
-    pub fn new(secret: String, expiry_hours: i64) -> Self {
+    pub fn new(secret: String, access_expiry_minutes: i64, refresh_expiry_days: i64) -> Self {
         Self {
             secret,
-            expiry_hours,
+            access_expiry_minutes,
+            refresh_expiry_days,
         }
     }
 
     pub fn from_env() -> Self {
         Self {
             secret: std::env::var("JWT_SECRET").unwrap_or_else(|_| "super-secret-key".to_string()),
-            expiry_hours: std::env::var("JWT_EXPIRY_HOURS")
-                .unwrap_or_else(|_| "24".to_string())
+            access_expiry_minutes: std::env::var("JWT_ACCESS_EXPIRY_MINUTES")
+                .unwrap_or_else(|_| "15".to_string())
                 .parse()
-                .unwrap_or(24),
+                .unwrap_or(15),
+            refresh_expiry_days: std::env::var("JWT_REFRESH_EXPIRY_DAYS")
+                .unwrap_or_else(|_| "14".to_string())
+                .parse()
+                .unwrap_or(14),
         }
     }
 }
@@ -0,0 +1,289 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use common::login;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+async fn setup_test_app() -> (Router, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret".to_string());
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: jwt_secret,
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+
+    let state = asset_management::api::server::AppState::new(pool.clone(), jwt_config);
+    (asset_management::api::server::create_app(state), pool)
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let body = body.map(|b| Body::from(b.to_string())).unwrap_or_default();
+
+    let response = app
+        .clone()
+        .oneshot(builder.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, json)
+}
+
+#[tokio::test]
+async fn test_service_account_scoped_key_lifecycle() {
+    let (app, pool) = setup_test_app().await;
+    let admin = login(&app, &pool, "admin@example.com").await;
+
+    // 1. A service account with the super admin role
+    let (role_id, organization_id): (Uuid, Option<Uuid>) = sqlx::query_as(
+        "SELECT (SELECT id FROM roles WHERE code = 'super_admin'), organization_id FROM users WHERE email = 'admin@example.com'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/service-accounts",
+        Some(&admin),
+        Some(json!({ "name": "ERP integration", "role_id": role_id, "organization_id": organization_id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "service account failed: {:?}", json);
+    let account_id = json["data"]["id"].as_str().unwrap().to_string();
+    let account_email = json["data"]["email"].as_str().unwrap().to_string();
+
+    // It cannot log in with a password
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/auth/login",
+        None,
+        Some(json!({ "email": account_email, "password": "!" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // 2. Scopes must be permissions of the account's role
+    let keys_uri = format!("/api/service-accounts/{}/api-keys", account_id);
+    let (status, _) = send(
+        &app,
+        "POST",
+        &keys_uri,
+        Some(&admin),
+        Some(json!({ "name": "bad", "scopes": ["no.such_permission"] })),
+    )
+    .await;
+    assert!(status.is_client_error(), "unexpected {}", status);
+
+    let (status, json) = send(
+        &app,
+        "POST",
+        &keys_uri,
+        Some(&admin),
+        Some(json!({ "name": "ERP sync", "scopes": ["insurance.read"], "expires_in_days": 30 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "key failed: {:?}", json);
+    let key_id = json["data"]["id"].as_str().unwrap().to_string();
+    let key = json["data"]["key"].as_str().unwrap().to_string();
+    assert!(key.starts_with(json["data"]["prefix"].as_str().unwrap()));
+    assert!(json["data"].get("key_hash").is_none());
+
+    // 3. The key works within its scopes only
+    let (status, json) = send(&app, "GET", "/api/insurances/expiring", Some(&key), None).await;
+    assert_eq!(status, StatusCode::OK, "scoped call failed: {:?}", json);
+    let (status, _) = send(
+        &app,
+        "PUT",
+        &format!("/api/insurances/{}", Uuid::new_v4()),
+        Some(&key),
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(&app, "GET", "/api/users", Some(&key), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/me/api-keys",
+        Some(&key),
+        Some(json!({ "name": "minted", "scopes": ["insurance.read"] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (last_used,): (Option<chrono::DateTime<chrono::Utc>>,) =
+        sqlx::query_as("SELECT last_used_at FROM api_keys WHERE id = $1::uuid")
+            .bind(&key_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(last_used.is_some());
+
+    // 4. Rotation replaces the secret, revocation disables the key
+    let (status, json) = send(
+        &app,
+        "POST",
+        &format!("/api/api-keys/{}/rotate", key_id),
+        Some(&admin),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "rotate failed: {:?}", json);
+    let rotated = json["data"]["key"].as_str().unwrap().to_string();
+    let (status, _) = send(&app, "GET", "/api/insurances/expiring", Some(&key), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(
+        &app,
+        "GET",
+        "/api/insurances/expiring",
+        Some(&rotated),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = send(
+        &app,
+        "DELETE",
+        &format!("/api/api-keys/{}", key_id),
+        Some(&admin),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(
+        &app,
+        "GET",
+        "/api/insurances/expiring",
+        Some(&rotated),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // 5. Everything is attributed to the key in the audit log
+    let actions: Vec<String> = sqlx::query_scalar(
+        "SELECT action FROM audit_logs WHERE api_key_id = $1::uuid ORDER BY created_at",
+    )
+    .bind(&key_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    for action in [
+        "API_KEY_CREATED",
+        "API_KEY_REQUEST",
+        "API_KEY_ROTATED",
+        "API_KEY_REVOKED",
+    ] {
+        assert!(actions.iter().any(|a| a == action), "{} missing", action);
+    }
+}
+
+#[tokio::test]
+async fn test_super_admin_owned_key_does_not_act_as_super_admin() {
+    let (app, pool) = setup_test_app().await;
+    let admin = login(&app, &pool, "admin@example.com").await;
+
+    // A super admin service account outside any organization, with a narrow key
+    let role_id: Uuid = sqlx::query_scalar("SELECT id FROM roles WHERE code = 'super_admin'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/service-accounts",
+        Some(&admin),
+        Some(json!({ "name": "Reporting export", "role_id": role_id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "service account failed: {:?}", json);
+    let account_id = json["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, json) = send(
+        &app,
+        "POST",
+        &format!("/api/service-accounts/{}/api-keys", account_id),
+        Some(&admin),
+        Some(json!({ "name": "export", "scopes": ["insurance.read"] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "key failed: {:?}", json);
+    let key = json["data"]["key"].as_str().unwrap().to_string();
+
+    // No organization bypass: the key has no active organization
+    let (status, _) = send(&app, "GET", "/api/insurances/expiring", Some(&key), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // No super admin signature on approvals
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/approvals/requests",
+        Some(&admin),
+        Some(json!({
+            "resource_type": "asset",
+            "resource_id": Uuid::new_v4(),
+            "action_type": "UPDATE",
+            "data": { "name": "Signed by a key" }
+        })),
+    )
+    .await;
+    assert_eq!(
+        status,
+        StatusCode::OK,
+        "approval request failed: {:?}",
+        json
+    );
+    let request_id = json["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        &format!("/api/approvals/{}/approve", request_id),
+        Some(&key),
+        Some(json!({})),
+    )
+    .await;
+    assert!(status.is_client_error(), "unexpected {}", status);
+
+    let signatures: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM approval_request_actions WHERE request_id = $1::uuid",
+    )
+    .bind(&request_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(signatures, 0);
+}
@@ -0,0 +1,187 @@
+use asset_management::api::server::AppState;
+use asset_management::application::services::{
+    ApprovalExecutor, ApprovalExecutorRegistry, ApprovalSubmission, ExecutionOutcome,
+};
+use asset_management::domain::errors::{DomainError, DomainResult};
+use asset_management::infrastructure::repositories::approval_repository::ApprovalRequest;
+use serde_json::json;
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+async fn setup_state() -> (AppState, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: "test-secret".to_string(),
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+    (AppState::new(pool.clone(), jwt_config), pool)
+}
+
+async fn admin_id(pool: &PgPool) -> Uuid {
+    sqlx::query_scalar("SELECT id FROM users WHERE email = 'admin@example.com'")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+async fn create_asset(pool: &PgPool, name: &str) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO assets (asset_code, name, category_id, status, organization_id)
+        SELECT $1, $2, '44444444-4444-4444-4444-444444444401', 'in_inventory', organization_id
+        FROM users WHERE email = 'admin@example.com'
+        RETURNING id
+        "#,
+    )
+    .bind(format!("AX-{}", &Uuid::new_v4().simple().to_string()[..10]))
+    .bind(name)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+async fn submit(
+    state: &AppState,
+    resource_type: &str,
+    resource_id: Uuid,
+    action_type: &str,
+    requested_by: Uuid,
+    data: serde_json::Value,
+) -> ApprovalRequest {
+    match state
+        .approval_service
+        .create_request(
+            resource_type,
+            resource_id,
+            action_type,
+            requested_by,
+            Some(data),
+            None,
+        )
+        .await
+        .unwrap()
+    {
+        ApprovalSubmission::Pending(request) => *request,
+        ApprovalSubmission::NotRequired => {
+            panic!("{} {} needs no approval", resource_type, action_type)
+        }
+    }
+}
+
+/// Sign every level as a super admin until the request is decided
+async fn sign_all(
+    registry: &ApprovalExecutorRegistry,
+    id: Uuid,
+    approver: Uuid,
+) -> ApprovalRequest {
+    for _ in 0..5 {
+        let request = registry.approve(id, approver, 1, None).await.unwrap();
+        if request.status != "PENDING" {
+            return request;
+        }
+    }
+    panic!("request {} still pending", id);
+}
+
+#[tokio::test]
+async fn test_approved_request_is_applied_with_its_approval() {
+    let (state, pool) = setup_state().await;
+    let admin = admin_id(&pool).await;
+    let asset_id = create_asset(&pool, "Before approval").await;
+
+    let request = submit(
+        &state,
+        "asset",
+        asset_id,
+        "UPDATE",
+        admin,
+        json!({ "name": "After approval" }),
+    )
+    .await;
+    let request = sign_all(&state.approval_executors, request.id, admin).await;
+
+    assert_eq!(request.status, "APPROVED");
+    assert!(request.executed_at.is_some());
+    assert!(request.execution_error.is_none());
+    assert_eq!(
+        request.execution_result.as_ref().unwrap()["name"],
+        "After approval"
+    );
+
+    let name: String = sqlx::query_scalar("SELECT name FROM assets WHERE id = $1")
+        .bind(asset_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(name, "After approval");
+}
+
+/// Writes through the approval's transaction, then refuses the change
+struct RefusingExecutor;
+
+#[async_trait::async_trait]
+impl ApprovalExecutor for RefusingExecutor {
+    async fn apply(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        request: &ApprovalRequest,
+        _approved_by: Uuid,
+    ) -> DomainResult<ExecutionOutcome> {
+        sqlx::query("UPDATE assets SET name = 'Half applied' WHERE id = $1")
+            .bind(request.resource_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| DomainError::Database(e.to_string()))?;
+        Err(DomainError::business_rule(
+            "asset",
+            "Refused after a partial write",
+        ))
+    }
+}
+
+#[tokio::test]
+async fn test_failed_apply_rolls_back_the_change() {
+    let (state, pool) = setup_state().await;
+    let admin = admin_id(&pool).await;
+    let asset_id = create_asset(&pool, "Untouched").await;
+
+    let resource_type = format!("test_{}", &Uuid::new_v4().simple().to_string()[..8]);
+    let registry = ApprovalExecutorRegistry::new(state.approval_service.clone()).register(
+        &resource_type,
+        "*",
+        RefusingExecutor,
+    );
+
+    let request = submit(&state, &resource_type, asset_id, "UPDATE", admin, json!({})).await;
+    let request = sign_all(&registry, request.id, admin).await;
+
+    assert_eq!(request.status, "FAILED");
+    assert!(request
+        .execution_error
+        .as_deref()
+        .unwrap()
+        .contains("Refused after a partial write"));
+
+    // The executor's write is gone; the signatures and the outcome are kept
+    let name: String = sqlx::query_scalar("SELECT name FROM assets WHERE id = $1")
+        .bind(asset_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(name, "Untouched");
+
+    let signed: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM approval_request_actions WHERE request_id = $1 AND action = 'approved'",
+    )
+    .bind(request.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert!(signed >= 1);
+}
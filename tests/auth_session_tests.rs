@@ -0,0 +1,195 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+async fn setup_test_app() -> (Router, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret".to_string());
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: jwt_secret,
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+
+    let state = asset_management::api::server::AppState::new(pool.clone(), jwt_config);
+    (asset_management::api::server::create_app(state), pool)
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let body = body.map(|b| Body::from(b.to_string())).unwrap_or_default();
+
+    let response = app
+        .clone()
+        .oneshot(builder.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, json)
+}
+
+/// A throwaway staff user sharing the admin password, so revoking its
+/// sessions does not disturb the other tests
+async fn create_user(pool: &PgPool) -> (Uuid, String) {
+    let id = Uuid::new_v4();
+    let email = format!("session-{}@example.com", id.simple());
+    sqlx::query(
+        r#"
+        INSERT INTO users (id, email, password_hash, name, role, role_id, organization_id)
+        SELECT $1, $2, a.password_hash, 'Session Test', 'staff',
+               (SELECT id FROM roles WHERE code = 'staff'), a.organization_id
+        FROM users a WHERE a.email = 'admin@example.com'
+        "#,
+    )
+    .bind(id)
+    .bind(&email)
+    .execute(pool)
+    .await
+    .unwrap();
+    (id, email)
+}
+
+async fn login(app: &Router, email: &str) -> (String, String) {
+    let (status, json) = send(
+        app,
+        "POST",
+        "/api/auth/login",
+        None,
+        Some(json!({ "email": email, "password": "admin123" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "login failed: {:?}", json);
+    assert!(json["expires_in"].as_i64().unwrap() > 0);
+    (
+        json["token"].as_str().unwrap().to_string(),
+        json["refresh_token"].as_str().unwrap().to_string(),
+    )
+}
+
+async fn refresh(app: &Router, refresh_token: &str) -> (StatusCode, Value) {
+    send(
+        app,
+        "POST",
+        "/api/auth/refresh",
+        None,
+        Some(json!({ "refresh_token": refresh_token })),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_refresh_rotation_and_reuse_detection() {
+    let (app, pool) = setup_test_app().await;
+    let (_, email) = create_user(&pool).await;
+    let (access, first_refresh) = login(&app, &email).await;
+
+    let (status, _) = send(&app, "GET", "/api/me", Some(&access), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 1. A refresh token is exchanged for a new pair
+    let (status, json) = refresh(&app, &first_refresh).await;
+    assert_eq!(status, StatusCode::OK, "refresh failed: {:?}", json);
+    let second_access = json["token"].as_str().unwrap().to_string();
+    let second_refresh = json["refresh_token"].as_str().unwrap().to_string();
+    assert_ne!(second_refresh, first_refresh);
+
+    let (status, _) = send(&app, "GET", "/api/me", Some(&second_access), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 2. Presenting the used token again revokes the whole session
+    let (status, _) = refresh(&app, &first_refresh).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = refresh(&app, &second_refresh).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(&app, "GET", "/api/me", Some(&second_access), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = refresh(&app, "not-a-token").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_logout_and_revocation() {
+    let (app, pool) = setup_test_app().await;
+    let (user_id, email) = create_user(&pool).await;
+
+    // 1. Logout ends only the current session
+    let (access, refresh_token) = login(&app, &email).await;
+    let (other_access, _) = login(&app, &email).await;
+
+    let (status, _) = send(&app, "POST", "/api/auth/logout", Some(&access), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(&app, "GET", "/api/me", Some(&access), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = refresh(&app, &refresh_token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(&app, "GET", "/api/me", Some(&other_access), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 2. Deactivating the user revokes the remaining session before expiry
+    let admin_token = common::login(&app, &pool, "admin@example.com").await;
+
+    let (status, json) = send(
+        &app,
+        "PUT",
+        &format!("/api/users/{}", user_id),
+        Some(&admin_token),
+        Some(json!({ "is_active": false })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "deactivate failed: {:?}", json);
+    let (status, _) = send(&app, "GET", "/api/me", Some(&other_access), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // 3. Reactivated, a fresh login works until an admin revokes all sessions
+    sqlx::query("UPDATE users SET is_active = true WHERE id = $1")
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (access, refresh_token) = login(&app, &email).await;
+
+    let (status, json) = send(
+        &app,
+        "POST",
+        &format!("/api/users/{}/revoke-sessions", user_id),
+        Some(&admin_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "revoke failed: {:?}", json);
+    assert_eq!(json["data"], json!(1));
+    let (status, _) = send(&app, "GET", "/api/me", Some(&access), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = refresh(&app, &refresh_token).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
@@ -0,0 +1,330 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use common::login;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+async fn setup_test_app() -> (Router, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret".to_string());
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: jwt_secret,
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+
+    let state = asset_management::api::server::AppState::new(pool.clone(), jwt_config);
+    (asset_management::api::server::create_app(state), pool)
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let body = body.map(|b| Body::from(b.to_string())).unwrap_or_default();
+
+    let response = app
+        .clone()
+        .oneshot(builder.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, json)
+}
+
+async fn switch_to(app: &Router, token: &str, organization_id: &str) -> String {
+    let (status, json) = send(
+        app,
+        "POST",
+        "/api/auth/switch-organization",
+        Some(token),
+        Some(json!({ "organization_id": organization_id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "switch failed: {:?}", json);
+    json["token"].as_str().unwrap().to_string()
+}
+
+async fn create_work_order(app: &Router, token: &str, asset_id: &str, estimate: &str) -> String {
+    // Work order numbers are per second
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+    let (status, json) = send(
+        app,
+        "POST",
+        "/api/work-orders",
+        Some(token),
+        Some(json!({
+            "asset_id": asset_id,
+            "wo_type": "corrective",
+            "estimated_cost": estimate
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "create WO failed: {:?}", json);
+    json["data"]["id"].as_str().unwrap().to_string()
+}
+
+async fn budget_line(app: &Router, token: &str, line_id: &str) -> Value {
+    let (status, json) = send(app, "GET", "/api/budgets/report", Some(token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    json["data"]["lines"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|l| l["budget_line_id"] == line_id)
+        .cloned()
+        .expect("line missing from report")
+}
+
+fn amount(value: &Value) -> f64 {
+    match value {
+        Value::String(s) => s.parse().unwrap(),
+        other => other.as_f64().unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn test_work_order_spend_against_budget() {
+    let (app, pool) = setup_test_app().await;
+    let suffix = &Uuid::new_v4().simple().to_string()[..8];
+
+    let root_token = login(&app, &pool, "admin@example.com").await;
+    let (admin_id,): (Uuid,) =
+        sqlx::query_as("SELECT id FROM users WHERE email = 'admin@example.com'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+    let (_, me) = send(
+        &app,
+        "GET",
+        "/api/me/organizations",
+        Some(&root_token),
+        None,
+    )
+    .await;
+    let root_id = me["data"][0]["id"].as_str().unwrap().to_string();
+
+    // 1. A cost center with an asset
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/organizations",
+        Some(&root_token),
+        Some(json!({
+            "code": format!("BUD-{}", suffix),
+            "name": format!("Budget Division {}", suffix),
+            "parent_id": root_id,
+            "org_type": "division",
+            "cost_center": format!("CC-{}", suffix)
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "create org failed: {:?}", json);
+    let org_id = json["data"]["id"].as_str().unwrap().to_string();
+    let token = switch_to(&app, &root_token, &org_id).await;
+
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/assets",
+        Some(&token),
+        Some(json!({
+            "name": "Budgeted Compressor",
+            "asset_code": format!("BUD-{}", suffix),
+            "category_id": "44444444-4444-4444-4444-444444444401",
+            "status": "in_inventory"
+        })),
+    )
+    .await;
+    assert_eq!(
+        status,
+        StatusCode::CREATED,
+        "create asset failed: {:?}",
+        json
+    );
+    let asset_id = json["data"]["id"].as_str().unwrap().to_string();
+
+    // 2. A blocking budget line for the current year
+    let year = chrono::Utc::now().format("%Y").to_string();
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/budgets",
+        Some(&token),
+        Some(json!({
+            "name": "Maintenance",
+            "period_start": format!("{}-01-01", year),
+            "period_end": format!("{}-12-31", year),
+            "amount": "1000"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "create budget failed: {:?}", json);
+    let line_id = json["data"]["id"].as_str().unwrap().to_string();
+
+    // 3. Over budget is blocked; within budget commits the estimate
+    let big = create_work_order(&app, &token, &asset_id, "1500").await;
+    let (status, _) = send(
+        &app,
+        "POST",
+        &format!("/api/work-orders/{}/approve", big),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let small = create_work_order(&app, &token, &asset_id, "400").await;
+    let (status, json) = send(
+        &app,
+        "POST",
+        &format!("/api/work-orders/{}/approve", small),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "approve failed: {:?}", json);
+    let line = budget_line(&app, &token, &line_id).await;
+    assert_eq!(amount(&line["committed"]), 400.0);
+    assert_eq!(amount(&line["available"]), 600.0);
+
+    // 4. Completion posts the actual and releases the commitment
+    send(
+        &app,
+        "POST",
+        &format!("/api/work-orders/{}/assign/{}", small, admin_id),
+        Some(&token),
+        None,
+    )
+    .await;
+    send(
+        &app,
+        "POST",
+        &format!("/api/work-orders/{}/start", small),
+        Some(&token),
+        None,
+    )
+    .await;
+    let (status, json) = send(
+        &app,
+        "POST",
+        &format!("/api/work-orders/{}/complete", small),
+        Some(&token),
+        Some(json!({ "work_performed": "Replaced valve", "actual_cost": "450" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "complete failed: {:?}", json);
+    let line = budget_line(&app, &token, &line_id).await;
+    assert_eq!(amount(&line["committed"]), 0.0);
+    assert_eq!(amount(&line["actual"]), 450.0);
+
+    // 5. An escalating line turns the overrun into an approval request
+    let (status, _) = send(
+        &app,
+        "PUT",
+        &format!("/api/budgets/{}", line_id),
+        Some(&token),
+        Some(json!({ "over_budget_action": "escalate" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, json) = send(
+        &app,
+        "POST",
+        &format!("/api/work-orders/{}/approve", big),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(
+        status,
+        StatusCode::ACCEPTED,
+        "escalation failed: {:?}",
+        json
+    );
+    assert_eq!(json["data"]["resource_type"], "budget");
+    let request_id = json["data"]["id"].as_str().unwrap().to_string();
+
+    // Sign every level of the workflow
+    for _ in 0..json["data"]["total_levels"].as_i64().unwrap() {
+        let (status, json) = send(
+            &app,
+            "POST",
+            &format!("/api/approvals/{}/approve", request_id),
+            Some(&token),
+            Some(json!({})),
+        )
+        .await;
+        assert_eq!(
+            status,
+            StatusCode::OK,
+            "overrun approval failed: {:?}",
+            json
+        );
+    }
+    let (_, json) = send(
+        &app,
+        "GET",
+        &format!("/api/work-orders/{}", big),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(json["status"], "approved");
+    let line = budget_line(&app, &token, &line_id).await;
+    assert_eq!(amount(&line["committed"]), 1500.0);
+    assert_eq!(amount(&line["available"]), -950.0);
+
+    // Cleanup
+    sqlx::query("DELETE FROM budget_entries WHERE budget_line_id = $1::uuid")
+        .bind(&line_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM approval_requests WHERE id = $1::uuid")
+        .bind(&request_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM budget_lines WHERE id = $1::uuid")
+        .bind(&line_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM assets WHERE id = $1::uuid")
+        .bind(&asset_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM organizations WHERE id = $1::uuid")
+        .bind(&org_id)
+        .execute(&pool)
+        .await
+        .ok();
+}
@@ -0,0 +1,215 @@
+mod common;
+
+use asset_management::api::server::{create_app, AppState};
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use common::login;
+use futures::StreamExt;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{tungstenite::Message, MaybeTlsStream, WebSocketStream};
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// One backend instance; every call starts another one against the same
+/// database and Redis
+async fn setup_test_app() -> (Router, AppState, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret".to_string());
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: jwt_secret,
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+
+    let state = AppState::new(pool.clone(), jwt_config);
+    (create_app(state.clone()), state, pool)
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let body = body.map(|b| Body::from(b.to_string())).unwrap_or_default();
+
+    let response = app
+        .clone()
+        .oneshot(builder.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, json)
+}
+
+/// Serve the app on a local port, for WebSocket clients
+async fn serve(app: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    format!("ws://{}/ws", addr)
+}
+
+async fn next_json(socket: &mut Socket) -> Value {
+    loop {
+        let msg = tokio::time::timeout(Duration::from_secs(5), socket.next())
+            .await
+            .expect("no message within 5s")
+            .expect("socket closed")
+            .unwrap();
+        if let Message::Text(text) = msg {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+/// Whether both instances reach Redis
+async fn cluster_connected(states: [&AppState; 2]) -> bool {
+    for _ in 0..30 {
+        if states.iter().all(|s| s.cluster.is_connected()) {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    false
+}
+
+#[tokio::test]
+#[ignore = "needs Redis at REDIS_URL; run with --ignored"]
+async fn test_events_reach_sockets_on_every_instance() {
+    let (app_a, state_a, pool) = setup_test_app().await;
+    let (app_b, state_b, _) = setup_test_app().await;
+    let url_a = serve(app_a.clone()).await;
+    let url_b = serve(app_b.clone()).await;
+    assert_ne!(state_a.cluster.node_id(), state_b.cluster.node_id());
+    assert!(
+        cluster_connected([&state_a, &state_b]).await,
+        "both instances must reach Redis at REDIS_URL"
+    );
+
+    // 1. A technician connected to each instance
+    let tech_id = Uuid::new_v4();
+    let email = format!("cluster-{}@example.com", tech_id.simple());
+    sqlx::query(
+        r#"
+        INSERT INTO users (id, email, password_hash, name, role, role_id, organization_id)
+        SELECT $1, $2, a.password_hash, 'Cluster Test', 'technician',
+               (SELECT id FROM roles WHERE code = 'technician'), a.organization_id
+        FROM users a WHERE a.email = 'admin@example.com'
+        "#,
+    )
+    .bind(tech_id)
+    .bind(&email)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let tech_token = login(&app_a, &pool, &email).await;
+    let admin_token = login(&app_b, &pool, "admin@example.com").await;
+
+    let mut sockets = Vec::new();
+    for url in [&url_a, &url_b] {
+        let (mut socket, _) =
+            tokio_tungstenite::connect_async(format!("{}?token={}", url, tech_token))
+                .await
+                .unwrap();
+        assert_eq!(next_json(&mut socket).await["type"], "authenticated");
+        sockets.push(socket);
+    }
+    let mut tech_b = sockets.pop().unwrap();
+    let mut tech_a = sockets.pop().unwrap();
+
+    // 2. A work order assigned through instance B
+    let asset_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO assets (asset_code, name, category_id, status, organization_id)
+        SELECT $1, $1, '44444444-4444-4444-4444-444444444401', 'in_inventory', organization_id
+        FROM users WHERE email = 'admin@example.com'
+        RETURNING id
+        "#,
+    )
+    .bind(format!("CL-{}", &tech_id.simple().to_string()[..8]))
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let (status, json) = send(
+        &app_b,
+        "POST",
+        "/api/work-orders",
+        Some(&admin_token),
+        Some(json!({ "asset_id": asset_id, "wo_type": "corrective" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{:?}", json);
+    let wo_id = json["data"]["id"].as_str().unwrap().to_string();
+
+    let mut events = state_a.event_publisher.subscribe();
+    let (status, json) = send(
+        &app_b,
+        "POST",
+        &format!("/api/work-orders/{}/assign/{}", wo_id, tech_id),
+        Some(&admin_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", json);
+
+    // 3. The event reaches the socket on B and, through Redis, the one on A
+    for socket in [&mut tech_b, &mut tech_a] {
+        let event = next_json(socket).await;
+        assert_eq!(event["event_type"], "WORK_ORDER_ASSIGNED", "{:?}", event);
+        assert_eq!(event["payload"]["id"], wo_id);
+    }
+
+    state_b
+        .event_publisher
+        .publish("CLUSTER_TEST", &json!({ "id": wo_id }))
+        .ok();
+    let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+        .await
+        .expect("no event within 5s")
+        .unwrap();
+    assert!(event.starts_with("CLUSTER_TEST:"), "{}", event);
+
+    // Cleanup
+    sqlx::query("DELETE FROM maintenance_work_orders WHERE id = $1::uuid")
+        .bind(&wo_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM assets WHERE id = $1")
+        .bind(asset_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(tech_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
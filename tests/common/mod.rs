@@ -0,0 +1,78 @@
+//! Login helpers shared by the API test crates
+
+use asset_management::shared::utils::crypto::hash_token;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+/// Log in with the seeded password and return the access token
+pub async fn login(app: &Router, pool: &PgPool, email: &str) -> String {
+    let (status, json) = post(
+        app,
+        "/api/auth/login",
+        json!({ "email": email, "password": "admin123" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "login failed: {:?}", json);
+    let json = pass_two_factor(app, pool, email, json).await;
+    json["token"].as_str().unwrap().to_string()
+}
+
+/// Finish the login challenge of a role flagged `requires_two_factor`, with a
+/// recovery code added for this login
+pub async fn pass_two_factor(app: &Router, pool: &PgPool, email: &str, login: Value) -> Value {
+    if login["two_factor_required"] != true {
+        return login;
+    }
+    let code = Uuid::new_v4().simple().to_string().to_uppercase();
+    sqlx::query(
+        r#"
+        WITH enrolled AS (
+            INSERT INTO user_two_factor (user_id, secret, enabled_at)
+            SELECT id, 'JBSWY3DPEHPK3PXP', NOW() FROM users WHERE email = $1
+            ON CONFLICT (user_id) DO UPDATE
+            SET enabled_at = COALESCE(user_two_factor.enabled_at, NOW())
+            RETURNING user_id
+        )
+        INSERT INTO user_recovery_codes (user_id, code_hash)
+        SELECT user_id, $2 FROM enrolled
+        "#,
+    )
+    .bind(email)
+    .bind(hash_token(&code))
+    .execute(pool)
+    .await
+    .unwrap();
+
+    let (status, json) = post(
+        app,
+        "/api/auth/2fa/verify",
+        json!({ "challenge_token": login["challenge_token"], "code": code }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "2FA verify failed: {:?}", json);
+    json
+}
+
+async fn post(app: &Router, uri: &str, body: Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
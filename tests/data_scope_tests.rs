@@ -0,0 +1,279 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use common::login;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+async fn setup_test_app() -> (Router, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret".to_string());
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: jwt_secret,
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+
+    let state = asset_management::api::server::AppState::new(pool.clone(), jwt_config);
+    (asset_management::api::server::create_app(state), pool)
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let body = body.map(|b| Body::from(b.to_string())).unwrap_or_default();
+
+    let response = app
+        .clone()
+        .oneshot(builder.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, json)
+}
+
+async fn insert_department(pool: &PgPool, code: &str, parent_id: Option<Uuid>) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO departments (code, name, parent_id) VALUES ($1, $1, $2) RETURNING id",
+    )
+    .bind(code)
+    .bind(parent_id)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+async fn insert_asset(
+    pool: &PgPool,
+    code: &str,
+    department_id: Uuid,
+    assigned_to: Option<Uuid>,
+) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO assets (asset_code, name, category_id, department_id, assigned_to, status, organization_id)
+        SELECT $1, $1, '44444444-4444-4444-4444-444444444401', $2, $3, 'in_inventory', organization_id
+        FROM users WHERE email = 'admin@example.com'
+        RETURNING id
+        "#,
+    )
+    .bind(code)
+    .bind(department_id)
+    .bind(assigned_to)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_department_scope_limits_manager() {
+    let (app, pool) = setup_test_app().await;
+    let suffix = &Uuid::new_v4().simple().to_string()[..8];
+
+    // 1. A manager of a department with a sub-department, and a second department
+    let parent = insert_department(&pool, &format!("DS-P-{}", suffix), None).await;
+    let child = insert_department(&pool, &format!("DS-C-{}", suffix), Some(parent)).await;
+    let other = insert_department(&pool, &format!("DS-O-{}", suffix), None).await;
+
+    let manager_id = Uuid::new_v4();
+    let email = format!("scope-{}@example.com", manager_id.simple());
+    sqlx::query(
+        r#"
+        INSERT INTO users (id, email, password_hash, name, role, role_id, department_id, organization_id)
+        SELECT $1, $2, a.password_hash, 'Scope Test', 'manager',
+               (SELECT id FROM roles WHERE code = 'manager'), $3, a.organization_id
+        FROM users a WHERE a.email = 'admin@example.com'
+        "#,
+    )
+    .bind(manager_id)
+    .bind(&email)
+    .bind(parent)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let in_child = insert_asset(&pool, &format!("DS-{}-CHILD", suffix), child, None).await;
+    let outside = insert_asset(&pool, &format!("DS-{}-OTHER", suffix), other, None).await;
+    let assigned = insert_asset(
+        &pool,
+        &format!("DS-{}-MINE", suffix),
+        other,
+        Some(manager_id),
+    )
+    .await;
+
+    let loan_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO asset_loans (loan_number, asset_id, borrower_id, loan_date, expected_return_date)
+        SELECT $1, $2, id, CURRENT_DATE, CURRENT_DATE + 7 FROM users WHERE email = 'admin@example.com'
+        RETURNING id
+        "#,
+    )
+    .bind(format!("DS-L-{}", suffix))
+    .bind(outside)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let token = login(&app, &pool, &email).await;
+
+    // 2. The department subtree and assigned assets are listed, others are not
+    let (status, json) = send(
+        &app,
+        "GET",
+        &format!("/api/assets/search?query=DS-{}", suffix),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", json);
+    let mut codes: Vec<&str> = json["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|a| a["asset_code"].as_str().unwrap())
+        .collect();
+    codes.sort();
+    assert_eq!(
+        codes,
+        vec![
+            format!("DS-{}-CHILD", suffix),
+            format!("DS-{}-MINE", suffix)
+        ]
+    );
+
+    // 3. Records outside the scope are not found, for reads and writes
+    for id in [in_child, assigned] {
+        let (status, _) = send(
+            &app,
+            "GET",
+            &format!("/api/assets/{}", id),
+            Some(&token),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    let (status, _) = send(
+        &app,
+        "GET",
+        &format!("/api/assets/{}", outside),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(
+        &app,
+        "PUT",
+        &format!("/api/assets/{}", outside),
+        Some(&token),
+        Some(json!({ "name": "Renamed" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    // The services enforce the scope on every path, not only the asset routes
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/mobile/audit",
+        Some(&token),
+        Some(json!({ "asset_id": outside, "notes": "Audited" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(
+        &app,
+        "GET",
+        &format!("/api/loans/{}", loan_id),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // 4. Admins see everything and the effective scope of the manager
+    let admin_token = login(&app, &pool, "admin@example.com").await;
+    let (status, _) = send(
+        &app,
+        "GET",
+        &format!("/api/loans/{}", loan_id),
+        Some(&admin_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, json) = send(
+        &app,
+        "GET",
+        &format!("/api/users/{}/permissions", manager_id),
+        Some(&admin_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", json);
+    let scopes_of = |code: &str| {
+        json.as_array()
+            .unwrap()
+            .iter()
+            .find(|p| p["code"] == code)
+            .map(|p| p["scopes"].clone())
+    };
+    assert_eq!(scopes_of("asset.read"), Some(json!(["department"])));
+    assert_eq!(scopes_of("work_order.read"), Some(json!(["department"])));
+    assert_eq!(scopes_of("loan.approve"), Some(json!(["department"])));
+    assert_eq!(scopes_of("report.export"), Some(json!(["department"])));
+    assert_eq!(scopes_of("asset.delete"), None);
+
+    // Cleanup
+    sqlx::query("DELETE FROM asset_loans WHERE id = $1")
+        .bind(loan_id)
+        .execute(&pool)
+        .await
+        .ok();
+    for id in [in_child, outside, assigned] {
+        sqlx::query("DELETE FROM assets WHERE id = $1")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .ok();
+    }
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(manager_id)
+        .execute(&pool)
+        .await
+        .ok();
+    for id in [child, parent, other] {
+        sqlx::query("DELETE FROM departments WHERE id = $1")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .ok();
+    }
+}
@@ -0,0 +1,360 @@
+mod common;
+
+use asset_management::api::server::{create_app, AppState};
+use asset_management::application::dto::{
+    CreateClientRequest, CreateRentalRequest, GenerateInvoiceRequest,
+};
+use asset_management::application::services::CreateWorkOrderRequest;
+use asset_management::domain::errors::DomainError;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use common::login;
+use futures::future::join_all;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::collections::HashSet;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+async fn setup_test_app() -> (Router, AppState, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret".to_string());
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: jwt_secret,
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+
+    let state = AppState::new(pool.clone(), jwt_config);
+    (create_app(state.clone()), state, pool)
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let body = body.map(|b| Body::from(b.to_string())).unwrap_or_default();
+
+    let response = app
+        .clone()
+        .oneshot(builder.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, json)
+}
+
+/// Counter value at the end of a number
+fn counter(number: &str) -> i64 {
+    number.rsplit('-').next().unwrap().parse().unwrap()
+}
+
+fn work_order_request(asset_id: Uuid, location_id: Option<Uuid>) -> CreateWorkOrderRequest {
+    serde_json::from_value(json!({
+        "asset_id": asset_id,
+        "wo_type": "inspection",
+        "location_id": location_id
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_document_numbers_are_unique_formatted_and_gap_free_for_invoices() {
+    let (app, state, pool) = setup_test_app().await;
+    let token = login(&app, &pool, "admin@example.com").await;
+    let admin_id: Uuid =
+        sqlx::query_scalar("SELECT id FROM users WHERE email = 'admin@example.com'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    let suffix = &Uuid::new_v4().simple().to_string()[..8];
+    let year = chrono::Utc::now().format("%Y").to_string();
+    let month = chrono::Utc::now().format("%Y%m").to_string();
+
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/assets",
+        Some(&token),
+        Some(json!({
+            "name": "Numbered Compressor",
+            "asset_code": format!("NUM-{}", suffix),
+            "category_id": "44444444-4444-4444-4444-444444444401",
+            "status": "in_inventory"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{:?}", json);
+    let asset_id = Uuid::parse_str(json["data"]["id"].as_str().unwrap()).unwrap();
+
+    // 1. Documents created at the same moment get distinct, consecutive numbers
+    let work_orders = join_all((0..10).map(|_| {
+        state
+            .work_order_service
+            .create(work_order_request(asset_id, None), None)
+    }))
+    .await;
+    let numbers: Vec<String> = work_orders
+        .into_iter()
+        .map(|wo| wo.unwrap().wo_number)
+        .collect();
+    let distinct: HashSet<&String> = numbers.iter().collect();
+    assert_eq!(distinct.len(), 10, "{:?}", numbers);
+    let prefix = format!("WO-{}-", year);
+    assert!(
+        numbers.iter().all(|n| n.starts_with(&prefix)),
+        "{:?}",
+        numbers
+    );
+    let mut values: Vec<i64> = numbers.iter().map(|n| counter(n)).collect();
+    values.sort();
+    assert_eq!(values.last().unwrap() - values[0], 9, "{:?}", values);
+
+    let clients = join_all((0..5).map(|i| {
+        state.rental_service.create_client(CreateClientRequest {
+            name: format!("Numbering Client {} {}", suffix, i),
+            company_name: None,
+            email: None,
+            phone: None,
+            address: None,
+            city: None,
+            contact_person: None,
+            tax_id: None,
+            notes: None,
+        })
+    }))
+    .await;
+    let clients: Vec<_> = clients.into_iter().map(|c| c.unwrap()).collect();
+    let codes: HashSet<&String> = clients.iter().map(|c| &c.client_code).collect();
+    assert_eq!(codes.len(), 5);
+    // Client codes never restart
+    assert!(
+        codes.iter().all(|c| c.starts_with("CLT-") && c.len() == 9),
+        "{:?}",
+        codes
+    );
+
+    // 2. Formats are admin-only and validated
+    let user_token = login(&app, &pool, "user@example.com").await;
+    let (status, _) = send(
+        &app,
+        "GET",
+        "/api/document-numbers",
+        Some(&user_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, json) = send(
+        &app,
+        "GET",
+        "/api/document-numbers/work_order",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", json);
+    let original = json["data"].clone();
+
+    let (status, _) = send(
+        &app,
+        "PUT",
+        "/api/document-numbers/work_order",
+        Some(&token),
+        Some(json!({ "reset_period": "weekly" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // 3. Location segment, monthly reset and shorter padding
+    let (status, json) = send(
+        &app,
+        "PUT",
+        "/api/document-numbers/work_order",
+        Some(&token),
+        Some(json!({ "segment": "location", "reset_period": "monthly", "padding": 3 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", json);
+    assert_eq!(json["data"]["updated_by"], admin_id.to_string());
+
+    let location_id = Uuid::parse_str("33333333-3333-3333-3333-333333333301").unwrap();
+    let wo = state
+        .work_order_service
+        .create(work_order_request(asset_id, Some(location_id)), None)
+        .await
+        .unwrap();
+    let prefix = format!("WO-GEDUNGA-{}-", month);
+    assert!(wo.wo_number.starts_with(&prefix), "{}", wo.wo_number);
+    assert!(wo.wo_number.len() >= prefix.len() + 3, "{}", wo.wo_number);
+    let created_ids: Vec<Uuid> = vec![wo.id];
+
+    let (status, _) = send(
+        &app,
+        "PUT",
+        "/api/document-numbers/work_order",
+        Some(&token),
+        Some(json!({
+            "segment": original["segment"],
+            "reset_period": original["reset_period"],
+            "padding": original["padding"]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 4. Invoices: consecutive, and numbers of failed invoices are given back
+    let rental = state
+        .rental_service
+        .create_rental(
+            CreateRentalRequest {
+                asset_id,
+                client_id: clients[0].id,
+                start_date: None,
+                expected_end_date: None,
+                daily_rate: None,
+                deposit_amount: None,
+                notes: None,
+            },
+            admin_id,
+        )
+        .await
+        .unwrap();
+    assert!(rental.rental_number.starts_with(&format!("RNT-{}-", year)));
+
+    let mut billing_ids = Vec::new();
+    for month in 1..=4 {
+        let id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO rental_billing_periods (rental_id, period_start, period_end, status)
+            VALUES ($1, make_date(2025, $2, 1), make_date(2025, $2, 28), 'approved')
+            RETURNING id
+            "#,
+        )
+        .bind(rental.id)
+        .bind(month)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        billing_ids.push(id);
+    }
+
+    let invoices = join_all(billing_ids[..3].iter().map(|id| {
+        state.billing_service.generate_invoice(
+            *id,
+            GenerateInvoiceRequest {
+                invoice_number: None,
+                due_days: None,
+            },
+        )
+    }))
+    .await;
+    let invoices: Vec<String> = invoices.into_iter().map(|i| i.unwrap()).collect();
+    assert!(invoices
+        .iter()
+        .all(|n| n.starts_with(&format!("INV-{}-", year))));
+    let mut values: Vec<i64> = invoices.iter().map(|n| counter(n)).collect();
+    values.sort();
+    assert_eq!(values, [values[0], values[0] + 1, values[0] + 2]);
+
+    // Invoicing twice and reusing a number both fail without using a number up
+    let again = state
+        .billing_service
+        .generate_invoice(
+            billing_ids[0],
+            GenerateInvoiceRequest {
+                invoice_number: None,
+                due_days: None,
+            },
+        )
+        .await;
+    assert!(matches!(
+        again,
+        Err(DomainError::BusinessRuleViolation { .. })
+    ));
+    let duplicate = state
+        .billing_service
+        .generate_invoice(
+            billing_ids[3],
+            GenerateInvoiceRequest {
+                invoice_number: Some(invoices[0].clone()),
+                due_days: None,
+            },
+        )
+        .await;
+    assert!(
+        matches!(duplicate, Err(DomainError::Conflict { .. })),
+        "{:?}",
+        duplicate
+    );
+
+    let next = state
+        .billing_service
+        .generate_invoice(
+            billing_ids[3],
+            GenerateInvoiceRequest {
+                invoice_number: None,
+                due_days: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(counter(&next), values[0] + 3, "{}", next);
+
+    // Cleanup
+    sqlx::query("DELETE FROM rentals WHERE id = $1")
+        .bind(rental.id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM maintenance_work_orders WHERE asset_id = $1 OR id = ANY($2)")
+        .bind(asset_id)
+        .bind(&created_ids)
+        .execute(&pool)
+        .await
+        .ok();
+    for client in &clients {
+        sqlx::query("DELETE FROM clients WHERE id = $1")
+            .bind(client.id)
+            .execute(&pool)
+            .await
+            .ok();
+    }
+    sqlx::query("DELETE FROM approval_requests WHERE entity_id = $1")
+        .bind(rental.id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM event_outbox WHERE aggregate_id = $1")
+        .bind(asset_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM assets WHERE id = $1")
+        .bind(asset_id)
+        .execute(&pool)
+        .await
+        .ok();
+}
@@ -0,0 +1,335 @@
+mod common;
+
+use asset_management::api::server::{create_app, AppState};
+use asset_management::application::dto::UploadDocumentRequest;
+use asset_management::infrastructure::repositories::DocumentRepository;
+use asset_management::infrastructure::storage::LocalStorage;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use chrono::{Duration, Utc};
+use common::login;
+use http_body_util::BodyExt;
+use sqlx::PgPool;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+async fn setup_test_app() -> (Router, AppState, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret".to_string());
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: jwt_secret,
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+
+    let state = AppState::new(pool.clone(), jwt_config);
+    (create_app(state.clone()), state, pool)
+}
+
+/// Raw response of a download, with its Content-Disposition header
+async fn download(app: &Router, uri: &str, token: &str) -> (StatusCode, Option<String>, Vec<u8>) {
+    let request = Request::builder()
+        .uri(uri)
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let disposition = response
+        .headers()
+        .get(header::CONTENT_DISPOSITION)
+        .map(|v| v.to_str().unwrap().to_string());
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, disposition, bytes.to_vec())
+}
+
+/// A user with the admin password and the given role
+async fn create_user(pool: &PgPool, role: &str) -> String {
+    let id = Uuid::new_v4();
+    let email = format!("doc-{}@example.com", id.simple());
+    sqlx::query(
+        r#"
+        INSERT INTO users (id, email, password_hash, name, role, role_id, organization_id)
+        SELECT $1, $2, a.password_hash, 'Document Test', $3,
+               (SELECT id FROM roles WHERE code = $3), a.organization_id
+        FROM users a WHERE a.email = 'admin@example.com'
+        "#,
+    )
+    .bind(id)
+    .bind(&email)
+    .bind(role)
+    .execute(pool)
+    .await
+    .unwrap();
+    email
+}
+
+async fn admin_id(pool: &PgPool) -> Uuid {
+    sqlx::query_scalar("SELECT id FROM users WHERE email = 'admin@example.com'")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+async fn create_asset(pool: &PgPool) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO assets (asset_code, name, category_id, status, organization_id)
+        SELECT $1, 'Document Test Asset', '44444444-4444-4444-4444-444444444401', 'in_inventory',
+               organization_id
+        FROM users WHERE email = 'admin@example.com'
+        RETURNING id
+        "#,
+    )
+    .bind(format!(
+        "DOC-{}",
+        &Uuid::new_v4().simple().to_string()[..10]
+    ))
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+fn upload_request(
+    name: Option<&str>,
+    type_: Option<&str>,
+    expires_in_days: Option<i64>,
+    data: &[u8],
+) -> UploadDocumentRequest {
+    UploadDocumentRequest {
+        name: name.map(str::to_string),
+        type_: type_.map(str::to_string),
+        expiry_date: expires_in_days.map(|d| Utc::now().date_naive() + Duration::days(d)),
+        original_name: Some("certificate.pdf".to_string()),
+        mime_type: Some("application/pdf".to_string()),
+        data: data.to_vec(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_replaced_document_is_kept_as_a_version() {
+    let (_app, state, pool) = setup_test_app().await;
+    let admin = admin_id(&pool).await;
+    let asset_id = create_asset(&pool).await;
+    let documents = &state.document_service;
+
+    let first = documents
+        .upload(
+            asset_id,
+            upload_request(Some("Load test"), Some("certificate"), Some(90), b"v1"),
+            admin,
+        )
+        .await
+        .unwrap();
+    assert_eq!(first.version, 1);
+    assert_eq!(first.type_, "CERTIFICATE");
+    assert_eq!(first.document_group_id, first.id);
+
+    // 1. A new version carries over what the upload leaves out
+    let second = documents
+        .replace(
+            asset_id,
+            first.id,
+            upload_request(None, None, None, b"v2"),
+            admin,
+        )
+        .await
+        .unwrap();
+    assert_eq!(second.version, 2);
+    assert!(second.is_current);
+    assert_eq!(second.document_group_id, first.document_group_id);
+    assert_eq!(second.name, "Load test");
+    assert_eq!(second.type_, "CERTIFICATE");
+    assert_eq!(second.expiry_date, first.expiry_date);
+
+    // 2. The previous version is kept, superseded
+    let versions = documents.list_versions(asset_id, second.id).await.unwrap();
+    assert_eq!(versions.len(), 2);
+    let previous = documents.get(asset_id, first.id).await.unwrap();
+    assert!(!previous.is_current);
+    assert_eq!(previous.replaced_by, Some(second.id));
+
+    let current = documents.list_by_asset(asset_id, false).await.unwrap();
+    assert_eq!(
+        current.iter().map(|d| d.id).collect::<Vec<_>>(),
+        vec![second.id]
+    );
+    assert_eq!(
+        documents.list_by_asset(asset_id, true).await.unwrap().len(),
+        2
+    );
+
+    // 3. Each version keeps its own file; only the current one can be replaced
+    let (_, data) = documents.download(asset_id, first.id).await.unwrap();
+    assert_eq!(data, b"v1");
+    let (_, data) = documents.download(asset_id, second.id).await.unwrap();
+    assert_eq!(data, b"v2");
+
+    assert!(documents
+        .replace(
+            asset_id,
+            first.id,
+            upload_request(None, None, None, b"v3"),
+            admin
+        )
+        .await
+        .is_err());
+
+    documents.delete(asset_id, second.id).await.unwrap();
+    assert!(documents.get(asset_id, first.id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_download_requires_asset_read() {
+    let (app, state, pool) = setup_test_app().await;
+    let admin = admin_id(&pool).await;
+    let asset_id = create_asset(&pool).await;
+    let document = state
+        .document_service
+        .upload(
+            asset_id,
+            upload_request(Some("Warranty"), Some("warranty"), None, b"warranty terms"),
+            admin,
+        )
+        .await
+        .unwrap();
+    let uri = format!(
+        "/api/assets/{}/documents/{}/download",
+        asset_id, document.id
+    );
+
+    let admin_token = login(&app, &pool, "admin@example.com").await;
+    let (status, disposition, data) = download(&app, &uri, &admin_token).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(data, b"warranty terms");
+    assert!(disposition.unwrap().starts_with("attachment;"));
+
+    // Staff have no asset.read
+    let email = create_user(&pool, "staff").await;
+    let staff_token = login(&app, &pool, &email).await;
+    let (status, _, data) = download(&app, &uri, &staff_token).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_ne!(data, b"warranty terms");
+
+    // A document is only reachable through its own asset
+    let other_asset = create_asset(&pool).await;
+    let (status, _, _) = download(
+        &app,
+        &format!(
+            "/api/assets/{}/documents/{}/download",
+            other_asset, document.id
+        ),
+        &admin_token,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_storage_rejects_keys_outside_its_root() {
+    let root = std::env::temp_dir().join(format!("documents-{}", Uuid::new_v4().simple()));
+    let storage = LocalStorage::new(&root);
+
+    storage.save("asset/file.pdf", b"inside").await.unwrap();
+    assert_eq!(storage.read("asset/file.pdf").await.unwrap(), b"inside");
+
+    for key in ["../escape.pdf", "asset/../../escape.pdf", "/tmp/escape.pdf"] {
+        let err = storage.save(key, b"outside").await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput, "{}", key);
+        let err = storage.read(key).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput, "{}", key);
+    }
+    assert!(!root.parent().unwrap().join("escape.pdf").exists());
+
+    tokio::fs::remove_dir_all(&root).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_expiring_documents_are_reminded_once() {
+    let (_app, state, pool) = setup_test_app().await;
+    let admin = admin_id(&pool).await;
+    let asset_id = create_asset(&pool).await;
+    let documents = &state.document_service;
+
+    let soon = documents
+        .upload(
+            asset_id,
+            upload_request(Some("Permit"), Some("permit"), Some(5), b"permit"),
+            admin,
+        )
+        .await
+        .unwrap();
+    let later = documents
+        .upload(
+            asset_id,
+            upload_request(Some("Insurance"), Some("certificate"), Some(90), b"policy"),
+            admin,
+        )
+        .await
+        .unwrap();
+    let expired = documents
+        .upload(
+            asset_id,
+            upload_request(Some("Old permit"), Some("permit"), Some(-1), b"old"),
+            admin,
+        )
+        .await
+        .unwrap();
+    // A superseded version no longer counts, only its replacement
+    let superseded = documents
+        .upload(
+            asset_id,
+            upload_request(Some("Inspection"), Some("certificate"), Some(3), b"v1"),
+            admin,
+        )
+        .await
+        .unwrap();
+    let renewed = documents
+        .replace(
+            asset_id,
+            superseded.id,
+            upload_request(None, None, Some(365), b"v2"),
+            admin,
+        )
+        .await
+        .unwrap();
+
+    let expiring: Vec<Uuid> = documents
+        .list_expiring(30)
+        .await
+        .unwrap()
+        .iter()
+        .map(|d| d.id)
+        .collect();
+    assert!(expiring.contains(&soon.id));
+    for id in [later.id, expired.id, superseded.id, renewed.id] {
+        assert!(!expiring.contains(&id));
+    }
+    assert!(documents.list_expiring(-1).await.is_err());
+
+    // The reminder marks the document, so the next run skips it
+    let run = documents.remind_expiring_documents().await.unwrap();
+    assert!(run.reminded >= 1);
+    let reminded = documents.get(asset_id, soon.id).await.unwrap();
+    assert!(reminded.expiry_notified_at.is_some());
+
+    let today = Utc::now().date_naive();
+    let pending = DocumentRepository::new(pool.clone())
+        .list_expiring(today, today + Duration::days(30), false)
+        .await
+        .unwrap();
+    assert!(pending.iter().all(|d| d.id != soon.id));
+
+    // It stays listed for users
+    let expiring = documents.list_expiring(30).await.unwrap();
+    assert!(expiring.iter().any(|d| d.id == soon.id));
+}
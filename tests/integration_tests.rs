@@ -1,10 +1,13 @@
 //! Integration Tests for Asset Management API
 
+mod common;
+
 use axum::{
     body::Body,
     http::{header, Request, StatusCode},
     Router,
 };
+use common::{login, pass_two_factor};
 use http_body_util::BodyExt;
 use serde_json::{json, Value};
 use sqlx::PgPool;
@@ -20,14 +23,15 @@ async fn setup_test_app() -> Router {
         .expect("Failed to create pool");
 
     let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret".to_string());
-    let jwt_expiry = std::env::var("JWT_EXPIRATION_HOURS")
+    let jwt_expiry = std::env::var("JWT_ACCESS_EXPIRY_MINUTES")
         .ok()
         .and_then(|s| s.parse().ok())
-        .unwrap_or(24);
+        .unwrap_or(15);
 
     let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
         secret: jwt_secret,
-        expiry_hours: jwt_expiry,
+        access_expiry_minutes: jwt_expiry,
+        refresh_expiry_days: 14,
     };
 
     let state = asset_management::api::server::AppState::new(pool, jwt_config);
@@ -36,31 +40,11 @@ async fn setup_test_app() -> Router {
 
 /// Helper to login and get token
 async fn get_auth_token(app: Router) -> (String, Router) {
-    let (_parts, body) = app
-        .oneshot(
-            Request::builder()
-                .method("POST")
-                .uri("/api/auth/login")
-                .header("Content-Type", "application/json")
-                .body(Body::from(
-                    json!({
-                        "email": "admin@example.com",
-                        "password": "admin123"
-                    })
-                    .to_string(),
-                ))
-                .unwrap(),
-        )
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
         .await
-        .unwrap()
-        .into_parts();
-
-    let body_bytes = body.collect().await.unwrap().to_bytes();
-    let json: Value = serde_json::from_slice(&body_bytes).unwrap();
-    let token = json["token"].as_str().unwrap().to_string();
-
-    // Need to recreate app since oneshot consumes it
-    let app = setup_test_app().await;
+        .expect("Failed to connect to DB");
+    let token = login(&app, &pool, "admin@example.com").await;
     (token, app)
 }
 
@@ -88,8 +72,13 @@ async fn test_health_check() {
 #[tokio::test]
 async fn test_login_success() {
     let app = setup_test_app().await;
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
 
     let response = app
+        .clone()
         .oneshot(
             Request::builder()
                 .method("POST")
@@ -112,6 +101,7 @@ async fn test_login_success() {
     let body = response.into_body().collect().await.unwrap().to_bytes();
     let json: Value = serde_json::from_slice(&body).unwrap();
     assert_eq!(json["success"], true);
+    let json = pass_two_factor(&app, &pool, "admin@example.com", json).await;
     assert!(json["token"].is_string());
 }
 
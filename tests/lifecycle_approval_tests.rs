@@ -0,0 +1,185 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use common::login;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+async fn setup_test_app() -> (Router, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: "test-secret".to_string(),
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+    let state = asset_management::api::server::AppState::new(pool.clone(), jwt_config);
+    (asset_management::api::server::create_app(state), pool)
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let body = body.map(|b| Body::from(b.to_string())).unwrap_or_default();
+
+    let response = app
+        .clone()
+        .oneshot(builder.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, json)
+}
+
+async fn create_asset(pool: &PgPool, category_id: Uuid) -> Uuid {
+    sqlx::query_scalar(
+        r#"
+        INSERT INTO assets (asset_code, name, category_id, status, organization_id)
+        SELECT $1, 'Lifecycle Approval Asset', $2, 'in_inventory', organization_id
+        FROM users WHERE email = 'admin@example.com'
+        RETURNING id
+        "#,
+    )
+    .bind(format!(
+        "LCA-{}",
+        &Uuid::new_v4().simple().to_string()[..10]
+    ))
+    .bind(category_id)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+}
+
+async fn request_transition(app: &Router, token: &str, asset_id: Uuid, target: &str) -> Value {
+    let (status, json) = send(
+        app,
+        "POST",
+        &format!("/api/assets/{}/lifecycle/request-transition", asset_id),
+        Some(token),
+        Some(json!({ "target_state": target, "reason": "Lifecycle approval test" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "transition failed: {:?}", json);
+    json["data"].clone()
+}
+
+#[tokio::test]
+async fn test_lifecycle_transitions_follow_approval_workflows() {
+    let (app, pool) = setup_test_app().await;
+    let token = login(&app, &pool, "admin@example.com").await;
+
+    let code = format!("LCA{}", &Uuid::new_v4().simple().to_string()[..8]).to_uppercase();
+    let category_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO categories (code, name) VALUES ($1, 'Lifecycle Approval Test') RETURNING id",
+    )
+    .bind(&code)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let asset_id = create_asset(&pool, category_id).await;
+
+    // 1. The seeded workflows gate deployment, nothing else
+    let (status, json) = send(
+        &app,
+        "GET",
+        &format!(
+            "/api/assets/{}/lifecycle/valid-transitions-with-approval",
+            asset_id
+        ),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let states = json["data"].as_array().unwrap();
+    let deployed = states.iter().find(|s| s["value"] == "deployed").unwrap();
+    assert_eq!(deployed["requires_approval"], true);
+    assert_eq!(deployed["approval_level"], 2);
+    let rental = states.iter().find(|s| s["value"] == "rented_out").unwrap();
+    assert_eq!(rental["requires_approval"], false);
+
+    let result = request_transition(&app, &token, asset_id, "deployed").await;
+    assert_eq!(result["result_type"], "ApprovalCreated");
+    let request_id: Uuid = result["approval_request_id"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    let result = request_transition(&app, &token, asset_id, "rented_out").await;
+    assert_eq!(result["result_type"], "Executed");
+    let result = request_transition(&app, &token, asset_id, "in_inventory").await;
+    assert_eq!(result["result_type"], "Executed");
+
+    // 2. A category workflow without approval lets its assets deploy directly
+    let workflow_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO approval_workflows (resource_type, action_type, asset_type, name, need_approval)
+        VALUES ('lifecycle_transition', 'transition_to_deployed', $1, 'Deploy test asset', FALSE)
+        RETURNING id
+        "#,
+    )
+    .bind(&code)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    let result = request_transition(&app, &token, asset_id, "deployed").await;
+    assert_eq!(result["result_type"], "Executed");
+    let status: String = sqlx::query_scalar("SELECT status FROM assets WHERE id = $1")
+        .bind(asset_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(status, "deployed");
+
+    sqlx::query("DELETE FROM approval_requests WHERE id = $1")
+        .bind(request_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM approval_workflows WHERE id = $1")
+        .bind(workflow_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM asset_lifecycle_history WHERE asset_id = $1")
+        .bind(asset_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM assets WHERE id = $1")
+        .bind(asset_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM categories WHERE id = $1")
+        .bind(category_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,116 @@
+use asset_management::api::server::AppState;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn setup_state() -> (AppState, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: "test-secret".to_string(),
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+    (AppState::new(pool.clone(), jwt_config), pool)
+}
+
+async fn create_user(pool: &PgPool) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query(
+        r#"
+        INSERT INTO users (id, email, password_hash, name, role, role_id, organization_id)
+        SELECT $1, $2, a.password_hash, 'Digest Test', 'staff',
+               (SELECT id FROM roles WHERE code = 'staff'), a.organization_id
+        FROM users a WHERE a.email = 'admin@example.com'
+        "#,
+    )
+    .bind(id)
+    .bind(format!("digest-{}@example.com", id.simple()))
+    .execute(pool)
+    .await
+    .unwrap();
+    id
+}
+
+async fn queue_item(pool: &PgPool, user_id: Uuid, subject: &str) {
+    sqlx::query(
+        r#"
+        INSERT INTO notification_deliveries
+            (user_id, event_type, channel, subject, body, status, digest_frequency)
+        VALUES ($1, 'maintenance_due', 'in_app', $2, $2, 'queued', 'hourly')
+        "#,
+    )
+    .bind(user_id)
+    .bind(subject)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_concurrent_digest_runs_send_one_digest() {
+    let (state, pool) = setup_state().await;
+    let user_id = create_user(&pool).await;
+    queue_item(&pool, user_id, "Pump service due").await;
+    queue_item(&pool, user_id, "Boiler inspection due").await;
+
+    // Two runs (e.g. two replicas on the same tick) see the same queued rows
+    let (a, b) = tokio::join!(
+        state.notification_service.send_digests("hourly"),
+        state.notification_service.send_digests("hourly"),
+    );
+    a.unwrap();
+    b.unwrap();
+
+    let digests: Vec<Uuid> = sqlx::query_scalar(
+        r#"
+        SELECT id FROM notification_deliveries
+        WHERE user_id = $1 AND event_type = 'notification.digest'
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(digests.len(), 1);
+
+    let included: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM notification_deliveries
+        WHERE user_id = $1 AND status = 'digested' AND digest_id = $2
+        "#,
+    )
+    .bind(user_id)
+    .bind(digests[0])
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(included, 2);
+
+    // Nothing left to claim: a later run sends nothing for this user
+    state
+        .notification_service
+        .send_digests("hourly")
+        .await
+        .unwrap();
+    let count: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM notification_deliveries
+        WHERE user_id = $1 AND event_type = 'notification.digest'
+        "#,
+    )
+    .bind(user_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(count, 1);
+
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
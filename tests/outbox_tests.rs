@@ -0,0 +1,252 @@
+mod common;
+
+use asset_management::api::server::{create_app, AppState};
+use asset_management::application::services::{EventSubscriber, OutboxRelay};
+use asset_management::domain::entities::OutboxEvent;
+use asset_management::infrastructure::repositories::OutboxRepository;
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    http::{header, HeaderMap, Request, StatusCode},
+    Router,
+};
+use common::login;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::sync::{Arc, Mutex};
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+async fn setup_test_app() -> (Router, AppState, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret".to_string());
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: jwt_secret,
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+
+    let state = AppState::new(pool.clone(), jwt_config);
+    (create_app(state.clone()), state, pool)
+}
+
+async fn send_with_headers(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+    headers: &[(&str, &str)],
+) -> (StatusCode, HeaderMap, Value) {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    for (name, value) in headers {
+        builder = builder.header(*name, *value);
+    }
+    let body = body.map(|b| Body::from(b.to_string())).unwrap_or_default();
+
+    let response = app
+        .clone()
+        .oneshot(builder.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, headers, json)
+}
+
+/// Fails the first event of one aggregate it sees, like a receiver that is down
+struct FlakySubscriber {
+    aggregate_id: Uuid,
+    seen: Mutex<Vec<(Uuid, String)>>,
+}
+
+#[async_trait]
+impl EventSubscriber for FlakySubscriber {
+    fn name(&self) -> &str {
+        "flaky"
+    }
+
+    async fn handle(&self, event: &OutboxEvent) -> Result<(), String> {
+        if event.aggregate_id != self.aggregate_id {
+            return Ok(());
+        }
+        let mut seen = self.seen.lock().unwrap();
+        seen.push((event.id, event.event_type.clone()));
+        if seen.len() == 1 {
+            return Err("receiver down".to_string());
+        }
+        Ok(())
+    }
+}
+
+async fn outbox_rows(pool: &PgPool, aggregate_id: Uuid) -> Vec<OutboxEvent> {
+    OutboxRepository::new(pool.clone())
+        .list_by_aggregate(aggregate_id)
+        .await
+        .unwrap()
+}
+
+/// Relay until every event of the aggregate has been attempted or delivered
+async fn relay_until(
+    relay: &OutboxRelay,
+    pool: &PgPool,
+    aggregate_id: Uuid,
+    done: impl Fn(&OutboxEvent) -> bool,
+) -> Vec<OutboxEvent> {
+    for _ in 0..100 {
+        relay.relay_pending().await.unwrap();
+        let rows = outbox_rows(pool, aggregate_id).await;
+        if rows.iter().all(&done) {
+            return rows;
+        }
+    }
+    panic!("outbox events of {} were not relayed", aggregate_id);
+}
+
+#[tokio::test]
+async fn test_outbox_events_are_stored_with_changes_and_relayed() {
+    let (app, state, pool) = setup_test_app().await;
+    let token = login(&app, &pool, "admin@example.com").await;
+    let admin_id: Uuid =
+        sqlx::query_scalar("SELECT id FROM users WHERE email = 'admin@example.com'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    let suffix = &Uuid::new_v4().simple().to_string()[..8];
+    let correlation_id = format!("corr-{}", suffix);
+
+    // 1. Creating and deploying an asset, in one correlated business transaction
+    let (status, headers, json) = send_with_headers(
+        &app,
+        "POST",
+        "/api/assets",
+        Some(&token),
+        Some(json!({
+            "name": "Outbox Pump",
+            "asset_code": format!("OBX-{}", suffix),
+            "category_id": "44444444-4444-4444-4444-444444444401",
+            "status": "in_inventory"
+        })),
+        &[
+            ("X-Correlation-ID", &correlation_id),
+            ("X-Request-ID", "req-create"),
+        ],
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{:?}", json);
+    assert_eq!(headers["x-correlation-id"], correlation_id.as_str());
+    assert_eq!(headers["x-request-id"], "req-create");
+    let asset_id = Uuid::parse_str(json["data"]["id"].as_str().unwrap()).unwrap();
+
+    let (status, headers, json) = send_with_headers(
+        &app,
+        "POST",
+        &format!("/api/assets/{}/lifecycle/transition", asset_id),
+        Some(&token),
+        Some(json!({ "target_state": "deployed", "reason": "Outbox test" })),
+        &[("X-Correlation-ID", &correlation_id)],
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", json);
+    let transition_request = headers["x-request-id"].to_str().unwrap().to_string();
+    assert!(Uuid::parse_str(&transition_request).is_ok());
+
+    // 2. Both events are in the outbox with ids filled in, not yet delivered
+    let rows = outbox_rows(&pool, asset_id).await;
+    let types: Vec<&str> = rows.iter().map(|r| r.event_type.as_str()).collect();
+    assert_eq!(types, ["asset.created", "asset.state_changed"]);
+    for (row, cause) in rows.iter().zip(["req-create", transition_request.as_str()]) {
+        let metadata = &row.envelope["metadata"];
+        assert_eq!(metadata["correlation_id"], correlation_id.as_str());
+        assert_eq!(metadata["causation_id"], cause);
+        assert_eq!(metadata["user_id"], admin_id.to_string());
+        assert_eq!(row.envelope["id"], row.id.to_string());
+        assert!(row.delivered_at.is_none());
+    }
+    assert_eq!(rows[1].payload()["from_state"], "in_inventory");
+    assert_eq!(rows[1].payload()["to_state"], "deployed");
+
+    // 3. A failing subscriber holds back the event, and the later event of
+    // the same asset waits for it
+    let subscriber = Arc::new(FlakySubscriber {
+        aggregate_id: asset_id,
+        seen: Mutex::new(Vec::new()),
+    });
+    let relay = OutboxRelay::new(
+        OutboxRepository::new(pool.clone()),
+        state.event_publisher.clone(),
+        state.ws_manager.clone(),
+    )
+    .with_subscriber(subscriber.clone());
+
+    let rows = relay_until(&relay, &pool, asset_id, |r| {
+        r.event_type != "asset.created" || r.attempts > 0
+    })
+    .await;
+    assert!(rows.iter().all(|r| r.delivered_at.is_none()), "{:?}", rows);
+    assert_eq!(rows[0].attempts, 1);
+    assert!(rows[0]
+        .last_error
+        .as_deref()
+        .unwrap()
+        .contains("receiver down"));
+    assert!(rows[0].next_attempt_at > chrono::Utc::now());
+    assert_eq!(subscriber.seen.lock().unwrap().len(), 1);
+
+    // 4. Once due again, both are delivered: the first one twice, in order
+    sqlx::query("UPDATE event_outbox SET next_attempt_at = NOW() WHERE aggregate_id = $1")
+        .bind(asset_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let mut events = state.event_publisher.subscribe();
+    let rows = relay_until(&relay, &pool, asset_id, |r| r.delivered_at.is_some()).await;
+    let seen = subscriber.seen.lock().unwrap().clone();
+    assert_eq!(
+        seen,
+        [
+            (rows[0].id, "asset.created".to_string()),
+            (rows[0].id, "asset.created".to_string()),
+            (rows[1].id, "asset.state_changed".to_string()),
+        ]
+    );
+
+    let mut published = Vec::new();
+    while let Ok(message) = events.try_recv() {
+        if message.contains(&asset_id.to_string()) {
+            published.push(message.split(':').next().unwrap().to_string());
+        }
+    }
+    assert_eq!(published, ["asset.created", "asset.state_changed"]);
+
+    // Cleanup
+    sqlx::query("DELETE FROM event_outbox WHERE aggregate_id = $1")
+        .bind(asset_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM asset_lifecycle_history WHERE asset_id = $1")
+        .bind(asset_id)
+        .execute(&pool)
+        .await
+        .ok();
+    sqlx::query("DELETE FROM assets WHERE id = $1")
+        .bind(asset_id)
+        .execute(&pool)
+        .await
+        .ok();
+}
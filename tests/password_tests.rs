@@ -0,0 +1,354 @@
+mod common;
+
+use asset_management::shared::utils::crypto::hash_token;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+async fn setup_test_app() -> (Router, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret".to_string());
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: jwt_secret,
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+
+    let state = asset_management::api::server::AppState::new(pool.clone(), jwt_config);
+    (asset_management::api::server::create_app(state), pool)
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let body = body.map(|b| Body::from(b.to_string())).unwrap_or_default();
+
+    let response = app
+        .clone()
+        .oneshot(builder.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, json)
+}
+
+async fn create_user(pool: &PgPool) -> (Uuid, String) {
+    let id = Uuid::new_v4();
+    let email = format!("password-{}@example.com", id.simple());
+    sqlx::query(
+        r#"
+        INSERT INTO users (id, email, password_hash, name, role, role_id, organization_id)
+        SELECT $1, $2, a.password_hash, 'Password Test', 'staff',
+               (SELECT id FROM roles WHERE code = 'staff'), a.organization_id
+        FROM users a WHERE a.email = 'admin@example.com'
+        "#,
+    )
+    .bind(id)
+    .bind(&email)
+    .execute(pool)
+    .await
+    .unwrap();
+    (id, email)
+}
+
+async fn login(app: &Router, email: &str, password: &str) -> (StatusCode, Value) {
+    send(
+        app,
+        "POST",
+        "/api/auth/login",
+        None,
+        Some(json!({ "email": email, "password": password })),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_password_policy_and_reset_flow() {
+    let (app, pool) = setup_test_app().await;
+    let (user_id, email) = create_user(&pool).await;
+
+    let (status, json) = login(&app, &email, "admin123").await;
+    assert_eq!(status, StatusCode::OK, "login failed: {:?}", json);
+    let token = json["token"].as_str().unwrap().to_string();
+
+    // 1. Weak and common passwords are refused
+    for weak in ["short", "alllowercaseletters", "Password123!"] {
+        let (status, json) = send(
+            &app,
+            "PUT",
+            "/api/me/password",
+            Some(&token),
+            Some(json!({ "old_password": "admin123", "new_password": weak })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}: {:?}", weak, json);
+    }
+    let (status, json) = send(
+        &app,
+        "PUT",
+        "/api/me/password",
+        Some(&token),
+        Some(json!({ "old_password": "admin123", "new_password": "Tr4ctor-Fleet" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "change failed: {:?}", json);
+
+    // 2. Recent passwords may not be reused
+    let (_, json) = login(&app, &email, "Tr4ctor-Fleet").await;
+    let token = json["token"].as_str().unwrap().to_string();
+    let (status, json) = send(
+        &app,
+        "PUT",
+        "/api/me/password",
+        Some(&token),
+        Some(json!({ "old_password": "Tr4ctor-Fleet", "new_password": "Tr4ctor-Fleet" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(json.to_string().contains("reuse"), "{:?}", json);
+
+    // 3. Forgot password answers the same for unknown emails and issues a
+    // token for known ones
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/auth/password/forgot",
+        None,
+        Some(json!({ "email": "nobody-here@example.com" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/auth/password/forgot",
+        None,
+        Some(json!({ "email": email })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (deliveries,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM notification_deliveries WHERE user_id = $1 AND event_type = 'auth.password_reset'",
+    )
+    .bind(user_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(deliveries, 1);
+
+    // The emailed token is only known by its hash; swap in one we know
+    let reset_token = format!("test-reset-{}", Uuid::new_v4());
+    let updated = sqlx::query(
+        "UPDATE password_reset_tokens SET token_hash = $2 WHERE user_id = $1 AND used_at IS NULL",
+    )
+    .bind(user_id)
+    .bind(hash_token(&reset_token))
+    .execute(&pool)
+    .await
+    .unwrap()
+    .rows_affected();
+    assert_eq!(updated, 1);
+
+    // 4. The token sets a new password once and ends existing sessions
+    let reset = json!({ "token": reset_token, "new_password": "Harbor#Crane42" });
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/auth/password/reset",
+        None,
+        Some(reset.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "reset failed: {:?}", json);
+    let (status, _) = send(&app, "POST", "/api/auth/password/reset", None, Some(reset)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = send(&app, "GET", "/api/me", Some(&token), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = login(&app, &email, "Tr4ctor-Fleet").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, json) = login(&app, &email, "Harbor#Crane42").await;
+    assert_eq!(status, StatusCode::OK, "login failed: {:?}", json);
+}
+
+#[tokio::test]
+async fn test_forced_password_change_at_login() {
+    let (app, pool) = setup_test_app().await;
+    let (user_id, email) = create_user(&pool).await;
+
+    let admin_token = common::login(&app, &pool, "admin@example.com").await;
+    let (_, json) = login(&app, &email, "admin123").await;
+    let user_token = json["token"].as_str().unwrap().to_string();
+
+    // 1. An admin forces a reset; the user's sessions end
+    let (status, json) = send(
+        &app,
+        "POST",
+        &format!("/api/users/{}/force-password-reset", user_id),
+        Some(&admin_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "force failed: {:?}", json);
+    let (status, _) = send(&app, "GET", "/api/me", Some(&user_token), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // 2. The password only yields a challenge for the change
+    let (status, challenge) = login(&app, &email, "admin123").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(challenge["password_change_required"], true);
+    assert!(challenge.get("token").is_none());
+    let challenge_token = challenge["challenge_token"].as_str().unwrap();
+
+    // The challenge is not an access token, nor a 2FA challenge
+    let (status, _) = send(&app, "GET", "/api/me", Some(challenge_token), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/auth/2fa/verify",
+        None,
+        Some(json!({ "challenge_token": challenge_token, "code": "000000" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // 3. The new password is subject to the policy
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/auth/password/change",
+        None,
+        Some(json!({ "challenge_token": challenge_token, "new_password": "admin123" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/auth/password/change",
+        None,
+        Some(json!({ "challenge_token": challenge_token, "new_password": "Quarry!Loader7" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "change failed: {:?}", json);
+    assert!(json["token"].as_str().is_some());
+    assert!(json["refresh_token"].as_str().is_some());
+
+    // 4. The next login is a normal one
+    let (status, json) = login(&app, &email, "Quarry!Loader7").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(json["token"].as_str().is_some(), "{:?}", json);
+
+    let (forced,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM audit_logs WHERE record_id = $1 AND action = 'PASSWORD_RESET_FORCED'",
+    )
+    .bind(user_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(forced, 1);
+}
+
+#[tokio::test]
+async fn test_reset_token_is_not_stored_in_delivery_log() {
+    use asset_management::application::services::NotificationService;
+    use asset_management::infrastructure::notifications::{FileSinkChannel, NotificationChannels};
+    use asset_management::infrastructure::repositories::NotificationRepository;
+
+    let (_app, pool) = setup_test_app().await;
+    let (user_id, _) = create_user(&pool).await;
+    // A digest preference must not hold the link back
+    sqlx::query(
+        "INSERT INTO notification_preferences (user_id, event_type, digest_frequency) VALUES ($1, 'auth.password_reset', 'daily')",
+    )
+    .bind(user_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let sink = std::env::temp_dir().join(format!("reset-{}.jsonl", Uuid::new_v4()));
+    let channels =
+        NotificationChannels::new().register("email", FileSinkChannel::new(sink.clone()));
+    let service = NotificationService::new(NotificationRepository::new(pool.clone()), channels);
+
+    let token = format!("secret-{}", Uuid::new_v4());
+    let reset_url = format!("https://app.example.com/reset?token={}", token);
+    let deliveries = service
+        .notify_password_reset(user_id, "Password Test", &token, &reset_url, 30)
+        .await
+        .unwrap();
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].status, "pending");
+
+    // The channel gets the real link
+    let mut sent = String::new();
+    for _ in 0..50 {
+        sent = tokio::fs::read_to_string(&sink).await.unwrap_or_default();
+        if !sent.is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    assert!(sent.contains(&reset_url), "sink: {}", sent);
+    let _ = tokio::fs::remove_file(&sink).await;
+    for _ in 0..50 {
+        let (status,): (String,) =
+            sqlx::query_as("SELECT status FROM notification_deliveries WHERE id = $1")
+                .bind(deliveries[0].id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        if status != "pending" {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+
+    // The log keeps neither the token nor the link
+    let (subject, body, payload, status, max_attempts): (Option<String>, String, Value, String, i32) =
+        sqlx::query_as(
+            "SELECT subject, body, payload, status, max_attempts FROM notification_deliveries WHERE id = $1",
+        )
+        .bind(deliveries[0].id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert!(!body.contains(&token), "body: {}", body);
+    assert!(!subject.unwrap_or_default().contains(&token));
+    assert!(
+        !payload.to_string().contains(&token),
+        "payload: {}",
+        payload
+    );
+    assert_eq!(payload["token"], "[redacted]");
+    assert_eq!(payload["reset_url"], "[redacted]");
+    assert_eq!(status, "sent");
+    assert_eq!(max_attempts, 1);
+}
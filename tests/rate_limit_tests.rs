@@ -0,0 +1,186 @@
+mod common;
+
+use std::net::SocketAddr;
+
+use asset_management::api::middleware::RateLimitLayer;
+use asset_management::domain::entities::RateLimitPolicy;
+use asset_management::infrastructure::cache::RateLimiter;
+use axum::{
+    body::Body,
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+    routing::get,
+    Router,
+};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+async fn setup_test_app() -> (Router, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret".to_string());
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: jwt_secret,
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+
+    let state = asset_management::api::server::AppState::new(pool.clone(), jwt_config);
+    (asset_management::api::server::create_app(state), pool)
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let body = body.map(|b| Body::from(b.to_string())).unwrap_or_default();
+
+    let response = app
+        .clone()
+        .oneshot(builder.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, json)
+}
+
+async fn ping(app: &Router, peer: Option<&str>) -> (StatusCode, Option<String>) {
+    let mut request = Request::builder().uri("/ping").body(Body::empty()).unwrap();
+    if let Some(peer) = peer {
+        let addr: SocketAddr = peer.parse().unwrap();
+        request.extensions_mut().insert(ConnectInfo(addr));
+    }
+    let response = app.clone().oneshot(request).await.unwrap();
+    let retry_after = response
+        .headers()
+        .get(header::RETRY_AFTER)
+        .map(|h| h.to_str().unwrap().to_string());
+    (response.status(), retry_after)
+}
+
+async fn login(app: &Router, email: &str, password: &str) -> (StatusCode, Value) {
+    send(
+        app,
+        "POST",
+        "/api/auth/login",
+        None,
+        Some(json!({ "email": email, "password": password })),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_rate_limit_layer_per_client_address() {
+    let policy = RateLimitPolicy {
+        name: "test",
+        capacity: 2,
+        refill_per_sec: 0.01,
+    };
+    let app = Router::new().route(
+        "/ping",
+        get(|| async { "pong" }).layer(RateLimitLayer::per_ip(RateLimiter::in_memory(), policy)),
+    );
+
+    assert_eq!(ping(&app, Some("10.0.0.1:4000")).await.0, StatusCode::OK);
+    assert_eq!(ping(&app, Some("10.0.0.1:4001")).await.0, StatusCode::OK);
+    let (status, retry_after) = ping(&app, Some("10.0.0.1:4002")).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(retry_after.as_deref(), Some("100"));
+
+    // Other clients have their own bucket
+    assert_eq!(ping(&app, Some("10.0.0.2:4000")).await.0, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_login_delay_lockout_and_unlock() {
+    let (app, pool) = setup_test_app().await;
+
+    let user_id = Uuid::new_v4();
+    let email = format!("lockout-{}@example.com", user_id.simple());
+    sqlx::query(
+        r#"
+        INSERT INTO users (id, email, password_hash, name, role, role_id, organization_id)
+        SELECT $1, $2, a.password_hash, 'Lockout Test', 'staff',
+               (SELECT id FROM roles WHERE code = 'staff'), a.organization_id
+        FROM users a WHERE a.email = 'admin@example.com'
+        "#,
+    )
+    .bind(user_id)
+    .bind(&email)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    // 1. A few failures are free, then attempts must wait
+    for _ in 0..3 {
+        let (status, _) = login(&app, &email, "wrong-password").await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+    let (status, json) = login(&app, &email, "admin123").await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS, "{:?}", json);
+
+    // 2. Reaching the threshold locks the account, even for the right password
+    sqlx::query(
+        "UPDATE users SET failed_login_attempts = 9, last_failed_login_at = NOW() - INTERVAL '1 hour' WHERE id = $1",
+    )
+    .bind(user_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let (status, json) = login(&app, &email, "wrong-password").await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS, "{:?}", json);
+    let (status, _) = login(&app, &email, "admin123").await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+
+    let (locked,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM audit_logs WHERE record_id = $1 AND action = 'ACCOUNT_LOCKED'",
+    )
+    .bind(user_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(locked, 1);
+
+    // 3. An admin lifts the lockout
+    let admin_token = common::login(&app, &pool, "admin@example.com").await;
+    let (status, json) = send(
+        &app,
+        "POST",
+        &format!("/api/users/{}/unlock", user_id),
+        Some(&admin_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "unlock failed: {:?}", json);
+
+    let (status, json) = login(&app, &email, "admin123").await;
+    assert_eq!(status, StatusCode::OK, "login failed: {:?}", json);
+
+    let (unlocked,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM audit_logs WHERE record_id = $1 AND action = 'ACCOUNT_UNLOCKED'",
+    )
+    .bind(user_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(unlocked, 1);
+}
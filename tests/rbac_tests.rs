@@ -1,10 +1,12 @@
+mod common;
+
 use axum::{
     body::Body,
     http::{header, Request, StatusCode},
     Router,
 };
-use http_body_util::BodyExt;
-use serde_json::{json, Value};
+use common::login;
+use serde_json::json;
 use sqlx::PgPool;
 use tower::util::ServiceExt;
 
@@ -18,7 +20,8 @@ async fn setup_test_app() -> Router {
     let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret".to_string());
     let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
         secret: jwt_secret,
-        expiry_hours: 24,
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
     };
 
     let state = asset_management::api::server::AppState::new(pool.clone(), jwt_config);
@@ -42,48 +45,12 @@ async fn setup_test_app() -> Router {
 }
 
 async fn get_token(app: Router, email: &str) -> (String, Router) {
-    let response = app
-        .oneshot(
-            Request::builder()
-                .method("POST")
-                .uri("/api/auth/login")
-                .header("Content-Type", "application/json")
-                .body(Body::from(
-                    json!({
-                        "email": email,
-                        "password": "admin123"
-                    })
-                    .to_string(),
-                ))
-                .unwrap(),
-        )
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
         .await
-        .unwrap();
-
-    let status = response.status();
-    let body = response.into_body().collect().await.unwrap().to_bytes();
-    let json_result: Result<Value, _> = serde_json::from_slice(&body);
-
-    if !status.is_success() {
-        println!(
-            "Login failed for {}: Status={}, Body={:?}",
-            email, status, body
-        );
-    }
-
-    let json = json_result.expect("Failed to parse JSON login response");
-
-    let token = json["token"]
-        .as_str()
-        .unwrap_or_else(|| {
-            panic!(
-                "Token not found in login response for {}. Response: {:?}",
-                email, json
-            )
-        })
-        .to_string();
-
-    (token, setup_test_app().await)
+        .expect("Failed to connect to DB");
+    let token = login(&app, &pool, email).await;
+    (token, app)
 }
 
 #[tokio::test]
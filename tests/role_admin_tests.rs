@@ -0,0 +1,275 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use common::login;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+async fn setup_test_app() -> (Router, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret".to_string());
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: jwt_secret,
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+
+    let state = asset_management::api::server::AppState::new(pool.clone(), jwt_config);
+    (asset_management::api::server::create_app(state), pool)
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let body = body.map(|b| Body::from(b.to_string())).unwrap_or_default();
+
+    let response = app
+        .clone()
+        .oneshot(builder.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, json)
+}
+
+#[tokio::test]
+async fn test_custom_role_lifecycle() {
+    let (app, pool) = setup_test_app().await;
+    let suffix = &Uuid::new_v4().simple().to_string()[..8];
+    let admin_token = login(&app, &pool, "admin@example.com").await;
+
+    // 1. Clone the technician role into a custom role
+    let (status, json) = send(&app, "GET", "/api/rbac/roles", Some(&admin_token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    let technician = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["code"] == "technician")
+        .unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let code = format!("field_tech_{}", suffix);
+    let (status, json) = send(
+        &app,
+        "POST",
+        &format!("/api/rbac/roles/{}/clone", technician),
+        Some(&admin_token),
+        Some(json!({ "code": code, "name": "Field Technician" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", json);
+    assert_eq!(json["data"]["is_system"], false);
+    let role_id: Uuid = json["data"]["id"].as_str().unwrap().parse().unwrap();
+
+    let (status, json) = send(
+        &app,
+        "GET",
+        &format!("/api/rbac/roles/{}/permissions", role_id),
+        Some(&admin_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let cloned: Vec<(&str, &str)> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| (p["code"].as_str().unwrap(), p["scope"].as_str().unwrap()))
+        .collect();
+    assert!(cloned.contains(&("work_order.read", "own")), "{:?}", cloned);
+
+    // Codes are unique and well-formed
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/rbac/roles",
+        Some(&admin_token),
+        Some(json!({ "code": code, "name": "Duplicate" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/rbac/roles",
+        Some(&admin_token),
+        Some(json!({ "code": "Not A Code", "name": "Bad" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // 2. A user holding the role, signed in
+    let user_id = Uuid::new_v4();
+    let email = format!("role-{}@example.com", user_id.simple());
+    sqlx::query(
+        r#"
+        INSERT INTO users (id, email, password_hash, name, role, role_id, organization_id)
+        SELECT $1, $2, a.password_hash, 'Role Test', $3, $4, a.organization_id
+        FROM users a WHERE a.email = 'admin@example.com'
+        "#,
+    )
+    .bind(user_id)
+    .bind(&email)
+    .bind(&code)
+    .bind(role_id)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let user_token = login(&app, &pool, &email).await;
+    let (status, _) = send(&app, "GET", "/api/me", Some(&user_token), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Only admins manage roles
+    let (status, _) = send(
+        &app,
+        "POST",
+        &format!("/api/rbac/roles/{}/permissions", role_id),
+        Some(&user_token),
+        Some(json!({ "code": "asset.delete" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // 3. Preview the new permission set, nothing is saved
+    let proposed = json!({ "permissions": [
+        { "code": "work_order.read", "scope": "department" },
+        { "code": "asset.read" }
+    ]});
+    let (status, json) = send(
+        &app,
+        "POST",
+        &format!("/api/rbac/roles/{}/permissions/preview", role_id),
+        Some(&admin_token),
+        Some(proposed.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", json);
+    let preview = &json["data"];
+    assert_eq!(preview["affected_users"], 1);
+    assert_eq!(preview["sessions_revoked"], 1);
+    let changes = preview["users"][0]["changes"].as_array().unwrap();
+    let change_of = |code: &str| {
+        changes
+            .iter()
+            .find(|c| c["code"] == code)
+            .map(|c| (c["before"].clone(), c["after"].clone()))
+    };
+    assert_eq!(change_of("asset.read"), Some((json!([]), json!(["all"]))));
+    assert_eq!(
+        change_of("work_order.read"),
+        Some((json!(["own"]), json!(["department"])))
+    );
+    assert_eq!(change_of("work_order.update").unwrap().1, json!([]));
+
+    let (status, _) = send(&app, "GET", "/api/me", Some(&user_token), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Unknown permissions are rejected
+    let (status, _) = send(
+        &app,
+        "PUT",
+        &format!("/api/rbac/roles/{}/permissions", role_id),
+        Some(&admin_token),
+        Some(json!({ "permissions": [{ "code": "asset.teleport" }] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // 4. Saving ends the user's sessions; they sign in with the new permissions
+    let (status, json) = send(
+        &app,
+        "PUT",
+        &format!("/api/rbac/roles/{}/permissions", role_id),
+        Some(&admin_token),
+        Some(proposed),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", json);
+    assert_eq!(json["data"].as_array().unwrap().len(), 2);
+
+    let (status, _) = send(&app, "GET", "/api/me", Some(&user_token), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let user_token = login(&app, &pool, &email).await;
+    let (status, _) = send(&app, "GET", "/api/me", Some(&user_token), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = send(
+        &app,
+        "DELETE",
+        &format!("/api/rbac/roles/{}/permissions/asset.read", role_id),
+        Some(&admin_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 5. Roles in use and system roles are not deleted
+    let (status, _) = send(
+        &app,
+        "DELETE",
+        &format!("/api/rbac/roles/{}", role_id),
+        Some(&admin_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = send(
+        &app,
+        "DELETE",
+        &format!("/api/rbac/roles/{}", technician),
+        Some(&admin_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (status, json) = send(
+        &app,
+        "DELETE",
+        &format!("/api/rbac/roles/{}", role_id),
+        Some(&admin_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", json);
+
+    // Cleanup
+    sqlx::query("DELETE FROM audit_logs WHERE table_name = 'roles' AND record_id = $1")
+        .bind(role_id)
+        .execute(&pool)
+        .await
+        .ok();
+}
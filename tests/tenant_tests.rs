@@ -0,0 +1,254 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use common::login;
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+async fn setup_test_app() -> (Router, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret".to_string());
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: jwt_secret,
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+
+    let state = asset_management::api::server::AppState::new(pool.clone(), jwt_config);
+    (asset_management::api::server::create_app(state), pool)
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let body = body.map(|b| Body::from(b.to_string())).unwrap_or_default();
+
+    let response = app
+        .clone()
+        .oneshot(builder.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, json)
+}
+
+async fn switch_to(app: &Router, token: &str, organization_id: &str) -> String {
+    let (status, json) = send(
+        app,
+        "POST",
+        "/api/auth/switch-organization",
+        Some(token),
+        Some(json!({ "organization_id": organization_id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "switch failed: {:?}", json);
+    json["token"].as_str().unwrap().to_string()
+}
+
+fn asset_codes(json: &Value) -> Vec<String> {
+    json["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|a| a["asset_code"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_tenant_cannot_read_other_tenant_assets() {
+    let (app, pool) = setup_test_app().await;
+    let suffix = &Uuid::new_v4().simple().to_string()[..8];
+
+    // 1. Login as admin of the root organization
+    let root_token = login(&app, &pool, "admin@example.com").await;
+
+    let (_, me) = send(
+        &app,
+        "GET",
+        "/api/me/organizations",
+        Some(&root_token),
+        None,
+    )
+    .await;
+    let root_id = me["data"][0]["id"].as_str().unwrap().to_string();
+
+    // 2. Create two sibling tenants below it
+    let mut tenants = Vec::new();
+    for name in ["A", "B"] {
+        let (status, json) = send(
+            &app,
+            "POST",
+            "/api/organizations",
+            Some(&root_token),
+            Some(json!({
+                "code": format!("TEN-{}-{}", name, suffix),
+                "name": format!("Tenant {} {}", name, suffix),
+                "parent_id": root_id,
+                "org_type": "division"
+            })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "create org failed: {:?}", json);
+        tenants.push(json["data"]["id"].as_str().unwrap().to_string());
+    }
+
+    // 3. Tenant A creates an asset
+    let token_a = switch_to(&app, &root_token, &tenants[0]).await;
+    let asset_code = format!("TEN-A-{}", suffix);
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/assets",
+        Some(&token_a),
+        Some(json!({
+            "name": "Tenant A Laptop",
+            "asset_code": asset_code,
+            "category_id": "44444444-4444-4444-4444-444444444401",
+            "status": "in_inventory"
+        })),
+    )
+    .await;
+    assert_eq!(
+        status,
+        StatusCode::CREATED,
+        "create asset failed: {:?}",
+        json
+    );
+    let asset_id = json["data"]["id"].as_str().unwrap().to_string();
+    assert_eq!(json["data"]["organization_id"], tenants[0].as_str());
+
+    let (_, json) = send(
+        &app,
+        "GET",
+        "/api/assets?per_page=100",
+        Some(&token_a),
+        None,
+    )
+    .await;
+    assert!(asset_codes(&json).contains(&asset_code));
+
+    // 4. Tenant B can neither list, search nor fetch it
+    let token_b = switch_to(&app, &root_token, &tenants[1]).await;
+    let (status, json) = send(
+        &app,
+        "GET",
+        "/api/assets?per_page=100",
+        Some(&token_b),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(asset_codes(&json).is_empty());
+
+    let (_, json) = send(
+        &app,
+        "GET",
+        &format!("/api/assets/search?query={}", asset_code),
+        Some(&token_b),
+        None,
+    )
+    .await;
+    assert!(asset_codes(&json).is_empty());
+
+    let (status, _) = send(
+        &app,
+        "GET",
+        &format!("/api/assets/{}", asset_id),
+        Some(&token_b),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // 5. The parent organization sees its subtree
+    let (status, _) = send(
+        &app,
+        "GET",
+        &format!("/api/assets/{}", asset_id),
+        Some(&root_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Cleanup
+    sqlx::query("DELETE FROM assets WHERE id = $1::uuid")
+        .bind(&asset_id)
+        .execute(&pool)
+        .await
+        .ok();
+    for id in &tenants {
+        sqlx::query("DELETE FROM organizations WHERE id = $1::uuid")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .ok();
+    }
+}
+
+#[tokio::test]
+async fn test_switch_to_foreign_organization_is_forbidden() {
+    let (app, pool) = setup_test_app().await;
+    let suffix = &Uuid::new_v4().simple().to_string()[..8];
+
+    // An organization outside every membership of the manager
+    let org_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO organizations (id, code, name) VALUES ($1, $2, $3)")
+        .bind(org_id)
+        .bind(format!("FOREIGN-{}", suffix))
+        .bind("Foreign Tenant")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // Seed hashes may be stale; reuse the admin password as rbac_tests does
+    sqlx::query(
+        "UPDATE users SET password_hash = (SELECT password_hash FROM users WHERE email = 'admin@example.com') WHERE email = 'manager@example.com'",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let token = login(&app, &pool, "manager@example.com").await;
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/auth/switch-organization",
+        Some(&token),
+        Some(json!({ "organization_id": org_id })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    sqlx::query("DELETE FROM organizations WHERE id = $1")
+        .bind(org_id)
+        .execute(&pool)
+        .await
+        .ok();
+}
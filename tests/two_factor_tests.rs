@@ -0,0 +1,237 @@
+use asset_management::domain::entities::{decode_secret, totp_code, TOTP_STEP_SECS};
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+async fn setup_test_app() -> (Router, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret".to_string());
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: jwt_secret,
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+
+    let state = asset_management::api::server::AppState::new(pool.clone(), jwt_config);
+    (asset_management::api::server::create_app(state), pool)
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let body = body.map(|b| Body::from(b.to_string())).unwrap_or_default();
+
+    let response = app
+        .clone()
+        .oneshot(builder.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, json)
+}
+
+/// A user with the admin password and the given role
+async fn create_user(pool: &PgPool, role: &str) -> String {
+    let id = Uuid::new_v4();
+    let email = format!("2fa-{}@example.com", id.simple());
+    sqlx::query(
+        r#"
+        INSERT INTO users (id, email, password_hash, name, role, role_id, organization_id)
+        SELECT $1, $2, a.password_hash, 'Two-Factor Test', $3,
+               (SELECT id FROM roles WHERE code = $3), a.organization_id
+        FROM users a WHERE a.email = 'admin@example.com'
+        "#,
+    )
+    .bind(id)
+    .bind(&email)
+    .bind(role)
+    .execute(pool)
+    .await
+    .unwrap();
+    email
+}
+
+async fn login(app: &Router, email: &str) -> Value {
+    let (status, json) = send(
+        app,
+        "POST",
+        "/api/auth/login",
+        None,
+        Some(json!({ "email": email, "password": "admin123" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "login failed: {:?}", json);
+    json
+}
+
+fn current_code(secret: &str) -> String {
+    let step = chrono::Utc::now().timestamp() / TOTP_STEP_SECS;
+    totp_code(&decode_secret(secret).unwrap(), step as u64)
+}
+
+#[tokio::test]
+async fn test_mandatory_two_factor_login() {
+    let (app, pool) = setup_test_app().await;
+    // Managers approve requests, so their role requires 2FA
+    let email = create_user(&pool, "manager").await;
+
+    // 1. The password alone only yields a challenge
+    let challenge = login(&app, &email).await;
+    assert_eq!(challenge["two_factor_required"], true);
+    assert_eq!(challenge["setup_required"], true);
+    assert!(challenge.get("token").is_none());
+    let challenge_token = challenge["challenge_token"].as_str().unwrap();
+
+    let (status, _) = send(&app, "GET", "/api/me", Some(challenge_token), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // 2. Enrol and complete the login with the first code
+    let (status, setup) = send(
+        &app,
+        "POST",
+        "/api/auth/2fa/setup",
+        None,
+        Some(json!({ "challenge_token": challenge_token })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "setup failed: {:?}", setup);
+    let secret = setup["data"]["secret"].as_str().unwrap().to_string();
+    assert!(setup["data"]["otpauth_uri"]
+        .as_str()
+        .unwrap()
+        .starts_with("otpauth://totp/"));
+
+    let code = current_code(&secret);
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/auth/2fa/verify",
+        None,
+        Some(json!({ "challenge_token": challenge_token, "code": code })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "verify failed: {:?}", json);
+    let token = json["token"].as_str().unwrap().to_string();
+    let recovery_codes: Vec<String> =
+        serde_json::from_value(json["recovery_codes"].clone()).unwrap();
+    assert_eq!(recovery_codes.len(), 10);
+
+    let (status, json) = send(&app, "GET", "/api/me/2fa", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["enabled"], true);
+    assert_eq!(json["data"]["required"], true);
+    assert_eq!(json["data"]["recovery_codes_left"], 10);
+
+    // 3. Next login: a used code is not accepted again, a recovery code is
+    let challenge = login(&app, &email).await;
+    assert_eq!(challenge["setup_required"], false);
+    let challenge_token = challenge["challenge_token"].as_str().unwrap();
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/auth/2fa/verify",
+        None,
+        Some(json!({ "challenge_token": challenge_token, "code": code })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/auth/2fa/verify",
+        None,
+        Some(json!({ "challenge_token": challenge_token, "code": recovery_codes[0] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "recovery code failed: {:?}", json);
+    assert!(json["token"].is_string());
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/auth/2fa/verify",
+        None,
+        Some(json!({ "challenge_token": challenge_token, "code": recovery_codes[0] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // 4. The role policy keeps 2FA on
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/me/2fa/disable",
+        Some(&token),
+        Some(json!({ "code": recovery_codes[1] })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{:?}", json);
+}
+
+#[tokio::test]
+async fn test_optional_two_factor_enrolment() {
+    let (app, pool) = setup_test_app().await;
+    let email = create_user(&pool, "staff").await;
+
+    let json = login(&app, &email).await;
+    let token = json["token"].as_str().unwrap().to_string();
+
+    let (status, setup) = send(&app, "POST", "/api/me/2fa/setup", Some(&token), None).await;
+    assert_eq!(status, StatusCode::OK, "setup failed: {:?}", setup);
+    let secret = setup["data"]["secret"].as_str().unwrap();
+
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/me/2fa/enable",
+        Some(&token),
+        Some(json!({ "code": current_code(secret) })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "enable failed: {:?}", json);
+    let recovery_codes: Vec<String> = serde_json::from_value(json["data"].clone()).unwrap();
+
+    // Once enabled, logins are challenged
+    let challenge = login(&app, &email).await;
+    assert_eq!(challenge["two_factor_required"], true);
+    assert_eq!(challenge["setup_required"], false);
+
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/me/2fa/disable",
+        Some(&token),
+        Some(json!({ "code": recovery_codes[0].to_lowercase() })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "disable failed: {:?}", json);
+
+    let json = login(&app, &email).await;
+    assert!(json["token"].is_string());
+}
@@ -0,0 +1,298 @@
+use asset_management::api::server::AppState;
+use asset_management::application::dto::{RecordAppraisalRequest, RecordImpairmentRequest};
+use asset_management::domain::entities::{
+    month_start, DepreciationSchedule, VALUATION_APPRAISAL, VALUATION_IMPAIRMENT,
+};
+use chrono::{Months, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn setup_state() -> (AppState, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: "test-secret".to_string(),
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+    (AppState::new(pool.clone(), jwt_config), pool)
+}
+
+async fn admin_id(pool: &PgPool) -> Uuid {
+    sqlx::query_scalar("SELECT id FROM users WHERE email = 'admin@example.com'")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+/// 12,000 bought a year ago, no residual, 24 months straight line: 500 a month,
+/// so the book value is 6,000 at the start of this month
+async fn create_depreciating_asset(state: &AppState, pool: &PgPool) -> Uuid {
+    let purchased = month_start(Utc::now().date_naive()) - Months::new(12);
+    let asset_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO assets (asset_code, name, category_id, status, organization_id,
+                            purchase_date, purchase_price, residual_value, useful_life_months)
+        SELECT $1, 'Valuation Test Asset', '44444444-4444-4444-4444-444444444401', 'in_inventory',
+               organization_id, $2, 12000, 0, 24
+        FROM users WHERE email = 'admin@example.com'
+        RETURNING id
+        "#,
+    )
+    .bind(format!(
+        "VAL-{}",
+        &Uuid::new_v4().simple().to_string()[..10]
+    ))
+    .bind(purchased)
+    .fetch_one(pool)
+    .await
+    .unwrap();
+
+    state
+        .depreciation_service
+        .recalculate_asset(asset_id)
+        .await
+        .unwrap();
+    asset_id
+}
+
+fn this_month(schedule: &[DepreciationSchedule]) -> &DepreciationSchedule {
+    let month = month_start(Utc::now().date_naive());
+    schedule.iter().find(|r| r.period_start == month).unwrap()
+}
+
+fn appraisal(market_value: i64, apply_revaluation: bool) -> RecordAppraisalRequest {
+    RecordAppraisalRequest {
+        valuation_date: Utc::now().date_naive(),
+        market_value: Decimal::from(market_value),
+        replacement_cost: None,
+        valuation_type: None,
+        appraiser: Some("Independent Appraiser".to_string()),
+        notes: None,
+        apply_revaluation,
+    }
+}
+
+fn impairment(recoverable_amount: i64, valuation_date: NaiveDate) -> RecordImpairmentRequest {
+    RecordImpairmentRequest {
+        valuation_date,
+        recoverable_amount: Decimal::from(recoverable_amount),
+        appraiser: None,
+        notes: Some("Flood damage".to_string()),
+    }
+}
+
+#[tokio::test]
+async fn test_impairment_writes_down_the_book_value() {
+    let (state, pool) = setup_state().await;
+    let admin = admin_id(&pool).await;
+    let asset_id = create_depreciating_asset(&state, &pool).await;
+    let today = Utc::now().date_naive();
+
+    let before = state
+        .depreciation_service
+        .get_schedule(asset_id)
+        .await
+        .unwrap();
+    assert_eq!(this_month(&before).opening_value, Decimal::from(6000));
+    assert_eq!(this_month(&before).valuation_adjustment, Decimal::ZERO);
+
+    // 1. Only a write-down is an impairment
+    assert!(state
+        .valuation_service
+        .record_impairment(asset_id, impairment(6000, today), admin)
+        .await
+        .is_err());
+
+    // 2. The loss is the book value it replaces minus the recoverable amount
+    let valuation = state
+        .valuation_service
+        .record_impairment(asset_id, impairment(4000, today), admin)
+        .await
+        .unwrap();
+    assert_eq!(valuation.valuation_type, VALUATION_IMPAIRMENT);
+    assert_eq!(valuation.original_cost, Some(Decimal::from(12000)));
+    assert_eq!(valuation.book_value, Some(Decimal::from(6000)));
+    assert_eq!(
+        valuation.accumulated_depreciation,
+        Some(Decimal::from(6000))
+    );
+    assert_eq!(valuation.revalued_amount, Some(Decimal::from(4000)));
+    assert_eq!(valuation.adjustment_amount, Some(Decimal::from(-2000)));
+
+    // 3. The schedule books the loss this month and depreciates the rest
+    let schedule = state
+        .depreciation_service
+        .get_schedule(asset_id)
+        .await
+        .unwrap();
+    let month = this_month(&schedule);
+    assert_eq!(month.opening_value, Decimal::from(6000));
+    assert_eq!(month.valuation_adjustment, Decimal::from(-2000));
+    assert_eq!(
+        month.closing_value,
+        Decimal::from(4000) - month.depreciation_amount
+    );
+    assert!(month.depreciation_amount < Decimal::from(500));
+    assert_eq!(schedule.last().unwrap().closing_value, Decimal::ZERO);
+    assert_eq!(
+        schedule
+            .iter()
+            .map(|r| r.valuation_adjustment)
+            .sum::<Decimal>(),
+        Decimal::from(-2000)
+    );
+
+    // 4. Reports and ROI carry the written-down basis
+    let position = state
+        .depreciation_service
+        .positions(today, None)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|p| p.asset_id == asset_id)
+        .unwrap();
+    assert_eq!(position.valuation_adjustment, Decimal::from(-2000));
+    assert_eq!(position.book_value, month.closing_value);
+
+    let roi = state
+        .analytics_service
+        .get_asset_roi(asset_id)
+        .await
+        .unwrap();
+    assert_eq!(roi.valuation_adjustment, Decimal::from(-2000));
+    assert_eq!(roi.cost_basis, Decimal::from(10000));
+    assert_eq!(roi.book_value, month.closing_value);
+    assert_eq!(
+        roi.net_profit,
+        -(roi.accumulated_depreciation + Decimal::from(2000))
+    );
+
+    // 5. A later date than today is refused
+    assert!(state
+        .valuation_service
+        .record_impairment(asset_id, impairment(1000, today.succ_opt().unwrap()), admin)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_revaluation_surplus_raises_the_book_value() {
+    let (state, pool) = setup_state().await;
+    let admin = admin_id(&pool).await;
+    let asset_id = create_depreciating_asset(&state, &pool).await;
+
+    let valuation = state
+        .valuation_service
+        .record_appraisal(asset_id, appraisal(7500, true), admin)
+        .await
+        .unwrap();
+    assert_eq!(valuation.valuation_type, VALUATION_APPRAISAL);
+    assert_eq!(valuation.book_value, Some(Decimal::from(6000)));
+    assert_eq!(valuation.revalued_amount, Some(Decimal::from(7500)));
+    assert_eq!(valuation.adjustment_amount, Some(Decimal::from(1500)));
+
+    let schedule = state
+        .depreciation_service
+        .get_schedule(asset_id)
+        .await
+        .unwrap();
+    let month = this_month(&schedule);
+    assert_eq!(month.opening_value, Decimal::from(6000));
+    assert_eq!(month.valuation_adjustment, Decimal::from(1500));
+    assert_eq!(
+        month.closing_value,
+        Decimal::from(7500) - month.depreciation_amount
+    );
+    assert!(month.depreciation_amount > Decimal::from(500));
+    assert_eq!(schedule.last().unwrap().closing_value, Decimal::ZERO);
+
+    let roi = state
+        .analytics_service
+        .get_asset_roi(asset_id)
+        .await
+        .unwrap();
+    assert_eq!(roi.valuation_adjustment, Decimal::from(1500));
+    assert_eq!(roi.cost_basis, Decimal::from(13500));
+    assert_eq!(roi.book_value, month.closing_value);
+    // A surplus is not income
+    assert_eq!(roi.net_profit, -roi.accumulated_depreciation);
+}
+
+#[tokio::test]
+async fn test_revaluation_deficit_lowers_the_book_value() {
+    let (state, pool) = setup_state().await;
+    let admin = admin_id(&pool).await;
+    let asset_id = create_depreciating_asset(&state, &pool).await;
+
+    // 1. An informational appraisal books nothing
+    let quote = state
+        .valuation_service
+        .record_appraisal(
+            asset_id,
+            RecordAppraisalRequest {
+                valuation_type: Some("market".to_string()),
+                ..appraisal(5500, false)
+            },
+            admin,
+        )
+        .await
+        .unwrap();
+    assert_eq!(quote.book_value, Some(Decimal::from(6000)));
+    assert!(quote.revalued_amount.is_none());
+    assert!(quote.adjustment_amount.is_none());
+    let schedule = state
+        .depreciation_service
+        .get_schedule(asset_id)
+        .await
+        .unwrap();
+    assert_eq!(this_month(&schedule).valuation_adjustment, Decimal::ZERO);
+
+    // 2. A revaluation below book value books the deficit
+    let valuation = state
+        .valuation_service
+        .record_appraisal(asset_id, appraisal(5000, true), admin)
+        .await
+        .unwrap();
+    assert_eq!(valuation.adjustment_amount, Some(Decimal::from(-1000)));
+
+    let schedule = state
+        .depreciation_service
+        .get_schedule(asset_id)
+        .await
+        .unwrap();
+    let month = this_month(&schedule);
+    assert_eq!(month.valuation_adjustment, Decimal::from(-1000));
+    assert_eq!(
+        month.closing_value,
+        Decimal::from(5000) - month.depreciation_amount
+    );
+    assert_eq!(schedule.last().unwrap().closing_value, Decimal::ZERO);
+
+    let roi = state
+        .analytics_service
+        .get_asset_roi(asset_id)
+        .await
+        .unwrap();
+    assert_eq!(roi.valuation_adjustment, Decimal::from(-1000));
+    assert_eq!(roi.cost_basis, Decimal::from(11000));
+    assert_eq!(roi.book_value, month.closing_value);
+
+    // 3. Both records are in the history; the same type and date is a conflict
+    let history = state
+        .valuation_service
+        .history(asset_id, None)
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 2);
+    assert!(state
+        .valuation_service
+        .record_appraisal(asset_id, appraisal(4800, true), admin)
+        .await
+        .is_err());
+}
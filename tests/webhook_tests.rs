@@ -0,0 +1,359 @@
+mod common;
+
+use asset_management::api::server::{create_app, AppState};
+use asset_management::domain::entities::OutboxEvent;
+use asset_management::infrastructure::repositories::OutboxRepository;
+use axum::{
+    body::{Body, Bytes},
+    extract::State,
+    http::{header, HeaderMap, Request, StatusCode},
+    routing::post,
+    Router,
+};
+use common::login;
+use hmac::{Hmac, Mac};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use sqlx::PgPool;
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+async fn setup_test_app() -> (Router, AppState, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret".to_string());
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: jwt_secret,
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+
+    let state = AppState::new(pool.clone(), jwt_config);
+    (create_app(state.clone()), state, pool)
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let body = body.map(|b| Body::from(b.to_string())).unwrap_or_default();
+
+    let response = app
+        .clone()
+        .oneshot(builder.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, json)
+}
+
+/// Stand-in for an external system: records what it receives and answers
+/// with a status the test controls
+#[derive(Clone)]
+struct Receiver {
+    status: Arc<AtomicU16>,
+    requests: Arc<Mutex<Vec<(HeaderMap, String)>>>,
+}
+
+async fn receive(State(receiver): State<Receiver>, headers: HeaderMap, body: Bytes) -> StatusCode {
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    receiver.requests.lock().unwrap().push((headers, body));
+    StatusCode::from_u16(receiver.status.load(Ordering::SeqCst)).unwrap()
+}
+
+async fn start_receiver() -> (Receiver, String) {
+    let receiver = Receiver {
+        status: Arc::new(AtomicU16::new(200)),
+        requests: Arc::new(Mutex::new(Vec::new())),
+    };
+    let app = Router::new()
+        .route("/hooks", post(receive))
+        .with_state(receiver.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hooks", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (receiver, url)
+}
+
+async fn create_asset(app: &Router, token: &str, code: &str) -> Uuid {
+    let (status, json) = send(
+        app,
+        "POST",
+        "/api/assets",
+        Some(token),
+        Some(json!({
+            "name": "Webhook Generator",
+            "asset_code": code,
+            "category_id": "44444444-4444-4444-4444-444444444401",
+            "status": "in_inventory"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{:?}", json);
+    Uuid::parse_str(json["data"]["id"].as_str().unwrap()).unwrap()
+}
+
+/// Relay the outbox until the asset's creation event went to the subscribers
+async fn relay_created(state: &AppState, pool: &PgPool, asset_id: Uuid) -> OutboxEvent {
+    let repository = OutboxRepository::new(pool.clone());
+    for _ in 0..100 {
+        state.outbox_relay.relay_pending().await.unwrap();
+        let rows = repository.list_by_aggregate(asset_id).await.unwrap();
+        if let Some(row) = rows.into_iter().find(|r| r.delivered_at.is_some()) {
+            return row;
+        }
+    }
+    panic!("creation of {} was not relayed", asset_id);
+}
+
+/// Wait for the first delivery of an event to reach `status`
+async fn delivery_of(
+    app: &Router,
+    token: &str,
+    subscription_id: &str,
+    event_id: Uuid,
+    status: &str,
+) -> Value {
+    for _ in 0..50 {
+        let (code, json) = send(
+            app,
+            "GET",
+            &format!(
+                "/api/webhooks/deliveries?subscription_id={}",
+                subscription_id
+            ),
+            Some(token),
+            None,
+        )
+        .await;
+        assert_eq!(code, StatusCode::OK, "{:?}", json);
+        if let Some(delivery) = json["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|d| d["event_id"] == event_id.to_string() && d["status"] == status)
+        {
+            return delivery.clone();
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("delivery of {} did not become {}", event_id, status);
+}
+
+fn verify_signature(secret: &str, headers: &HeaderMap, body: &str) {
+    let timestamp = headers["x-webhook-timestamp"].to_str().unwrap();
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+    let expected = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+    assert_eq!(headers["x-webhook-signature"], expected.as_str());
+}
+
+#[tokio::test]
+async fn test_webhooks_are_signed_retried_and_redelivered() {
+    let (app, state, pool) = setup_test_app().await;
+    let token = login(&app, &pool, "admin@example.com").await;
+    let (receiver, url) = start_receiver().await;
+    let suffix = &Uuid::new_v4().simple().to_string()[..8];
+
+    // Events from earlier runs are relayed before the subscription exists
+    while state.outbox_relay.relay_pending().await.unwrap() > 0 {}
+
+    // 1. Subscriptions are admin-only and validated
+    let user_token = login(&app, &pool, "user@example.com").await;
+    let (status, _) = send(&app, "GET", "/api/webhooks", Some(&user_token), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/webhooks",
+        Some(&token),
+        Some(json!({ "name": "ERP", "url": "ftp://erp.example.com" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{:?}", json);
+
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/webhooks",
+        Some(&token),
+        Some(json!({
+            "name": format!("ERP {}", suffix),
+            "url": url,
+            "event_types": ["asset.created"],
+            "max_attempts": 2
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{:?}", json);
+    let subscription_id = json["data"]["id"].as_str().unwrap().to_string();
+    let secret = json["data"]["secret"].as_str().unwrap().to_string();
+    assert_eq!(secret.len(), 64);
+
+    let (status, json) = send(
+        &app,
+        "GET",
+        &format!("/api/webhooks/{}", subscription_id),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(json["data"].get("secret").is_none(), "{:?}", json);
+
+    // 2. A matching event is POSTed as its signed envelope
+    let asset_id = create_asset(&app, &token, &format!("WHK-{}", suffix)).await;
+    let event = relay_created(&state, &pool, asset_id).await;
+    let delivery = delivery_of(&app, &token, &subscription_id, event.id, "sent").await;
+    assert_eq!(delivery["attempts"], 1);
+    assert_eq!(delivery["last_status_code"], 200);
+
+    let requests = receiver.requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 1);
+    let (headers, body) = &requests[0];
+    verify_signature(&secret, headers, body);
+    assert_eq!(headers["x-webhook-event"], "asset.created");
+    assert_eq!(
+        headers["x-webhook-delivery"],
+        delivery["id"].as_str().unwrap()
+    );
+    let envelope: Value = serde_json::from_str(body).unwrap();
+    assert_eq!(envelope["id"], event.id.to_string());
+    assert_eq!(envelope["aggregate_id"], asset_id.to_string());
+
+    // The same event relayed again is not delivered twice
+    let again = state.webhook_service.dispatch(&event).await.unwrap();
+    assert!(again.is_empty());
+
+    // 3. A failing receiver gets retried with backoff, then the delivery is dead
+    receiver.status.store(503, Ordering::SeqCst);
+    let failing_asset_id = create_asset(&app, &token, &format!("WHK-{}-2", suffix)).await;
+    let failing_event = relay_created(&state, &pool, failing_asset_id).await;
+    let delivery = delivery_of(&app, &token, &subscription_id, failing_event.id, "retrying").await;
+    let delivery_id = delivery["id"].as_str().unwrap().to_string();
+    assert_eq!(delivery["attempts"], 1);
+    assert_eq!(delivery["last_status_code"], 503);
+    let next: chrono::DateTime<chrono::Utc> =
+        serde_json::from_value(delivery["next_attempt_at"].clone()).unwrap();
+    assert!(next > chrono::Utc::now() + chrono::Duration::seconds(20));
+
+    sqlx::query("UPDATE webhook_deliveries SET next_attempt_at = NOW() WHERE id = $1")
+        .bind(Uuid::parse_str(&delivery_id).unwrap())
+        .execute(&pool)
+        .await
+        .unwrap();
+    state.webhook_service.retry_due_deliveries().await.unwrap();
+
+    let (status, json) = send(
+        &app,
+        "GET",
+        &format!(
+            "/api/webhooks/dead-letters?subscription_id={}",
+            subscription_id
+        ),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let dead = json["data"].as_array().unwrap();
+    assert_eq!(dead.len(), 1, "{:?}", dead);
+    assert_eq!(dead[0]["id"], delivery_id.as_str());
+    assert_eq!(dead[0]["attempts"], 2);
+    assert_eq!(receiver.requests.lock().unwrap().len(), 3);
+
+    // 4. Redelivered by hand once the receiver is back
+    receiver.status.store(204, Ordering::SeqCst);
+    let (status, json) = send(
+        &app,
+        "POST",
+        &format!("/api/webhooks/deliveries/{}/redeliver", delivery_id),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", json);
+    assert_eq!(json["data"]["status"], "sent");
+    assert_eq!(json["data"]["attempts"], 1);
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        &format!("/api/webhooks/deliveries/{}/redeliver", delivery_id),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let requests = receiver.requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 4);
+    let (headers, body) = &requests[3];
+    verify_signature(&secret, headers, body);
+    assert!(body.contains(&failing_asset_id.to_string()));
+
+    // 5. The delivery log holds both events, newest first
+    let (status, json) = send(
+        &app,
+        "GET",
+        &format!(
+            "/api/webhooks/deliveries?subscription_id={}&status=sent",
+            subscription_id
+        ),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let log: Vec<&str> = json["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["event_id"].as_str().unwrap())
+        .collect();
+    assert_eq!(log, [failing_event.id.to_string(), event.id.to_string()]);
+
+    // Cleanup
+    let (status, _) = send(
+        &app,
+        "DELETE",
+        &format!("/api/webhooks/{}", subscription_id),
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    for id in [asset_id, failing_asset_id] {
+        sqlx::query("DELETE FROM event_outbox WHERE aggregate_id = $1")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .ok();
+        sqlx::query("DELETE FROM assets WHERE id = $1")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .ok();
+    }
+}
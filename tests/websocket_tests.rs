@@ -0,0 +1,231 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use common::login;
+use futures::{SinkExt, StreamExt};
+use http_body_util::BodyExt;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{tungstenite::Message, MaybeTlsStream, WebSocketStream};
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn setup_test_app() -> (Router, PgPool) {
+    dotenvy::dotenv().ok();
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to DB");
+
+    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "test-secret".to_string());
+    let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
+        secret: jwt_secret,
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
+    };
+
+    let state = asset_management::api::server::AppState::new(pool.clone(), jwt_config);
+    (asset_management::api::server::create_app(state), pool)
+}
+
+async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let body = body.map(|b| Body::from(b.to_string())).unwrap_or_default();
+
+    let response = app
+        .clone()
+        .oneshot(builder.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, json)
+}
+
+/// Serve the app on a local port, for WebSocket clients
+async fn serve(app: Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    format!("ws://{}/ws", addr)
+}
+
+async fn next_json(socket: &mut Socket) -> Value {
+    loop {
+        let msg = tokio::time::timeout(Duration::from_secs(5), socket.next())
+            .await
+            .expect("no message within 5s")
+            .expect("socket closed")
+            .unwrap();
+        if let Message::Text(text) = msg {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+async fn send_json(socket: &mut Socket, value: Value) {
+    socket.send(Message::Text(value.to_string())).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_websocket_auth_topics_and_user_push() {
+    let (app, pool) = setup_test_app().await;
+    let url = serve(app.clone()).await;
+    let suffix = &Uuid::new_v4().simple().to_string()[..8];
+
+    // 1. Without a valid token the socket is refused
+    let (mut socket, _) = tokio_tungstenite::connect_async(format!("{}?token=bogus", url))
+        .await
+        .unwrap();
+    let reply = next_json(&mut socket).await;
+    assert_eq!(reply["type"], "error", "{:?}", reply);
+
+    let (mut socket, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+    send_json(
+        &mut socket,
+        json!({ "type": "subscribe", "topic": "alerts" }),
+    )
+    .await;
+    assert_eq!(next_json(&mut socket).await["type"], "error");
+
+    // 2. A technician, connected twice: token in the query and in the first message
+    let tech_id = Uuid::new_v4();
+    let email = format!("ws-{}@example.com", tech_id.simple());
+    sqlx::query(
+        r#"
+        INSERT INTO users (id, email, password_hash, name, role, role_id, organization_id)
+        SELECT $1, $2, a.password_hash, 'WS Test', 'technician',
+               (SELECT id FROM roles WHERE code = 'technician'), a.organization_id
+        FROM users a WHERE a.email = 'admin@example.com'
+        "#,
+    )
+    .bind(tech_id)
+    .bind(&email)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let tech_token = login(&app, &pool, &email).await;
+    let admin_token = login(&app, &pool, "admin@example.com").await;
+
+    let (mut tech_a, _) = tokio_tungstenite::connect_async(format!("{}?token={}", url, tech_token))
+        .await
+        .unwrap();
+    let hello = next_json(&mut tech_a).await;
+    assert_eq!(hello["type"], "authenticated", "{:?}", hello);
+    assert_eq!(hello["user_id"], tech_id.to_string());
+
+    let (mut tech_b, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+    send_json(&mut tech_b, json!({ "type": "auth", "token": tech_token })).await;
+    assert_eq!(next_json(&mut tech_b).await["type"], "authenticated");
+
+    send_json(&mut tech_b, json!({ "type": "ping" })).await;
+    assert_eq!(next_json(&mut tech_b).await["type"], "pong");
+
+    // 3. A work order; the admin follows it, the technician cannot follow its
+    // asset, which is outside their scope
+    let asset_id: Uuid = sqlx::query_scalar(
+        r#"
+        INSERT INTO assets (asset_code, name, category_id, status, organization_id)
+        SELECT $1, $1, '44444444-4444-4444-4444-444444444401', 'in_inventory', organization_id
+        FROM users WHERE email = 'admin@example.com'
+        RETURNING id
+        "#,
+    )
+    .bind(format!("WS-{}", suffix))
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let (status, json) = send(
+        &app,
+        "POST",
+        "/api/work-orders",
+        Some(&admin_token),
+        Some(json!({ "asset_id": asset_id, "wo_type": "corrective" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{:?}", json);
+    let wo_id = json["data"]["id"].as_str().unwrap().to_string();
+
+    let (mut admin, _) = tokio_tungstenite::connect_async(format!("{}?token={}", url, admin_token))
+        .await
+        .unwrap();
+    assert_eq!(next_json(&mut admin).await["type"], "authenticated");
+    let topic = format!("work_order:{}", wo_id);
+    send_json(&mut admin, json!({ "type": "subscribe", "topic": topic })).await;
+    let reply = next_json(&mut admin).await;
+    assert_eq!(reply, json!({ "type": "subscribed", "topic": topic }));
+
+    send_json(
+        &mut tech_a,
+        json!({ "type": "subscribe", "topic": format!("asset:{}", asset_id) }),
+    )
+    .await;
+    assert_eq!(next_json(&mut tech_a).await["message"], "Topic not found");
+    send_json(
+        &mut tech_a,
+        json!({ "type": "subscribe", "topic": "planet:1" }),
+    )
+    .await;
+    assert_eq!(next_json(&mut tech_a).await["message"], "Unknown topic");
+
+    // 4. Assigning reaches both technician connections and the topic follower
+    let (status, json) = send(
+        &app,
+        "POST",
+        &format!("/api/work-orders/{}/assign/{}", wo_id, tech_id),
+        Some(&admin_token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{:?}", json);
+
+    for socket in [&mut tech_a, &mut tech_b] {
+        let event = next_json(socket).await;
+        assert_eq!(event["event_type"], "WORK_ORDER_ASSIGNED", "{:?}", event);
+        assert_eq!(event["payload"]["id"], wo_id);
+        assert!(event.get("topic").is_none());
+    }
+    let event = next_json(&mut admin).await;
+    assert_eq!(event["event_type"], "WORK_ORDER_ASSIGNED");
+    assert_eq!(event["topic"], topic);
+
+    // Cleanup
+    sqlx::query("DELETE FROM maintenance_work_orders WHERE id = $1::uuid")
+        .bind(&wo_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM assets WHERE id = $1")
+        .bind(asset_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM users WHERE id = $1")
+        .bind(tech_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
@@ -1,10 +1,13 @@
 //! Integration Tests for Asset Management Workflow
 
+mod common;
+
 use axum::{
     body::Body,
     http::{header, Request, StatusCode},
     Router,
 };
+use common::login;
 use http_body_util::BodyExt;
 use serde_json::{json, Value};
 use sqlx::PgPool;
@@ -23,7 +26,8 @@ async fn setup_test_app() -> Router {
 
     let jwt_config = asset_management::shared::utils::jwt::JwtConfig {
         secret: jwt_secret,
-        expiry_hours: 24,
+        access_expiry_minutes: 15,
+        refresh_expiry_days: 14,
     };
 
     let state = asset_management::api::server::AppState::new(pool, jwt_config);
@@ -32,34 +36,11 @@ async fn setup_test_app() -> Router {
 
 /// Helper to login and get token
 async fn get_auth_token(app: Router) -> (String, Router) {
-    // We clone the router logic by creating a fresh one because oneshot consumes it
-    // But since we can't easily clone Router, we just reuse the setup function strategy
-    // In a real optimized test we might restructure, but for integration this is fine.
-
-    // First request to get token
-    let response = app
-        .clone()
-        .oneshot(
-            Request::builder()
-                .method("POST")
-                .uri("/api/auth/login")
-                .header("Content-Type", "application/json")
-                .body(Body::from(
-                    json!({
-                        "email": "admin@example.com",
-                        "password": "admin123"
-                    })
-                    .to_string(),
-                ))
-                .unwrap(),
-        )
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let pool = PgPool::connect(&database_url)
         .await
-        .unwrap();
-
-    let body = response.into_body().collect().await.unwrap().to_bytes();
-    let json: Value = serde_json::from_slice(&body).unwrap();
-    let token = json["token"].as_str().expect("Token not found").to_string();
-
+        .expect("Failed to connect to DB");
+    let token = login(&app, &pool, "admin@example.com").await;
     (token, app)
 }
 
@@ -7,6 +7,7 @@ import { WebSocketProvider } from './contexts/WebSocketContext';
 import { PageLoading } from './components/ui';
 
 const LoginPage = lazy(() => import('./pages/LoginPage'));
+const ResetPasswordPage = lazy(() => import('./pages/ResetPasswordPage'));
 const AdminDashboard = lazy(() => import('./pages/AdminDashboard'));
 
 const queryClient = new QueryClient({
@@ -31,6 +32,7 @@ function App() {
           <Suspense fallback={<PageLoading />}>
             <Routes>
               <Route path="/login" element={<LoginPage />} />
+              <Route path="/reset-password" element={<ResetPasswordPage />} />
 
               {/* Main Dashboard with Tab Navigation - handles all internal routes */}
               <Route
@@ -2,13 +2,35 @@ import axios from 'axios';
 import { showToast } from '../components/ui/Toast';
 import { useAuthStore } from '../store/useAuthStore';
 
+const baseURL = import.meta.env.VITE_API_URL || 'http://localhost:8080/api';
+
 export const api = axios.create({
-    baseURL: import.meta.env.VITE_API_URL || 'http://localhost:8080/api',
+    baseURL,
     headers: {
         'Content-Type': 'application/json',
     },
 });
 
+// One refresh at a time; concurrent 401s wait for the same rotation
+let refreshing: Promise<string | null> | null = null;
+
+const refreshAccessToken = (): Promise<string | null> => {
+    const refreshToken = useAuthStore.getState().refreshToken;
+    if (!refreshToken) return Promise.resolve(null);
+
+    refreshing ??= axios
+        .post(`${baseURL}/auth/refresh`, { refresh_token: refreshToken })
+        .then((res) => {
+            useAuthStore.getState().setTokens(res.data.token, res.data.refresh_token);
+            return res.data.token as string;
+        })
+        .catch(() => null)
+        .finally(() => {
+            refreshing = null;
+        });
+    return refreshing;
+};
+
 api.interceptors.request.use((config) => {
     const token = useAuthStore.getState().token;
     if (token) {
@@ -19,12 +41,25 @@ api.interceptors.request.use((config) => {
 
 api.interceptors.response.use(
     (response) => response,
-    (error) => {
+    async (error) => {
+        const original = error.config;
+        const isAuthCall = original?.url?.startsWith('/auth/');
+
+        // Access tokens are short-lived: rotate the refresh token and retry once
+        if (error.response?.status === 401 && original && !original._retried && !isAuthCall) {
+            original._retried = true;
+            const token = await refreshAccessToken();
+            if (token) {
+                original.headers.Authorization = `Bearer ${token}`;
+                return api(original);
+            }
+        }
+
         const message = error.response?.data?.message || 'Something went wrong';
         console.error(message);
 
-        if (error.response?.status === 401) {
-            useAuthStore.getState().logout();
+        if (error.response?.status === 401 && !isAuthCall) {
+            useAuthStore.getState().clearSession();
             showToast('Please login again', 'error', 'Session Expired');
         }
 
@@ -267,7 +267,8 @@ export function ApprovalCenter() {
     const getStatusBadge = (status: string): 'info' | 'success' | 'warning' | 'danger' | 'default' => {
         switch (status) {
             case 'APPROVED_L1': return 'info';
-            case 'APPROVED_L2': return 'success';
+            case 'APPROVED_L2':
+            case 'APPROVED': return 'success';
             case 'REJECTED':
             case 'rejected': return 'danger';
             case 'PENDING':
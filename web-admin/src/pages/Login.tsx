@@ -18,8 +18,8 @@ export function Login() {
         setLoading(true);
         try {
             const response = await api.post('/auth/login', { email, password });
-            const { token, user } = response.data;
-            login(token, user);
+            const { token, refresh_token, user } = response.data;
+            login(token, user, refresh_token);
             success(`Logged in as ${user.name}`, 'Welcome back!'); // Note: User might not have name if typed weakly, but assuming it does.
             navigate('/');
         } catch (err: any) {
@@ -1,7 +1,7 @@
 // Login Page with Dark Theme
 import { useState } from 'react';
-import { useNavigate } from 'react-router-dom';
-import { LogIn, AlertCircle, Eye, EyeOff, Package } from 'lucide-react';
+import { Link, useNavigate } from 'react-router-dom';
+import { LogIn, AlertCircle, Eye, EyeOff, Package, ShieldCheck, KeyRound } from 'lucide-react';
 import { api } from '../api/client';
 import { useAuthStore } from '../store/useAuthStore';
 
@@ -11,6 +11,15 @@ export function LoginPage() {
     const [error, setError] = useState('');
     const [loading, setLoading] = useState(false);
     const [showPassword, setShowPassword] = useState(false);
+    // Second login step when the account uses (or must set up) 2FA
+    const [challengeToken, setChallengeToken] = useState('');
+    const [setup, setSetup] = useState<{ secret: string; otpauth_uri: string } | null>(null);
+    const [code, setCode] = useState('');
+    const [recoveryCodes, setRecoveryCodes] = useState<string[]>([]);
+    const [pendingLogin, setPendingLogin] = useState<{ token: string; refresh_token: string; user: any } | null>(null);
+    // Set when an admin requires a new password before logging in
+    const [passwordChangeToken, setPasswordChangeToken] = useState('');
+    const [newPassword, setNewPassword] = useState('');
     const navigate = useNavigate();
     const login = useAuthStore((state) => state.login);
 
@@ -33,9 +42,7 @@ export function LoginPage() {
 
         try {
             const response = await api.post('/auth/login', { email, password });
-            const { token, user } = response.data;
-            login(token, user);
-            navigate('/');
+            await continueLogin(response.data);
         } catch (err: any) {
             setError(err.response?.data?.error || 'Login gagal. Periksa kembali email dan password.');
         } finally {
@@ -43,6 +50,78 @@ export function LoginPage() {
         }
     };
 
+    // A login response either carries tokens or asks for another step
+    const continueLogin = async (data: any) => {
+        if (data.password_change_required) {
+            setPasswordChangeToken(data.challenge_token);
+            return;
+        }
+        if (data.two_factor_required) {
+            const challenge = data.challenge_token;
+            setChallengeToken(challenge);
+            if (data.setup_required) {
+                const setupResponse = await api.post('/auth/2fa/setup', { challenge_token: challenge });
+                setSetup(setupResponse.data.data);
+            }
+            return;
+        }
+        const { token, refresh_token, user } = data;
+        login(token, user, refresh_token);
+        navigate('/');
+    };
+
+    const handlePasswordChange = async (e: React.FormEvent) => {
+        e.preventDefault();
+        if (loading) return;
+
+        setError('');
+        setLoading(true);
+
+        try {
+            const response = await api.post('/auth/password/change', {
+                challenge_token: passwordChangeToken,
+                new_password: newPassword,
+            });
+            setPasswordChangeToken('');
+            await continueLogin(response.data);
+        } catch (err: any) {
+            setError(err.response?.data?.error || 'Password baru tidak memenuhi kebijakan.');
+        } finally {
+            setLoading(false);
+        }
+    };
+
+    const handleVerify = async (e: React.FormEvent) => {
+        e.preventDefault();
+        if (loading) return;
+
+        setError('');
+        setLoading(true);
+
+        try {
+            const response = await api.post('/auth/2fa/verify', { challenge_token: challengeToken, code });
+            const { token, refresh_token, user, recovery_codes } = response.data;
+            if (recovery_codes?.length) {
+                // Show the recovery codes once before entering the dashboard
+                setRecoveryCodes(recovery_codes);
+                setPendingLogin({ token, refresh_token, user });
+                return;
+            }
+            login(token, user, refresh_token);
+            navigate('/');
+        } catch (err: any) {
+            setError(err.response?.data?.error || 'Kode verifikasi tidak valid.');
+        } finally {
+            setLoading(false);
+        }
+    };
+
+    const finishLogin = () => {
+        if (!pendingLogin) return;
+        login(pendingLogin.token, pendingLogin.user, pendingLogin.refresh_token);
+        navigate('/');
+    };
+
     return (
         <div className="min-h-screen bg-slate-950 flex items-center justify-center p-4 relative overflow-hidden font-sans">
             {/* Background Effects */}
@@ -83,6 +162,85 @@ export function LoginPage() {
                         </div>
                     )}
 
+                    {recoveryCodes.length > 0 ? (
+                        <div className="space-y-5">
+                            <p className="text-sm text-slate-300">
+                                Simpan kode pemulihan berikut di tempat aman. Setiap kode hanya dapat dipakai sekali jika perangkat autentikator tidak tersedia.
+                            </p>
+                            <div className="grid grid-cols-2 gap-2 font-mono text-sm text-cyan-300">
+                                {recoveryCodes.map((c) => (
+                                    <span key={c} className="px-3 py-2 bg-slate-950/50 border border-slate-700 rounded-lg text-center">{c}</span>
+                                ))}
+                            </div>
+                            <button
+                                type="button"
+                                onClick={finishLogin}
+                                className="w-full py-3.5 bg-gradient-to-r from-cyan-600 to-blue-600 hover:from-cyan-500 hover:to-blue-500 text-white font-semibold rounded-xl transition-all"
+                            >
+                                Saya sudah menyimpan kode ini
+                            </button>
+                        </div>
+                    ) : passwordChangeToken ? (
+                        <form onSubmit={handlePasswordChange} className="space-y-5">
+                            <p className="text-sm text-slate-300">
+                                Administrator meminta Anda mengganti password sebelum melanjutkan.
+                            </p>
+                            <div className="space-y-1.5">
+                                <label className="block text-sm font-medium text-slate-300">
+                                    Password baru
+                                </label>
+                                <input
+                                    type="password"
+                                    autoComplete="new-password"
+                                    value={newPassword}
+                                    onChange={(e) => setNewPassword(e.target.value)}
+                                    className="w-full px-4 py-3 bg-slate-950/50 border border-slate-700 rounded-xl focus:ring-2 focus:ring-cyan-500/50 focus:border-cyan-500 outline-none transition text-white placeholder-slate-600 focus:bg-slate-950"
+                                    required
+                                />
+                            </div>
+                            <button
+                                type="submit"
+                                disabled={loading}
+                                className="w-full py-3.5 bg-gradient-to-r from-cyan-600 to-blue-600 hover:from-cyan-500 hover:to-blue-500 text-white font-semibold rounded-xl flex items-center justify-center gap-2 transition-all disabled:opacity-50 mt-2 shadow-lg shadow-cyan-900/20 active:scale-[0.98] ring-1 ring-white/10"
+                            >
+                                <KeyRound size={18} />
+                                Simpan password
+                            </button>
+                        </form>
+                    ) : challengeToken ? (
+                        <form onSubmit={handleVerify} className="space-y-5">
+                            {setup && (
+                                <div className="space-y-2 text-sm text-slate-300">
+                                    <p>Akun Anda wajib memakai verifikasi dua langkah. Tambahkan kunci berikut ke aplikasi autentikator:</p>
+                                    <code className="block px-3 py-2 bg-slate-950/50 border border-slate-700 rounded-lg text-cyan-300 break-all">{setup.secret}</code>
+                                    <a href={setup.otpauth_uri} className="text-cyan-400 hover:underline text-xs">Buka di aplikasi autentikator</a>
+                                </div>
+                            )}
+                            <div className="space-y-1.5">
+                                <label className="block text-sm font-medium text-slate-300">
+                                    {setup ? 'Kode verifikasi' : 'Kode verifikasi atau kode pemulihan'}
+                                </label>
+                                <input
+                                    type="text"
+                                    inputMode={setup ? 'numeric' : 'text'}
+                                    autoComplete="one-time-code"
+                                    value={code}
+                                    onChange={(e) => setCode(e.target.value)}
+                                    className="w-full px-4 py-3 bg-slate-950/50 border border-slate-700 rounded-xl focus:ring-2 focus:ring-cyan-500/50 focus:border-cyan-500 outline-none transition text-white placeholder-slate-600 focus:bg-slate-950 tracking-widest"
+                                    placeholder="123456"
+                                    required
+                                />
+                            </div>
+                            <button
+                                type="submit"
+                                disabled={loading}
+                                className="w-full py-3.5 bg-gradient-to-r from-cyan-600 to-blue-600 hover:from-cyan-500 hover:to-blue-500 text-white font-semibold rounded-xl flex items-center justify-center gap-2 transition-all disabled:opacity-50 mt-2 shadow-lg shadow-cyan-900/20 active:scale-[0.98] ring-1 ring-white/10"
+                            >
+                                <ShieldCheck size={18} />
+                                Verifikasi
+                            </button>
+                        </form>
+                    ) : (
                     <form onSubmit={handleSubmit} className="space-y-5">
                         <div className="space-y-1.5">
                             <label className="block text-sm font-medium text-slate-300">
@@ -135,7 +293,13 @@ export function LoginPage() {
                                 </>
                             )}
                         </button>
+                        <p className="text-center text-sm">
+                            <Link to="/reset-password" className="text-cyan-400 hover:underline">
+                                Lupa password?
+                            </Link>
+                        </p>
                     </form>
+                    )}
 
                     <p className="text-center text-xs text-slate-500 mt-8 font-light">
                         Protected by robust authentication
@@ -0,0 +1,108 @@
+// Forgot / Reset Password Page
+import { useState } from 'react';
+import { Link, useSearchParams } from 'react-router-dom';
+import { AlertCircle, KeyRound, Mail, Package } from 'lucide-react';
+import { api } from '../api/client';
+
+const inputClass = 'w-full px-4 py-3 bg-slate-950/50 border border-slate-700 rounded-xl focus:ring-2 focus:ring-cyan-500/50 focus:border-cyan-500 outline-none transition text-white placeholder-slate-600 focus:bg-slate-950';
+const buttonClass = 'w-full py-3.5 bg-gradient-to-r from-cyan-600 to-blue-600 hover:from-cyan-500 hover:to-blue-500 text-white font-semibold rounded-xl flex items-center justify-center gap-2 transition-all disabled:opacity-50 mt-2 shadow-lg shadow-cyan-900/20 active:scale-[0.98] ring-1 ring-white/10';
+
+export function ResetPasswordPage() {
+    // The emailed link carries the token; without it the page asks for the email
+    const [searchParams] = useSearchParams();
+    const token = searchParams.get('token') || '';
+    const [email, setEmail] = useState('');
+    const [newPassword, setNewPassword] = useState('');
+    const [error, setError] = useState('');
+    const [message, setMessage] = useState('');
+    const [loading, setLoading] = useState(false);
+
+    const submit = async (e: React.FormEvent) => {
+        e.preventDefault();
+        if (loading) return;
+
+        setError('');
+        setLoading(true);
+
+        try {
+            if (token) {
+                await api.post('/auth/password/reset', { token, new_password: newPassword });
+                setMessage('Password berhasil diganti. Silakan masuk dengan password baru.');
+            } else {
+                await api.post('/auth/password/forgot', { email });
+                setMessage('Jika email terdaftar, tautan untuk mengganti password telah dikirim.');
+            }
+        } catch (err: any) {
+            setError(err.response?.data?.error || 'Permintaan gagal. Coba lagi.');
+        } finally {
+            setLoading(false);
+        }
+    };
+
+    return (
+        <div className="min-h-screen bg-slate-950 flex items-center justify-center p-4 font-sans">
+            <div className="w-full max-w-md">
+                <div className="text-center mb-8">
+                    <div className="inline-flex items-center justify-center w-20 h-20 bg-slate-900 border border-slate-700/50 rounded-2xl shadow-xl">
+                        <Package size={36} className="text-cyan-500" />
+                    </div>
+                    <h1 className="text-3xl font-bold text-white tracking-tight mt-4">Asset Manager</h1>
+                </div>
+
+                <div className="bg-slate-900/60 backdrop-blur-xl rounded-2xl shadow-2xl p-8 border border-white/5 ring-1 ring-white/10">
+                    <h2 className="text-xl font-semibold text-white mb-6 text-center">
+                        {token ? 'Buat Password Baru' : 'Lupa Password'}
+                    </h2>
+
+                    {error && (
+                        <div className="mb-6 p-3 bg-red-500/10 border border-red-500/20 rounded-lg flex items-center gap-2 text-red-400 text-sm">
+                            <AlertCircle size={16} className="flex-shrink-0" />
+                            {error}
+                        </div>
+                    )}
+
+                    {message ? (
+                        <p className="text-sm text-slate-300 text-center">{message}</p>
+                    ) : (
+                        <form onSubmit={submit} className="space-y-5">
+                            <div className="space-y-1.5">
+                                <label className="block text-sm font-medium text-slate-300">
+                                    {token ? 'Password baru' : 'Email'}
+                                </label>
+                                {token ? (
+                                    <input
+                                        type="password"
+                                        autoComplete="new-password"
+                                        value={newPassword}
+                                        onChange={(e) => setNewPassword(e.target.value)}
+                                        className={inputClass}
+                                        required
+                                    />
+                                ) : (
+                                    <input
+                                        type="email"
+                                        value={email}
+                                        onChange={(e) => setEmail(e.target.value)}
+                                        className={inputClass}
+                                        placeholder="nama@perusahaan.com"
+                                        required
+                                    />
+                                )}
+                            </div>
+                            <button type="submit" disabled={loading} className={buttonClass}>
+                                {token ? <KeyRound size={18} /> : <Mail size={18} />}
+                                {token ? 'Simpan password' : 'Kirim tautan'}
+                            </button>
+                        </form>
+                    )}
+
+                    <p className="text-center text-sm mt-6">
+                        <Link to="/login" className="text-cyan-400 hover:underline">Kembali ke halaman masuk</Link>
+                    </p>
+                </div>
+            </div>
+        </div>
+    );
+}
+
+export default ResetPasswordPage;
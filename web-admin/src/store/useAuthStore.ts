@@ -18,9 +18,12 @@ export interface User {
 
 interface AuthState {
     token: string | null;
+    refreshToken: string | null;
     user: User | null;
-    login: (token: string, user: Omit<User, 'permissions' | 'role_level'>) => void;
+    login: (token: string, user: Omit<User, 'permissions' | 'role_level'>, refreshToken?: string) => void;
+    setTokens: (token: string, refreshToken: string) => void;
     logout: () => void;
+    clearSession: () => void;
     refreshUser: () => Promise<void>;
     isAuthenticated: () => boolean;
     isAdmin: () => boolean;
@@ -38,8 +41,9 @@ export const useAuthStore = create<AuthState>()(
     persist(
         (set, get) => ({
             token: null,
+            refreshToken: null,
             user: null,
-            login: (token, baseUser) => {
+            login: (token, baseUser, refreshToken) => {
                 try {
                     const decoded = jwtDecode<JwtClaims>(token);
                     const permissions = decoded.permissions || [];
@@ -47,6 +51,7 @@ export const useAuthStore = create<AuthState>()(
 
                     set({
                         token,
+                        refreshToken: refreshToken ?? get().refreshToken,
                         user: {
                             ...baseUser,
                             permissions,
@@ -59,6 +64,7 @@ export const useAuthStore = create<AuthState>()(
                     // Fallback to basic user if decode fails (shouldn't happen with valid token)
                     set({
                         token,
+                        refreshToken: refreshToken ?? get().refreshToken,
                         user: {
                             ...baseUser,
                             permissions: [],
@@ -67,7 +73,15 @@ export const useAuthStore = create<AuthState>()(
                     });
                 }
             },
-            logout: () => set({ token: null, user: null }),
+            setTokens: (token, refreshToken) => set({ token, refreshToken }),
+            logout: () => {
+                // Revoke the session server-side; local state is cleared regardless
+                if (get().token) {
+                    api.post('/auth/logout').catch(() => undefined);
+                }
+                get().clearSession();
+            },
+            clearSession: () => set({ token: null, refreshToken: null, user: null }),
             refreshUser: async () => {
                 const token = get().token;
                 if (!token) return;